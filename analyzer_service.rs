@@ -1,78 +1,543 @@
-use tokio::time::{self, Duration, Instant};
-use std::error::Error;
-use std::fmt;
-
-/// 텍스트 분석 결과를 담는 구조체
-#[derive(Debug, Clone)]
-pub struct AnalysisResult {
-    pub word_count: usize,
-    pub readability_score: f64,
-    pub detected_sentiment: String,
-    pub processing_time_ms: u128,
-}
-
-/// 사용자 정의 에러 타입
-#[derive(Debug)]
-pub struct AnalysisError(pub String);
-
-impl fmt::Display for AnalysisError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Analysis failed: {}", self.0)
-    }
-}
-
-impl Error for AnalysisError {}
-
-/// 텍스트 분석 서비스 구조체
-pub struct AnalyzerService;
-
-impl AnalyzerService {
-    pub fn new() -> Self {
-        println!("[Analyzer] AnalyzerService가 초기화되었습니다.");
-        Self {}
-    }
-
-    /// 텍스트 분석을 비동기적으로 수행합니다.
-    pub async fn analyze_text(&self, source_code: &str) -> Result<AnalysisResult, AnalysisError> {
-        let start_time = Instant::now();
-        time::sleep(Duration::from_millis(30)).await;
-
-        let word_count = source_code.split_whitespace().count();
-        if word_count == 0 {
-            return Err(AnalysisError("분석할 텍스트가 비어 있거나 공백만 포함합니다.".into()));
-        }
-
-        let sentiment = Self::detect_sentiment(source_code);
-        let readability_score = Self::calculate_readability(word_count);
-        let processing_time_ms = start_time.elapsed().as_millis();
-
-        Ok(AnalysisResult {
-            word_count,
-            readability_score,
-            detected_sentiment: sentiment,
-            processing_time_ms,
-        })
-    }
-
-    /// 감정 분석 로직 (키워드 기반)
-    fn detect_sentiment(text: &str) -> String {
-        let positive_keywords = ["hello", "success", "great", "awesome", "good"];
-        let negative_keywords = ["error", "fail", "panic", "bad", "crash"];
-
-        let lower = text.to_lowercase();
-
-        if negative_keywords.iter().any(|kw| lower.contains(kw)) {
-            "Negative".to_string()
-        } else if positive_keywords.iter().any(|kw| lower.contains(kw)) {
-            "Positive".to_string()
-        } else {
-            "Neutral".to_string()
-        }
-    }
-
-    /// 가독성 점수 계산 (단순 모델)
-    fn calculate_readability(word_count: usize) -> f64 {
-        let score = word_count as f64 / 10.0;
-        score.min(1.0)
-    }
-}
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{self, Duration, Instant};
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::data_structures::{Diagnostic, Expression, Span, Statement};
+use crate::error_codes::{ANALYZER_NOTE, LONG_FUNCTION, POOR_NAMING};
+use crate::lexer_service::LexerService;
+use crate::messages;
+use crate::parser_service::ParserService;
+
+/// 이 줄 수를 넘는 함수는 E0100 경고를 받습니다.
+pub const LONG_FUNCTION_LINES: usize = 50;
+
+/// 한 글자여도 관례상 괜찮은 이름들 (반복 변수, 좌표 등)
+const CONVENTIONAL_SHORT_NAMES: &[&str] = &["i", "j", "k", "n", "x", "y", "z", "_"];
+/// 의미를 담지 못하는 이름들
+const VAGUE_NAMES: &[&str] = &["tmp", "temp", "foo", "bar", "baz", "data", "val", "thing", "stuff"];
+
+/// 텍스트 분석 결과를 담는 구조체
+#[derive(Debug, Clone)]
+pub struct AnalysisResult {
+    pub word_count: usize,
+    pub detected_sentiment: String,
+    /// 함수(및 매크로, 최상위 코드)별 지표
+    pub functions: Vec<FunctionReport>,
+    pub processing_time_ms: u128,
+    /// 결과를 만든 백엔드 이름
+    pub backend: String,
+    /// 선택한 백엔드를 쓰지 못해 기본 백엔드로 대신한 경우 그 이유
+    pub degraded: Option<String>,
+}
+
+impl AnalysisResult {
+    /// 모든 함수 보고서의 경고를 모읍니다.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.functions.iter().flat_map(|f| f.warnings.iter().cloned()).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FunctionKind {
+    /// 파일 최상위의 문장들
+    TopLevel,
+    Function,
+    Macro,
+}
+
+/// 함수 하나에 대한 가독성 지표입니다.
+#[derive(Debug, Clone)]
+pub struct FunctionReport {
+    pub name: String,
+    pub kind: FunctionKind,
+    pub span: Span,
+    pub word_count: usize,
+    pub line_count: usize,
+    /// 주석 줄 / 비어 있지 않은 줄 (0.0 ~ 1.0)
+    pub comment_density: f64,
+    /// 좋은 이름의 비율 (0.0 ~ 1.0). 이름이 하나도 없으면 1.0 입니다.
+    pub naming_score: f64,
+    /// 점수를 깎은 이름들 (선언 순서, 중복 없음)
+    pub poor_names: Vec<String>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// 사용자 정의 에러 타입
+#[derive(Debug)]
+pub struct AnalysisError(pub String);
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Analysis failed: {}", self.0)
+    }
+}
+
+impl Error for AnalysisError {}
+
+pub type AnalysisFuture<'a> = Pin<Box<dyn Future<Output = Result<AnalysisResult, AnalysisError>> + Send + 'a>>;
+
+/// 분석 백엔드. 기본 휴리스틱 외에 외부 서비스(HTTP ML 서버, 로컬 모델 등)를 등록할 수 있습니다.
+pub trait Analyzer: Send + Sync {
+    /// `--analyzer <name>` 으로 선택할 때 쓰는 이름
+    fn name(&self) -> &str;
+    fn analyze<'a>(&'a self, source: &'a str) -> AnalysisFuture<'a>;
+}
+
+/// 기본 백엔드의 이름
+pub const DEFAULT_ANALYZER: &str = "heuristic";
+/// 백엔드 하나에 허용하는 기본 분석 시간
+pub const DEFAULT_ANALYZER_TIMEOUT_MS: u64 = 2000;
+
+/// 텍스트 분석 서비스: 등록된 백엔드 중 하나를 골라 실행하고,
+/// 백엔드가 없거나 실패하거나 시간을 넘기면 기본 휴리스틱 백엔드로 대신합니다.
+pub struct AnalyzerService {
+    backends: Vec<Box<dyn Analyzer>>,
+}
+
+impl AnalyzerService {
+    pub fn new() -> Self {
+        println!("[Analyzer] AnalyzerService가 초기화되었습니다.");
+        Self { backends: vec![Box::new(HeuristicAnalyzer)] }
+    }
+
+    /// 백엔드를 등록합니다. 같은 이름의 백엔드가 있으면 교체합니다.
+    pub fn register(&mut self, analyzer: Box<dyn Analyzer>) {
+        self.backends.retain(|b| b.name() != analyzer.name());
+        self.backends.push(analyzer);
+    }
+
+    pub fn backend_names(&self) -> Vec<&str> {
+        self.backends.iter().map(|b| b.name()).collect()
+    }
+
+    /// 기본 백엔드로 분석합니다.
+    pub async fn analyze_text(&self, source_code: &str) -> Result<AnalysisResult, AnalysisError> {
+        HeuristicAnalyzer.analyze(source_code).await
+    }
+
+    /// `selection` 은 등록된 백엔드 이름 또는 `http://host:port/path` 주소입니다.
+    pub async fn analyze_with(
+        &self,
+        source_code: &str,
+        selection: &str,
+        timeout_ms: u64,
+    ) -> Result<AnalysisResult, AnalysisError> {
+        if selection == DEFAULT_ANALYZER {
+            return self.analyze_text(source_code).await;
+        }
+
+        let remote;
+        let backend: &dyn Analyzer = if selection.starts_with("http://") {
+            remote = HttpAnalyzer::new(selection);
+            &remote
+        } else {
+            match self.backends.iter().find(|b| b.name() == selection) {
+                Some(backend) => backend.as_ref(),
+                None => {
+                    let reason = format!(
+                        "등록되지 않은 분석기 '{}' (사용 가능: {})",
+                        selection,
+                        self.backend_names().join(", ")
+                    );
+                    return self.degrade(source_code, reason).await;
+                }
+            }
+        };
+
+        match time::timeout(Duration::from_millis(timeout_ms), backend.analyze(source_code)).await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(e)) => self.degrade(source_code, format!("'{}': {}", backend.name(), e)).await,
+            Err(_) => {
+                let reason = format!("'{}' 가 {}ms 안에 응답하지 않았습니다", backend.name(), timeout_ms);
+                self.degrade(source_code, reason).await
+            }
+        }
+    }
+
+    async fn degrade(&self, source_code: &str, reason: String) -> Result<AnalysisResult, AnalysisError> {
+        println!("[Analyzer] 기본 분석기로 대신합니다: {}", reason);
+        let mut result = self.analyze_text(source_code).await?;
+        result.degraded = Some(reason);
+        Ok(result)
+    }
+}
+
+/// 키워드와 AST 지표를 쓰는 기본 백엔드입니다.
+pub struct HeuristicAnalyzer;
+
+impl Analyzer for HeuristicAnalyzer {
+    fn name(&self) -> &str {
+        DEFAULT_ANALYZER
+    }
+
+    fn analyze<'a>(&'a self, source: &'a str) -> AnalysisFuture<'a> {
+        Box::pin(Self::analyze_source(source))
+    }
+}
+
+impl HeuristicAnalyzer {
+    async fn analyze_source(source_code: &str) -> Result<AnalysisResult, AnalysisError> {
+        let start_time = Instant::now();
+        time::sleep(Duration::from_millis(30)).await;
+
+        let word_count = source_code.split_whitespace().count();
+        if word_count == 0 {
+            return Err(AnalysisError("분석할 텍스트가 비어 있거나 공백만 포함합니다.".into()));
+        }
+
+        let sentiment = Self::detect_sentiment(source_code);
+        let functions = Self::analyze_functions(source_code);
+        let processing_time_ms = start_time.elapsed().as_millis();
+
+        Ok(AnalysisResult {
+            word_count,
+            detected_sentiment: sentiment,
+            functions,
+            processing_time_ms,
+            backend: DEFAULT_ANALYZER.into(),
+            degraded: None,
+        })
+    }
+
+    /// 소스를 파싱해 최상위 코드, 함수, 매크로마다 보고서를 만듭니다. (소스 순서)
+    pub fn analyze_functions(source: &str) -> Vec<FunctionReport> {
+        let mut parser = ParserService::new(LexerService::new(source));
+        let program = parser.parse_program();
+
+        let mut reports = vec![];
+        let whole = Span { start: 0, end: source.chars().count() };
+        let mut names = vec![];
+        for stmt in &program.statements {
+            collect_statement(stmt, &mut names);
+        }
+        reports.push(build_report(messages::tr("trace.top_level", &[]), FunctionKind::TopLevel, whole, names, source));
+
+        for stmt in &program.statements {
+            find_functions(stmt, source, &mut reports);
+        }
+        reports
+    }
+
+    /// 감정 분석 로직 (키워드 기반)
+    fn detect_sentiment(text: &str) -> String {
+        let positive_keywords = ["hello", "success", "great", "awesome", "good"];
+        let negative_keywords = ["error", "fail", "panic", "bad", "crash"];
+
+        let lower = text.to_lowercase();
+
+        if negative_keywords.iter().any(|kw| lower.contains(kw)) {
+            "Negative".to_string()
+        } else if positive_keywords.iter().any(|kw| lower.contains(kw)) {
+            "Positive".to_string()
+        } else {
+            "Neutral".to_string()
+        }
+    }
+}
+
+/// 외부 HTTP 분석 서비스 백엔드입니다.
+///
+/// 소스를 `text/plain` 본문으로 POST 하고, 응답 본문의 줄을 다음과 같이 해석합니다.
+///
+///   sentiment=Positive
+///   note=<start>:<end>:<message>      (바이트 오프셋 span 에 붙는 정보 진단)
+///
+/// 함수별 지표는 기본 백엔드로 계산하고, note 는 span 을 포함하는 가장 안쪽 함수 보고서에 붙입니다.
+pub struct HttpAnalyzer {
+    url: String,
+}
+
+impl HttpAnalyzer {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    async fn request(&self, source: &str) -> Result<AnalysisResult, AnalysisError> {
+        let start_time = Instant::now();
+        let address = self
+            .url
+            .strip_prefix("http://")
+            .ok_or_else(|| AnalysisError(format!("http:// 주소가 아닙니다: {}", self.url)))?;
+        let (host, path) = address.split_once('/').map_or((address, "/".to_string()), |(h, p)| (h, format!("/{}", p)));
+
+        let mut stream = TcpStream::connect(host).await.map_err(|e| AnalysisError(format!("{} 연결 실패: {}", host, e)))?;
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path,
+            host,
+            source.len(),
+            source
+        );
+        stream.write_all(request.as_bytes()).await.map_err(|e| AnalysisError(e.to_string()))?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.map_err(|e| AnalysisError(e.to_string()))?;
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((response.as_str(), ""));
+        let status = head.split_whitespace().nth(1).unwrap_or("");
+        if status != "200" {
+            return Err(AnalysisError(format!("HTTP 상태 {}", status)));
+        }
+
+        let mut functions = HeuristicAnalyzer::analyze_functions(source);
+        let mut sentiment = "Neutral".to_string();
+        for line in body.lines() {
+            if let Some(value) = line.strip_prefix("sentiment=") {
+                sentiment = value.trim().to_string();
+            } else if let Some(note) = line.strip_prefix("note=") {
+                let mut parts = note.splitn(3, ':');
+                let (Some(Ok(start)), Some(Ok(end)), Some(message)) = (
+                    parts.next().map(str::parse::<usize>),
+                    parts.next().map(str::parse::<usize>),
+                    parts.next(),
+                ) else {
+                    continue;
+                };
+                let span = Span { start, end };
+                let owner = functions
+                    .iter_mut()
+                    .filter(|f| f.span.start <= start && end <= f.span.end)
+                    .min_by_key(|f| f.span.end - f.span.start);
+                if let Some(owner) = owner {
+                    owner.warnings.push(ANALYZER_NOTE.diagnostic(span, "", &[&self.url, &message]));
+                }
+            }
+        }
+
+        Ok(AnalysisResult {
+            word_count: source.split_whitespace().count(),
+            detected_sentiment: sentiment,
+            functions,
+            processing_time_ms: start_time.elapsed().as_millis(),
+            backend: self.url.clone(),
+            degraded: None,
+        })
+    }
+}
+
+impl Analyzer for HttpAnalyzer {
+    fn name(&self) -> &str {
+        &self.url
+    }
+
+    fn analyze<'a>(&'a self, source: &'a str) -> AnalysisFuture<'a> {
+        Box::pin(self.request(source))
+    }
+}
+
+// ─── 함수 찾기 ─────────────────────────────
+
+/// `let name = fn(...) {...}` 는 바인딩 이름을, 그 밖의 함수 값은 익명 이름을 씁니다.
+fn find_functions(stmt: &Statement, source: &str, reports: &mut Vec<FunctionReport>) {
+    match stmt {
+        Statement::LetStatement { name, value, .. } => find_in_expression(value, Some(name.as_str()), source, reports),
+        Statement::ExpressionStatement(expr)
+        | Statement::DiscardStatement(_, expr)
+        | Statement::ReturnStatement(_, expr)
+        | Statement::YieldStatement(_, expr) => find_in_expression(expr, None, source, reports),
+        Statement::DeferStatement(_, body) => find_functions(body, source, reports),
+        Statement::BlockStatement { statements, .. } => {
+            for s in statements {
+                find_functions(s, source, reports);
+            }
+        }
+        Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+            find_in_expression(condition, None, source, reports);
+            find_functions(then_branch, source, reports);
+            if let Some(else_branch) = else_branch {
+                find_functions(else_branch, source, reports);
+            }
+        }
+        Statement::WhileStatement { condition, body, .. } | Statement::ForInStatement { iterable: condition, body, .. } => {
+            find_in_expression(condition, None, source, reports);
+            find_functions(body, source, reports);
+        }
+        Statement::ForStatement { initializer, condition, increment, body, .. } => {
+            if let Some(init) = initializer {
+                find_functions(init, source, reports);
+            }
+            for expr in condition.iter().chain(increment.iter()) {
+                find_in_expression(expr, None, source, reports);
+            }
+            find_functions(body, source, reports);
+        }
+        Statement::MacroDefinition { name, parameters, body, span } => {
+            let mut names: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+            collect_statement(body, &mut names);
+            reports.push(build_report(name.clone(), FunctionKind::Macro, *span, names, source));
+            find_functions(body, source, reports);
+        }
+        Statement::Attributed { statement, .. } => find_functions(statement, source, reports),
+        Statement::TypeAlias { .. } | Statement::EnumDeclaration { .. } => {}
+    }
+}
+
+fn find_in_expression(expr: &Expression, binding: Option<&str>, source: &str, reports: &mut Vec<FunctionReport>) {
+    match expr {
+        Expression::Function(span, parameters, body) => {
+            let name = binding.map_or_else(|| messages::tr("trace.anonymous", &[]), str::to_string);
+            let mut names: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+            collect_statement(body, &mut names);
+            reports.push(build_report(name, FunctionKind::Function, *span, names, source));
+            find_functions(body, source, reports);
+        }
+        other => {
+            for child in child_expressions(other) {
+                find_in_expression(child, None, source, reports);
+            }
+        }
+    }
+}
+
+fn child_expressions(expr: &Expression) -> Vec<&Expression> {
+    match expr {
+        Expression::Literal(..) | Expression::Identifier(..) | Expression::Function(..) => vec![],
+        Expression::PrefixOperation(_, _, inner)
+        | Expression::Grouped(_, inner)
+        | Expression::Reflect(_, inner)
+        | Expression::Eval(_, inner)
+        | Expression::TypeOf(_, inner)
+        | Expression::Assign(_, _, _, inner) => vec![inner],
+        Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => vec![left, right],
+        Expression::Ternary(_, cond, then, otherwise) => vec![cond, then, otherwise],
+        Expression::Call(_, callee, args) => std::iter::once(callee.as_ref()).chain(args.iter().map(|a| a.as_ref())).collect(),
+        Expression::MacroCall(_, _, args) | Expression::ArrayLiteral(_, args) => args.iter().map(|a| a.as_ref()).collect(),
+        Expression::MapLiteral(_, entries) => entries.iter().flat_map(|(key, value)| [key.as_ref(), value.as_ref()]).collect(),
+        Expression::Index(_, target, index) => vec![target, index],
+        Expression::Member(_, target, _) => vec![target],
+        Expression::MethodCall(_, target, _, args) => std::iter::once(target.as_ref()).chain(args.iter().map(|a| a.as_ref())).collect(),
+        Expression::Slice(_, target, start, end) => {
+            std::iter::once(target.as_ref()).chain(start.as_deref()).chain(end.as_deref()).collect()
+        }
+        Expression::Match(_, subject, arms) => std::iter::once(subject.as_ref())
+            .chain(arms.iter().flat_map(|arm| arm.guard.as_deref().into_iter().chain([arm.body.as_ref()])))
+            .collect(),
+    }
+}
+
+// ─── 이름 모으기 ─────────────────────────────
+
+/// 함수 본문에서 선언되거나 참조된 이름을 모읍니다. 중첩 함수의 본문은 그 함수의 몫이므로 제외합니다.
+fn collect_statement(stmt: &Statement, names: &mut Vec<String>) {
+    match stmt {
+        Statement::LetStatement { name, value, .. } => {
+            names.push(name.to_string());
+            collect_expression(value, names);
+        }
+        Statement::ExpressionStatement(expr)
+        | Statement::DiscardStatement(_, expr)
+        | Statement::ReturnStatement(_, expr)
+        | Statement::YieldStatement(_, expr) => collect_expression(expr, names),
+        Statement::DeferStatement(_, body) => collect_statement(body, names),
+        Statement::BlockStatement { statements, .. } => {
+            for s in statements {
+                collect_statement(s, names);
+            }
+        }
+        Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+            collect_expression(condition, names);
+            collect_statement(then_branch, names);
+            if let Some(else_branch) = else_branch {
+                collect_statement(else_branch, names);
+            }
+        }
+        Statement::WhileStatement { condition, body, .. } => {
+            collect_expression(condition, names);
+            collect_statement(body, names);
+        }
+        Statement::ForInStatement { variable, iterable, body, .. } => {
+            names.push(variable.to_string());
+            collect_expression(iterable, names);
+            collect_statement(body, names);
+        }
+        Statement::ForStatement { initializer, condition, increment, body, .. } => {
+            if let Some(init) = initializer {
+                collect_statement(init, names);
+            }
+            for expr in condition.iter().chain(increment.iter()) {
+                collect_expression(expr, names);
+            }
+            collect_statement(body, names);
+        }
+        Statement::MacroDefinition { name, .. } => names.push(name.clone()),
+        Statement::Attributed { statement, .. } => collect_statement(statement, names),
+        Statement::TypeAlias { .. } => {}
+        Statement::EnumDeclaration { variants, .. } => names.extend(variants.iter().map(|v| v.name.to_string())),
+    }
+}
+
+fn collect_expression(expr: &Expression, names: &mut Vec<String>) {
+    match expr {
+        Expression::Identifier(_, name) => names.push(name.to_string()),
+        Expression::MacroCall(_, name, _) => names.push(name.clone()),
+        _ => {}
+    }
+    for child in child_expressions(expr) {
+        collect_expression(child, names);
+    }
+}
+
+fn is_poor_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    (name.chars().count() == 1 && !CONVENTIONAL_SHORT_NAMES.contains(&name))
+        || VAGUE_NAMES.contains(&lower.as_str())
+}
+
+// ─── 지표 계산 ─────────────────────────────
+
+fn build_report(name: String, kind: FunctionKind, span: Span, mut names: Vec<String>, source: &str) -> FunctionReport {
+    let text = &source[span.byte_range(source)];
+    let lines: Vec<&str> = text.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    let comment_lines = lines.iter().filter(|l| l.starts_with("//")).count();
+    let comment_density = if lines.is_empty() { 0.0 } else { comment_lines as f64 / lines.len() as f64 };
+
+    let mut seen = std::collections::HashSet::new();
+    names.retain(|n| seen.insert(n.clone()));
+    let poor_names: Vec<String> = names.iter().filter(|n| is_poor_name(n)).cloned().collect();
+    let naming_score = if names.is_empty() { 1.0 } else { 1.0 - poor_names.len() as f64 / names.len() as f64 };
+
+    let line_count = text.lines().count();
+    let mut warnings = vec![];
+    if kind != FunctionKind::TopLevel {
+        if line_count > LONG_FUNCTION_LINES {
+            warnings.push(
+                LONG_FUNCTION
+                    .diagnostic(span, "", &[&name, &line_count, &LONG_FUNCTION_LINES])
+                    .with_help(LONG_FUNCTION.message("help", &[])),
+            );
+        }
+        if !poor_names.is_empty() {
+            warnings.push(POOR_NAMING.diagnostic(span, "", &[&name, &poor_names.join(", ")]));
+        }
+    }
+
+    FunctionReport {
+        name,
+        kind,
+        span,
+        word_count: text.split_whitespace().count(),
+        line_count,
+        comment_density,
+        naming_score,
+        poor_names,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_reports_use_character_spans() {
+        let source = "// 한글로 길게 쓴 머리말 주석입니다\nlet f = fn(a) {\n    // 본문 주석\n    return a;\n};\nreturn f(1);\n";
+        let reports = HeuristicAnalyzer::analyze_functions(source);
+        let function = reports.iter().find(|r| r.kind == FunctionKind::Function).expect("function report");
+        assert_eq!(function.line_count, 4);
+        assert_eq!(function.comment_density, 0.25);
+        assert_eq!(reports[0].line_count, 6);
+    }
+}
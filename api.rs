@@ -0,0 +1,372 @@
+// api.rs
+// `High::api`: 임베딩하는 쪽이 기대도 되는 안정 공개 API 입니다.
+//
+// 이 모듈의 이름과 시그니처는 `API_VERSION` 의 유의적 버전(semver)을 따릅니다. 호환되지 않게 바꾸면
+// 주 버전을 올리고, 필드나 변형을 더하는 것은 부 버전 변경입니다. 그래서 공개 구조체와 열거형은
+// 모두 `#[non_exhaustive]` 이며, 옵션은 `Options::default()` 에서 필드를 고쳐 씁니다.
+//
+// 내부 서비스 타입(CompilerService, Program, Value 등)은 여기서 드러내지 않습니다. 루트의 다른
+// 모듈은 `#[doc(hidden)]` 이며 언제든 바뀔 수 있습니다.
+//
+// `check`, `run`, `format` 은 `interpreter` 기능만 있으면 되고, 전체 파이프라인을 도는 `compile` 과
+// 그 결과 타입은 tokio 가 필요한 `cli` 기능에서만 있습니다.
+//
+// 파일 끝의 `signatures` 블록이 공개 시그니처를 고정합니다. 시그니처가 바뀌면 컴파일이 깨지므로,
+// 그 블록을 고쳐야 한다면 `API_VERSION` 도 함께 올려야 합니다.
+// 시그니처 밖의 약속(구문 오류 거부, 주석 보존, 문자 단위 위치)은 파일 끝의 `tests` 가 확인합니다.
+
+use crate::ast_printer;
+use crate::attributes;
+#[cfg(feature = "cli")]
+use crate::compiler_services::{CompileOptions, CompileRequest, CompilerService};
+use crate::conditional_compilation::{self, CfgContext};
+use crate::data_structures::{self, DiagnosticLevel, Value};
+use crate::dead_code;
+use crate::division_check;
+use crate::unused_value;
+use crate::named_types;
+use crate::diagnostic_renderer::DiagnosticRenderer;
+use crate::engine::Engine;
+use crate::error_codes;
+use crate::ft_runtime::Capabilities;
+#[cfg(feature = "cli")]
+use crate::executor_service::ExecutionStatus;
+use crate::lexer_service::LexerService;
+use crate::macro_expander::MacroExpander;
+use crate::messages;
+use crate::parser_service::ParserService;
+use crate::resolver::Resolver;
+use crate::stdlib;
+
+/// 이 facade 의 버전
+pub const API_VERSION: &str = "2.0.0";
+
+// ─── 옵션 ─────────────────────────────
+
+/// `compile`, `check`, `run` 공용 옵션
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct Options {
+    /// 0-3
+    pub optimization_level: u8,
+    /// 표준 라이브러리(`stdlib/*.high`)를 링크하지 않음
+    pub no_std: bool,
+    /// 사용자 코드보다 먼저 실행되는 소스
+    pub prelude: Option<String>,
+    /// 실행 전에 넣어 두는 전역 값
+    pub globals: Vec<(String, Global)>,
+    /// `@cfg` 평가용 플래그 (`name` 또는 `key=value`)
+    pub cfg_flags: Vec<String>,
+    /// `run` 에서 `run_process` 내장 함수를 허용 (기본값 거부)
+    pub allow_process: bool,
+    /// 암시적 any 바인딩을 오류로 봄 (`--strict`, E0013)
+    pub strict: bool,
+}
+
+/// 호스트가 넣을 수 있는 전역 값
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Global {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+}
+
+impl From<&Global> for Value {
+    fn from(global: &Global) -> Self {
+        match global {
+            Global::Integer(i) => Value::Integer(*i),
+            Global::Float(f) => Value::Float(*f),
+            Global::Boolean(b) => Value::Boolean(*b),
+            Global::String(s) => Value::String(s.as_str().into()),
+        }
+    }
+}
+
+// ─── 진단 ─────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// 소스 위치가 붙은 진단. `code` 는 `high explain` 으로 설명을 볼 수 있는 코드입니다.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub help: Option<String>,
+    /// 문자(char) 단위 위치 (시작, 끝). 바이트 오프셋이 아닙니다.
+    pub span: (usize, usize),
+}
+
+impl Diagnostic {
+    /// 소스 조각과 밑줄을 붙여 사람이 읽을 형태로 만듭니다.
+    pub fn render(&self, source: &str, file_name: &str, color: bool) -> String {
+        DiagnosticRenderer::new(color).render(&self.to_internal(), source, file_name)
+    }
+
+    fn to_internal(&self) -> data_structures::Diagnostic {
+        data_structures::Diagnostic {
+            level: match self.severity {
+                Severity::Info => DiagnosticLevel::Info,
+                Severity::Warning => DiagnosticLevel::Warning,
+                Severity::Error => DiagnosticLevel::Error,
+            },
+            message: self.message.clone(),
+            span: data_structures::Span { start: self.span.0, end: self.span.1 },
+            help: self.help.clone(),
+            code: self.code,
+        }
+    }
+}
+
+impl From<&data_structures::Diagnostic> for Diagnostic {
+    fn from(diag: &data_structures::Diagnostic) -> Self {
+        Diagnostic {
+            severity: match diag.level {
+                DiagnosticLevel::Info => Severity::Info,
+                DiagnosticLevel::Warning => Severity::Warning,
+                DiagnosticLevel::Error | DiagnosticLevel::HerFatal => Severity::Error,
+            },
+            code: diag.code,
+            message: diag.message.clone(),
+            help: diag.help.clone(),
+            span: (diag.span.start, diag.span.end),
+        }
+    }
+}
+
+// ─── 결과 ─────────────────────────────
+
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RunStatus {
+    Success,
+    RuntimeError,
+    /// 컴파일 오류로 실행하지 않음
+    Skipped,
+    Cancelled,
+    TimedOut,
+}
+
+/// `compile` 결과
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Compilation {
+    pub success: bool,
+    pub diagnostics: Vec<Diagnostic>,
+    /// 위치가 없는 오류 (`[E0000] 메시지` 형식)
+    pub errors: Vec<String>,
+    pub status: RunStatus,
+    /// 프로그램의 종료 코드 (최상위 `return` 의 정수, 오류면 101 등. exit_code.rs)
+    pub exit_code: i32,
+    /// 실행기가 남긴 출력 줄
+    pub output: Vec<String>,
+}
+
+// ─── 진입점 ─────────────────────────────
+
+/// 분석부터 실행까지 전체 파이프라인을 돌립니다. 네이티브 코드는 만들지 않습니다.
+///
+/// 인터프리터 값이 스레드 사이를 옮길 수 없으므로 돌려주는 future 는 `Send` 가 아닙니다.
+#[cfg(feature = "cli")]
+pub async fn compile(source: &str, options: &Options) -> Compilation {
+    let request = CompileRequest { source_code: source.to_string(), options: compile_options(options) };
+    let result = CompilerService::new().compile(request).await;
+    Compilation {
+        success: result.success,
+        diagnostics: result.diagnostics.iter().map(Diagnostic::from).collect(),
+        errors: result.errors,
+        status: match result.execution_status {
+            ExecutionStatus::Success => RunStatus::Success,
+            ExecutionStatus::RuntimeError => RunStatus::RuntimeError,
+            ExecutionStatus::Skipped => RunStatus::Skipped,
+            ExecutionStatus::Cancelled => RunStatus::Cancelled,
+            ExecutionStatus::TimedOut => RunStatus::TimedOut,
+        },
+        exit_code: result.exit_code,
+        output: result.execution_log,
+    }
+}
+
+/// 실행하지 않고 프런트엔드 검사(구문, 어트리뷰트, 타입 표기, 이름 해석, 0 나누기, 매크로 확장, return 누락)만 합니다.
+pub fn check(source: &str, options: &Options) -> Vec<Diagnostic> {
+    let mut parser = ParserService::new(LexerService::new(source));
+    let mut program = parser.parse_program();
+    let modules = if options.no_std { vec![] } else { stdlib::link(&mut program) };
+    let mut diagnostics = parser.take_diagnostics();
+    diagnostics.extend(attributes::check_program(&program));
+    conditional_compilation::prune_program(&mut program, &CfgContext::new("her_vm", &options.cfg_flags));
+    diagnostics.extend(named_types::check_program(&program, &modules, options.strict));
+    diagnostics.extend(Resolver::resolve_program(&program));
+    diagnostics.extend(dead_code::analyze(&program, &modules).diagnostics(program.span, false));
+    diagnostics.extend(division_check::check_program(&program));
+    diagnostics.extend(unused_value::check_program(&program));
+    // 매크로 오류 문자열에는 이미 `[E005x]` 코드가 들어 있습니다.
+    if let Err(errors) = MacroExpander::expand(&mut program) {
+        diagnostics.extend(errors.iter().map(|e| data_structures::Diagnostic {
+            level: DiagnosticLevel::Error,
+            message: messages::tr("compile.macro_failed", &[e]),
+            span: program.span,
+            help: None,
+            code: None,
+        }));
+    }
+    if !program.ends_with_return() {
+        diagnostics.push(error_codes::MISSING_RETURN.diagnostic(program.span, "", &[]));
+    }
+    diagnostics.iter().map(Diagnostic::from).collect()
+}
+
+/// 인터프리터로 바로 실행하고 출력 줄을 돌려줍니다. 실패하면 패닉 메시지나 오류입니다.
+pub fn run(source: &str, options: &Options) -> Result<Vec<String>, String> {
+    let mut engine = Engine::new();
+    engine.set_capabilities(Capabilities { processes: options.allow_process, ..Capabilities::default() });
+    engine.set_cfg(CfgContext::new("her_vm", &options.cfg_flags));
+    for (name, value) in &options.globals {
+        engine.set_global(name, value.into());
+    }
+    if let Some(prelude) = stdlib::execution_prelude(options.no_std, options.prelude.as_deref()) {
+        engine.load_prelude(&prelude)?;
+    }
+    engine.run(source)
+}
+
+/// 소스를 표준 형태로 다시 씁니다. 주석은 남깁니다.
+///
+/// 구문 오류가 있으면 읽지 못한 부분이 사라진 다른 프로그램이 나오므로, 다시 쓰지 않고 그 진단을 돌려줍니다.
+pub fn format(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let mut parser = ParserService::new(LexerService::new(source));
+    let program = parser.parse_program();
+    let errors: Vec<Diagnostic> = parser
+        .take_diagnostics()
+        .iter()
+        .filter(|d| matches!(d.level, DiagnosticLevel::Error | DiagnosticLevel::HerFatal))
+        .map(Diagnostic::from)
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(ast_printer::print_program_with_comments(&program, parser.comments()))
+}
+
+#[cfg(feature = "cli")]
+fn compile_options(options: &Options) -> CompileOptions {
+    CompileOptions {
+        optimization_level: options.optimization_level,
+        no_std: options.no_std,
+        prelude: options.prelude.clone(),
+        globals: options.globals.iter().map(|(name, value)| (name.clone(), value.into())).collect(),
+        cfg_flags: options.cfg_flags.clone(),
+        allow_process: options.allow_process,
+        strict: options.strict,
+        ..CompileOptions::default()
+    }
+}
+
+// 공개 시그니처 고정 (semver 가드)
+#[allow(dead_code)]
+mod signatures {
+    use super::*;
+
+    const CHECK: fn(&str, &Options) -> Vec<Diagnostic> = check;
+    const RUN: fn(&str, &Options) -> Result<Vec<String>, String> = run;
+    const FORMAT: fn(&str) -> Result<String, Vec<Diagnostic>> = format;
+    const RENDER: fn(&Diagnostic, &str, &str, bool) -> String = Diagnostic::render;
+
+    #[cfg(feature = "cli")]
+    fn compile_signature<'a>(source: &'a str, options: &'a Options) -> impl std::future::Future<Output = Compilation> + 'a {
+        compile(source, options)
+    }
+
+    fn fields(options: &mut Options, diagnostic: &Diagnostic) {
+        options.optimization_level = 0u8;
+        options.no_std = false;
+        options.prelude = None::<String>;
+        options.globals = Vec::<(String, Global)>::new();
+        options.cfg_flags = Vec::<String>::new();
+        options.allow_process = false;
+        options.strict = false;
+        let _: (Severity, Option<&'static str>, &String, &Option<String>, (usize, usize)) =
+            (diagnostic.severity, diagnostic.code, &diagnostic.message, &diagnostic.help, diagnostic.span);
+    }
+
+    #[cfg(feature = "cli")]
+    fn compilation_fields(compilation: &Compilation) {
+        let _: (bool, &Vec<Diagnostic>, &Vec<String>, RunStatus, i32, &Vec<String>) = (
+            compilation.success,
+            &compilation.diagnostics,
+            &compilation.errors,
+            compilation.status,
+            compilation.exit_code,
+            &compilation.output,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Options {
+        Options { no_std: true, ..Options::default() }
+    }
+
+    #[test]
+    fn api_version_is_semver() {
+        let parts: Vec<u64> = API_VERSION.split('.').map(|part| part.parse().unwrap()).collect();
+        assert_eq!(parts.len(), 3, "{}", API_VERSION);
+        // `format` 이 Result 를 돌려주게 바뀌며 2.0.0 이 되었습니다. 시그니처를 바꾸면 이 값도 올리세요.
+        assert_eq!(parts[0], 2);
+    }
+
+    #[test]
+    fn run_returns_output_lines() {
+        let output = run("let x = 2; return x * 3;", &options()).unwrap();
+        assert_eq!(output.last().map(String::as_str), Some("Return value: Integer(6)"));
+    }
+
+    #[test]
+    fn run_refuses_parse_errors() {
+        assert!(run("let x = -2 * 3 ** 2; return x;", &options()).is_err());
+    }
+
+    #[test]
+    fn check_reports_codes_and_char_spans() {
+        let source = "// 한글 주석\nlet y = 1e3;\nreturn y;";
+        let diagnostics = check(source, &options());
+        let diagnostic = diagnostics.iter().find(|d| d.severity == Severity::Error).unwrap_or_else(|| panic!("{:?}", diagnostics));
+        assert_eq!(diagnostic.code, Some("E0110"));
+        let start = source[..source.find("1e3").unwrap()].chars().count();
+        assert_eq!(diagnostic.span, (start, start + 3));
+    }
+
+    #[test]
+    fn format_keeps_comments() {
+        let source = "// 머리말\nlet x = 1; /* 블록 */\nif x > 0 {\n// 안쪽\nprint(x);\n// 블록 끝\n}\nreturn x;\n// 파일 끝\n";
+        let formatted = format(source).unwrap();
+        assert_eq!(
+            formatted,
+            "// 머리말\nlet x = 1;\n/* 블록 */\nif x > 0 {\n    // 안쪽\n    print(x);\n    // 블록 끝\n}\nreturn x;\n// 파일 끝\n"
+        );
+        assert_eq!(format(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn format_refuses_parse_errors() {
+        for source in ["let x = -2 * 3 ** 2; return x;", "let y = 1e3; return y;"] {
+            let errors = format(source).unwrap_err();
+            assert!(!errors.is_empty(), "{}", source);
+            assert!(errors.iter().all(|d| d.severity == Severity::Error && d.code == Some("E0110")), "{:?}", errors);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// arrays.rs
+// 배열 값(`Value::Array`)의 연산입니다.
+//
+// 배열은 요소들을 `Arc<[Value]>` 한 덩어리로 저장하므로 값을 복사해도 요소를 복사하지 않습니다.
+// 바이트 버퍼처럼 바뀌지 않는 값이며, 자르기(`xs[1:3]`)와 이어 붙이기(`a + b`)는 새 배열을 만듭니다.
+//
+//   [1, 2, 3]        리터럴. 요소의 타입은 섞여도 됩니다. 요소 중 오류 값이 있으면 배열 대신 그 오류입니다.
+//   xs[i]            i 번째 요소
+//   xs[start:end]    [start, end) 범위의 새 배열. 양쪽 모두 생략할 수 있습니다.
+//   a == b           길이가 같고 같은 자리의 요소끼리 모두 `==` 이면 참
+//   len(xs)          요소 수
+
+use crate::bytes::AccessError;
+use crate::data_structures::{TokenKind, Value};
+use crate::operators;
+
+/// `target[index]`
+pub fn index(target: &Value, index: &Value) -> Result<Value, AccessError> {
+    let (Value::Array(items), Value::Integer(i)) = (target, index) else {
+        return Err(AccessError::TypeMismatch);
+    };
+    usize::try_from(*i)
+        .ok()
+        .and_then(|at| items.get(at))
+        .cloned()
+        .ok_or(AccessError::IndexOutOfBounds { index: *i, len: items.len() })
+}
+
+/// `target[start:end]`. 생략한 쪽은 처음이나 끝입니다.
+pub fn slice(target: &Value, start: Option<&Value>, end: Option<&Value>) -> Result<Value, AccessError> {
+    let Value::Array(items) = target else {
+        return Err(AccessError::TypeMismatch);
+    };
+    let bound = |value: Option<&Value>, default: usize| match value {
+        None => Ok(default as i64),
+        Some(Value::Integer(i)) => Ok(*i),
+        Some(_) => Err(AccessError::TypeMismatch),
+    };
+    let (start, end) = (bound(start, 0)?, bound(end, items.len())?);
+    if start < 0 || start > end || end > items.len() as i64 {
+        return Err(AccessError::SliceOutOfBounds { start, end, len: items.len() });
+    }
+    Ok(Value::Array(items[start as usize..end as usize].into()))
+}
+
+/// 요소별 `==`. 비교할 수 없는 요소 쌍이 있으면 None 입니다.
+pub fn equal(a: &[Value], b: &[Value]) -> Option<bool> {
+    if a.len() != b.len() {
+        return Some(false);
+    }
+    for (x, y) in a.iter().zip(b) {
+        match operators::binary(&TokenKind::Eq, x, y).ok()? {
+            Value::Boolean(true) => {}
+            Value::Boolean(false) => return Some(false),
+            _ => return None,
+        }
+    }
+    Some(true)
+}
@@ -0,0 +1,83 @@
+// artifact_metadata.rs
+// 네이티브 실행 파일에 기록되는 메타데이터(컴파일러 버전, 소스 해시, 증명 블록 해시 등)를 다룹니다.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+/// 바이너리에 기록되는 컴파일러 버전입니다.
+pub const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 메타데이터 섹션의 시작 표식입니다.
+pub const METADATA_MAGIC: &[u8] = b"HIGHMETA\n";
+
+/// 링크 이후 증명 블록 해시로 덮어쓸 고정 길이 자리표시자입니다.
+const PROOF_HASH_PLACEHOLDER: &str = "________________";
+
+/// 실행 파일에서 읽어 낸 `key=value` 메타데이터입니다.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactMetadata {
+    pub entries: Vec<(String, String)>,
+}
+
+impl ArtifactMetadata {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+/// 코드 생성 단계에서 섹션에 기록할 항목들을 만듭니다.
+/// 증명 블록 해시는 아직 알 수 없으므로 자리표시자로 남겨 둡니다.
+pub fn build_entries(source_code: &str, optimization_level: u8) -> Vec<(String, String)> {
+    vec![
+        ("compiler_version".into(), COMPILER_VERSION.into()),
+        ("source_hash".into(), source_hash(source_code)),
+        ("optimization_level".into(), optimization_level.to_string()),
+        ("proof_block_hash".into(), PROOF_HASH_PLACEHOLDER.into()),
+    ]
+}
+
+/// 증명 블록과 같은 방식(`Blockchain::calculate_hash`)의 해시. `blockchain` 기능 없이도 빌드되도록 따로 둡니다.
+fn source_hash(source_code: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    source_code.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+/// 링크된 바이너리의 자리표시자를 실제 증명 블록 해시로 교체합니다.
+pub fn stamp_proof_hash(binary_path: &str, block_hash: &str) -> Result<(), String> {
+    let mut bytes = fs::read(binary_path).map_err(|e| format!("바이너리 읽기 실패: {}", e))?;
+    let placeholder = PROOF_HASH_PLACEHOLDER.as_bytes();
+
+    let offset = find(&bytes, placeholder)
+        .ok_or("메타데이터 섹션에서 증명 해시 자리표시자를 찾을 수 없습니다.")?;
+    let stamped = format!("{:<width$}", block_hash, width = placeholder.len());
+    if stamped.len() != placeholder.len() {
+        return Err(format!("증명 해시가 너무 깁니다: {}", block_hash));
+    }
+
+    bytes[offset..offset + placeholder.len()].copy_from_slice(stamped.as_bytes());
+    fs::write(binary_path, bytes).map_err(|e| format!("바이너리 쓰기 실패: {}", e))
+}
+
+/// 실행 파일에서 메타데이터 섹션을 찾아 읽어 냅니다. (`high inspect`)
+pub fn read_metadata(binary_path: &str) -> Result<ArtifactMetadata, String> {
+    let bytes = fs::read(binary_path).map_err(|e| format!("'{}' 읽기 실패: {}", binary_path, e))?;
+    let start = find(&bytes, METADATA_MAGIC)
+        .ok_or("High 메타데이터 섹션이 없습니다. (High 컴파일러로 생성된 바이너리가 아닙니다)")?
+        + METADATA_MAGIC.len();
+    let end = bytes[start..].iter().position(|&b| b == 0).map_or(bytes.len(), |p| start + p);
+
+    let text = String::from_utf8_lossy(&bytes[start..end]);
+    let entries = text
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.trim_end().to_string()))
+        .collect();
+
+    Ok(ArtifactMetadata { entries })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
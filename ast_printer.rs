@@ -0,0 +1,275 @@
+// ast_printer.rs
+// Program (AST)을 다시 High 소스 코드 텍스트로 출력합니다. (`--emit-expanded` 등에서 사용)
+
+use crate::bytes;
+use crate::compat::*;
+use crate::data_structures::{Attribute, AttributeArg, Expression, Pattern, Program, Statement, Value};
+use crate::formatting;
+use crate::lexer_service::{Comment, CommentKind};
+
+const INDENT: &str = "    ";
+
+/// Program 전체를 High 소스 텍스트로 변환합니다.
+pub fn print_program(program: &Program) -> String {
+    print_program_with_comments(program, &[])
+}
+
+/// 주석을 남기며 Program 을 출력합니다. (포매터에서 사용)
+///
+/// 주석은 소스에서 바로 뒤에 오는 문장 앞에 제 줄로 붙고, 블록 끝의 주석은 닫는 `}` 앞에, 파일 끝의 주석은 맨 끝에
+/// 남습니다. 식 한가운데의 주석은 다음 문장 앞으로 옮겨지지만 사라지지는 않습니다. `comments` 는 소스 순서여야 합니다.
+pub fn print_program_with_comments(program: &Program, comments: &[Comment]) -> String {
+    let mut pending = comments;
+    let mut out = String::new();
+    for stmt in &program.statements {
+        print_statement(stmt, 0, &mut pending, &mut out);
+    }
+    print_comments(&mut pending, usize::MAX, 0, &mut out);
+    out
+}
+
+/// `before` 위치보다 앞에서 시작하는 남은 주석을 `depth` 들여쓰기로 한 줄씩 출력합니다.
+fn print_comments(pending: &mut &[Comment], before: usize, depth: usize, out: &mut String) {
+    while let Some((comment, rest)) = pending.split_first() {
+        if comment.span.start >= before {
+            break;
+        }
+        out.push_str(&INDENT.repeat(depth));
+        match comment.kind {
+            CommentKind::Line => out.push_str(&format!("//{}\n", comment.text)),
+            CommentKind::Block => out.push_str(&format!("/*{}*/\n", comment.text)),
+        }
+        *pending = rest;
+    }
+}
+
+fn print_statement(stmt: &Statement, depth: usize, pending: &mut &[Comment], out: &mut String) {
+    print_comments(pending, stmt.span().start, depth, out);
+    let pad = INDENT.repeat(depth);
+    match stmt {
+        Statement::ExpressionStatement(expr) => {
+            out.push_str(&format!("{}{};\n", pad, expression(expr, pending)));
+        }
+        Statement::DiscardStatement(_, expr) => {
+            out.push_str(&format!("{}_ = {};\n", pad, expression(expr, pending)));
+        }
+        Statement::LetStatement { name, value, type_annotation, is_mutable, .. } => {
+            let mutability = if *is_mutable { "mut " } else { "" };
+            let annotation = type_annotation
+                .as_ref()
+                .map(|t| format!(": {}", t))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "{}let {}{}{} = {};\n",
+                pad, mutability, name, annotation, expression(value, pending)
+            ));
+        }
+        Statement::ReturnStatement(_, expr) => {
+            out.push_str(&format!("{}return {};\n", pad, expression(expr, pending)));
+        }
+        Statement::YieldStatement(_, expr) => {
+            out.push_str(&format!("{}yield {};\n", pad, expression(expr, pending)));
+        }
+        Statement::TypeAlias { name, target, .. } => {
+            out.push_str(&format!("{}type {} = {};\n", pad, name, target));
+        }
+        Statement::EnumDeclaration { name, variants, .. } => {
+            let variants: Vec<String> = variants
+                .iter()
+                .map(|v| match v.fields.as_slice() {
+                    [] => v.name.to_string(),
+                    fields => format!("{}({})", v.name, fields.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", ")),
+                })
+                .collect();
+            out.push_str(&format!("{}enum {} {{ {} }}\n", pad, name, variants.join(", ")));
+        }
+        Statement::BlockStatement { .. } => {
+            out.push_str(&pad);
+            print_block(stmt, depth, pending, out);
+            out.push('\n');
+        }
+        Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+            out.push_str(&format!("{}if {} ", pad, expression(condition, pending)));
+            print_block(then_branch, depth, pending, out);
+            if let Some(else_stmt) = else_branch {
+                out.push_str(" else ");
+                print_block(else_stmt, depth, pending, out);
+            }
+            out.push('\n');
+        }
+        Statement::DeferStatement(_, body) => {
+            out.push_str(&format!("{}defer ", pad));
+            print_block(body, depth, pending, out);
+            out.push('\n');
+        }
+        Statement::WhileStatement { condition, body, .. } => {
+            out.push_str(&format!("{}while {} ", pad, expression(condition, pending)));
+            print_block(body, depth, pending, out);
+            out.push('\n');
+        }
+        Statement::ForStatement { initializer, condition, increment, body, .. } => {
+            let init = initializer
+                .as_ref()
+                .map(|s| print_inline_statement(s))
+                .unwrap_or_default();
+            let cond = condition.as_ref().map(|c| expression(c, pending)).unwrap_or_default();
+            let inc = increment.as_ref().map(|i| expression(i, pending)).unwrap_or_default();
+            out.push_str(&format!("{}for {}; {}; {} ", pad, init, cond, inc));
+            print_block(body, depth, pending, out);
+            out.push('\n');
+        }
+        Statement::ForInStatement { variable, iterable, body, .. } => {
+            out.push_str(&format!("{}for {} in {} ", pad, variable, expression(iterable, pending)));
+            print_block(body, depth, pending, out);
+            out.push('\n');
+        }
+        Statement::MacroDefinition { name, parameters, body, .. } => {
+            out.push_str(&format!("{}macro {}({}) ", pad, name, parameters.join(", ")));
+            print_block(body, depth, pending, out);
+            out.push('\n');
+        }
+        Statement::Attributed { attributes, statement, .. } => {
+            for attribute in attributes {
+                out.push_str(&format!("{}{}\n", pad, print_attribute(attribute)));
+            }
+            print_statement(statement, depth, pending, out);
+        }
+    }
+}
+
+fn print_attribute(attribute: &Attribute) -> String {
+    if attribute.args.is_empty() {
+        return format!("@{}", attribute.name);
+    }
+    let args: Vec<String> = attribute
+        .args
+        .iter()
+        .map(|arg| match arg {
+            AttributeArg::Flag(name) => name.clone(),
+            AttributeArg::KeyValue(key, value) => format!("{} = {:?}", key, value),
+            AttributeArg::Literal(text) => format!("{:?}", text),
+        })
+        .collect();
+    format!("@{}({})", attribute.name, args.join(", "))
+}
+
+/// 블록이 아닌 문장도 `{ ... }`로 감싸서 출력합니다.
+fn print_block(stmt: &Statement, depth: usize, pending: &mut &[Comment], out: &mut String) {
+    out.push_str("{\n");
+    match stmt {
+        Statement::BlockStatement { statements, span, .. } => {
+            for inner in statements {
+                print_statement(inner, depth + 1, pending, out);
+            }
+            print_comments(pending, span.end, depth + 1, out);
+        }
+        other => print_statement(other, depth + 1, pending, out),
+    }
+    out.push_str(&INDENT.repeat(depth));
+    out.push('}');
+}
+
+/// `for` 초기화 절처럼 한 줄로 써야 하는 문장을 출력합니다.
+fn print_inline_statement(stmt: &Statement) -> String {
+    let mut text = String::new();
+    print_statement(stmt, 0, &mut &[][..], &mut text);
+    text.trim_end().trim_end_matches(';').to_string()
+}
+
+/// 표현식을 High 소스 텍스트로 변환합니다.
+pub fn print_expression(expr: &Expression) -> String {
+    expression(expr, &mut &[][..])
+}
+
+/// `print_expression` 과 같지만, 함수 본문 안의 주석을 `pending` 에서 꺼내 본문 문장 앞에 붙입니다.
+fn expression(expr: &Expression, pending: &mut &[Comment]) -> String {
+    match expr {
+        Expression::Literal(_, value) => print_literal(value),
+        Expression::Identifier(_, name) => name.to_string(),
+        Expression::PrefixOperation(_, op, right) => format!("{}{}", op, expression(right, pending)),
+        Expression::InfixOperation(_, op, left, right) => {
+            format!("{} {} {}", expression(left, pending), op, expression(right, pending))
+        }
+        Expression::Range(_, start, end, inclusive) => {
+            format!("{}{}{}", expression(start, pending), if *inclusive { "..=" } else { ".." }, expression(end, pending))
+        }
+        Expression::Ternary(_, cond, then_expr, else_expr) => format!(
+            "{} ? {} : {}",
+            expression(cond, pending),
+            expression(then_expr, pending),
+            expression(else_expr, pending)
+        ),
+        Expression::Function(_, parameters, body) => {
+            let names: Vec<&str> = parameters.iter().map(|p| p.as_str()).collect();
+            let mut text = format!("fn({}) ", names.join(", "));
+            print_block(body, 0, pending, &mut text);
+            text
+        }
+        Expression::Call(_, function, args) => {
+            format!("{}({})", expression(function, pending), print_arguments(args, pending))
+        }
+        Expression::Grouped(_, inner) => format!("({})", expression(inner, pending)),
+        Expression::Reflect(_, inner) => format!("reflect {}", expression(inner, pending)),
+        Expression::Eval(_, inner) => format!("eval {}", expression(inner, pending)),
+        Expression::TypeOf(_, inner) => format!("type_of {}", expression(inner, pending)),
+        Expression::MacroCall(_, name, args) => format!("{}({})", name, print_arguments(args, pending)),
+        Expression::ArrayLiteral(_, elements) => format!("[{}]", print_arguments(elements, pending)),
+        Expression::MapLiteral(_, entries) => {
+            let entries: Vec<String> =
+                entries.iter().map(|(key, value)| format!("{}: {}", expression(key, pending), expression(value, pending))).collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Expression::Index(_, target, index) => format!("{}[{}]", expression(target, pending), expression(index, pending)),
+        Expression::Member(_, target, name) => format!("{}.{}", expression(target, pending), name),
+        Expression::MethodCall(_, target, name, args) => {
+            format!("{}.{}({})", expression(target, pending), name, print_arguments(args, pending))
+        }
+        Expression::Slice(_, target, start, end) => format!(
+            "{}[{}:{}]",
+            expression(target, pending),
+            start.as_ref().map_or(String::new(), |s| expression(s, pending)),
+            end.as_ref().map_or(String::new(), |e| expression(e, pending))
+        ),
+        Expression::Match(_, subject, arms) => {
+            let arms: Vec<String> = arms
+                .iter()
+                .map(|arm| {
+                    let guard = arm.guard.as_ref().map_or(String::new(), |g| format!(" if {}", expression(g, pending)));
+                    format!("{}{} => {}", print_pattern(&arm.pattern), guard, expression(&arm.body, pending))
+                })
+                .collect();
+            format!("match {} {{ {} }}", expression(subject, pending), arms.join(", "))
+        }
+        Expression::Assign(_, op, name, value) => format!("{} {} {}", name, op, expression(value, pending)),
+    }
+}
+
+fn print_pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Wildcard => "_".into(),
+        Pattern::Binding(name) => name.to_string(),
+        Pattern::Literal(value) => print_literal(value),
+        Pattern::Range { start, end, inclusive } => format!("{}{}{}", start, if *inclusive { "..=" } else { ".." }, end),
+        Pattern::Or(alternatives) => alternatives.iter().map(print_pattern).collect::<Vec<_>>().join(" | "),
+        Pattern::Variant(name, fields) if fields.is_empty() => name.to_string(),
+        Pattern::Variant(name, fields) => {
+            format!("{}({})", name, fields.iter().map(print_pattern).collect::<Vec<_>>().join(", "))
+        }
+    }
+}
+
+fn print_arguments(args: &[Box<Expression>], pending: &mut &[Comment]) -> String {
+    args.iter().map(|a| expression(a, pending)).collect::<Vec<_>>().join(", ")
+}
+
+fn print_literal(value: &Value) -> String {
+    match value {
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => formatting::float_literal(*f).unwrap_or_else(|| format!("{:?}", f)),
+        Value::Boolean(b) => b.to_string(),
+        Value::String(s) => format!("{:?}", s),
+        Value::Bytes(b) => format!("b\"{}\"", bytes::escape(b)),
+        Value::Null => "null".into(),
+        other => format!("{:?}", other),
+    }
+}
@@ -0,0 +1,154 @@
+// attributes.rs
+// `@name(args)` 어트리뷰트의 내장 목록과 검증 로직입니다.
+//
+// 내장 어트리뷰트와 이를 소비하는 단계:
+//   @cfg(...)          조건부 컴파일 (conditional_compilation)
+//   @test              테스트 모드에서만 컴파일되는 항목 (conditional_compilation)
+//   @inline            상수 바인딩을 사용 지점에 인라인 (optimizer)
+//   @deprecated("msg") 사용 중단 경고 (resolver)
+//   @allow(deprecated) 경고 억제. 파일 맨 앞의 `@!allow(...)` 는 파일 전체에 적용 (resolver, dead_code, division_check, unused_value)
+//   @export            외부에서 부르는 진입점. 최상위 코드에서 쓰지 않아도 도달 가능으로 봄 (dead_code)
+//   @derive(eq, show, clone) enum 선언의 비교·문자열 변환·복사 자동 구현 (ft_runtime)
+
+use crate::compat::*;
+use crate::data_structures::{
+    Attribute, AttributeArg, Diagnostic, Program, Statement,
+};
+use crate::error_codes;
+
+pub const BUILTIN_ATTRIBUTES: &[&str] = &["cfg", "test", "inline", "deprecated", "allow", "derive", "export"];
+
+/// `@derive(...)` 로 자동 구현할 수 있는 동작
+pub const DERIVABLE: &[&str] = &["eq", "show", "clone"];
+
+/// 어트리뷰트 목록에 주어진 이름이 있는지 확인합니다.
+pub fn has_attribute(attributes: &[Attribute], name: &str) -> bool {
+    attributes.iter().any(|a| a.name == name)
+}
+
+/// `@deprecated` / `@deprecated("msg")` 를 찾아 메시지를 돌려줍니다.
+pub fn deprecation(attributes: &[Attribute]) -> Option<Option<String>> {
+    attributes.iter().find(|a| a.name == "deprecated").map(|a| {
+        a.args.iter().find_map(|arg| match arg {
+            AttributeArg::Literal(msg) => Some(msg.clone()),
+            _ => None,
+        })
+    })
+}
+
+/// `@derive(...)` 에 나열된 동작 이름들 (알 수 없는 이름은 검사 단계에서 걸러집니다)
+pub fn derived(attributes: &[Attribute]) -> Vec<&str> {
+    attributes
+        .iter()
+        .filter(|a| a.name == "derive")
+        .flat_map(|a| &a.args)
+        .filter_map(|arg| match arg {
+            AttributeArg::Flag(name) if DERIVABLE.contains(&name.as_str()) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 프로그램 전체의 어트리뷰트를 검사하여 경고/오류 진단을 만듭니다.
+pub fn check_program(program: &Program) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = program.attributes.iter().filter_map(check_attribute).collect();
+    for stmt in &program.statements {
+        check_statement(stmt, &mut diagnostics);
+    }
+    diagnostics
+}
+
+fn check_statement(stmt: &Statement, diagnostics: &mut Vec<Diagnostic>) {
+    match stmt {
+        Statement::Attributed { attributes, statement, .. } => {
+            for attribute in attributes {
+                if let Some(diag) = check_attribute(attribute) {
+                    diagnostics.push(diag);
+                } else if let Some(diag) = check_placement(attribute, statement) {
+                    diagnostics.push(diag);
+                }
+            }
+            check_statement(statement, diagnostics);
+        }
+        Statement::BlockStatement { statements, .. } => {
+            for s in statements {
+                check_statement(s, diagnostics);
+            }
+        }
+        Statement::IfStatement { then_branch, else_branch, .. } => {
+            check_statement(then_branch, diagnostics);
+            if let Some(else_stmt) = else_branch {
+                check_statement(else_stmt, diagnostics);
+            }
+        }
+        Statement::WhileStatement { body, .. }
+        | Statement::ForStatement { body, .. }
+        | Statement::ForInStatement { body, .. }
+        | Statement::DeferStatement(_, body)
+        | Statement::MacroDefinition { body, .. } => check_statement(body, diagnostics),
+        _ => {}
+    }
+}
+
+fn check_attribute(attribute: &Attribute) -> Option<Diagnostic> {
+    let problem = match attribute.name.as_str() {
+        "cfg" if attribute.args.is_empty() => Some("cfg_empty"),
+        "test" | "inline" | "export" if !attribute.args.is_empty() => Some("no_args"),
+        "allow" if attribute.args.is_empty() => Some("allow_empty"),
+        "derive"
+            if attribute.args.is_empty()
+                || attribute.args.iter().any(|a| !matches!(a, AttributeArg::Flag(f) if DERIVABLE.contains(&f.as_str()))) =>
+        {
+            Some("derive_args")
+        }
+        "deprecated"
+            if attribute.args.len() > 1
+                || attribute.args.iter().any(|a| !matches!(a, AttributeArg::Literal(_))) =>
+        {
+            Some("deprecated_args")
+        }
+        name if !BUILTIN_ATTRIBUTES.contains(&name) => {
+            return Some(
+                error_codes::UNKNOWN_ATTRIBUTE
+                    .diagnostic(attribute.span, "", &[&name])
+                    .with_help(error_codes::UNKNOWN_ATTRIBUTE.message("help", &[&BUILTIN_ATTRIBUTES.join(", ")])),
+            );
+        }
+        _ => None,
+    };
+
+    problem.map(|detail| {
+        let code = &error_codes::INVALID_ATTRIBUTE_ARGS;
+        code.diagnostic(attribute.span, "", &[&attribute.name, &code.message(detail, &[])])
+    })
+}
+
+/// 특정 종류의 항목에만 붙는 어트리뷰트가 다른 문장에 붙었는지 검사합니다.
+fn check_placement(attribute: &Attribute, statement: &Statement) -> Option<Diagnostic> {
+    match attribute.name.as_str() {
+        "derive" if !matches!(statement, Statement::EnumDeclaration { .. }) => {
+            let code = &error_codes::MISPLACED_ATTRIBUTE;
+            Some(code.diagnostic(attribute.span, "", &[&attribute.name, &code.message("type_declaration", &[])]))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_service::LexerService;
+    use crate::parser_service::ParserService;
+
+    fn codes(source: &str) -> Vec<&'static str> {
+        let program = ParserService::new(LexerService::new(source)).parse_program();
+        check_program(&program).iter().filter_map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn derive_is_placed_on_enum_declarations_only() {
+        assert!(codes("@derive(eq, show)\nenum Color { Red, Green }\nreturn 0;").is_empty());
+        assert_eq!(codes("@derive(eq)\nlet point = 1;\nreturn point;"), vec!["E0042"]);
+        assert_eq!(codes("@derive(hash)\nenum Color { Red }\nreturn 0;"), vec!["E0041"]);
+    }
+}
@@ -0,0 +1,220 @@
+// benchmarks.rs
+// 런타임 성능 측정용 고정 입력(fixture)과 측정기입니다. `high bench` 가 실행합니다.
+//
+// 변수 조회 벤치마크는 아래 함수를 좁은 반복문에서 계속 호출합니다. 본문은 중첩 스코프에서
+// 바깥 지역 변수를 여러 번 읽으므로, 이름으로 환경 사슬을 따라가는 비용이 그대로 드러납니다.
+//
+//   let lookup = fn(a, b) {
+//       let c = a + b
+//       {
+//           let d = c * a
+//           {
+//               let e = (a + b) * (c - d) + (a * c) - (b * d)
+//               return e + a + b + c + d
+//           }
+//       }
+//   }
+//
+// 식별자 할당 벤치마크는 이름이 많은 프로그램을 렉싱·파싱·실행하는 동안의 힙 할당 횟수를 셉니다.
+// 횟수는 `CountingAllocator` 가 전역 할당자로 설치된 경우(`high` 바이너리)에만 집계됩니다.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::bytecode;
+use crate::data_structures::{Expression, FunctionValue, Span, Statement, TokenKind, Value};
+use crate::ft_runtime::HighEnduranceRuntime;
+use crate::interner::Symbol;
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+
+pub const DEFAULT_ITERATIONS: u32 = 20_000;
+
+/// 식별자 할당 벤치마크의 변수 개수
+pub const DEFAULT_IDENTIFIERS: usize = 50;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// 힙 할당 횟수를 세는 전역 할당자입니다. 실제 할당은 시스템 할당자에 맡깁니다.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// 지금까지의 할당 횟수. `CountingAllocator` 가 설치되지 않았으면 항상 0 입니다.
+pub fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone)]
+pub struct LookupBenchmark {
+    pub iterations: u32,
+    /// 트리 순회 인터프리터
+    pub interpreted: Duration,
+    /// 바이트코드, 지역 변수도 이름으로 조회
+    pub by_name: Duration,
+    /// 바이트코드, 지역 변수는 칸 번호로 조회
+    pub by_slot: Duration,
+}
+
+impl LookupBenchmark {
+    /// 이름 조회 대비 칸 조회의 속도 향상 배율
+    pub fn slot_speedup(&self) -> f64 {
+        self.by_name.as_secs_f64() / self.by_slot.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn render_text(&self) -> String {
+        let per_call = |d: Duration| d.as_nanos() as f64 / f64::from(self.iterations.max(1));
+        format!(
+            "variable lookup ({} calls)\n  interpreter      {:>8.2}ms  {:>8.0}ns/call\n  bytecode (name)  {:>8.2}ms  {:>8.0}ns/call\n  bytecode (slot)  {:>8.2}ms  {:>8.0}ns/call\n  slot speedup     {:>8.2}x\n",
+            self.iterations,
+            self.interpreted.as_secs_f64() * 1000.0,
+            per_call(self.interpreted),
+            self.by_name.as_secs_f64() * 1000.0,
+            per_call(self.by_name),
+            self.by_slot.as_secs_f64() * 1000.0,
+            per_call(self.by_slot),
+            self.slot_speedup(),
+        )
+    }
+}
+
+/// 세 가지 실행 방식으로 같은 함수를 호출하고, 결과가 모두 같은지 확인한 뒤 시간을 잽니다.
+pub fn variable_lookup(iterations: u32) -> Result<LookupBenchmark, String> {
+    let parameters = vec![Symbol::intern("a"), Symbol::intern("b")];
+    let body = lookup_fixture();
+    let by_name = bytecode::compile_by_name(&parameters, &body)?;
+    let by_slot = bytecode::compile(&parameters, &body)?;
+    let function = FunctionValue::new(parameters, body);
+
+    let mut rt = HighEnduranceRuntime::new();
+    rt.tiering.borrow_mut().threshold = None;
+
+    let expected = rt.call_function(&function, arguments(1));
+    for chunk in [&by_name, &by_slot] {
+        let actual = bytecode::execute(&mut rt, chunk, arguments(1));
+        if format!("{:?}", actual) != format!("{:?}", expected) {
+            return Err(format!("bytecode result {:?} differs from interpreter {:?}", actual, expected));
+        }
+    }
+
+    let interpreted = time(&mut rt, iterations, |rt, i| rt.call_function(&function, arguments(i)));
+    let by_name = time(&mut rt, iterations, |rt, i| bytecode::execute(rt, &by_name, arguments(i)));
+    let by_slot = time(&mut rt, iterations, |rt, i| bytecode::execute(rt, &by_slot, arguments(i)));
+    Ok(LookupBenchmark { iterations, interpreted, by_name, by_slot })
+}
+
+#[derive(Debug, Clone)]
+pub struct IdentifierBenchmark {
+    pub statements: usize,
+    /// 렉싱과 파싱 중의 할당 횟수
+    pub parse_allocations: usize,
+    /// 실행 중의 할당 횟수
+    pub execute_allocations: usize,
+}
+
+impl IdentifierBenchmark {
+    pub fn render_text(&self) -> String {
+        let per_statement = |n: usize| n as f64 / self.statements.max(1) as f64;
+        format!(
+            "identifier allocations ({} statements)\n  lex + parse      {:>8}  {:>6.1}/stmt\n  execute          {:>8}  {:>6.1}/stmt\n",
+            self.statements,
+            self.parse_allocations,
+            per_statement(self.parse_allocations),
+            self.execute_allocations,
+            per_statement(self.execute_allocations),
+        )
+    }
+}
+
+/// 변수 `identifiers` 개를 선언하고 여러 번 읽는 프로그램의 할당 횟수를 잽니다.
+/// 이름은 한 번 인터닝되면 다시 할당되지 않으므로, 두 번째 실행부터의 횟수를 보고합니다.
+pub fn identifier_allocations(identifiers: usize) -> IdentifierBenchmark {
+    let mut source = String::new();
+    for i in 0..identifiers {
+        source.push_str(&format!("let value{i} = {i}\nlet copy{i} = value{i}\n"));
+    }
+    for _ in 0..20 {
+        for i in 0..identifiers {
+            source.push_str(&format!("copy{i}\nvalue{i}\n"));
+        }
+    }
+
+    let measure = || {
+        let start = allocations();
+        let program = ParserService::new(LexerService::new(&source)).parse_program();
+        let parsed = allocations();
+        let statements = program.statements.len();
+        HighEnduranceRuntime::new().execute_program(program);
+        IdentifierBenchmark { statements, parse_allocations: parsed - start, execute_allocations: allocations() - parsed }
+    };
+    measure();
+    measure()
+}
+
+fn arguments(i: u32) -> Vec<Value> {
+    vec![Value::Integer(i64::from(i % 100)), Value::Integer(3)]
+}
+
+fn time(rt: &mut HighEnduranceRuntime, iterations: u32, mut call: impl FnMut(&mut HighEnduranceRuntime, u32) -> Value) -> Duration {
+    let start = Instant::now();
+    for i in 0..iterations {
+        call(rt, i);
+        // 바인딩마다 쌓이는 실행 로그가 측정을 흐리지 않도록 비웁니다.
+        rt.output.clear();
+    }
+    start.elapsed()
+}
+
+// ─── 고정 입력 ─────────────────────────────
+
+fn lookup_fixture() -> Statement {
+    let e = infix(
+        TokenKind::Minus,
+        infix(
+            TokenKind::Plus,
+            infix(TokenKind::Asterisk, infix(TokenKind::Plus, ident("a"), ident("b")), infix(TokenKind::Minus, ident("c"), ident("d"))),
+            infix(TokenKind::Asterisk, ident("a"), ident("c")),
+        ),
+        infix(TokenKind::Asterisk, ident("b"), ident("d")),
+    );
+    let result = ["a", "b", "c", "d"].iter().fold(ident("e"), |sum, name| infix(TokenKind::Plus, sum, ident(name)));
+
+    let inner = block(vec![let_statement("e", e), Statement::ReturnStatement(span(), result)]);
+    let middle = block(vec![let_statement("d", infix(TokenKind::Asterisk, ident("c"), ident("a"))), inner]);
+    block(vec![let_statement("c", infix(TokenKind::Plus, ident("a"), ident("b"))), middle])
+}
+
+fn span() -> Span {
+    Span { start: 0, end: 0 }
+}
+
+fn ident(name: &str) -> Box<Expression> {
+    Box::new(Expression::Identifier(span(), Symbol::intern(name)))
+}
+
+fn infix(op: TokenKind<'static>, left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::InfixOperation(span(), op, left, right))
+}
+
+fn let_statement(name: &str, value: Box<Expression>) -> Statement {
+    Statement::LetStatement { name: Symbol::intern(name), value, type_annotation: None, is_mutable: false, span: span() }
+}
+
+fn block(statements: Vec<Statement>) -> Statement {
+    Statement::BlockStatement { statements, span: span() }
+}
@@ -0,0 +1,176 @@
+// bundle.rs
+// `high bundle`: High 프로그램과 인터프리터를 실행 파일 하나로 묶습니다.
+//
+// 번들 = 실행기(runner) 바이너리 + 프로그램 페이로드 + 꼬리표. 꼬리표는 파일 끝 16바이트로,
+// 페이로드 길이(u64, 리틀 엔디언)와 `MAGIC` 입니다. 실행기는 시작할 때 자기 실행 파일 끝을 보고
+// 꼬리표가 있으면 컴파일러 대신 묶인 프로그램을 실행합니다.
+//
+// 실행기는 기본적으로 지금 돌고 있는 `high` 자신이며, `--runner` 로 다른 OS 용으로 빌드한
+// `high` 를 줄 수 있습니다. 페이로드에는 표준 라이브러리 링크, `@cfg` 정리, 매크로 확장을 마친
+// AST 를 소스 형태(ast_printer)로 넣으므로, 받는 쪽 실행기의 표준 라이브러리 버전에 기대지 않습니다.
+// NASM 이나 링커는 필요 없습니다.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::artifact_metadata;
+use crate::ast_printer;
+use crate::attributes;
+use crate::conditional_compilation::{self, CfgContext};
+use crate::data_structures::DiagnosticLevel;
+use crate::engine::Engine;
+use crate::exit_code;
+use crate::lexer_service::LexerService;
+use crate::macro_expander::MacroExpander;
+use crate::messages;
+use crate::parser_service::ParserService;
+use crate::stdlib;
+
+/// 번들 꼬리표의 표식
+pub const MAGIC: &[u8; 8] = b"HIGHBNDL";
+/// 꼬리표 크기: 페이로드 길이 8바이트 + 표식 8바이트
+const TRAILER_LEN: u64 = 16;
+/// 페이로드 형식 버전. 읽을 수 없는 버전이면 실행하지 않습니다.
+const FORMAT_VERSION: &str = "1";
+
+/// 실행 파일에 묶이는 프로그램
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    /// 원본 파일 이름 (오류 메시지용)
+    pub name: String,
+    /// 번들을 만든 컴파일러 버전
+    pub compiler_version: String,
+    /// 매크로 확장까지 마친 프로그램 소스
+    pub program: String,
+}
+
+impl Bundle {
+    /// 소스를 실행 직전 형태로 만듭니다. 어트리뷰트나 매크로 오류가 있으면 묶지 않습니다.
+    pub fn from_source(name: &str, source: &str, no_std: bool) -> Result<Bundle, Vec<String>> {
+        let mut program = ParserService::new(LexerService::new(source)).parse_program();
+        if !no_std {
+            stdlib::link(&mut program);
+        }
+        let errors: Vec<String> = attributes::check_program(&program)
+            .iter()
+            .filter(|d| matches!(d.level, DiagnosticLevel::Error))
+            .map(|d| d.coded_message())
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        conditional_compilation::prune_program(&mut program, &CfgContext::new("her_vm", &[]));
+        MacroExpander::expand(&mut program)
+            .map_err(|errors| errors.iter().map(|e| messages::tr("compile.macro_failed", &[e])).collect::<Vec<_>>())?;
+
+        Ok(Bundle {
+            name: name.to_string(),
+            compiler_version: artifact_metadata::COMPILER_VERSION.to_string(),
+            program: ast_printer::print_program(&program),
+        })
+    }
+
+    /// `key=value` 머리 줄, 빈 줄, 프로그램 순서입니다.
+    fn encode(&self) -> Vec<u8> {
+        format!(
+            "format={}\nname={}\ncompiler_version={}\n\n{}",
+            FORMAT_VERSION, self.name, self.compiler_version, self.program
+        )
+        .into_bytes()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Bundle, String> {
+        let text = std::str::from_utf8(bytes).map_err(|_| "번들 페이로드가 UTF-8 이 아닙니다.".to_string())?;
+        let (head, program) = text.split_once("\n\n").ok_or("번들 머리를 읽을 수 없습니다.")?;
+        let field = |key: &str| {
+            head.lines()
+                .find_map(|line| line.strip_prefix(key).and_then(|rest| rest.strip_prefix('=')))
+                .unwrap_or("")
+                .to_string()
+        };
+        if field("format") != FORMAT_VERSION {
+            return Err(format!("지원하지 않는 번들 형식 '{}' (이 실행기: {})", field("format"), FORMAT_VERSION));
+        }
+        Ok(Bundle { name: field("name"), compiler_version: field("compiler_version"), program: program.to_string() })
+    }
+}
+
+/// `runner` 뒤에 번들을 붙여 `output` 에 씁니다. 실행기가 이미 번들이면 그 페이로드는 떼어 냅니다.
+pub fn write(runner: &Path, bundle: &Bundle, output: &Path) -> Result<(), String> {
+    let mut bytes = fs::read(runner).map_err(|e| format!("실행기 '{}' 읽기 실패: {}", runner.display(), e))?;
+    if let Some(start) = payload_start(&bytes) {
+        bytes.truncate(start);
+    }
+    let payload = bundle.encode();
+    bytes.extend_from_slice(&payload);
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(MAGIC);
+    fs::write(output, bytes).map_err(|e| format!("'{}' 쓰기 실패: {}", output.display(), e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(output, fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("실행 권한 부여 실패: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 실행 파일에 묶인 번들을 읽습니다. 번들이 아니면 `None` 입니다.
+pub fn read(path: &Path) -> Result<Option<Bundle>, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let size = file.metadata().map_err(|e| e.to_string())?.len();
+    if size < TRAILER_LEN {
+        return Ok(None);
+    }
+    // 번들이 아닌 실행 파일을 통째로 읽지 않도록 꼬리표부터 봅니다.
+    let mut trailer = [0u8; TRAILER_LEN as usize];
+    file.seek(SeekFrom::End(-(TRAILER_LEN as i64))).map_err(|e| e.to_string())?;
+    file.read_exact(&mut trailer).map_err(|e| e.to_string())?;
+    let Some(len) = trailer_payload_len(&trailer, size) else {
+        return Ok(None);
+    };
+    let mut payload = vec![0u8; len as usize];
+    file.seek(SeekFrom::Start(size - TRAILER_LEN - len)).map_err(|e| e.to_string())?;
+    file.read_exact(&mut payload).map_err(|e| e.to_string())?;
+    Bundle::decode(&payload).map(Some)
+}
+
+/// 지금 실행 중인 바이너리에 묶인 번들. 읽을 수 없으면 평소처럼 컴파일러로 동작합니다.
+pub fn embedded() -> Option<Bundle> {
+    let exe = std::env::current_exe().ok()?;
+    read(&exe).ok().flatten()
+}
+
+/// 묶인 프로그램을 실행하고 프로세스 종료 코드를 돌려줍니다.
+pub fn run(bundle: &Bundle) -> i32 {
+    let mut engine = Engine::new();
+    match engine.run(&bundle.program) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+            engine.exit_code()
+        }
+        Err(e) => {
+            eprintln!("{}: {}", bundle.name, e);
+            exit_code::RUNTIME_ERROR
+        }
+    }
+}
+
+/// 파일 전체에서 페이로드가 시작하는 위치를 찾습니다.
+fn payload_start(bytes: &[u8]) -> Option<usize> {
+    let trailer_start = bytes.len().checked_sub(TRAILER_LEN as usize)?;
+    let len = trailer_payload_len(&bytes[trailer_start..], bytes.len() as u64)?;
+    Some(trailer_start - len as usize)
+}
+
+fn trailer_payload_len(trailer: &[u8], file_size: u64) -> Option<u64> {
+    if &trailer[8..16] != MAGIC {
+        return None;
+    }
+    let len = u64::from_le_bytes(trailer[..8].try_into().ok()?);
+    (len <= file_size - TRAILER_LEN).then_some(len)
+}
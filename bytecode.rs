@@ -0,0 +1,647 @@
+// bytecode.rs
+// 함수 본문용 스택 기반 바이트코드와 그 실행기(VM)입니다.
+//
+// 계층형 실행(tiering.rs)이 자주 호출되는 함수를 트리 순회 대신 이 VM 으로 실행합니다.
+// 명령어 열(Chunk)은 AST 와 달리 Rc 를 담지 않으므로 다른 스레드로 보낼 수 있습니다.
+//
+// VM 은 트리 순회 실행기와 같은 결과를 내야 합니다. 연산자는 operators.rs, 호출·내장 함수·eval 은
+// 런타임의 것을 그대로 쓰고, 스코프도 같은 Environment 사슬로 만들며, 실행 로그도 똑같이 남깁니다.
+// 그대로 옮길 수 없는 본문(yield, defer, 함수 리터럴, 매크로 정의, `let mut` 과 대입, for-in 과 범위)은 컴파일하지 않습니다.
+//
+// 프레임 안에서 선언된 지역 변수는 이름 대신 컴파일 시점에 정한 (스코프 깊이, 칸 번호) 로 읽고 씁니다
+// (resolver::SlotResolver). 전역이나 호출한 쪽의 바인딩처럼 프레임 밖의 이름만 실행 시점에 이름으로 찾습니다.
+
+use core::cell::RefCell;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+use crate::compat::*;
+use crate::data_structures::{Expression, Span, Statement, TokenKind, TypeAnnotation, Value};
+use crate::error_codes;
+use crate::ft_runtime::{self, Environment, HighEnduranceRuntime};
+use crate::interner::Symbol;
+use crate::messages;
+use crate::operators;
+use crate::pretty;
+use crate::resolver::SlotResolver;
+
+/// 명령어 열에 담을 수 있는 상수 (스레드 사이로 보낼 수 있는 값만)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constant {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(Arc<str>),
+    Null,
+}
+
+impl Constant {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(i) => Some(Constant::Integer(*i)),
+            Value::Float(f) => Some(Constant::Float(*f)),
+            Value::Boolean(b) => Some(Constant::Boolean(*b)),
+            Value::String(s) => Some(Constant::String(s.clone())),
+            Value::Null => Some(Constant::Null),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            Constant::Integer(i) => Value::Integer(*i),
+            Constant::Float(f) => Value::Float(*f),
+            Constant::Boolean(b) => Value::Boolean(*b),
+            Constant::String(s) => Value::String(s.clone()),
+            Constant::Null => Value::Null,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Callee {
+    /// `f(x)`: 보이는 바인딩을 부르고, 없으면 내장 함수입니다.
+    Named(Symbol),
+    /// 프레임 안의 지역 변수에 담긴 함수
+    Slot { name: Symbol, depth: usize, slot: usize },
+    /// 매크로 확장을 거치지 않은 `name(x)` 호출
+    Macro(Symbol),
+    /// 인자들 아래에 놓인 값을 부릅니다.
+    Value,
+}
+
+#[derive(Debug, Clone)]
+pub enum Op {
+    Constant(usize),
+    /// 프레임 밖의 이름을 실행 시점에 찾습니다.
+    Load(Symbol),
+    /// `depth` 단계 바깥 스코프의 칸을 읽습니다.
+    LoadSlot { depth: usize, slot: usize, name: Symbol },
+    /// 값을 꺼내 현재 스코프에 이름으로 바인딩합니다.
+    Define(Symbol),
+    /// 값을 꺼내 현재 스코프의 칸에 바인딩합니다.
+    DefineSlot { slot: usize, name: Symbol },
+    Pop,
+    /// 식 문장의 값을 꺼내 실행 로그에 남깁니다.
+    PopExpression,
+    /// 주어진 배치(`Chunk::scopes`)로 새 스코프를 엽니다.
+    EnterScope(usize),
+    ExitScope,
+    Unary(TokenKind<'static>, Span),
+    Binary(TokenKind<'static>, Span),
+    /// `&&` / `||`: 맨 위 값으로 결과가 정해지면 그 값을 남긴 채 이동합니다.
+    ShortCircuit(TokenKind<'static>, usize),
+    Jump(usize),
+    /// 조건 값을 꺼내 `true` 가 아니면 이동합니다.
+    JumpUnlessTrue(usize),
+    /// 조건식(`c ? a : b`)의 조건이 bool 이 아니면 그 자리에 오류 값을 남기고 이동합니다.
+    CheckCondition(usize),
+    Call { callee: Callee, argc: usize, span: Span },
+    TypeOf,
+    Reflect,
+    Eval,
+    Return,
+}
+
+impl Op {
+    fn jump_target(&self) -> Option<usize> {
+        match self {
+            Op::Jump(target) | Op::JumpUnlessTrue(target) | Op::CheckCondition(target) | Op::ShortCircuit(_, target) => {
+                Some(*target)
+            }
+            _ => None,
+        }
+    }
+
+    fn jump_target_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Op::Jump(target) | Op::JumpUnlessTrue(target) | Op::CheckCondition(target) | Op::ShortCircuit(_, target) => {
+                Some(target)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 컴파일된 함수 본문
+#[derive(Debug, Clone, Default)]
+pub struct Chunk {
+    pub parameters: Vec<Symbol>,
+    pub constants: Vec<Constant>,
+    pub code: Vec<Op>,
+    /// 스코프 배치별 칸 이름. 0번은 매개변수가 놓이는 프레임 스코프입니다.
+    pub scopes: Vec<Arc<[Symbol]>>,
+}
+
+// ─── AST → 바이트코드 ─────────────────────────────
+
+/// 함수 본문을 명령어 열로 옮깁니다. 옮길 수 없는 구문이 있으면 그 이유를 돌려줍니다.
+pub fn compile(parameters: &[Symbol], body: &Statement) -> Result<Chunk, String> {
+    compile_with(parameters, body, true)
+}
+
+/// 지역 변수도 이름으로 찾는 명령어 열을 만듭니다. 칸 해석의 효과를 재는 기준선입니다.
+pub fn compile_by_name(parameters: &[Symbol], body: &Statement) -> Result<Chunk, String> {
+    compile_with(parameters, body, false)
+}
+
+fn compile_with(parameters: &[Symbol], body: &Statement, use_slots: bool) -> Result<Chunk, String> {
+    let mut compiler = Compiler { chunk: Chunk::default(), slots: SlotResolver::default(), use_slots };
+    compiler.chunk.parameters = parameters.to_vec();
+    compiler.slots.enter();
+    if use_slots {
+        for p in parameters {
+            compiler.slots.declare(*p);
+        }
+    }
+    // 본문 블록의 문장은 매개변수가 바인딩된 프레임 스코프에서 바로 실행됩니다.
+    compiler.statements(&block_statements(body))?;
+    let null = compiler.constant(Constant::Null);
+    compiler.emit(Op::Constant(null));
+    compiler.emit(Op::Return);
+
+    let mut chunk = compiler.chunk;
+    chunk.scopes = compiler.slots.into_layouts().into_iter().map(Arc::from).collect();
+    Ok(chunk)
+}
+
+struct Compiler {
+    chunk: Chunk,
+    slots: SlotResolver,
+    use_slots: bool,
+}
+
+impl Compiler {
+    fn emit(&mut self, op: Op) -> usize {
+        self.chunk.code.push(op);
+        self.chunk.code.len() - 1
+    }
+
+    fn here(&self) -> usize {
+        self.chunk.code.len()
+    }
+
+    /// 앞서 낸 점프 명령어의 목적지를 현재 위치로 정합니다.
+    fn patch(&mut self, at: usize) {
+        let here = self.here();
+        if let Some(target) = self.chunk.code[at].jump_target_mut() {
+            *target = here;
+        }
+    }
+
+    fn constant(&mut self, constant: Constant) -> usize {
+        if let Some(index) = self.chunk.constants.iter().position(|c| *c == constant) {
+            return index;
+        }
+        self.chunk.constants.push(constant);
+        self.chunk.constants.len() - 1
+    }
+
+    fn statements(&mut self, statements: &[Statement]) -> Result<(), String> {
+        statements.iter().try_for_each(|s| self.statement(s))
+    }
+
+    /// 새 스코프 안에서 문장을 실행합니다. (블록 문장은 그 안의 문장들을 한 스코프에서 실행)
+    fn scoped(&mut self, stmt: &Statement) -> Result<(), String> {
+        self.enter_scope();
+        self.statements(&block_statements(stmt))?;
+        self.exit_scope();
+        Ok(())
+    }
+
+    fn enter_scope(&mut self) {
+        let layout = self.slots.enter();
+        self.emit(Op::EnterScope(layout));
+    }
+
+    fn exit_scope(&mut self) {
+        self.slots.exit();
+        self.emit(Op::ExitScope);
+    }
+
+    fn resolve(&self, name: Symbol) -> Option<(usize, usize)> {
+        if self.use_slots {
+            self.slots.resolve(name)
+        } else {
+            None
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) -> Result<(), String> {
+        match stmt {
+            Statement::ExpressionStatement(expr) => {
+                self.expression(expr)?;
+                self.emit(Op::PopExpression);
+            }
+            Statement::DiscardStatement(_, expr) => {
+                self.expression(expr)?;
+                self.emit(Op::Pop);
+            }
+            // 칸은 대입할 수 있는지 기억하지 않으므로 바꿀 수 있는 바인딩은 인터프리터에 맡깁니다.
+            Statement::LetStatement { is_mutable: true, .. } => return Err("mutable binding".into()),
+            // 표기된 `let` 의 실행 시점 강제 변환은 인터프리터에만 있습니다. (HighEnduranceRuntime::coerce_binding)
+            Statement::LetStatement { type_annotation: Some(annotation), .. } if *annotation != TypeAnnotation::Any => {
+                return Err("type annotation".into())
+            }
+            Statement::LetStatement { name, value, .. } => {
+                self.expression(value)?;
+                if self.use_slots {
+                    let slot = self.slots.declare(*name);
+                    self.emit(Op::DefineSlot { slot, name: *name });
+                } else {
+                    self.emit(Op::Define(*name));
+                }
+            }
+            Statement::ReturnStatement(_, expr) => {
+                self.expression(expr)?;
+                self.emit(Op::Return);
+            }
+            Statement::BlockStatement { .. } => self.scoped(stmt)?,
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                self.expression(condition)?;
+                let to_else = self.emit(Op::JumpUnlessTrue(0));
+                self.scoped(then_branch)?;
+                let to_end = self.emit(Op::Jump(0));
+                self.patch(to_else);
+                if let Some(else_branch) = else_branch {
+                    self.scoped(else_branch)?;
+                }
+                self.patch(to_end);
+            }
+            Statement::WhileStatement { condition, body, .. } => {
+                let start = self.here();
+                self.expression(condition)?;
+                let to_end = self.emit(Op::JumpUnlessTrue(0));
+                self.scoped(body)?;
+                self.emit(Op::Jump(start));
+                self.patch(to_end);
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => {
+                self.enter_scope();
+                if let Some(init) = initializer {
+                    self.statement(init)?;
+                }
+                let start = self.here();
+                let to_end = match condition {
+                    Some(condition) => {
+                        self.expression(condition)?;
+                        Some(self.emit(Op::JumpUnlessTrue(0)))
+                    }
+                    None => None,
+                };
+                self.scoped(body)?;
+                if let Some(increment) = increment {
+                    self.expression(increment)?;
+                    self.emit(Op::Pop);
+                }
+                self.emit(Op::Jump(start));
+                if let Some(to_end) = to_end {
+                    self.patch(to_end);
+                }
+                self.exit_scope();
+            }
+            Statement::Attributed { statement, .. } => self.statement(statement)?,
+            Statement::YieldStatement(_, _) => return Err("yield".into()),
+            Statement::DeferStatement(_, _) => return Err("defer".into()),
+            Statement::MacroDefinition { .. } => return Err("macro definition".into()),
+            Statement::EnumDeclaration { .. } => return Err("enum".into()),
+            Statement::ForInStatement { .. } => return Err("for-in".into()),
+            // 타입 별칭은 실행 시점에 아무 일도 하지 않습니다.
+            Statement::TypeAlias { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self, expr: &Expression) -> Result<(), String> {
+        match expr {
+            Expression::Literal(_, value) => {
+                let constant = Constant::from_value(value).ok_or("non-constant literal")?;
+                let index = self.constant(constant);
+                self.emit(Op::Constant(index));
+            }
+            Expression::Identifier(_, name) => {
+                let op = match self.resolve(*name) {
+                    Some((depth, slot)) => Op::LoadSlot { depth, slot, name: *name },
+                    None => Op::Load(*name),
+                };
+                self.emit(op);
+            }
+            Expression::Grouped(_, inner) => self.expression(inner)?,
+            Expression::PrefixOperation(span, op, inner) => {
+                self.expression(inner)?;
+                self.emit(Op::Unary(op.clone(), *span));
+            }
+            Expression::InfixOperation(span, op, left, right) => {
+                self.expression(left)?;
+                let short_circuit = matches!(op, TokenKind::And | TokenKind::Or).then(|| self.emit(Op::ShortCircuit(op.clone(), 0)));
+                self.expression(right)?;
+                self.emit(Op::Binary(op.clone(), *span));
+                if let Some(at) = short_circuit {
+                    self.patch(at);
+                }
+            }
+            Expression::Ternary(_, condition, then_expr, else_expr) => {
+                self.expression(condition)?;
+                let checked = self.emit(Op::CheckCondition(0));
+                let to_else = self.emit(Op::JumpUnlessTrue(0));
+                self.expression(then_expr)?;
+                let to_end = self.emit(Op::Jump(0));
+                self.patch(to_else);
+                self.expression(else_expr)?;
+                self.patch(to_end);
+                self.patch(checked);
+            }
+            Expression::TypeOf(_, inner) => {
+                self.expression(inner)?;
+                self.emit(Op::TypeOf);
+            }
+            Expression::Reflect(_, inner) => {
+                self.expression(inner)?;
+                self.emit(Op::Reflect);
+            }
+            Expression::Eval(_, inner) => {
+                self.expression(inner)?;
+                self.emit(Op::Eval);
+            }
+            Expression::Call(span, function, args) => {
+                let callee = match function.as_ref() {
+                    Expression::Identifier(_, name) => match self.resolve(*name) {
+                        Some((depth, slot)) => Callee::Slot { name: *name, depth, slot },
+                        None => Callee::Named(*name),
+                    },
+                    other => {
+                        self.expression(other)?;
+                        Callee::Value
+                    }
+                };
+                for arg in args {
+                    self.expression(arg)?;
+                }
+                self.emit(Op::Call { callee, argc: args.len(), span: *span });
+            }
+            Expression::MacroCall(span, name, args) => {
+                for arg in args {
+                    self.expression(arg)?;
+                }
+                self.emit(Op::Call { callee: Callee::Macro(Symbol::intern(name)), argc: args.len(), span: *span });
+            }
+            Expression::Function(..) => return Err("function literal".into()),
+            Expression::ArrayLiteral(..) => return Err("array literal".into()),
+            Expression::MapLiteral(..) => return Err("map literal".into()),
+            Expression::Index(..) => return Err("index".into()),
+            Expression::Slice(..) => return Err("slice".into()),
+            Expression::Member(..) => return Err("member access".into()),
+            Expression::Assign(..) => return Err("assignment".into()),
+            Expression::Range(..) => return Err("range".into()),
+            Expression::MethodCall(..) => return Err("method call".into()),
+            Expression::Match(..) => return Err("match".into()),
+        }
+        Ok(())
+    }
+}
+
+fn block_statements(stmt: &Statement) -> Vec<Statement> {
+    match stmt {
+        Statement::BlockStatement { statements, .. } => statements.clone(),
+        other => vec![other.clone()],
+    }
+}
+
+// ─── 최적화와 검증 (백그라운드에서 실행) ─────────────────────────────
+
+/// 상수끼리의 연산을 미리 계산하고 명령어 열을 검증합니다.
+pub fn optimize(mut chunk: Chunk) -> Result<Chunk, String> {
+    while fold_constants(&mut chunk) {}
+    verify(&chunk)?;
+    Ok(chunk)
+}
+
+/// `Constant a; Constant b; Binary op` 와 `Constant a; Unary op` 를 한 번 접습니다. 바뀐 것이 있으면 true 입니다.
+fn fold_constants(chunk: &mut Chunk) -> bool {
+    let targets: Vec<usize> = chunk.code.iter().filter_map(Op::jump_target).collect();
+    // 중간으로 점프해 들어오는 명령어 묶음은 합칠 수 없습니다.
+    let foldable = |from: usize, to: usize| !targets.iter().any(|t| (from + 1..=to).contains(t));
+
+    for i in 0..chunk.code.len() {
+        let folded = match &chunk.code[i..] {
+            [Op::Constant(a), Op::Constant(b), Op::Binary(op, _), ..] if foldable(i, i + 2) => {
+                let (left, right) = (chunk.constants[*a].to_value(), chunk.constants[*b].to_value());
+                operators::binary(op, &left, &right).ok().map(|v| (v, 3))
+            }
+            [Op::Constant(a), Op::Unary(op, _), ..] if foldable(i, i + 1) => {
+                operators::unary(op, &chunk.constants[*a].to_value()).ok().map(|v| (v, 2))
+            }
+            _ => None,
+        };
+        let Some((value, length)) = folded else {
+            continue;
+        };
+        let Some(constant) = Constant::from_value(&value) else {
+            continue;
+        };
+        let index = match chunk.constants.iter().position(|c| *c == constant) {
+            Some(index) => index,
+            None => {
+                chunk.constants.push(constant);
+                chunk.constants.len() - 1
+            }
+        };
+        chunk.code[i] = Op::Constant(index);
+        let mut keep = vec![true; chunk.code.len()];
+        for removed in &mut keep[i + 1..i + length] {
+            *removed = false;
+        }
+        compact(chunk, &keep);
+        return true;
+    }
+    false
+}
+
+/// `keep` 이 false 인 명령어를 지우고 점프 목적지를 새 위치로 옮깁니다.
+fn compact(chunk: &mut Chunk, keep: &[bool]) {
+    let mut new_index = Vec::with_capacity(keep.len() + 1);
+    let mut next = 0;
+    for &kept in keep {
+        new_index.push(next);
+        if kept {
+            next += 1;
+        }
+    }
+    new_index.push(next);
+
+    let code = core::mem::take(&mut chunk.code);
+    chunk.code = code.into_iter().zip(keep).filter(|(_, &kept)| kept).map(|(op, _)| op).collect();
+    for op in &mut chunk.code {
+        if let Some(target) = op.jump_target_mut() {
+            *target = new_index[*target];
+        }
+    }
+}
+
+/// 점프 목적지, 상수 번호, 스코프 배치 번호가 범위 안에 있는지, 명령어 열이 Return 으로 끝나는지 확인합니다.
+pub fn verify(chunk: &Chunk) -> Result<(), String> {
+    for (at, op) in chunk.code.iter().enumerate() {
+        if let Some(target) = op.jump_target() {
+            if target > chunk.code.len() {
+                return Err(format!("{}: jump target {} out of range", at, target));
+            }
+        }
+        match op {
+            Op::Constant(index) if *index >= chunk.constants.len() => {
+                return Err(format!("{}: constant {} out of range", at, index));
+            }
+            Op::EnterScope(layout) if *layout >= chunk.scopes.len() => {
+                return Err(format!("{}: scope layout {} out of range", at, layout));
+            }
+            _ => {}
+        }
+    }
+    match chunk.code.last() {
+        Some(Op::Return) => Ok(()),
+        _ => Err("chunk does not end with return".into()),
+    }
+}
+
+// ─── 실행 ─────────────────────────────
+
+/// 현재 환경을 바깥으로 하는 프레임에 인자를 바인딩하고 명령어 열을 실행하여 반환값을 돌려줍니다.
+pub fn execute(rt: &mut HighEnduranceRuntime, chunk: &Chunk, args: Vec<Value>) -> Value {
+    let mut frame = Environment::with_slots(rt.environment.clone(), chunk.scopes[0].clone());
+    for (param, arg) in chunk.parameters.iter().zip(args) {
+        match chunk.scopes[0].iter().position(|n| n == param) {
+            Some(slot) => frame.slots[slot] = Some(arg),
+            None => frame.set(*param, arg),
+        }
+    }
+    let saved = core::mem::replace(&mut rt.environment, Rc::new(RefCell::new(frame)));
+    let result = run(rt, chunk);
+    rt.environment = saved;
+    result
+}
+
+fn run(rt: &mut HighEnduranceRuntime, chunk: &Chunk) -> Value {
+    let mut stack: Vec<Value> = vec![];
+    let mut pc = 0;
+
+    while let Some(op) = chunk.code.get(pc) {
+        pc += 1;
+        match op {
+            Op::Constant(index) => stack.push(chunk.constants[*index].to_value()),
+            Op::Load(name) => stack.push(rt.lookup(*name)),
+            Op::LoadSlot { depth, slot, name } => {
+                let value = rt.environment.borrow().get_slot(*depth, *slot);
+                stack.push(value.unwrap_or_else(|| rt.lookup(*name)));
+            }
+            Op::Define(name) => {
+                let value = pop(&mut stack);
+                rt.environment.borrow_mut().set(*name, value);
+                rt.emit(format!("Variable '{}' bound", name));
+            }
+            Op::DefineSlot { slot, name } => {
+                let value = pop(&mut stack);
+                rt.environment.borrow_mut().slots[*slot] = Some(value);
+                rt.emit(format!("Variable '{}' bound", name));
+            }
+            Op::Pop => {
+                pop(&mut stack);
+            }
+            Op::PopExpression => {
+                let value = pop(&mut stack);
+                rt.emit(format!("Expression result: {}", pretty::pretty(&value)));
+            }
+            Op::EnterScope(layout) => {
+                let enclosed = Environment::with_slots(rt.environment.clone(), chunk.scopes[*layout].clone());
+                rt.environment = Rc::new(RefCell::new(enclosed));
+            }
+            Op::ExitScope => {
+                let outer = rt.environment.borrow().outer.clone();
+                if let Some(outer) = outer {
+                    rt.environment = outer;
+                }
+            }
+            Op::Unary(op, span) => {
+                let operand = pop(&mut stack);
+                let value = rt.apply_unary(op, operand, *span);
+                stack.push(value);
+            }
+            Op::Binary(op, span) => {
+                let right = pop(&mut stack);
+                let left = pop(&mut stack);
+                let value = rt.apply_binary(op, left, right, *span);
+                stack.push(value);
+            }
+            Op::ShortCircuit(op, target) => {
+                if let Some(value) = stack.last().and_then(|left| operators::short_circuit(op, left)) {
+                    *stack.last_mut().expect("값이 있음") = value;
+                    pc = *target;
+                }
+            }
+            Op::Jump(target) => {
+                // 뒤로 가는 점프는 반복문입니다. 반복마다 취소를 확인합니다.
+                if *target < pc && rt.check_cancelled() {
+                    return Value::Null;
+                }
+                pc = *target;
+            }
+            Op::JumpUnlessTrue(target) => {
+                if !matches!(pop(&mut stack), Value::Boolean(true)) {
+                    pc = *target;
+                }
+            }
+            Op::CheckCondition(target) => {
+                let condition = stack.last_mut().expect("조건 값이 있음");
+                if !matches!(condition, Value::Boolean(_)) {
+                    if !matches!(condition, Value::Error(_)) {
+                        *condition = error_codes::TYPE_MISMATCH.error_value("condition", &[&ft_runtime::type_name(condition)]);
+                    }
+                    pc = *target;
+                }
+            }
+            Op::Call { callee, argc, span } => {
+                let args = stack.split_off(stack.len().saturating_sub(*argc));
+                let value = match callee {
+                    Callee::Named(name) => {
+                        let bound = rt.environment.borrow().get(*name);
+                        rt.call_resolved(name.as_str(), *span, bound, args, false)
+                    }
+                    Callee::Slot { name, depth, slot } => {
+                        let bound = rt.environment.borrow().get_slot(*depth, *slot);
+                        rt.call_resolved(name.as_str(), *span, bound, args, false)
+                    }
+                    Callee::Macro(name) => {
+                        let bound = rt.environment.borrow().get(*name);
+                        rt.call_resolved(name.as_str(), *span, bound, args, true)
+                    }
+                    Callee::Value => {
+                        let function = pop(&mut stack);
+                        rt.call_resolved(&messages::tr("trace.anonymous", &[]), *span, Some(function), args, false)
+                    }
+                };
+                stack.push(value);
+            }
+            Op::TypeOf => {
+                let value = pop(&mut stack);
+                stack.push(ft_runtime::type_of(&value));
+            }
+            Op::Reflect => {
+                let value = pop(&mut stack);
+                stack.push(ft_runtime::reflect(&value));
+            }
+            Op::Eval => {
+                let value = pop(&mut stack);
+                stack.push(ft_runtime::eval_value(value));
+            }
+            Op::Return => return pop(&mut stack),
+        }
+
+        // 인터프리터와 마찬가지로 패닉이 나면 남은 본문을 실행하지 않습니다.
+        if rt.panic.is_some() {
+            return Value::Null;
+        }
+    }
+    Value::Null
+}
+
+fn pop(stack: &mut Vec<Value>) -> Value {
+    stack.pop().unwrap_or(Value::Null)
+}
@@ -0,0 +1,91 @@
+// bytes.rs
+// 바이너리 데이터용 바이트 버퍼(`Value::Bytes`)의 연산입니다.
+//
+// 버퍼는 `Arc<[u8]>` 한 덩어리로 저장되므로 값을 복사해도 내용을 복사하지 않고, 정수 배열보다 8배 작습니다.
+// 버퍼는 바뀌지 않는 값입니다. 자르기(`b[1:3]`)와 이어 붙이기(`a + b`)는 새 버퍼를 만듭니다.
+//
+//   b"GIF89a\x00\xff"       리터럴. `\xHH`, `\n`, `\t`, `\r`, `\0`, `\\`, `\"` 이스케이프, 그 밖의 문자는 UTF-8 바이트
+//   b[i]                    i 번째 바이트 (0-255 정수)
+//   b[start:end]            [start, end) 범위의 새 버퍼. 양쪽 모두 생략할 수 있습니다.
+//   len(b)  to_hex(b)  from_hex("ff00")  to_utf8("글자")  from_utf8(b)  read_file_bytes("a.bin")
+
+use crate::compat::*;
+use crate::data_structures::Value;
+
+/// 인덱싱/자르기가 실패한 이유
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessError {
+    /// 인덱싱할 수 없는 값이거나 인덱스가 정수가 아님
+    TypeMismatch,
+    IndexOutOfBounds { index: i64, len: usize },
+    SliceOutOfBounds { start: i64, end: i64, len: usize },
+    /// 이름으로 찾는 키가 없음 (정규식 매치의 그룹 이름 등)
+    UnknownKey(String),
+}
+
+/// `target[index]`
+pub fn index(target: &Value, index: &Value) -> Result<Value, AccessError> {
+    let (Value::Bytes(bytes), Value::Integer(i)) = (target, index) else {
+        return Err(AccessError::TypeMismatch);
+    };
+    usize::try_from(*i)
+        .ok()
+        .and_then(|at| bytes.get(at))
+        .map(|byte| Value::Integer(*byte as i64))
+        .ok_or(AccessError::IndexOutOfBounds { index: *i, len: bytes.len() })
+}
+
+/// `target[start:end]`. 생략한 쪽은 처음이나 끝입니다.
+pub fn slice(target: &Value, start: Option<&Value>, end: Option<&Value>) -> Result<Value, AccessError> {
+    let Value::Bytes(bytes) = target else {
+        return Err(AccessError::TypeMismatch);
+    };
+    let bound = |value: Option<&Value>, default: usize| match value {
+        None => Ok(default as i64),
+        Some(Value::Integer(i)) => Ok(*i),
+        Some(_) => Err(AccessError::TypeMismatch),
+    };
+    let (start, end) = (bound(start, 0)?, bound(end, bytes.len())?);
+    if start < 0 || start > end || end > bytes.len() as i64 {
+        return Err(AccessError::SliceOutOfBounds { start, end, len: bytes.len() });
+    }
+    Ok(Value::Bytes(bytes[start as usize..end as usize].into()))
+}
+
+/// 두 자리씩 읽습니다. 공백은 건너뛰고, 대소문자를 가리지 않습니다.
+pub fn from_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("16진수 자릿수가 홀수입니다 ({}자리)", digits.len()));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let high = pair[0].to_digit(16).ok_or_else(|| format!("16진수가 아닌 문자 '{}'", pair[0]))?;
+            let low = pair[1].to_digit(16).ok_or_else(|| format!("16진수가 아닌 문자 '{}'", pair[1]))?;
+            Ok((high * 16 + low) as u8)
+        })
+        .collect()
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// `b"..."` 리터럴 안에 들어갈 표기. 출력할 수 있는 ASCII 는 그대로, 나머지는 이스케이프합니다.
+pub fn escape(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'\n' => text.push_str("\\n"),
+            b'\t' => text.push_str("\\t"),
+            b'\r' => text.push_str("\\r"),
+            b'\0' => text.push_str("\\0"),
+            b'\\' => text.push_str("\\\\"),
+            b'"' => text.push_str("\\\""),
+            0x20..=0x7e => text.push(byte as char),
+            other => text.push_str(&format!("\\x{:02x}", other)),
+        }
+    }
+    text
+}
@@ -0,0 +1,452 @@
+// call_graph.rs
+// 호출 그래프: 어떤 함수(매크로)가 어떤 함수를 부르는지 모으고, 강한 연결 요소(SCC)로 재귀를 찾습니다.
+//
+// 노드는 `let name = fn(...) {...}` 로 바인딩된 함수, `macro name(...)` 정의, 그리고 파일 최상위 코드입니다.
+// 호출 대상 이름은 호출 지점에서 보이는 바인딩으로 해석하므로, 가려진(shadowed) 이름은 다른 노드가 됩니다.
+// 최적화기의 인라이너는 순환에 속한 함수를 인라인하지 않으며, `high analyze --callgraph --dot` 이 이 그래프를 그립니다.
+
+use alloc::collections::BTreeSet;
+
+use crate::compat::*;
+use crate::data_structures::{Expression, Program, Span, Statement};
+use crate::patterns;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind {
+    TopLevel,
+    Function,
+    Macro,
+}
+
+#[derive(Debug, Clone)]
+pub struct CallNode {
+    pub name: String,
+    pub kind: NodeKind,
+    pub span: Span,
+    /// 정의 문장 전체 (`let name = fn ...`, `macro name ...`). 최상위 노드는 프로그램 범위입니다.
+    pub declaration: Span,
+}
+
+/// 호출 지점 하나. `span` 은 호출식에서 함수 이름이 적힌 자리입니다.
+#[derive(Debug, Clone)]
+pub struct CallSite {
+    pub caller: usize,
+    pub callee: usize,
+    pub span: Span,
+}
+
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    pub nodes: Vec<CallNode>,
+    /// `edges[i]` 는 노드 i 가 호출하는 노드 번호들입니다.
+    pub edges: Vec<BTreeSet<usize>>,
+    /// 소스 순서의 호출 지점 (간선마다 하나 이상)
+    pub calls: Vec<CallSite>,
+    /// `uses[i]` 는 노드 i 가 호출하지 않고 값으로만 참조하는 노드 번호들입니다. (`apply(f, 1)` 의 `f`)
+    pub uses: Vec<BTreeSet<usize>>,
+}
+
+/// 최상위 코드 노드의 번호
+pub const TOP_LEVEL: usize = 0;
+
+impl CallGraph {
+    /// 프로그램의 호출 그래프를 만듭니다. 이름 해석(및 @cfg 정리)이 끝난 AST 를 받습니다.
+    /// 최상위 문장의 정의는 다른 어떤 정의보다 먼저, 소스 순서대로 1번부터 번호를 받습니다.
+    pub fn build(program: &Program) -> Self {
+        let mut builder = Builder {
+            graph: CallGraph::default(),
+            scopes: vec![HashMap::new()],
+            current: TOP_LEVEL,
+            declared: HashMap::new(),
+        };
+        builder.add_node("<main>".into(), NodeKind::TopLevel, program.span, program.span);
+        builder.statements(&program.statements);
+        builder.graph
+    }
+
+    pub fn node_index(&self, name: &str) -> Option<usize> {
+        self.nodes.iter().position(|n| n.name == name)
+    }
+
+    /// Tarjan 알고리즘으로 강한 연결 요소를 구합니다. 각 요소 안의 노드는 번호 순입니다.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: vec![None; self.nodes.len()],
+            lowlink: vec![0; self.nodes.len()],
+            on_stack: vec![false; self.nodes.len()],
+            stack: vec![],
+            next_index: 0,
+            components: vec![],
+        };
+        for node in 0..self.nodes.len() {
+            if tarjan.index[node].is_none() {
+                tarjan.visit(node);
+            }
+        }
+        tarjan.components
+    }
+
+    /// 순환(직접 재귀 또는 상호 재귀)을 이루는 요소들입니다.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        self.strongly_connected_components()
+            .into_iter()
+            .filter(|component| component.len() > 1 || self.edges[component[0]].contains(&component[0]))
+            .collect()
+    }
+
+    /// 순환에 속한 함수 이름들
+    pub fn recursive_names(&self) -> BTreeSet<String> {
+        self.cycles().into_iter().flatten().map(|node| self.nodes[node].name.clone()).collect()
+    }
+
+    /// 사람이 읽는 요약입니다.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        for (node, callees) in self.nodes.iter().zip(&self.edges) {
+            let names: Vec<&str> = callees.iter().map(|&c| self.nodes[c].name.as_str()).collect();
+            out.push_str(&format!("{} ({:?}) -> [{}]\n", node.name, node.kind, names.join(", ")));
+        }
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            out.push_str("\nno recursion\n");
+        } else {
+            out.push_str("\nrecursion:\n");
+            for component in cycles {
+                let names: Vec<&str> = component.iter().map(|&c| self.nodes[c].name.as_str()).collect();
+                let kind = if component.len() == 1 { "direct" } else { "mutual" };
+                out.push_str(&format!("  {}: {}\n", kind, names.join(" <-> ")));
+            }
+        }
+        out
+    }
+
+    /// Graphviz DOT 형식. 순환에 속한 노드와 그 안의 간선은 빨간색으로 표시합니다.
+    pub fn to_dot(&self) -> String {
+        let mut component_of = vec![usize::MAX; self.nodes.len()];
+        let cycles = self.cycles();
+        for (id, component) in cycles.iter().enumerate() {
+            for &node in component {
+                component_of[node] = id;
+            }
+        }
+
+        let mut out = String::from("digraph callgraph {\n    node [shape=box, fontname=\"monospace\"];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let shape = match node.kind {
+                NodeKind::TopLevel => ", shape=doubleoctagon",
+                NodeKind::Function => "",
+                NodeKind::Macro => ", shape=hexagon",
+            };
+            let color = if component_of[i] != usize::MAX { ", color=red" } else { "" };
+            out.push_str(&format!("    n{} [label=\"{}\"{}{}];\n", i, escape(&node.name), shape, color));
+        }
+        for (from, callees) in self.edges.iter().enumerate() {
+            for &to in callees {
+                let in_cycle = component_of[from] != usize::MAX && component_of[from] == component_of[to];
+                let color = if in_cycle { " [color=red]" } else { "" };
+                out.push_str(&format!("    n{} -> n{}{};\n", from, to, color));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// ─── 그래프 만들기 ─────────────────────────────
+
+struct Builder {
+    graph: CallGraph,
+    /// 이름 → 노드 번호. 함수가 아닌 바인딩은 None 으로 기존 함수 이름을 가립니다.
+    scopes: Vec<HashMap<String, Option<usize>>>,
+    current: usize,
+    /// 미리 선언된 정의 문장(주소) → 노드 번호
+    declared: HashMap<*const Statement, usize>,
+}
+
+impl Builder {
+    fn add_node(&mut self, name: String, kind: NodeKind, span: Span, declaration: Span) -> usize {
+        self.graph.nodes.push(CallNode { name, kind, span, declaration });
+        self.graph.edges.push(BTreeSet::new());
+        self.graph.uses.push(BTreeSet::new());
+        self.graph.nodes.len() - 1
+    }
+
+    fn bind(&mut self, name: &str, node: Option<usize>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), node);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name)).copied().flatten()
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    /// 함수 본문을 `node` 의 몸체로 방문합니다.
+    fn body(&mut self, node: usize, parameters: &[impl AsRef<str>], body: &Statement) {
+        let outer = core::mem::replace(&mut self.current, node);
+        self.scoped(|b| {
+            for p in parameters {
+                b.bind(p.as_ref(), None);
+            }
+            b.statement(body);
+        });
+        self.current = outer;
+    }
+
+    fn call(&mut self, name: &str, start: usize) {
+        if let Some(callee) = self.lookup(name) {
+            self.graph.edges[self.current].insert(callee);
+            let span = Span { start, end: start + name.chars().count() };
+            self.graph.calls.push(CallSite { caller: self.current, callee, span });
+        }
+    }
+
+    fn refer(&mut self, name: &str) {
+        if let Some(node) = self.lookup(name) {
+            self.graph.uses[self.current].insert(node);
+        }
+    }
+
+    /// 같은 블록의 함수는 실행 시점에 서로를 볼 수 있으므로, 상호 재귀를 찾기 위해 먼저 모두 선언합니다.
+    fn statements(&mut self, statements: &[Statement]) {
+        for stmt in statements {
+            self.declare(stmt);
+        }
+        for stmt in statements {
+            self.statement(stmt);
+        }
+    }
+
+    fn declare(&mut self, stmt: &Statement) -> Option<usize> {
+        let (name, kind, span) = match stmt {
+            Statement::LetStatement { name, value, .. } => match value.as_ref() {
+                Expression::Function(span, ..) => (name.to_string(), NodeKind::Function, *span),
+                _ => return None,
+            },
+            Statement::MacroDefinition { name, span, .. } => (name.clone(), NodeKind::Macro, *span),
+            Statement::Attributed { statement, .. } => return self.declare(statement),
+            _ => return None,
+        };
+        if let Some(&node) = self.declared.get(&(stmt as *const Statement)) {
+            return Some(node);
+        }
+        let node = self.add_node(name.clone(), kind, span, stmt.span());
+        self.bind(&name, Some(node));
+        self.declared.insert(stmt as *const Statement, node);
+        Some(node)
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::LetStatement { name, value, .. } => match value.as_ref() {
+                // 재귀 호출이 자신을 가리키도록 본문보다 이름을 먼저 바인딩합니다.
+                Expression::Function(_, parameters, body) => {
+                    if let Some(node) = self.declare(stmt) {
+                        self.bind(name.as_str(), Some(node));
+                        self.body(node, parameters, body);
+                    }
+                }
+                other => {
+                    self.expression(other);
+                    self.bind(name.as_str(), None);
+                }
+            },
+            Statement::MacroDefinition { name, parameters, body, .. } => {
+                if let Some(node) = self.declare(stmt) {
+                    self.bind(name, Some(node));
+                    self.body(node, parameters, body);
+                }
+            }
+            Statement::ExpressionStatement(expr)
+            | Statement::DiscardStatement(_, expr)
+            | Statement::ReturnStatement(_, expr)
+            | Statement::YieldStatement(_, expr) => self.expression(expr),
+            Statement::BlockStatement { statements, .. } => self.scoped(|b| b.statements(statements)),
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                self.expression(condition);
+                self.scoped(|b| b.statement(then_branch));
+                if let Some(else_branch) = else_branch {
+                    self.scoped(|b| b.statement(else_branch));
+                }
+            }
+            Statement::WhileStatement { condition, body, .. } => {
+                self.expression(condition);
+                self.scoped(|b| b.statement(body));
+            }
+            Statement::ForInStatement { variable, iterable, body, .. } => {
+                self.expression(iterable);
+                self.scoped(|b| {
+                    b.bind(variable.as_str(), None);
+                    b.statement(body);
+                });
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => self.scoped(|b| {
+                if let Some(init) = initializer {
+                    b.statement(init);
+                }
+                if let Some(cond) = condition {
+                    b.expression(cond);
+                }
+                if let Some(inc) = increment {
+                    b.expression(inc);
+                }
+                b.statement(body);
+            }),
+            Statement::DeferStatement(_, body) => self.scoped(|b| b.statement(body)),
+            Statement::Attributed { statement, .. } => self.statement(statement),
+            Statement::TypeAlias { .. } => {}
+            Statement::EnumDeclaration { variants, .. } => {
+                for variant in variants {
+                    self.bind(variant.name.as_str(), None);
+                }
+            }
+        }
+    }
+
+    fn expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Call(_, callee, args) => {
+                match callee.as_ref() {
+                    Expression::Identifier(span, name) => self.call(name.as_str(), span.start),
+                    other => self.expression(other),
+                }
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+            Expression::MacroCall(span, name, args) => {
+                self.call(name, span.start);
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+            // 바인딩되지 않은 함수 값은 그 본문의 호출을 둘러싼 함수의 것으로 봅니다.
+            Expression::Function(_, parameters, body) => self.scoped(|b| {
+                for p in parameters {
+                    b.bind(p.as_str(), None);
+                }
+                b.statement(body);
+            }),
+            Expression::PrefixOperation(_, _, inner)
+            | Expression::Grouped(_, inner)
+            | Expression::Reflect(_, inner)
+            | Expression::Eval(_, inner)
+            | Expression::TypeOf(_, inner) => self.expression(inner),
+            Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+                self.expression(left);
+                self.expression(right);
+            }
+            Expression::Ternary(_, cond, then_expr, else_expr) => {
+                self.expression(cond);
+                self.expression(then_expr);
+                self.expression(else_expr);
+            }
+            Expression::ArrayLiteral(_, elements) => {
+                for element in elements {
+                    self.expression(element);
+                }
+            }
+            Expression::MapLiteral(_, entries) => {
+                for (key, value) in entries {
+                    self.expression(key);
+                    self.expression(value);
+                }
+            }
+            Expression::Index(_, target, index) => {
+                self.expression(target);
+                self.expression(index);
+            }
+            Expression::Member(_, target, _) => self.expression(target),
+            // 대입한 함수 값은 새 노드가 되지 않고 바인딩되지 않은 함수 값처럼 둘러싼 함수에 속합니다.
+            Expression::Assign(_, _, _, value) => self.expression(value),
+            Expression::MethodCall(span, target, name, args) => {
+                self.call(name.as_str(), span.start);
+                self.expression(target);
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+            Expression::Slice(_, target, start, end) => {
+                self.expression(target);
+                for bound in start.iter().chain(end) {
+                    self.expression(bound);
+                }
+            }
+            Expression::Match(_, subject, arms) => {
+                self.expression(subject);
+                for arm in arms {
+                    self.scoped(|b| {
+                        for name in patterns::bindings(&arm.pattern) {
+                            b.bind(name.as_str(), None);
+                        }
+                        if let Some(guard) = &arm.guard {
+                            b.expression(guard);
+                        }
+                        b.expression(&arm.body);
+                    });
+                }
+            }
+            Expression::Identifier(_, name) => self.refer(name.as_str()),
+            Expression::Literal(..) => {}
+        }
+    }
+}
+
+// ─── 강한 연결 요소 ─────────────────────────────
+
+struct Tarjan<'a> {
+    graph: &'a CallGraph,
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.lowlink[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &callee in &self.graph.edges[node] {
+            match self.index[callee] {
+                None => {
+                    self.visit(callee);
+                    self.lowlink[node] = self.lowlink[node].min(self.lowlink[callee]);
+                }
+                Some(index) if self.on_stack[callee] => {
+                    self.lowlink[node] = self.lowlink[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.lowlink[node]) == self.index[node] {
+            let mut component = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            self.components.push(component);
+        }
+    }
+}
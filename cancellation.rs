@@ -0,0 +1,272 @@
+// cancellation.rs
+// 오래 걸리는 컴파일과 실행을 중단하기 위한 취소 토큰입니다.
+//
+// 토큰의 복제본은 모두 같은 상태를 공유하므로, 한 곳(데몬의 cancel 요청, Ctrl-C 처리 등)에서
+// `cancel` 하면 파이프라인 전체가 봅니다. 동기 코드는 `is_cancelled` 를 주기적으로 확인하고,
+// 외부 도구는 `run_command` 로 실행해 취소되면 프로세스를 죽입니다.
+//
+// 비동기로 기다리는 `cancelled` 만 tokio 가 필요하므로 `tokio` 기능이 있을 때만 있습니다.
+// 토큰 자체는 no_std 코어(런타임의 실행 중단)에서도 쓰고, `run_command` 는 하위 프로세스를 만들 수 있는
+// `std` 빌드에서만 있습니다. (WASI 에는 프로세스가 없습니다)
+
+use alloc::sync::{Arc, Weak};
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+use std::io::{self, BufRead, BufReader, Read, Write};
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+use std::sync::mpsc;
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+use std::process::{Child, Command, Output, Stdio};
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+use std::thread;
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+use std::time::Duration;
+
+#[cfg(feature = "tokio")]
+use tokio::sync::Notify;
+
+use crate::compat::*;
+
+/// 자식 프로세스가 끝났는지, 취소되었는지 확인하는 간격
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    #[cfg(feature = "tokio")]
+    notify: Notify,
+    /// `child_token` 으로 만든 토큰. 이 토큰이 취소되면 함께 취소합니다.
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl Inner {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        #[cfg(feature = "tokio")]
+        self.notify.notify_waiters();
+        let children = core::mem::take(&mut *lock(&self.children));
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 취소를 알립니다. 여러 번 불러도 됩니다.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 이 토큰이 취소되면 함께 취소되지만, 따로 취소해도 부모에는 영향이 없는 토큰을 만듭니다.
+    /// 단계별 제한 시간처럼 파이프라인의 일부만 멈출 때 씁니다.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        let mut children = lock(&self.inner.children);
+        // 잠금을 쥔 뒤에 확인해야 `cancel` 과 엇갈려 자식을 놓치지 않습니다.
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            children.retain(|c| c.strong_count() > 0);
+            children.push(Arc::downgrade(&child.inner));
+        }
+        child
+    }
+
+    /// 취소될 때까지 기다립니다. 이미 취소되었으면 바로 돌아옵니다.
+    #[cfg(feature = "tokio")]
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// `run_command` 실패 이유
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+#[derive(Debug)]
+pub enum CommandError {
+    /// 토큰이 취소되어 프로세스를 죽였습니다.
+    Cancelled,
+    Io(io::Error),
+}
+
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+impl core::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CommandError::Cancelled => write!(f, "취소됨"),
+            CommandError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// 명령을 실행하고 끝날 때까지 기다립니다. `stdin` 이 있으면 표준 입력으로 넘깁니다.
+///
+/// 표준 출력과 오류는 모아서 돌려줍니다. 기다리는 동안 토큰이 취소되면 프로세스를 죽입니다.
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+pub fn run_command(command: &mut Command, stdin: Option<&[u8]>, token: &CancellationToken) -> Result<Output, CommandError> {
+    if token.is_cancelled() {
+        return Err(CommandError::Cancelled);
+    }
+    let mut child = command
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(CommandError::Io)?;
+
+    // 파이프가 가득 차 자식이 멈추지 않도록 입출력은 별도 스레드에서 처리합니다.
+    let writer = match (stdin, child.stdin.take()) {
+        (Some(input), Some(mut pipe)) => {
+            let input = input.to_vec();
+            Some(thread::spawn(move || pipe.write_all(&input)))
+        }
+        _ => None,
+    };
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = loop {
+        if token.is_cancelled() {
+            kill(&mut child);
+            return Err(CommandError::Cancelled);
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                kill(&mut child);
+                return Err(CommandError::Io(e));
+            }
+        }
+    };
+
+    if let Some(writer) = writer {
+        // 자식이 입력을 다 읽지 않고 끝나면 쓰기가 실패할 수 있으며, 그 결과는 종료 상태가 알려줍니다.
+        let _ = writer.join();
+    }
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// `run_command` 와 같지만 표준 출력을 줄 단위로 읽어 생기는 대로 `on_line` 에 넘깁니다. (줄바꿈 제외)
+/// 돌려주는 `Output::stdout` 에도 같은 내용이 모두 들어갑니다.
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+pub fn run_command_lines(
+    command: &mut Command,
+    stdin: Option<&[u8]>,
+    token: &CancellationToken,
+    on_line: &mut dyn FnMut(&str),
+) -> Result<Output, CommandError> {
+    if token.is_cancelled() {
+        return Err(CommandError::Cancelled);
+    }
+    let mut child = command
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(CommandError::Io)?;
+
+    let writer = match (stdin, child.stdin.take()) {
+        (Some(input), Some(mut pipe)) => {
+            let input = input.to_vec();
+            Some(thread::spawn(move || pipe.write_all(&input)))
+        }
+        _ => None,
+    };
+    let (sender, lines) = mpsc::channel::<String>();
+    let reader = child.stdout.take().map(|pipe| {
+        thread::spawn(move || {
+            for line in BufReader::new(pipe).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        })
+    });
+    let stderr = drain(child.stderr.take());
+
+    let mut stdout = vec![];
+    let mut forward = |line: String, stdout: &mut Vec<u8>| {
+        on_line(&line);
+        stdout.extend_from_slice(line.as_bytes());
+        stdout.push(b'\n');
+    };
+    let status = loop {
+        if token.is_cancelled() {
+            kill(&mut child);
+            return Err(CommandError::Cancelled);
+        }
+        match lines.recv_timeout(POLL_INTERVAL) {
+            Ok(line) => {
+                forward(line, &mut stdout);
+                continue;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            // 자식이 표준 출력을 닫았습니다. 끝날 때까지 기다리기만 합니다.
+            Err(mpsc::RecvTimeoutError::Disconnected) => thread::sleep(POLL_INTERVAL),
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(e) => {
+                kill(&mut child);
+                return Err(CommandError::Io(e));
+            }
+        }
+    };
+
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    // 끝난 뒤 파이프에 남은 줄까지 넘깁니다.
+    if let Some(reader) = reader {
+        let _ = reader.join();
+    }
+    for line in lines.try_iter() {
+        forward(line, &mut stdout);
+    }
+    Ok(Output { status, stdout, stderr: stderr.join().unwrap_or_default() })
+}
+
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = vec![];
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
@@ -0,0 +1,35 @@
+// compat.rs
+// `std` 기능 없이(alloc 만으로) 빌드할 때 코어 모듈이 쓰는 std 대체물입니다.
+//
+// 코어 모듈(렉서, 파서, 분석 패스, 런타임)은 `use crate::compat::*;` 로 alloc 타입과 매크로를 가져옵니다.
+// std 빌드에서는 prelude 에 이미 있는 같은 타입을 다시 가져오는 것일 뿐입니다.
+// 해시 표는 두 빌드 모두 hashbrown 을 쓰고, 전역 표(인터너, 연산자 표)의 잠금은 std 가 없으면 spin 입니다.
+
+pub use alloc::borrow::ToOwned;
+pub use alloc::boxed::Box;
+pub use alloc::string::{String, ToString};
+pub use alloc::vec::Vec;
+pub use alloc::{format, vec};
+pub use hashbrown::HashMap;
+
+#[cfg(feature = "std")]
+pub use std::sync::{Mutex, MutexGuard, OnceLock};
+
+#[cfg(not(feature = "std"))]
+pub use spin::{Mutex, MutexGuard, Once as OnceLock};
+
+/// 잠금을 얻습니다. std 잠금이 오염(poison)되었어도 안의 값을 그대로 씁니다.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    #[cfg(feature = "std")]
+    return mutex.lock().unwrap_or_else(|e| e.into_inner());
+    #[cfg(not(feature = "std"))]
+    return mutex.lock();
+}
+
+/// 한 번만 초기화되는 전역 값
+pub fn get_or_init<T>(cell: &OnceLock<T>, init: impl FnOnce() -> T) -> &T {
+    #[cfg(feature = "std")]
+    return cell.get_or_init(init);
+    #[cfg(not(feature = "std"))]
+    return cell.call_once(init);
+}
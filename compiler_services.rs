@@ -1,196 +1,985 @@
-use tokio::time::Instant;
-use crate::analyzer_service::{AnalyzerService, AnalysisResult};
-use crate::executor_service::{ExecutorService, ExecutionRequest, ExecutionResult, ExecutionStatus};
-use crate::blockchain::Blockchain;
-use crate::lexer_service::LexerService;
-use crate::parser_service::ParserService;
-use crate::optimizer::Optimizer;
-use crate::data_structures::{Program, Statement};
-use crate::ir_generator::generate_ir;
-use crate::native_codegen::{generate_native_binary, assemble_and_link};
-
-pub struct CompilerService {
-    analyzer: AnalyzerService,
-    executor: ExecutorService,
-    blockchain: Blockchain,
-}
-
-impl CompilerService {
-    pub fn new() -> Self {
-        Self {
-            analyzer: AnalyzerService::new(),
-            executor: ExecutorService::new(),
-            blockchain: Blockchain::new(),
-        }
-    }
-
-    pub async fn compile(&mut self, request: CompileRequest) -> CompileResult {
-        let start_time = Instant::now();
-        let mut errors = vec![];
-        let mut success = true;
-
-        let analysis_report = self.run_analysis(&request.source_code, &mut errors, &mut success).await;
-        let mut program = self.run_parsing(&request.source_code, &mut errors, &mut success);
-
-        if request.options.optimization_level > 0 {
-            Optimizer::optimize(&mut program);
-        }
-
-        if !ends_with_return(&program) {
-            success = false;
-            errors.push("컴파일 실패: 실행 흐름이 균형을 이루지 않음 (return 누락 또는 위치 오류).".into());
-        }
-
-        let mut compiled_output = String::new();
-        if success && request.options.emit_native {
-            let ir = generate_ir(&program);
-            let asm_path = "compiled.asm";
-
-            #[cfg(target_os = "windows")]
-            let bin_path = "compiled.exe";
-
-            #[cfg(not(target_os = "windows"))]
-            let bin_path = "compiled.out";
-
-            match generate_native_binary(&ir, asm_path) {
-                Ok(_) => match assemble_and_link(asm_path, bin_path) {
-                    Ok(_) => {
-                        compiled_output = format!("네이티브 실행 파일 생성 완료: {}", bin_path);
-                    }
-                    Err(e) => {
-                        success = false;
-                        errors.push(format!("링커 실패: {}", e));
-                    }
-                },
-                Err(e) => {
-                    success = false;
-                    errors.push(format!("어셈블리 생성 실패: {}", e));
-                }
-            }
-        }
-
-        let execution_result = if success {
-            let exec_request = ExecutionRequest {
-                compiled_code_reference: compiled_output.clone(),
-                input_data: if analysis_report.detected_sentiment == "Positive" {
-                    Some("Success Data".into())
-                } else {
-                    None
-                },
-            };
-
-            let result = self.executor.execute_code(exec_request).await;
-
-            if matches!(result.status, ExecutionStatus::RuntimeError) {
-                success = false;
-                errors.push("실행 중 에러 발생: 런타임 오류".into());
-            }
-
-            result
-        } else {
-            ExecutionResult {
-                output_log: vec!["[Executor] 실행되지 않음: 컴파일 에러.".into()],
-                status: ExecutionStatus::Skipped,
-                execution_time_ms: 0,
-            }
-        };
-
-        let proof_hash = format!(
-            "POCI_{}_{}_{:?}",
-            request.source_code.len(),
-            request.options.target_platform,
-            execution_result.status
-        );
-        let new_block = self.blockchain.add_block(proof_hash);
-        let total_time_ms = start_time.elapsed().as_millis();
-
-        CompileResult {
-            success,
-            compiled_output,
-            analysis_report,
-            execution_log: execution_result.output_log,
-            execution_status: execution_result.status,
-            proof_block_index: new_block.index,
-            errors,
-            total_time_ms,
-        }
-    }
-
-    async fn run_analysis(&self, source: &str, errors: &mut Vec<String>, success: &mut bool) -> AnalysisResult {
-        match self.analyzer.analyze_text(source).await {
-            Ok(report) => report,
-            Err(e) => {
-                errors.push(format!("분석 실패: {}", e));
-                *success = false;
-                AnalysisResult {
-                    word_count: 0,
-                    readability_score: 0.0,
-                    detected_sentiment: "Error".into(),
-                    processing_time_ms: 0,
-                }
-            }
-        }
-    }
-
-    fn run_parsing(&self, source: &str, errors: &mut Vec<String>, success: &mut bool) -> Program {
-        let lexer = LexerService::new(source);
-        let mut parser = ParserService::new(lexer);
-        parser.parse_program()
-    }
-}
-
-// ─── 실행 흐름 검사 ─────────────────────────────
-
-fn ends_with_return(program: &Program) -> bool {
-    if let Some(last_stmt) = program.statements.last() {
-        is_terminal(last_stmt)
-    } else {
-        false
-    }
-}
-
-fn is_terminal(stmt: &Box<Statement>) -> bool {
-    match stmt.as_ref() {
-        Statement::ReturnStatement(_) => true,
-        Statement::BlockStatement { statements, .. } => {
-            if let Some(inner_last) = statements.last() {
-                is_terminal(inner_last)
-            } else {
-                false
-            }
-        }
-        Statement::IfStatement { then_branch, else_branch, .. } => {
-            let then_term = is_terminal(then_branch);
-            let else_term = else_branch.as_ref().map_or(false, is_terminal);
-            then_term && else_term
-        }
-        _ => false,
-    }
-}
-
-// ─── 요청 및 결과 구조체 ─────────────────────────────
-
-#[derive(Debug)]
-pub struct CompileRequest {
-    pub source_code: String,
-    pub options: CompileOptions,
-}
-
-#[derive(Debug)]
-pub struct CompileOptions {
-    pub target_platform: String,
-    pub optimization_level: u8,
-    pub emit_native: bool,
-}
-
-#[derive(Debug)]
-pub struct CompileResult {
-    pub success: bool,
-    pub compiled_output: String,
-    pub analysis_report: AnalysisResult,
-    pub execution_log: Vec<String>,
-    pub execution_status: ExecutionStatus,
-    pub proof_block_index: u32,
-    pub errors: Vec<String>,
-    pub total_time_ms: u128,
-}
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+use std::fs;
+use std::future::{self, Future};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+use crate::analyzer_service::{Analyzer, AnalyzerService, AnalysisResult, DEFAULT_ANALYZER, DEFAULT_ANALYZER_TIMEOUT_MS};
+use crate::executor_service::{ExecutorService, ExecutionRequest, ExecutionResult, ExecutionStatus};
+use crate::blockchain::Blockchain;
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+use crate::optimizer::{MathMode, Optimizer, OptimizerSettings};
+use crate::macro_expander::MacroExpander;
+use crate::conditional_compilation::{self, CfgContext};
+use crate::ast_printer;
+use crate::stdlib;
+use crate::reproducible::{self, Artifacts};
+use crate::ft_runtime::Capabilities;
+use crate::cancellation::CancellationToken;
+use crate::stage_timeouts::{Stage, StageError, StageTimeouts, StageWatch};
+use crate::data_structures::{Diagnostic, DiagnosticLevel, Program, Value};
+use crate::attributes;
+use crate::error_codes;
+use crate::exit_code;
+use crate::messages;
+use crate::resolver::Resolver;
+use crate::dead_code;
+use crate::division_check;
+use crate::unused_value;
+use crate::named_types;
+use crate::hir;
+use crate::ir_generator::generate_ir;
+use crate::ir_simplify;
+use crate::profile::Profile;
+use crate::rust_emitter_service::RustEmitterService;
+use crate::py_emitter_service::{self, PyEmitterService};
+use crate::escape_analysis::{self, AllocationStats};
+use crate::artifact_metadata;
+use crate::llvm_codegen::{self, generate_llvm_ir};
+use crate::native_codegen::{generate_native_binary, CodegenConfig, TargetOs, BASELINE_CPU};
+use crate::toolchain::{self, ProcessRunner, ToolError, ToolPaths, ToolRunner, Toolchain};
+
+/// 컴파일 한 번이 어디까지 가는지
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pass {
+    /// 분석부터 링크, 실행, 증명 블록까지
+    Full,
+    /// `--reproducible` 확인용 두 번째 컴파일. 같은 단계를 거쳐 산출물을 쓰고 멈춥니다.
+    Rebuild,
+}
+
+/// `compile_batch` 가 한 번에 컴파일하는 요청 수
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+pub struct CompilerService {
+    analyzer: AnalyzerService,
+    executor: ExecutorService,
+    /// 동시에 컴파일하는 요청(`compile_batch`)이 함께 씁니다. 빌린 채로 await 하지 않습니다.
+    blockchain: RefCell<Blockchain>,
+    tool_runner: Arc<dyn ToolRunner>,
+    /// 네이티브 실행 파일은 경로가 하나이므로 `emit_native` 컴파일은 한 번에 하나씩 합니다.
+    native_build: Semaphore,
+}
+
+impl CompilerService {
+    pub fn new() -> Self {
+        Self {
+            analyzer: AnalyzerService::new(),
+            executor: ExecutorService::new(),
+            blockchain: RefCell::new(Blockchain::new()),
+            tool_runner: Arc::new(ProcessRunner),
+            native_build: Semaphore::new(1),
+        }
+    }
+
+    /// 외부 분석 백엔드를 등록합니다. `CompileOptions::analyzer` 에 이름을 주면 선택됩니다.
+    pub fn register_analyzer(&mut self, analyzer: Box<dyn Analyzer>) {
+        self.analyzer.register(analyzer);
+    }
+
+    /// NASM/링커 실행기를 바꿉니다. 도구가 없는 환경에서는 `toolchain::MockRunner` 를 넘깁니다.
+    pub fn set_tool_runner(&mut self, runner: Arc<dyn ToolRunner>) {
+        self.tool_runner = runner;
+    }
+
+    pub async fn compile(&self, request: CompileRequest) -> CompileResult {
+        self.compile_cancellable(request, &CancellationToken::new()).await
+    }
+
+    /// 여러 프로그램을 `DEFAULT_BATCH_CONCURRENCY` 개씩 동시에 컴파일합니다.
+    pub async fn compile_batch(&self, requests: Vec<CompileRequest>) -> BatchResult {
+        self.compile_batch_with(requests, DEFAULT_BATCH_CONCURRENCY, &CancellationToken::new()).await
+    }
+
+    /// 여러 프로그램을 최대 `concurrency` 개씩 동시에 컴파일하고, 요청 순서대로 결과와 집계를 돌려줍니다.
+    ///
+    /// 요청들은 이 서비스의 분석기, 도구 실행기, 증명 블록체인을 함께 씁니다. 증명 블록은 끝난 순서대로 붙습니다.
+    /// 동시 실행은 한 작업 안에서 번갈아 도는 방식이므로(인터프리터 값은 스레드를 옮길 수 없음), 분석기 응답,
+    /// 실행기 대기처럼 기다리는 구간이 겹칩니다. `token` 을 취소하면 남은 요청이 모두 `Cancelled` 로 끝납니다.
+    pub async fn compile_batch_with(&self, requests: Vec<CompileRequest>, concurrency: usize, token: &CancellationToken) -> BatchResult {
+        let start_time = Instant::now();
+        let slots = Semaphore::new(concurrency.max(1));
+        let mut pending: Vec<Option<Pin<Box<dyn Future<Output = CompileResult> + '_>>>> = requests
+            .into_iter()
+            .map(|request| {
+                let slots = &slots;
+                let compilation: Pin<Box<dyn Future<Output = CompileResult> + '_>> = Box::pin(async move {
+                    // 세마포어는 닫지 않으므로 얻기는 실패하지 않습니다.
+                    let _slot = slots.acquire().await.ok();
+                    self.compile_cancellable(request, token).await
+                });
+                Some(compilation)
+            })
+            .collect();
+        let mut results: Vec<Option<CompileResult>> = pending.iter().map(|_| None).collect();
+
+        future::poll_fn(|cx| {
+            for (compilation, result) in pending.iter_mut().zip(results.iter_mut()) {
+                if let Some(running) = compilation {
+                    if let Poll::Ready(done) = running.as_mut().poll(cx) {
+                        *result = Some(done);
+                        *compilation = None;
+                    }
+                }
+            }
+            if pending.iter().all(Option::is_none) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+
+        let results: Vec<CompileResult> = results.into_iter().flatten().collect();
+        let summary = BatchSummary::new(&results, start_time.elapsed().as_millis());
+        BatchResult { results, summary }
+    }
+
+    /// `token` 이 취소되면 다음 단계로 넘어가지 않고, 실행 중인 외부 도구와 인터프리터를 멈춥니다.
+    /// 취소된 결과는 `execution_status` 가 `Cancelled` 이고 새 증명 블록을 만들지 않습니다.
+    /// 단계가 `CompileOptions::timeouts` 를 넘기면 같은 방식으로 멈추고 상태는 `TimedOut` 입니다.
+    pub async fn compile_cancellable(&self, request: CompileRequest, token: &CancellationToken) -> CompileResult {
+        self.compile_pass(&request, token, Pass::Full, &mut Artifacts::default()).await
+    }
+
+    /// 컴파일 한 번. 파일로 쓴 산출물은 `artifacts` 에 모읍니다. `Pass::Rebuild` 는 산출물을 쓰고 나서 멈춥니다.
+    async fn compile_pass(&self, request: &CompileRequest, token: &CancellationToken, pass: Pass, artifacts: &mut Artifacts) -> CompileResult {
+        // 다시 컴파일할 때는 첫 컴파일이 이미 네이티브 빌드 자리를 잡고 있습니다.
+        let _native_build = match request.options.emit_native && pass == Pass::Full {
+            true => self.native_build.acquire().await.ok(),
+            false => None,
+        };
+        let start_time = Instant::now();
+        let timeouts = &request.options.timeouts;
+        let mut errors = vec![];
+        let mut success = true;
+
+        let watch = StageWatch::start(Stage::Analysis, timeouts, token);
+        let analysis = match pass {
+            // 분석 결과는 산출물에 들어가지 않으므로 외부 분석기에 다시 묻지 않습니다.
+            Pass::Rebuild => Some(empty_analysis(&request.options.analyzer, "Skipped")),
+            Pass::Full => tokio::select! {
+                report = self.run_analysis(&request.source_code, &request.options, &mut errors, &mut success) => Some(report),
+                _ = watch.token().cancelled() => None,
+            },
+        };
+        let analysis_report = match (analysis, watch.finish()) {
+            (Some(report), Ok(())) => report,
+            (report, finished) => {
+                let report = report.unwrap_or_else(|| empty_analysis(&request.options.analyzer, "Aborted"));
+                let reason = finished.err().unwrap_or(StageError::Cancelled);
+                return self.aborted_result(start_time, reason, errors, vec![], report, vec![]);
+            }
+        };
+
+        let watch = StageWatch::measure(Stage::Parsing, timeouts, token);
+        let (mut program, syntax_errors) = self.run_parsing(&request.source_code, &mut errors, &mut success);
+        // 표준 라이브러리는 사용자 코드 앞에 링크되어 이후 모든 단계를 함께 거칩니다.
+        let modules = if request.options.no_std { vec![] } else { stdlib::link(&mut program) };
+        if let Err(reason) = watch.finish() {
+            return self.aborted_result(start_time, reason, errors, syntax_errors, analysis_report, vec![]);
+        }
+
+        // 어트리뷰트 검증: 알 수 없는 어트리뷰트는 경고, 잘못된 인자는 오류입니다.
+        let mut diagnostics = syntax_errors;
+        diagnostics.extend(attributes::check_program(&program));
+        for diag in diagnostics.iter().filter(|d| matches!(d.level, DiagnosticLevel::Error)) {
+            success = false;
+            errors.push(diag.coded_message());
+        }
+
+        // 함수별 분석 경고 (긴 함수, 이름 품질)는 컴파일을 멈추지 않습니다.
+        diagnostics.extend(analysis_report.diagnostics());
+        if let Some(reason) = &analysis_report.degraded {
+            diagnostics.push(error_codes::ANALYZER_UNAVAILABLE.diagnostic(program.span, "", &[reason]));
+        }
+
+        // 조건부 컴파일: 비활성 @cfg 항목을 타입 검사 전에 제거합니다.
+        let mut cfg = CfgContext::new(&request.options.target_platform, &request.options.cfg_flags);
+        // 다른 OS 용으로 만들 때는 `@cfg(target_os = ...)` 도 그 OS 를 봅니다.
+        if let Some(os) = &request.options.target_os {
+            cfg.target_os = os.clone();
+        }
+        conditional_compilation::prune_program(&mut program, &cfg);
+
+        // 타입 표기: 선언되지 않은 타입 이름과 잘못된 `type` 별칭, `--strict` 의 암시적 any 는 오류입니다.
+        for diag in named_types::check_program(&program, &modules, request.options.strict) {
+            success = false;
+            errors.push(diag.coded_message());
+            diagnostics.push(diag);
+        }
+
+        // 이름 해석: @deprecated 심볼 참조 경고 등. 불변 바인딩에 대입(E0007)하는 것은 오류입니다.
+        for diag in Resolver::resolve_program(&program) {
+            if matches!(diag.level, DiagnosticLevel::Error | DiagnosticLevel::HerFatal) {
+                success = false;
+                errors.push(diag.coded_message());
+            }
+            diagnostics.push(diag);
+        }
+
+        // 도달성: 쓰이지 않는 함수 경고. `--strip-dead` 면 그 정의와 쓰이지 않는 모듈을 여기서 지웁니다.
+        let dead = dead_code::analyze(&program, &modules);
+        diagnostics.extend(dead.diagnostics(program.span, request.options.strip_dead));
+        if request.options.strip_dead {
+            dead.strip(&mut program);
+        }
+        diagnostics.extend(division_check::check_program(&program));
+        diagnostics.extend(unused_value::check_program(&program));
+
+        // 매크로 확장: 이후 단계는 MacroDefinition/MacroCall이 없는 AST만 다룹니다.
+        if let Err(macro_errors) = MacroExpander::expand(&mut program) {
+            success = false;
+            errors.extend(macro_errors.into_iter().map(|e| messages::tr("compile.macro_failed", &[&e])));
+        }
+        let expanded_source = if request.options.emit_expanded {
+            Some(ast_printer::print_program(&program))
+        } else {
+            None
+        };
+
+        // 다른 소스로 만든 프로필의 범위는 엉뚱한 문장을 가리키므로 쓰지 않습니다.
+        if request.options.profile_use.as_ref().is_some_and(|profile| !profile.matches(&request.source_code)) {
+            diagnostics.push(error_codes::STALE_PROFILE.diagnostic(program.span, "", &[]));
+        }
+        if request.options.optimization_level > 0 {
+            let watch = StageWatch::measure(Stage::Optimization, timeouts, token);
+            Optimizer::optimize(&mut program, request.options.optimizer_settings(&request.source_code));
+            if let Err(reason) = watch.finish() {
+                return self.aborted_result(start_time, reason, errors, diagnostics, analysis_report, vec![]);
+            }
+        }
+
+        if token.is_cancelled() {
+            return self.aborted_result(start_time, StageError::Cancelled, errors, diagnostics, analysis_report, vec![]);
+        }
+
+        if !program.ends_with_return() {
+            success = false;
+            errors.push(error_codes::MISSING_RETURN.tagged("", &[]));
+        }
+
+        // 탈출하지 않는 클로저는 네이티브 코드에서 스택에 놓입니다. (IR 생성기가 같은 분석을 씁니다)
+        // 코드 생성 시간에서 외부 도구 시간은 빼고 잽니다. (도구는 tools 단계로 따로 제한)
+        let codegen = StageWatch::measure(Stage::Codegen, timeouts, token);
+        let mut tools_time = Duration::ZERO;
+
+        let escapes = escape_analysis::analyze(&program);
+        let allocation_stats = escapes.stats();
+
+        let target_os = request.options.target_os.as_deref().map_or(Ok(TargetOs::host()), TargetOs::parse);
+        let codegen_config = match target_os.and_then(|os| {
+            CodegenConfig::new(&request.options.target_cpu, &request.options.target_features).map(|config| config.with_os(os))
+        }) {
+            Ok(config) => config,
+            Err(e) => {
+                success = false;
+                errors.push(error_codes::INVALID_TARGET.tagged("", &[&e]));
+                CodegenConfig::baseline()
+            }
+        };
+
+        let mut compiled_output = String::new();
+        let mut binary_path = None;
+        // 증명 해시는 메타데이터 섹션이 있는 네이티브 바이너리에만 새깁니다.
+        let mut native_binary = None;
+        let python_target = request.options.target_platform == py_emitter_service::TARGET;
+        let llvm_target = request.options.target_platform == llvm_codegen::TARGET;
+        let wants_hir = request.options.emit_native
+            || request.options.rust_project.is_some()
+            || request.options.emit_rust_source
+            || request.options.emit_ir
+            || python_target
+            || llvm_target;
+        let hir = if success && wants_hir {
+            match hir::lower(&program) {
+                Ok(mut hir) => {
+                    if let Some(profile) = request.options.profile_for(&request.source_code) {
+                        ir_simplify::lay_out_branches(&mut hir, profile);
+                    }
+                    Some(hir)
+                }
+                Err(lower_errors) => {
+                    success = false;
+                    errors.extend(lower_errors.into_iter().map(|e| messages::tr("compile.macro_failed", &[&e])));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Rust/Python 백엔드는 원래 HIR 을, IR 을 만드는 쪽은 단순화한 HIR 을 씁니다.
+        let ir_hir = hir.as_ref().map(|hir| request.options.ir_program(hir));
+        let ir_hir = ir_hir.as_deref();
+
+        // `--emit-rust <dir>`: cargo 프로젝트를 쓰고, `--cargo-build` 이면 바로 빌드합니다.
+        if let (Some(hir), Some(dir)) = (&hir, &request.options.rust_project) {
+            let dir = Path::new(dir);
+            let name = dir.file_name().map_or("high_program".into(), |n| n.to_string_lossy().into_owned());
+            match RustEmitterService::emit_project(hir, &name) {
+                Ok(project) => match project.write_to(dir) {
+                    Ok(()) => {
+                        for (path, content) in &project.files {
+                            artifacts.push(dir.join(path).display().to_string(), content.as_bytes());
+                        }
+                        compiled_output = format!("Rust 프로젝트 생성 완료: {}", dir.display());
+                        if request.options.cargo_build && pass == Pass::Full {
+                            let tools = StageWatch::start(Stage::Tools, timeouts, token);
+                            let built = RustEmitterService::cargo_build(dir, &project.name, tools.token());
+                            tools_time += tools.elapsed();
+                            if let Err(reason) = tools.finish() {
+                                return self.aborted_result(start_time, reason, errors, diagnostics, analysis_report, vec![]);
+                            }
+                            match built {
+                                Ok(path) => binary_path = Some(path.display().to_string()),
+                                Err(e) => {
+                                    success = false;
+                                    errors.push(error_codes::CARGO_BUILD_FAILED.tagged("", &[&e]));
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        success = false;
+                        errors.push(error_codes::CODEGEN_FAILED.tagged("", &[&e]));
+                    }
+                },
+                Err(backend_diagnostics) => {
+                    success = false;
+                    errors.extend(backend_diagnostics.iter().map(|d| d.coded_message()));
+                    diagnostics.extend(backend_diagnostics);
+                }
+            }
+        }
+
+        // 플레이그라운드용 목록: 파일을 쓰지 않고 결과에만 담으며, 실패해도 컴파일을 멈추지 않습니다.
+        let mut rust_source = None;
+        let mut ir_listing = None;
+        if let (Some(hir), true) = (&hir, request.options.emit_rust_source) {
+            match RustEmitterService::emit_project(hir, "playground") {
+                Ok(project) => rust_source = project.file("src/main.rs").map(str::to_string),
+                Err(backend_diagnostics) => diagnostics.extend(backend_diagnostics),
+            }
+        }
+        if let (Some(hir), true) = (ir_hir, request.options.emit_ir) {
+            ir_listing = Some(generate_ir(hir, &escapes).to_string());
+        }
+
+        let mut python_source = None;
+        if let (Some(hir), true) = (&hir, python_target) {
+            match PyEmitterService::emit(hir) {
+                Ok(source) => match fs::write(py_emitter_service::OUTPUT_PATH, &source) {
+                    Ok(()) => {
+                        artifacts.push(py_emitter_service::OUTPUT_PATH, source.as_bytes());
+                        compiled_output = format!("Python 코드 생성 완료: {}", py_emitter_service::OUTPUT_PATH);
+                        python_source = Some(source);
+                    }
+                    Err(e) => {
+                        success = false;
+                        errors.push(error_codes::CODEGEN_FAILED.tagged("", &[&e]));
+                    }
+                },
+                Err(backend_diagnostics) => {
+                    success = false;
+                    errors.extend(backend_diagnostics.iter().map(|d| d.coded_message()));
+                    diagnostics.extend(backend_diagnostics);
+                }
+            }
+        }
+
+        // LLVM 타깃: .ll 텍스트만 쓰고, 빌드는 사용자의 clang 에 맡깁니다.
+        if let (Some(hir), true) = (ir_hir, llvm_target) {
+            let ir = generate_ir(hir, &escapes);
+            let metadata = artifact_metadata::build_entries(
+                &request.source_code,
+                request.options.optimization_level,
+            );
+            let text = generate_llvm_ir(&ir, &codegen_config, &metadata);
+            match fs::write(llvm_codegen::OUTPUT_PATH, &text) {
+                Ok(()) => {
+                    artifacts.push(llvm_codegen::OUTPUT_PATH, text);
+                    compiled_output = format!("LLVM IR 생성 완료: {}", llvm_codegen::OUTPUT_PATH);
+                }
+                Err(e) => {
+                    success = false;
+                    errors.push(error_codes::CODEGEN_FAILED.tagged("", &[&e]));
+                }
+            }
+        }
+
+        let cache_dir = request.options.cache_dir.as_deref();
+        let asm_path = match cache_dir {
+            Some(dir) => Path::new(dir).join("compiled.asm").to_string_lossy().into_owned(),
+            None => "compiled.asm".to_string(),
+        };
+        let asm_path = asm_path.as_str();
+        if let (Some(hir), true) = (ir_hir, request.options.emit_native) {
+            let ir = generate_ir(hir, &escapes);
+            let bin_path = codegen_config.os.binary_path();
+
+            let metadata = artifact_metadata::build_entries(
+                &request.source_code,
+                request.options.optimization_level,
+            );
+
+            let generated = cache_dir
+                .map_or(Ok(()), |dir| fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir, e)))
+                .and_then(|()| generate_native_binary(&ir, asm_path, &codegen_config, &metadata));
+            match generated {
+                Ok(_) => {
+                    if request.options.reproducible {
+                        match fs::read(asm_path) {
+                            Ok(asm) => artifacts.push(asm_path, asm),
+                            Err(e) => {
+                                success = false;
+                                errors.push(error_codes::CODEGEN_FAILED.tagged("", &[&e]));
+                            }
+                        }
+                    }
+                    // 링크한 실행 파일은 비교하지 않으므로 다시 컴파일할 때는 도구를 부르지 않습니다.
+                    if pass == Pass::Rebuild {
+                        return self.rebuilt_result(start_time, success, errors, diagnostics, analysis_report);
+                    }
+                    let toolchain = Toolchain::with_runner(request.options.tool_paths.clone(), self.tool_runner.clone());
+                    let tools = StageWatch::start(Stage::Tools, timeouts, token);
+                    let linked = toolchain.assemble_and_link(codegen_config.os, asm_path, bin_path, tools.token());
+                    tools_time += tools.elapsed();
+                    if let Err(reason) = tools.finish() {
+                        return self.aborted_result(start_time, reason, errors, diagnostics, analysis_report, vec![]);
+                    }
+                    match linked {
+                        Ok(warnings) => {
+                            diagnostics.extend(toolchain::message_diagnostics(&warnings));
+                            compiled_output = format!("네이티브 실행 파일 생성 완료: {}", bin_path);
+                            binary_path = Some(bin_path.to_string());
+                            native_binary = Some(bin_path);
+                        }
+                        Err(ToolError::Cancelled) => {
+                            return self.aborted_result(start_time, StageError::Cancelled, errors, diagnostics, analysis_report, vec![]);
+                        }
+                        Err(e) => {
+                            success = false;
+                            errors.push(error_codes::LINK_FAILED.tagged("", &[&e]));
+                            diagnostics.extend(e.diagnostics());
+                        }
+                    }
+                }
+                Err(e) => {
+                    success = false;
+                    errors.push(error_codes::CODEGEN_FAILED.tagged("", &[&e]));
+                }
+            }
+        }
+
+        if pass == Pass::Rebuild {
+            return self.rebuilt_result(start_time, success, errors, diagnostics, analysis_report);
+        }
+
+        // `--reproducible`: 같은 요청을 이 서비스로 처음부터 다시 컴파일해 산출물이 바이트 단위로 같은지 확인합니다.
+        if success && request.options.reproducible {
+            let mut second = Artifacts::default();
+            let rebuilt = Box::pin(self.compile_pass(request, token, Pass::Rebuild, &mut second)).await;
+            if !rebuilt.success {
+                success = false;
+                errors.push(error_codes::NOT_REPRODUCIBLE.tagged("rebuild", &[&rebuilt.errors.join("; ")]));
+            } else if let Some((artifact, offset)) = artifacts.first_difference(&second) {
+                success = false;
+                errors.push(error_codes::NOT_REPRODUCIBLE.tagged("", &[&artifact, &offset]));
+            }
+        }
+
+        if let Err(reason) = codegen.finish_excluding(tools_time) {
+            return self.aborted_result(start_time, reason, errors, diagnostics, analysis_report, vec![]);
+        }
+
+        let execution = StageWatch::start(Stage::Execution, timeouts, token);
+        let execution_result = if let (true, true, Some(source)) = (success, request.options.run_python, &python_source) {
+            // `--run-python`: 인터프리터 대신 생성된 Python 코드를 실행합니다.
+            match PyEmitterService::run(source, execution.token()) {
+                Ok(result) => {
+                    if matches!(result.status, ExecutionStatus::RuntimeError) {
+                        success = false;
+                        errors.push("실행 중 에러 발생: 런타임 오류".into());
+                    }
+                    result
+                }
+                Err(e) => {
+                    success = false;
+                    errors.push(error_codes::PYTHON_FAILED.tagged("", &[&e]));
+                    ExecutionResult {
+                        output_log: vec![],
+                        status: ExecutionStatus::Skipped,
+                        execution_time_ms: 0,
+                        exit_code: exit_code::FAILURE,
+                        gc: None,
+                    }
+                }
+            }
+        } else if success {
+            let exec_request = ExecutionRequest {
+                compiled_code_reference: compiled_output.clone(),
+                input_data: if analysis_report.detected_sentiment == "Positive" {
+                    Some("Success Data".into())
+                } else {
+                    None
+                },
+                source_code: if request.options.emit_native {
+                    None
+                } else {
+                    Some(request.source_code.clone())
+                },
+                // 다른 OS 를 겨냥한 바이너리는 여기서 실행할 수 없습니다.
+                binary_path: binary_path.clone().filter(|_| codegen_config.os == TargetOs::host()),
+                prelude: Self::execution_prelude(&request.options),
+                globals: request.options.globals.clone(),
+                cancellation: execution.token().clone(),
+                capabilities: Capabilities {
+                    processes: request.options.allow_process,
+                    debug: request.options.allow_debug,
+                    ..Capabilities::default()
+                },
+            };
+
+            let result = self.executor.execute_code(exec_request).await;
+
+            if matches!(result.status, ExecutionStatus::RuntimeError) {
+                success = false;
+                errors.push("실행 중 에러 발생: 런타임 오류".into());
+            }
+
+            result
+        } else {
+            ExecutionResult {
+                output_log: vec!["[Executor] 실행되지 않음: 컴파일 에러.".into()],
+                status: ExecutionStatus::Skipped,
+                execution_time_ms: 0,
+                exit_code: exit_code::FAILURE,
+                gc: None,
+            }
+        };
+
+        let proof_hash = format!(
+            "POCI_{}_{}_{:?}",
+            request.source_code.len(),
+            request.options.target_platform,
+            execution_result.status
+        );
+        if let Err(reason) = execution.finish() {
+            return self.aborted_result(start_time, reason, errors, diagnostics, analysis_report, execution_result.output_log);
+        }
+        let added = if request.options.reproducible {
+            self.blockchain.borrow_mut().add_block_at(proof_hash, reproducible::timestamp(), token)
+        } else {
+            self.blockchain.borrow_mut().add_block(proof_hash, token)
+        };
+        let Some(new_block) = added else {
+            let log = execution_result.output_log;
+            return self.aborted_result(start_time, StageError::Cancelled, errors, diagnostics, analysis_report, log);
+        };
+
+        // 증명 블록을 실제 산출물에 연결합니다.
+        if let Some(path) = native_binary {
+            let block_hash = Blockchain::calculate_hash(&new_block);
+            if let Err(e) = artifact_metadata::stamp_proof_hash(path, &block_hash) {
+                errors.push(format!("메타데이터 스탬핑 실패: {}", e));
+            }
+        }
+
+        let total_time_ms = start_time.elapsed().as_millis();
+
+        CompileResult {
+            success,
+            compiled_output,
+            binary_path,
+            expanded_source,
+            rust_source,
+            ir_listing,
+            diagnostics,
+            analysis_report,
+            allocation_stats,
+            execution_log: execution_result.output_log,
+            execution_status: execution_result.status,
+            exit_code: execution_result.exit_code,
+            proof_block_index: new_block.index,
+            errors,
+            total_time_ms,
+        }
+    }
+
+    async fn run_analysis(&self, source: &str, options: &CompileOptions, errors: &mut Vec<String>, success: &mut bool) -> AnalysisResult {
+        match self.analyzer.analyze_with(source, &options.analyzer, options.analyzer_timeout_ms).await {
+            Ok(report) => report,
+            Err(e) => {
+                errors.push(format!("분석 실패: {}", e));
+                *success = false;
+                empty_analysis(&options.analyzer, "Error")
+            }
+        }
+    }
+
+    /// 취소되었거나 단계 제한 시간을 넘긴 컴파일의 결과. 지금까지 모은 진단과 실행 로그만 담고,
+    /// 증명 블록은 마지막 블록을 가리킵니다.
+    fn aborted_result(
+        &self,
+        start_time: Instant,
+        reason: StageError,
+        mut errors: Vec<String>,
+        diagnostics: Vec<Diagnostic>,
+        analysis_report: AnalysisResult,
+        execution_log: Vec<String>,
+    ) -> CompileResult {
+        let (execution_status, exit_code) = match reason {
+            StageError::Cancelled => {
+                errors.push(error_codes::CANCELLED.tagged("", &[]));
+                (ExecutionStatus::Cancelled, exit_code::CANCELLED)
+            }
+            StageError::TimedOut(stage, limit) => {
+                errors.push(error_codes::STAGE_TIMED_OUT.tagged("", &[&stage, &limit.as_millis()]));
+                (ExecutionStatus::TimedOut, exit_code::TIMED_OUT)
+            }
+        };
+        CompileResult {
+            success: false,
+            compiled_output: String::new(),
+            binary_path: None,
+            expanded_source: None,
+            rust_source: None,
+            ir_listing: None,
+            diagnostics,
+            analysis_report,
+            allocation_stats: AllocationStats::default(),
+            execution_log,
+            execution_status,
+            exit_code,
+            proof_block_index: self.blockchain.borrow().chain.last().map_or(0, |block| block.index),
+            errors,
+            total_time_ms: start_time.elapsed().as_millis(),
+        }
+    }
+
+    /// `--reproducible` 의 두 번째 컴파일 결과. 산출물만 비교하므로 실행하지 않고 증명 블록도 만들지 않습니다.
+    fn rebuilt_result(
+        &self,
+        start_time: Instant,
+        success: bool,
+        errors: Vec<String>,
+        diagnostics: Vec<Diagnostic>,
+        analysis_report: AnalysisResult,
+    ) -> CompileResult {
+        CompileResult {
+            success,
+            compiled_output: String::new(),
+            binary_path: None,
+            expanded_source: None,
+            rust_source: None,
+            ir_listing: None,
+            diagnostics,
+            analysis_report,
+            allocation_stats: AllocationStats::default(),
+            execution_log: vec![],
+            execution_status: ExecutionStatus::Skipped,
+            exit_code: exit_code::SUCCESS,
+            proof_block_index: self.blockchain.borrow().chain.last().map_or(0, |block| block.index),
+            errors,
+            total_time_ms: start_time.elapsed().as_millis(),
+        }
+    }
+
+    /// 인터프리터에 넘길 프렐류드: 표준 라이브러리 다음에 사용자 프렐류드가 옵니다.
+    fn execution_prelude(options: &CompileOptions) -> Option<String> {
+        stdlib::execution_prelude(options.no_std, options.prelude.as_deref())
+    }
+
+    /// 구문 오류가 있으면 실패로 표시하지만, 복구한 프로그램으로 이후 단계도 진행해 다른 진단을 함께 모읍니다.
+    fn run_parsing(&self, source: &str, errors: &mut Vec<String>, success: &mut bool) -> (Program, Vec<Diagnostic>) {
+        let lexer = LexerService::new(source);
+        let mut parser = ParserService::new(lexer);
+        let program = parser.parse_program();
+        let diagnostics = parser.take_diagnostics();
+        if !diagnostics.is_empty() {
+            *success = false;
+            errors.extend(diagnostics.iter().map(Diagnostic::coded_message));
+        }
+        (program, diagnostics)
+    }
+}
+
+/// 분석 결과가 없을 때 쓰는 빈 보고서 (`sentiment` 에 이유를 적습니다)
+fn empty_analysis(backend: &str, sentiment: &str) -> AnalysisResult {
+    AnalysisResult {
+        word_count: 0,
+        detected_sentiment: sentiment.into(),
+        functions: vec![],
+        processing_time_ms: 0,
+        backend: backend.into(),
+        degraded: None,
+    }
+}
+
+// ─── 요청 및 결과 구조체 ─────────────────────────────
+
+#[derive(Debug)]
+pub struct CompileRequest {
+    pub source_code: String,
+    pub options: CompileOptions,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompileOptions {
+    pub target_platform: String,
+    pub optimization_level: u8,
+    pub emit_native: bool,
+    /// `--target-cpu`: 코드 생성 대상 CPU (기본값은 baseline x86-64)
+    pub target_cpu: String,
+    /// `--features`: `+feat` / `-feat` 형식의 추가 타깃 기능
+    pub target_features: Vec<String>,
+    /// `--target-os`: 네이티브 실행 파일을 만들 OS (linux, windows, macos). 없으면 호스트 OS
+    pub target_os: Option<String>,
+    /// `--emit-expanded`: 매크로 확장 후의 소스를 결과에 포함
+    pub emit_expanded: bool,
+    /// `--cfg name` / `--cfg key=value`: `@cfg` 평가용 사용자 정의 플래그
+    pub cfg_flags: Vec<String>,
+    /// `--prelude <file>`: 사용자 코드보다 먼저 실행되는 프렐류드 소스
+    pub prelude: Option<String>,
+    /// `--global name=value`: 실행 전에 루트 Environment에 주입되는 전역 값
+    pub globals: Vec<(String, Value)>,
+    /// `--analyzer`: 분석 백엔드 이름 또는 `http://` 주소
+    pub analyzer: String,
+    /// `--analyzer-timeout <ms>`: 이 시간을 넘기면 기본 분석기로 대신합니다.
+    pub analyzer_timeout_ms: u64,
+    /// `--emit-rust <dir>`: Rust 백엔드로 cargo 프로젝트를 생성할 디렉터리
+    pub rust_project: Option<String>,
+    /// `--cargo-build`: 생성된 cargo 프로젝트를 `cargo build --release` 로 빌드
+    pub cargo_build: bool,
+    /// `--run-python`: `target_platform = "python"` 으로 생성한 코드를 Python 인터프리터로 실행
+    pub run_python: bool,
+    /// `--no-std`: 표준 라이브러리(`stdlib/*.high`)를 링크하지 않음
+    pub no_std: bool,
+    /// `--allow-process`: 인터프리터 실행에서 `run_process` 를 허용
+    pub allow_process: bool,
+    /// `--allow-debug`: 인터프리터 실행에서 `callstack` / `locals` 를 허용
+    pub allow_debug: bool,
+    /// 생성된 Rust 소스를 파일 대신 `CompileResult::rust_source` 로 돌려줌 (high serve)
+    pub emit_rust_source: bool,
+    /// IR 목록을 `CompileResult::ir_listing` 으로 돌려줌 (high serve)
+    pub emit_ir: bool,
+    /// `--timeout <단계>=<ms>`: 단계별 제한 시간
+    pub timeouts: StageTimeouts,
+    /// `--nasm <path>` / `--linker <path>`: 네이티브 백엔드가 부르는 도구 경로
+    pub tool_paths: ToolPaths,
+    /// `--cache-dir <dir>`: 네이티브 빌드의 중간 파일(.asm, .o)을 둘 디렉터리. 없으면 현재 디렉터리
+    pub cache_dir: Option<String>,
+    /// `--reproducible`: 증명 블록 시간을 고정하고, 다시 컴파일한 산출물이 바이트 단위로 같은지 확인
+    pub reproducible: bool,
+    /// `--strip-dead`: 도달할 수 없는 함수와 쓰이지 않는 표준 라이브러리 모듈을 코드 생성 전에 지움
+    pub strip_dead: bool,
+    /// `--strict-math` / `--fast-math`: 실수 상수 접기 엄격도. 없으면 최적화 수준으로 정합니다. (`MathMode::for_level`)
+    pub math: Option<MathMode>,
+    /// `--strict`: 표기가 없고 타입을 알 수 없는 바인딩(암시적 any)을 오류로 봄 (E0013)
+    pub strict: bool,
+    /// `--unroll-limit <n>`: 펼칠 루프의 최대 반복 횟수 (0 이면 끔). 없으면 최적화 수준으로 정합니다.
+    pub unroll_limit: Option<u32>,
+    /// `--profile-use <file>`: `high build --profile-generate` 로 만든 실행 프로필 (profile.rs)
+    pub profile_use: Option<Arc<Profile>>,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            target_platform: "her_vm".into(),
+            optimization_level: 0,
+            emit_native: false,
+            target_cpu: BASELINE_CPU.into(),
+            target_features: vec![],
+            target_os: None,
+            emit_expanded: false,
+            cfg_flags: vec![],
+            prelude: None,
+            globals: vec![],
+            analyzer: DEFAULT_ANALYZER.into(),
+            analyzer_timeout_ms: DEFAULT_ANALYZER_TIMEOUT_MS,
+            rust_project: None,
+            cargo_build: false,
+            run_python: false,
+            no_std: false,
+            allow_process: false,
+            allow_debug: false,
+            emit_rust_source: false,
+            emit_ir: false,
+            timeouts: StageTimeouts::default(),
+            tool_paths: ToolPaths::default(),
+            cache_dir: None,
+            reproducible: false,
+            strip_dead: false,
+            math: None,
+            strict: false,
+            unroll_limit: None,
+            profile_use: None,
+        }
+    }
+}
+
+impl CompileOptions {
+    pub fn math_mode(&self) -> MathMode {
+        self.math.unwrap_or_else(|| MathMode::for_level(self.optimization_level))
+    }
+
+    pub fn optimizer_settings(&self, source: &str) -> OptimizerSettings<'_> {
+        OptimizerSettings {
+            level: self.optimization_level,
+            math: self.math_mode(),
+            unroll_limit: self.unroll_limit.unwrap_or_else(|| OptimizerSettings::default_unroll_limit(self.optimization_level)),
+            profile: self.profile_for(source),
+        }
+    }
+
+    /// `source` 를 최적화할 때 쓸 프로필. 최적화 수준이 0 이거나 다른 소스로 만든 프로필이면 None 입니다.
+    pub fn profile_for(&self, source: &str) -> Option<&Profile> {
+        self.profile_use.as_deref().filter(|profile| self.optimization_level > 0 && profile.matches(source))
+    }
+
+    /// IR 을 만들 HIR. 최적화 수준이 0 보다 크면 한 번 더 단순화합니다. (ir_simplify.rs)
+    pub fn ir_program<'a>(&self, hir: &'a hir::Program) -> Cow<'a, hir::Program> {
+        if self.optimization_level == 0 {
+            return Cow::Borrowed(hir);
+        }
+        let mut simplified = hir.clone();
+        ir_simplify::simplify(&mut simplified, self.math_mode());
+        Cow::Owned(simplified)
+    }
+}
+
+#[derive(Debug)]
+pub struct CompileResult {
+    pub success: bool,
+    pub compiled_output: String,
+    /// 네이티브 바이너리(또는 `--cargo-build` 로 빌드한 Rust 실행 파일)가 생성된 경우 그 경로
+    pub binary_path: Option<String>,
+    /// `--emit-expanded`: 매크로 확장 후의 소스 코드
+    pub expanded_source: Option<String>,
+    /// `emit_rust_source`: Rust 백엔드가 생성한 `src/main.rs`
+    pub rust_source: Option<String>,
+    /// `emit_ir`: 네이티브 코드 생성기에 넘기는 IR 목록
+    pub ir_listing: Option<String>,
+    /// 컴파일을 멈추지 않는 경고를 포함한 모든 진단
+    pub diagnostics: Vec<Diagnostic>,
+    pub analysis_report: AnalysisResult,
+    /// 탈출 분석으로 힙 할당이 생략된(스택에 놓인) 할당 수
+    pub allocation_stats: AllocationStats,
+    pub execution_log: Vec<String>,
+    pub execution_status: ExecutionStatus,
+    /// 프로그램의 종료 코드 (exit_code.rs)
+    pub exit_code: i32,
+    pub proof_block_index: u32,
+    pub errors: Vec<String>,
+    pub total_time_ms: u128,
+}
+
+/// `compile_batch` 결과: 요청 순서대로의 결과와 집계
+#[derive(Debug)]
+pub struct BatchResult {
+    pub results: Vec<CompileResult>,
+    pub summary: BatchSummary,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    /// 컴파일이나 실행이 실패한 요청 (취소, 제한 시간 초과 제외)
+    pub failed: usize,
+    pub cancelled: usize,
+    pub timed_out: usize,
+    /// 모든 결과의 경고 진단 수
+    pub warnings: usize,
+    /// 일괄 컴파일 전체가 걸린 시간
+    pub wall_time_ms: u128,
+    /// 요청별 컴파일 시간의 합. `wall_time_ms` 보다 크면 그만큼 겹쳐 돌았습니다.
+    pub compile_time_ms: u128,
+}
+
+impl BatchSummary {
+    fn new(results: &[CompileResult], wall_time_ms: u128) -> Self {
+        let mut summary = BatchSummary { total: results.len(), wall_time_ms, ..Self::default() };
+        for result in results {
+            match result.execution_status {
+                ExecutionStatus::Cancelled => summary.cancelled += 1,
+                ExecutionStatus::TimedOut => summary.timed_out += 1,
+                _ if result.success => summary.succeeded += 1,
+                _ => summary.failed += 1,
+            }
+            summary.warnings += result.diagnostics.iter().filter(|d| matches!(d.level, DiagnosticLevel::Warning)).count();
+            summary.compile_time_ms += result.total_time_ms;
+        }
+        summary
+    }
+
+    pub fn all_succeeded(&self) -> bool {
+        self.succeeded == self.total
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: [&dyn fmt::Display; 6] =
+            [&self.total, &self.succeeded, &self.failed, &self.cancelled, &self.timed_out, &self.wall_time_ms];
+        write!(f, "{}", messages::tr("compile.batch_summary", &counts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolchain::MockRunner;
+
+    /// 다른 OS 용 네이티브 빌드는 도구를 모의 실행기로 돌리고, 만든 파일을 실행하지 않습니다.
+    #[tokio::test]
+    async fn reproducible_rebuild_runs_the_service_pipeline_without_tools() {
+        let (os, tool_calls) = if cfg!(target_os = "windows") { ("linux", 3) } else { ("windows", 2) };
+        let dir = std::env::temp_dir().join(format!("high-reproducible-{}", std::process::id()));
+        let runner = Arc::new(MockRunner::new());
+        let mut service = CompilerService::new();
+        service.set_tool_runner(runner.clone());
+        let options = CompileOptions {
+            emit_native: true,
+            target_os: Some(os.into()),
+            cache_dir: Some(dir.to_string_lossy().into_owned()),
+            reproducible: true,
+            optimization_level: 2,
+            no_std: true,
+            ..CompileOptions::default()
+        };
+        let source = "fn add(a, b) { return a + b; }\nlet x = add(1, 2) * 3;\nreturn x;";
+        let result = service.compile(CompileRequest { source_code: source.into(), options }).await;
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(result.success, "{:?}", result.errors);
+        assert!(!result.errors.iter().any(|e| e.contains("E0097")), "{:?}", result.errors);
+        // 두 번째 컴파일은 어셈블리를 쓰고 멈추므로 도구 호출과 증명 블록은 첫 컴파일 것뿐입니다.
+        assert_eq!(runner.calls().len(), tool_calls);
+        assert_eq!(result.proof_block_index, 1);
+    }
+
+    #[tokio::test]
+    async fn assigning_an_immutable_binding_fails_compilation() {
+        let service = CompilerService::new();
+        let compile = |source: &str| {
+            let options = CompileOptions { no_std: true, ..CompileOptions::default() };
+            service.compile(CompileRequest { source_code: source.into(), options })
+        };
+        let result = compile("let x = 1;\nx = 2;\nreturn x;").await;
+        assert!(!result.success);
+        assert!(result.errors.iter().any(|e| e.starts_with("[E0007]")), "{:?}", result.errors);
+        let result = compile("let mut x = 1;\nx = 2;\nreturn x;").await;
+        assert!(result.success, "{:?}", result.errors);
+    }
+}
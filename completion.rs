@@ -0,0 +1,334 @@
+// completion.rs
+// 자동 완성 후보 (데몬의 completion)
+//
+// 커서 바로 앞의 이름 조각(접두어)으로 시작하는 후보를 문맥에 따라 돌려줍니다.
+//
+//   이름 자리   커서에서 보이는 바인딩(안쪽 스코프가 바깥의 같은 이름을 가림), 최상위 함수·매크로,
+//               표준 라이브러리 매크로, 내장 함수, 키워드. 보이는 바인딩은 이름 해석기(`Resolver::visible_at`)가
+//               정하고, 최상위 함수·매크로는 선언보다 앞에서도 보입니다.
+//   타입 자리   `let x: ` 나 `type T = ` 뒤, 합 타입의 `|` 뒤에서는 내장 타입과 `type` 별칭만 냅니다.
+//   필드 자리   `r.` 뒤에서 `r` 이 필드를 아는 레코드(`run_process` 결과)면 그 필드를 냅니다. 레코드 필드는
+//               `r["stdout"]` 로 읽으므로 점부터 커서까지를 인덱싱으로 바꾸는 편집을 돌려줍니다.
+//
+// 바인딩의 설명(detail)은 타입 표기, 없으면 리터럴 초기값의 타입, 함수면 매개변수 목록이고 모르면 `any` 입니다.
+// 요청마다 파일 전체를 다시 파싱합니다. 입력 중인 불완전한 소스는 파서의 오류 복구에 맡깁니다.
+// 위치와 범위는 스팬과 같은 문자 위치입니다.
+
+use std::collections::HashMap;
+
+use crate::data_structures::{Expression, Program, Span, Statement, TokenKind};
+use crate::ft_runtime::{self, BUILTINS};
+use crate::json::Json;
+use crate::lexer_service::LexerService;
+use crate::navigation::tokenize;
+use crate::parser_service::ParserService;
+use crate::resolver::{Resolver, SymbolKind};
+use crate::stdlib;
+use crate::visitor::{walk_statement, Visitor};
+
+/// 타입 자리에 쓸 수 있는 내장 타입
+const BUILTIN_TYPES: &[&str] = &["int", "float", "bool", "string", "void", "any"];
+
+const KEYWORDS: &[&str] = &[
+    "let", "mut", "fn", "if", "else", "while", "for", "in", "return", "match", "macro", "type", "enum",
+    "type_of", "eval", "reflect", "async", "await", "yield", "defer", "true", "false",
+];
+
+/// 필드를 아는 레코드: (레코드 이름, [(필드, 타입)])
+const RECORDS: &[(&str, &[(&str, &str)])] = &[("process", &[("exit_code", "int"), ("stdout", "string"), ("stderr", "string")])];
+
+/// 레코드를 돌려주는 내장 함수: (함수, 레코드 이름)
+const RECORD_BUILTINS: &[(&str, &str)] = &[("run_process", "process")];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionKind {
+    Variable,
+    Function,
+    Macro,
+    Field,
+    Type,
+    Keyword,
+}
+
+impl CompletionKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            CompletionKind::Variable => "variable",
+            CompletionKind::Function => "function",
+            CompletionKind::Macro => "macro",
+            CompletionKind::Field => "field",
+            CompletionKind::Type => "type",
+            CompletionKind::Keyword => "keyword",
+        }
+    }
+}
+
+/// 후보 하나. 고르면 `replace` 범위를 `insert` 로 바꿉니다.
+#[derive(Debug, Clone)]
+pub struct Completion {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// 타입이나 매개변수 목록
+    pub detail: String,
+    pub insert: String,
+    pub replace: Span,
+}
+
+impl Completion {
+    fn new(label: &str, kind: CompletionKind, detail: impl Into<String>) -> Self {
+        let replace = Span { start: 0, end: 0 };
+        Self { label: label.to_string(), kind, detail: detail.into(), insert: label.to_string(), replace }
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("label", Json::from(self.label.as_str())),
+            ("kind", Json::from(self.kind.name())),
+            ("detail", Json::from(self.detail.as_str())),
+            ("insert", Json::from(self.insert.as_str())),
+            ("start", Json::from(self.replace.start as u64)),
+            ("end", Json::from(self.replace.end as u64)),
+        ])
+    }
+}
+
+pub fn to_json(completions: &[Completion]) -> Json {
+    Json::Array(completions.iter().map(Completion::to_json).collect())
+}
+
+/// `offset` 에서의 완성 후보. 보이는 바인딩이 먼저이고, 그다음 표준 라이브러리·내장 함수, 키워드 순입니다.
+pub fn complete(source: &str, offset: usize) -> Vec<Completion> {
+    let chars: Vec<char> = source.chars().collect();
+    let offset = offset.min(chars.len());
+    let start = (0..offset).rev().take_while(|&i| chars[i].is_alphanumeric() || chars[i] == '_').last().unwrap_or(offset);
+    let prefix: String = chars[start..offset].iter().collect();
+    let program = ParserService::new(LexerService::new(source)).parse_program();
+    let visible = visible_at(&program, offset);
+
+    let mut candidates = if start > 0 && chars[start - 1] == '.' {
+        let receiver_end = start - 1;
+        let receiver_start =
+            (0..receiver_end).rev().take_while(|&i| chars[i].is_alphanumeric() || chars[i] == '_').last().unwrap_or(receiver_end);
+        let receiver: String = chars[receiver_start..receiver_end].iter().collect();
+        let fields = visible.iter().find(|c| c.label == receiver).map_or(&[][..], |c| record_fields(&c.detail));
+        let replace = Span { start: receiver_end, end: offset };
+        return fields
+            .iter()
+            .filter(|(field, _)| field.starts_with(&prefix))
+            .map(|(field, ty)| Completion { insert: format!("[\"{}\"]", field), replace, ..Completion::new(field, CompletionKind::Field, *ty) })
+            .collect();
+    } else if in_type_position(source, start) {
+        let mut types: Vec<Completion> = BUILTIN_TYPES.iter().map(|t| Completion::new(t, CompletionKind::Type, "builtin")).collect();
+        types.extend(aliases(&program));
+        types
+    } else {
+        let mut names = visible;
+        names.extend(library());
+        names.extend(KEYWORDS.iter().map(|k| Completion::new(k, CompletionKind::Keyword, "keyword")));
+        names
+    };
+
+    let mut seen = vec![];
+    candidates.retain(|c| c.label.starts_with(&prefix) && !seen.contains(&c.label) && {
+        seen.push(c.label.clone());
+        true
+    });
+    for candidate in &mut candidates {
+        candidate.replace = Span { start, end: offset };
+    }
+    candidates
+}
+
+fn record_fields(detail: &str) -> &'static [(&'static str, &'static str)] {
+    RECORDS.iter().find(|(name, _)| *name == detail).map_or(&[], |(_, fields)| fields)
+}
+
+/// 커서 앞의 토큰들이 `:` 나 `type NAME =` 에서 시작한 타입 표기(`int | Meters | `)인지
+fn in_type_position(source: &str, start: usize) -> bool {
+    let tokens = tokenize(source);
+    let mut before = tokens.iter().rev().skip_while(|t| t.span.end > start).map(|t| &t.kind);
+    loop {
+        match before.next() {
+            Some(TokenKind::Colon) => return true,
+            Some(TokenKind::Assign) => {
+                return matches!(before.next(), Some(TokenKind::Identifier(_))) && matches!(before.next(), Some(TokenKind::Type));
+            }
+            Some(TokenKind::BitOr) => {
+                if !matches!(
+                    before.next(),
+                    Some(
+                        TokenKind::Identifier(_)
+                            | TokenKind::Int
+                            | TokenKind::Float
+                            | TokenKind::Bool
+                            | TokenKind::String
+                            | TokenKind::Void
+                            | TokenKind::Any
+                    )
+                ) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// 프로그램 어디에서 선언했든 보이는 `type` 별칭
+fn aliases(program: &Program) -> Vec<Completion> {
+    struct Aliases(Vec<Completion>);
+    impl Visitor for Aliases {
+        fn visit_statement(&mut self, stmt: &Statement) {
+            if let Statement::TypeAlias { name, target, .. } = stmt {
+                self.0.push(Completion::new(name.as_str(), CompletionKind::Type, target.to_string()));
+            }
+            walk_statement(self, stmt);
+        }
+    }
+    let mut aliases = Aliases(vec![]);
+    aliases.visit_program(program);
+    aliases.0
+}
+
+/// 표준 라이브러리의 최상위 정의와 내장 함수
+fn library() -> Vec<Completion> {
+    let mut items = vec![];
+    for module in stdlib::MODULES {
+        let program = ParserService::new(LexerService::new(module.source)).parse_program();
+        items.extend(program.statements.iter().filter_map(declaration).map(|mut item| {
+            item.detail = format!("{} ({})", item.detail, module.name);
+            item
+        }));
+    }
+    items.extend(BUILTINS.iter().map(|name| Completion::new(name, CompletionKind::Function, "builtin")));
+    items
+}
+
+/// `offset` 에서 보이는 바인딩. 안쪽 스코프의 것이 먼저이고, 최상위 함수·매크로는 선언 위치와 관계없이 보입니다.
+fn visible_at(program: &Program, offset: usize) -> Vec<Completion> {
+    let mut declarations = Declarations(HashMap::new());
+    declarations.visit_program(program);
+    let mut visible: Vec<Completion> = Resolver::visible_at(program, offset)
+        .into_iter()
+        .map(|symbol| {
+            let name = symbol.name.as_str();
+            let kind = if symbol.kind == SymbolKind::Macro { CompletionKind::Macro } else { CompletionKind::Variable };
+            declarations.0.get(&(name.to_string(), symbol.declaration_span.start)).cloned().unwrap_or_else(|| Completion::new(name, kind, "any"))
+        })
+        .collect();
+    // 해석기는 선언한 다음부터 보지만, 최상위 함수·매크로는 뒤에 선언했거나 자기 본문 안이어도 부를 수 있습니다.
+    let hoisted: Vec<Completion> = program
+        .statements
+        .iter()
+        .filter_map(declaration)
+        .filter(|item| matches!(item.kind, CompletionKind::Function | CompletionKind::Macro))
+        .filter(|item| !visible.iter().any(|v| v.label == item.label))
+        .collect();
+    visible.extend(hoisted);
+    visible
+}
+
+/// 선언 문장 하나가 만드는 후보
+fn declaration(stmt: &Statement) -> Option<Completion> {
+    match stmt {
+        Statement::LetStatement { name, value, type_annotation, .. } => Some(match (unwrap_grouped(value), type_annotation) {
+            (Expression::Function(_, parameters, _), _) => {
+                let parameters: Vec<&str> = parameters.iter().map(|p| p.as_str()).collect();
+                Completion::new(name.as_str(), CompletionKind::Function, format!("fn({})", parameters.join(", ")))
+            }
+            (_, Some(annotation)) => Completion::new(name.as_str(), CompletionKind::Variable, annotation.to_string()),
+            (value, None) => Completion::new(name.as_str(), CompletionKind::Variable, value_type(value)),
+        }),
+        Statement::MacroDefinition { name, parameters, .. } => {
+            Some(Completion::new(name, CompletionKind::Macro, format!("macro({})", parameters.join(", "))))
+        }
+        Statement::TypeAlias { name, target, .. } => Some(Completion::new(name.as_str(), CompletionKind::Type, target.to_string())),
+        Statement::Attributed { statement, .. } => declaration(statement),
+        _ => None,
+    }
+}
+
+/// 초기값으로 알 수 있는 타입. 리터럴과 레코드를 돌려주는 내장 함수 호출만 봅니다.
+fn value_type(value: &Expression) -> String {
+    match value {
+        Expression::Literal(_, literal) => ft_runtime::type_name(literal).to_string(),
+        Expression::MacroCall(_, name, _) => {
+            RECORD_BUILTINS.iter().find(|(builtin, _)| builtin == name).map_or("any", |(_, record)| record).to_string()
+        }
+        _ => "any".to_string(),
+    }
+}
+
+fn unwrap_grouped(expr: &Expression) -> &Expression {
+    match expr {
+        Expression::Grouped(_, inner) => unwrap_grouped(inner),
+        other => other,
+    }
+}
+
+/// (이름, 선언 위치) → 그 선언의 후보. 위치는 해석기가 기록하는 `declaration_span` 의 시작입니다.
+/// 매개변수, `for` 변수, 패턴 바인딩은 여기 없으므로 `any` 입니다.
+struct Declarations(HashMap<(String, usize), Completion>);
+
+impl Visitor for Declarations {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Some(item) = declaration(stmt) {
+            self.0.entry((item.label.clone(), stmt.span().start)).or_insert(item);
+        }
+        if let Statement::EnumDeclaration { name, variants, .. } = stmt {
+            for variant in variants {
+                let item = Completion::new(variant.name.as_str(), CompletionKind::Variable, name.as_str());
+                self.0.insert((item.label.clone(), variant.span.start), item);
+            }
+        }
+        walk_statement(self, stmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `|` 자리에서의 후보 (이름 바인딩만)
+    fn names(marked: &str) -> Vec<(String, String)> {
+        let offset = marked.chars().position(|c| c == '|').unwrap();
+        let source: String = marked.chars().filter(|&c| c != '|').collect();
+        let program = ParserService::new(LexerService::new(&source)).parse_program();
+        visible_at(&program, offset).into_iter().map(|c| (c.label, c.detail)).collect()
+    }
+
+    fn labels(marked: &str) -> Vec<String> {
+        names(marked).into_iter().map(|(label, _)| label).collect()
+    }
+
+    #[test]
+    fn visible_bindings_follow_the_resolver_scopes() {
+        let source = "let a = 1;\nfn f(b) {\n  let c = 2.5;\n  { let hidden = 0; }\n  |\n}\nlet d = 3;\nfn later() { return 0; }\nreturn 0;";
+        assert_eq!(labels(source), ["c", "b", "a", "f", "later"]);
+        assert_eq!(names(source)[0].1, "float");
+        assert_eq!(names(source)[3].1, "fn(b)");
+    }
+
+    #[test]
+    fn a_let_is_not_visible_in_its_own_initializer() {
+        assert_eq!(labels("let a = 1;\nlet b = |"), ["a"]);
+        assert_eq!(labels("let a = 1;\nlet b = 2;|"), ["b", "a"]);
+    }
+
+    #[test]
+    fn inner_bindings_shadow_outer_ones() {
+        let source = "let x = 1;\nfn f(x) { return |; }\nreturn 0;";
+        assert_eq!(names(source)[0], ("x".to_string(), "any".to_string()));
+        let completions = complete(&source.replace('|', ""), source.find('|').unwrap());
+        assert_eq!(completions.iter().filter(|c| c.label == "x").count(), 1);
+    }
+
+    #[test]
+    fn enum_variants_and_record_fields() {
+        assert!(names("enum Color { Red, Green }\nlet c = |").contains(&("Green".to_string(), "Color".to_string())));
+        let source = "let p = run_process(\"ls\");\np.st";
+        let fields: Vec<(String, String)> =
+            complete(source, source.chars().count()).into_iter().map(|c| (c.label, c.insert)).collect();
+        assert_eq!(fields, [("stdout".to_string(), "[\"stdout\"]".to_string()), ("stderr".to_string(), "[\"stderr\"]".to_string())]);
+    }
+}
@@ -0,0 +1,130 @@
+// conditional_compilation.rs
+// `@cfg(...)` 어트리뷰트를 CompileOptions에 비추어 평가하고, 비활성 항목을 타입 검사 전에 제거합니다.
+
+use crate::attributes::has_attribute;
+use crate::compat::*;
+use crate::data_structures::{Attribute, AttributeArg, Program, Statement};
+use crate::visitor::{walk_statement_mut, MutVisitor};
+
+/// `@cfg` 평가에 사용되는 컴파일 설정입니다.
+#[derive(Debug, Clone)]
+pub struct CfgContext {
+    /// CompileOptions.target_platform (예: "her_vm", "python")
+    pub target_platform: String,
+    /// 호스트 OS (예: "linux", "windows", "macos")
+    pub target_os: String,
+    /// `--cfg name` 또는 `--cfg key=value` 로 전달된 사용자 정의 플래그
+    pub flags: Vec<String>,
+}
+
+impl CfgContext {
+    pub fn new(target_platform: &str, flags: &[String]) -> Self {
+        Self {
+            target_platform: target_platform.to_string(),
+            target_os: host_os().to_string(),
+            flags: flags.to_vec(),
+        }
+    }
+
+    /// 하나의 `@cfg` 인자가 현재 설정에서 참인지 평가합니다.
+    fn is_enabled(&self, arg: &AttributeArg) -> bool {
+        match arg {
+            AttributeArg::Flag(name) => self.flags.iter().any(|f| f == name),
+            AttributeArg::KeyValue(key, value) => {
+                let builtin = match key.as_str() {
+                    "target" => *value == self.target_platform || *value == self.target_os,
+                    "target_os" => *value == self.target_os,
+                    _ => false,
+                };
+                builtin || self.flags.iter().any(|f| *f == format!("{}={}", key, value))
+            }
+            AttributeArg::Literal(_) => false,
+        }
+    }
+
+    /// 문장에 붙은 모든 `@cfg`가 참일 때만 문장을 유지합니다. (여러 인자는 AND)
+    /// `@test` 항목은 `test` 플래그가 있을 때만 유지됩니다.
+    pub fn is_active(&self, attributes: &[Attribute]) -> bool {
+        if has_attribute(attributes, "test") && !self.flags.iter().any(|f| f == "test") {
+            return false;
+        }
+        attributes
+            .iter()
+            .filter(|a| a.name == "cfg")
+            .all(|a| a.args.iter().all(|arg| self.is_enabled(arg)))
+    }
+}
+
+/// 호스트 OS 이름. std 가 없는 빌드(마이크로컨트롤러 등)는 "none" 입니다.
+fn host_os() -> &'static str {
+    #[cfg(feature = "std")]
+    return std::env::consts::OS;
+    #[cfg(not(feature = "std"))]
+    return "none";
+}
+
+/// 비활성화된 `@cfg` 항목을 프로그램에서 제거합니다. 제거된 항목 수를 반환합니다.
+/// 블록과 함수 본문 등 문장 목록이 있는 곳이면 어디든 지웁니다.
+pub fn prune_program(program: &mut Program, ctx: &CfgContext) -> usize {
+    let mut pruner = Pruner { ctx, removed: 0 };
+    pruner.visit_program(program);
+    pruner.removed
+}
+
+struct Pruner<'a> {
+    ctx: &'a CfgContext,
+    removed: usize,
+}
+
+impl Pruner<'_> {
+    fn prune_statements(&mut self, statements: &mut Vec<Statement>) {
+        let before = statements.len();
+        statements.retain(|stmt| match stmt {
+            Statement::Attributed { attributes, .. } => self.ctx.is_active(attributes),
+            _ => true,
+        });
+        self.removed += before - statements.len();
+        for stmt in statements.iter_mut() {
+            self.visit_statement(stmt);
+        }
+    }
+}
+
+impl MutVisitor for Pruner<'_> {
+    fn visit_program(&mut self, program: &mut Program) {
+        self.prune_statements(&mut program.statements);
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        match stmt {
+            Statement::BlockStatement { statements, .. } => self.prune_statements(statements),
+            _ => walk_statement_mut(self, stmt),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_service::LexerService;
+    use crate::parser_service::ParserService;
+    use crate::ast_printer;
+
+    fn pruned(source: &str, os: &str) -> (String, usize) {
+        let mut program = ParserService::new(LexerService::new(source)).parse_program();
+        let ctx = CfgContext { target_os: os.into(), ..CfgContext::new("her_vm", &[]) };
+        let removed = prune_program(&mut program, &ctx);
+        (ast_printer::print_program(&program), removed)
+    }
+
+    #[test]
+    fn cfg_items_inside_function_bodies_are_pruned() {
+        let source = "fn sep() {\n@cfg(target = \"linux\") let s = \"/\";\n@cfg(target = \"windows\") let s = \"\\\\\";\nreturn s;\n}\nlet f = fn() { if true { @cfg(target_os = \"windows\") print(1); } return 0; };\nreturn sep();";
+        let (linux, removed) = pruned(source, "linux");
+        assert_eq!(removed, 2, "{}", linux);
+        assert!(linux.contains("\"/\"") && !linux.contains("\\\\") && !linux.contains("print"), "{}", linux);
+        let (windows, removed) = pruned(source, "windows");
+        assert_eq!(removed, 1, "{}", windows);
+        assert!(!windows.contains("\"/\"") && windows.contains("print"), "{}", windows);
+    }
+}
@@ -0,0 +1,300 @@
+// config.rs
+// CLI 설정 계층 (high config)
+//
+// 같은 설정이 여러 곳에 있으면 아래쪽이 이깁니다.
+//
+//   1. 기본값
+//   2. $HIGH_HOME/high.config.toml    HIGH_HOME 이 없으면 ~/.high
+//   3. 프로젝트의 high.config.toml     현재 디렉터리부터 위로 올라가며 처음 찾은 파일
+//   4. 환경 변수                        HIGH_OPT_LEVEL, HIGH_TARGET, HIGH_COLOR, HIGH_NASM, HIGH_LINKER, HIGH_CACHE_DIR
+//   5. 명령줄 플래그                    --opt-level, --target, --color, --nasm, --linker, --cache-dir
+//
+// 설정 파일은 TOML 의 부분 집합입니다. `key = value` 줄과 `[toolchain]` 절, 문자열/정수 값, `#` 주석만 읽습니다.
+//
+//   opt_level = 3
+//   target = "llvm"
+//   color = "never"              # auto, always, never
+//   cache_dir = ".high-cache"
+//
+//   [toolchain]
+//   nasm = "/opt/nasm/bin/nasm"
+//   linker = "ld.lld"
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::compiler_services::CompileOptions;
+use crate::diagnostic_renderer::DiagnosticRenderer;
+
+pub const FILE_NAME: &str = "high.config.toml";
+
+/// 진단 출력 색상
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// 표준 출력이 TTY 이고 NO_COLOR 가 없을 때만
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "auto" => Some(Self::Auto),
+            "always" => Some(Self::Always),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+}
+
+/// 값이 어디서 왔는지
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(&'static str),
+    Flag,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "기본값"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "환경 변수 {}", var),
+            Source::Flag => write!(f, "명령줄 플래그"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T) -> Self {
+        Self { value, source: Source::Default }
+    }
+
+    fn set(&mut self, value: T, source: Source) {
+        *self = Self { value, source };
+    }
+}
+
+/// 모든 계층을 합친 유효 설정
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub opt_level: Setting<u8>,
+    pub target: Setting<String>,
+    pub color: Setting<ColorChoice>,
+    pub nasm: Setting<Option<String>>,
+    pub linker: Setting<Option<String>>,
+    pub cache_dir: Setting<Option<String>>,
+    /// 읽은 설정 파일 (낮은 우선순위부터)
+    pub files: Vec<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            opt_level: Setting::new(2),
+            target: Setting::new("her_vm".into()),
+            color: Setting::new(ColorChoice::Auto),
+            nasm: Setting::new(None),
+            linker: Setting::new(None),
+            cache_dir: Setting::new(None),
+            files: vec![],
+        }
+    }
+}
+
+/// (설정 파일 키, 환경 변수, 명령줄 플래그)
+const KEYS: &[(&str, &str, &str)] = &[
+    ("opt_level", "HIGH_OPT_LEVEL", "--opt-level"),
+    ("target", "HIGH_TARGET", "--target"),
+    ("color", "HIGH_COLOR", "--color"),
+    ("toolchain.nasm", "HIGH_NASM", "--nasm"),
+    ("toolchain.linker", "HIGH_LINKER", "--linker"),
+    ("cache_dir", "HIGH_CACHE_DIR", "--cache-dir"),
+];
+
+impl Config {
+    /// 파일과 환경 변수 계층을 읽습니다. 명령줄 플래그는 `apply_flags` 로 덧씌웁니다.
+    pub fn load() -> Result<Self, String> {
+        let cwd = std::env::current_dir().map_err(|e| format!("현재 디렉터리를 알 수 없습니다: {}", e))?;
+        Self::load_from(home_dir().as_deref(), &cwd, |var| std::env::var(var).ok())
+    }
+
+    /// `load` 의 본체. 홈 디렉터리, 작업 디렉터리, 환경 변수 조회를 밖에서 받습니다.
+    pub fn load_from(home: Option<&Path>, cwd: &Path, env: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let mut config = Self::default();
+        let user_file = match env("HIGH_HOME") {
+            Some(dir) => Some(PathBuf::from(dir).join(FILE_NAME)),
+            None => home.map(|home| home.join(".high").join(FILE_NAME)),
+        };
+        let project_file = cwd.ancestors().map(|dir| dir.join(FILE_NAME)).find(|path| path.is_file());
+        for path in [user_file, project_file].into_iter().flatten() {
+            // 프로젝트가 HIGH_HOME 안에 있으면 같은 파일을 두 번 읽지 않습니다.
+            if path.is_file() && !config.files.contains(&path) {
+                config.read_file(&path)?;
+            }
+        }
+        for (key, var, _) in KEYS {
+            if let Some(value) = env(var) {
+                config.set(key, &value, Source::Env(var)).map_err(|e| format!("{}: {}", var, e))?;
+            }
+        }
+        Ok(config)
+    }
+
+    fn read_file(&mut self, path: &Path) -> Result<(), String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{} 을 읽지 못했습니다: {}", path.display(), e))?;
+        for (key, value) in parse_file(&text).map_err(|e| format!("{}: {}", path.display(), e))? {
+            self.set(&key, &value, Source::File(path.to_path_buf()))
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        self.files.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// 설정 키 하나를 덮어씁니다. 빈 문자열은 도구 경로/캐시 디렉터리를 기본값으로 되돌립니다.
+    fn set(&mut self, key: &str, value: &str, source: Source) -> Result<(), String> {
+        let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        match key {
+            "opt_level" => match value.parse() {
+                Ok(level @ 0..=3) => self.opt_level.set(level, source),
+                _ => return Err(format!("opt_level 은 0-3 이어야 합니다: {}", value)),
+            },
+            "target" => self.target.set(value.into(), source),
+            "color" => {
+                let choice = ColorChoice::parse(value).ok_or(format!("color 는 auto, always, never 중 하나입니다: {}", value))?;
+                self.color.set(choice, source);
+            }
+            "toolchain.nasm" => self.nasm.set(optional(value), source),
+            "toolchain.linker" => self.linker.set(optional(value), source),
+            "cache_dir" => self.cache_dir.set(optional(value), source),
+            other => return Err(format!("알 수 없는 설정 키: {}", other)),
+        }
+        Ok(())
+    }
+
+    /// 설정에 대응하는 명령줄 플래그를 가장 높은 우선순위로 적용합니다. 다른 인자는 건너뜁니다.
+    pub fn apply_flags(&mut self, args: &[String]) -> Result<(), String> {
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if let Some((key, _, flag)) = KEYS.iter().find(|(_, _, flag)| flag == arg) {
+                let value = iter.next().ok_or(format!("{} 뒤에 값이 필요합니다.", flag))?;
+                self.set(key, value, Source::Flag)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 플래그 파서가 이미 처리한 설정 플래그인지 (값 하나를 함께 받습니다)
+    pub fn is_flag(arg: &str) -> bool {
+        KEYS.iter().any(|(_, _, flag)| *flag == arg)
+    }
+
+    /// 설정 값으로 채운 CompileOptions. 나머지 플래그는 호출자가 그 위에 적용합니다.
+    pub fn compile_options(&self) -> CompileOptions {
+        let mut options = CompileOptions {
+            target_platform: self.target.value.clone(),
+            optimization_level: self.opt_level.value,
+            emit_native: true,
+            cache_dir: self.cache_dir.value.clone(),
+            ..Default::default()
+        };
+        options.tool_paths.assembler = self.nasm.value.clone();
+        options.tool_paths.linker = self.linker.value.clone();
+        options
+    }
+
+    pub fn renderer(&self) -> DiagnosticRenderer {
+        match self.color.value {
+            ColorChoice::Auto => DiagnosticRenderer::for_stdout(),
+            ColorChoice::Always => DiagnosticRenderer::new(true),
+            ColorChoice::Never => DiagnosticRenderer::new(false),
+        }
+    }
+
+    /// `high config` 출력. 키마다 유효 값과 출처를 한 줄씩 씁니다.
+    pub fn describe(&self) -> String {
+        let unset = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".into());
+        let rows = [
+            ("opt_level", self.opt_level.value.to_string(), &self.opt_level.source),
+            ("target", self.target.value.clone(), &self.target.source),
+            ("color", self.color.value.name().to_string(), &self.color.source),
+            ("toolchain.nasm", unset(&self.nasm.value), &self.nasm.source),
+            ("toolchain.linker", unset(&self.linker.value), &self.linker.source),
+            ("cache_dir", unset(&self.cache_dir.value), &self.cache_dir.source),
+        ];
+        let mut out = String::new();
+        for (key, value, source) in rows {
+            out.push_str(&format!("{:<18} = {:<20} # {}\n", key, value, source));
+        }
+        if self.files.is_empty() {
+            out.push_str(&format!("\n읽은 설정 파일 없음 ({})\n", FILE_NAME));
+        } else {
+            out.push_str("\n읽은 설정 파일:\n");
+            for path in &self.files {
+                out.push_str(&format!("  {}\n", path.display()));
+            }
+        }
+        out
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from)
+}
+
+/// `(절.키, 값)` 목록. 문자열 값은 따옴표를 벗깁니다.
+fn parse_file(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut entries = vec![];
+    let mut section = String::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let at = |message: &str| format!("{}번째 줄: {}", number + 1, message);
+        if let Some(name) = line.strip_prefix('[') {
+            section = name.strip_suffix(']').ok_or_else(|| at("절 이름이 ']' 로 끝나지 않습니다"))?.trim().to_string();
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| at("`key = value` 형식이 아닙니다"))?;
+        let value = value.trim();
+        let value = match value.strip_prefix('"') {
+            Some(quoted) => quoted.strip_suffix('"').ok_or_else(|| at("문자열이 닫히지 않았습니다"))?,
+            None => value,
+        };
+        let key = key.trim();
+        let key = if section.is_empty() { key.to_string() } else { format!("{}.{}", section, key) };
+        entries.push((key, value.to_string()));
+    }
+    Ok(entries)
+}
+
+/// 문자열 밖의 `#` 부터 줄 끝까지 버립니다.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
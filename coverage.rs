@@ -0,0 +1,171 @@
+// coverage.rs
+// 문장 단위 코드 커버리지 (high run --coverage, high test --coverage)
+//
+// 런타임이 문장을 실행할 때마다 그 문장의 범위(span)에 적중 횟수를 더하고, 보고서는 소스의 모든 문장과
+// 맞춰 봅니다. 블록과 `@attr` 는 그 안의 문장으로 세며, 매크로 정의 본문은 세지 않습니다.
+// 커버리지를 켠 런타임은 함수를 바이트코드로 올리지 않으므로 모든 문장이 인터프리터를 지나갑니다.
+//
+// 보고서 형식
+//   lcov   SF/DA/LF/LH 레코드. 문장이 시작하는 줄마다 그 줄 문장들의 가장 큰 적중 수를 씁니다.
+//   json   {"files": [{"path", "covered", "total", "percent", "statements": [{"line", "start", "end", "hits"}]}]}
+
+use alloc::collections::BTreeMap;
+
+use crate::compat::*;
+use crate::data_structures::{Program, Span, Statement};
+use crate::visitor::{walk_statement, Visitor};
+
+/// 실행 중에 모으는 적중 횟수. 키는 문장 범위 (start, end) 입니다.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    hits: BTreeMap<(usize, usize), u64>,
+}
+
+impl Coverage {
+    pub fn hit(&mut self, stmt: &Statement) {
+        if counted(stmt) {
+            let span = stmt.span();
+            *self.hits.entry((span.start, span.end)).or_insert(0) += 1;
+        }
+    }
+
+    pub fn hits(&self, span: Span) -> u64 {
+        self.hits.get(&(span.start, span.end)).copied().unwrap_or(0)
+    }
+
+    /// `program` 의 모든 문장과 적중 횟수를 맞춘 파일 하나의 보고서
+    pub fn report(&self, path: &str, source: &str, program: &Program) -> FileCoverage {
+        let mut collector = Collector(vec![]);
+        collector.visit_program(program);
+        let mut spans = collector.0;
+        spans.sort_by_key(|span| (span.start, span.end));
+        spans.dedup_by_key(|span| (span.start, span.end));
+        let statements = spans
+            .into_iter()
+            .map(|span| StatementCoverage { span, line: line_of(source, span.start), hits: self.hits(span) })
+            .collect();
+        FileCoverage { path: path.to_string(), statements }
+    }
+}
+
+/// 블록과 어트리뷰트 래퍼는 안쪽 문장으로 셉니다.
+fn counted(stmt: &Statement) -> bool {
+    !matches!(stmt, Statement::BlockStatement { .. } | Statement::Attributed { .. })
+}
+
+struct Collector(Vec<Span>);
+
+impl Visitor for Collector {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Statement::MacroDefinition { .. } = stmt {
+            return;
+        }
+        if counted(stmt) {
+            self.0.push(stmt.span());
+        }
+        walk_statement(self, stmt);
+    }
+}
+
+/// 1부터 세는 줄 번호
+fn line_of(source: &str, offset: usize) -> usize {
+    source.as_bytes()[..offset.min(source.len())].iter().filter(|&&b| b == b'\n').count() + 1
+}
+
+#[derive(Debug, Clone)]
+pub struct StatementCoverage {
+    pub span: Span,
+    pub line: usize,
+    pub hits: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileCoverage {
+    pub path: String,
+    pub statements: Vec<StatementCoverage>,
+}
+
+impl FileCoverage {
+    pub fn covered(&self) -> usize {
+        self.statements.iter().filter(|s| s.hits > 0).count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.statements.len()
+    }
+
+    /// 문장이 없으면 100 입니다.
+    pub fn percent(&self) -> f64 {
+        if self.statements.is_empty() {
+            100.0
+        } else {
+            self.covered() as f64 * 100.0 / self.total() as f64
+        }
+    }
+
+    /// `sample.high: 12/15 statements (80.0%)`
+    pub fn summary(&self) -> String {
+        format!("{}: {}/{} statements ({:.1}%)", self.path, self.covered(), self.total(), self.percent())
+    }
+
+    /// 실행되지 않은 문장이 있는 줄 번호
+    pub fn missed_lines(&self) -> Vec<usize> {
+        let mut lines: Vec<usize> = self.statements.iter().filter(|s| s.hits == 0).map(|s| s.line).collect();
+        lines.dedup();
+        lines
+    }
+
+    /// lcov 트레이스 파일 레코드 하나
+    pub fn lcov(&self) -> String {
+        let mut lines: BTreeMap<usize, u64> = BTreeMap::new();
+        for statement in &self.statements {
+            let hits = lines.entry(statement.line).or_insert(0);
+            *hits = (*hits).max(statement.hits);
+        }
+        let mut out = format!("TN:\nSF:{}\n", self.path);
+        for (line, hits) in &lines {
+            out.push_str(&format!("DA:{},{}\n", line, hits));
+        }
+        out.push_str(&format!("LF:{}\nLH:{}\nend_of_record\n", lines.len(), lines.values().filter(|&&h| h > 0).count()));
+        out
+    }
+
+    #[cfg(feature = "cli")]
+    pub fn to_json(&self) -> crate::json::Json {
+        use crate::json::Json;
+        Json::object([
+            ("path", Json::String(self.path.clone())),
+            ("covered", Json::Number(self.covered() as f64)),
+            ("total", Json::Number(self.total() as f64)),
+            ("percent", Json::Number(self.percent())),
+            (
+                "statements",
+                Json::Array(
+                    self.statements
+                        .iter()
+                        .map(|s| {
+                            Json::object([
+                                ("line", Json::Number(s.line as f64)),
+                                ("start", Json::Number(s.span.start as f64)),
+                                ("end", Json::Number(s.span.end as f64)),
+                                ("hits", Json::Number(s.hits as f64)),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+/// `--coverage-out` 경로의 확장자가 `.json` 이면 JSON, 아니면 lcov 로 씁니다.
+#[cfg(feature = "cli")]
+pub fn write_report(files: &[FileCoverage], out_path: &str) -> Result<(), String> {
+    let text = if out_path.ends_with(".json") {
+        let files = crate::json::Json::Array(files.iter().map(FileCoverage::to_json).collect());
+        format!("{}\n", crate::json::Json::object([("files", files)]))
+    } else {
+        files.iter().map(FileCoverage::lcov).collect()
+    };
+    std::fs::write(out_path, text).map_err(|e| format!("'{}' 에 커버리지 보고서를 쓰지 못했습니다: {}", out_path, e))
+}
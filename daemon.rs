@@ -0,0 +1,486 @@
+// daemon.rs
+// `high daemon`: CompilerService 를 오래 떠 있는 JSON-RPC 2.0 서버로 띄웁니다.
+// IDE 통합과 빌드 시스템이 요청마다 프로세스를 새로 띄우지 않도록 하고, 캐시를 요청 사이에 공유합니다.
+//
+// 전송: TCP, 메시지 하나가 한 줄 (줄바꿈으로 구분한 JSON)
+//
+//   compile   {source, opt?, no_std?, emit?: ["rust", "ir", "expanded"]}
+//             → 플레이그라운드와 같은 결과 객체 + "cached"
+//   cancel    {id}   요청을 취소합니다. 처리 중이면 컴파일/실행을 멈추고, 취소된 요청은 -32800 으로 응답합니다.
+//   rename    {source, offset, new_name}   → {edits: [{start, end, new_text}]}
+//   codeAction {source, start, end, name?} → [{title, kind, edits} | {title, kind, disabled: {reason}}]
+//             선택 범위에 쓸 수 있는 리팩터링 (지금은 함수 추출). 위치는 문자 위치입니다.
+//   references {source, offset}          → {name, locations: [{start, end, declaration}]}
+//   callHierarchy {source, offset}       → {item, incoming: [{from, ranges}], outgoing: [{to, ranges}]}
+//   completion {source, offset}          → [{label, kind, detail, insert, start, end}] (completion.rs)
+//   semanticTokens {source}               → {legend: {tokenTypes, tokenModifiers}, data} (LSP 와 같은 인코딩)
+//   stats     {}     처리한 요청 수, 캐시 적중 수 등
+//   shutdown  {}     새 요청을 받지 않고, 앞서 받은 요청을 마친 뒤 종료합니다.
+//
+// 컴파일은 한 작업자가 받은 순서대로 처리합니다 (실행기의 Engine 은 스레드 사이를 옮길 수 없음). 연결 읽기는 별도 작업이므로 컴파일 중에도 cancel 을 받을 수 있습니다.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::cancellation::CancellationToken;
+use crate::compiler_services::{CompileOptions, CompileRequest, CompilerService};
+use crate::executor_service::ExecutionStatus;
+use crate::json::Json;
+use crate::playground_server::result_json;
+use crate::completion;
+use crate::navigation;
+use crate::refactor;
+use crate::semantic_tokens;
+
+pub const DEFAULT_PORT: u16 = 7420;
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// 캐시에 남기는 컴파일 결과 수. 넘치면 가장 오래된 결과부터 버립니다.
+const CACHE_CAPACITY: usize = 128;
+
+// JSON-RPC 오류 코드 (-32800 은 LSP 의 RequestCancelled 와 같습니다)
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const SHUTTING_DOWN: i64 = -32000;
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+/// JSON-RPC 오류 응답
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub(crate) fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
+
+type Writer = Arc<tokio::sync::Mutex<OwnedWriteHalf>>;
+
+/// 작업자에게 넘어가는 요청 하나
+struct Job {
+    id: Json,
+    method: String,
+    params: Json,
+    writer: Writer,
+}
+
+/// 연결 작업과 작업자가 함께 보는 상태
+#[derive(Default)]
+struct Shared {
+    /// 취소 요청을 받았지만 아직 작업자가 꺼내지 않은 요청 id (JSON 표기)
+    cancelled: HashSet<String>,
+    /// 작업자가 처리 중인 요청 id 와 그 취소 토큰
+    running: Option<(String, CancellationToken)>,
+    shutting_down: bool,
+}
+
+/// 같은 소스와 옵션의 컴파일 결과
+#[derive(Default)]
+struct ResultCache {
+    entries: HashMap<u64, Json>,
+    order: VecDeque<u64>,
+}
+
+impl ResultCache {
+    fn key(source: &str, options: &CompileOptions) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        format!("{:?}", options).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn insert(&mut self, key: u64, result: Json) {
+        if self.entries.insert(key, result).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+pub struct CompilerDaemon {
+    compiler: CompilerService,
+    cache: ResultCache,
+    started: Instant,
+    requests: u64,
+    cache_hits: u64,
+}
+
+impl CompilerDaemon {
+    pub fn new() -> Self {
+        Self {
+            compiler: CompilerService::new(),
+            cache: ResultCache::default(),
+            started: Instant::now(),
+            requests: 0,
+            cache_hits: 0,
+        }
+    }
+
+    /// `host:port` 에서 요청을 받습니다. `shutdown` 요청이나 Ctrl-C 를 받으면 돌아옵니다.
+    pub async fn serve(&mut self, host: &str, port: u16) -> io::Result<()> {
+        let listener = TcpListener::bind((host, port)).await?;
+        println!("[Daemon] {} 에서 JSON-RPC 요청을 기다립니다.", listener.local_addr()?);
+
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let (jobs, mut queue) = mpsc::unbounded_channel::<Job>();
+        let acceptor = tokio::spawn(accept_loop(listener, jobs, shared.clone()));
+
+        loop {
+            let job = tokio::select! {
+                job = queue.recv() => job,
+                _ = tokio::signal::ctrl_c() => None,
+            };
+            let Some(job) = job else { break };
+
+            let key = job.id.to_string();
+            let token = CancellationToken::new();
+            let cancelled = {
+                let mut state = lock(&shared);
+                state.running = Some((key.clone(), token.clone()));
+                state.cancelled.remove(&key)
+            };
+            let response = if cancelled {
+                Err(RpcError::new(REQUEST_CANCELLED, "요청이 취소되었습니다."))
+            } else {
+                self.dispatch(&job.method, &job.params, &token).await
+            };
+            lock(&shared).running = None;
+            respond(&job.writer, &job.id, response).await;
+            if job.method == "shutdown" {
+                break;
+            }
+        }
+
+        // 정상 종료: 새 연결과 요청을 막고, 이미 줄에 선 요청에는 종료 중이라고 답합니다.
+        lock(&shared).shutting_down = true;
+        acceptor.abort();
+        queue.close();
+        while let Some(job) = queue.recv().await {
+            respond(&job.writer, &job.id, Err(RpcError::new(SHUTTING_DOWN, "데몬이 종료 중입니다."))).await;
+        }
+        println!("[Daemon] 종료합니다. (요청 {}개, 캐시 적중 {}개)", self.requests, self.cache_hits);
+        Ok(())
+    }
+
+    async fn dispatch(&mut self, method: &str, params: &Json, token: &CancellationToken) -> Result<Json, RpcError> {
+        self.requests += 1;
+        match method {
+            "compile" => self.compile(params, token).await,
+            "rename" => rename(params),
+            "codeAction" => code_actions(params),
+            "references" => navigate(params, |source, offset| navigation::references(source, offset).map(|r| r.to_json())),
+            "completion" => navigate(params, |source, offset| Ok(completion::to_json(&completion::complete(source, offset)))),
+            "callHierarchy" => navigate(params, |source, offset| navigation::call_hierarchy(source, offset).map(|h| h.to_json())),
+            "semanticTokens" => string_param(params, "source").map(semantic_tokens::to_json),
+            "stats" => Ok(Json::object([
+                ("requests", Json::from(self.requests)),
+                ("cache_hits", Json::from(self.cache_hits)),
+                ("cache_entries", Json::from(self.cache.entries.len() as u64)),
+                ("uptime_ms", Json::from(self.started.elapsed().as_millis() as u64)),
+            ])),
+            "shutdown" => Ok(Json::Null),
+            other => Err(RpcError::new(METHOD_NOT_FOUND, format!("알 수 없는 메서드: {}", other))),
+        }
+    }
+
+    async fn compile(&mut self, params: &Json, token: &CancellationToken) -> Result<Json, RpcError> {
+        let source = params
+            .get("source")
+            .and_then(Json::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "params.source 문자열이 필요합니다."))?
+            .to_string();
+        let options = compile_options(params)?;
+
+        let key = ResultCache::key(&source, &options);
+        if let Some(cached) = self.cache.entries.get(&key) {
+            self.cache_hits += 1;
+            return Ok(with_cached_flag(cached.clone(), true));
+        }
+
+        let result = self
+            .compiler
+            .compile_cancellable(CompileRequest { source_code: source.clone(), options }, token)
+            .await;
+        if matches!(result.execution_status, ExecutionStatus::Cancelled) {
+            return Err(RpcError::new(REQUEST_CANCELLED, "요청이 취소되었습니다."));
+        }
+        let json = result_json(&result, &source);
+        // 제한 시간 초과는 그때의 부하에 따라 달라지므로 캐시하지 않습니다.
+        if !matches!(result.execution_status, ExecutionStatus::TimedOut) {
+            self.cache.insert(key, json.clone());
+        }
+        Ok(with_cached_flag(json, false))
+    }
+}
+
+impl Default for CompilerDaemon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn compile_options(params: &Json) -> Result<CompileOptions, RpcError> {
+    let mut options = CompileOptions::default();
+    if let Some(level) = params.get("opt") {
+        match level.as_u64() {
+            Some(level @ 0..=3) => options.optimization_level = level as u8,
+            _ => return Err(RpcError::new(INVALID_PARAMS, "params.opt 는 0-3 이어야 합니다.")),
+        }
+    }
+    options.no_std = params.get("no_std").and_then(Json::as_bool).unwrap_or(false);
+    for item in params.get("emit").and_then(Json::as_array).unwrap_or(&[]) {
+        match item.as_str() {
+            Some("rust") => options.emit_rust_source = true,
+            Some("ir") => options.emit_ir = true,
+            Some("expanded") => options.emit_expanded = true,
+            _ => return Err(RpcError::new(INVALID_PARAMS, format!("알 수 없는 emit 항목: {}", item))),
+        }
+    }
+    Ok(options)
+}
+
+fn navigate(params: &Json, query: impl FnOnce(&str, usize) -> Result<Json, String>) -> Result<Json, RpcError> {
+    let source = string_param(params, "source")?;
+    let offset = offset_param(params, "offset")?;
+    query(source, offset).map_err(|e| RpcError::new(INVALID_PARAMS, e))
+}
+
+fn rename(params: &Json) -> Result<Json, RpcError> {
+    let source = string_param(params, "source")?;
+    let offset = offset_param(params, "offset")?;
+    let new_name = string_param(params, "new_name")?;
+    refactor::rename(source, offset, new_name)
+        .map(|edits| refactor::edits_json(&edits))
+        .map_err(|e| RpcError::new(INVALID_PARAMS, e))
+}
+
+/// LSP codeAction 처럼 선택 범위에 쓸 수 있는 동작 목록. 쓸 수 없으면 이유와 함께 `disabled` 로 돌려줍니다.
+fn code_actions(params: &Json) -> Result<Json, RpcError> {
+    let source = string_param(params, "source")?;
+    let (start, end) = (offset_param(params, "start")?, offset_param(params, "end")?);
+    let name = match params.get("name").and_then(Json::as_str) {
+        Some(name) => name.to_string(),
+        None => refactor::fresh_name(source, "extracted"),
+    };
+    let mut action = vec![
+        ("title".to_string(), Json::from(format!("'{}' 함수로 추출", name))),
+        ("kind".to_string(), Json::from("refactor.extract.function")),
+    ];
+    match refactor::extract_function(source, start, end, &name) {
+        Ok(edits) => action.push(("edits".into(), Json::Array(edits.iter().map(refactor::TextEdit::to_json).collect()))),
+        Err(reason) => action.push(("disabled".into(), Json::object([("reason", Json::from(reason))]))),
+    }
+    Ok(Json::Array(vec![Json::Object(action)]))
+}
+
+fn string_param<'p>(params: &'p Json, key: &str) -> Result<&'p str, RpcError> {
+    params
+        .get(key)
+        .and_then(Json::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("params.{} 문자열이 필요합니다.", key)))
+}
+
+fn offset_param(params: &Json, key: &str) -> Result<usize, RpcError> {
+    params
+        .get(key)
+        .and_then(Json::as_u64)
+        .map(|n| n as usize)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("params.{} 는 0 이상의 정수여야 합니다.", key)))
+}
+
+fn with_cached_flag(mut json: Json, cached: bool) -> Json {
+    if let Json::Object(entries) = &mut json {
+        entries.push(("cached".into(), Json::from(cached)));
+    }
+    json
+}
+
+fn lock(shared: &Mutex<Shared>) -> std::sync::MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// ─── 연결 처리 ─────────────────────────────
+
+async fn accept_loop(listener: TcpListener, jobs: mpsc::UnboundedSender<Job>, shared: Arc<Mutex<Shared>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                println!("[Daemon] 연결: {}", peer);
+                tokio::spawn(connection(stream, jobs.clone(), shared.clone()));
+            }
+            Err(e) => println!("[Daemon] 연결 수락 실패: {}", e),
+        }
+    }
+}
+
+/// 한 연결의 요청 줄을 읽어 작업자에게 넘깁니다. `cancel` 은 작업자를 기다리지 않고 여기서 처리합니다.
+async fn connection(stream: TcpStream, jobs: mpsc::UnboundedSender<Job>, shared: Arc<Mutex<Shared>>) {
+    let (reader, writer) = stream.into_split();
+    let writer: Writer = Arc::new(tokio::sync::Mutex::new(writer));
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message = match Json::parse(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                respond(&writer, &Json::Null, Err(RpcError::new(PARSE_ERROR, e))).await;
+                continue;
+            }
+        };
+        let id = message.get("id").cloned().unwrap_or(Json::Null);
+        let Some(method) = message.get("method").and_then(Json::as_str) else {
+            respond(&writer, &id, Err(RpcError::new(INVALID_REQUEST, "method 가 필요합니다."))).await;
+            continue;
+        };
+        let params = message.get("params").cloned().unwrap_or(Json::object::<&str>([]));
+
+        if lock(&shared).shutting_down {
+            respond(&writer, &id, Err(RpcError::new(SHUTTING_DOWN, "데몬이 종료 중입니다."))).await;
+            continue;
+        }
+        if method == "cancel" {
+            let response = match params.get("id") {
+                Some(target) => {
+                    let key = target.to_string();
+                    let mut state = lock(&shared);
+                    match &state.running {
+                        Some((running, token)) if *running == key => token.cancel(),
+                        _ => {
+                            state.cancelled.insert(key);
+                        }
+                    }
+                    Ok(Json::Null)
+                }
+                None => Err(RpcError::new(INVALID_PARAMS, "params.id 가 필요합니다.")),
+            };
+            respond(&writer, &id, response).await;
+            continue;
+        }
+
+        let job = Job { id, method: method.to_string(), params, writer: writer.clone() };
+        if let Err(rejected) = jobs.send(job) {
+            let job = rejected.0;
+            respond(&job.writer, &job.id, Err(RpcError::new(SHUTTING_DOWN, "데몬이 종료 중입니다."))).await;
+        }
+    }
+}
+
+async fn respond(writer: &Writer, id: &Json, response: Result<Json, RpcError>) {
+    let body = match response {
+        Ok(result) => ("result", result),
+        Err(error) => (
+            "error",
+            Json::object([("code", Json::Number(error.code as f64)), ("message", Json::from(error.message))]),
+        ),
+    };
+    let message = Json::object([("jsonrpc", Json::from("2.0")), ("id", id.clone()), body]);
+    let mut line = message.to_string();
+    line.push('\n');
+    // 연결이 이미 끊겼다면 응답을 버립니다.
+    let _ = writer.lock().await.write_all(line.as_bytes()).await;
+}
+
+// ─── 클라이언트 ─────────────────────────────
+
+/// 데몬에 붙는 클라이언트. `call` 은 응답을 기다리고, `send`/`receive` 로 여러 요청을 겹쳐 보낼 수 있습니다.
+pub struct DaemonClient {
+    lines: tokio::io::Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    next_id: u64,
+}
+
+impl DaemonClient {
+    pub async fn connect(address: &str) -> io::Result<Self> {
+        let (reader, writer) = TcpStream::connect(address).await?.into_split();
+        Ok(Self { lines: BufReader::new(reader).lines(), writer, next_id: 1 })
+    }
+
+    /// 요청을 보내고 그 id 를 돌려줍니다.
+    pub async fn send(&mut self, method: &str, params: Json) -> io::Result<u64> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = Json::object([
+            ("jsonrpc", Json::from("2.0")),
+            ("id", Json::from(id)),
+            ("method", Json::from(method)),
+            ("params", params),
+        ]);
+        self.writer.write_all(format!("{}\n", message).as_bytes()).await?;
+        Ok(id)
+    }
+
+    /// 다음 응답을 읽습니다. 응답의 id 와 결과를 돌려줍니다.
+    pub async fn receive(&mut self) -> io::Result<(Option<u64>, Result<Json, RpcError>)> {
+        let line = self
+            .lines
+            .next_line()
+            .await?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "데몬 연결이 끊겼습니다."))?;
+        let message = Json::parse(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let id = message.get("id").and_then(Json::as_u64);
+        let response = match (message.get("result"), message.get("error")) {
+            (_, Some(error)) => Err(RpcError {
+                code: match error.get("code") {
+                    Some(Json::Number(code)) => *code as i64,
+                    _ => INVALID_REQUEST,
+                },
+                message: error.get("message").and_then(Json::as_str).unwrap_or("").to_string(),
+            }),
+            (Some(result), None) => Ok(result.clone()),
+            (None, None) => Err(RpcError::new(INVALID_REQUEST, "result 도 error 도 없는 응답입니다.")),
+        };
+        Ok((id, response))
+    }
+
+    /// 요청을 보내고 그 응답을 기다립니다. 사이에 온 다른 응답은 버립니다.
+    pub async fn call(&mut self, method: &str, params: Json) -> io::Result<Result<Json, RpcError>> {
+        let id = self.send(method, params).await?;
+        loop {
+            let (response_id, response) = self.receive().await?;
+            if response_id == Some(id) {
+                return Ok(response);
+            }
+        }
+    }
+
+    pub async fn compile(&mut self, source: &str) -> io::Result<Result<Json, RpcError>> {
+        self.call("compile", Json::object([("source", Json::from(source))])).await
+    }
+
+    /// 앞서 `send` 로 보낸 요청을 취소합니다. 취소 요청 자신의 id 를 돌려줍니다.
+    pub async fn cancel(&mut self, id: u64) -> io::Result<u64> {
+        self.send("cancel", Json::object([("id", Json::from(id))])).await
+    }
+
+    pub async fn shutdown(&mut self) -> io::Result<Result<Json, RpcError>> {
+        self.call("shutdown", Json::object::<&str>([])).await
+    }
+}
@@ -1,251 +1,672 @@
-use std::fmt;
-
-//
-// ─── 런타임 값 ────────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-pub enum Value {
-    Integer(i64),
-    Float(f64),
-    Boolean(bool),
-    String(String),
-    Function(Box<FunctionValue>),
-    Null,
-    Return(Box<Value>),
-    Error(String),
-    Reflection(ReflectionInfo),
-    Macro(String), // 매크로 이름 또는 본문
-    Type(String),  // 런타임 타입 표현
-}
-
-#[derive(Debug, Clone)]
-pub struct FunctionValue {
-    pub parameters: Vec<String>,
-    pub body: Statement,
-}
-
-#[derive(Debug, Clone)]
-pub struct ReflectionInfo {
-    pub type_name: String,
-    pub details: String,
-}
-
-//
-// ─── 타입 시스템 ─────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-pub enum TypeAnnotation {
-    Int,
-    Float,
-    Bool,
-    String,
-    Void,
-    Any,
-    Custom(String),
-    Infer,
-}
-
-//
-// ─── 토큰 ─────────────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-
-
-pub enum TokenKind {
-    // ─── 리터럴 ─────────────────────────────
-    IntegerLiteral(i64),
-    FloatLiteral(String),
-    StringLiteral(String),
-    BooleanLiteral(bool),
-
-    // ─── 식별자 ─────────────────────────────
-    Identifier(String),
-
-    // ─── 키워드 ─────────────────────────────
-    Fn,
-    Let,
-    Mut,
-    If,
-    Else,
-    While,
-    For,
-    Return,
-    Match,
-    Macro,
-    TypeOf,
-    Eval,
-    Reflect,
-    Async,
-    Await,
-    True,
-    False,
-
-    // ─── 타입 키워드 ────────────────────────
-    Int,
-    Float,
-    Bool,
-    String,
-    Void,
-    Any,
-
-    // ─── 산술 연산자 ────────────────────────
-    Plus,
-    Minus,
-    Asterisk,
-    Slash,
-    Percent,
-
-    // ─── 비교 연산자 ────────────────────────
-    Eq,
-    Neq,
-    Less,
-    Greater,
-    LessEqual,
-    GreaterEqual,
-
-    // ─── 논리 연산자 ────────────────────────
-    And,
-    Or,
-    Bang,
-
-    // ─── 비트 연산자 ────────────────────────
-    BitAnd,
-    BitOr,
-    BitXor,
-    ShiftLeft,
-    ShiftRight,
-
-    // ─── 대입 연산자 ────────────────────────
-    Assign,
-    PlusAssign,
-    MinusAssign,
-
-    // ─── 삼항 연산자 ────────────────────────
-    Question,
-    Colon,
-
-    // ─── 구문 기호 ──────────────────────────
-    Comma,
-    Semicolon,
-    Dot,
-    Arrow,
-
-    // ─── 괄호 ───────────────────────────────
-    LParen,
-    RParen,
-    LBrace,
-    RBrace,
-    LBracket,
-    RBracket,
-
-    // ─── 기타 ───────────────────────────────
-    Eof,
-    Illegal(char),
-}
-
-
-#[derive(Debug, Clone)]
-pub struct Token {
-    pub kind: TokenKind,
-    pub span: Span,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Span {
-    pub start: usize,
-    pub end: usize,
-}
-
-//
-// ─── 표현식 ───────────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-pub enum Expression {
-    Literal(Span, Value),
-    Identifier(Span, String),
-    PrefixOperation(Span, TokenKind, Box<Expression>),
-    InfixOperation(Span, TokenKind, Box<Expression>, Box<Expression>),
-    Ternary(Span, Box<Expression>, Box<Expression>, Box<Expression>),
-    Function(Span, Vec<String>, Box<Statement>),
-    Call(Span, Box<Expression>, Vec<Box<Expression>>),
-    Grouped(Span, Box<Expression>),
-    Reflect(Span, Box<Expression>),
-    Eval(Span, Box<Expression>),
-    TypeOf(Span, Box<Expression>),
-    MacroCall(Span, String, Vec<Box<Expression>>),
-}
-
-//
-// ─── 문장 ─────────────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-pub enum Statement {
-    ExpressionStatement(Box<Expression>),
-    LetStatement {
-        name: String,
-        value: Box<Expression>,
-        type_annotation: Option<TypeAnnotation>,
-        is_mutable: bool,
-    },
-    ReturnStatement(Box<Expression>),
-    BlockStatement {
-        statements: Vec<Box<Statement>>,
-        span: Span,
-    },
-    IfStatement {
-        condition: Box<Expression>,
-        then_branch: Box<Statement>,
-        else_branch: Option<Box<Statement>>,
-    },
-    WhileStatement {
-        condition: Box<Expression>,
-        body: Box<Statement>,
-    },
-    ForStatement {
-        initializer: Option<Box<Statement>>,
-        condition: Option<Box<Expression>>,
-        increment: Option<Box<Expression>>,
-        body: Box<Statement>,
-    },
-    MacroDefinition {
-        name: String,
-        parameters: Vec<String>,
-        body: Box<Statement>,
-    },
-}
-
-//
-// ─── 프로그램 ─────────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-pub struct Program {
-    pub root_id: usize,
-    pub statements: Vec<Box<Statement>>,
-    pub span: Span,
-}
-
-//
-// ─── 진단 ─────────────────────────────────────────────────────────────────────
-//
-
-#[derive(Debug, Clone)]
-pub enum DiagnosticLevel {
-    Info,
-    Warning,
-    Error,
-    HerFatal,
-}
-
-#[derive(Debug, Clone)]
-pub struct Diagnostic {
-    pub level: DiagnosticLevel,
-    pub message: String,
-    pub span: Span,
-    pub help: Option<String>,
-}
+use alloc::borrow::Cow;
+use core::cell::RefCell;
+use core::fmt;
+use alloc::rc::{Rc, Weak};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::bytecode::Constant;
+use crate::bytes;
+use crate::maps;
+use crate::datetime::DateTime;
+use crate::regexp::RegexMatch;
+use crate::compat::*;
+use crate::generator::GeneratorState;
+use crate::interner::Symbol;
+
+//
+// ─── 런타임 값 ────────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// 복사해도 문자열을 새로 할당하지 않습니다.
+    String(Arc<str>),
+    Function(Box<FunctionValue>),
+    Null,
+    Return(Box<Value>),
+    Error(String),
+    Reflection(ReflectionInfo),
+    Macro(String), // 매크로 이름 또는 본문
+    Type(String),  // 런타임 타입 표현
+    Generator(Rc<RefCell<GeneratorState>>), // yield 로 값을 하나씩 내놓는 지연 시퀀스
+    WeakRef(Weak<RefCell<GeneratorState>>), // weak_ref() 결과: 대상을 살려 두지 않는 참조 (heap.rs)
+    Bytes(Arc<[u8]>), // 바이트 버퍼 (bytes.rs). 복사해도 내용을 새로 할당하지 않습니다.
+    Match(Arc<RegexMatch>),     // regex_match() 결과: 그룹과 위치 (regexp.rs)
+    Matches(Arc<[RegexMatch]>), // regex_find_all() 결과
+    DateTime(DateTime), // 오프셋을 가진 시각 (datetime.rs)
+    Duration(i64),      // 기간 (밀리초)
+    Record(Rc<Record>), // 내장 함수가 돌려주는 이름 붙은 필드 묶음 (`r["field"]`)
+    Array(Arc<[Value]>), // `[1, 2, 3]` 배열 (arrays.rs). 복사해도 요소를 새로 할당하지 않습니다.
+    Map(Rc<maps::Map>), // `{ "a": 1 }` 맵 (maps.rs). 넣은 순서를 기억합니다.
+    EnumVariant(Rc<EnumVariant>), // `Red`, `Rgb(1, 2, 3)` enum 변형 값 또는 변형 생성자
+    Range(i64, i64), // `start..end` 정수 범위 (ranges.rs). 끝은 포함하지 않습니다.
+}
+
+/// `enum` 선언이 만든 변형. 필드가 있는 변형의 이름은 생성자에 바인딩되며, 불러야 값이 됩니다.
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub enum_name: Symbol,
+    pub name: Symbol,
+    /// 선언한 필드 타입. 생성자가 인자를 강제 변환할 때 씁니다.
+    pub field_types: Vec<TypeAnnotation>,
+    /// 생성자면 None 입니다. 필드 없는 변형은 처음부터 `Some(vec![])` 인 값입니다.
+    pub fields: Option<Vec<Value>>,
+    /// 선언에 붙은 `@derive(...)`
+    pub derives: Derives,
+}
+
+/// `@derive(eq, show, clone)` 로 자동 구현한 동작. 없는 동작의 `==`, 문자열 변환, `clone()` 은 오류입니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Derives {
+    pub eq: bool,
+    pub show: bool,
+    pub clone: bool,
+}
+
+impl Derives {
+    /// `attributes::derived` 가 돌려준 이름들로 만듭니다. 알 수 없는 이름은 무시합니다.
+    pub fn from_names<'n>(names: impl IntoIterator<Item = &'n str>) -> Self {
+        let mut derives = Derives::default();
+        for name in names {
+            match name {
+                "eq" => derives.eq = true,
+                "show" => derives.show = true,
+                "clone" => derives.clone = true,
+                _ => {}
+            }
+        }
+        derives
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionValue {
+    /// 함수 값마다 새로 붙는 번호. 값이 복사되어도 같은 번호를 유지합니다.
+    pub id: usize,
+    pub parameters: Vec<Symbol>,
+    pub body: Statement,
+}
+
+static NEXT_FUNCTION_ID: AtomicUsize = AtomicUsize::new(1);
+
+impl FunctionValue {
+    pub fn new(parameters: Vec<Symbol>, body: Statement) -> Self {
+        Self { id: NEXT_FUNCTION_ID.fetch_add(1, Ordering::Relaxed), parameters, body }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReflectionInfo {
+    pub type_name: String,
+    pub details: String,
+}
+
+/// 읽기 전용 레코드. `name` 은 표시에만 씁니다 (예: `process`).
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+impl Record {
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.fields.iter().find(|(name, _)| name == field).map(|(_, value)| value)
+    }
+}
+
+//
+// ─── 타입 시스템 ─────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeAnnotation {
+    Int,
+    Float,
+    Bool,
+    String,
+    Void,
+    Any,
+    Custom(String),
+    /// `int | string`: 구성원 중 하나의 타입. 구성원은 둘 이상이며 중첩되지 않습니다. (파서가 펼칩니다)
+    Union(Vec<TypeAnnotation>),
+    /// `[int]`: 요소가 모두 그 타입인 배열
+    Array(Box<TypeAnnotation>),
+    /// `{string: int}`: 키와 값이 모두 그 타입인 맵
+    Map(Box<TypeAnnotation>, Box<TypeAnnotation>),
+    Infer,
+}
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeAnnotation::Int => write!(f, "int"),
+            TypeAnnotation::Float => write!(f, "float"),
+            TypeAnnotation::Bool => write!(f, "bool"),
+            TypeAnnotation::String => write!(f, "string"),
+            TypeAnnotation::Void => write!(f, "void"),
+            TypeAnnotation::Any => write!(f, "any"),
+            TypeAnnotation::Custom(name) => write!(f, "{}", name),
+            TypeAnnotation::Union(members) => {
+                let members: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", members.join(" | "))
+            }
+            TypeAnnotation::Array(element) => write!(f, "[{}]", element),
+            TypeAnnotation::Map(key, value) => write!(f, "{{{}: {}}}", key, value),
+            TypeAnnotation::Infer => write!(f, "_"),
+        }
+    }
+}
+
+//
+// ─── 토큰 ─────────────────────────────────────────────────────────────────────
+//
+
+/// 토큰 종류. 리터럴 텍스트는 소스 문자열을 빌려 씁니다. (`'src`)
+///
+/// AST 에 남는 연산자 토큰은 빌린 데이터가 없으므로 `TokenKind<'static>` 입니다.
+#[derive(Debug, Clone)]
+pub enum TokenKind<'src> {
+    // ─── 리터럴 ─────────────────────────────
+    IntegerLiteral(i64),
+    FloatLiteral(&'src str),
+    /// 이스케이프가 없으면 소스 조각을 그대로 빌리고, 있으면 처리한 문자열을 소유합니다.
+    StringLiteral(Cow<'src, str>),
+    /// `b"..."`: 이스케이프를 처리한 바이트
+    BytesLiteral(Vec<u8>),
+    BooleanLiteral(bool),
+
+    // ─── 식별자 ─────────────────────────────
+    Identifier(Symbol),
+    /// 렉서 플러그인이 인식한 키워드/리터럴: `constructor(argument)` 호출이 됩니다.
+    Custom { constructor: Symbol, argument: Option<Constant> },
+
+    // ─── 키워드 ─────────────────────────────
+    Fn,
+    Let,
+    Mut,
+    If,
+    Else,
+    While,
+    For,
+    /// `for x in xs`
+    In,
+    Return,
+    Match,
+    Macro,
+    /// `type Name = ...` 타입 별칭 선언
+    Type,
+    /// `enum Name { A, B(int) }` 선언
+    Enum,
+    TypeOf,
+    Eval,
+    Reflect,
+    Async,
+    Await,
+    Yield,
+    Defer,
+    True,
+    False,
+
+    // ─── 타입 키워드 ────────────────────────
+    Int,
+    Float,
+    Bool,
+    String,
+    Void,
+    Any,
+
+    // ─── 산술 연산자 ────────────────────────
+    Plus,
+    Minus,
+    Asterisk,
+    Slash,
+    Percent,
+
+    // ─── 비교 연산자 ────────────────────────
+    Eq,
+    Neq,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+
+    // ─── 논리 연산자 ────────────────────────
+    And,
+    Or,
+    Bang,
+
+    // ─── 비트 연산자 ────────────────────────
+    BitAnd,
+    BitOr,
+    BitXor,
+    /// `~` (비트 반전, 전위 연산자)
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
+
+    // ─── 대입 연산자 ────────────────────────
+    Assign,
+    PlusAssign,
+    MinusAssign,
+
+    // ─── 삼항 연산자 ────────────────────────
+    Question,
+    Colon,
+
+    // ─── 구문 기호 ──────────────────────────
+    At,
+    Comma,
+    Semicolon,
+    Dot,
+    /// `..` (범위)
+    DotDot,
+    /// `..=` (끝을 포함하는 범위)
+    DotDotEq,
+    /// `=>` (match arm)
+    Arrow,
+
+    // ─── 괄호 ───────────────────────────────
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+
+    // ─── 기타 ───────────────────────────────
+    Eof,
+    Illegal(char),
+}
+
+impl fmt::Display for TokenKind<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            TokenKind::IntegerLiteral(v) => return write!(f, "{}", v),
+            TokenKind::FloatLiteral(s) => return write!(f, "{}", s),
+            TokenKind::StringLiteral(s) => return write!(f, "\"{}\"", s),
+            TokenKind::BytesLiteral(b) => return write!(f, "b\"{}\"", bytes::escape(b)),
+            TokenKind::BooleanLiteral(b) => return write!(f, "{}", b),
+            TokenKind::Identifier(name) => return write!(f, "{}", name),
+            TokenKind::Custom { constructor, argument: Some(arg) } => return write!(f, "{}({:?})", constructor, arg),
+            TokenKind::Custom { constructor, argument: None } => return write!(f, "{}()", constructor),
+            TokenKind::Illegal(c) => return write!(f, "{}", c),
+            TokenKind::Fn => "fn",
+            TokenKind::Let => "let",
+            TokenKind::Mut => "mut",
+            TokenKind::If => "if",
+            TokenKind::Else => "else",
+            TokenKind::While => "while",
+            TokenKind::For => "for",
+            TokenKind::In => "in",
+            TokenKind::Return => "return",
+            TokenKind::Match => "match",
+            TokenKind::Macro => "macro",
+            TokenKind::Type => "type",
+            TokenKind::Enum => "enum",
+            TokenKind::TypeOf => "type_of",
+            TokenKind::Eval => "eval",
+            TokenKind::Reflect => "reflect",
+            TokenKind::Async => "async",
+            TokenKind::Await => "await",
+            TokenKind::Yield => "yield",
+            TokenKind::Defer => "defer",
+            TokenKind::True => "true",
+            TokenKind::False => "false",
+            TokenKind::Int => "int",
+            TokenKind::Float => "float",
+            TokenKind::Bool => "bool",
+            TokenKind::String => "string",
+            TokenKind::Void => "void",
+            TokenKind::Any => "any",
+            TokenKind::Plus => "+",
+            TokenKind::Minus => "-",
+            TokenKind::Asterisk => "*",
+            TokenKind::Slash => "/",
+            TokenKind::Percent => "%",
+            TokenKind::Eq => "==",
+            TokenKind::Neq => "!=",
+            TokenKind::Less => "<",
+            TokenKind::Greater => ">",
+            TokenKind::LessEqual => "<=",
+            TokenKind::GreaterEqual => ">=",
+            TokenKind::And => "&&",
+            TokenKind::Or => "||",
+            TokenKind::Bang => "!",
+            TokenKind::BitAnd => "&",
+            TokenKind::BitOr => "|",
+            TokenKind::BitXor => "^",
+            TokenKind::Tilde => "~",
+            TokenKind::ShiftLeft => "<<",
+            TokenKind::ShiftRight => ">>",
+            TokenKind::Assign => "=",
+            TokenKind::PlusAssign => "+=",
+            TokenKind::MinusAssign => "-=",
+            TokenKind::Question => "?",
+            TokenKind::Colon => ":",
+            TokenKind::At => "@",
+            TokenKind::Comma => ",",
+            TokenKind::Semicolon => ";",
+            TokenKind::Dot => ".",
+            TokenKind::DotDot => "..",
+            TokenKind::DotDotEq => "..=",
+            TokenKind::Arrow => "=>",
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LBrace => "{",
+            TokenKind::RBrace => "}",
+            TokenKind::LBracket => "[",
+            TokenKind::RBracket => "]",
+            TokenKind::Eof => "<eof>",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Token<'src> {
+    pub kind: TokenKind<'src>,
+    pub span: Span,
+}
+
+/// 소스 안의 위치. 바이트가 아니라 문자(char) 단위입니다.
+#[derive(Debug, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// `source` 를 자를 수 있는 바이트 범위로 바꿉니다. 소스 끝을 넘는 위치는 끝으로 자릅니다.
+    pub fn byte_range(&self, source: &str) -> core::ops::Range<usize> {
+        let mut offsets = source.char_indices().map(|(i, _)| i).chain(core::iter::once(source.len()));
+        let start = offsets.nth(self.start).unwrap_or(source.len());
+        let end = match self.end.checked_sub(self.start + 1) {
+            Some(rest) => offsets.nth(rest).unwrap_or(source.len()),
+            None => start,
+        };
+        start..end
+    }
+}
+
+//
+// ─── 표현식 ───────────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Literal(Span, Value),
+    Identifier(Span, Symbol),
+    PrefixOperation(Span, TokenKind<'static>, Box<Expression>),
+    InfixOperation(Span, TokenKind<'static>, Box<Expression>, Box<Expression>),
+    Ternary(Span, Box<Expression>, Box<Expression>, Box<Expression>),
+    Function(Span, Vec<Symbol>, Box<Statement>),
+    Call(Span, Box<Expression>, Vec<Box<Expression>>),
+    Grouped(Span, Box<Expression>),
+    Reflect(Span, Box<Expression>),
+    Eval(Span, Box<Expression>),
+    TypeOf(Span, Box<Expression>),
+    MacroCall(Span, String, Vec<Box<Expression>>),
+    /// `[a, b, c]`
+    ArrayLiteral(Span, Vec<Box<Expression>>),
+    /// `{ key: value, ... }`
+    MapLiteral(Span, Vec<(Box<Expression>, Box<Expression>)>),
+    /// `target[index]`
+    Index(Span, Box<Expression>, Box<Expression>),
+    /// `target[start:end]`. 생략한 쪽은 None 입니다.
+    Slice(Span, Box<Expression>, Option<Box<Expression>>, Option<Box<Expression>>),
+    /// `target.name`. 필드 이름 문자열로 읽는 `target["name"]` 과 같습니다.
+    Member(Span, Box<Expression>, Symbol),
+    /// `target.name(args)`. 받는 값을 첫 인자로 넘기는 `name(target, args)` 호출입니다. (`"abc".len()`)
+    MethodCall(Span, Box<Expression>, Symbol, Vec<Box<Expression>>),
+    /// `match subject { pattern if guard => body, ... }`
+    Match(Span, Box<Expression>, Vec<MatchArm>),
+    /// `name = value`, `name += value`, `name -= value`. 연산자는 Assign/PlusAssign/MinusAssign 이고 값은 새 값입니다.
+    Assign(Span, TokenKind<'static>, Symbol, Box<Expression>),
+    /// `start..end`, `start..=end`. 마지막 필드가 true 면 끝을 포함합니다.
+    Range(Span, Box<Expression>, Box<Expression>, bool),
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Literal(span, _)
+            | Expression::Identifier(span, _)
+            | Expression::PrefixOperation(span, ..)
+            | Expression::InfixOperation(span, ..)
+            | Expression::Ternary(span, ..)
+            | Expression::Function(span, ..)
+            | Expression::Call(span, ..)
+            | Expression::Grouped(span, _)
+            | Expression::Reflect(span, _)
+            | Expression::Eval(span, _)
+            | Expression::TypeOf(span, _)
+            | Expression::MacroCall(span, ..)
+            | Expression::ArrayLiteral(span, _)
+            | Expression::MapLiteral(span, _)
+            | Expression::Index(span, ..)
+            | Expression::Slice(span, ..)
+            | Expression::Member(span, ..)
+            | Expression::MethodCall(span, ..)
+            | Expression::Match(span, ..)
+            | Expression::Assign(span, ..)
+            | Expression::Range(span, ..) => *span,
+        }
+    }
+}
+
+//
+// ─── 패턴 ─────────────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// `_`
+    Wildcard,
+    /// `name` : 값을 이름에 바인딩
+    Binding(Symbol),
+    /// `1`, `"a"`, `true`
+    Literal(Value),
+    /// `1..10` / `1..=10`
+    Range { start: i64, end: i64, inclusive: bool },
+    /// `a | b | c`
+    Or(Vec<Pattern>),
+    /// `Rgb(r, _, 0)` 필드 패턴을 가진 enum 변형, 또는 앞에서 선언한 필드 없는 변형 `Red`
+    Variant(Symbol, Vec<Pattern>),
+}
+
+/// `pattern if guard => body`
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Box<Expression>>,
+    pub body: Box<Expression>,
+    pub span: Span,
+}
+
+//
+// ─── 문장 ─────────────────────────────────────────────────────────────────────
+//
+
+/// 모든 문장은 자기 소스 범위를 가집니다. 식 문장의 범위는 식의 범위와 같습니다.
+#[derive(Debug, Clone)]
+pub enum Statement {
+    ExpressionStatement(Box<Expression>),
+    /// `_ = expr;` 식을 실행하고 값은 일부러 버립니다. 쓰이지 않는 값 경고(E0104)의 대상이 아닙니다.
+    DiscardStatement(Span, Box<Expression>),
+    LetStatement {
+        name: Symbol,
+        value: Box<Expression>,
+        type_annotation: Option<TypeAnnotation>,
+        is_mutable: bool,
+        span: Span,
+    },
+    ReturnStatement(Span, Box<Expression>),
+    /// 제너레이터 함수 안에서 값을 하나 내놓고 실행을 멈춥니다.
+    YieldStatement(Span, Box<Expression>),
+    /// 둘러싼 스코프를 빠져나갈 때 실행할 블록을 등록합니다. (역순 실행)
+    DeferStatement(Span, Box<Statement>),
+    BlockStatement {
+        statements: Vec<Statement>,
+        span: Span,
+    },
+    IfStatement {
+        condition: Box<Expression>,
+        then_branch: Box<Statement>,
+        else_branch: Option<Box<Statement>>,
+        span: Span,
+    },
+    WhileStatement {
+        condition: Box<Expression>,
+        body: Box<Statement>,
+        span: Span,
+    },
+    ForStatement {
+        initializer: Option<Box<Statement>>,
+        condition: Option<Box<Expression>>,
+        increment: Option<Box<Expression>>,
+        body: Box<Statement>,
+        span: Span,
+    },
+    /// `for x in iterable { ... }`. 범위, 배열, 제너레이터를 돌며 반복마다 새 스코프에 `variable` 을 바인딩합니다.
+    ForInStatement {
+        variable: Symbol,
+        iterable: Box<Expression>,
+        body: Box<Statement>,
+        span: Span,
+    },
+    MacroDefinition {
+        name: String,
+        parameters: Vec<String>,
+        body: Box<Statement>,
+        span: Span,
+    },
+    /// `type Meters = int;` 타입 별칭. 타입 표기를 해석할 때와 표기된 `let` 의 실행 시점 강제 변환에 쓰입니다. (type_alias)
+    TypeAlias {
+        name: Symbol,
+        target: TypeAnnotation,
+        span: Span,
+    },
+    /// `enum Color { Red, Rgb(int, int, int) }`. 실행하면 변형 이름마다 값이나 생성자를 바인딩합니다.
+    EnumDeclaration {
+        name: Symbol,
+        variants: Vec<VariantDeclaration>,
+        span: Span,
+    },
+    /// `@name(args)` 어트리뷰트가 붙은 문장. 범위는 첫 어트리뷰트부터 문장 끝까지입니다.
+    Attributed {
+        attributes: Vec<Attribute>,
+        statement: Box<Statement>,
+        span: Span,
+    },
+}
+
+/// enum 선언의 변형 하나. 필드 없는 변형은 `fields` 가 비어 있습니다.
+#[derive(Debug, Clone)]
+pub struct VariantDeclaration {
+    pub name: Symbol,
+    pub fields: Vec<TypeAnnotation>,
+    pub span: Span,
+}
+
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::ExpressionStatement(expr) => expr.span(),
+            Statement::ReturnStatement(span, _)
+            | Statement::DiscardStatement(span, _)
+            | Statement::YieldStatement(span, _)
+            | Statement::DeferStatement(span, _)
+            | Statement::LetStatement { span, .. }
+            | Statement::BlockStatement { span, .. }
+            | Statement::IfStatement { span, .. }
+            | Statement::WhileStatement { span, .. }
+            | Statement::ForStatement { span, .. }
+            | Statement::ForInStatement { span, .. }
+            | Statement::MacroDefinition { span, .. }
+            | Statement::TypeAlias { span, .. }
+            | Statement::EnumDeclaration { span, .. }
+            | Statement::Attributed { span, .. } => *span,
+        }
+    }
+
+    /// 이 문장을 지나면 반드시 return 하는지 (마지막 문장, 또는 양쪽 가지가 모두 return 하는 if)
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            Statement::ReturnStatement(_, _) => true,
+            Statement::BlockStatement { statements, .. } => statements.last().is_some_and(|last| last.is_terminal()),
+            Statement::IfStatement { then_branch, else_branch, .. } => {
+                then_branch.is_terminal() && else_branch.as_ref().is_some_and(|b| b.is_terminal())
+            }
+            _ => false,
+        }
+    }
+}
+
+//
+// ─── 어트리뷰트 ───────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone)]
+pub struct Attribute {
+    pub name: String,
+    pub args: Vec<AttributeArg>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum AttributeArg {
+    /// `@cfg(debug)`
+    Flag(String),
+    /// `@cfg(target = "linux")`
+    KeyValue(String, String),
+    /// `@deprecated("message")`
+    Literal(String),
+}
+
+//
+// ─── 프로그램 ─────────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    /// 프로그램 루트의 노드 번호 (`node_ids::NodeIndex` 에서 0)
+    pub root_id: usize,
+    pub statements: Vec<Statement>,
+    pub span: Span,
+    /// `@!name(args)` 형식의 파일 단위 어트리뷰트
+    pub attributes: Vec<Attribute>,
+}
+
+impl Program {
+    /// 최상위 흐름이 return 으로 끝나는지 (MISSING_RETURN 검사)
+    pub fn ends_with_return(&self) -> bool {
+        self.statements.last().is_some_and(|last| last.is_terminal())
+    }
+}
+
+//
+// ─── 진단 ─────────────────────────────────────────────────────────────────────
+//
+
+#[derive(Debug, Clone)]
+pub enum DiagnosticLevel {
+    Info,
+    Warning,
+    Error,
+    HerFatal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub message: String,
+    pub span: Span,
+    pub help: Option<String>,
+    /// `error_codes` 레지스트리의 안정적인 코드 (예: "E0010"). 정보성 진단은 None 입니다.
+    pub code: Option<&'static str>,
+}
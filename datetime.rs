@@ -0,0 +1,337 @@
+// datetime.rs
+// 날짜/시간 값(`Value::DateTime`)과 기간 값(`Value::Duration`)입니다.
+//
+// DateTime 은 UTC 기준 밀리초 시각과 고정 UTC 오프셋(분)의 쌍입니다. 비교와 뺄셈은 시각만 보므로
+// `2024-01-01T09:00:00+09:00 == 2024-01-01T00:00:00Z` 입니다. 오프셋은 표시와 달력 계산(포맷)에만 씁니다.
+// Duration 은 밀리초 정수이며, 달력 단위(월, 년)는 길이가 일정하지 않아 두지 않습니다.
+//
+//   now()                                  현재 시각 (UTC, std 기능 필요)
+//   parse_datetime(text)                   ISO-8601: 2024-05-01, 2024-05-01T12:30, 2024-05-01T12:30:00.250+09:00
+//   parse_datetime(text, pattern)          strftime 식 패턴으로 읽기. 오프셋이 없으면 UTC
+//   format_datetime(dt)  format_datetime(dt, "%Y/%m/%d %H:%M")
+//   to_offset(dt, "+09:00")                같은 시각을 다른 오프셋으로 표시
+//   from_epoch_millis(ms)  epoch_millis(dt | duration)
+//   duration(ms)  seconds(n)  minutes(n)  hours(n)  days(n)
+//
+//   dt + dur, dur + dt, dt - dur  → DateTime      dt - dt → Duration
+//   dur ± dur, dur * int, int * dur, dur / int  → Duration      -dur
+//
+// 패턴 지정자: %Y %m %d %H %M %S %f(밀리초 3자리) %z(+0900) %:z(+09:00) %j(연중 일) %a %b(영어 약칭) %%
+// 읽기에서는 %Y %m %d %H %M %S %f %z %:z %% 만 쓸 수 있습니다. 달력은 선발 그레고리력입니다.
+
+use crate::compat::*;
+
+pub const MILLIS_PER_SECOND: i64 = 1_000;
+pub const MILLIS_PER_MINUTE: i64 = 60 * MILLIS_PER_SECOND;
+pub const MILLIS_PER_HOUR: i64 = 60 * MILLIS_PER_MINUTE;
+pub const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTime {
+    /// 1970-01-01T00:00:00Z 부터의 밀리초
+    pub millis: i64,
+    /// 표시에 쓰는 UTC 오프셋 (분)
+    pub offset_minutes: i32,
+}
+
+/// 오프셋을 적용한 달력 표현
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Civil {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millis: u32,
+}
+
+impl DateTime {
+    pub fn utc(millis: i64) -> Self {
+        Self { millis, offset_minutes: 0 }
+    }
+
+    /// 같은 오프셋에서 기간만큼 옮긴 시각. 범위를 넘으면 끝에 멈춥니다.
+    pub fn shifted(self, duration: i64) -> Self {
+        Self { millis: self.millis.saturating_add(duration), ..self }
+    }
+
+    fn civil(&self) -> Civil {
+        let local = self.millis.saturating_add(self.offset_minutes as i64 * MILLIS_PER_MINUTE);
+        let (days, rest) = (local.div_euclid(MILLIS_PER_DAY), local.rem_euclid(MILLIS_PER_DAY));
+        let (year, month, day) = civil_from_days(days);
+        Civil {
+            year,
+            month,
+            day,
+            hour: (rest / MILLIS_PER_HOUR) as u32,
+            minute: (rest % MILLIS_PER_HOUR / MILLIS_PER_MINUTE) as u32,
+            second: (rest % MILLIS_PER_MINUTE / MILLIS_PER_SECOND) as u32,
+            millis: (rest % MILLIS_PER_SECOND) as u32,
+        }
+    }
+
+    fn from_civil(civil: &Civil, offset_minutes: i32) -> Result<Self, String> {
+        if !(1..=12).contains(&civil.month) || civil.day == 0 || civil.day > days_in_month(civil.year, civil.month) {
+            return Err(format!("없는 날짜입니다: {}-{:02}-{:02}", civil.year, civil.month, civil.day));
+        }
+        if civil.hour > 23 || civil.minute > 59 || civil.second > 59 {
+            return Err(format!("없는 시각입니다: {:02}:{:02}:{:02}", civil.hour, civil.minute, civil.second));
+        }
+        let local = days_from_civil(civil.year, civil.month, civil.day) * MILLIS_PER_DAY
+            + civil.hour as i64 * MILLIS_PER_HOUR
+            + civil.minute as i64 * MILLIS_PER_MINUTE
+            + civil.second as i64 * MILLIS_PER_SECOND
+            + civil.millis as i64;
+        Ok(Self { millis: local - offset_minutes as i64 * MILLIS_PER_MINUTE, offset_minutes })
+    }
+
+    /// ISO-8601 표기. 밀리초는 0이 아닐 때만, 오프셋이 0이면 `Z` 입니다.
+    pub fn iso(&self) -> String {
+        let c = self.civil();
+        let mut text = format!(
+            "{}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year_text(c.year),
+            c.month,
+            c.day,
+            c.hour,
+            c.minute,
+            c.second
+        );
+        if c.millis != 0 {
+            text.push_str(&format!(".{:03}", c.millis));
+        }
+        match self.offset_minutes {
+            0 => text.push('Z'),
+            offset => text.push_str(&offset_text(offset, true)),
+        }
+        text
+    }
+}
+
+// ─── 달력 계산 (Howard Hinnant 의 civil 알고리즘) ─────────────────────────────
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn year_text(year: i64) -> String {
+    match year {
+        0..=9999 => format!("{:04}", year),
+        _ => format!("{:+05}", year),
+    }
+}
+
+fn offset_text(offset_minutes: i32, colon: bool) -> String {
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset_minutes.abs() / 60, offset_minutes.abs() % 60);
+    match colon {
+        true => format!("{}{:02}:{:02}", sign, hours, minutes),
+        false => format!("{}{:02}{:02}", sign, hours, minutes),
+    }
+}
+
+/// `Z`, `+09:00`, `+0900`, `-05` 를 분 단위 오프셋으로 읽습니다.
+pub fn parse_offset(text: &str) -> Result<i32, String> {
+    let invalid = || format!("잘못된 UTC 오프셋 '{}'", text);
+    if text == "Z" || text == "z" {
+        return Ok(0);
+    }
+    let (sign, rest) = match text.chars().next() {
+        Some('+') => (1, &text[1..]),
+        Some('-') => (-1, &text[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if !digits.chars().all(|c| c.is_ascii_digit()) || !matches!(digits.len(), 2 | 4) {
+        return Err(invalid());
+    }
+    let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+    let minutes: i32 = if digits.len() == 4 { digits[2..].parse().map_err(|_| invalid())? } else { 0 };
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(sign * (hours * 60 + minutes))
+}
+
+// ─── 포맷 ─────────────────────────────
+
+pub fn format(value: &DateTime, pattern: &str) -> Result<String, String> {
+    let c = value.civil();
+    let mut text = String::new();
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => text.push_str(&year_text(c.year)),
+            Some('m') => text.push_str(&format!("{:02}", c.month)),
+            Some('d') => text.push_str(&format!("{:02}", c.day)),
+            Some('H') => text.push_str(&format!("{:02}", c.hour)),
+            Some('M') => text.push_str(&format!("{:02}", c.minute)),
+            Some('S') => text.push_str(&format!("{:02}", c.second)),
+            Some('f') => text.push_str(&format!("{:03}", c.millis)),
+            Some('z') => text.push_str(&offset_text(value.offset_minutes, false)),
+            Some(':') if chars.next() == Some('z') => text.push_str(&offset_text(value.offset_minutes, true)),
+            Some('j') => {
+                let day_of_year = days_from_civil(c.year, c.month, c.day) - days_from_civil(c.year, 1, 1) + 1;
+                text.push_str(&format!("{:03}", day_of_year));
+            }
+            Some('a') => {
+                // 1970-01-01 은 목요일입니다.
+                let days = days_from_civil(c.year, c.month, c.day);
+                text.push_str(WEEKDAYS[(days + 3).rem_euclid(7) as usize]);
+            }
+            Some('b') => text.push_str(MONTHS[c.month as usize - 1]),
+            Some('%') => text.push('%'),
+            Some(other) => return Err(format!("지원하지 않는 날짜 지정자 '%{}'", other)),
+            None => return Err("패턴이 '%' 로 끝났습니다.".into()),
+        }
+    }
+    Ok(text)
+}
+
+// ─── 읽기 ─────────────────────────────
+
+/// ISO-8601 의 날짜, 날짜+시각 형식. 오프셋이 없으면 UTC 입니다.
+pub fn parse_iso(text: &str) -> Result<DateTime, String> {
+    let text = text.trim();
+    let (date, time) = match text.find(['T', 't', ' ']) {
+        Some(at) => (&text[..at], Some(&text[at + 1..])),
+        None => (text, None),
+    };
+    let parsed = match time {
+        None => parse(date, "%Y-%m-%d"),
+        Some(time) => {
+            let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+                Some(at) => (&time[..at], parse_offset(&time[at..])?),
+                None => (time, 0),
+            };
+            let pattern = match clock.len() {
+                5 => "%Y-%m-%dT%H:%M",
+                8 => "%Y-%m-%dT%H:%M:%S",
+                _ => "%Y-%m-%dT%H:%M:%S.%f",
+            };
+            parse(&format!("{}T{}", date, clock), pattern)
+                .and_then(|local| DateTime::from_civil(&local.civil(), offset))
+        }
+    };
+    parsed.map_err(|e| format!("ISO-8601 날짜가 아닙니다 ('{}'): {}", text, e))
+}
+
+/// 패턴대로 읽습니다. 숫자 지정자는 정해진 자릿수(%Y 는 4, %f 는 3, 나머지는 2)를 읽습니다.
+pub fn parse(text: &str, pattern: &str) -> Result<DateTime, String> {
+    let mut civil = Civil { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0, millis: 0 };
+    let mut offset = 0;
+    let mut rest = text;
+    let mut spec = pattern.chars();
+
+    let number = |rest: &mut &str, width: usize, what: &str| -> Result<u32, String> {
+        let digits = rest.get(..width).filter(|d| d.chars().all(|c| c.is_ascii_digit()));
+        let digits = digits.ok_or_else(|| format!("{} 자리에 숫자 {}개가 필요합니다: '{}'", what, width, rest))?;
+        *rest = &rest[width..];
+        Ok(digits.parse().unwrap_or(0))
+    };
+
+    while let Some(ch) = spec.next() {
+        if ch != '%' {
+            rest = rest
+                .strip_prefix(ch)
+                .ok_or_else(|| format!("'{}' 가 와야 할 자리에 '{}' 가 있습니다.", ch, rest))?;
+            continue;
+        }
+        match spec.next() {
+            Some('Y') => civil.year = number(&mut rest, 4, "연도")? as i64,
+            Some('m') => civil.month = number(&mut rest, 2, "월")?,
+            Some('d') => civil.day = number(&mut rest, 2, "일")?,
+            Some('H') => civil.hour = number(&mut rest, 2, "시")?,
+            Some('M') => civil.minute = number(&mut rest, 2, "분")?,
+            Some('S') => civil.second = number(&mut rest, 2, "초")?,
+            Some('f') => civil.millis = number(&mut rest, 3, "밀리초")?,
+            Some(':') if spec.next() == Some('z') => {
+                let end = if rest.starts_with(['Z', 'z']) { 1 } else { 6.min(rest.len()) };
+                offset = parse_offset(&rest[..end])?;
+                rest = &rest[end..];
+            }
+            Some('z') => {
+                let end = if rest.starts_with(['Z', 'z']) { 1 } else { 5.min(rest.len()) };
+                offset = parse_offset(&rest[..end])?;
+                rest = &rest[end..];
+            }
+            Some('%') => rest = rest.strip_prefix('%').ok_or_else(|| format!("'%' 가 와야 합니다: '{}'", rest))?,
+            Some(other) => return Err(format!("읽기에 쓸 수 없는 날짜 지정자 '%{}'", other)),
+            None => return Err("패턴이 '%' 로 끝났습니다.".into()),
+        }
+    }
+    if !rest.is_empty() {
+        return Err(format!("남은 글자가 있습니다: '{}'", rest));
+    }
+    DateTime::from_civil(&civil, offset)
+}
+
+// ─── 기간 ─────────────────────────────
+
+/// `1d2h3m4.5s` 처럼 큰 단위부터 0이 아닌 것만 적습니다.
+pub fn format_duration(millis: i64) -> String {
+    if millis == 0 {
+        return "0s".into();
+    }
+    let mut text = String::new();
+    if millis < 0 {
+        text.push('-');
+    }
+    let mut rest = millis.unsigned_abs();
+    for (unit, size) in [("d", MILLIS_PER_DAY), ("h", MILLIS_PER_HOUR), ("m", MILLIS_PER_MINUTE)] {
+        let count = rest / size as u64;
+        if count > 0 {
+            text.push_str(&format!("{}{}", count, unit));
+        }
+        rest %= size as u64;
+    }
+    match (rest / 1000, rest % 1000) {
+        (0, 0) => {}
+        (seconds, 0) => text.push_str(&format!("{}s", seconds)),
+        (seconds, ms) => {
+            let fraction = format!("{:03}", ms);
+            text.push_str(&format!("{}.{}s", seconds, fraction.trim_end_matches('0')));
+        }
+    }
+    text
+}
@@ -0,0 +1,192 @@
+// dead_code.rs
+// 프로그램 전체 도달성 분석: 쓰이지 않는 함수와 표준 라이브러리 모듈 (E0061, E0062, `--strip-dead`)
+//
+// 진입점은 최상위 코드(`<main>`), 최상위 `fn main`, `@export` 를 붙인 최상위 정의입니다. 호출 그래프(call_graph)의
+// 호출 간선과 값 참조(`apply(f, 1)` 의 `f`)를 따라 닿지 않는 최상위 함수·매크로 정의가 죽은 정의입니다.
+//
+//   사용자 정의   죽은 정의마다 E0061 경고. `@allow(dead_code)` / 파일 맨 앞의 `@!allow(dead_code)` 로 끕니다.
+//   모듈          링크된 모듈의 최상위 정의가 하나도 닿지 않으면 쓰이지 않는 모듈입니다. (E0062, --strip-dead 일 때만)
+//   --strip-dead  죽은 사용자 정의와 쓰이지 않는 모듈 전체를 매크로 확장 전에 지워, 백엔드 출력에서 뺍니다.
+//
+// 블록 안의 지역 함수는 그 블록이 실행될 때만 보이므로 대상이 아닙니다. `eval` 은 실행 중에 어떤 이름이든
+// 부를 수 있으므로, 프로그램에 `eval` 이 있으면 모든 정의를 도달 가능으로 봅니다.
+
+use alloc::collections::BTreeSet;
+
+use crate::attributes::has_attribute;
+use crate::call_graph::{CallGraph, CallNode, NodeKind, TOP_LEVEL};
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, Expression, Program, Span, Statement};
+use crate::error_codes;
+use crate::resolver::allows;
+use crate::stdlib::LinkedModule;
+use crate::visitor::{walk_expression, Visitor};
+
+/// 도달할 수 없는 사용자 정의 하나
+#[derive(Debug, Clone)]
+pub struct DeadDefinition {
+    pub name: String,
+    pub kind: NodeKind,
+    /// 정의 문장 전체
+    pub span: Span,
+    /// `@allow(dead_code)` 로 경고를 끈 정의 (--strip-dead 는 그래도 지웁니다)
+    pub allowed: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DeadCode {
+    /// 소스 순서의 죽은 사용자 정의
+    pub definitions: Vec<DeadDefinition>,
+    /// 정의를 하나도 쓰지 않는 링크된 모듈
+    pub modules: Vec<&'static str>,
+    /// 지울 최상위 문장 번호
+    statements: BTreeSet<usize>,
+}
+
+/// 최상위 정의의 도달성을 구합니다. `modules` 는 `stdlib::link` 가 돌려준 모듈 범위이며, 그 밖의 문장이 사용자 코드입니다.
+/// @cfg 정리가 끝나고 매크로 확장 전인 AST 를 받습니다.
+pub fn analyze(program: &Program, modules: &[LinkedModule]) -> DeadCode {
+    let mut report = DeadCode::default();
+    let mut evals = EvalFinder(false);
+    evals.visit_program(program);
+    if evals.0 {
+        return report;
+    }
+
+    let graph = CallGraph::build(program);
+    // 최상위 정의는 소스 순서대로 1번부터 번호를 받습니다. (CallGraph::build)
+    let definitions: Vec<(usize, usize)> = program
+        .statements
+        .iter()
+        .enumerate()
+        .filter(|(_, stmt)| defines(stmt))
+        .enumerate()
+        .map(|(n, (index, _))| (index, n + 1))
+        .collect();
+
+    let mut reachable = vec![false; graph.nodes.len()];
+    let mut pending = vec![TOP_LEVEL];
+    pending.extend(
+        definitions
+            .iter()
+            .filter(|&&(index, node)| exported(&program.statements[index]) || is_main(&graph.nodes[node]))
+            .map(|&(_, node)| node),
+    );
+    while let Some(node) = pending.pop() {
+        if core::mem::replace(&mut reachable[node], true) {
+            continue;
+        }
+        pending.extend(graph.edges[node].iter().chain(&graph.uses[node]).filter(|&&next| !reachable[next]));
+    }
+
+    let module_of = |index: usize| modules.iter().position(|m| m.statements.contains(&index));
+    let mut used_modules = vec![false; modules.len()];
+    for &(index, node) in &definitions {
+        match module_of(index) {
+            Some(module) => used_modules[module] |= reachable[node],
+            None if !reachable[node] => {
+                let stmt = &program.statements[index];
+                report.statements.insert(index);
+                report.definitions.push(DeadDefinition {
+                    name: graph.nodes[node].name.clone(),
+                    kind: graph.nodes[node].kind.clone(),
+                    span: stmt.span(),
+                    allowed: allows(&program.attributes, "dead_code") || allowed(stmt),
+                });
+            }
+            None => {}
+        }
+    }
+    for (module, used) in modules.iter().zip(used_modules) {
+        if !used {
+            report.modules.push(module.name);
+            report.statements.extend(module.statements.clone());
+        }
+    }
+    report
+}
+
+impl DeadCode {
+    /// 죽은 사용자 정의마다 E0061 경고. `strip` 이면 지운 모듈도 E0062 로 알립니다.
+    pub fn diagnostics(&self, program_span: Span, strip: bool) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self
+            .definitions
+            .iter()
+            .filter(|d| !d.allowed)
+            .map(|d| {
+                let code = &error_codes::DEAD_FUNCTION;
+                let kind = code.message(if d.kind == NodeKind::Macro { "macro" } else { "function" }, &[]);
+                code.diagnostic(d.span, "", &[&kind, &d.name]).with_help(code.message("help", &[]))
+            })
+            .collect();
+        if strip {
+            diagnostics.extend(self.modules.iter().map(|name| error_codes::UNREACHABLE_MODULE.diagnostic(program_span, "", &[name])));
+        }
+        diagnostics
+    }
+
+    /// 죽은 사용자 정의와 쓰이지 않는 모듈의 문장을 지웁니다. `analyze` 에 넘긴 프로그램 그대로여야 합니다.
+    pub fn strip(&self, program: &mut Program) {
+        let mut index = 0;
+        program.statements.retain(|_| {
+            index += 1;
+            !self.statements.contains(&(index - 1))
+        });
+    }
+}
+
+/// 호출 그래프가 노드로 만드는 정의 문장인지 (call_graph 의 `declare` 와 같은 규칙)
+fn defines(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::LetStatement { value, .. } => matches!(value.as_ref(), Expression::Function(..)),
+        Statement::MacroDefinition { .. } => true,
+        Statement::Attributed { statement, .. } => defines(statement),
+        _ => false,
+    }
+}
+
+/// `fn main` 은 호스트가 부르는 진입점이므로 최상위 코드에서 부르지 않아도 쓰이는 것으로 봅니다.
+fn is_main(node: &CallNode) -> bool {
+    node.kind == NodeKind::Function && node.name == "main"
+}
+
+fn exported(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Attributed { attributes, statement, .. } => has_attribute(attributes, "export") || exported(statement),
+        _ => false,
+    }
+}
+
+fn allowed(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Attributed { attributes, statement, .. } => allows(attributes, "dead_code") || allowed(statement),
+        _ => false,
+    }
+}
+
+struct EvalFinder(bool);
+
+impl Visitor for EvalFinder {
+    fn visit_expression(&mut self, expr: &Expression) {
+        self.0 |= matches!(expr, Expression::Eval(..));
+        walk_expression(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_service::LexerService;
+    use crate::parser_service::ParserService;
+
+    fn dead_names(source: &str) -> Vec<String> {
+        let program = ParserService::new(LexerService::new(source)).parse_program();
+        analyze(&program, &[]).definitions.into_iter().map(|d| d.name).collect()
+    }
+
+    #[test]
+    fn main_is_an_entry_point() {
+        let source = "fn helper(x) { return x * 2; }\nfn unused() { return 0; }\nfn main() { return helper(1); }\nreturn 0;";
+        assert_eq!(dead_names(source), ["unused"]);
+    }
+}
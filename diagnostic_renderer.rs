@@ -0,0 +1,179 @@
+// diagnostic_renderer.rs
+// 진단을 rustc 와 비슷한 형태로 출력합니다: 색이 있는 심각도 라벨, 밑줄이 그어진 소스 조각, help.
+//
+//   경고[E0060]: 사용 중단된(deprecated) 'old' 를 사용합니다
+//     --> main.high:3:9
+//      |
+//    3 | return old
+//      |        ^^^
+//      = 도움말: ...
+//
+// 표준 출력이 터미널이 아니거나 NO_COLOR 가 설정되어 있으면 색을 쓰지 않습니다.
+
+#[cfg(feature = "std")]
+use std::io::IsTerminal;
+
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, DiagnosticLevel, Span};
+use crate::messages;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const BLUE: &str = "\x1b[1;34m";
+const TAB_WIDTH: usize = 4;
+
+pub struct DiagnosticRenderer {
+    color: bool,
+}
+
+impl DiagnosticRenderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// 표준 출력이 TTY 이고 NO_COLOR 가 없을 때만 색을 씁니다.
+    #[cfg(feature = "std")]
+    pub fn for_stdout() -> Self {
+        Self::new(std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none())
+    }
+
+    /// 소스 위치가 있는 진단을 렌더링합니다.
+    pub fn render(&self, diag: &Diagnostic, source: &str, path: &str) -> String {
+        let mut out = self.header(&diag.level, diag.code, &diag.message);
+
+        if let Some(snippet) = self.snippet(diag.span, &diag.level, source, path) {
+            out.push_str(&snippet);
+        }
+        if let Some(help) = &diag.help {
+            out.push_str(&self.help(help));
+        }
+        out
+    }
+
+    /// 위치 정보 없이 문자열로만 전달된 오류를 렌더링합니다. `[E0000] 메시지` 형식이면 코드를 분리합니다.
+    pub fn render_message(&self, level: &DiagnosticLevel, text: &str) -> String {
+        let (code, message) = split_code(text);
+        self.header(level, code, message)
+    }
+
+    fn header(&self, level: &DiagnosticLevel, code: Option<&str>, message: &str) -> String {
+        let label = match code {
+            Some(code) => format!("{}[{}]", level_label(level), code),
+            None => level_label(level),
+        };
+        format!(
+            "{}: {}\n",
+            self.paint(level_color(level), &label),
+            self.paint(BOLD, message)
+        )
+    }
+
+    fn snippet(&self, span: Span, level: &DiagnosticLevel, source: &str, path: &str) -> Option<String> {
+        if span.start == 0 && span.end == 0 || span.start > source.chars().count() {
+            return None;
+        }
+        // span 은 문자 위치라서 한글이 앞에 있으면 바이트 위치와 다릅니다.
+        let bytes = span.byte_range(source);
+        let (start, end) = (bytes.start, bytes.end.max(bytes.start));
+
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+        let line_number = source[..start].matches('\n').count() + 1;
+        let column = source[line_start..start].chars().count() + 1;
+        let line = &source[line_start..line_end];
+
+        // 여러 줄에 걸친 span 은 첫 줄의 끝까지만 밑줄을 긋습니다.
+        let marked_end = end.min(line_end).max(start);
+        let offset = display_width(&source[line_start..start]);
+        let width = display_width(&source[start..marked_end]).max(1);
+
+        let gutter = " ".repeat(line_number.to_string().len());
+        let bar = self.paint(BLUE, "|");
+        let mut out = format!("{}{} {}:{}:{}\n", gutter, self.paint(BLUE, "-->"), path, line_number, column);
+        out.push_str(&format!("{} {}\n", gutter, bar));
+        let line = line.replace('\t', &" ".repeat(TAB_WIDTH));
+        out.push_str(&format!("{} {} {}\n", self.paint(BLUE, &line_number.to_string()), bar, line));
+        out.push_str(&format!(
+            "{} {} {}{}\n",
+            gutter,
+            bar,
+            " ".repeat(offset),
+            self.paint(level_color(level), &"^".repeat(width))
+        ));
+        Some(out)
+    }
+
+    fn help(&self, help: &str) -> String {
+        let mut lines = help.lines();
+        let first = lines.next().unwrap_or("");
+        let mut out = format!("  {} {}: {}\n", self.paint(BLUE, "="), self.paint(BOLD, &messages::tr("render.help", &[])), first);
+        for line in lines {
+            out.push_str(&format!("    {}\n", line));
+        }
+        out
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+fn level_label(level: &DiagnosticLevel) -> String {
+    let key = match level {
+        DiagnosticLevel::Info => "render.info",
+        DiagnosticLevel::Warning => "render.warning",
+        DiagnosticLevel::Error => "render.error",
+        DiagnosticLevel::HerFatal => "render.fatal",
+    };
+    messages::tr(key, &[])
+}
+
+fn level_color(level: &DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Info => "\x1b[1;36m",
+        DiagnosticLevel::Warning => "\x1b[1;33m",
+        DiagnosticLevel::Error => "\x1b[1;31m",
+        DiagnosticLevel::HerFatal => "\x1b[1;35m",
+    }
+}
+
+/// `[E0080] 메시지` → (Some("E0080"), "메시지")
+fn split_code(text: &str) -> (Option<&str>, &str) {
+    if let Some(rest) = text.strip_prefix('[') {
+        if let Some((code, message)) = rest.split_once("] ") {
+            if code.starts_with('E') && code[1..].chars().all(|c| c.is_ascii_digit()) {
+                return (Some(code), message);
+            }
+        }
+    }
+    (None, text)
+}
+
+/// 터미널에서의 표시 폭: 한글과 CJK 문자는 두 칸, 탭은 TAB_WIDTH 칸을 차지합니다.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x09 => TAB_WIDTH,
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFF00..=0xFF60 => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippet_uses_char_positions() {
+        // `없음` 은 문자 위치 19..21 이고, 앞의 `이름` 때문에 바이트 위치는 23..29 입니다.
+        let source = "let 이름 = 1;\nreturn 없음;";
+        let span = Span { start: 19, end: 21 };
+        let snippet = DiagnosticRenderer::new(false).snippet(span, &DiagnosticLevel::Error, source, "a.high").unwrap();
+        assert_eq!(snippet, " --> a.high:2:8\n  |\n2 | return 없음;\n  |        ^^^^\n");
+    }
+}
@@ -0,0 +1,63 @@
+// division_check.rs
+// 컴파일 시점에 증명되는 0 나누기 (E0033)
+//
+// 나누는 쪽이 리터럴만으로 이루어진 식이고 그 값이 정수 0 이면 `/` 와 `%` 는 실행 시점에 반드시
+// 패닉(E0030)하거나 타입 오류입니다. 나뉘는 쪽은 보지 않습니다. 실수 0.0 으로 나누기는 무한대/NaN 이므로 대상이 아닙니다.
+// 나눗셈 규칙 자체는 operators.rs 머리말에 있습니다.
+//
+// `@allow(division_by_zero)` 를 붙인 문장이나 파일 맨 앞의 `@!allow(division_by_zero)` 로 끕니다.
+// 매크로 정의 본문은 확장 전 틀이므로 검사하지 않습니다.
+
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, Expression, Program, Statement, TokenKind, Value};
+use crate::error_codes;
+use crate::operators;
+use crate::resolver::allows;
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+pub fn check_program(program: &Program) -> Vec<Diagnostic> {
+    if allows(&program.attributes, "division_by_zero") {
+        return vec![];
+    }
+    let mut check = DivisionCheck { diagnostics: vec![] };
+    check.visit_program(program);
+    check.diagnostics
+}
+
+/// 리터럴, 괄호, 단항/이항 연산만으로 된 식의 값. 실행 시점과 같은 `operators` 의미로 계산합니다.
+pub fn constant_value(expr: &Expression) -> Option<Value> {
+    match expr {
+        Expression::Literal(_, value) => Some(value.clone()),
+        Expression::Grouped(_, inner) => constant_value(inner),
+        Expression::PrefixOperation(_, op, operand) => operators::unary(op, &constant_value(operand)?).ok(),
+        Expression::InfixOperation(_, op, left, right) => {
+            operators::binary(op, &constant_value(left)?, &constant_value(right)?).ok()
+        }
+        _ => None,
+    }
+}
+
+struct DivisionCheck {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for DivisionCheck {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::MacroDefinition { .. } => {}
+            Statement::Attributed { attributes, .. } if allows(attributes, "division_by_zero") => {}
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        if let Expression::InfixOperation(span, op @ (TokenKind::Slash | TokenKind::Percent), _, right) = expr {
+            if matches!(constant_value(right), Some(Value::Integer(0))) {
+                let code = &error_codes::DIVISION_BY_ZERO;
+                let operation = code.message(if matches!(op, TokenKind::Slash) { "division" } else { "remainder" }, &[]);
+                self.diagnostics.push(code.diagnostic(*span, "", &[&operation]).with_help(code.message("help", &[])));
+            }
+        }
+        walk_expression(self, expr);
+    }
+}
@@ -0,0 +1,331 @@
+// effects.rs
+// 효과 분석: 식과 함수가 순수(Pure)한지, 입출력 같은 관찰 가능한 효과(Io)가 있는지,
+// 알 수 없는지(Unknown) 추론합니다.
+//
+// 최적화기는 이 결과로 쓰이지 않는 순수 바인딩을 지우고(DCE), 루프 불변 순수 식을 루프 밖으로
+// 옮기고(LICM), 순수 내장 함수 호출을 컴파일 시점에 계산합니다.
+//
+// 종료하지 않을 수 있는 코드(루프, 재귀)는 지우거나 옮기면 동작이 바뀌므로 Unknown 으로 봅니다.
+
+
+use crate::call_graph::CallGraph;
+use crate::compat::*;
+use crate::data_structures::{Expression, Program, Statement};
+use crate::patterns;
+
+/// 효과의 크기 순서: Pure < Io < Unknown. 여러 효과를 합치면 큰 쪽이 됩니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Effect {
+    Pure,
+    Io,
+    Unknown,
+}
+
+impl Effect {
+    pub fn join(self, other: Effect) -> Effect {
+        self.max(other)
+    }
+}
+
+/// 내장 함수의 효과. 사용자가 같은 이름을 다시 바인딩하면 쓰이지 않습니다.
+pub fn builtin_effect(name: &str) -> Option<Effect> {
+    match name {
+        "format" | "len" | "to_hex" | "from_hex" | "to_utf8" | "from_utf8" => Some(Effect::Pure),
+        // 맵은 바뀌지 않는 값이라 `insert` 도 새 맵을 만들 뿐입니다.
+        "keys" | "values" | "contains_key" | "insert" | "clone" => Some(Effect::Pure),
+        // 패닉은 실행을 멈추고 메시지를 출력하므로 관찰 가능한 효과입니다.
+        "panic" | "assert" => Some(Effect::Io),
+        // 수집은 값을 바꾸지 않지만 통계와 회수 수를 돌려주므로 지우거나 옮기지 않습니다.
+        "gc" => Some(Effect::Io),
+        "weak_ref" => Some(Effect::Pure),
+        // 결과가 수집 시점에 달려 있습니다.
+        "deref" | "on_finalize" => Some(Effect::Io),
+        "read_file_bytes" | "run_process" | "read_line" | "read_all" => Some(Effect::Io),
+        "regex_match" | "regex_find_all" | "regex_replace" | "match_start" | "match_end" => Some(Effect::Pure),
+        "parse_datetime" | "format_datetime" | "to_offset" | "from_epoch_millis" | "epoch_millis" => Some(Effect::Pure),
+        "duration" | "seconds" | "minutes" | "hours" | "days" => Some(Effect::Pure),
+        // 부를 때마다 값이 다르므로 접거나 지우지 않습니다.
+        "now" => Some(Effect::Io),
+        // 부른 자리의 호출 스택과 바인딩을 읽으므로 옮기거나 접지 않습니다.
+        "callstack" | "locals" => Some(Effect::Io),
+        _ => None,
+    }
+}
+
+/// 이름별 함수 효과 표
+#[derive(Debug, Default)]
+pub struct EffectTable {
+    functions: HashMap<String, Effect>,
+    /// 함수가 아닌 값으로 (또는 함수로 두 번 이상) 바인딩된 이름: 호출 대상이 무엇인지 모릅니다.
+    ambiguous: Vec<String>,
+}
+
+impl EffectTable {
+    /// 프로그램의 모든 `let name = fn...` 함수의 효과를 고정점 반복으로 추론합니다.
+    pub fn infer(program: &Program) -> Self {
+        let mut definitions: Vec<(String, &Expression)> = vec![];
+        let mut other_bindings: Vec<String> = vec![];
+        for stmt in &program.statements {
+            collect_bindings(stmt, &mut definitions, &mut other_bindings);
+        }
+
+        let mut table = EffectTable::default();
+        for (name, _) in &definitions {
+            let repeated = definitions.iter().filter(|(n, _)| n == name).count() > 1;
+            if repeated || other_bindings.contains(name) {
+                table.ambiguous.push(name.clone());
+            }
+        }
+
+        // 재귀(호출 그래프의 순환)에 속한 함수는 종료를 보장할 수 없습니다.
+        let recursive = CallGraph::build(program).recursive_names();
+        for (name, _) in &definitions {
+            let start = if recursive.contains(name) { Effect::Unknown } else { Effect::Pure };
+            table.functions.insert(name.clone(), start);
+        }
+
+        // 효과는 커지기만 하므로 변화가 없을 때까지 반복하면 끝납니다.
+        loop {
+            let mut changed = false;
+            for (name, function) in &definitions {
+                let Expression::Function(_, _, body) = function else {
+                    continue;
+                };
+                let effect = table.statement(body);
+                let entry = table.functions.get_mut(name).expect("모든 정의가 등록됨");
+                if effect > *entry {
+                    *entry = effect;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        table
+    }
+
+    /// 사용자 함수의 효과. 알 수 없는 이름이면 None 입니다.
+    pub fn function(&self, name: &str) -> Option<Effect> {
+        if self.ambiguous.iter().any(|n| n == name) {
+            return None;
+        }
+        self.functions.get(name).copied()
+    }
+
+    /// 이름으로 호출했을 때의 효과
+    pub fn call(&self, name: &str) -> Effect {
+        if self.ambiguous.iter().any(|n| n == name) {
+            return Effect::Unknown;
+        }
+        match self.functions.get(name) {
+            Some(effect) => *effect,
+            None => builtin_effect(name).unwrap_or(Effect::Unknown),
+        }
+    }
+
+    /// 사용자가 다시 바인딩하지 않은 내장 함수 이름인지
+    pub fn is_builtin(&self, name: &str) -> bool {
+        builtin_effect(name).is_some() && !self.functions.contains_key(name) && !self.ambiguous.iter().any(|n| n == name)
+    }
+
+    pub fn expression(&self, expr: &Expression) -> Effect {
+        match expr {
+            Expression::Literal(..) | Expression::Identifier(..) => Effect::Pure,
+            // 함수 값을 만드는 것 자체는 본문을 실행하지 않습니다.
+            Expression::Function(..) => Effect::Pure,
+            Expression::Call(_, callee, args) => {
+                let call = match callee.as_ref() {
+                    Expression::Identifier(_, name) => self.call(name.as_str()),
+                    _ => Effect::Unknown,
+                };
+                args.iter().fold(call, |effect, arg| effect.join(self.expression(arg)))
+            }
+            Expression::MacroCall(_, name, args) => {
+                args.iter().fold(self.call(name), |effect, arg| effect.join(self.expression(arg)))
+            }
+            Expression::MethodCall(_, target, name, args) => args
+                .iter()
+                .fold(self.call(name.as_str()).join(self.expression(target)), |effect, arg| effect.join(self.expression(arg))),
+            // eval 은 임의의 코드를 실행합니다.
+            Expression::Eval(..) => Effect::Unknown,
+            // 바인딩을 바꾸면 뒤에서 읽는 값이 달라지므로 지우거나 옮길 수 없습니다.
+            Expression::Assign(_, _, _, value) => Effect::Io.join(self.expression(value)),
+            Expression::PrefixOperation(_, _, inner)
+            | Expression::Grouped(_, inner)
+            | Expression::Reflect(_, inner)
+            | Expression::TypeOf(_, inner)
+            | Expression::Member(_, inner, _) => self.expression(inner),
+            Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+                self.expression(left).join(self.expression(right))
+            }
+            Expression::Ternary(_, cond, then_expr, else_expr) => self
+                .expression(cond)
+                .join(self.expression(then_expr))
+                .join(self.expression(else_expr)),
+            Expression::ArrayLiteral(_, elements) => {
+                elements.iter().fold(Effect::Pure, |effect, element| effect.join(self.expression(element)))
+            }
+            Expression::MapLiteral(_, entries) => entries
+                .iter()
+                .fold(Effect::Pure, |effect, (key, value)| effect.join(self.expression(key)).join(self.expression(value))),
+            Expression::Index(_, target, index) => self.expression(target).join(self.expression(index)),
+            Expression::Slice(_, target, start, end) => start
+                .iter()
+                .chain(end)
+                .fold(self.expression(target), |effect, bound| effect.join(self.expression(bound))),
+            Expression::Match(_, subject, arms) => arms
+                .iter()
+                .flat_map(|arm| arm.guard.iter().chain([&arm.body]))
+                .fold(self.expression(subject), |effect, e| effect.join(self.expression(e))),
+        }
+    }
+
+    pub fn statement(&self, stmt: &Statement) -> Effect {
+        match stmt {
+            Statement::ExpressionStatement(expr)
+            | Statement::DiscardStatement(_, expr)
+            | Statement::ReturnStatement(_, expr)
+            | Statement::LetStatement { value: expr, .. } => self.expression(expr),
+            // yield 는 호출자에게 제어를 넘기므로 관찰 가능합니다.
+            Statement::YieldStatement(_, _) => Effect::Io,
+            Statement::DeferStatement(_, body) => self.statement(body),
+            Statement::BlockStatement { statements, .. } => {
+                statements.iter().fold(Effect::Pure, |effect, s| effect.join(self.statement(s)))
+            }
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                let effect = self.expression(condition).join(self.statement(then_branch));
+                else_branch.as_ref().map_or(effect, |e| effect.join(self.statement(e)))
+            }
+            Statement::WhileStatement { .. } | Statement::ForStatement { .. } | Statement::ForInStatement { .. } => Effect::Unknown,
+            Statement::MacroDefinition { .. } | Statement::TypeAlias { .. } | Statement::EnumDeclaration { .. } => Effect::Pure,
+            Statement::Attributed { statement, .. } => self.statement(statement),
+        }
+    }
+}
+
+fn collect_bindings<'a>(stmt: &'a Statement, definitions: &mut Vec<(String, &'a Expression)>, others: &mut Vec<String>) {
+    match stmt {
+        Statement::LetStatement { name, value, .. } => {
+            if let Expression::Function(_, parameters, body) = value.as_ref() {
+                definitions.push((name.to_string(), value));
+                others.extend(parameters.iter().map(|p| p.to_string()));
+                collect_bindings(body, definitions, others);
+            } else {
+                others.push(name.to_string());
+                collect_in_expression(value, definitions, others);
+            }
+        }
+        Statement::ExpressionStatement(expr)
+        | Statement::DiscardStatement(_, expr)
+        | Statement::ReturnStatement(_, expr)
+        | Statement::YieldStatement(_, expr) => {
+            collect_in_expression(expr, definitions, others)
+        }
+        Statement::DeferStatement(_, body) => collect_bindings(body, definitions, others),
+        Statement::BlockStatement { statements, .. } => {
+            for s in statements {
+                collect_bindings(s, definitions, others);
+            }
+        }
+        Statement::IfStatement { then_branch, else_branch, .. } => {
+            collect_bindings(then_branch, definitions, others);
+            if let Some(else_branch) = else_branch {
+                collect_bindings(else_branch, definitions, others);
+            }
+        }
+        Statement::WhileStatement { body, .. } => collect_bindings(body, definitions, others),
+        Statement::ForInStatement { variable, iterable, body, .. } => {
+            others.push(variable.to_string());
+            collect_in_expression(iterable, definitions, others);
+            collect_bindings(body, definitions, others);
+        }
+        Statement::ForStatement { initializer, body, .. } => {
+            if let Some(init) = initializer {
+                collect_bindings(init, definitions, others);
+            }
+            collect_bindings(body, definitions, others);
+        }
+        Statement::MacroDefinition { name, parameters, .. } => {
+            others.push(name.clone());
+            others.extend(parameters.iter().cloned());
+        }
+        Statement::Attributed { statement, .. } => collect_bindings(statement, definitions, others),
+        Statement::TypeAlias { .. } => {}
+        Statement::EnumDeclaration { variants, .. } => others.extend(variants.iter().map(|v| v.name.to_string())),
+    }
+}
+
+/// 익명 함수의 매개변수도 이름을 가릴 수 있습니다.
+fn collect_in_expression<'a>(expr: &'a Expression, definitions: &mut Vec<(String, &'a Expression)>, others: &mut Vec<String>) {
+    match expr {
+        Expression::Function(_, parameters, body) => {
+            others.extend(parameters.iter().map(|p| p.to_string()));
+            collect_bindings(body, definitions, others);
+        }
+        Expression::Call(_, callee, args) => {
+            collect_in_expression(callee, definitions, others);
+            for arg in args {
+                collect_in_expression(arg, definitions, others);
+            }
+        }
+        Expression::MacroCall(_, _, args) | Expression::ArrayLiteral(_, args) => {
+            for arg in args {
+                collect_in_expression(arg, definitions, others);
+            }
+        }
+        Expression::MethodCall(_, target, _, args) => {
+            collect_in_expression(target, definitions, others);
+            for arg in args {
+                collect_in_expression(arg, definitions, others);
+            }
+        }
+        Expression::MapLiteral(_, entries) => {
+            for (key, value) in entries {
+                collect_in_expression(key, definitions, others);
+                collect_in_expression(value, definitions, others);
+            }
+        }
+        // 대입된 이름은 어떤 값을 부를지 모릅니다.
+        Expression::Assign(_, _, name, value) => {
+            others.push(name.to_string());
+            collect_in_expression(value, definitions, others);
+        }
+        Expression::PrefixOperation(_, _, inner)
+        | Expression::Grouped(_, inner)
+        | Expression::Reflect(_, inner)
+        | Expression::Eval(_, inner)
+        | Expression::TypeOf(_, inner)
+        | Expression::Member(_, inner, _) => collect_in_expression(inner, definitions, others),
+        Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+            collect_in_expression(left, definitions, others);
+            collect_in_expression(right, definitions, others);
+        }
+        Expression::Ternary(_, cond, then_expr, else_expr) => {
+            collect_in_expression(cond, definitions, others);
+            collect_in_expression(then_expr, definitions, others);
+            collect_in_expression(else_expr, definitions, others);
+        }
+        Expression::Index(_, target, index) => {
+            collect_in_expression(target, definitions, others);
+            collect_in_expression(index, definitions, others);
+        }
+        Expression::Slice(_, target, start, end) => {
+            collect_in_expression(target, definitions, others);
+            for bound in start.iter().chain(end) {
+                collect_in_expression(bound, definitions, others);
+            }
+        }
+        // 패턴의 바인딩도 이름을 가립니다.
+        Expression::Match(_, subject, arms) => {
+            collect_in_expression(subject, definitions, others);
+            for arm in arms {
+                others.extend(patterns::bindings(&arm.pattern).iter().map(|n| n.to_string()));
+                for e in arm.guard.iter().chain([&arm.body]) {
+                    collect_in_expression(e, definitions, others);
+                }
+            }
+        }
+        Expression::Literal(..) | Expression::Identifier(..) => {}
+    }
+}
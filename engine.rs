@@ -0,0 +1,381 @@
+// engine.rs
+// 임베딩용 API: 호스트 애플리케이션(게임 엔진, 도구 등)이 High 스크립트를 실행할 때 사용합니다.
+// 루트 Environment에 전역 값과 프렐류드(prelude)를 주입한 뒤 사용자 코드를 실행합니다.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::cancellation::CancellationToken;
+use crate::conditional_compilation::{self, CfgContext};
+use crate::coverage::Coverage;
+use crate::profile::Profile;
+use crate::trace::Trace;
+use crate::data_structures::{Diagnostic, DiagnosticLevel, Value};
+use crate::ft_runtime::{self, Capabilities, HighEnduranceRuntime, OutputSink};
+use crate::exit_code;
+use crate::input::Input;
+use crate::heap::{CollectionReport, GcStats};
+use crate::interner::Symbol;
+use crate::lexer_service::{LexerPlugin, LexerService};
+use crate::macro_expander::{MacroExpander, MacroTable};
+use crate::parser_service::ParserService;
+use crate::precedence::{Associativity, PrecedenceTable};
+use crate::snapshot::Snapshot;
+use crate::tiering::TierStats;
+
+pub struct Engine {
+    runtime: HighEnduranceRuntime,
+    /// 호스트가 주입한 전역 값 이름 (리로드 시 항상 유지)
+    host_globals: Vec<String>,
+    /// 리로드 시 다시 실행할 프렐류드
+    prelude: Option<String>,
+    /// 스크립트를 읽을 때 쓰는 렉서 플러그인
+    lexer_plugins: Vec<Rc<dyn LexerPlugin>>,
+    /// 사용자 정의 중위 연산자를 포함한 우선순위 표
+    operators: PrecedenceTable,
+    /// 프렐류드와 앞선 실행에서 정의된 매크로 (이후 실행에서도 확장됨)
+    macros: MacroTable,
+    /// 실행 전에 비활성 `@cfg` 항목을 지울 때 쓰는 설정. 기본값은 호스트 OS 의 `her_vm` 입니다.
+    cfg: CfgContext,
+}
+
+/// `Engine::reload` 결과: 어떤 바인딩이 유지/교체/제거되었는지 보고합니다.
+#[derive(Debug, Clone, Default)]
+pub struct ReloadReport {
+    /// 이름과 타입이 같아 이전 값을 그대로 유지한 상태 바인딩
+    pub kept: Vec<String>,
+    /// 새 정의로 교체된 함수/매크로
+    pub replaced: Vec<String>,
+    /// 타입이 바뀌어 새 초기값으로 재설정된 바인딩
+    pub reset: Vec<String>,
+    /// 새 스크립트에서 처음 등장한 바인딩
+    pub added: Vec<String>,
+    /// 새 스크립트에 더 이상 없는 바인딩
+    pub dropped: Vec<String>,
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self {
+            runtime: HighEnduranceRuntime::new(),
+            host_globals: vec![],
+            prelude: None,
+            lexer_plugins: vec![],
+            operators: PrecedenceTable::standard(),
+            macros: MacroTable::default(),
+            cfg: CfgContext::new("her_vm", &[]),
+        }
+    }
+
+    /// 루트 Environment에 전역 값을 주입합니다. (예: `world`, `player`)
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        if !self.host_globals.iter().any(|g| g == name) {
+            self.host_globals.push(name.to_string());
+        }
+        self.runtime.environment.borrow_mut().set(Symbol::intern(name), value);
+    }
+
+    /// 여러 전역 값을 한 번에 주입합니다.
+    pub fn set_globals(&mut self, globals: &[(String, Value)]) {
+        for (name, value) in globals {
+            self.set_global(name, value.clone());
+        }
+    }
+
+    /// 루트 Environment에서 전역 값을 읽습니다.
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.runtime.environment.borrow().get(Symbol::intern(name))
+    }
+
+    /// 이 토큰이 취소되면 실행 중인 스크립트가 반복문이나 함수 호출 사이에서 멈춥니다.
+    pub fn set_cancellation(&mut self, token: CancellationToken) {
+        self.runtime.cancellation = token;
+    }
+
+    /// 스크립트에 허락할 권한 (파일 읽기, 프로세스 실행). 기본값은 `Capabilities::default()` 입니다.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.runtime.capabilities = capabilities;
+    }
+
+    /// 출력 줄을 실행이 끝나기 전에 생기는 대로 받습니다. `run` 의 반환값에도 같은 줄이 들어갑니다.
+    pub fn set_output_sink(&mut self, sink: Option<OutputSink>) {
+        self.runtime.output_sink = sink;
+    }
+
+    /// `@cfg(...)` 를 평가할 설정 (`--cfg` 플래그 등). 이후 실행하는 소스에 적용됩니다.
+    pub fn set_cfg(&mut self, cfg: CfgContext) {
+        self.cfg = cfg;
+    }
+
+    /// `read_line`, `read_all` 이 읽을 입력. 기본값은 빈 입력입니다.
+    pub fn set_input(&mut self, input: Input) {
+        *self.runtime.input.borrow_mut() = input;
+    }
+
+    /// 렉서 플러그인을 등록합니다. 같은 이름의 플러그인이 있으면 교체합니다.
+    /// 플러그인 리터럴이 부르는 생성자 함수는 프렐류드나 전역 값으로 제공해야 합니다.
+    pub fn register_lexer_plugin(&mut self, plugin: Rc<dyn LexerPlugin>) {
+        self.lexer_plugins.retain(|p| p.name() != plugin.name());
+        self.lexer_plugins.push(plugin);
+    }
+
+    /// `left <spelling> right` 를 `function(left, right)` 로 읽는 중위 연산자를 등록합니다.
+    /// 이미 있는 연산자나 문법 기호와 겹치면 등록하지 않고 이유를 돌려줍니다.
+    pub fn register_infix_operator(
+        &mut self,
+        spelling: &str,
+        precedence: u8,
+        associativity: Associativity,
+        function: &str,
+    ) -> Result<(), String> {
+        self.operators.register(spelling, precedence, associativity, function)
+    }
+
+    /// 사용자 코드보다 먼저 프렐류드 소스를 실행하여 그 바인딩을 전역으로 남깁니다.
+    pub fn load_prelude(&mut self, prelude_source: &str) -> Result<(), String> {
+        self.prelude = Some(prelude_source.to_string());
+        self.run_cell(prelude_source)
+            .map(|_| ())
+            .map_err(|e| format!("프렐류드 실행 실패: {}", e))
+    }
+
+    /// 스크립트를 현재 Environment에서 실행하고, 이번 실행에서 생성된 출력 줄을 반환합니다.
+    /// 매크로는 실행 전에 확장되며, 프렐류드나 앞선 실행에서 정의한 매크로도 쓸 수 있습니다.
+    /// 런타임 오류와 HER 치명 진단(E0032 등)은 Err 이며, 그 자리에서 실행을 멈춥니다.
+    pub fn run(&mut self, source: &str) -> Result<Vec<String>, String> {
+        self.run_with(source, true)
+    }
+
+    /// 노트북 셀처럼 프로그램의 일부인 소스를 실행합니다. 맨 바깥 스코프의 흐름 균형(E0032)만 검사하지 않습니다.
+    pub fn run_cell(&mut self, source: &str) -> Result<Vec<String>, String> {
+        self.run_with(source, false)
+    }
+
+    fn run_with(&mut self, source: &str, whole_program: bool) -> Result<Vec<String>, String> {
+        let lexer = LexerService::with_plugins(source, &self.lexer_plugins);
+        let mut parser = ParserService::with_operators(lexer, &self.operators);
+        let mut program = parser.parse_program();
+        // 일부만 읽은 프로그램은 다른 뜻으로 실행되므로 `high build`/`check` 처럼 구문 오류가 있으면 실행하지 않습니다.
+        let errors: Vec<String> = parser
+            .take_diagnostics()
+            .iter()
+            .filter(|d| matches!(d.level, DiagnosticLevel::Error | DiagnosticLevel::HerFatal))
+            .map(Diagnostic::coded_message)
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        conditional_compilation::prune_program(&mut program, &self.cfg);
+        MacroExpander::expand_with(&mut program, &mut self.macros).map_err(|errors| errors.join("\n"))?;
+
+        let first_line = self.runtime.output.len();
+        self.runtime.last_value = None;
+        self.runtime.exit_value = None;
+        let diag = if whole_program { self.runtime.execute_program(program) } else { self.runtime.execute_cell(program) };
+        // 프로그램 종료: 남은 finalizer 의 대상 중 순환에만 붙잡힌 것도 해제되도록 먼저 수집합니다.
+        if self.runtime.panic.is_none() && self.runtime.heap.borrow().has_finalizers() {
+            self.runtime.collect_garbage();
+            self.runtime.run_finalizers();
+        }
+        let output = self.runtime.output[first_line..].to_vec();
+
+        if let Some(panic) = self.runtime.panic.take() {
+            return Err(format!(
+                "{} ({}..{})\n{}",
+                panic.message, panic.span.start, panic.span.end, panic.stack_trace()
+            ));
+        }
+        if matches!(diag.level, DiagnosticLevel::Error | DiagnosticLevel::HerFatal) {
+            Err(diag.coded_message())
+        } else {
+            Ok(output)
+        }
+    }
+
+    /// 스크립트를 다시 컴파일하여 함수 정의를 교체하고, 이름과 타입이 그대로인 상태 값은 유지합니다.
+    ///
+    /// 새 스크립트는 별도의 런타임에서 (호스트 전역 값과 프렐류드를 포함해) 실행되며,
+    /// 그 결과 바인딩을 기존 Environment와 병합합니다. 실패하면 기존 상태는 바뀌지 않습니다.
+    pub fn reload(&mut self, source: &str) -> Result<ReloadReport, String> {
+        let mut fresh = Engine::new();
+        fresh.lexer_plugins = self.lexer_plugins.clone();
+        fresh.operators = self.operators.clone();
+        fresh.cfg = self.cfg.clone();
+        for name in &self.host_globals {
+            if let Some(value) = self.get_global(name) {
+                fresh.set_global(name, value);
+            }
+        }
+        if let Some(prelude) = self.prelude.clone() {
+            fresh.load_prelude(&prelude)?;
+        }
+        fresh.run(source)?;
+
+        let old_store = self.runtime.environment.borrow().store.clone();
+        let mut new_store = fresh.runtime.environment.borrow().store.clone();
+        let mut report = ReloadReport::default();
+
+        for (name, new_value) in new_store.iter_mut() {
+            if self.host_globals.iter().any(|g| *name == g.as_str()) {
+                continue;
+            }
+            match old_store.get(name) {
+                _ if matches!(new_value, Value::Function(_) | Value::Macro(_)) => {
+                    report.replaced.push(name.to_string());
+                }
+                Some(old_value) if ft_runtime::type_name(old_value) == ft_runtime::type_name(new_value) => {
+                    *new_value = old_value.clone();
+                    report.kept.push(name.to_string());
+                }
+                Some(_) => report.reset.push(name.to_string()),
+                None => report.added.push(name.to_string()),
+            }
+        }
+        report.dropped = old_store
+            .keys()
+            .filter(|name| !new_store.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect();
+
+        for list in [&mut report.kept, &mut report.replaced, &mut report.reset, &mut report.added, &mut report.dropped] {
+            list.sort();
+        }
+
+        let mut environment = self.runtime.environment.borrow_mut();
+        environment.store = new_store;
+        environment.mutable = fresh.runtime.environment.borrow().mutable.clone();
+        Ok(report)
+    }
+
+    /// 전역 상태의 체크포인트 (snapshot.rs). 매크로 표는 담지 않으므로 되돌린 뒤에도 그 사이에 정의한 매크로는 남습니다.
+    pub fn snapshot(&self) -> Snapshot {
+        self.runtime.snapshot()
+    }
+
+    /// `snapshot` 을 찍은 때의 전역 상태로 되돌립니다.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), String> {
+        self.runtime.restore(snapshot)
+    }
+
+    /// 함수를 바이트코드로 올리는 호출 횟수를 정합니다. None 이면 항상 인터프리터로 실행합니다.
+    pub fn set_tier_up_threshold(&mut self, threshold: Option<u32>) {
+        self.runtime.tiering.borrow_mut().threshold = threshold;
+    }
+
+    /// 이후 `run` 에서 실행하는 문장의 적중 횟수를 셉니다. 프렐류드 문장을 빼려면 `load_prelude` 뒤에 켭니다.
+    pub fn enable_coverage(&mut self) -> Rc<RefCell<Coverage>> {
+        self.runtime.enable_coverage()
+    }
+
+    /// 이후 `run` 에서 실행하는 분기, 루프, 함수 호출을 셉니다. (high build --profile-generate, profile.rs)
+    pub fn enable_profile(&mut self) -> Rc<RefCell<Profile>> {
+        self.runtime.enable_profile()
+    }
+
+    /// 이후 `run` 에서 실행하는 문장을 기록합니다. (high run --trace, trace.rs)
+    pub fn enable_trace(&mut self) -> Rc<RefCell<Trace>> {
+        self.runtime.enable_trace()
+    }
+
+    /// 인터프리터/바이트코드 호출 통계
+    pub fn tier_stats(&self) -> TierStats {
+        self.runtime.tiering.borrow().stats.clone()
+    }
+
+    /// 도달할 수 없는 제너레이터 순환을 지금 회수합니다. (스크립트의 `gc()` 와 같음)
+    pub fn collect_garbage(&mut self) -> CollectionReport {
+        let report = self.runtime.collect_garbage();
+        self.runtime.run_finalizers();
+        report
+    }
+
+    /// 이 Engine 이 만들어진 뒤의 누적 수집 통계
+    pub fn gc_stats(&self) -> GcStats {
+        self.runtime.gc_stats()
+    }
+
+    /// 마지막 `run` 의 종료 코드 (exit_code.rs). 실패한 `run` 이면 의미가 없습니다.
+    pub fn exit_code(&self) -> i32 {
+        exit_code::from_value(self.runtime.exit_value.as_ref())
+    }
+
+    /// 마지막 `run` 에서 실행된 최상위 `return` 의 값. `return` 이 없었으면 None
+    pub fn exit_value(&self) -> Option<&Value> {
+        self.runtime.exit_value.as_ref()
+    }
+
+    /// 마지막 `run` 에서 마지막으로 실행된 식 문장이나 return 의 값
+    pub fn last_value(&self) -> Option<&Value> {
+        self.runtime.last_value.as_ref()
+    }
+
+    /// 내부 런타임에 직접 접근합니다.
+    pub fn runtime(&mut self) -> &mut HighEnduranceRuntime {
+        &mut self.runtime
+    }
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+impl Engine {
+    /// 소스를 실행하고 최상위 `return` 의 값을 돌려줍니다. 실행이 실패하거나 `return` 이 없으면 패닉합니다.
+    pub(crate) fn exit_value_of(&mut self, source: &str) -> Value {
+        self.run(source).unwrap();
+        self.exit_value().cloned().unwrap()
+    }
+}
+
+/// 새 엔진에서 소스를 실행한 최상위 `return` 의 값 (`Engine::exit_value_of`)
+#[cfg(test)]
+pub(crate) fn run_to_exit(source: &str) -> Value {
+    Engine::new().exit_value_of(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn her_fatal_diagnostics_fail_the_run() {
+        let error = Engine::new().run("{ let a = 1; } return 5;").unwrap_err();
+        assert!(error.starts_with("[E0032]"), "{}", error);
+        let error = Engine::new().run("let a = 1;\nlet b = 2;\nlet c = 3;\nlet d = 4;").unwrap_err();
+        assert!(error.starts_with("[E0032]"), "{}", error);
+        // `return` 으로 끝나는 프로그램과 블록은 균형이 맞습니다.
+        let mut engine = Engine::new();
+        engine.run("{ let a = 1; return a; } return 5;").unwrap();
+        assert!(matches!(engine.exit_value(), Some(Value::Integer(5))));
+    }
+
+    #[test]
+    fn inactive_cfg_items_are_pruned_before_running() {
+        let source = "@cfg(target = \"linux\") let sep = \"/\";\n@cfg(target = \"windows\") let sep = \"\\\\\";\nreturn sep;";
+        let mut engine = Engine::new();
+        engine.set_cfg(CfgContext { target_os: "linux".into(), ..CfgContext::new("her_vm", &[]) });
+        engine.run(source).unwrap();
+        assert!(matches!(engine.exit_value(), Some(Value::String(s)) if &**s == "/"));
+        let mut engine = Engine::new();
+        engine.set_cfg(CfgContext { target_os: "windows".into(), ..CfgContext::new("her_vm", &[]) });
+        engine.run(source).unwrap();
+        assert!(matches!(engine.exit_value(), Some(Value::String(s)) if &**s == "\\"));
+        // `--cfg` 플래그
+        let mut engine = Engine::new();
+        engine.set_cfg(CfgContext::new("her_vm", &["fast".to_string()]));
+        engine.run("let n = 1;\n@cfg(fast) let n = 2;\nreturn n;").unwrap();
+        assert!(matches!(engine.exit_value(), Some(Value::Integer(2))));
+    }
+
+    #[test]
+    fn cells_skip_the_outer_flow_check_only() {
+        let mut engine = Engine::new();
+        engine.run_cell("let a = 1;").unwrap();
+        engine.run_cell("let b = a + 1;").unwrap();
+        assert!(matches!(engine.get_global("b"), Some(Value::Integer(2))));
+        assert!(engine.run_cell("{ let c = 1; }").unwrap_err().starts_with("[E0032]"));
+    }
+}
@@ -0,0 +1,1195 @@
+// error_codes.rs
+// 진단 코드 레지스트리: 모든 진단은 여기 등록된 코드로 만들어집니다.
+//
+// 코드는 한 번 배포되면 의미가 바뀌지 않습니다. 새 진단은 새 번호를 받고,
+// 더 이상 쓰지 않는 코드도 `high explain` 을 위해 목록에 남겨 둡니다.
+//
+//   E00xx 런타임 · E002x 서식 · E003x 실행 흐름 · E004x 어트리뷰트
+//   E005x 매크로 · E006x 이름 해석 · E007x match 검사 · E008x 컴파일 · E009x 코드 생성
+//   E010x 코드 분석 · E011x 구문
+
+use core::fmt::Display;
+
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, DiagnosticLevel, Span, Value};
+use crate::messages::{self, t, Localized};
+
+/// 레지스트리에 등록된 진단 코드 하나입니다.
+#[derive(Debug)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub level: DiagnosticLevel,
+    pub title: Localized,
+    /// `high explain` 으로 출력되는 긴 설명 (예제 포함)
+    pub explanation: Localized,
+}
+
+impl ErrorCode {
+    /// 메시지 카탈로그에서 이 코드의 메시지를 찾습니다. `detail` 이 비어 있지 않으면 `코드.detail` 키를 씁니다.
+    pub fn message(&self, detail: &str, args: &[&dyn Display]) -> String {
+        if detail.is_empty() {
+            messages::tr(self.code, args)
+        } else {
+            messages::tr(&format!("{}.{}", self.code, detail), args)
+        }
+    }
+
+    /// 카탈로그 메시지로 진단을 만듭니다.
+    pub fn diagnostic(&self, span: Span, detail: &str, args: &[&dyn Display]) -> Diagnostic {
+        self.at(span, self.message(detail, args))
+    }
+
+    /// 카탈로그 메시지를 `[E0000] 메시지` 형식으로 돌려줍니다.
+    pub fn tagged(&self, detail: &str, args: &[&dyn Display]) -> String {
+        self.tag(self.message(detail, args))
+    }
+
+    /// 카탈로그 메시지로 런타임 오류 값을 만듭니다.
+    pub fn error_value(&self, detail: &str, args: &[&dyn Display]) -> Value {
+        self.runtime_error(self.message(detail, args))
+    }
+
+    /// 이 코드의 진단을 만듭니다.
+    pub fn at(&self, span: Span, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            level: self.level.clone(),
+            message: message.into(),
+            span,
+            help: None,
+            code: Some(self.code),
+        }
+    }
+
+    /// 진단 대신 문자열 오류만 쓰는 단계(매크로 확장, 코드 생성 등)를 위한 `[E0000] 메시지` 형식입니다.
+    pub fn tag(&self, message: impl AsRef<str>) -> String {
+        format!("[{}] {}", self.code, message.as_ref())
+    }
+
+    /// 런타임 오류 값을 만듭니다.
+    pub fn runtime_error(&self, message: impl AsRef<str>) -> Value {
+        Value::Error(self.tag(message))
+    }
+}
+
+impl Diagnostic {
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// 코드가 있으면 `[E0000] 메시지`, 없으면 메시지만 돌려줍니다.
+    pub fn coded_message(&self) -> String {
+        match self.code {
+            Some(code) => format!("[{}] {}", code, self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+// ─── 런타임 ─────────────────────────────
+
+pub const UNDEFINED_VARIABLE: ErrorCode = ErrorCode {
+    code: "E0001",
+    level: DiagnosticLevel::Error,
+    title: t("정의되지 않은 변수", "undefined variable"),
+    explanation: t(
+        "\
+선언되지 않았거나 현재 스코프에서 보이지 않는 이름을 사용했습니다.
+
+    {
+        let inner = 1
+    }
+    return inner      // E0001: inner 는 블록 밖에서 보이지 않습니다
+
+이름의 철자를 확인하거나, 사용하는 위치를 선언과 같은 스코프로 옮기세요.",
+        "\
+A name was used that is not declared or not visible in the current scope.
+
+    {
+        let inner = 1
+    }
+    return inner      // E0001: inner is not visible outside the block
+
+Check the spelling, or move the use into the same scope as the declaration.",
+    ),
+};
+
+pub const NOT_CALLABLE: ErrorCode = ErrorCode {
+    code: "E0002",
+    level: DiagnosticLevel::Error,
+    title: t("호출할 수 없는 값", "value is not callable"),
+    explanation: t(
+        "\
+함수가 아닌 값을 호출했습니다.
+
+    let n = 3
+    n(1)              // E0002: 정수는 호출할 수 없습니다
+
+호출 대상이 `fn(...) { ... }` 함수 값인지 확인하세요.",
+        "\
+A value that is not a function was called.
+
+    let n = 3
+    n(1)              // E0002: integers cannot be called
+
+Make sure the callee is a `fn(...) { ... }` function value.",
+    ),
+};
+
+pub const ARITY_MISMATCH: ErrorCode = ErrorCode {
+    code: "E0003",
+    level: DiagnosticLevel::Error,
+    title: t("인자 개수 불일치", "wrong number of arguments"),
+    explanation: t(
+        "\
+함수나 내장 함수에 선언과 다른 개수의 인자를 넘겼습니다.
+
+    let add = fn(a, b) { return a + b }
+    add(1)            // E0003: 2개 예상, 1개 제공
+
+`assert()` 는 조건식이, `format()` 은 서식 문자열이 반드시 필요합니다.",
+        "\
+A function or built-in was called with a different number of arguments than it declares.
+
+    let add = fn(a, b) { return a + b }
+    add(1)            // E0003: expected 2, got 1
+
+`assert()` always needs a condition and `format()` always needs a format string.",
+    ),
+};
+
+pub const UNSUPPORTED: ErrorCode = ErrorCode {
+    code: "E0004",
+    level: DiagnosticLevel::Error,
+    title: t("아직 지원되지 않는 기능", "unsupported feature"),
+    explanation: t(
+        "\
+현재 실행 백엔드가 처리하지 못하는 표현식이나 기능을 사용했습니다.
+
+같은 프로그램이 다른 백엔드(예: 네이티브 코드 생성)에서는 동작할 수 있습니다.
+가능하면 지원되는 더 단순한 형태로 바꾸어 쓰세요.",
+        "\
+The current execution backend cannot handle this expression or feature yet.
+
+The same program may work on another backend (for example native codegen).
+Where possible, rewrite it in a simpler supported form.",
+    ),
+};
+
+pub const EVAL_FAILED: ErrorCode = ErrorCode {
+    code: "E0005",
+    level: DiagnosticLevel::Error,
+    title: t("eval 실패", "eval failed"),
+    explanation: t(
+        "\
+`eval` 로 실행한 문자열 코드가 오류로 끝났습니다.
+
+    eval \"return undefined_name\"   // E0005
+
+eval 에 넘기는 코드를 별도 파일로 실행해 보면 원래 오류를 더 쉽게 볼 수 있습니다.",
+        "\
+Code executed through `eval` ended with an error.
+
+    eval \"return undefined_name\"   // E0005
+
+Running the evaluated code as a separate file makes the original error easier to see.",
+    ),
+};
+
+pub const CAPABILITY_DENIED: ErrorCode = ErrorCode {
+    code: "E0006",
+    level: DiagnosticLevel::Error,
+    title: t("허용되지 않은 작업", "capability denied"),
+    explanation: t(
+        "\
+스크립트가 호스트에게 허락받지 않은 일(프로세스 실행, 파일 읽기, 호출 스택·바인딩 들여다보기)을 하려고 했습니다.
+
+    run_process(\"git\", \"status\")   // E0006: 기본값은 거부
+    locals()                         // E0006: 기본값은 거부
+
+`high run --allow-process` 나 임베딩 API 의 `Options::allow_process` 로 허용합니다.
+`callstack()` / `locals()` 는 `--allow-debug` (`Capabilities::debug`) 로 허용합니다.
+신뢰할 수 없는 스크립트에는 허용하지 마세요.",
+        "\
+The script tried to do something the host has not allowed (running processes, reading files,
+inspecting the call stack or bindings).
+
+    run_process(\"git\", \"status\")   // E0006: denied by default
+    locals()                         // E0006: denied by default
+
+Grant it with `high run --allow-process` or `Options::allow_process` in the embedding API.
+`callstack()` / `locals()` are granted with `--allow-debug` (`Capabilities::debug`).
+Do not grant it to untrusted scripts.",
+    ),
+};
+
+pub const ASSIGN_IMMUTABLE: ErrorCode = ErrorCode {
+    code: "E0007",
+    level: DiagnosticLevel::Error,
+    title: t("불변 바인딩에 대입", "assignment to immutable binding"),
+    explanation: t(
+        "\
+`let` 으로 만든 바인딩에 대입했습니다. 값을 바꿀 바인딩은 `let mut` 으로 선언합니다.
+
+    let total = 0
+    total += 1                 // E0007
+    let mut count = 0
+    count += 1                 // 됩니다
+
+대입은 이름을 찾을 수 있는 가장 가까운 바인딩을 바꿉니다. 같은 스코프에서 `let` 으로 다시 바인딩하면
+그 이름은 다시 불변입니다. 선언된 곳을 알 수 있으면 실행 전에 보고하고, 아니면 실행 중에 오류 값이 됩니다.",
+        "\
+A binding created with `let` was assigned to. Declare bindings that change with `let mut`.
+
+    let total = 0
+    total += 1                 // E0007
+    let mut count = 0
+    count += 1                 // fine
+
+An assignment changes the nearest binding of the name. Rebinding the name with `let` in the same scope
+makes it immutable again. It is reported before running when the declaration is known, and otherwise
+becomes an error value at run time.",
+    ),
+};
+
+pub const TYPE_MISMATCH: ErrorCode = ErrorCode {
+    code: "E0010",
+    level: DiagnosticLevel::Error,
+    title: t("타입 불일치", "type mismatch"),
+    explanation: t(
+        "\
+연산이나 내장 함수가 기대하는 타입과 다른 값을 받았습니다.
+
+    eval 42                    // E0010: eval 은 문자열을 받습니다
+    format(1, 2)               // E0010: 서식 문자열은 문자열이어야 합니다
+
+`type_of` 로 값의 실제 타입을 확인할 수 있습니다.",
+        "\
+An operation or built-in received a value of a different type than it expects.
+
+    eval 42                    // E0010: eval takes a string
+    format(1, 2)               // E0010: the format string must be a string
+
+Use `type_of` to inspect the actual type of a value.",
+    ),
+};
+
+pub const UNKNOWN_TYPE: ErrorCode = ErrorCode {
+    code: "E0011",
+    level: DiagnosticLevel::Error,
+    title: t("알 수 없는 타입", "unknown type"),
+    explanation: t(
+        "\
+타입 표기에 쓴 이름이 내장 타입도 아니고 `type` 으로 선언한 별칭도 아닙니다.
+
+    let distance: Meter = 10      // E0011: `type Meter = int` 가 없습니다
+
+내장 타입은 int, float, bool, string, void, any 입니다. 철자가 비슷한 이름이 있으면 도움말이 제안합니다.
+별칭은 프로그램 어디에서 선언해도 보이므로 쓰는 곳보다 뒤에 둬도 됩니다.",
+        "\
+A name used in a type annotation is neither a built-in type nor an alias declared with `type`.
+
+    let distance: Meter = 10      // E0011: there is no `type Meter = int`
+
+The built-in types are int, float, bool, string, void and any. The help suggests a similarly spelled name
+when there is one. Aliases are visible throughout the program, so they may be declared after their uses.",
+    ),
+};
+
+pub const INVALID_TYPE_ALIAS: ErrorCode = ErrorCode {
+    code: "E0012",
+    level: DiagnosticLevel::Error,
+    title: t("잘못된 타입 별칭", "invalid type alias"),
+    explanation: t(
+        "\
+`type` 별칭 선언이 잘못되었습니다. 같은 이름을 두 번 선언했거나, 별칭끼리 서로를 가리켜
+내장 타입에 닿지 않습니다.
+
+    type Meters = int
+    type Meters = float           // E0012: 이미 선언된 이름
+    type A = B
+    type B = A                    // E0012: A -> B -> A
+
+타입 이름은 프로그램 전체에서 하나의 이름공간을 쓰므로 블록 안의 별칭도 다른 별칭과 이름이 겹치면 안 됩니다.",
+        "\
+A `type` alias declaration is invalid: the name is declared twice, or aliases refer to each other
+and never reach a built-in type.
+
+    type Meters = int
+    type Meters = float           // E0012: already declared
+    type A = B
+    type B = A                    // E0012: A -> B -> A
+
+Type names share one namespace across the whole program, so an alias in a block must not reuse another alias's name.",
+    ),
+};
+
+pub const IMPLICIT_ANY: ErrorCode = ErrorCode {
+    code: "E0013",
+    level: DiagnosticLevel::Error,
+    title: t("암시적 any", "implicit any"),
+    explanation: t(
+        "\
+`--strict` 에서 표기가 없고 초기값으로 타입을 알 수 없는 바인딩입니다. 이런 바인딩은 기본 모드에서
+암시적으로 `any` 가 되어 검사가 실행 시점으로 미뤄지는데, strict 모드는 이를 허용하지 않습니다.
+
+    let n = parse(text)           // E0013: 호출 결과의 타입을 알 수 없습니다
+    let n: int = parse(text)      // 실행 시점에 int 인지 확인합니다
+    let n: any = parse(text)      // 명시한 any 는 strict 에서도 허용됩니다
+
+리터럴, 표기된 바인딩, 함수 리터럴처럼 타입을 알 수 있는 초기값은 표기가 없어도 됩니다.
+함수 매개변수는 표기할 수 없으므로 대상이 아닙니다.",
+        "\
+Under `--strict`, a binding has no annotation and its type cannot be inferred from its initializer.
+In the default mode such a binding is implicitly `any` and its checks are deferred to run time;
+strict mode forbids that.
+
+    let n = parse(text)           // E0013: the type of a call result is unknown
+    let n: int = parse(text)      // checked to be an int at run time
+    let n: any = parse(text)      // an explicit any is allowed even under strict
+
+Initializers whose type is known, such as literals, annotated bindings and function literals, need no annotation.
+Function parameters cannot be annotated and are not checked.",
+    ),
+};
+
+// ─── 서식 ─────────────────────────────
+
+pub const INVALID_FORMAT: ErrorCode = ErrorCode {
+    code: "E0020",
+    level: DiagnosticLevel::Error,
+    title: t("잘못된 서식 문자열", "invalid format string"),
+    explanation: t(
+        "\
+`format()` 의 서식 문자열을 해석할 수 없거나 인자와 맞지 않습니다.
+
+    format(\"{}, {}\", 1)        // E0020: 인자가 부족합니다
+    format(\"{:q}\", 1)          // E0020: 지원하지 않는 지정자
+    format(\"{:x}\", 1.5)        // E0020: 16진수는 정수에만 쓸 수 있습니다
+
+지원하는 형식: {[index][:[[fill]align][0][width][.precision][x|X]]}
+중괄호 문자 자체는 `{{` 와 `}}` 로 씁니다.",
+        "\
+The format string passed to `format()` cannot be parsed or does not match its arguments.
+
+    format(\"{}, {}\", 1)        // E0020: not enough arguments
+    format(\"{:q}\", 1)          // E0020: unsupported specifier
+    format(\"{:x}\", 1.5)        // E0020: hex only applies to integers
+
+Supported syntax: {[index][:[[fill]align][0][width][.precision][x|X]]}
+Write `{{` and `}}` for literal braces.",
+    ),
+};
+
+// ─── 실행 흐름 ─────────────────────────────
+
+pub const PANIC: ErrorCode = ErrorCode {
+    code: "E0030",
+    level: DiagnosticLevel::Error,
+    title: t("패닉", "panic"),
+    explanation: t(
+        "\
+`panic(msg)` 가 호출되었거나 `assert(cond, msg)` 의 조건이 거짓이었습니다.
+
+    assert(balance >= 0, \"잔액은 음수가 될 수 없습니다\")   // E0030
+
+진단의 도움말에 호출 스택이 함께 출력됩니다. 패닉이 스코프를 빠져나가는 동안
+등록된 `defer` 블록은 모두 실행됩니다.",
+        "\
+`panic(msg)` was called, or the condition of `assert(cond, msg)` was false.
+
+    assert(balance >= 0, \"balance must not be negative\")   // E0030
+
+The diagnostic help includes the call stack. Every registered `defer` block
+runs while the panic unwinds out of its scope.",
+    ),
+};
+
+pub const YIELD_OUTSIDE_GENERATOR: ErrorCode = ErrorCode {
+    code: "E0031",
+    level: DiagnosticLevel::Error,
+    title: t("제너레이터 밖의 yield", "yield outside a generator"),
+    explanation: t(
+        "\
+`yield` 는 제너레이터 함수의 본문 안에서만 쓸 수 있습니다.
+
+    yield 1                               // E0031
+
+    let numbers = fn() { yield 1 yield 2 }  // 올바른 사용",
+        "\
+`yield` may only appear in the body of a generator function.
+
+    yield 1                               // E0031
+
+    let numbers = fn() { yield 1 yield 2 }  // correct",
+    ),
+};
+
+pub const UNBALANCED_FLOW: ErrorCode = ErrorCode {
+    code: "E0032",
+    level: DiagnosticLevel::HerFatal,
+    title: t("균형이 맞지 않는 실행 흐름", "unbalanced execution flow"),
+    explanation: t(
+        "\
+HER 런타임의 실행 흐름 검사가 스코프 안의 문장 수가 균형을 이루지 않는다고 판단했습니다.
+`return` 으로 끝나는 스코프는 검사하지 않습니다.
+
+이 검사는 HER VM 고유의 규칙이며, 제어 흐름이 올바르게 종료되도록 문장을 정리하면 사라집니다.
+
+    { let a = 1; }             // E0032
+    return 5;
+
+    { let a = 1; return a; }   // correct",
+        "\
+The HER runtime's flow check decided the number of statements in a scope is unbalanced.
+Scopes that end in `return` are not checked.
+
+This rule is specific to the HER VM; it goes away once control flow in the scope terminates cleanly.
+
+    { let a = 1; }             // E0032
+    return 5;
+
+    { let a = 1; return a; }   // correct",
+    ),
+};
+
+pub const DIVISION_BY_ZERO: ErrorCode = ErrorCode {
+    code: "E0033",
+    level: DiagnosticLevel::Warning,
+    title: t("항상 0 으로 나누는 연산", "division by a constant zero"),
+    explanation: t(
+        "\
+`/` 나 `%` 의 나누는 쪽이 컴파일 시점에 정수 0 으로 계산됩니다. 이 식은 실행되면 반드시 패닉(E0030)합니다.
+
+    let average = total / (2 - 2)     // E0033
+
+정수 나눗셈은 몫을 0 쪽으로 자르고, 나머지의 부호는 나뉘는 수를 따릅니다. (-7 / 2 == -3, -7 % 2 == -1)
+의도한 패닉이면 `panic(msg)` 로 바꾸거나 `@allow(division_by_zero)` 로 경고를 끌 수 있습니다.",
+        "\
+The divisor of `/` or `%` evaluates to the integer 0 at compile time. The expression always panics (E0030)
+when it runs.
+
+    let average = total / (2 - 2)     // E0033
+
+Integer division truncates toward zero, and the remainder takes the sign of the dividend
+(-7 / 2 == -3, -7 % 2 == -1). If the panic is intended, use `panic(msg)` instead, or silence the warning
+with `@allow(division_by_zero)`.",
+    ),
+};
+
+// ─── 어트리뷰트 ─────────────────────────────
+
+pub const UNKNOWN_ATTRIBUTE: ErrorCode = ErrorCode {
+    code: "E0040",
+    level: DiagnosticLevel::Warning,
+    title: t("알 수 없는 어트리뷰트", "unknown attribute"),
+    explanation: t(
+        "\
+컴파일러가 모르는 어트리뷰트입니다. 경고만 내고 무시합니다.
+
+    @inlnie          // E0040: @inline 의 오타
+    let x = 1
+
+사용 가능한 어트리뷰트: @cfg, @test, @inline, @deprecated, @allow, @derive",
+        "\
+The compiler does not know this attribute. It is ignored with a warning.
+
+    @inlnie          // E0040: typo of @inline
+    let x = 1
+
+Available attributes: @cfg, @test, @inline, @deprecated, @allow, @derive",
+    ),
+};
+
+pub const INVALID_ATTRIBUTE_ARGS: ErrorCode = ErrorCode {
+    code: "E0041",
+    level: DiagnosticLevel::Error,
+    title: t("잘못된 어트리뷰트 인자", "invalid attribute arguments"),
+    explanation: t(
+        "\
+어트리뷰트에 허용되지 않는 인자를 넘겼습니다.
+
+    @cfg                 // E0041: 조건이 필요합니다
+    @test(fast)          // E0041: @test 는 인자를 받지 않습니다
+    @deprecated(a, b)    // E0041: 문자열 메시지 하나만 받습니다
+    @derive(hash)        // E0041: eq, show, clone 만 자동 구현할 수 있습니다",
+        "\
+An attribute received arguments it does not accept.
+
+    @cfg                 // E0041: a condition is required
+    @test(fast)          // E0041: @test takes no arguments
+    @deprecated(a, b)    // E0041: takes a single string message
+    @derive(hash)        // E0041: only eq, show and clone can be derived",
+    ),
+};
+
+pub const MISPLACED_ATTRIBUTE: ErrorCode = ErrorCode {
+    code: "E0042",
+    level: DiagnosticLevel::Error,
+    title: t("붙일 수 없는 위치의 어트리뷰트", "attribute on an unsupported item"),
+    explanation: t(
+        "\
+어트리뷰트가 적용되지 않는 항목에 붙었습니다.
+
+    @derive(eq)          // E0042: @derive 는 enum 선언에만 붙습니다
+    let point = 1",
+        "\
+The attribute was placed on an item it does not apply to.
+
+    @derive(eq)          // E0042: @derive applies to enum declarations only
+    let point = 1",
+    ),
+};
+
+// ─── 매크로 ─────────────────────────────
+
+pub const MACRO_BODY: ErrorCode = ErrorCode {
+    code: "E0050",
+    level: DiagnosticLevel::Error,
+    title: t("확장할 수 없는 매크로 본문", "macro body cannot be expanded"),
+    explanation: t(
+        "\
+매크로 본문은 하나의 표현식(또는 return 문)이어야 합니다.
+
+    macro twice(x) { return x * 2 }        // 올바름
+    macro bad(x) { let y = x return y }    // E0050",
+        "\
+A macro body must be a single expression (or return statement).
+
+    macro twice(x) { return x * 2 }        // correct
+    macro bad(x) { let y = x return y }    // E0050",
+    ),
+};
+
+pub const MACRO_RECURSION: ErrorCode = ErrorCode {
+    code: "E0051",
+    level: DiagnosticLevel::Error,
+    title: t("매크로 확장 깊이 초과", "macro expansion too deep"),
+    explanation: t(
+        "\
+매크로가 자기 자신을 (직접 또는 간접적으로) 계속 확장하여 최대 깊이를 넘었습니다.
+
+    macro forever(x) { return forever(x) }   // E0051
+
+재귀가 필요하면 매크로 대신 함수를 사용하세요.",
+        "\
+A macro kept expanding itself (directly or indirectly) past the maximum depth.
+
+    macro forever(x) { return forever(x) }   // E0051
+
+Use a function instead of a macro when recursion is needed.",
+    ),
+};
+
+pub const MACRO_ARITY: ErrorCode = ErrorCode {
+    code: "E0052",
+    level: DiagnosticLevel::Error,
+    title: t("매크로 인자 개수 불일치", "wrong number of macro arguments"),
+    explanation: t(
+        "\
+매크로 호출의 인자 수가 매크로 정의의 매개변수 수와 다릅니다.
+
+    macro twice(x) { return x * 2 }
+    twice(1, 2)      // E0052: 1개 예상, 2개 제공",
+        "\
+The number of arguments in a macro call differs from the macro's parameters.
+
+    macro twice(x) { return x * 2 }
+    twice(1, 2)      // E0052: expected 1, got 2",
+    ),
+};
+
+// ─── 이름 해석 ─────────────────────────────
+
+pub const DEPRECATED_USE: ErrorCode = ErrorCode {
+    code: "E0060",
+    level: DiagnosticLevel::Warning,
+    title: t("사용 중단된 이름 사용", "use of a deprecated name"),
+    explanation: t(
+        "\
+`@deprecated` 로 표시된 이름을 사용했습니다.
+
+    @deprecated(\"new_limit 를 사용하세요\")
+    let old_limit = 10
+    return old_limit     // E0060
+
+문장 앞의 `@allow(deprecated)` 또는 파일 맨 앞의 `@!allow(deprecated)` 로 경고를 끌 수 있습니다.",
+        "\
+A name marked `@deprecated` was used.
+
+    @deprecated(\"use new_limit\")
+    let old_limit = 10
+    return old_limit     // E0060
+
+Silence it with `@allow(deprecated)` on the statement or `@!allow(deprecated)` at the top of the file.",
+    ),
+};
+
+pub const DEAD_FUNCTION: ErrorCode = ErrorCode {
+    code: "E0061",
+    level: DiagnosticLevel::Warning,
+    title: t("도달할 수 없는 함수", "unreachable function"),
+    explanation: t(
+        "\
+최상위 코드, `fn main`, `@export` 로 표시한 정의 어디에서도 부르거나 참조하지 않는 함수(매크로)입니다.
+도달할 수 없는 함수끼리만 서로 부르는 경우도 포함합니다.
+
+    let helper = fn(x) { return x * 2; }     // E0061
+    return 1
+
+쓰지 않는 정의는 지우거나, 외부에서 부를 진입점이면 `@export` 를 붙이세요. `@allow(dead_code)` 또는
+파일 맨 앞의 `@!allow(dead_code)` 로 경고를 끌 수 있습니다. `--strip-dead` 는 코드 생성 전에 이 정의들을 지웁니다.
+`eval` 을 쓰는 프로그램은 어떤 이름이든 부를 수 있으므로 검사하지 않습니다.",
+        "\
+A function (or macro) that is never called or referenced from top-level code, `fn main`, or a definition
+marked `@export`. Functions that only call each other are included.
+
+    let helper = fn(x) { return x * 2; }     // E0061
+    return 1
+
+Remove the definition, or mark it `@export` if it is an entry point called from outside. Silence it with
+`@allow(dead_code)` or `@!allow(dead_code)` at the top of the file. `--strip-dead` removes these definitions
+before code generation. Programs that use `eval` are not checked, since it can call any name.",
+    ),
+};
+
+pub const UNREACHABLE_MODULE: ErrorCode = ErrorCode {
+    code: "E0062",
+    level: DiagnosticLevel::Info,
+    title: t("쓰이지 않는 모듈", "unused module"),
+    explanation: t(
+        "\
+링크된 표준 라이브러리 모듈의 정의를 프로그램이 하나도 쓰지 않습니다. `--strip-dead` 로 컴파일할 때
+알려 주며, 그 모듈은 코드 생성 전에 통째로 빠집니다.",
+        "\
+The program uses none of the definitions of a linked standard library module. This is reported when compiling
+with `--strip-dead`, and the whole module is removed before code generation.",
+    ),
+};
+
+// ─── match 검사 ─────────────────────────────
+
+pub const UNREACHABLE_ARM: ErrorCode = ErrorCode {
+    code: "E0070",
+    level: DiagnosticLevel::Warning,
+    title: t("도달할 수 없는 match arm", "unreachable match arm"),
+    explanation: t(
+        "\
+앞선 arm 들이 이 arm 의 모든 값을 이미 처리하므로 이 arm 은 실행되지 않습니다.
+
+    match n {
+        _ => 0,
+        1 => 1,      // E0070
+    }",
+        "\
+Earlier arms already handle every value of this arm, so it never runs.
+
+    match n {
+        _ => 0,
+        1 => 1,      // E0070
+    }",
+    ),
+};
+
+pub const SHADOWED_ALTERNATIVE: ErrorCode = ErrorCode {
+    code: "E0071",
+    level: DiagnosticLevel::Warning,
+    title: t("가려진 or-패턴 대안", "shadowed or-pattern alternative"),
+    explanation: t(
+        "\
+or-패턴의 대안이 같은 패턴의 앞선 대안에 완전히 포함됩니다.
+
+    match n {
+        0..10 | 5 => \"small\",   // E0071: 5 는 0..10 에 포함됩니다
+        _ => \"big\",
+    }",
+        "\
+An alternative of an or-pattern is fully covered by an earlier alternative of the same pattern.
+
+    match n {
+        0..10 | 5 => \"small\",   // E0071: 5 is already in 0..10
+        _ => \"big\",
+    }",
+    ),
+};
+
+pub const NON_EXHAUSTIVE_MATCH: ErrorCode = ErrorCode {
+    code: "E0072",
+    level: DiagnosticLevel::Warning,
+    title: t("모든 경우를 처리하지 않는 match", "non-exhaustive match"),
+    explanation: t(
+        "\
+match 가 일부 값에 대해 일치하는 arm 이 없습니다. 그런 값이 들어오면 결과는 null 입니다.
+
+    match n {
+        0 => \"zero\",
+    }                 // E0072: `_ => ...` arm 을 추가하세요",
+        "\
+Some values have no matching arm. For those values the match evaluates to null.
+
+    match n {
+        0 => \"zero\",
+    }                 // E0072: add a `_ => ...` arm",
+    ),
+};
+
+// ─── 컴파일 ─────────────────────────────
+
+pub const MISSING_RETURN: ErrorCode = ErrorCode {
+    code: "E0080",
+    level: DiagnosticLevel::Error,
+    title: t("return 누락", "missing return"),
+    explanation: t(
+        "\
+컴파일되는 프로그램은 마지막 문장(또는 마지막 블록의 마지막 문장)이 return 이어야 합니다.
+
+    let x = 1
+    return x         // 올바름",
+        "\
+A compiled program must end with a return statement (or a block whose last statement is a return).
+
+    let x = 1
+    return x         // correct",
+    ),
+};
+
+pub const INVALID_TARGET: ErrorCode = ErrorCode {
+    code: "E0081",
+    level: DiagnosticLevel::Error,
+    title: t("잘못된 타깃 설정", "invalid target configuration"),
+    explanation: t(
+        "\
+`--target-cpu`, `--features` 또는 `--target-os` 값이 지원되지 않습니다.
+
+    high --target-cpu x86-64-v4      // E0081
+    high --features +avx512          // E0081
+    high --target-os freebsd         // E0081
+
+지원 CPU: x86-64, x86-64-v2, x86-64-v3
+지원 기능: cmov, sse2, sse4.2, popcnt, avx2
+지원 OS: linux (ELF), windows (PE), macos (Mach-O)",
+        "\
+The `--target-cpu`, `--features` or `--target-os` value is not supported.
+
+    high --target-cpu x86-64-v4      // E0081
+    high --features +avx512          // E0081
+    high --target-os freebsd         // E0081
+
+Supported CPUs: x86-64, x86-64-v2, x86-64-v3
+Supported features: cmov, sse2, sse4.2, popcnt, avx2
+Supported OSes: linux (ELF), windows (PE), macos (Mach-O)",
+    ),
+};
+
+// ─── 코드 생성 ─────────────────────────────
+
+pub const CODEGEN_FAILED: ErrorCode = ErrorCode {
+    code: "E0090",
+    level: DiagnosticLevel::Error,
+    title: t("어셈블리 생성 실패", "assembly generation failed"),
+    explanation: t(
+        "\
+네이티브 코드 생성기가 어셈블리 파일을 쓰지 못했습니다.
+
+출력 디렉터리의 쓰기 권한과 남은 디스크 공간을 확인하세요.",
+        "\
+The native code generator could not write the assembly file.
+
+Check write permissions and free disk space in the output directory.",
+    ),
+};
+
+pub const LINK_FAILED: ErrorCode = ErrorCode {
+    code: "E0091",
+    level: DiagnosticLevel::Error,
+    title: t("어셈블 또는 링크 실패", "assembling or linking failed"),
+    explanation: t(
+        "\
+NASM 또는 링커 실행이 실패했습니다. 도구가 출력한 오류는 각각 이 코드의 진단으로 함께 보고됩니다.
+
+`nasm` 과 링커(`ld`, Windows 에서는 `gcc`)가 PATH 에 있는지 확인하거나, 경로를 직접 지정하세요.
+
+    high --nasm /opt/nasm/bin/nasm --linker /usr/bin/ld.bfd",
+        "\
+Running NASM or the linker failed. Each error the tool printed is also reported as a diagnostic with this code.
+
+Make sure `nasm` and a linker (`ld`, or `gcc` on Windows) are on PATH, or give their paths explicitly.
+
+    high --nasm /opt/nasm/bin/nasm --linker /usr/bin/ld.bfd",
+    ),
+};
+
+pub const BACKEND_UNSUPPORTED: ErrorCode = ErrorCode {
+    code: "E0092",
+    level: DiagnosticLevel::Error,
+    title: t("백엔드가 지원하지 않는 구문", "construct not supported by the backend"),
+    explanation: t(
+        "\
+선택한 코드 생성 백엔드가 이 구문을 옮길 수 없습니다. 인터프리터에서는 실행됩니다.
+
+Rust 백엔드는 최상위 `let` 에 묶인 함수만 `fn` 항목으로 만들며, 함수를 값으로 넘기거나
+최상위 변수를 함수 안에서 읽을 수 없습니다. reflect/eval/typeof, yield, defer 도 아직 지원하지 않습니다.
+
+    let add = fn(a, b) { return a + b; };   // fn add(a: Value, b: Value) -> Value
+    let twice = fn(f, x) { return f(f(x)); }; // E0092: 함수 값 호출",
+        "\
+The selected code generation backend cannot translate this construct. The interpreter still runs it.
+
+The Rust backend turns only functions bound by a top-level `let` into `fn` items. Functions cannot be
+passed as values or read top-level variables. reflect/eval/typeof, yield and defer are not supported yet.
+
+    let add = fn(a, b) { return a + b; };   // fn add(a: Value, b: Value) -> Value
+    let twice = fn(f, x) { return f(f(x)); }; // E0092: call through a function value",
+    ),
+};
+
+pub const CARGO_BUILD_FAILED: ErrorCode = ErrorCode {
+    code: "E0093",
+    level: DiagnosticLevel::Error,
+    title: t("생성된 cargo 프로젝트 빌드 실패", "building the generated cargo project failed"),
+    explanation: t(
+        "\
+`--cargo-build` 로 생성된 프로젝트에서 `cargo build --release` 를 실행했지만 실패했습니다.
+
+`cargo` 가 PATH 에 있는지 확인하고, 생성된 디렉터리에서 직접 빌드해 컴파일러 출력을 확인하세요.",
+        "\
+`--cargo-build` ran `cargo build --release` in the generated project and it failed.
+
+Make sure `cargo` is on PATH, and build the generated directory by hand to see the compiler output.",
+    ),
+};
+
+pub const PYTHON_FAILED: ErrorCode = ErrorCode {
+    code: "E0094",
+    level: DiagnosticLevel::Error,
+    title: t("Python 인터프리터 실행 실패", "running the Python interpreter failed"),
+    explanation: t(
+        "\
+`--run-python` 이 생성된 코드를 Python 인터프리터에 넘기지 못했습니다.
+
+Python 3 이 `python3` (Windows 에서는 `python`) 으로 PATH 에 있는지 확인하세요.
+생성된 코드는 compiled.py 에 남아 있으므로 직접 실행해 볼 수도 있습니다.",
+        "\
+`--run-python` could not hand the generated code to the Python interpreter.
+
+Make sure Python 3 is on PATH as `python3` (`python` on Windows).
+The generated code is kept in compiled.py, so you can also run it by hand.",
+    ),
+};
+
+pub const CANCELLED: ErrorCode = ErrorCode {
+    code: "E0095",
+    level: DiagnosticLevel::Error,
+    title: t("작업 취소됨", "operation cancelled"),
+    explanation: t(
+        "\
+컴파일 또는 실행이 끝나기 전에 취소되었습니다. 결과의 실행 상태는 `Cancelled` 입니다.
+
+데몬의 `cancel` 요청이나 호스트의 CancellationToken 이 취소를 알리면, 진행 중인 단계가 멈추고
+실행 중이던 외부 도구(NASM, 링커, cargo, Python)는 종료됩니다. 인터프리터는 반복문과 함수 호출
+사이에서 멈추며, 등록된 `defer` 블록은 패닉과 같은 방식으로 실행됩니다.",
+        "\
+The compilation or execution was cancelled before it finished. The result's execution status is `Cancelled`.
+
+When a daemon `cancel` request or the host's CancellationToken signals cancellation, the running stage stops
+and any external tool in progress (NASM, the linker, cargo, Python) is killed. The interpreter stops between
+loop iterations and function calls, running registered `defer` blocks the same way as for a panic.",
+    ),
+};
+
+pub const STAGE_TIMED_OUT: ErrorCode = ErrorCode {
+    code: "E0096",
+    level: DiagnosticLevel::Error,
+    title: t("단계 제한 시간 초과", "stage timed out"),
+    explanation: t(
+        "\
+컴파일 단계 하나가 `CompileOptions::timeouts` 에 정한 시간 안에 끝나지 않아 컴파일을 멈췄습니다.
+결과의 실행 상태는 `TimedOut` 입니다.
+
+단계는 analysis, parsing, optimization, codegen, tools(NASM, 링커, cargo), execution 이며
+`--timeout <단계>=<ms>` 로 바꿀 수 있습니다. 0 은 제한 없음입니다.
+
+    high --timeout execution=500 --timeout tools=60000
+
+외부 도구와 인터프리터는 시간이 지나는 즉시 멈추고, 파싱처럼 중간에 멈출 수 없는 단계는
+끝난 뒤에 판정합니다. NASM 이나 링커가 멈춘 것 같다면 도구 설치와 입력 파일을 확인하세요.",
+        "\
+A compilation stage did not finish within the limit set in `CompileOptions::timeouts`, so compilation stopped.
+The result's execution status is `TimedOut`.
+
+The stages are analysis, parsing, optimization, codegen, tools (NASM, the linker, cargo) and execution,
+and each can be changed with `--timeout <stage>=<ms>`. 0 means no limit.
+
+    high --timeout execution=500 --timeout tools=60000
+
+External tools and the interpreter are stopped as soon as the limit passes; stages that cannot be interrupted,
+such as parsing, are judged after they finish. If NASM or the linker seems to hang, check the tool install and its input.",
+    ),
+};
+
+pub const NOT_REPRODUCIBLE: ErrorCode = ErrorCode {
+    code: "E0097",
+    level: DiagnosticLevel::Error,
+    title: t("재현할 수 없는 빌드", "build is not reproducible"),
+    explanation: t(
+        "\
+`--reproducible` 빌드에서 같은 요청을 다시 컴파일했더니 산출물이 처음과 바이트 단위로 달랐습니다.
+
+재현 가능한 빌드는 증명 블록 시간을 SOURCE_DATE_EPOCH (없으면 0) 로 고정하고, 파일로 쓴 산출물
+(NASM 어셈블리, LLVM IR, Python 소스, Rust 프로젝트)을 두 번째 컴파일과 비교합니다. 같은 입력에서
+출력이 달라졌다면 컴파일러 패스가 시간, 난수, 해시 맵 순회 순서 같은 비결정적인 값을 출력에 흘린
+것이므로 컴파일러 버그로 보고해 주세요.
+
+    high --reproducible --target llvm",
+        "\
+In a `--reproducible` build, compiling the same request a second time produced output that differs byte-for-byte
+from the first.
+
+Reproducible builds pin the proof block time to SOURCE_DATE_EPOCH (0 if unset) and compare every artifact written
+to disk (NASM assembly, LLVM IR, Python source, the Rust project) against a second compilation. Output that
+changes for the same input means a compiler pass leaked something nondeterministic, such as the time, random
+numbers or hash map iteration order, into the output; please report it as a compiler bug.
+
+    high --reproducible --target llvm",
+    ),
+};
+
+pub const STALE_PROFILE: ErrorCode = ErrorCode {
+    code: "E0098",
+    level: DiagnosticLevel::Warning,
+    title: t("소스와 맞지 않는 프로필", "profile does not match the source"),
+    explanation: t(
+        "\
+`--profile-use` 로 준 프로필이 지금 컴파일하는 소스로 만든 것이 아닙니다. 프로필은 분기, 루프, 함수를
+소스의 문자 위치로 가리키므로 소스가 바뀌면 엉뚱한 문장을 가리킵니다. 컴파일러는 이 프로필을 쓰지 않고
+프로필 없이 최적화합니다.
+
+    high build main.high --profile-generate           # high.profile 을 다시 만듭니다
+    high build main.high --opt-level 2 --profile-use high.profile
+
+소스를 고친 뒤에는 프로필을 다시 만드세요.",
+        "\
+The profile given with `--profile-use` was not recorded from the source being compiled. A profile points at
+branches, loops and functions by character position in the source, so after an edit it points at the wrong
+statements. The compiler ignores the profile and optimizes without it.
+
+    high build main.high --profile-generate           # recreates high.profile
+    high build main.high --opt-level 2 --profile-use high.profile
+
+Regenerate the profile after changing the source.",
+    ),
+};
+
+// ─── 코드 분석 ─────────────────────────────
+
+pub const LONG_FUNCTION: ErrorCode = ErrorCode {
+    code: "E0100",
+    level: DiagnosticLevel::Warning,
+    title: t("함수가 너무 김", "function is too long"),
+    explanation: t(
+        "\
+함수(또는 매크로) 본문이 50줄을 넘습니다. 긴 함수는 읽고 테스트하기 어렵습니다.
+
+    let process = fn(order) {
+        ...               // E0100: 80줄짜리 함수
+    }
+
+서로 다른 일을 하는 부분을 이름 있는 작은 함수로 나누세요.",
+        "\
+The body of a function (or macro) is longer than 50 lines. Long functions are
+hard to read and to test.
+
+    let process = fn(order) {
+        ...               // E0100: an 80-line function
+    }
+
+Split the parts that do different jobs into smaller named functions.",
+    ),
+};
+
+pub const POOR_NAMING: ErrorCode = ErrorCode {
+    code: "E0101",
+    level: DiagnosticLevel::Info,
+    title: t("의미가 드러나지 않는 이름", "uninformative names"),
+    explanation: t(
+        "\
+함수 안에서 의미를 알기 어려운 이름을 사용했습니다. 관례적인 반복 변수
+(i, j, k, n, x, y, z)를 제외한 한 글자 이름과 tmp, foo, data 같은 이름이 해당합니다.
+
+    let total = fn(a, b) {
+        let tmp = a       // E0101: 'a', 'b', 'tmp'
+        return tmp
+    }
+
+값이 무엇을 나타내는지 드러나는 이름을 쓰세요.",
+        "\
+The function uses names whose meaning is hard to tell: single-letter names other
+than conventional loop variables (i, j, k, n, x, y, z), and names such as tmp, foo or data.
+
+    let total = fn(a, b) {
+        let tmp = a       // E0101: 'a', 'b', 'tmp'
+        return tmp
+    }
+
+Use names that say what the value represents.",
+    ),
+};
+
+pub const ANALYZER_NOTE: ErrorCode = ErrorCode {
+    code: "E0102",
+    level: DiagnosticLevel::Info,
+    title: t("외부 분석기 의견", "external analyzer note"),
+    explanation: t(
+        "\
+`--analyzer` 로 선택한 외부 분석기가 이 위치에 대해 남긴 의견입니다.
+내용은 분석기마다 다르며 컴파일 결과에는 영향을 주지 않습니다.",
+        "\
+A note left at this location by the external analyzer selected with `--analyzer`.
+Its content depends on the analyzer and does not affect compilation.",
+    ),
+};
+
+pub const ANALYZER_UNAVAILABLE: ErrorCode = ErrorCode {
+    code: "E0103",
+    level: DiagnosticLevel::Warning,
+    title: t("분석기를 사용할 수 없음", "analyzer unavailable"),
+    explanation: t(
+        "\
+`--analyzer` 로 선택한 분석 백엔드가 등록되어 있지 않거나, 실패했거나,
+`--analyzer-timeout` 안에 응답하지 않아 기본 휴리스틱 분석기로 대신했습니다.
+
+    high --analyzer http://127.0.0.1:9000/analyze --analyzer-timeout 500
+
+분석기 서버가 실행 중인지, 주소와 제한 시간이 맞는지 확인하세요.",
+        "\
+The analysis backend selected with `--analyzer` is not registered, failed, or did
+not answer within `--analyzer-timeout`, so the default heuristic analyzer was used.
+
+    high --analyzer http://127.0.0.1:9000/analyze --analyzer-timeout 500
+
+Check that the analyzer server is running and that the address and timeout are right.",
+    ),
+};
+
+pub const UNUSED_VALUE: ErrorCode = ErrorCode {
+    code: "E0104",
+    level: DiagnosticLevel::Warning,
+    title: t("쓰이지 않는 값", "unused value"),
+    explanation: t(
+        "\
+부작용이 없는 식을 문장으로 쓰면 값을 계산한 뒤 그대로 버리므로 아무 일도 하지 않습니다.
+함수 본문의 마지막 식도 반환값이 되지 않으므로 `return` 이 필요합니다.
+
+    1 + 2;                        // E0104
+    _ = 1 + 2;                    // 일부러 버립니다
+    let area = fn(w, h) {
+        w * h                     // E0104: `return w * h` 로 쓰세요
+    }
+
+입출력이 있거나 효과를 알 수 없는 호출은 대상이 아닙니다. 프로그램의 마지막 문장은 결과로
+출력되므로 검사하지 않습니다. `@allow(unused_value)` 로 끌 수 있습니다.",
+        "\
+An expression without side effects used as a statement computes a value and throws it away,
+so it does nothing. The last expression of a function body is not its result either; use `return`.
+
+    1 + 2;                        // E0104
+    _ = 1 + 2;                    // discarded on purpose
+    let area = fn(w, h) {
+        w * h                     // E0104: write `return w * h`
+    }
+
+Calls with I/O or unknown effects are not reported. The last statement of a program is printed
+as its result and is not checked. Silence it with `@allow(unused_value)`.",
+    ),
+};
+
+// ─── 구문 ─────────────────────────────
+
+pub const SYNTAX_ERROR: ErrorCode = ErrorCode {
+    code: "E0110",
+    level: DiagnosticLevel::Error,
+    title: t("구문 오류", "syntax error"),
+    explanation: t(
+        "\
+소스를 문법대로 읽을 수 없습니다. 파서는 오류를 남기고 가까운 경계에서 다시 읽기 시작하므로
+한 번에 여러 오류가 보고될 수 있습니다.
+
+    f(a b)                        // E0110: 'b' 앞에 ',' 가 빠졌습니다
+    f(a, +)                       // E0110: 예상하지 못한 '+'. 닫는 ')' 에서 다시 읽습니다
+    f(a, b,)                      // 끝의 쉼표는 허용됩니다
+    f(a                           // E0110: '(' 가 닫히지 않았습니다
+    let s = \"abc                  // E0110: 문자열이 닫히지 않았습니다
+
+호출 인자, 매개변수, 어트리뷰트 인자 목록은 각각 최대 255개입니다.",
+        "\
+The source cannot be read according to the grammar. The parser records the error and resumes
+at a nearby boundary, so several errors may be reported at once.
+
+    f(a b)                        // E0110: missing ',' before 'b'
+    f(a, +)                       // E0110: unexpected '+'; parsing resumes at the closing ')'
+    f(a, b,)                      // a trailing comma is allowed
+    f(a                           // E0110: unclosed '('
+    let s = \"abc                  // E0110: unterminated string literal
+
+Call arguments, parameters and attribute arguments are limited to 255 entries per list.",
+    ),
+};
+
+/// 등록된 모든 코드 (번호 순)
+pub const ALL: &[&ErrorCode] = &[
+    &UNDEFINED_VARIABLE,
+    &NOT_CALLABLE,
+    &ARITY_MISMATCH,
+    &UNSUPPORTED,
+    &EVAL_FAILED,
+    &CAPABILITY_DENIED,
+    &ASSIGN_IMMUTABLE,
+    &TYPE_MISMATCH,
+    &UNKNOWN_TYPE,
+    &INVALID_TYPE_ALIAS,
+    &IMPLICIT_ANY,
+    &INVALID_FORMAT,
+    &PANIC,
+    &YIELD_OUTSIDE_GENERATOR,
+    &UNBALANCED_FLOW,
+    &DIVISION_BY_ZERO,
+    &UNKNOWN_ATTRIBUTE,
+    &INVALID_ATTRIBUTE_ARGS,
+    &MISPLACED_ATTRIBUTE,
+    &MACRO_BODY,
+    &MACRO_RECURSION,
+    &MACRO_ARITY,
+    &DEPRECATED_USE,
+    &DEAD_FUNCTION,
+    &UNREACHABLE_MODULE,
+    &UNREACHABLE_ARM,
+    &SHADOWED_ALTERNATIVE,
+    &NON_EXHAUSTIVE_MATCH,
+    &MISSING_RETURN,
+    &INVALID_TARGET,
+    &CODEGEN_FAILED,
+    &LINK_FAILED,
+    &BACKEND_UNSUPPORTED,
+    &CARGO_BUILD_FAILED,
+    &PYTHON_FAILED,
+    &CANCELLED,
+    &STAGE_TIMED_OUT,
+    &NOT_REPRODUCIBLE,
+    &STALE_PROFILE,
+    &LONG_FUNCTION,
+    &POOR_NAMING,
+    &ANALYZER_NOTE,
+    &ANALYZER_UNAVAILABLE,
+    &UNUSED_VALUE,
+    &SYNTAX_ERROR,
+];
+
+/// 코드 문자열(대소문자 무시)로 등록된 코드를 찾습니다.
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    ALL.iter().copied().find(|c| c.code.eq_ignore_ascii_case(code))
+}
+
+/// `high explain <code>` 출력 텍스트입니다.
+pub fn explain(code: &str) -> Option<String> {
+    lookup(code).map(|c| format!("{} ({:?}): {}\n\n{}\n", c.code, c.level, c.title.get(), c.explanation.get()))
+}
@@ -0,0 +1,314 @@
+// escape_analysis.rs
+// 탈출 분석: 클로저(함수 값) 할당이 만들어진 스코프보다 오래 살아남는지 판정합니다.
+//
+// 살아남지 않는다고 증명된 할당은 네이티브 코드 생성에서 힙 대신 스택에 놓입니다.
+// 증명하지 못한 경우는 모두 탈출로 봅니다(보수적). 값이 탈출하는 경우:
+//   - return / yield 로 돌려줌
+//   - 함수 호출의 인자로 넘김 (호출된 쪽이 보관할 수 있음)
+//   - 다른 바인딩에 복사함 (`let g = f`)
+//   - 다른 함수 본문에서 참조함 (그 함수가 캡처하여 더 오래 살 수 있음)
+//   - reflect() 로 넘김
+// 호출 대상(`f(1)`)으로 쓰이거나 연산자 피연산자, typeof() 인자로만 쓰이면 탈출하지 않습니다.
+
+
+use crate::compat::*;
+use crate::data_structures::{Expression, Program, Span, Statement};
+use crate::interner::Symbol;
+use crate::patterns;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AllocationKind {
+    Closure,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EscapeReason {
+    Returned,
+    Yielded,
+    PassedAsArgument,
+    StoredInBinding(String),
+    CapturedByClosure,
+    Reflected,
+    StoredInArray,
+    StoredInMap,
+}
+
+/// 할당 지점 하나 (현재는 함수 값을 만드는 식)
+#[derive(Debug, Clone)]
+pub struct AllocationSite {
+    pub kind: AllocationKind,
+    pub span: Span,
+    /// `let name = fn...` 으로 바인딩된 경우 그 이름
+    pub binding: Option<String>,
+    /// 탈출하면 첫 번째로 발견된 이유, 스택에 둘 수 있으면 None
+    pub escape: Option<EscapeReason>,
+}
+
+impl AllocationSite {
+    pub fn on_stack(&self) -> bool {
+        self.escape.is_none()
+    }
+}
+
+/// 컴파일 결과에 실리는 할당 통계
+#[derive(Debug, Clone, Default)]
+pub struct AllocationStats {
+    pub sites: usize,
+    /// 힙 할당이 생략되고 스택에 놓인 수
+    pub stack: usize,
+    pub heap: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct EscapeReport {
+    /// 소스 순서의 할당 지점들
+    pub sites: Vec<AllocationSite>,
+}
+
+impl EscapeReport {
+    pub fn stats(&self) -> AllocationStats {
+        let stack = self.sites.iter().filter(|s| s.on_stack()).count();
+        AllocationStats { sites: self.sites.len(), stack, heap: self.sites.len() - stack }
+    }
+
+    /// `let name = fn...` 바인딩의 할당 지점을 찾습니다. 같은 이름이 여러 번 바인딩되면 첫 번째입니다.
+    pub fn binding(&self, name: &str) -> Option<&AllocationSite> {
+        self.sites.iter().find(|s| s.binding.as_deref() == Some(name))
+    }
+}
+
+/// 값이 쓰이는 문맥
+#[derive(Debug, Clone)]
+enum Use {
+    /// 값이 곧 버려지거나 소비됨 (연산자 피연산자, 조건 등)
+    Consumed,
+    /// 호출 대상
+    Callee,
+    Escapes(EscapeReason),
+}
+
+pub fn analyze(program: &Program) -> EscapeReport {
+    let mut analyzer = EscapeAnalyzer { report: EscapeReport::default(), scopes: vec![HashMap::new()], enclosing: vec![] };
+    for stmt in &program.statements {
+        analyzer.statement(stmt);
+    }
+    analyzer.report
+}
+
+struct EscapeAnalyzer {
+    report: EscapeReport,
+    /// 이름 → (할당 지점 번호, 선언된 함수 깊이). 할당이 아닌 바인딩은 None 으로 이름을 가립니다.
+    scopes: Vec<HashMap<Symbol, Option<(usize, usize)>>>,
+    /// 지금 분석 중인 함수 본문들의 할당 지점 (바깥부터). 길이가 곧 중첩 깊이입니다.
+    enclosing: Vec<usize>,
+}
+
+impl EscapeAnalyzer {
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: Symbol, site: Option<usize>) {
+        let depth = self.enclosing.len();
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, site.map(|s| (s, depth)));
+        }
+    }
+
+    fn lookup(&self, name: Symbol) -> Option<(usize, usize)> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name)).copied().flatten()
+    }
+
+    fn escape(&mut self, site: usize, reason: EscapeReason) {
+        let entry = &mut self.report.sites[site].escape;
+        if entry.is_none() {
+            *entry = Some(reason);
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::LetStatement { name, value, .. } => {
+                let site = match value.as_ref() {
+                    Expression::Function(span, parameters, body) => Some(self.function(*span, Some(*name), parameters, body)),
+                    other => {
+                        self.expression(other, Use::Escapes(EscapeReason::StoredInBinding(name.to_string())));
+                        None
+                    }
+                };
+                self.bind(*name, site);
+            }
+            Statement::ExpressionStatement(expr) | Statement::DiscardStatement(_, expr) => self.expression(expr, Use::Consumed),
+            Statement::ReturnStatement(_, expr) => self.expression(expr, Use::Escapes(EscapeReason::Returned)),
+            Statement::YieldStatement(_, expr) => self.expression(expr, Use::Escapes(EscapeReason::Yielded)),
+            Statement::BlockStatement { statements, .. } => self.scoped(|a| {
+                for s in statements {
+                    a.statement(s);
+                }
+            }),
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                self.expression(condition, Use::Consumed);
+                self.scoped(|a| a.statement(then_branch));
+                if let Some(else_branch) = else_branch {
+                    self.scoped(|a| a.statement(else_branch));
+                }
+            }
+            Statement::WhileStatement { condition, body, .. } => {
+                self.expression(condition, Use::Consumed);
+                self.scoped(|a| a.statement(body));
+            }
+            // 요소가 반복 변수에 바인딩됩니다.
+            Statement::ForInStatement { variable, iterable, body, .. } => {
+                self.expression(iterable, Use::Escapes(EscapeReason::StoredInBinding(variable.to_string())));
+                self.scoped(|a| {
+                    a.bind(*variable, None);
+                    a.statement(body);
+                });
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => self.scoped(|a| {
+                if let Some(init) = initializer {
+                    a.statement(init);
+                }
+                for expr in condition.iter().chain(increment.iter()) {
+                    a.expression(expr, Use::Consumed);
+                }
+                a.statement(body);
+            }),
+            // defer 블록은 둘러싼 스코프가 끝나기 전에 실행되므로 같은 스코프로 봅니다.
+            Statement::DeferStatement(_, body) => self.scoped(|a| a.statement(body)),
+            Statement::MacroDefinition { .. } | Statement::TypeAlias { .. } => {}
+            Statement::EnumDeclaration { variants, .. } => {
+                for variant in variants {
+                    self.bind(variant.name, None);
+                }
+            }
+            Statement::Attributed { statement, .. } => self.statement(statement),
+        }
+    }
+
+    /// 함수 값 할당 지점을 등록하고 본문을 분석합니다.
+    fn function(&mut self, span: Span, binding: Option<Symbol>, parameters: &[Symbol], body: &Statement) -> usize {
+        let site = self.report.sites.len();
+        self.report.sites.push(AllocationSite {
+            kind: AllocationKind::Closure,
+            span,
+            binding: binding.map(|name| name.to_string()),
+            escape: None,
+        });
+        self.enclosing.push(site);
+        self.scoped(|a| {
+            // 재귀 호출은 자기 자신을 호출 대상으로만 쓰므로 탈출이 아닙니다.
+            if let Some(name) = binding {
+                a.bind(name, Some(site));
+            }
+            for p in parameters {
+                a.bind(*p, None);
+            }
+            a.statement(body);
+        });
+        self.enclosing.pop();
+        site
+    }
+
+    fn expression(&mut self, expr: &Expression, usage: Use) {
+        match expr {
+            Expression::Identifier(_, name) => {
+                let Some((site, depth)) = self.lookup(*name) else {
+                    return;
+                };
+                let captured = depth < self.enclosing.len() && !self.enclosing.contains(&site);
+                match usage {
+                    Use::Escapes(reason) => self.escape(site, reason),
+                    // 바깥 함수의 클로저를 안쪽 함수가 참조하면 캡처로 봅니다 (자기 재귀 호출 제외).
+                    Use::Callee | Use::Consumed if captured => {
+                        self.escape(site, EscapeReason::CapturedByClosure)
+                    }
+                    _ => {}
+                }
+            }
+            Expression::Function(span, parameters, body) => {
+                let site = self.function(*span, None, parameters, body);
+                if let Use::Escapes(reason) = usage {
+                    self.escape(site, reason);
+                }
+            }
+            Expression::Call(_, callee, args) => {
+                self.expression(callee, Use::Callee);
+                for arg in args {
+                    self.expression(arg, Use::Escapes(EscapeReason::PassedAsArgument));
+                }
+            }
+            Expression::MacroCall(_, _, args) => {
+                for arg in args {
+                    self.expression(arg, Use::Escapes(EscapeReason::PassedAsArgument));
+                }
+            }
+            // 받는 값도 첫 인자로 넘어갑니다.
+            Expression::MethodCall(_, target, _, args) => {
+                for arg in core::iter::once(target).chain(args) {
+                    self.expression(arg, Use::Escapes(EscapeReason::PassedAsArgument));
+                }
+            }
+            // 배열에 넣은 값은 배열을 따라 어디로든 갈 수 있습니다.
+            Expression::ArrayLiteral(_, elements) => {
+                for element in elements {
+                    self.expression(element, Use::Escapes(EscapeReason::StoredInArray));
+                }
+            }
+            // 키는 int, bool, string 만 되므로 맵에 남는 것은 값뿐입니다.
+            Expression::MapLiteral(_, entries) => {
+                for (key, value) in entries {
+                    self.expression(key, Use::Consumed);
+                    self.expression(value, Use::Escapes(EscapeReason::StoredInMap));
+                }
+            }
+            Expression::Grouped(_, inner) => self.expression(inner, usage),
+            Expression::Ternary(_, cond, then_expr, else_expr) => {
+                self.expression(cond, Use::Consumed);
+                self.expression(then_expr, usage.clone());
+                self.expression(else_expr, usage);
+            }
+            Expression::PrefixOperation(_, _, inner)
+            | Expression::TypeOf(_, inner)
+            | Expression::Eval(_, inner)
+            | Expression::Member(_, inner, _) => {
+                self.expression(inner, Use::Consumed)
+            }
+            Expression::Reflect(_, inner) => self.expression(inner, Use::Escapes(EscapeReason::Reflected)),
+            // 대입한 값은 바인딩에 남아 스코프를 벗어날 때까지 살아 있습니다.
+            Expression::Assign(_, _, name, value) => {
+                self.expression(value, Use::Escapes(EscapeReason::StoredInBinding(name.to_string())))
+            }
+            Expression::InfixOperation(_, _, left, right) | Expression::Index(_, left, right) | Expression::Range(_, left, right, _) => {
+                self.expression(left, Use::Consumed);
+                self.expression(right, Use::Consumed);
+            }
+            Expression::Slice(_, target, start, end) => {
+                self.expression(target, Use::Consumed);
+                for bound in start.iter().chain(end) {
+                    self.expression(bound, Use::Consumed);
+                }
+            }
+            Expression::Match(_, subject, arms) => {
+                // 패턴이 바인딩하는 이름은 대상 값을 가리킵니다.
+                let bound = arms.iter().flat_map(|arm| patterns::bindings(&arm.pattern)).next();
+                let subject_use = bound.map_or(Use::Consumed, |name| Use::Escapes(EscapeReason::StoredInBinding(name.to_string())));
+                self.expression(subject, subject_use);
+                for arm in arms {
+                    self.scoped(|a| {
+                        for name in patterns::bindings(&arm.pattern) {
+                            a.bind(name, None);
+                        }
+                        if let Some(guard) = &arm.guard {
+                            a.expression(guard, Use::Consumed);
+                        }
+                        a.expression(&arm.body, usage.clone());
+                    });
+                }
+            }
+            Expression::Literal(..) => {}
+        }
+    }
+}
@@ -1,69 +1,228 @@
-use tokio::time::{self, Duration};
-
-/// 실행 상태를 나타내는 열거형
-#[derive(Debug)]
-pub enum ExecutionStatus {
-    Success,
-    RuntimeError,
-    Skipped,
-}
-
-/// 코드 실행 요청 구조체
-#[derive(Debug)]
-pub struct ExecutionRequest {
-    pub compiled_code_reference: String,
-    pub input_data: Option<String>,
-}
-
-/// 실행 결과 구조체
-#[derive(Debug)]
-pub struct ExecutionResult {
-    pub output_log: Vec<String>,
-    pub status: ExecutionStatus,
-    pub execution_time_ms: u128,
-}
-
-/// 실행기 서비스
-pub struct ExecutorService {}
-
-impl ExecutorService {
-    pub fn new() -> Self {
-        println!("[Executor] ExecutorService가 초기화되었습니다.");
-        Self {}
-    }
-
-    pub async fn execute_code(&self, request: ExecutionRequest) -> ExecutionResult {
-        let start_time = time::Instant::now();
-        let mut output_log = vec![];
-        let mut status = ExecutionStatus::Success;
-
-        println!("[Executor] 코드 실행 시작...");
-        time::sleep(Duration::from_millis(30)).await;
-        output_log.push(">> [System] Runtime environment started.".into());
-
-        let delay = (request.compiled_code_reference.len() * 2).max(50);
-        time::sleep(Duration::from_millis(delay as u64)).await;
-
-        if request.compiled_code_reference.contains("error") {
-            status = ExecutionStatus::RuntimeError;
-            let fault = request.compiled_code_reference.split(' ').last().unwrap_or("UNKNOWN");
-            output_log.push(format!(">> [Error] Segmentation Fault at instruction: {}", fault));
-        } else {
-            output_log.push(Self::generate_output(&request));
-        }
-
-        let execution_time_ms = start_time.elapsed().as_millis();
-        println!("[Executor] 실행 완료. 상태: {:?}, 소요 시간: {}ms", status, execution_time_ms);
-
-        ExecutionResult {
-            output_log,
-            status,
-            execution_time_ms,
-        }
-    }
-
-    fn generate_output(request: &ExecutionRequest) -> String {
-        let input = request.input_data.as_deref().unwrap_or("None");
-        format!(">> [Code Output] Hello from the compiled code! Input data was: {}", input)
-    }
-}
+use std::process::Command;
+use std::rc::Rc;
+
+use tokio::sync::mpsc;
+use tokio::time::{self, Duration};
+
+use crate::cancellation::{self, CancellationToken, CommandError};
+use crate::data_structures::Value;
+use crate::engine::Engine;
+use crate::error_codes;
+use crate::exit_code;
+use crate::ft_runtime::{Capabilities, OutputSink};
+use crate::heap::GcStats;
+use crate::input::Input;
+
+/// `execute_code_streaming` 이 출력 줄을 생기는 대로 보내는 채널
+pub type OutputStream = mpsc::UnboundedSender<String>;
+
+/// 실행 상태를 나타내는 열거형
+#[derive(Debug)]
+pub enum ExecutionStatus {
+    Success,
+    RuntimeError,
+    Skipped,
+    /// 끝나기 전에 CancellationToken 으로 취소됨
+    Cancelled,
+    /// 단계 제한 시간(`StageTimeouts`)을 넘겨 멈춤
+    TimedOut,
+}
+
+/// 코드 실행 요청 구조체
+#[derive(Debug, Default)]
+pub struct ExecutionRequest {
+    pub compiled_code_reference: String,
+    /// 프로그램의 표준 입력. 인터프리터에서는 `read_line`/`read_all` 이 읽고, 네이티브 바이너리에는 파이프로 넘깁니다.
+    pub input_data: Option<String>,
+    /// 인터프리터 모드: 네이티브 바이너리 대신 이 소스를 직접 실행합니다.
+    pub source_code: Option<String>,
+    /// 소스가 없을 때 실행할 네이티브 바이너리. 표준 출력의 각 줄이 로그가 됩니다.
+    pub binary_path: Option<String>,
+    /// 사용자 코드보다 먼저 실행되는 프렐류드 소스
+    pub prelude: Option<String>,
+    /// 실행 전에 루트 Environment에 주입되는 전역 값
+    pub globals: Vec<(String, Value)>,
+    /// 취소되면 실행을 멈추고 `ExecutionStatus::Cancelled` 를 돌려줍니다.
+    pub cancellation: CancellationToken,
+    /// 스크립트에 허락한 권한 (프로세스 실행 등)
+    pub capabilities: Capabilities,
+}
+
+/// 실행 결과 구조체
+#[derive(Debug)]
+pub struct ExecutionResult {
+    pub output_log: Vec<String>,
+    pub status: ExecutionStatus,
+    pub execution_time_ms: u128,
+    /// 프로그램의 종료 코드 (exit_code.rs). 실행하지 않았으면 `exit_code::FAILURE` 입니다.
+    pub exit_code: i32,
+    /// 인터프리터로 실행했을 때의 수집 통계. 네이티브나 Python 실행이면 None 입니다.
+    pub gc: Option<GcStats>,
+}
+
+/// 실행기 서비스
+pub struct ExecutorService {}
+
+impl ExecutorService {
+    pub fn new() -> Self {
+        println!("[Executor] ExecutorService가 초기화되었습니다.");
+        Self {}
+    }
+
+    pub async fn execute_code(&self, request: ExecutionRequest) -> ExecutionResult {
+        self.execute(request, OutputLog::new(None)).await
+    }
+
+    /// `execute_code` 와 같지만, 로그의 각 줄(인터프리터 출력, 네이티브 바이너리의 표준 출력 포함)을
+    /// 실행이 끝나기를 기다리지 않고 생기는 대로 `stream` 으로도 보냅니다. 돌려주는 로그에는 모든 줄이 들어갑니다.
+    /// 실행이 끝나면 보내는 쪽을 닫으므로 받는 쪽은 `recv` 가 None 일 때까지 읽으면 됩니다.
+    pub async fn execute_code_streaming(&self, request: ExecutionRequest, stream: OutputStream) -> ExecutionResult {
+        self.execute(request, OutputLog::new(Some(stream))).await
+    }
+
+    async fn execute(&self, request: ExecutionRequest, mut log: OutputLog) -> ExecutionResult {
+        let start_time = time::Instant::now();
+        let mut status = ExecutionStatus::Success;
+        let mut code = exit_code::SUCCESS;
+        let mut gc = None;
+
+        println!("[Executor] 코드 실행 시작...");
+        if !Self::pause(&request.cancellation, Duration::from_millis(30)).await {
+            return Self::cancelled(log, start_time);
+        }
+        log.push(">> [System] Runtime environment started.".into());
+
+        let delay = (request.compiled_code_reference.len() * 2).max(50);
+        if !Self::pause(&request.cancellation, Duration::from_millis(delay as u64)).await {
+            return Self::cancelled(log, start_time);
+        }
+
+        if let Some(source) = &request.source_code {
+            let (result, stats) = Self::interpret(&request, source, log.sink());
+            gc = Some(stats);
+            match result {
+                // 받는 쪽이 있으면 이미 보냈습니다.
+                Ok((lines, exit)) => {
+                    log.lines.extend(lines);
+                    code = exit;
+                }
+                Err(_) if request.cancellation.is_cancelled() => return Self::cancelled(log, start_time),
+                Err(e) => {
+                    status = ExecutionStatus::RuntimeError;
+                    code = exit_code::RUNTIME_ERROR;
+                    log.push(format!(">> [Error] {}", e));
+                }
+            }
+        } else if let Some(path) = &request.binary_path {
+            let input = request.input_data.as_deref().map(str::as_bytes);
+            let result = cancellation::run_command_lines(&mut Command::new(path), input, &request.cancellation, &mut |line| {
+                log.push(line.to_string())
+            });
+            match result {
+                Ok(output) if !exit_code::is_failure(output.status.code()) => {
+                    code = output.status.code().unwrap_or(exit_code::SUCCESS);
+                }
+                Ok(output) => {
+                    status = ExecutionStatus::RuntimeError;
+                    code = output.status.code().unwrap_or(exit_code::RUNTIME_ERROR);
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    log.push(format!(">> [Error] {} {}", output.status, stderr.trim_end()).trim_end().to_string());
+                }
+                Err(CommandError::Cancelled) => return Self::cancelled(log, start_time),
+                Err(CommandError::Io(e)) => {
+                    status = ExecutionStatus::RuntimeError;
+                    code = exit_code::RUNTIME_ERROR;
+                    log.push(format!(">> [Error] {}: {}", path, e));
+                }
+            }
+        } else if request.compiled_code_reference.contains("error") {
+            status = ExecutionStatus::RuntimeError;
+            code = exit_code::RUNTIME_ERROR;
+            let fault = request.compiled_code_reference.split(' ').last().unwrap_or("UNKNOWN");
+            log.push(format!(">> [Error] Segmentation Fault at instruction: {}", fault));
+        } else {
+            log.push(Self::generate_output(&request));
+        }
+
+        let execution_time_ms = start_time.elapsed().as_millis();
+        println!("[Executor] 실행 완료. 상태: {:?}, 소요 시간: {}ms", status, execution_time_ms);
+
+        ExecutionResult {
+            output_log: log.lines,
+            status,
+            execution_time_ms,
+            exit_code: code,
+            gc,
+        }
+    }
+
+    /// `duration` 만큼 기다립니다. 그 전에 취소되면 거짓입니다.
+    async fn pause(token: &CancellationToken, duration: Duration) -> bool {
+        tokio::select! {
+            _ = time::sleep(duration) => true,
+            _ = token.cancelled() => false,
+        }
+    }
+
+    fn cancelled(mut log: OutputLog, start_time: time::Instant) -> ExecutionResult {
+        log.push(format!(">> [Cancelled] {}", error_codes::CANCELLED.message("", &[])));
+        ExecutionResult {
+            output_log: log.lines,
+            status: ExecutionStatus::Cancelled,
+            execution_time_ms: start_time.elapsed().as_millis(),
+            exit_code: exit_code::CANCELLED,
+            gc: None,
+        }
+    }
+
+    /// 전역 값과 프렐류드를 주입한 Engine에서 소스를 실행하고 출력 줄과 종료 코드를 돌려줍니다.
+    /// 실패해도 그때까지의 수집 통계를 돌려줍니다.
+    fn interpret(request: &ExecutionRequest, source: &str, sink: Option<OutputSink>) -> (Result<(Vec<String>, i32), String>, GcStats) {
+        let mut engine = Engine::new();
+        engine.set_output_sink(sink);
+        if let Some(input) = &request.input_data {
+            engine.set_input(Input::text(input));
+        }
+        engine.set_cancellation(request.cancellation.clone());
+        engine.set_capabilities(request.capabilities);
+        engine.set_globals(&request.globals);
+        let result = match &request.prelude {
+            Some(prelude) => engine.load_prelude(prelude).and_then(|()| engine.run(source)),
+            None => engine.run(source),
+        };
+        (result.map(|lines| (lines, engine.exit_code())), engine.gc_stats())
+    }
+
+    fn generate_output(request: &ExecutionRequest) -> String {
+        let input = request.input_data.as_deref().unwrap_or("None");
+        format!(">> [Code Output] Hello from the compiled code! Input data was: {}", input)
+    }
+}
+
+/// 실행 로그. 받는 쪽이 있으면 쌓는 줄을 바로 보냅니다.
+struct OutputLog {
+    lines: Vec<String>,
+    stream: Option<OutputStream>,
+}
+
+impl OutputLog {
+    fn new(stream: Option<OutputStream>) -> Self {
+        Self { lines: vec![], stream }
+    }
+
+    fn push(&mut self, line: String) {
+        if let Some(stream) = &self.stream {
+            // 받는 쪽이 먼저 끝났어도 로그는 계속 모읍니다.
+            let _ = stream.send(line.clone());
+        }
+        self.lines.push(line);
+    }
+
+    /// 인터프리터 출력을 같은 채널로 보내는 콜백
+    fn sink(&self) -> Option<OutputSink> {
+        let stream = self.stream.clone()?;
+        Some(Rc::new(move |line: &str| {
+            let _ = stream.send(line.to_string());
+        }))
+    }
+}
@@ -0,0 +1,43 @@
+// exit_code.rs
+// High 프로그램의 종료 코드 규칙
+//
+// 프로그램 최상위의 `return` 값이 프로그램의 결과입니다 (E0080). 실행기는 그 값을 `ExecutionResult::exit_code` 로,
+// 컴파일러는 `CompileResult::exit_code` 로 옮기고, `high run` 과 `high bundle` 로 만든 실행 파일은 그 값으로
+// 프로세스를 끝냅니다. 셸 스크립트와 CI 는 이 값만 보면 됩니다.
+//
+//   return 정수                    그 값. 유닉스는 하위 8비트만 남깁니다 (256 → 0). i32 범위 밖이면 1
+//   return 오류 값                 101
+//   return 그 밖의 값, return 없음   0
+//   패닉, 런타임 오류                101 (Rust/Python 백엔드가 오류로 끝날 때와 같음)
+//   컴파일 오류로 실행하지 않음        1
+//   단계 제한 시간 초과              124 (timeout(1) 과 같음)
+//   취소                          130 (SIGINT 로 끝난 프로세스의 셸 관례)
+//   네이티브 바이너리, Python 실행    그 프로세스의 종료 코드. 시그널로 끝나 코드가 없으면 101
+//
+// 하위 프로세스는 101, 134(네이티브 백엔드의 패닉) 로 끝났거나 시그널로 끝났을 때만 실행 실패로 봅니다.
+// 그 밖의 0 이 아닌 코드는 프로그램이 돌려준 결과입니다. Python/Rust 백엔드도 최상위 `return` 의 정수를
+// 종료 코드로 씁니다.
+
+use crate::data_structures::Value;
+
+pub const SUCCESS: i32 = 0;
+pub const FAILURE: i32 = 1;
+pub const RUNTIME_ERROR: i32 = 101;
+pub const TIMED_OUT: i32 = 124;
+pub const CANCELLED: i32 = 130;
+/// 네이티브/LLVM 백엔드가 패닉으로 끝낼 때의 코드 (SIGABRT 관례)
+pub const ABORTED: i32 = 134;
+
+/// 최상위 `return` 값의 종료 코드. `None` 은 `return` 이 실행되지 않았다는 뜻입니다.
+pub fn from_value(value: Option<&Value>) -> i32 {
+    match value {
+        Some(Value::Integer(n)) => i32::try_from(*n).unwrap_or(FAILURE),
+        Some(Value::Error(_)) => RUNTIME_ERROR,
+        _ => SUCCESS,
+    }
+}
+
+/// 하위 프로세스의 종료 코드가 실행 실패인지. `None` 은 시그널로 끝났다는 뜻입니다.
+pub fn is_failure(code: Option<i32>) -> bool {
+    matches!(code, None | Some(RUNTIME_ERROR) | Some(ABORTED))
+}
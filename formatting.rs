@@ -0,0 +1,301 @@
+// formatting.rs
+// `format("{}: {:.2}", name, score)` 내장 함수의 서식 문자열 해석과 값 출력입니다.
+//
+// 서식 문법은 Rust `format!` 의 부분집합이므로, Rust 백엔드는 검증만 거친 뒤
+// 템플릿을 그대로 `format!` 에 넘길 수 있습니다.
+//
+//   {[index][:[[fill]align][0][width][.precision][x|X]]}
+//   align: `<` 왼쪽, `>` 오른쪽, `^` 가운데.  `{{` / `}}` 는 중괄호 문자 그대로입니다.
+
+use crate::bytes;
+use crate::datetime;
+use crate::pretty;
+use crate::compat::*;
+use crate::data_structures::Value;
+use crate::error_codes::INVALID_FORMAT;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+    Decimal,
+    LowerHex,
+    UpperHex,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatSpec {
+    pub fill: char,
+    /// None 이면 값의 기본 정렬(숫자는 오른쪽, 나머지는 왼쪽)을 따릅니다.
+    pub align: Option<Align>,
+    pub zero_pad: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub radix: Radix,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self { fill: ' ', align: None, zero_pad: false, width: None, precision: None, radix: Radix::Decimal }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    /// `index` 는 암묵적 위치(`{}`)까지 모두 해석된 인자 번호입니다.
+    Placeholder { index: usize, spec: FormatSpec },
+}
+
+/// 서식 문자열을 조각으로 나눕니다. 문법 오류는 Err 로 돌려줍니다.
+pub fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = vec![];
+    let mut text = String::new();
+    let mut next_index = 0;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '}' => return Err(INVALID_FORMAT.message("unmatched_close", &[])),
+            '{' => {
+                let mut body = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => body.push(ch),
+                        None => return Err(INVALID_FORMAT.message("unclosed_open", &[])),
+                    }
+                }
+                if !text.is_empty() {
+                    segments.push(Segment::Text(core::mem::take(&mut text)));
+                }
+                let (position, spec) = body.split_once(':').unwrap_or((body.as_str(), ""));
+                let index = if position.is_empty() {
+                    next_index += 1;
+                    next_index - 1
+                } else {
+                    position
+                        .trim()
+                        .parse()
+                        .map_err(|_| INVALID_FORMAT.message("bad_index", &[&position]))?
+                };
+                segments.push(Segment::Placeholder { index, spec: parse_spec(spec)? });
+            }
+            other => text.push(other),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
+
+/// 너비와 정밀도의 상한. 사용자 입력 하나로 런타임이 패닉하거나 메모리를 다 쓰지 않도록 막습니다.
+pub const MAX_WIDTH: usize = u16::MAX as usize;
+
+fn parse_spec(spec: &str) -> Result<FormatSpec, String> {
+    let mut result = FormatSpec::default();
+    let chars: Vec<char> = spec.chars().collect();
+    let mut i = 0;
+
+    let align_of = |c: char| match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        _ => None,
+    };
+    if let Some(align) = chars.get(1).and_then(|&c| align_of(c)) {
+        result.fill = chars[0];
+        result.align = Some(align);
+        i = 2;
+    } else if let Some(align) = chars.first().and_then(|&c| align_of(c)) {
+        result.align = Some(align);
+        i = 1;
+    }
+
+    if chars.get(i) == Some(&'0') {
+        result.zero_pad = true;
+        i += 1;
+    }
+    let bounded = |digits: String| match digits.parse::<usize>() {
+        Ok(n) if n <= MAX_WIDTH => Ok(n),
+        _ => Err(INVALID_FORMAT.message("too_large", &[&format!("{{:{}}}", spec), &MAX_WIDTH])),
+    };
+    let width = take_digits(&chars, &mut i);
+    if !width.is_empty() {
+        result.width = Some(bounded(width)?);
+    }
+    if chars.get(i) == Some(&'.') {
+        i += 1;
+        let precision = take_digits(&chars, &mut i);
+        if precision.is_empty() {
+            return Err(INVALID_FORMAT.message("missing_precision", &[&format!("{{:{}}}", spec)]));
+        }
+        result.precision = Some(bounded(precision)?);
+    }
+    match chars.get(i) {
+        Some('x') => result.radix = Radix::LowerHex,
+        Some('X') => result.radix = Radix::UpperHex,
+        Some(_) => return Err(INVALID_FORMAT.message("bad_spec", &[&format!("{{:{}}}", spec)])),
+        None => return Ok(result),
+    }
+    if i + 1 != chars.len() {
+        return Err(INVALID_FORMAT.message("bad_spec", &[&format!("{{:{}}}", spec)]));
+    }
+    Ok(result)
+}
+
+fn take_digits(chars: &[char], i: &mut usize) -> String {
+    let start = *i;
+    while chars.get(*i).is_some_and(|c| c.is_ascii_digit()) {
+        *i += 1;
+    }
+    chars[start..*i].iter().collect()
+}
+
+/// 서식 문자열에 인자를 채워 넣습니다. 인자가 모자라거나 남으면 오류입니다.
+pub fn format_values(template: &str, args: &[Value]) -> Result<String, String> {
+    let segments = parse_template(template)?;
+    let mut used = vec![false; args.len()];
+    let mut out = String::new();
+
+    for segment in &segments {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Placeholder { index, spec } => {
+                let value = args.get(*index).ok_or_else(|| {
+                    INVALID_FORMAT.message("missing_argument", &[index, &args.len()])
+                })?;
+                used[*index] = true;
+                out.push_str(&format_one(value, spec)?);
+            }
+        }
+    }
+    if let Some(unused) = used.iter().position(|u| !u) {
+        return Err(INVALID_FORMAT.message("unused_argument", &[&unused]));
+    }
+    Ok(out)
+}
+
+fn format_one(value: &Value, spec: &FormatSpec) -> Result<String, String> {
+    let is_numeric = matches!(value, Value::Integer(_) | Value::Float(_));
+    let body = match (value, spec.radix) {
+        (Value::Integer(i), Radix::LowerHex) => format!("{:x}", i),
+        (Value::Integer(i), Radix::UpperHex) => format!("{:X}", i),
+        (other, Radix::LowerHex | Radix::UpperHex) => {
+            return Err(INVALID_FORMAT.message("hex_non_integer", &[&format!("{:?}", other)]))
+        }
+        (Value::Float(f), _) => match spec.precision {
+            Some(p) => format!("{:.*}", p, f),
+            None => format!("{}", f),
+        },
+        (Value::String(s), _) => match spec.precision {
+            Some(p) => s.chars().take(p).collect(),
+            None => s.to_string(),
+        },
+        (other, _) => display_value(other),
+    };
+
+    let width = spec.width.unwrap_or(0);
+    let len = body.chars().count();
+    if len >= width {
+        return Ok(body);
+    }
+    let padding = width - len;
+
+    // `{:08.2}` 처럼 0 채움이 지정된 숫자는 부호 뒤에 0을 채웁니다.
+    if spec.zero_pad && spec.align.is_none() && is_numeric {
+        let (sign, digits) = body.split_at(usize::from(body.starts_with('-')));
+        return Ok(format!("{}{}{}", sign, "0".repeat(padding), digits));
+    }
+
+    let align = spec.align.unwrap_or(if is_numeric { Align::Right } else { Align::Left });
+    let fill = |n: usize| spec.fill.to_string().repeat(n);
+    Ok(match align {
+        Align::Left => format!("{}{}", body, fill(padding)),
+        Align::Right => format!("{}{}", fill(padding), body),
+        Align::Center => format!("{}{}{}", fill(padding / 2), body, fill(padding - padding / 2)),
+    })
+}
+
+/// 다시 읽으면 같은 비트의 실수가 되는 High 소스 표기. 어휘 분석기는 지수 표기를 읽지 못하므로
+/// 항상 `123.5` 꼴로 씁니다. 음수는 `-` 앞붙이 연산으로 읽히며, 부호 반전은 정확하므로 `-0.0` 도 그대로 돌아옵니다.
+/// NaN 과 무한대는 리터럴로 쓸 수 없어 `None` 입니다.
+pub fn float_literal(f: f64) -> Option<String> {
+    if !f.is_finite() {
+        return None;
+    }
+    // `Display` 는 지수 없이 가장 짧은 왕복 표기를 씁니다.
+    let text = f.to_string();
+    Some(if text.contains('.') { text } else { format!("{}.0", text) })
+}
+
+/// 사용자에게 보여줄 값의 기본 표현입니다. (`{}` 자리표시자)
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::String(s) => s.to_string(),
+        Value::Null => "null".into(),
+        Value::Error(e) => format!("error: {}", e),
+        Value::Type(t) => t.clone(),
+        Value::Bytes(b) => format!("b\"{}\"", bytes::escape(b)),
+        Value::Match(m) => m.display(),
+        Value::Matches(all) => format!("matches({})", all.len()),
+        Value::DateTime(t) => t.iso(),
+        Value::Duration(d) => datetime::format_duration(*d),
+        Value::Range(start, end) => format!("{}..{}", start, end),
+        Value::Record(record) => {
+            let fields: Vec<String> = record
+                .fields
+                .iter()
+                .map(|(name, value)| match value {
+                    Value::String(s) => format!("{}: {:?}", name, s),
+                    other => format!("{}: {}", name, display_value(other)),
+                })
+                .collect();
+            format!("{}({})", record.name, fields.join(", "))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(display_element).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Map(map) => {
+            let entries: Vec<String> = map.iter().map(|(key, value)| format!("{}: {}", key, display_element(value))).collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Value::EnumVariant(variant) => match &variant.fields {
+            // 문자열 변환은 `@derive(show)` 한 enum 에만 있고, 나머지는 타입 이름만 보여 줍니다.
+            Some(_) if !variant.derives.show => format!("<{}>", variant.enum_name),
+            Some(fields) if fields.is_empty() => variant.name.to_string(),
+            Some(fields) => format!("{}({})", variant.name, fields.iter().map(display_element).collect::<Vec<_>>().join(", ")),
+            // 생성자는 필드 타입으로 보여 줍니다. (`Rgb(int, int, int)`)
+            None => format!("{}({})", variant.name, variant.field_types.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", ")),
+        },
+        Value::WeakRef(target) if target.strong_count() == 0 => "weak(collected)".into(),
+        Value::WeakRef(_) => "weak(generator)".into(),
+        other => pretty::dump(other),
+    }
+}
+
+/// 배열, 맵, enum 변형 안의 값. 문자열은 따옴표로 감싸 `["1"]` 과 `[1]` 을 가립니다.
+fn display_element(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{:?}", s),
+        other => display_value(other),
+    }
+}
@@ -1,259 +1,1614 @@
-use std::collections::HashMap;
-use std::rc::Rc;
-use std::cell::RefCell;
-
-use crate::data_structures::{
-    Program, Value, Diagnostic, DiagnosticLevel, Statement, Expression, Span, ReflectionInfo,
-};
-
-use crate::lexer_service::LexerService;
-use crate::parser_service::ParserService;
-
-pub type ValueStore = HashMap<String, Value>;
-
-#[derive(Debug, Clone)]
-pub struct Environment {
-    pub store: ValueStore,
-    pub outer: Option<Rc<RefCell<Environment>>>,
-}
-
-impl Environment {
-    pub fn new() -> Self {
-        Self { store: HashMap::new(), outer: None }
-    }
-
-    pub fn new_enclosed(outer: Rc<RefCell<Environment>>) -> Self {
-        Self { store: HashMap::new(), outer: Some(outer) }
-    }
-
-    pub fn get(&self, name: &str) -> Option<Value> {
-        self.store.get(name).cloned().or_else(|| {
-            self.outer.as_ref()?.borrow().get(name)
-        })
-    }
-
-    pub fn set(&mut self, name: String, val: Value) {
-        self.store.insert(name, val);
-    }
-}
-
-pub struct HighEnduranceRuntime {
-    pub environment: Rc<RefCell<Environment>>,
-    pub output: Vec<String>,
-}
-
-impl HighEnduranceRuntime {
-    pub fn new() -> Self {
-        Self {
-            environment: Rc::new(RefCell::new(Environment::new())),
-            output: Vec::new(),
-        }
-    }
-
-    pub fn execute_program(&mut self, program: Program) -> Diagnostic {
-        let mut executed_count = 0;
-
-        for statement in program.statements.iter() {
-            match statement.as_ref() {
-                Statement::ExpressionStatement(expr) => {
-                    let val = self.evaluate_expression(expr);
-                    self.output.push(format!("Expression result: {:?}", val));
-                    executed_count += 1;
-                }
-                Statement::LetStatement { name, value, .. } => {
-                    let val = self.evaluate_expression(value);
-                    self.environment.borrow_mut().set(name.clone(), val);
-                    self.output.push(format!("Variable '{}' bound", name));
-                    executed_count += 1;
-                }
-                Statement::ReturnStatement(expr) => {
-                    let val = self.evaluate_expression(expr);
-                    self.output.push(format!("Return value: {:?}", val));
-                    executed_count += 1;
-                }
-                Statement::BlockStatement { statements, .. } => {
-                    self.output.push("Entering block scope.".to_string());
-                    let enclosed = Rc::new(RefCell::new(Environment::new_enclosed(self.environment.clone())));
-                    let mut block_rt = HighEnduranceRuntime {
-                        environment: enclosed,
-                        output: Vec::new(),
-                    };
-                    let block_prog = Program {
-                        root_id: 0,
-                        statements: statements.clone(),
-                        span: program.span,
-                    };
-                    let diag = block_rt.execute_program(block_prog);
-                    self.output.extend(block_rt.output);
-                    executed_count += 1;
-
-                    if matches!(diag.level, DiagnosticLevel::HerFatal | DiagnosticLevel::Error) {
-                        return diag;
-                    }
-                }
-                Statement::IfStatement { condition, then_branch, else_branch } => {
-                    let cond_val = self.evaluate_expression(condition);
-                    if matches!(cond_val, Value::Boolean(true)) {
-                        let _ = self.execute_program(Program {
-                            root_id: 0,
-                            statements: vec![then_branch.clone()],
-                            span: program.span,
-                        });
-                    } else if let Some(else_stmt) = else_branch {
-                        let _ = self.execute_program(Program {
-                            root_id: 0,
-                            statements: vec![else_stmt.clone()],
-                            span: program.span,
-                        });
-                    }
-                    executed_count += 1;
-                }
-                Statement::WhileStatement { condition, body } => {
-                    while matches!(self.evaluate_expression(condition), Value::Boolean(true)) {
-                        let _ = self.execute_program(Program {
-                            root_id: 0,
-                            statements: vec![body.clone()],
-                            span: program.span,
-                        });
-                    }
-                    executed_count += 1;
-                }
-                Statement::ForStatement { initializer, condition, increment, body } => {
-                    if let Some(init) = initializer {
-                        let _ = self.execute_program(Program {
-                            root_id: 0,
-                            statements: vec![init.clone()],
-                            span: program.span,
-                        });
-                    }
-                    while condition.as_ref().map_or(true, |c| matches!(self.evaluate_expression(c), Value::Boolean(true))) {
-                        let _ = self.execute_program(Program {
-                            root_id: 0,
-                            statements: vec![body.clone()],
-                            span: program.span,
-                        });
-                        if let Some(inc) = increment {
-                            let _ = self.evaluate_expression(inc);
-                        }
-                    }
-                    executed_count += 1;
-                }
-                Statement::MacroDefinition { name, parameters, body } => {
-                    self.environment.borrow_mut().set(name.clone(), Value::Macro(name.clone()));
-                    self.output.push(format!("Macro '{}' defined with {} parameter(s)", name, parameters.len()));
-                    executed_count += 1;
-                }
-            }
-        }
-
-        if executed_count > 0 && executed_count % 3 != 0 {
-            Diagnostic {
-                level: DiagnosticLevel::HerFatal,
-                message: format!("Unbalanced execution flow: {} statements", executed_count),
-                span: program.span,
-                help: Some("Ensure control flows terminate correctly.".into()),
-            }
-        } else {
-            Diagnostic {
-                level: DiagnosticLevel::Info,
-                message: format!("Executed {} statements successfully.", executed_count),
-                span: program.span,
-                help: None,
-            }
-        }
-    }
-
-    pub fn evaluate_expression(&mut self, expr: &Expression) -> Value {
-        match expr {
-            Expression::Literal(_, val) => val.clone(),
-            Expression::Identifier(_, name) => {
-                self.environment.borrow().get(name).unwrap_or(Value::Error(format!("Undefined variable '{}'", name)))
-            }
-            Expression::Reflect(_, inner) => {
-                let val = self.evaluate_expression(inner);
-                reflect(&val)
-            }
-            Expression::Eval(_, code_expr) => {
-                let code_val = self.evaluate_expression(code_expr);
-                if let Value::String(code) = code_val {
-                    match eval_string(&code) {
-                        Ok(val) => val,
-                        Err(e) => Value::Error(format!("Eval failed: {}", e)),
-                    }
-                } else {
-                    Value::Error("eval() expects a string".into())
-                }
-            }
-            Expression::TypeOf(_, inner) => {
-                let val = self.evaluate_expression(inner);
-                match &val {
-                    Value::Integer(_) => Value::Type("int".into()),
-                    Value::Float(_) => Value::Type("float".into()),
-                    Value::Boolean(_) => Value::Type("bool".into()),
-                    Value::String(_) => Value::Type("string".into()),
-                    _ => Value::Type("unknown".into()),
-                }
-            }
-            Expression::MacroCall(_, name, args) => {
-                self.output.push(format!("Macro '{}' called with {} args", name, args.len()));
-                Value::Null
-            }
-            _ => Value::Error("Unsupported expression".into()),
-        }
-    }
-}
-
-pub fn reflect(val: &Value) -> Value {
-    let type_name = match val {
-        Value::Integer(_) => "int",
-        Value::Float(_) => "float",
-        Value::Boolean(_) => "bool",
-        Value::String(_) => "string",
-        Value::Function(_) => "function",
-        Value::Null => "null",
-        Value::Return(_) => "return",
-        Value::Error(_) => "error",
-        Value::Reflection(_) => "reflection",
-        Value::Macro(_) => "macro",
-        Value::Type(_) => "type",
-    };
-    Value::Reflection(ReflectionInfo {
-        type_name: type_name.into(),
-        details: format!("{:?}", val),
-    })
-}
-
-pub fn eval_string(source: &str) -> Result<Value, String> {
-    let lexer = LexerService::new(source);
-    let mut parser = ParserService::new(lexer);
-    let program = parser.parse_program();
-
-    let mut runtime = HighEnduranceRuntime::new();
-    let diag = runtime.execute_program(program);
-
-    if matches!(diag.level, DiagnosticLevel::HerFatal | DiagnosticLevel::Error) {
-        Err(diag.message)
-    } else {
-        Ok(runtime.output.last()
-            .map(|line| Value::String(line.clone()))
-            .unwrap_or(Value::Null))
-    }
-}
-
-fn ends_with_return(stmt: &Statement) -> bool {
-    match stmt {
-        Statement::ReturnStatement(_) => true,
-        Statement::BlockStatement { statements, .. } => {
-            if let Some(last) = statements.last() {
-                ends_with_return(last)
-            } else {
-                false
-            }
-        }
-        Statement::IfStatement { then_branch, else_branch, .. } => {
-            ends_with_return(then_branch)
-                && else_branch.as_ref().map_or(false, |b| ends_with_return(b))
-        }
-        _ => false
-    }
-}
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use alloc::sync::Arc;
+
+use crate::compat::*;
+use crate::data_structures::{
+    Program, Value, Diagnostic, DiagnosticLevel, Statement, Expression, Span, ReflectionInfo,
+    FunctionValue, MatchArm, Record, TokenKind, TypeAnnotation, EnumVariant, Derives, VariantDeclaration,
+};
+use crate::attributes;
+use crate::error_codes;
+use crate::interner::Symbol;
+use crate::formatting;
+use crate::messages;
+use crate::patterns;
+use crate::pretty;
+use crate::generator::{self, GeneratorState};
+use crate::heap::{CollectionReport, GcStats, Heap};
+use crate::input::Input;
+use crate::bytecode;
+use crate::coverage::Coverage;
+use crate::profile::Profile;
+use crate::trace::{self, Trace, TraceMark};
+use crate::bytes::{self, AccessError};
+use crate::arrays;
+use crate::ranges::{self, Cursor};
+use crate::maps::{self, Map};
+use crate::regexp;
+use crate::datetime::{self, DateTime};
+use crate::operators::{self, OperatorError};
+use crate::named_types::{self, NamedTypes};
+use crate::tiering::Tiering;
+use crate::snapshot::Snapshot;
+use crate::cancellation::CancellationToken;
+
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+
+pub type ValueStore = HashMap<Symbol, Value>;
+
+#[derive(Debug, Clone)]
+pub struct Environment {
+    pub store: ValueStore,
+    pub outer: Option<Rc<RefCell<Environment>>>,
+    /// 바이트코드 스코프의 지역 변수 칸. 컴파일 시점에 정해진 (스코프 깊이, 칸 번호) 로 바로 읽습니다.
+    /// 아직 바인딩되지 않은 칸은 None 입니다.
+    pub slots: Vec<Option<Value>>,
+    /// 칸의 이름. 호출된 함수처럼 이름으로 찾는 경우에만 씁니다.
+    pub slot_names: Option<Arc<[Symbol]>>,
+    /// `let mut` 으로 바인딩한 이름과 그 타입 표기. 대입은 이 이름들에만 할 수 있습니다.
+    pub mutable: HashMap<Symbol, Option<TypeAnnotation>>,
+}
+
+/// 대입할 수 없는 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignError {
+    Undefined,
+    Immutable,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self { store: HashMap::new(), outer: None, slots: vec![], slot_names: None, mutable: HashMap::new() }
+    }
+
+    pub fn new_enclosed(outer: Rc<RefCell<Environment>>) -> Self {
+        Self { store: HashMap::new(), outer: Some(outer), slots: vec![], slot_names: None, mutable: HashMap::new() }
+    }
+
+    /// 칸 배치가 정해진 바이트코드 스코프를 만듭니다.
+    pub fn with_slots(outer: Rc<RefCell<Environment>>, names: Arc<[Symbol]>) -> Self {
+        Self {
+            store: HashMap::new(),
+            outer: Some(outer),
+            slots: vec![None; names.len()],
+            slot_names: Some(names),
+            mutable: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, name: Symbol) -> Option<Value> {
+        self.store.get(&name).cloned().or_else(|| self.get_slot_by_name(name)).or_else(|| {
+            self.outer.as_ref()?.borrow().get(name)
+        })
+    }
+
+    fn get_slot_by_name(&self, name: Symbol) -> Option<Value> {
+        let names = self.slot_names.as_ref()?;
+        let slot = names.iter().position(|&n| n == name)?;
+        self.slots[slot].clone()
+    }
+
+    /// `depth` 단계 바깥 스코프의 칸을 읽습니다.
+    pub fn get_slot(&self, depth: usize, slot: usize) -> Option<Value> {
+        if depth == 0 {
+            return self.slots.get(slot).cloned().flatten();
+        }
+        self.outer.as_ref()?.borrow().get_slot(depth - 1, slot)
+    }
+
+    /// 불변 바인딩. 같은 스코프의 `let mut` 을 가리면 그 이름에는 더 대입할 수 없습니다.
+    pub fn set(&mut self, name: Symbol, val: Value) {
+        self.mutable.remove(&name);
+        self.store.insert(name, val);
+    }
+
+    /// `let mut` 바인딩. 대입한 값은 `annotation` 으로 강제 변환합니다.
+    pub fn set_mutable(&mut self, name: Symbol, val: Value, annotation: Option<TypeAnnotation>) {
+        self.mutable.insert(name, annotation);
+        self.store.insert(name, val);
+    }
+
+    /// 이름을 찾을 수 있는 가장 가까운 스코프의 바인딩. 대입할 수 있으면 그 타입 표기와 함께 돌려줍니다.
+    pub fn assignable(&self, name: Symbol) -> Result<Option<TypeAnnotation>, AssignError> {
+        if let Some(annotation) = self.mutable.get(&name) {
+            return Ok(annotation.clone());
+        }
+        if self.store.contains_key(&name) || self.slot_names.as_ref().is_some_and(|names| names.contains(&name)) {
+            return Err(AssignError::Immutable);
+        }
+        match &self.outer {
+            Some(outer) => outer.borrow().assignable(name),
+            None => Err(AssignError::Undefined),
+        }
+    }
+
+    /// `assignable` 이 찾은 바인딩의 값을 바꿉니다.
+    pub fn assign(&mut self, name: Symbol, val: Value) {
+        if self.mutable.contains_key(&name) {
+            self.store.insert(name, val);
+        } else if let Some(outer) = &self.outer {
+            outer.borrow_mut().assign(name, val);
+        }
+    }
+}
+
+/// 호출 스택의 한 프레임: 호출된 함수 이름과 호출 위치입니다.
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    pub name: String,
+    pub call_site: Span,
+}
+
+/// `panic()` 또는 실패한 `assert()` 로 발생한 런타임 오류입니다.
+/// 처리되기 전까지 런타임에 남아 있으며, 바깥 스코프로 전파되면서 defer 를 실행합니다.
+#[derive(Debug, Clone)]
+pub struct RuntimePanic {
+    pub message: String,
+    pub span: Span,
+    /// 가장 바깥 호출이 먼저 오는 호출 스택
+    pub stack: Vec<CallFrame>,
+}
+
+impl RuntimePanic {
+    pub fn stack_trace(&self) -> String {
+        let mut trace = messages::tr("trace.header", &[]);
+        for (depth, frame) in self.stack.iter().rev().enumerate() {
+            let location = format!("{}..{}", frame.call_site.start, frame.call_site.end);
+            trace.push_str("\n  ");
+            trace.push_str(&messages::tr("trace.frame", &[&depth, &frame.name, &location]));
+        }
+        if self.stack.is_empty() {
+            trace.push_str("\n  ");
+            trace.push_str(&messages::tr("trace.top_level", &[]));
+        }
+        trace
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        error_codes::PANIC.diagnostic(self.span, "", &[&self.message]).with_help(self.stack_trace())
+    }
+}
+
+/// 출력 줄이 생길 때마다 불리는 콜백 (`Engine::set_output_sink`)
+pub type OutputSink = Rc<dyn Fn(&str)>;
+
+pub struct HighEnduranceRuntime {
+    pub environment: Rc<RefCell<Environment>>,
+    pub output: Vec<String>,
+    /// 있으면 `output` 에 쌓는 줄을 바로 넘깁니다. (블록 실행용 런타임과 공유)
+    pub output_sink: Option<OutputSink>,
+    pub call_stack: Vec<CallFrame>,
+    /// 아직 처리되지 않은 패닉 (있으면 실행 중인 스코프들이 차례로 중단됩니다)
+    pub panic: Option<RuntimePanic>,
+    /// 함수별 호출 횟수와 바이트코드로 올라간 본문 (블록 실행용 런타임과 공유)
+    pub tiering: Rc<RefCell<Tiering>>,
+    /// 취소되면 반복문과 함수 호출 사이에서 패닉처럼 실행을 멈춥니다.
+    pub cancellation: CancellationToken,
+    /// 마지막으로 실행한 식 문장이나 return 의 값 (노트북 셀의 결과)
+    pub last_value: Option<Value>,
+    /// 마지막으로 실행한 최상위 `return` 의 값 (프로그램의 종료 코드, exit_code.rs)
+    pub exit_value: Option<Value>,
+    /// 제너레이터 순환을 회수하는 수집기와 그 통계 (블록 실행용 런타임과 공유)
+    pub heap: Rc<RefCell<Heap>>,
+    /// 파일 읽기, 프로세스 실행처럼 스크립트 밖에 닿는 내장 함수의 허용 여부
+    pub capabilities: Capabilities,
+    /// 문장별 적중 횟수 (`enable_coverage` 로 켜며 블록 실행용 런타임과 공유)
+    pub coverage: Option<Rc<RefCell<Coverage>>>,
+    /// 문장별 실행 기록 (`enable_trace` 로 켜며 블록 실행용 런타임과 공유)
+    pub trace: Option<Rc<RefCell<Trace>>>,
+    /// 분기·루프·호출 횟수 (`enable_profile` 로 켜며 블록 실행용 런타임과 공유, profile.rs)
+    pub profile: Option<Rc<RefCell<Profile>>>,
+    /// 실행한 `type` 별칭 (표기된 `let` 의 강제 변환용, 블록 실행용 런타임과 공유)
+    pub types: Rc<RefCell<NamedTypes>>,
+    /// `read_line`, `read_all` 이 읽는 입력 (블록 실행용 런타임과 공유)
+    pub input: Rc<RefCell<Input>>,
+}
+
+/// 호스트가 스크립트에 허락한 권한. 막힌 내장 함수는 E0006 오류 값을 돌려줍니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capabilities {
+    /// `read_file_bytes`
+    pub files: bool,
+    /// `run_process` (`high run --allow-process`)
+    pub processes: bool,
+    /// `callstack`, `locals` 디버그 내장 함수 (`high run --allow-debug`)
+    pub debug: bool,
+}
+
+impl Default for Capabilities {
+    /// 파일 읽기는 허용, 프로세스 실행과 디버그 내장 함수는 거부
+    fn default() -> Self {
+        Self { files: true, processes: false, debug: false }
+    }
+}
+
+/// 같은 이름의 바인딩이 없을 때 쓰이는 내장 함수들
+pub const BUILTINS: &[&str] = &[
+    "panic", "assert", "format", "gc", "weak_ref", "deref", "on_finalize",
+    "len", "to_hex", "from_hex", "to_utf8", "from_utf8", "read_file_bytes",
+    "regex_match", "regex_find_all", "regex_replace", "match_start", "match_end",
+    "now", "parse_datetime", "format_datetime", "to_offset", "from_epoch_millis", "epoch_millis",
+    "duration", "seconds", "minutes", "hours", "days", "run_process", "callstack", "locals",
+    "read_line", "read_all", "keys", "values", "contains_key", "insert", "clone",
+];
+
+pub fn is_builtin(name: &str) -> bool {
+    BUILTINS.contains(&name)
+}
+
+impl HighEnduranceRuntime {
+    pub fn new() -> Self {
+        Self {
+            environment: Rc::new(RefCell::new(Environment::new())),
+            output: Vec::new(),
+            output_sink: None,
+            call_stack: Vec::new(),
+            panic: None,
+            tiering: Rc::new(RefCell::new(Tiering::default())),
+            cancellation: CancellationToken::new(),
+            last_value: None,
+            exit_value: None,
+            heap: Rc::new(RefCell::new(Heap::default())),
+            capabilities: Capabilities::default(),
+            coverage: None,
+            trace: None,
+            profile: None,
+            types: Rc::new(RefCell::new(NamedTypes::default())),
+            input: Rc::new(RefCell::new(Input::default())),
+        }
+    }
+
+    /// 이후 실행하는 문장의 적중 횟수를 셉니다. 모든 문장이 인터프리터를 지나도록 바이트코드 승격을 끕니다.
+    pub fn enable_coverage(&mut self) -> Rc<RefCell<Coverage>> {
+        self.tiering.borrow_mut().threshold = None;
+        self.coverage.get_or_insert_with(Default::default).clone()
+    }
+
+    pub fn cover(&self, stmt: &Statement) {
+        if let Some(coverage) = &self.coverage {
+            coverage.borrow_mut().hit(stmt);
+        }
+    }
+
+    /// 이후 실행하는 문장을 기록합니다. 모든 문장이 인터프리터를 지나도록 바이트코드 승격을 끕니다. (trace.rs)
+    pub fn enable_trace(&mut self) -> Rc<RefCell<Trace>> {
+        self.tiering.borrow_mut().threshold = None;
+        self.trace.get_or_insert_with(Default::default).clone()
+    }
+
+    /// 이후 실행하는 분기, 루프, 함수 호출을 셉니다. 모든 문장이 인터프리터를 지나도록 바이트코드 승격을 끕니다.
+    pub fn enable_profile(&mut self) -> Rc<RefCell<Profile>> {
+        self.tiering.borrow_mut().threshold = None;
+        self.profile.get_or_insert_with(Default::default).clone()
+    }
+
+    /// 프로필을 켰으면 횟수를 더합니다.
+    pub fn profile(&self, record: impl FnOnce(&mut Profile)) {
+        if let Some(profile) = &self.profile {
+            record(&mut profile.borrow_mut());
+        }
+    }
+
+    /// 출력 한 줄을 남기고, 받는 쪽이 있으면 바로 넘깁니다.
+    pub fn emit(&mut self, line: String) {
+        if let Some(sink) = &self.output_sink {
+            sink(&line);
+        }
+        self.output.push(line);
+    }
+
+    /// 기록 중이면 문장의 사건을 엽니다. `env` 는 문장이 실행되는 스코프입니다.
+    pub fn trace_begin(&self, stmt: &Statement, env: &Rc<RefCell<Environment>>) -> Option<TraceMark> {
+        let trace = self.trace.as_ref().filter(|_| trace::traced(stmt))?;
+        let event = trace.borrow_mut().begin(stmt.span());
+        Some((event, shown_bindings(env)))
+    }
+
+    /// 사건을 닫고 실행 전후의 바인딩 차이와 문장의 값을 남깁니다.
+    pub fn trace_end(&self, mark: Option<TraceMark>, env: &Rc<RefCell<Environment>>, value: Option<&Value>) {
+        if let (Some((event, before)), Some(trace)) = (mark, &self.trace) {
+            trace.borrow_mut().end(event, &before, &shown_bindings(env), value.map(trace::show));
+        }
+    }
+
+    /// 전역 환경에서 닿을 수 있는 상태(값, 함수 본문, 멈춘 제너레이터)를 담습니다. (snapshot.rs)
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::capture(&self.environment)
+    }
+
+    /// 스냅샷을 찍은 때의 상태로 되돌립니다. 처리되지 않은 패닉은 버리고, 출력 기록은 그대로 둡니다.
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), String> {
+        self.environment = snapshot.rebuild()?;
+        self.heap.borrow_mut().adopt(&self.environment);
+        self.panic = None;
+        Ok(())
+    }
+
+    /// 프로그램(또는 블록)을 하나의 스코프로 실행합니다.
+    ///
+    /// `defer { ... }` 로 등록된 블록은 이 스코프를 빠져나갈 때 실행됩니다.
+    /// - 정상 종료, `return`, 오류로 인한 조기 종료 모두에서 실행됩니다.
+    /// - 여러 개가 등록되면 등록의 역순(LIFO)으로 실행됩니다.
+    /// - defer 본문은 등록 시점이 아니라 실행 시점의 변수 값을 봅니다.
+    pub fn execute_program(&mut self, program: Program) -> Diagnostic {
+        self.execute_with(program, true)
+    }
+
+    /// 노트북 셀이나 프렐류드처럼 프로그램의 일부인 소스를 실행합니다.
+    /// 맨 바깥 스코프는 아직 끝나지 않았으므로 흐름 균형(E0032)을 검사하지 않습니다. 안쪽 블록은 검사합니다.
+    pub fn execute_cell(&mut self, program: Program) -> Diagnostic {
+        self.execute_with(program, false)
+    }
+
+    fn execute_with(&mut self, program: Program, check_flow: bool) -> Diagnostic {
+        let mut deferred = vec![];
+        let diag = self.execute_scope(&program, &mut deferred, check_flow);
+        self.run_deferred(deferred, program.span);
+        diag
+    }
+
+    fn run_deferred(&mut self, deferred: Vec<Statement>, span: Span) {
+        // 패닉으로 빠져나가는 중이어도 defer 본문은 끝까지 실행하고, 처음 패닉을 그대로 전파합니다.
+        let pending = self.panic.take();
+        for body in deferred.into_iter().rev() {
+            let _ = self.execute_program(Program {
+                root_id: 0,
+                statements: vec![body],
+                span,
+                attributes: vec![],
+            });
+        }
+        if pending.is_some() {
+            self.panic = pending;
+        }
+    }
+
+    fn execute_scope(&mut self, program: &Program, deferred: &mut Vec<Statement>, check_flow: bool) -> Diagnostic {
+        let mut executed_count = 0;
+
+        for statement in program.statements.iter() {
+            self.cover(statement);
+            let scope = self.environment.clone();
+            let mark = self.trace_begin(statement, &scope);
+            match statement {
+                Statement::ExpressionStatement(expr) => {
+                    let val = self.evaluate_expression(expr);
+                    self.emit(format!("Expression result: {}", pretty::pretty(&val)));
+                    self.last_value = Some(val);
+                    executed_count += 1;
+                }
+                // 값은 출력에도 셀 결과에도 남기지 않습니다.
+                Statement::DiscardStatement(_, expr) => {
+                    self.evaluate_expression(expr);
+                    executed_count += 1;
+                }
+                Statement::LetStatement { name, value, type_annotation, is_mutable, .. } => {
+                    let mut val = self.evaluate_expression(value);
+                    if let Some(annotation) = type_annotation {
+                        val = self.coerce_binding(*name, annotation, val);
+                    }
+                    if *is_mutable {
+                        self.environment.borrow_mut().set_mutable(*name, val, type_annotation.clone());
+                    } else {
+                        self.environment.borrow_mut().set(*name, val);
+                    }
+                    self.emit(format!("Variable '{}' bound", name));
+                    executed_count += 1;
+                }
+                Statement::ReturnStatement(_, expr) => {
+                    let val = self.evaluate_expression(expr);
+                    self.emit(format!("Return value: {}", pretty::pretty(&val)));
+                    // 함수 본문은 제너레이터 실행기가 돌리므로 여기의 return 은 항상 최상위입니다.
+                    self.exit_value = Some(val.clone());
+                    self.last_value = Some(val);
+                    executed_count += 1;
+                }
+                Statement::YieldStatement(span, _) => {
+                    return error_codes::YIELD_OUTSIDE_GENERATOR.diagnostic(*span, "", &[]);
+                }
+                Statement::BlockStatement { statements, .. } => {
+                    self.emit("Entering block scope.".to_string());
+                    let enclosed = Rc::new(RefCell::new(Environment::new_enclosed(self.environment.clone())));
+                    let mut block_rt = HighEnduranceRuntime {
+                        environment: enclosed,
+                        output: Vec::new(),
+                        output_sink: self.output_sink.clone(),
+                        call_stack: self.call_stack.clone(),
+                        panic: None,
+                        tiering: self.tiering.clone(),
+                        cancellation: self.cancellation.clone(),
+                        last_value: None,
+                        exit_value: None,
+                        heap: self.heap.clone(),
+                        capabilities: self.capabilities,
+                        coverage: self.coverage.clone(),
+                        trace: self.trace.clone(),
+                        profile: self.profile.clone(),
+                        types: self.types.clone(),
+                        input: self.input.clone(),
+                    };
+                    let block_prog = Program {
+                        root_id: 0,
+                        statements: statements.clone(),
+                        span: program.span,
+                        attributes: vec![],
+                    };
+                    let diag = block_rt.execute_program(block_prog);
+                    self.output.extend(block_rt.output);
+                    self.exit_value = block_rt.exit_value.or(self.exit_value.take());
+                    self.panic = self.panic.take().or(block_rt.panic);
+                    executed_count += 1;
+
+                    if matches!(diag.level, DiagnosticLevel::HerFatal | DiagnosticLevel::Error) {
+                        return diag;
+                    }
+                }
+                Statement::IfStatement { condition, then_branch, else_branch, span } => {
+                    let cond_val = self.evaluate_expression(condition);
+                    let taken = matches!(cond_val, Value::Boolean(true));
+                    self.profile(|profile| profile.branch(*span, taken));
+                    if taken {
+                        let _ = self.execute_program(Program {
+                            root_id: 0,
+                            statements: vec![then_branch.as_ref().clone()],
+                            span: program.span,
+                            attributes: vec![],
+                        });
+                    } else if let Some(else_stmt) = else_branch {
+                        let _ = self.execute_program(Program {
+                            root_id: 0,
+                            statements: vec![else_stmt.as_ref().clone()],
+                            span: program.span,
+                            attributes: vec![],
+                        });
+                    }
+                    executed_count += 1;
+                }
+                Statement::WhileStatement { condition, body, .. } => {
+                    self.profile(|profile| profile.enter_loop(body.span()));
+                    while !self.check_cancelled() && matches!(self.evaluate_expression(condition), Value::Boolean(true)) {
+                        self.profile(|profile| profile.iterate(body.span()));
+                        let _ = self.execute_program(Program {
+                            root_id: 0,
+                            statements: vec![body.as_ref().clone()],
+                            span: program.span,
+                            attributes: vec![],
+                        });
+                    }
+                    executed_count += 1;
+                }
+                Statement::ForStatement { initializer, condition, increment, body, .. } => {
+                    if let Some(init) = initializer {
+                        let _ = self.execute_program(Program {
+                            root_id: 0,
+                            statements: vec![init.as_ref().clone()],
+                            span: program.span,
+                            attributes: vec![],
+                        });
+                    }
+                    self.profile(|profile| profile.enter_loop(body.span()));
+                    while !self.check_cancelled()
+                        && condition.as_ref().is_none_or(|c| matches!(self.evaluate_expression(c), Value::Boolean(true)))
+                    {
+                        self.profile(|profile| profile.iterate(body.span()));
+                        let _ = self.execute_program(Program {
+                            root_id: 0,
+                            statements: vec![body.as_ref().clone()],
+                            span: program.span,
+                            attributes: vec![],
+                        });
+                        if let Some(inc) = increment {
+                            let _ = self.evaluate_expression(inc);
+                        }
+                    }
+                    executed_count += 1;
+                }
+                // 반복마다 새 스코프를 열어 반복 변수를 바인딩합니다.
+                Statement::ForInStatement { variable, iterable, body, span } => {
+                    let iterable = self.evaluate_expression(iterable);
+                    if let Some(mut cursor) = self.iterate(&iterable, *span) {
+                        self.profile(|profile| profile.enter_loop(body.span()));
+                        while !self.check_cancelled() {
+                            let Some(item) = cursor.next(self) else {
+                                break;
+                            };
+                            self.profile(|profile| profile.iterate(body.span()));
+                            let mut loop_env = Environment::new_enclosed(self.environment.clone());
+                            loop_env.set(*variable, item);
+                            let saved = core::mem::replace(&mut self.environment, Rc::new(RefCell::new(loop_env)));
+                            let _ = self.execute_program(Program {
+                                root_id: 0,
+                                statements: vec![body.as_ref().clone()],
+                                span: program.span,
+                                attributes: vec![],
+                            });
+                            self.environment = saved;
+                        }
+                    }
+                    executed_count += 1;
+                }
+                Statement::MacroDefinition { name, parameters, body, .. } => {
+                    self.environment.borrow_mut().set(Symbol::intern(name), Value::Macro(name.clone()));
+                    self.emit(format!("Macro '{}' defined with {} parameter(s)", name, parameters.len()));
+                    executed_count += 1;
+                }
+                Statement::DeferStatement(_, body) => {
+                    deferred.push(body.as_ref().clone());
+                    executed_count += 1;
+                }
+                // 타입 별칭은 이후 표기된 `let` 이 값을 검사할 때 씁니다. 중복 선언은 먼저 것이 남습니다. (E0012)
+                Statement::TypeAlias { name, target, span } => {
+                    self.types.borrow_mut().declare(*name, target, *span);
+                    executed_count += 1;
+                }
+                Statement::EnumDeclaration { name, variants, .. } => {
+                    self.declare_enum(*name, variants, Derives::default());
+                    executed_count += 1;
+                }
+                Statement::Attributed { attributes, statement, .. } => {
+                    // `@derive(...)` 는 enum 선언이 만드는 변형마다 남깁니다.
+                    if let Statement::EnumDeclaration { name, variants, .. } = statement.as_ref() {
+                        self.declare_enum(*name, variants, Derives::from_names(attributes::derived(attributes)));
+                    } else {
+                        let _ = self.execute_program(Program {
+                            root_id: 0,
+                            statements: vec![statement.as_ref().clone()],
+                            span: program.span,
+                            attributes: vec![],
+                        });
+                    }
+                    executed_count += 1;
+                }
+            }
+            if mark.is_some() {
+                let value = matches!(statement, Statement::ExpressionStatement(_) | Statement::ReturnStatement(..));
+                self.trace_end(mark, &scope, self.last_value.as_ref().filter(|_| value));
+            }
+
+            if let Some(panic) = &self.panic {
+                return panic.to_diagnostic();
+            }
+        }
+
+        // `return` 으로 끝나는 스코프는 흐름이 닫혔으므로 문장 수와 상관없이 균형이 맞습니다.
+        if check_flow && executed_count > 0 && executed_count % 3 != 0 && !program.ends_with_return() {
+            error_codes::UNBALANCED_FLOW
+                .diagnostic(program.span, "", &[&executed_count])
+                .with_help(error_codes::UNBALANCED_FLOW.message("help", &[]))
+        } else {
+            Diagnostic {
+                level: DiagnosticLevel::Info,
+                message: messages::tr("runtime.executed", &[&executed_count]),
+                span: program.span,
+                help: None,
+                code: None,
+            }
+        }
+    }
+
+    pub fn evaluate_expression(&mut self, expr: &Expression) -> Value {
+        match expr {
+            Expression::Literal(_, val) => val.clone(),
+            Expression::Identifier(_, name) => self.lookup(*name),
+            Expression::Grouped(_, inner) => self.evaluate_expression(inner),
+            Expression::PrefixOperation(span, op, inner) => {
+                let operand = self.evaluate_expression(inner);
+                self.apply_unary(op, operand, *span)
+            }
+            Expression::InfixOperation(span, op, left, right) => {
+                let left = self.evaluate_expression(left);
+                if let Some(value) = operators::short_circuit(op, &left) {
+                    return value;
+                }
+                let right = self.evaluate_expression(right);
+                self.apply_binary(op, left, right, *span)
+            }
+            // 고른 쪽만 평가합니다. 조건이 오류 값이면 그 오류, bool 이 아니면 E0010 입니다.
+            Expression::Range(_, start, end, inclusive) => match (self.evaluate_expression(start), self.evaluate_expression(end)) {
+                (Value::Integer(start), Value::Integer(end)) => Value::Range(start, if *inclusive { end.saturating_add(1) } else { end }),
+                (error @ Value::Error(_), _) | (_, error @ Value::Error(_)) => error,
+                (start, end) => error_codes::TYPE_MISMATCH.error_value("range", &[&type_name(&start), &type_name(&end)]),
+            },
+            Expression::Ternary(_, condition, then_expr, else_expr) => match self.evaluate_expression(condition) {
+                Value::Boolean(true) => self.evaluate_expression(then_expr),
+                Value::Boolean(false) => self.evaluate_expression(else_expr),
+                error @ Value::Error(_) => error,
+                other => error_codes::TYPE_MISMATCH.error_value("condition", &[&type_name(&other)]),
+            },
+            Expression::Reflect(_, inner) => {
+                let val = self.evaluate_expression(inner);
+                reflect(&val)
+            }
+            Expression::Eval(_, code_expr) => {
+                let code_val = self.evaluate_expression(code_expr);
+                eval_value(code_val)
+            }
+            Expression::TypeOf(_, inner) => {
+                let val = self.evaluate_expression(inner);
+                type_of(&val)
+            }
+            Expression::MacroCall(span, name, args) => {
+                let callee = self.environment.borrow().get(Symbol::intern(name));
+                let arg_values = self.evaluate_arguments(args);
+                self.call_resolved(name, *span, callee, arg_values, true)
+            }
+            Expression::Function(_, parameters, body) => {
+                Value::Function(Box::new(FunctionValue::new(parameters.clone(), body.as_ref().clone())))
+            }
+            Expression::ArrayLiteral(_, elements) => {
+                let items = self.evaluate_arguments(elements);
+                match items.iter().find(|item| matches!(item, Value::Error(_))) {
+                    Some(error) => error.clone(),
+                    None => Value::Array(items.into()),
+                }
+            }
+            Expression::MapLiteral(_, entries) => {
+                let mut map = Map::default();
+                for (key, value) in entries {
+                    let (key, value) = (self.evaluate_expression(key), self.evaluate_expression(value));
+                    if let Value::Error(_) = key {
+                        return key;
+                    }
+                    if let Value::Error(_) = value {
+                        return value;
+                    }
+                    let Some(key) = maps::Key::from_value(&key) else {
+                        return error_codes::TYPE_MISMATCH.error_value("map_key", &[&type_name(&key)]);
+                    };
+                    map.insert(key, value);
+                }
+                Value::Map(Rc::new(map))
+            }
+            Expression::Index(span, target, index) => {
+                let (target, index) = (self.evaluate_expression(target), self.evaluate_expression(index));
+                self.index_value(target, index, *span)
+            }
+            Expression::Member(span, target, name) => {
+                let target = self.evaluate_expression(target);
+                if let Value::Error(_) = target {
+                    return target;
+                }
+                self.index_value(target, Value::String(name.as_str().into()), *span)
+            }
+            Expression::MethodCall(span, target, name, args) => {
+                let receiver = self.evaluate_expression(target);
+                if let Value::Error(_) = receiver {
+                    return receiver;
+                }
+                let callee = self.environment.borrow().get(*name);
+                let mut arg_values = vec![receiver];
+                arg_values.extend(self.evaluate_arguments(args));
+                self.call_resolved(name.as_str(), *span, callee, arg_values, false)
+            }
+            Expression::Slice(span, target, start, end) => {
+                let target = self.evaluate_expression(target);
+                let start = start.as_ref().map(|s| self.evaluate_expression(s));
+                let end = end.as_ref().map(|e| self.evaluate_expression(e));
+                let result = match target {
+                    Value::Array(_) => arrays::slice(&target, start.as_ref(), end.as_ref()),
+                    _ => bytes::slice(&target, start.as_ref(), end.as_ref()),
+                };
+                self.access_result(result, &target, *span)
+            }
+            Expression::Match(_, subject, arms) => {
+                let subject = self.evaluate_expression(subject);
+                if let Value::Error(_) = subject {
+                    return subject;
+                }
+                // 일치하는 arm 이 없으면 null 입니다 (E0072).
+                self.evaluate_match_arms(arms, &subject).unwrap_or(Value::Null)
+            }
+            Expression::Assign(span, op, name, value) => {
+                let value = self.evaluate_expression(value);
+                let annotation = match self.environment.borrow().assignable(*name) {
+                    Ok(annotation) => annotation,
+                    Err(AssignError::Undefined) => return error_codes::UNDEFINED_VARIABLE.error_value("", &[name]),
+                    Err(AssignError::Immutable) => return error_codes::ASSIGN_IMMUTABLE.error_value("", &[name]),
+                };
+                let value = match op {
+                    TokenKind::PlusAssign => self.apply_binary(&TokenKind::Plus, self.lookup(*name), value, *span),
+                    TokenKind::MinusAssign => self.apply_binary(&TokenKind::Minus, self.lookup(*name), value, *span),
+                    _ => value,
+                };
+                let value = match &annotation {
+                    Some(annotation) => self.coerce_binding(*name, annotation, value),
+                    None => value,
+                };
+                self.environment.borrow_mut().assign(*name, value.clone());
+                value
+            }
+            Expression::Call(span, function, args) => match function.as_ref() {
+                Expression::Identifier(_, name) => {
+                    let callee = self.environment.borrow().get(*name);
+                    let arg_values = self.evaluate_arguments(args);
+                    self.call_resolved(name.as_str(), *span, callee, arg_values, false)
+                }
+                other => {
+                    let callee = self.evaluate_expression(other);
+                    let arg_values = self.evaluate_arguments(args);
+                    self.call_resolved(&messages::tr("trace.anonymous", &[]), *span, Some(callee), arg_values, false)
+                }
+            },
+        }
+    }
+
+    /// `target[index]` 와 `target.name`
+    fn index_value(&mut self, target: Value, index: Value, span: Span) -> Value {
+        let result = match (&target, &index) {
+            (Value::Record(record), Value::String(field)) => record
+                .get(field)
+                .cloned()
+                .ok_or_else(|| AccessError::UnknownKey(field.to_string())),
+            // 필드 순서로도 읽습니다. (`callstack()[0]`)
+            (Value::Record(record), Value::Integer(i)) => usize::try_from(*i)
+                .ok()
+                .and_then(|i| record.fields.get(i))
+                .map(|(_, value)| value.clone())
+                .ok_or(AccessError::IndexOutOfBounds { index: *i, len: record.fields.len() }),
+            (Value::Match(_) | Value::Matches(_), _) => regexp::index(&target, &index),
+            // `xs[1..3]` 는 `xs[1:3]` 입니다.
+            (Value::Array(_) | Value::Bytes(_), Value::Range(start, end)) => {
+                let (start, end) = (Value::Integer(*start), Value::Integer(*end));
+                match target {
+                    Value::Array(_) => arrays::slice(&target, Some(&start), Some(&end)),
+                    _ => bytes::slice(&target, Some(&start), Some(&end)),
+                }
+            }
+            (Value::Array(_), _) => arrays::index(&target, &index),
+            (Value::Map(map), key) => {
+                let Some(key) = maps::Key::from_value(key) else {
+                    return error_codes::TYPE_MISMATCH.error_value("map_key", &[&type_name(key)]);
+                };
+                map.get(&key).cloned().ok_or_else(|| AccessError::UnknownKey(formatting::display_value(&index)))
+            }
+            _ => bytes::index(&target, &index),
+        };
+        self.access_result(result, &target, span)
+    }
+
+    /// 현재 환경에서 이름을 찾습니다. 없으면 오류 값입니다.
+    pub fn lookup(&self, name: Symbol) -> Value {
+        self.environment.borrow().get(name).unwrap_or_else(|| error_codes::UNDEFINED_VARIABLE.error_value("", &[&name]))
+    }
+
+    fn evaluate_arguments(&mut self, args: &[Box<Expression>]) -> Vec<Value> {
+        args.iter().map(|a| self.evaluate_expression(a)).collect()
+    }
+
+    /// 표기된 `let` 의 강제 변환 지점 (named_types 의 점진적 타입 규칙). int 는 float 자리에서 넓히고,
+    /// 속하지 않는 값은 E0010 오류 값이 됩니다. 아직 실행하지 않은 별칭을 쓴 표기는 검사하지 않습니다.
+    fn coerce_binding(&self, name: Symbol, annotation: &TypeAnnotation, value: Value) -> Value {
+        if let Value::Error(_) = value {
+            return value;
+        }
+        let Ok(expected) = self.types.borrow().resolve(annotation) else {
+            return value;
+        };
+        let found = type_name(&value);
+        named_types::coerce(&expected, value)
+            .unwrap_or_else(|| error_codes::TYPE_MISMATCH.error_value("coercion", &[&name, &expected, &found]))
+    }
+
+    /// 필드 없는 변형은 값, 필드가 있는 변형은 생성자로 바인딩합니다.
+    fn declare_enum(&mut self, name: Symbol, variants: &[VariantDeclaration], derives: Derives) {
+        for variant in variants {
+            let fields = variant.fields.is_empty().then(Vec::new);
+            let value = EnumVariant { enum_name: name, name: variant.name, field_types: variant.fields.clone(), fields, derives };
+            self.environment.borrow_mut().set(variant.name, Value::EnumVariant(Rc::new(value)));
+        }
+    }
+
+    /// `Rgb(1, 2, 3)`: 인자를 선언한 필드 타입으로 강제 변환해 변형 값을 만듭니다. 맞지 않는 인자가 있으면 그 오류 값입니다.
+    fn construct_variant(&self, variant: &EnumVariant, args: Vec<Value>) -> Value {
+        if args.len() != variant.field_types.len() {
+            return error_codes::ARITY_MISMATCH.error_value("function", &[&variant.field_types.len(), &args.len()]);
+        }
+        let mut fields = Vec::with_capacity(args.len());
+        for (annotation, arg) in variant.field_types.iter().zip(args) {
+            match self.coerce_binding(variant.name, annotation, arg) {
+                error @ Value::Error(_) => return error,
+                field => fields.push(field),
+            }
+        }
+        Value::EnumVariant(Rc::new(EnumVariant { fields: Some(fields), ..variant.clone() }))
+    }
+
+    /// 이항 연산을 계산합니다. 오류 값인 피연산자는 그대로 전파하고, 정수를 0으로 나누면 패닉입니다.
+    pub fn apply_binary(&mut self, op: &TokenKind, left: Value, right: Value, span: Span) -> Value {
+        if let Value::Error(_) = left {
+            return left;
+        }
+        if let Value::Error(_) = right {
+            return right;
+        }
+        match operators::binary(op, &left, &right) {
+            Ok(value) => value,
+            Err(OperatorError::TypeMismatch) => error_codes::TYPE_MISMATCH.error_value(
+                "operator",
+                &[&operators::symbol(op), &type_name(&left), &type_name(&right)],
+            ),
+            Err(OperatorError::DivisionByZero) => {
+                self.raise_panic(error_codes::PANIC.message("division_by_zero", &[]), span)
+            }
+            Err(OperatorError::NotDerived(derive)) => {
+                let enum_name = match &left {
+                    Value::EnumVariant(variant) => variant.enum_name.to_string(),
+                    other => type_name(other).to_string(),
+                };
+                error_codes::TYPE_MISMATCH.error_value("not_derived", &[&enum_name, &derive])
+            }
+        }
+    }
+
+    pub fn apply_unary(&mut self, op: &TokenKind, operand: Value, span: Span) -> Value {
+        if let Value::Error(_) = operand {
+            return operand;
+        }
+        match operators::unary(op, &operand) {
+            Ok(value) => value,
+            Err(OperatorError::DivisionByZero) => {
+                self.raise_panic(error_codes::PANIC.message("division_by_zero", &[]), span)
+            }
+            Err(OperatorError::TypeMismatch | OperatorError::NotDerived(_)) => {
+                error_codes::TYPE_MISMATCH.error_value("unary_operator", &[&operators::symbol(op), &type_name(&operand)])
+            }
+        }
+    }
+
+    /// 이름(또는 식)으로 찾은 호출 대상을 부릅니다.
+    ///
+    /// 바인딩이 없으면 내장 함수를 찾고, 매크로 확장을 거치지 않은 호출이면 기록만 남깁니다.
+    pub fn call_resolved(&mut self, name: &str, span: Span, callee: Option<Value>, args: Vec<Value>, macro_call: bool) -> Value {
+        match callee {
+            Some(Value::Function(func)) => self.call_with_frame(name, span, &func, args),
+            Some(Value::EnumVariant(variant)) if variant.fields.is_none() => self.construct_variant(&variant, args),
+            None if is_builtin(name) => self.call_builtin(name, span, args),
+            _ if macro_call => {
+                self.emit(format!("Macro '{}' called with {} args", name, args.len()));
+                Value::Null
+            }
+            other => {
+                let value = other.unwrap_or_else(|| error_codes::UNDEFINED_VARIABLE.error_value("", &[&name]));
+                error_codes::NOT_CALLABLE.error_value("", &[&pretty::dump(&value)])
+            }
+        }
+    }
+
+    /// 호출 스택에 프레임을 쌓은 채로 함수를 호출합니다. (패닉 시 스택 추적에 사용)
+    fn call_with_frame(&mut self, name: &str, call_site: Span, func: &FunctionValue, args: Vec<Value>) -> Value {
+        self.call_stack.push(CallFrame { name: name.to_string(), call_site });
+        let result = self.call_function(func, args);
+        self.call_stack.pop();
+        result
+    }
+
+    /// 런타임 패닉을 발생시킵니다. 이미 진행 중인 패닉이 있으면 처음 것을 유지합니다.
+    /// 토큰이 취소되었으면 취소 패닉을 올립니다. 이미 패닉 중이거나 취소되었으면 참입니다.
+    pub fn check_cancelled(&mut self) -> bool {
+        if self.panic.is_none() && self.cancellation.is_cancelled() {
+            let message = error_codes::CANCELLED.message("", &[]);
+            self.raise_panic(message, Span { start: 0, end: 0 });
+        }
+        self.panic.is_some()
+    }
+
+    /// 범위를 벗어난 접근은 0으로 나누기처럼 패닉입니다.
+    fn access_result(&mut self, result: Result<Value, AccessError>, target: &Value, span: Span) -> Value {
+        match result {
+            Ok(value) => value,
+            Err(AccessError::TypeMismatch) => error_codes::TYPE_MISMATCH.error_value("index", &[&type_name(target)]),
+            Err(AccessError::IndexOutOfBounds { index, len }) => {
+                let message = error_codes::PANIC.message("index_out_of_bounds", &[&index, &len]);
+                self.raise_panic(message, span)
+            }
+            Err(AccessError::SliceOutOfBounds { start, end, len }) => {
+                let message = error_codes::PANIC.message("slice_out_of_bounds", &[&start, &end, &len]);
+                self.raise_panic(message, span)
+            }
+            Err(AccessError::UnknownKey(key)) => {
+                let message = error_codes::PANIC.message("unknown_key", &[&key]);
+                self.raise_panic(message, span)
+            }
+        }
+    }
+
+    /// for-in 의 반복 위치. 돌 수 없는 값이면 패닉입니다. 오류 값은 그 메시지로 패닉합니다.
+    pub(crate) fn iterate(&mut self, iterable: &Value, span: Span) -> Option<Cursor> {
+        let cursor = Cursor::new(iterable);
+        if cursor.is_none() {
+            let message = match iterable {
+                Value::Error(message) => message.clone(),
+                other => error_codes::PANIC.message("not_iterable", &[&type_name(other)]),
+            };
+            self.raise_panic(message, span);
+        }
+        cursor
+    }
+
+    fn raise_panic(&mut self, message: String, span: Span) -> Value {
+        if self.panic.is_none() {
+            self.panic = Some(RuntimePanic { message: message.clone(), span, stack: self.call_stack.clone() });
+        }
+        error_codes::PANIC.runtime_error(message)
+    }
+
+    /// 같은 이름의 변수가 없을 때 쓰이는 내장 함수입니다. (`is_builtin` 이 참인 이름)
+    fn call_builtin(&mut self, name: &str, span: Span, mut args: Vec<Value>) -> Value {
+        match name {
+            "panic" => {
+                let message = match args.first() {
+                    Some(value) => formatting::display_value(value),
+                    None => error_codes::PANIC.message("explicit", &[]),
+                };
+                self.raise_panic(message, span)
+            }
+            "assert" => {
+                let condition = match args.first() {
+                    Some(cond) => cond,
+                    None => return error_codes::ARITY_MISMATCH.error_value("assert", &[]),
+                };
+                match condition {
+                    Value::Boolean(true) => Value::Null,
+                    Value::Boolean(false) => {
+                        let message = match args.get(1) {
+                            Some(value) => error_codes::PANIC
+                                .message("assert_failed_message", &[&formatting::display_value(value)]),
+                            None => error_codes::PANIC.message("assert_failed", &[]),
+                        };
+                        self.raise_panic(message, span)
+                    }
+                    other => {
+                        let message = error_codes::PANIC.message("assert_not_bool", &[&type_name(other)]);
+                        self.raise_panic(message, span)
+                    }
+                }
+            }
+            "format" => {
+                if args.is_empty() {
+                    return error_codes::ARITY_MISMATCH.error_value("format", &[]);
+                }
+                let Value::String(template) = args.remove(0) else {
+                    return error_codes::TYPE_MISMATCH.error_value("format", &[]);
+                };
+                match formatting::format_values(&template, &args) {
+                    Ok(text) => Value::String(text.into()),
+                    Err(e) => error_codes::INVALID_FORMAT.runtime_error(e),
+                }
+            }
+            "gc" => {
+                if !args.is_empty() {
+                    return error_codes::ARITY_MISMATCH.error_value("function", &[&0, &args.len()]);
+                }
+                let report = self.collect_garbage();
+                self.run_finalizers();
+                Value::Integer(report.objects as i64)
+            }
+            "weak_ref" | "deref" if args.len() != 1 => {
+                error_codes::ARITY_MISMATCH.error_value("function", &[&1, &args.len()])
+            }
+            "weak_ref" => match &args[0] {
+                Value::Generator(state) => Value::WeakRef(Rc::downgrade(state)),
+                other => error_codes::TYPE_MISMATCH.error_value("weak_ref", &[&type_name(other)]),
+            },
+            "deref" => match &args[0] {
+                Value::WeakRef(target) => target.upgrade().map_or(Value::Null, Value::Generator),
+                other => error_codes::TYPE_MISMATCH.error_value("deref", &[&type_name(other)]),
+            },
+            "on_finalize" => {
+                if args.len() != 2 {
+                    return error_codes::ARITY_MISMATCH.error_value("function", &[&2, &args.len()]);
+                }
+                let target = match &args[0] {
+                    Value::Generator(state) => state.clone(),
+                    Value::WeakRef(target) => match target.upgrade() {
+                        Some(state) => state,
+                        None => return Value::Null,
+                    },
+                    other => return error_codes::TYPE_MISMATCH.error_value("weak_ref", &[&type_name(other)]),
+                };
+                let Value::Function(finalizer) = &args[1] else {
+                    return error_codes::TYPE_MISMATCH.error_value("on_finalize", &[&type_name(&args[1])]);
+                };
+                self.heap.borrow_mut().on_finalize(&target, finalizer.as_ref().clone());
+                Value::Null
+            }
+            "len" | "to_hex" | "from_hex" | "to_utf8" | "from_utf8" | "read_file_bytes" | "clone" if args.len() != 1 => {
+                error_codes::ARITY_MISMATCH.error_value("function", &[&1, &args.len()])
+            }
+            "len" => match &args[0] {
+                Value::Bytes(b) => Value::Integer(b.len() as i64),
+                Value::String(s) => Value::Integer(s.chars().count() as i64),
+                Value::Match(m) => Value::Integer(m.groups.len() as i64),
+                Value::Matches(all) => Value::Integer(all.len() as i64),
+                Value::Record(record) => Value::Integer(record.fields.len() as i64),
+                Value::Array(items) => Value::Integer(items.len() as i64),
+                Value::Map(map) => Value::Integer(map.len() as i64),
+                Value::Range(start, end) => Value::Integer(ranges::len(*start, *end)),
+                other => error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"bytes, string, array, map, range, match, record", &type_name(other)]),
+            },
+            // 값은 바뀌지 않으므로 복사본은 같은 값입니다. enum 값은 `@derive(clone)` 해야 복사할 수 있습니다.
+            "clone" => match &args[0] {
+                Value::EnumVariant(variant) if variant.fields.is_some() && !variant.derives.clone => {
+                    error_codes::TYPE_MISMATCH.error_value("not_derived", &[&variant.enum_name, &"clone"])
+                }
+                Value::EnumVariant(variant) => Value::EnumVariant(Rc::new(variant.as_ref().clone())),
+                other @ (Value::Generator(_) | Value::WeakRef(_)) => {
+                    error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"enum, array, map, string, number", &type_name(other)])
+                }
+                other => other.clone(),
+            },
+            "to_hex" => match &args[0] {
+                Value::Bytes(b) => Value::String(bytes::to_hex(b).into()),
+                other => error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"bytes", &type_name(other)]),
+            },
+            "from_utf8" => match &args[0] {
+                Value::Bytes(b) => match core::str::from_utf8(b) {
+                    Ok(text) => Value::String(text.into()),
+                    Err(e) => error_codes::TYPE_MISMATCH.error_value("from_utf8", &[&e.valid_up_to()]),
+                },
+                other => error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"bytes", &type_name(other)]),
+            },
+            "to_utf8" | "from_hex" | "read_file_bytes" => {
+                let Value::String(text) = &args[0] else {
+                    return error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"string", &type_name(&args[0])]);
+                };
+                match name {
+                    "to_utf8" => Value::Bytes(text.as_bytes().into()),
+                    "from_hex" => match bytes::from_hex(text) {
+                        Ok(decoded) => Value::Bytes(decoded.into()),
+                        Err(e) => error_codes::TYPE_MISMATCH.error_value("from_hex", &[&e]),
+                    },
+                    _ if !self.capabilities.files => error_codes::CAPABILITY_DENIED.error_value("files", &[&name]),
+                    _ => read_file_bytes(text),
+                }
+            }
+            "match_start" | "match_end" => {
+                let group = match args.len() {
+                    1 => Value::Integer(0),
+                    2 => args[1].clone(),
+                    n => return error_codes::ARITY_MISMATCH.error_value("function", &[&2, &n]),
+                };
+                match &args[0] {
+                    Value::Match(m) => regexp::position(m, &group, name == "match_end"),
+                    other => error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"match", &type_name(other)]),
+                }
+            }
+            "run_process" => {
+                let argv = match process_argv(&args) {
+                    Ok(argv) => argv,
+                    Err(error) => return error,
+                };
+                if !self.capabilities.processes {
+                    return error_codes::CAPABILITY_DENIED.error_value("process", &[]);
+                }
+                run_process(&argv, &self.cancellation)
+            }
+            "now" | "callstack" | "locals" | "read_line" | "read_all" if !args.is_empty() => {
+                error_codes::ARITY_MISMATCH.error_value("function", &[&0, &args.len()])
+            }
+            "now" => now(),
+            "read_line" => self.input.borrow_mut().read_line().map_or(Value::Null, |line| Value::String(line.into())),
+            "read_all" => Value::String(self.input.borrow_mut().read_all().into()),
+            "callstack" | "locals" if !self.capabilities.debug => error_codes::CAPABILITY_DENIED.error_value("debug", &[&name]),
+            "callstack" => self.callstack(),
+            "locals" => self.locals(),
+            "parse_datetime" | "format_datetime" | "to_offset" => self.call_datetime(name, &args),
+            "keys" | "values" | "contains_key" | "insert" => call_map(name, &args),
+            "from_epoch_millis" | "duration" | "seconds" | "minutes" | "hours" | "days" | "epoch_millis"
+                if args.len() != 1 =>
+            {
+                error_codes::ARITY_MISMATCH.error_value("function", &[&1, &args.len()])
+            }
+            "epoch_millis" => match &args[0] {
+                Value::DateTime(t) => Value::Integer(t.millis),
+                Value::Duration(d) => Value::Integer(*d),
+                other => error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"datetime, duration", &type_name(other)]),
+            },
+            "from_epoch_millis" | "duration" | "seconds" | "minutes" | "hours" | "days" => {
+                let Value::Integer(n) = &args[0] else {
+                    return error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"int", &type_name(&args[0])]);
+                };
+                let unit = match name {
+                    "seconds" => datetime::MILLIS_PER_SECOND,
+                    "minutes" => datetime::MILLIS_PER_MINUTE,
+                    "hours" => datetime::MILLIS_PER_HOUR,
+                    "days" => datetime::MILLIS_PER_DAY,
+                    _ => 1,
+                };
+                match name {
+                    "from_epoch_millis" => Value::DateTime(DateTime::utc(*n)),
+                    _ => Value::Duration(n.saturating_mul(unit)),
+                }
+            }
+            #[cfg(not(feature = "regex"))]
+            "regex_match" | "regex_find_all" | "regex_replace" => error_codes::UNSUPPORTED.error_value("regex", &[&name]),
+            #[cfg(feature = "regex")]
+            "regex_match" | "regex_find_all" | "regex_replace" => self.call_regex(name, &args),
+            _ => error_codes::UNDEFINED_VARIABLE.error_value("", &[&name]),
+        }
+    }
+
+    /// 도달할 수 없는 제너레이터 순환을 회수합니다. (`gc()` 내장 함수)
+    pub fn collect_garbage(&mut self) -> CollectionReport {
+        self.heap.borrow_mut().collect(&self.environment)
+    }
+
+    /// 대상이 해제된 finalizer 를 등록 순서대로 부릅니다. 패닉 중이거나 취소되었으면 남겨 둡니다.
+    pub fn run_finalizers(&mut self) {
+        while !self.check_cancelled() {
+            let Some(finalizer) = self.heap.borrow_mut().next_due_finalizer() else {
+                break;
+            };
+            self.call_with_frame("<finalizer>", Span { start: 0, end: 0 }, &finalizer, vec![]);
+        }
+    }
+
+    pub fn gc_stats(&self) -> GcStats {
+        self.heap.borrow().stats()
+    }
+
+    /// 함수 값을 호출합니다. 본문에 `yield` 가 있으면 본문을 실행하지 않고 제너레이터를 돌려줍니다.
+    ///
+    /// 일반 함수는 바이트코드로 컴파일되어 있으면 VM 으로, 아니면 인터프리터로 실행합니다 (tiering.rs).
+    pub fn call_function(&mut self, func: &FunctionValue, args: Vec<Value>) -> Value {
+        if func.parameters.len() != args.len() {
+            return error_codes::ARITY_MISMATCH.error_value("function", &[&func.parameters.len(), &args.len()]);
+        }
+        if self.check_cancelled() {
+            return Value::Null;
+        }
+        self.profile(|profile| profile.call(func.body.span()));
+
+        let is_generator = generator::contains_yield(&func.body);
+        if !is_generator {
+            // 빌림을 VM 실행 중까지 들고 있지 않도록 먼저 꺼냅니다. (본문이 다른 함수를 부를 수 있음)
+            let compiled = self.tiering.borrow_mut().compiled(func.id);
+            if let Some(chunk) = compiled {
+                return bytecode::execute(self, &chunk, args);
+            }
+            self.tiering.borrow_mut().record_call(func);
+        }
+
+        let mut call_env = Environment::new_enclosed(self.environment.clone());
+        for (param, arg) in func.parameters.iter().zip(args) {
+            call_env.set(*param, arg);
+        }
+        let call_env = Rc::new(RefCell::new(call_env));
+
+        if is_generator {
+            let state = Rc::new(RefCell::new(GeneratorState::new(&func.body, call_env)));
+            if self.heap.borrow_mut().track(&state) {
+                self.collect_garbage();
+            }
+            return Value::Generator(state);
+        }
+        GeneratorState::new(&func.body, call_env).run_to_completion(self)
+    }
+
+    /// 값과 일치하고 guard 가 참인 첫 번째 arm 을 찾아, 그 바인딩이 담긴 환경에서 본문을 평가합니다.
+    pub fn evaluate_match_arms(&mut self, arms: &[MatchArm], value: &Value) -> Option<Value> {
+        for arm in arms {
+            let Some(bindings) = patterns::match_pattern(&arm.pattern, value) else {
+                continue;
+            };
+
+            let mut arm_env = Environment::new_enclosed(self.environment.clone());
+            for (name, bound) in bindings {
+                arm_env.set(name, bound);
+            }
+            let saved = core::mem::replace(&mut self.environment, Rc::new(RefCell::new(arm_env)));
+
+            let guard_passed = arm
+                .guard
+                .as_ref()
+                .is_none_or(|g| matches!(self.evaluate_expression(g), Value::Boolean(true)));
+            let result = guard_passed.then(|| self.evaluate_expression(&arm.body));
+
+            self.environment = saved;
+            if result.is_some() {
+                return result;
+            }
+        }
+        None
+    }
+
+    /// 제너레이터를 다음 `yield` 까지 실행합니다. 끝났으면 None 을 돌려줍니다.
+    pub fn resume_generator(&mut self, generator: &Rc<RefCell<GeneratorState>>) -> Option<Value> {
+        generator.borrow_mut().resume(self)
+    }
+}
+
+/// `typeof` 의 결과
+pub fn type_of(val: &Value) -> Value {
+    match val {
+        Value::Integer(_) => Value::Type("int".into()),
+        Value::Float(_) => Value::Type("float".into()),
+        Value::Boolean(_) => Value::Type("bool".into()),
+        Value::String(_) => Value::Type("string".into()),
+        Value::Array(_) => Value::Type("array".into()),
+        Value::Map(_) => Value::Type("map".into()),
+        Value::Range(..) => Value::Type("range".into()),
+        Value::EnumVariant(variant) => Value::Type(variant.enum_name.to_string()),
+        // 타입 표기의 `void` 와 같은 이름입니다.
+        Value::Null => Value::Type("void".into()),
+        _ => Value::Type("unknown".into()),
+    }
+}
+
+/// `eval` 의 결과: 문자열 코드를 별도 런타임에서 실행합니다.
+pub fn eval_value(code: Value) -> Value {
+    let Value::String(code) = code else {
+        return error_codes::TYPE_MISMATCH.error_value("eval", &[]);
+    };
+    match eval_string(&code) {
+        Ok(val) => val,
+        Err(e) => error_codes::EVAL_FAILED.error_value("", &[&e]),
+    }
+}
+
+/// 값의 런타임 타입 이름을 돌려줍니다.
+pub fn type_name(val: &Value) -> &'static str {
+    match val {
+        Value::Integer(_) => "int",
+        Value::Float(_) => "float",
+        Value::Boolean(_) => "bool",
+        Value::String(_) => "string",
+        Value::Function(_) => "function",
+        Value::Null => "null",
+        Value::Return(_) => "return",
+        Value::Error(_) => "error",
+        Value::Reflection(_) => "reflection",
+        Value::Macro(_) => "macro",
+        Value::Type(_) => "type",
+        Value::Generator(_) => "generator",
+        Value::WeakRef(_) => "weak",
+        Value::Bytes(_) => "bytes",
+        Value::Match(_) => "match",
+        Value::Matches(_) => "matches",
+        Value::DateTime(_) => "datetime",
+        Value::Duration(_) => "duration",
+        Value::Record(_) => "record",
+        Value::Array(_) => "array",
+        Value::Map(_) => "map",
+        Value::EnumVariant(_) => "enum",
+        Value::Range(..) => "range",
+    }
+}
+
+/// 디버그 내장 함수. 값은 읽기 전용 레코드이며 부른 시점의 복사본입니다.
+impl HighEnduranceRuntime {
+    /// `callstack()`: 가장 안쪽 호출이 먼저 오는 `frame(function, call_start, call_end)` 레코드들.
+    /// 필드 이름은 깊이("0", "1", ...)이며 최상위 코드에서는 빈 레코드입니다.
+    fn callstack(&self) -> Value {
+        let frames = self.call_stack.iter().rev().enumerate().map(|(depth, frame)| {
+            let frame = Record {
+                name: "frame".into(),
+                fields: vec![
+                    ("function".into(), Value::String(frame.name.as_str().into())),
+                    ("call_start".into(), Value::Integer(frame.call_site.start as i64)),
+                    ("call_end".into(), Value::Integer(frame.call_site.end as i64)),
+                ],
+            };
+            (depth.to_string(), Value::Record(Rc::new(frame)))
+        });
+        Value::Record(Rc::new(Record { name: "callstack".into(), fields: frames.collect() }))
+    }
+
+    /// `locals()`: 지금 보이는 바인딩 (이름 → 값). 최상위 전역까지 포함합니다. (visible_bindings)
+    fn locals(&self) -> Value {
+        Value::Record(Rc::new(Record { name: "locals".into(), fields: visible_bindings(&self.environment) }))
+    }
+}
+
+/// `env` 에서 보이는 바인딩. 안쪽 스코프부터, 한 스코프 안에서는 이름 순이며 가려진 바깥 바인딩과
+/// 아직 값이 없는 바이트코드 칸은 빠집니다.
+fn visible_bindings(env: &Rc<RefCell<Environment>>) -> Vec<(String, Value)> {
+    let mut fields: Vec<(String, Value)> = vec![];
+    let mut scope = Some(env.clone());
+    while let Some(env) = scope {
+        let env = env.borrow();
+        let slots = env.slot_names.iter().flat_map(|names| names.iter().zip(&env.slots));
+        let slots = slots.filter_map(|(name, value)| Some((*name, value.clone()?)));
+        let mut bindings: Vec<(Symbol, Value)> = env.store.iter().map(|(name, value)| (*name, value.clone())).chain(slots).collect();
+        bindings.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        for (name, value) in bindings {
+            if !fields.iter().any(|(seen, _)| seen == name.as_str()) {
+                fields.push((name.as_str().to_string(), value));
+            }
+        }
+        scope = env.outer.clone();
+    }
+    fields
+}
+
+/// 실행 기록용 바인딩 표시 (trace::show)
+fn shown_bindings(env: &Rc<RefCell<Environment>>) -> Vec<(String, String)> {
+    visible_bindings(env).into_iter().map(|(name, value)| (name, trace::show(&value))).collect()
+}
+
+impl HighEnduranceRuntime {
+    /// parse_datetime(text[, pattern]), format_datetime(dt[, pattern]), to_offset(dt, offset)
+    fn call_datetime(&mut self, name: &str, args: &[Value]) -> Value {
+        let expected = if name == "to_offset" { 2..=2 } else { 1..=2 };
+        if !expected.contains(&args.len()) {
+            return error_codes::ARITY_MISMATCH.error_value("function", &[expected.end(), &args.len()]);
+        }
+        let pattern = match args.get(1) {
+            None => None,
+            Some(Value::String(s)) => Some(s.as_ref()),
+            Some(other) => return error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"string", &type_name(other)]),
+        };
+        let result = match (name, &args[0]) {
+            ("parse_datetime", Value::String(text)) => match pattern {
+                Some(pattern) => datetime::parse(text, pattern),
+                None => datetime::parse_iso(text),
+            }
+            .map(Value::DateTime),
+            ("format_datetime", Value::DateTime(t)) => match pattern {
+                Some(pattern) => datetime::format(t, pattern),
+                None => Ok(t.iso()),
+            }
+            .map(|text| Value::String(text.into())),
+            ("to_offset", Value::DateTime(t)) => datetime::parse_offset(pattern.unwrap_or_default())
+                .map(|offset_minutes| Value::DateTime(DateTime { offset_minutes, ..*t })),
+            (_, other) => {
+                let expected = if name == "parse_datetime" { "string" } else { "datetime" };
+                return error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &expected, &type_name(other)]);
+            }
+        };
+        result.unwrap_or_else(|e| error_codes::TYPE_MISMATCH.error_value("datetime", &[&e]))
+    }
+}
+
+/// keys(m), values(m), contains_key(m, key), insert(m, key, value)
+fn call_map(name: &str, args: &[Value]) -> Value {
+    let expected = match name {
+        "keys" | "values" => 1,
+        "contains_key" => 2,
+        _ => 3,
+    };
+    if args.len() != expected {
+        return error_codes::ARITY_MISMATCH.error_value("function", &[&expected, &args.len()]);
+    }
+    let Value::Map(map) = &args[0] else {
+        return error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"map", &type_name(&args[0])]);
+    };
+    match name {
+        "keys" => return Value::Array(map.iter().map(|(key, _)| key.to_value()).collect()),
+        "values" => return Value::Array(map.iter().map(|(_, value)| value.clone()).collect()),
+        _ => {}
+    }
+    let Some(key) = maps::Key::from_value(&args[1]) else {
+        return error_codes::TYPE_MISMATCH.error_value("map_key", &[&type_name(&args[1])]);
+    };
+    if name == "contains_key" {
+        return Value::Boolean(map.contains_key(&key));
+    }
+    let mut map = map.as_ref().clone();
+    map.insert(key, args[2].clone());
+    Value::Map(Rc::new(map))
+}
+
+/// `run_process(cmd, arg, ...)` 와 `run_process(cmd, [arg, ...])` 의 인자를 명령줄로 모읍니다.
+fn process_argv(args: &[Value]) -> Result<Vec<String>, Value> {
+    let (command, rest) = match args.split_first() {
+        Some(split) => split,
+        None => return Err(error_codes::ARITY_MISMATCH.error_value("function", &[&1, &0])),
+    };
+    let rest: &[Value] = match rest {
+        [Value::Array(items)] => items,
+        _ => rest,
+    };
+    core::iter::once(command)
+        .chain(rest)
+        .map(|arg| match arg {
+            Value::String(s) => Ok(s.to_string()),
+            other => Err(error_codes::TYPE_MISMATCH.error_value("builtin", &[&"run_process", &"string", &type_name(other)])),
+        })
+        .collect()
+}
+
+/// 명령을 끝까지 기다려 `process(exit_code, stdout, stderr)` 레코드로 돌려줍니다. 출력은 UTF-8 로 읽고
+/// 잘못된 바이트는 대체 문자로 바꿉니다. 시그널로 끝나 종료 코드가 없으면 -1 입니다.
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+fn run_process(argv: &[String], cancellation: &CancellationToken) -> Value {
+    match wait_for_process(argv.to_vec(), cancellation.clone()) {
+        Ok(output) => Value::Record(Rc::new(Record {
+            name: "process".into(),
+            fields: vec![
+                ("exit_code".into(), Value::Integer(output.status.code().unwrap_or(-1) as i64)),
+                ("stdout".into(), Value::String(String::from_utf8_lossy(&output.stdout).as_ref().into())),
+                ("stderr".into(), Value::String(String::from_utf8_lossy(&output.stderr).as_ref().into())),
+            ],
+        })),
+        Err(e) => Value::Error(messages::tr("runtime.process_failed", &[&argv[0], &e])),
+    }
+}
+
+/// 자식 프로세스를 기다리는 일은 블로킹이므로, 멀티 스레드 tokio 런타임 안에서는 `spawn_blocking` 으로 넘기고
+/// 기다리는 동안 이 작업자 스레드를 `block_in_place` 로 비워 줍니다. 런타임 밖이나 단일 스레드 런타임에서는
+/// 그 자리에서 기다립니다.
+#[cfg(all(feature = "std", not(target_os = "wasi")))]
+fn wait_for_process(
+    argv: Vec<String>,
+    cancellation: CancellationToken,
+) -> Result<std::process::Output, crate::cancellation::CommandError> {
+    let wait = move || {
+        let mut command = std::process::Command::new(&argv[0]);
+        command.args(&argv[1..]);
+        crate::cancellation::run_command(&mut command, None, &cancellation)
+    };
+    #[cfg(feature = "tokio")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread {
+            let task = handle.spawn_blocking(wait);
+            return tokio::task::block_in_place(|| handle.block_on(task))
+                .unwrap_or_else(|e| Err(crate::cancellation::CommandError::Io(std::io::Error::other(e))));
+        }
+    }
+    wait()
+}
+
+/// 하위 프로세스를 만들 수 없는 빌드 (no_std, WASI)
+#[cfg(not(all(feature = "std", not(target_os = "wasi"))))]
+fn run_process(_argv: &[String], _cancellation: &CancellationToken) -> Value {
+    error_codes::UNSUPPORTED.error_value("run_process", &[])
+}
+
+#[cfg(feature = "std")]
+fn now() -> Value {
+    let since_epoch = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+    // 시계가 1970년 이전이면 0 으로 봅니다.
+    Value::DateTime(DateTime::utc(since_epoch.map_or(0, |d| d.as_millis() as i64)))
+}
+
+/// 시계가 없는 빌드
+#[cfg(not(feature = "std"))]
+fn now() -> Value {
+    error_codes::UNSUPPORTED.error_value("now", &[])
+}
+
+#[cfg(feature = "regex")]
+impl HighEnduranceRuntime {
+    fn call_regex(&mut self, name: &str, args: &[Value]) -> Value {
+        let arity = if name == "regex_replace" { 3 } else { 2 };
+        if args.len() != arity {
+            return error_codes::ARITY_MISMATCH.error_value("function", &[&arity, &args.len()]);
+        }
+        let mut strings = vec![];
+        for arg in args {
+            match arg {
+                Value::String(s) => strings.push(s.as_ref()),
+                other => return error_codes::TYPE_MISMATCH.error_value("builtin", &[&name, &"string", &type_name(other)]),
+            }
+        }
+        let (pattern, text) = (strings[0], strings[1]);
+        let result = match name {
+            "regex_match" => regexp::find(pattern, text).map(|m| m.map_or(Value::Null, |m| Value::Match(Arc::new(m)))),
+            "regex_find_all" => regexp::find_all(pattern, text).map(|all| Value::Matches(all.into())),
+            _ => regexp::replace(pattern, text, strings[2]).map(|s| Value::String(s.into())),
+        };
+        result.unwrap_or_else(|e| error_codes::TYPE_MISMATCH.error_value("regex", &[&e]))
+    }
+}
+
+#[cfg(feature = "std")]
+fn read_file_bytes(path: &str) -> Value {
+    match std::fs::read(path) {
+        Ok(contents) => Value::Bytes(contents.into()),
+        Err(e) => Value::Error(messages::tr("runtime.read_failed", &[&path, &e])),
+    }
+}
+
+/// 파일 시스템이 없는 빌드
+#[cfg(not(feature = "std"))]
+fn read_file_bytes(_path: &str) -> Value {
+    error_codes::UNSUPPORTED.error_value("read_file_bytes", &[])
+}
+
+pub fn reflect(val: &Value) -> Value {
+    Value::Reflection(ReflectionInfo {
+        type_name: type_name(val).into(),
+        details: pretty::dump(val),
+    })
+}
+
+pub fn eval_string(source: &str) -> Result<Value, String> {
+    let lexer = LexerService::new(source);
+    let mut parser = ParserService::new(lexer);
+    let program = parser.parse_program();
+
+    let mut runtime = HighEnduranceRuntime::new();
+    let diag = runtime.execute_program(program);
+
+    if matches!(diag.level, DiagnosticLevel::HerFatal | DiagnosticLevel::Error) {
+        Err(diag.message)
+    } else {
+        Ok(runtime.output.last()
+            .map(|line| Value::String(line.as_str().into()))
+            .unwrap_or(Value::Null))
+    }
+}
+
+fn ends_with_return(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::ReturnStatement(_, _) => true,
+        Statement::BlockStatement { statements, .. } => {
+            if let Some(last) = statements.last() {
+                ends_with_return(last)
+            } else {
+                false
+            }
+        }
+        Statement::IfStatement { then_branch, else_branch, .. } => {
+            ends_with_return(then_branch)
+                && else_branch.as_ref().map_or(false, |b| ends_with_return(b))
+        }
+        _ => false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capabilities;
+    use crate::data_structures::Value;
+    use crate::engine::{run_to_exit, Engine};
+
+    fn text(value: Value) -> String {
+        match value {
+            Value::String(s) => s.to_string(),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    const DERIVED: &str = "@derive(eq, show, clone)\nenum Color { Red, Rgb(int, int, int) }\nenum Plain { A, B(int) }\n";
+
+    #[test]
+    fn derived_enums_compare_show_and_clone() {
+        assert!(matches!(run_to_exit(&format!("{}return Rgb(1, 2, 3) == Rgb(1, 2, 3);", DERIVED)), Value::Boolean(true)));
+        assert!(matches!(run_to_exit(&format!("{}return Rgb(1, 2, 3) != Red;", DERIVED)), Value::Boolean(true)));
+        assert_eq!(text(run_to_exit(&format!("{}return format(\"{{}} {{}}\", Rgb(1, 2, 3), Red);", DERIVED))), "Rgb(1, 2, 3) Red");
+        assert!(matches!(run_to_exit(&format!("{}let c = Rgb(1, 2, 3);\nreturn clone(c) == c;", DERIVED)), Value::Boolean(true)));
+    }
+
+    #[test]
+    fn enums_without_derive_refuse_eq_show_and_clone() {
+        let error = |source: &str| match run_to_exit(&format!("{}{}", DERIVED, source)) {
+            Value::Error(e) => e,
+            other => panic!("{:?}", other),
+        };
+        assert!(error("return A == A;").contains("@derive(eq)"));
+        assert!(error("return clone(B(2));").contains("@derive(clone)"));
+        assert_eq!(text(run_to_exit(&format!("{}return format(\"{{}}\", B(1));", DERIVED))), "<Plain>");
+    }
+
+    /// 프로세스 실행을 허락한 엔진에서 최상위 `return` 의 값
+    fn run_allowing_process(source: &str) -> Value {
+        let mut engine = Engine::new();
+        engine.set_capabilities(Capabilities { processes: true, ..Capabilities::default() });
+        engine.exit_value_of(source)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_process_takes_an_argument_array() {
+        let stdout = |source: &str| text(run_allowing_process(&format!("let p = {};\nreturn p[\"stdout\"];", source)));
+        assert_eq!(stdout("run_process(\"echo\", [\"hi\", \"there\"])"), "hi there\n");
+        assert_eq!(stdout("run_process(\"echo\", \"hi\", \"there\")"), "hi there\n");
+        assert_eq!(stdout("run_process(\"echo\", [])"), "\n");
+        match run_allowing_process("return run_process(\"echo\", [1]);") {
+            Value::Error(e) => assert!(e.contains("int"), "{}", e),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    /// 멀티 스레드 런타임 안에서도 작업자 스레드를 막지 않고 결과를 받습니다.
+    #[cfg(all(unix, feature = "tokio"))]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn run_process_inside_tokio_runtime() {
+        let value = run_allowing_process("let p = run_process(\"echo\", [\"hi\"]);\nreturn p[\"exit_code\"];");
+        assert!(matches!(value, Value::Integer(0)), "{:?}", value);
+    }
+}
@@ -0,0 +1,415 @@
+// generator.rs
+// `yield` 를 포함한 제너레이터 함수의 실행 상태입니다.
+//
+// 트리 순회 인터프리터는 재귀 호출로 문장을 실행하므로 중간에 멈출 수 없습니다.
+// 제너레이터는 대신 명시적인 프레임 스택(블록 위치, 반복문 상태)을 보관하여
+// `resume` 때마다 다음 `yield` 까지만 실행하고 멈춥니다.
+//
+// `yield` 가 없는 일반 함수도 같은 실행기로 끝까지 돌려 `return` 값을 얻습니다 (`run_to_completion`).
+
+use core::cell::RefCell;
+use alloc::rc::Rc;
+
+use crate::compat::*;
+use crate::data_structures::{Expression, Program, Statement, Value};
+use crate::ft_runtime::{Environment, HighEnduranceRuntime};
+use crate::interner::Symbol;
+use crate::ranges::Cursor;
+use crate::snapshot::{Capture, FrameImage, GeneratorImage, Rebuild};
+
+#[derive(Debug)]
+enum Frame {
+    Block {
+        statements: Vec<Statement>,
+        index: usize,
+        env: Rc<RefCell<Environment>>,
+        /// 이 블록에서 등록된 defer 본문 (블록을 빠져나갈 때 역순으로 실행)
+        deferred: Vec<Statement>,
+    },
+    While {
+        condition: Box<Expression>,
+        body: Box<Statement>,
+        env: Rc<RefCell<Environment>>,
+    },
+    For {
+        condition: Option<Box<Expression>>,
+        increment: Option<Box<Expression>>,
+        body: Box<Statement>,
+        started: bool,
+        env: Rc<RefCell<Environment>>,
+    },
+    ForIn {
+        variable: Symbol,
+        cursor: Cursor,
+        body: Box<Statement>,
+        env: Rc<RefCell<Environment>>,
+    },
+}
+
+#[derive(Debug)]
+pub struct GeneratorState {
+    frames: Vec<Frame>,
+    finished: bool,
+    /// `return` 으로 돌려준 값
+    returned: Option<Value>,
+}
+
+impl GeneratorState {
+    /// 함수 본문과 (매개변수가 바인딩된) 호출 환경으로 제너레이터를 만듭니다. 본문은 아직 실행되지 않습니다.
+    pub fn new(body: &Statement, env: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            frames: vec![Frame::Block { statements: block_statements(body), index: 0, env, deferred: vec![] }],
+            finished: false,
+            returned: None,
+        }
+    }
+
+    /// 이미 끝난 제너레이터. 스냅샷을 복원할 때 자리를 먼저 잡는 데 씁니다.
+    pub(crate) fn finished() -> Self {
+        Self { frames: vec![], finished: true, returned: None }
+    }
+
+    /// 멈춘 위치와 프레임 환경을 스냅샷 표에 담습니다.
+    pub(crate) fn to_image(&self, capture: &mut Capture) -> GeneratorImage {
+        let frames = self
+            .frames
+            .iter()
+            .map(|frame| match frame {
+                Frame::Block { statements, index, env, deferred } => FrameImage::Block {
+                    statements: statements.clone(),
+                    index: *index,
+                    env: capture.environment(env),
+                    deferred: deferred.clone(),
+                },
+                Frame::While { condition, body, env } => FrameImage::While {
+                    condition: condition.as_ref().clone(),
+                    body: body.as_ref().clone(),
+                    env: capture.environment(env),
+                },
+                Frame::For { condition, increment, body, started, env } => FrameImage::For {
+                    condition: condition.as_ref().map(|c| c.as_ref().clone()),
+                    increment: increment.as_ref().map(|i| i.as_ref().clone()),
+                    body: body.as_ref().clone(),
+                    started: *started,
+                    env: capture.environment(env),
+                },
+                Frame::ForIn { variable, cursor, body, env } => FrameImage::ForIn {
+                    variable: variable.as_str().to_string(),
+                    remaining: capture.value(&cursor.remaining()),
+                    body: body.as_ref().clone(),
+                    env: capture.environment(env),
+                },
+            })
+            .collect();
+        GeneratorImage { frames, finished: self.finished, returned: self.returned.as_ref().map(|v| capture.value(v)) }
+    }
+
+    pub(crate) fn from_image(image: &GeneratorImage, rebuild: &mut Rebuild) -> Result<Self, String> {
+        let mut frames = vec![];
+        for frame in &image.frames {
+            frames.push(match frame {
+                FrameImage::Block { statements, index, env, deferred } => Frame::Block {
+                    statements: statements.clone(),
+                    index: *index,
+                    env: rebuild.environment(*env)?,
+                    deferred: deferred.clone(),
+                },
+                FrameImage::While { condition, body, env } => Frame::While {
+                    condition: Box::new(condition.clone()),
+                    body: Box::new(body.clone()),
+                    env: rebuild.environment(*env)?,
+                },
+                FrameImage::For { condition, increment, body, started, env } => Frame::For {
+                    condition: condition.clone().map(Box::new),
+                    increment: increment.clone().map(Box::new),
+                    body: Box::new(body.clone()),
+                    started: *started,
+                    env: rebuild.environment(*env)?,
+                },
+                FrameImage::ForIn { variable, remaining, body, env } => Frame::ForIn {
+                    variable: Symbol::intern(variable),
+                    cursor: Cursor::new(&rebuild.value(remaining)?).ok_or("for-in 프레임이 돌 수 없는 값을 담고 있습니다.")?,
+                    body: Box::new(body.clone()),
+                    env: rebuild.environment(*env)?,
+                },
+            });
+        }
+        let returned = image.returned.as_ref().map(|v| rebuild.value(v)).transpose()?;
+        Ok(Self { frames, finished: image.finished, returned })
+    }
+
+    /// 일반 함수 본문을 끝까지 실행하고 `return` 값을 돌려줍니다. `return` 이 없으면 Null 입니다.
+    pub fn run_to_completion(mut self, rt: &mut HighEnduranceRuntime) -> Value {
+        while !self.finished {
+            self.resume(rt);
+        }
+        self.returned.unwrap_or(Value::Null)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 프레임이 붙잡고 있는 환경들 (heap.rs 의 수집기가 따라갑니다)
+    pub(crate) fn environments(&self) -> impl Iterator<Item = &Rc<RefCell<Environment>>> {
+        self.frames.iter().map(|frame| match frame {
+            Frame::Block { env, .. } | Frame::While { env, .. } | Frame::For { env, .. } | Frame::ForIn { env, .. } => env,
+        })
+    }
+
+    pub(crate) fn returned(&self) -> Option<&Value> {
+        self.returned.as_ref()
+    }
+
+    /// 프레임 스택이 차지하는 바이트 수 (추정)
+    pub(crate) fn frame_footprint(&self) -> usize {
+        self.frames.len() * core::mem::size_of::<Frame>()
+    }
+
+    /// 다음 `yield` 까지 실행하고 그 값을 돌려줍니다. 본문이 끝나면 None 입니다.
+    pub fn resume(&mut self, rt: &mut HighEnduranceRuntime) -> Option<Value> {
+        while !self.finished {
+            // 패닉이 나거나 취소되면 남은 문장을 실행하지 않고 defer 만 실행하며 빠져나갑니다.
+            if rt.check_cancelled() {
+                self.unwind(rt);
+                break;
+            }
+            let Some(frame) = self.frames.last_mut() else {
+                self.finished = true;
+                break;
+            };
+
+            match frame {
+                Frame::Block { statements, index, env, .. } => {
+                    let Some(stmt) = statements.get(*index).cloned() else {
+                        if let Some(frame) = self.frames.pop() {
+                            run_deferred(rt, frame);
+                        }
+                        continue;
+                    };
+                    *index += 1;
+                    let env = env.clone();
+                    if let Some(value) = self.step(rt, &stmt, env) {
+                        return Some(value);
+                    }
+                }
+                Frame::While { condition, body, env } => {
+                    let (condition, body, env) = (condition.clone(), body.clone(), env.clone());
+                    if is_true(&with_env(rt, &env, |rt| rt.evaluate_expression(&condition))) {
+                        rt.profile(|profile| profile.iterate(body.span()));
+                        self.push_block(&body, &env);
+                    } else {
+                        self.frames.pop();
+                    }
+                }
+                Frame::For { condition, increment, body, started, env } => {
+                    let env = env.clone();
+                    if *started {
+                        if let Some(inc) = increment.clone() {
+                            with_env(rt, &env, |rt| rt.evaluate_expression(&inc));
+                        }
+                    }
+                    *started = true;
+                    let (condition, body) = (condition.clone(), body.clone());
+                    let proceed = condition.is_none_or(|c| {
+                        is_true(&with_env(rt, &env, |rt| rt.evaluate_expression(&c)))
+                    });
+                    if proceed {
+                        rt.profile(|profile| profile.iterate(body.span()));
+                        self.push_block(&body, &env);
+                    } else {
+                        self.frames.pop();
+                    }
+                }
+                Frame::ForIn { variable, cursor, body, env } => {
+                    let (variable, body, env) = (*variable, body.clone(), env.clone());
+                    match cursor.next(rt) {
+                        Some(item) => {
+                            rt.profile(|profile| profile.iterate(body.span()));
+                            let mut loop_env = Environment::new_enclosed(env);
+                            loop_env.set(variable, item);
+                            self.push_block(&body, &Rc::new(RefCell::new(loop_env)));
+                        }
+                        None => {
+                            self.frames.pop();
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// 문장 하나를 실행합니다. 제어 흐름 문장은 새 프레임을 쌓고, `yield` 는 값을 돌려줍니다.
+    fn step(&mut self, rt: &mut HighEnduranceRuntime, stmt: &Statement, env: Rc<RefCell<Environment>>) -> Option<Value> {
+        // `other` 로 넘기는 문장은 execute_scope 가 세고 기록합니다.
+        let control = matches!(
+            stmt,
+            Statement::YieldStatement(..)
+                | Statement::ReturnStatement(..)
+                | Statement::DeferStatement(..)
+                | Statement::IfStatement { .. }
+                | Statement::WhileStatement { .. }
+                | Statement::ForStatement { .. }
+                | Statement::ForInStatement { .. }
+        );
+        if control {
+            rt.cover(stmt);
+        }
+        // 제어 흐름 문장의 사건은 값만 남기고 바로 닫습니다. 본문의 문장은 각자 기록됩니다.
+        let mark = if control { rt.trace_begin(stmt, &env) } else { None };
+        match stmt {
+            Statement::YieldStatement(_, expr) => {
+                let value = with_env(rt, &env, |rt| rt.evaluate_expression(expr));
+                rt.trace_end(mark, &env, Some(&value));
+                Some(value)
+            }
+            Statement::ReturnStatement(_, expr) => {
+                let value = with_env(rt, &env, |rt| rt.evaluate_expression(expr));
+                rt.trace_end(mark, &env, Some(&value));
+                self.returned = Some(value);
+                self.unwind(rt);
+                None
+            }
+            _ => {
+                rt.trace_end(mark, &env, None);
+                self.step_untraced(rt, stmt, env)
+            }
+        }
+    }
+
+    /// 기록을 마친 문장을 실행합니다.
+    fn step_untraced(&mut self, rt: &mut HighEnduranceRuntime, stmt: &Statement, env: Rc<RefCell<Environment>>) -> Option<Value> {
+        match stmt {
+            Statement::DeferStatement(_, body) => {
+                if let Some(Frame::Block { deferred, .. }) = self.frames.last_mut() {
+                    deferred.push(body.as_ref().clone());
+                }
+                None
+            }
+            Statement::BlockStatement { .. } => {
+                self.push_block(stmt, &env);
+                None
+            }
+            Statement::IfStatement { condition, then_branch, else_branch, span } => {
+                let taken = is_true(&with_env(rt, &env, |rt| rt.evaluate_expression(condition)));
+                rt.profile(|profile| profile.branch(*span, taken));
+                if taken {
+                    self.push_block(then_branch, &env);
+                } else if let Some(else_stmt) = else_branch {
+                    self.push_block(else_stmt, &env);
+                }
+                None
+            }
+            Statement::WhileStatement { condition, body, .. } => {
+                rt.profile(|profile| profile.enter_loop(body.span()));
+                self.frames.push(Frame::While { condition: condition.clone(), body: body.clone(), env });
+                None
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => {
+                rt.profile(|profile| profile.enter_loop(body.span()));
+                let loop_env = Rc::new(RefCell::new(Environment::new_enclosed(env)));
+                if let Some(init) = initializer {
+                    run_statement(rt, &loop_env, init);
+                }
+                self.frames.push(Frame::For {
+                    condition: condition.clone(),
+                    increment: increment.clone(),
+                    body: body.clone(),
+                    started: false,
+                    env: loop_env,
+                });
+                None
+            }
+            Statement::ForInStatement { variable, iterable, body, span } => {
+                let iterable = with_env(rt, &env, |rt| rt.evaluate_expression(iterable));
+                if let Some(cursor) = rt.iterate(&iterable, *span) {
+                    rt.profile(|profile| profile.enter_loop(body.span()));
+                    self.frames.push(Frame::ForIn { variable: *variable, cursor, body: body.clone(), env });
+                }
+                None
+            }
+            Statement::Attributed { statement, .. } => self.step(rt, statement, env),
+            other => {
+                run_statement(rt, &env, other);
+                None
+            }
+        }
+    }
+
+    /// 모든 프레임의 defer 를 안쪽부터 실행하고 끝냅니다. 진행 중인 패닉은 그대로 전파합니다.
+    fn unwind(&mut self, rt: &mut HighEnduranceRuntime) {
+        let pending = rt.panic.take();
+        while let Some(frame) = self.frames.pop() {
+            run_deferred(rt, frame);
+        }
+        if pending.is_some() {
+            rt.panic = pending;
+        }
+        self.finished = true;
+    }
+
+    fn push_block(&mut self, stmt: &Statement, outer: &Rc<RefCell<Environment>>) {
+        self.frames.push(Frame::Block {
+            statements: block_statements(stmt),
+            index: 0,
+            env: Rc::new(RefCell::new(Environment::new_enclosed(outer.clone()))),
+            deferred: vec![],
+        });
+    }
+}
+
+/// 문장 안에 `yield` 가 있으면 그 함수는 제너레이터 함수입니다. (중첩 함수 리터럴은 제외)
+pub fn contains_yield(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::YieldStatement(_, _) => true,
+        Statement::BlockStatement { statements, .. } => statements.iter().any(contains_yield),
+        Statement::IfStatement { then_branch, else_branch, .. } => {
+            contains_yield(then_branch) || else_branch.as_ref().is_some_and(|e| contains_yield(e))
+        }
+        Statement::WhileStatement { body, .. }
+        | Statement::ForStatement { body, .. }
+        | Statement::ForInStatement { body, .. } => contains_yield(body),
+        Statement::Attributed { statement, .. } => contains_yield(statement),
+        _ => false,
+    }
+}
+
+fn block_statements(stmt: &Statement) -> Vec<Statement> {
+    match stmt {
+        Statement::BlockStatement { statements, .. } => statements.clone(),
+        other => vec![other.clone()],
+    }
+}
+
+fn is_true(value: &Value) -> bool {
+    matches!(value, Value::Boolean(true))
+}
+
+/// 런타임의 현재 환경을 잠시 제너레이터 프레임의 환경으로 바꿔서 실행합니다.
+fn with_env<T>(rt: &mut HighEnduranceRuntime, env: &Rc<RefCell<Environment>>, f: impl FnOnce(&mut HighEnduranceRuntime) -> T) -> T {
+    let saved = core::mem::replace(&mut rt.environment, env.clone());
+    let result = f(rt);
+    rt.environment = saved;
+    result
+}
+
+/// 빠져나가는 블록 프레임의 defer 본문을 등록 역순으로 실행합니다.
+fn run_deferred(rt: &mut HighEnduranceRuntime, frame: Frame) {
+    if let Frame::Block { env, deferred, .. } = frame {
+        for body in deferred.into_iter().rev() {
+            run_statement(rt, &env, &body);
+        }
+    }
+}
+
+fn run_statement(rt: &mut HighEnduranceRuntime, env: &Rc<RefCell<Environment>>, stmt: &Statement) {
+    with_env(rt, env, |rt| {
+        let _ = rt.execute_program(Program {
+            root_id: 0,
+            statements: vec![stmt.clone()],
+            span: stmt.span(),
+            attributes: vec![],
+        });
+    });
+}
@@ -0,0 +1,288 @@
+// heap.rs
+// 런타임 힙의 순환 수집기(추적 GC)와 그 통계입니다.
+//
+// 값은 `Rc` 로 공유되므로 대부분은 참조 수가 0 이 되는 순간 해제됩니다. 남는 것은 순환입니다.
+// 멈춘 제너레이터의 프레임은 자기 호출 환경을 붙잡고, 그 환경(또는 바깥의 전역 환경)이 다시 그
+// 제너레이터를 담으면 서로를 붙잡아 해제되지 않습니다. 환경끼리는 `outer` 로만 이어지고 값 중에
+// 환경을 붙잡는 것은 제너레이터뿐이므로, 모든 순환은 제너레이터를 하나 이상 지납니다.
+//
+// 그래서 힙은 만들어진 제너레이터를 약한 참조로 기억해 두고, 수집할 때
+//   1. 표시: 지금 환경(과 그 바깥)에서 닿는 환경과 제너레이터를 표시합니다.
+//   2. 시험 삭제: 표시되지 않은 제너레이터에서 닿는 부분 그래프의 내부 참조를 셉니다. 강한 참조가
+//      그보다 많은 노드는 호스트나 Rust 스택(실행 중인 호출)이 들고 있으므로 그 노드에서 닿는 것까지 살립니다.
+//   3. 쓸기: 남은 제너레이터의 프레임과 반환 값을 비워 순환을 끊습니다. 환경은 그 뒤 `Rc` 가 해제합니다.
+//
+// 회수한 바이트 수는 구조체 크기로 계산한 추정치이며, 멈춘 시간은 `std` 기능이 없으면 0 입니다.
+//
+// 약한 참조와 finalizer
+//   `weak_ref(g)` 는 제너레이터를 살려 두지 않는 참조를 만들고, `deref(w)` 는 대상이 살아 있으면 그 값,
+//   해제되었으면 null 입니다. 식별성이 있는 힙 값은 제너레이터뿐이므로 다른 값(복사되는 값)에는 쓸 수 없습니다.
+//   `on_finalize(g, f)` 는 g 가 해제된 뒤 인자 없는 함수 f 를 한 번 부릅니다. f 는 g 를 받지 않습니다(이미 없음).
+//   - finalizer 는 안전한 지점에서만 돕니다: `gc()` 호출 직후, `Engine::collect_garbage` 직후, 그리고
+//     `Engine::run` 이 끝날 때(프로그램 종료). 종료 때는 finalizer 가 남아 있으면 먼저 수집을 한 번 합니다.
+//     생성 중의 자동 수집은 회수만 하고 finalizer 를 부르지 않으므로 식 한가운데서 사용자 코드가 끼어들지 않습니다.
+//   - 종료 때까지 살아 있는 값의 finalizer 는 부르지 않습니다.
+//   - 패닉이 났거나 취소(제한 시간 초과 포함)된 실행에서는 finalizer 를 부르지 않고 남겨 둡니다. finalizer 도
+//     같은 취소 토큰 아래에서 돌므로 실행 예산을 넘겨 돌 수 없으며, 같은 Engine 을 다시 쓰면 다음 안전한 지점에서 돕니다.
+//   - 등록 순서대로 부르며, finalizer 의 패닉은 그 실행의 패닉입니다.
+
+use alloc::rc::{Rc, Weak};
+use core::cell::RefCell;
+use core::mem::size_of;
+
+use crate::compat::*;
+use crate::data_structures::{FunctionValue, Value};
+use crate::ft_runtime::Environment;
+use crate::generator::GeneratorState;
+use crate::interner::Symbol;
+
+/// 이만큼 새 제너레이터를 만들면 자동으로 수집합니다. 수집 뒤에는 살아 있는 수의 두 배로 늘어납니다.
+pub const DEFAULT_THRESHOLD: usize = 1024;
+
+type EnvRef = Rc<RefCell<Environment>>;
+type GenRef = Rc<RefCell<GeneratorState>>;
+
+/// 실행 동안의 누적 수집 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    /// 수집 횟수 (`gc()` 와 자동 수집 모두)
+    pub collections: u64,
+    /// 회수한 환경과 제너레이터 수
+    pub objects_reclaimed: u64,
+    /// 회수한 바이트 수 (추정)
+    pub bytes_reclaimed: u64,
+    pub total_pause_ns: u64,
+    pub max_pause_ns: u64,
+    /// 지금 추적 중인(살아 있는) 제너레이터 수
+    pub tracked: usize,
+    pub finalizers_run: u64,
+}
+
+/// 수집 한 번의 결과
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectionReport {
+    pub objects: u64,
+    pub bytes: u64,
+    pub pause_ns: u64,
+}
+
+pub struct Heap {
+    generators: Vec<Weak<RefCell<GeneratorState>>>,
+    allocated_since_collection: usize,
+    threshold: usize,
+    stats: GcStats,
+    /// 대상과 그 대상이 해제된 뒤 부를 함수 (등록 순서)
+    finalizers: Vec<(Weak<RefCell<GeneratorState>>, FunctionValue)>,
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Self {
+            generators: vec![],
+            allocated_since_collection: 0,
+            threshold: DEFAULT_THRESHOLD,
+            stats: GcStats::default(),
+            finalizers: vec![],
+        }
+    }
+}
+
+impl Heap {
+    /// 새 제너레이터를 추적합니다. 자동 수집할 때가 되었으면 참입니다.
+    pub fn track(&mut self, generator: &GenRef) -> bool {
+        self.generators.push(Rc::downgrade(generator));
+        self.allocated_since_collection += 1;
+        self.allocated_since_collection >= self.threshold
+    }
+
+    /// `root` 에서 닿는 제너레이터 중 아직 추적하지 않은 것을 추적합니다. (스냅샷 복원 뒤)
+    pub fn adopt(&mut self, root: &EnvRef) {
+        let known: HashMap<usize, ()> = self.generators.iter().map(|g| (g.as_ptr() as usize, ())).collect();
+        let mut reached = Graph::default();
+        reached.visit(Node::Env(root.clone()), &HashMap::new());
+        for node in reached.nodes {
+            if let Node::Gen(generator) = node {
+                if !known.contains_key(&(Rc::as_ptr(&generator) as usize)) {
+                    self.generators.push(Rc::downgrade(&generator));
+                }
+            }
+        }
+    }
+
+    pub fn on_finalize(&mut self, target: &GenRef, finalizer: FunctionValue) {
+        self.finalizers.push((Rc::downgrade(target), finalizer));
+    }
+
+    pub fn has_finalizers(&self) -> bool {
+        !self.finalizers.is_empty()
+    }
+
+    /// 대상이 해제된 finalizer 중 가장 먼저 등록된 것을 꺼냅니다.
+    pub fn next_due_finalizer(&mut self) -> Option<FunctionValue> {
+        let index = self.finalizers.iter().position(|(target, _)| target.strong_count() == 0)?;
+        self.stats.finalizers_run += 1;
+        Some(self.finalizers.remove(index).1)
+    }
+
+    pub fn stats(&self) -> GcStats {
+        GcStats { tracked: self.generators.iter().filter(|g| g.strong_count() > 0).count(), ..self.stats }
+    }
+
+    /// `root` 를 지금 환경으로 보고 순환 쓰레기를 회수합니다.
+    pub fn collect(&mut self, root: &EnvRef) -> CollectionReport {
+        #[cfg(feature = "std")]
+        let started = std::time::Instant::now();
+
+        // 1. 표시
+        let mut marked = Graph::default();
+        marked.visit(Node::Env(root.clone()), &HashMap::new());
+        let marked = marked.index;
+
+        // 2. 시험 삭제: 표시되지 않은 살아 있는 제너레이터에서 시작하는 부분 그래프
+        let mut graph = Graph::default();
+        for generator in self.generators.iter().filter_map(Weak::upgrade) {
+            if !marked.contains_key(&(Rc::as_ptr(&generator) as usize)) {
+                graph.visit(Node::Gen(generator), &marked);
+            }
+        }
+        let mut internal = vec![0usize; graph.nodes.len()];
+        for edges in graph.edges.iter().flatten() {
+            for &target in edges {
+                internal[target] += 1;
+            }
+        }
+        // 그래프가 노드마다 하나씩 들고 있는 참조를 뺍니다. 빌려 쓰는 중이라 들여다볼 수 없는 노드는 살립니다.
+        let mut live: Vec<usize> = (0..graph.nodes.len())
+            .filter(|&i| graph.edges[i].is_none() || graph.nodes[i].strong_count() - 1 > internal[i])
+            .collect();
+        let mut alive = vec![false; graph.nodes.len()];
+        while let Some(i) = live.pop() {
+            if !core::mem::replace(&mut alive[i], true) {
+                live.extend(graph.edges[i].iter().flatten().copied());
+            }
+        }
+
+        // 3. 쓸기
+        let mut report = CollectionReport::default();
+        for (node, _) in graph.nodes.iter().zip(&alive).filter(|(_, alive)| !**alive) {
+            report.objects += 1;
+            report.bytes += node.footprint() as u64;
+            if let Node::Gen(generator) = node {
+                // 비운 상태를 빌림 밖에서 버립니다. 그래프가 모든 노드를 들고 있으므로 여기서 해제되는 노드는 없습니다.
+                let cleared = core::mem::replace(&mut *generator.borrow_mut(), GeneratorState::finished());
+                drop(cleared);
+            }
+        }
+        drop(graph);
+        self.generators.retain(|g| g.strong_count() > 0);
+
+        #[cfg(feature = "std")]
+        {
+            report.pause_ns = started.elapsed().as_nanos() as u64;
+        }
+        self.stats.collections += 1;
+        self.stats.objects_reclaimed += report.objects;
+        self.stats.bytes_reclaimed += report.bytes;
+        self.stats.total_pause_ns += report.pause_ns;
+        self.stats.max_pause_ns = self.stats.max_pause_ns.max(report.pause_ns);
+        self.allocated_since_collection = 0;
+        self.threshold = DEFAULT_THRESHOLD.max(self.generators.len() * 2);
+        report
+    }
+}
+
+enum Node {
+    Env(EnvRef),
+    Gen(GenRef),
+}
+
+impl Node {
+    fn key(&self) -> usize {
+        match self {
+            Node::Env(env) => Rc::as_ptr(env) as usize,
+            Node::Gen(generator) => Rc::as_ptr(generator) as usize,
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Node::Env(env) => Rc::strong_count(env),
+            Node::Gen(generator) => Rc::strong_count(generator),
+        }
+    }
+
+    /// 이 노드가 직접 가리키는 환경과 제너레이터. 지금 빌려 쓰는 중이면 None 입니다.
+    fn children(&self) -> Option<Vec<Node>> {
+        let mut children = vec![];
+        match self {
+            Node::Env(env) => {
+                let env = env.try_borrow().ok()?;
+                children.extend(env.outer.clone().map(Node::Env));
+                for value in env.store.values().chain(env.slots.iter().flatten()) {
+                    value_children(value, &mut children);
+                }
+            }
+            Node::Gen(generator) => {
+                let generator = generator.try_borrow().ok()?;
+                children.extend(generator.environments().cloned().map(Node::Env));
+                if let Some(value) = generator.returned() {
+                    value_children(value, &mut children);
+                }
+            }
+        }
+        Some(children)
+    }
+
+    fn footprint(&self) -> usize {
+        match self {
+            Node::Env(env) => env.try_borrow().map_or(size_of::<Environment>(), |env| {
+                size_of::<Environment>()
+                    + env.store.len() * (size_of::<Symbol>() + size_of::<Value>())
+                    + env.slots.len() * size_of::<Option<Value>>()
+            }),
+            Node::Gen(generator) => {
+                size_of::<GeneratorState>() + generator.try_borrow().map_or(0, |g| g.frame_footprint())
+            }
+        }
+    }
+}
+
+fn value_children(value: &Value, children: &mut Vec<Node>) {
+    match value {
+        Value::Generator(generator) => children.push(Node::Gen(generator.clone())),
+        Value::Return(inner) => value_children(inner, children),
+        _ => {}
+    }
+}
+
+/// 방문한 노드와 간선. 노드마다 강한 참조를 하나씩 들고 있습니다.
+#[derive(Default)]
+struct Graph {
+    index: HashMap<usize, usize>,
+    nodes: Vec<Node>,
+    /// 노드별 자식 번호. 들여다볼 수 없던 노드는 None 입니다.
+    edges: Vec<Option<Vec<usize>>>,
+}
+
+impl Graph {
+    /// `start` 에서 닿는 노드를 모두 넣습니다. `stop` 에 있는 노드로는 들어가지 않습니다.
+    fn visit(&mut self, start: Node, stop: &HashMap<usize, usize>) {
+        let mut pending = vec![start];
+        let mut child_keys: Vec<Option<Vec<usize>>> = vec![];
+        while let Some(node) = pending.pop() {
+            let key = node.key();
+            if stop.contains_key(&key) || self.index.contains_key(&key) {
+                continue;
+            }
+            self.index.insert(key, self.nodes.len());
+            let children = node.children();
+            child_keys.push(children.as_ref().map(|children| children.iter().map(Node::key).collect()));
+            pending.extend(children.into_iter().flatten());
+            self.nodes.push(node);
+        }
+        // `stop` 에 있는 자식(표시된 노드)으로 가는 간선은 세지 않습니다.
+        for keys in child_keys {
+            self.edges.push(keys.map(|keys| keys.iter().filter_map(|key| self.index.get(key).copied()).collect()));
+        }
+    }
+}
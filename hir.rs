@@ -0,0 +1,524 @@
+// hir.rs
+// 고수준 중간 표현(HIR): 파서 출력과 IR 사이의 단계입니다.
+//
+// 파스 트리를 더 작은 노드 집합으로 내리고(lowering), 모든 식에 타입을 붙입니다.
+//   - 매크로는 확장되고, 매크로가 아닌 `name(args)` 는 일반 호출이 됩니다.
+//   - 삼항식 `c ? a : b` 는 if 식이 됩니다.
+//   - `for (init; cond; inc) body` 는 `{ init; while cond { body; inc } }` 가 됩니다.
+//   - `match s { p if g => e, ... }` 는 대상을 `match` 매개변수로 받아 arm 을 차례로 검사하는 함수를
+//     곧바로 부르는 식이 됩니다. 일치하는 arm 이 없으면 null 을 돌려줍니다. (`match` 는 키워드이므로 사용자
+//     이름과 겹치지 않습니다)
+//   - 괄호 식은 사라지고, 어트리뷰트는 앞 단계(@cfg, @inline, @test 등)에서 이미 쓰였으므로 벗겨냅니다.
+//   - if·while·defer 의 본문은 항상 블록입니다.
+//
+// 타입은 리터럴, 바인딩, 연산자에서 지역적으로 추론합니다. 연산자 결과 타입은 런타임 연산자 의미
+// (`operators`)에 표본 값을 넣어 구하므로 실행 결과와 어긋나지 않습니다. 알 수 없으면 `Type::Unknown` 입니다.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::data_structures::{self, Expression, MatchArm, Pattern, Span, Statement, TokenKind, TypeAnnotation, Value};
+use crate::formatting::display_value;
+use crate::interner::Symbol;
+use crate::macro_expander::MacroExpander;
+use crate::named_types::NamedTypes;
+use crate::operators;
+use crate::patterns;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    Bool,
+    String,
+    Null,
+    /// 매개변수 개수
+    Function(usize),
+    Unknown,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Bool => write!(f, "bool"),
+            Type::String => write!(f, "string"),
+            Type::Null => write!(f, "null"),
+            Type::Function(arity) => write!(f, "fn/{}", arity),
+            Type::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub ty: Type,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExprKind {
+    Literal(Value),
+    Variable(Symbol),
+    Unary(TokenKind<'static>, Box<Expr>),
+    Binary(TokenKind<'static>, Box<Expr>, Box<Expr>),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Function(Vec<Symbol>, Block),
+    Call(Box<Expr>, Vec<Expr>),
+    Array(Vec<Expr>),
+    Map(Vec<(Expr, Expr)>),
+    Reflect(Box<Expr>),
+    Eval(Box<Expr>),
+    TypeOf(Box<Expr>),
+    Index(Box<Expr>, Box<Expr>),
+    Slice(Box<Expr>, Option<Box<Expr>>, Option<Box<Expr>>),
+    /// `name = value`. 복합 대입 `name += value` 는 `name = name + value` 로 풉니다.
+    Assign(Symbol, Box<Expr>),
+    /// `start..end`. true 면 `start..=end` 입니다.
+    Range(Box<Expr>, Box<Expr>, bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub statements: Vec<Stmt>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum Stmt {
+    Expr(Expr),
+    Let { name: Symbol, mutable: bool, value: Expr, span: Span },
+    Return(Expr, Span),
+    Yield(Expr, Span),
+    Defer(Block, Span),
+    Block(Block),
+    If { condition: Expr, then_branch: Block, else_branch: Option<Block>, span: Span },
+    While { condition: Expr, body: Block, span: Span },
+    /// `for variable in iterable`. 백엔드는 아직 지원하지 않습니다.
+    ForIn { variable: Symbol, iterable: Expr, body: Block, span: Span },
+    /// enum 선언. 변형 이름들을 바인딩하며 백엔드는 아직 지원하지 않습니다.
+    Enum { name: Symbol, variants: Vec<Symbol>, span: Span },
+}
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub statements: Vec<Stmt>,
+    pub span: Span,
+}
+
+/// 파스 트리를 HIR 로 내립니다. 매크로 확장에 실패하면 그 오류들을 돌려줍니다.
+pub fn lower(program: &data_structures::Program) -> Result<Program, Vec<String>> {
+    let mut expanded = program.clone();
+    MacroExpander::expand(&mut expanded)?;
+    let mut lowerer = Lowerer { scopes: vec![HashMap::new()], types: NamedTypes::collect(&expanded) };
+    let statements = expanded.statements.iter().map(|s| lowerer.statement(s)).collect();
+    Ok(Program { statements, span: program.span })
+}
+
+struct Lowerer {
+    /// 바인딩 이름 → 추론된 타입 (바깥 스코프부터)
+    scopes: Vec<HashMap<Symbol, Type>>,
+    /// `type` 별칭. 해석하지 못한 표기는 `Type::Unknown` 이 됩니다. (named_types 가 따로 진단합니다)
+    types: NamedTypes,
+}
+
+impl Lowerer {
+    fn scoped<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.scopes.push(HashMap::new());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn lookup(&self, name: Symbol) -> Type {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name)).cloned().unwrap_or(Type::Unknown)
+    }
+
+    fn bind(&mut self, name: Symbol, ty: Type) {
+        self.scopes.last_mut().expect("최상위 스코프가 있어야 합니다").insert(name, ty);
+    }
+
+    // ─── 문장 ─────────────────────────────
+
+    fn statement(&mut self, stmt: &Statement) -> Stmt {
+        match stmt {
+            Statement::ExpressionStatement(expr) | Statement::DiscardStatement(_, expr) => Stmt::Expr(self.expression(expr)),
+            Statement::LetStatement { name, value, type_annotation, is_mutable, span } => {
+                let value = self.expression(value);
+                // 값에서 추론한 타입이 우선이고, 추론하지 못했을 때만 타입 표기를 믿습니다.
+                let ty = match &value.ty {
+                    Type::Unknown => type_annotation.as_ref().map_or(Type::Unknown, |a| self.annotation_type(a)),
+                    known => known.clone(),
+                };
+                self.bind(*name, ty);
+                Stmt::Let { name: *name, mutable: *is_mutable, value, span: *span }
+            }
+            Statement::ReturnStatement(span, expr) => Stmt::Return(self.expression(expr), *span),
+            Statement::YieldStatement(span, expr) => Stmt::Yield(self.expression(expr), *span),
+            Statement::DeferStatement(span, body) => Stmt::Defer(self.block(body), *span),
+            Statement::BlockStatement { .. } => Stmt::Block(self.block(stmt)),
+            Statement::IfStatement { condition, then_branch, else_branch, span } => Stmt::If {
+                condition: self.expression(condition),
+                then_branch: self.block(then_branch),
+                else_branch: else_branch.as_ref().map(|e| self.block(e)),
+                span: *span,
+            },
+            Statement::WhileStatement { condition, body, span } => Stmt::While {
+                condition: self.expression(condition),
+                body: self.block(body),
+                span: *span,
+            },
+            Statement::ForStatement { initializer, condition, increment, body, span } => self.scoped(|l| {
+                let mut statements = vec![];
+                if let Some(init) = initializer {
+                    statements.push(l.statement(init));
+                }
+                let condition = match condition {
+                    Some(cond) => l.expression(cond),
+                    None => Expr { kind: ExprKind::Literal(Value::Boolean(true)), ty: Type::Bool, span: *span },
+                };
+                let mut body = l.block(body);
+                if let Some(inc) = increment {
+                    body.statements.push(Stmt::Expr(l.expression(inc)));
+                }
+                statements.push(Stmt::While { condition, body, span: *span });
+                Stmt::Block(Block { statements, span: *span })
+            }),
+            Statement::ForInStatement { variable, iterable, body, span } => {
+                let iterable = self.expression(iterable);
+                let body = self.scoped(|l| {
+                    l.bind(*variable, if matches!(iterable.kind, ExprKind::Range(..)) { Type::Int } else { Type::Unknown });
+                    l.block(body)
+                });
+                Stmt::ForIn { variable: *variable, iterable, body, span: *span }
+            }
+            Statement::Attributed { statement, .. } => self.statement(statement),
+            // 확장기가 정의를 모두 지우므로 여기까지 오지 않습니다.
+            Statement::MacroDefinition { span, .. } => Stmt::Block(Block { statements: vec![], span: *span }),
+            // 별칭은 `types` 에 미리 모아 두었습니다.
+            Statement::TypeAlias { span, .. } => Stmt::Block(Block { statements: vec![], span: *span }),
+            Statement::EnumDeclaration { name, variants, span } => {
+                let variants: Vec<Symbol> = variants.iter().map(|v| v.name).collect();
+                for variant in &variants {
+                    self.bind(*variant, Type::Unknown);
+                }
+                Stmt::Enum { name: *name, variants, span: *span }
+            }
+        }
+    }
+
+    /// 문장을 블록으로 내립니다. 블록이 아닌 문장은 한 문장짜리 블록으로 감쌉니다.
+    fn block(&mut self, stmt: &Statement) -> Block {
+        self.scoped(|l| match stmt {
+            Statement::BlockStatement { statements, span } => {
+                Block { statements: statements.iter().map(|s| l.statement(s)).collect(), span: *span }
+            }
+            other => Block { statements: vec![l.statement(other)], span: other.span() },
+        })
+    }
+
+    // ─── 식 ─────────────────────────────
+
+    fn expression(&mut self, expr: &Expression) -> Expr {
+        let span = expr.span();
+        let typed = |kind: ExprKind, ty: Type| Expr { kind, ty, span };
+        match expr {
+            Expression::Literal(_, value) => typed(ExprKind::Literal(value.clone()), value_type(value)),
+            Expression::Identifier(_, name) => typed(ExprKind::Variable(*name), self.lookup(*name)),
+            Expression::Grouped(_, inner) => Expr { span, ..self.expression(inner) },
+            Expression::PrefixOperation(_, op, operand) => {
+                let operand = self.expression(operand);
+                let ty = sample(&operand.ty)
+                    .and_then(|v| operators::unary(op, &v).ok())
+                    .map_or(Type::Unknown, |v| value_type(&v));
+                typed(ExprKind::Unary(op.clone(), Box::new(operand)), ty)
+            }
+            Expression::InfixOperation(_, op, left, right) => {
+                let (left, right) = (self.expression(left), self.expression(right));
+                let ty = match (sample(&left.ty), sample(&right.ty)) {
+                    (Some(l), Some(r)) => operators::binary(op, &l, &r).map_or(Type::Unknown, |v| value_type(&v)),
+                    _ => Type::Unknown,
+                };
+                typed(ExprKind::Binary(op.clone(), Box::new(left), Box::new(right)), ty)
+            }
+            Expression::Ternary(_, cond, then_expr, else_expr) => {
+                let (cond, then_expr, else_expr) =
+                    (self.expression(cond), self.expression(then_expr), self.expression(else_expr));
+                let ty = if then_expr.ty == else_expr.ty { then_expr.ty.clone() } else { Type::Unknown };
+                typed(ExprKind::If(Box::new(cond), Box::new(then_expr), Box::new(else_expr)), ty)
+            }
+            Expression::Function(_, parameters, body) => {
+                let body = self.scoped(|l| {
+                    for p in parameters {
+                        l.bind(*p, Type::Unknown);
+                    }
+                    l.block(body)
+                });
+                typed(ExprKind::Function(parameters.clone(), body), Type::Function(parameters.len()))
+            }
+            Expression::Call(_, callee, args) => {
+                let callee = self.expression(callee);
+                let args = args.iter().map(|a| self.expression(a)).collect();
+                typed(ExprKind::Call(Box::new(callee), args), Type::Unknown)
+            }
+            // 확장 뒤에 남은 `name(args)` 는 매크로가 아닌 함수 호출입니다.
+            Expression::MacroCall(_, name, args) => {
+                let name = Symbol::intern(name);
+                let callee = typed(ExprKind::Variable(name), self.lookup(name));
+                let args = args.iter().map(|a| self.expression(a)).collect();
+                typed(ExprKind::Call(Box::new(callee), args), Type::Unknown)
+            }
+            Expression::Reflect(_, inner) => typed(ExprKind::Reflect(Box::new(self.expression(inner))), Type::Unknown),
+            Expression::Eval(_, inner) => typed(ExprKind::Eval(Box::new(self.expression(inner))), Type::Unknown),
+            Expression::TypeOf(_, inner) => typed(ExprKind::TypeOf(Box::new(self.expression(inner))), Type::Unknown),
+            Expression::ArrayLiteral(_, elements) => {
+                let elements = elements.iter().map(|e| self.expression(e)).collect();
+                typed(ExprKind::Array(elements), Type::Unknown)
+            }
+            Expression::MapLiteral(_, entries) => {
+                let entries = entries.iter().map(|(key, value)| (self.expression(key), self.expression(value))).collect();
+                typed(ExprKind::Map(entries), Type::Unknown)
+            }
+            Expression::Index(_, target, index) => {
+                let (target, index) = (self.expression(target), self.expression(index));
+                typed(ExprKind::Index(Box::new(target), Box::new(index)), Type::Unknown)
+            }
+            // `a.b` 는 `a["b"]`, `a.b(c)` 는 `b(a, c)` 입니다.
+            Expression::Member(_, target, name) => {
+                let target = self.expression(target);
+                let field = typed(ExprKind::Literal(Value::String(name.as_str().into())), Type::String);
+                typed(ExprKind::Index(Box::new(target), Box::new(field)), Type::Unknown)
+            }
+            Expression::MethodCall(_, target, name, args) => {
+                let callee = typed(ExprKind::Variable(*name), self.lookup(*name));
+                let args = core::iter::once(target).chain(args).map(|a| self.expression(a)).collect();
+                typed(ExprKind::Call(Box::new(callee), args), Type::Unknown)
+            }
+            Expression::Slice(_, target, start, end) => {
+                let target = self.expression(target);
+                let start = start.as_ref().map(|s| Box::new(self.expression(s)));
+                let end = end.as_ref().map(|e| Box::new(self.expression(e)));
+                typed(ExprKind::Slice(Box::new(target), start, end), Type::Unknown)
+            }
+            Expression::Range(_, start, end, inclusive) => {
+                let (start, end) = (self.expression(start), self.expression(end));
+                typed(ExprKind::Range(Box::new(start), Box::new(end), *inclusive), Type::Unknown)
+            }
+            Expression::Match(_, subject, arms) => self.match_expression(subject, arms, span),
+            Expression::Assign(_, op, name, value) => {
+                let mut value = self.expression(value);
+                let op = match op {
+                    TokenKind::PlusAssign => Some(TokenKind::Plus),
+                    TokenKind::MinusAssign => Some(TokenKind::Minus),
+                    _ => None,
+                };
+                if let Some(op) = op {
+                    let current = typed(ExprKind::Variable(*name), self.lookup(*name));
+                    value = typed(ExprKind::Binary(op, Box::new(current), Box::new(value)), Type::Unknown);
+                }
+                let ty = value.ty.clone();
+                typed(ExprKind::Assign(*name, Box::new(value)), ty)
+            }
+        }
+    }
+
+    fn match_expression(&mut self, subject: &Expression, arms: &[MatchArm], span: Span) -> Expr {
+        let subject = self.expression(subject);
+        let parameter = Symbol::intern("match");
+        let (statements, ty) = self.scoped(|l| {
+            l.bind(parameter, subject.ty.clone());
+            let target = Expr { kind: ExprKind::Variable(parameter), ty: subject.ty.clone(), span };
+            let mut statements = vec![];
+            let mut types = vec![];
+            let mut exhaustive = false;
+            for arm in arms {
+                let test = pattern_test(&arm.pattern, &target);
+                let mut body = l.scoped(|l| {
+                    let mut body = vec![];
+                    for name in patterns::bindings(&arm.pattern) {
+                        l.bind(name, target.ty.clone());
+                        body.push(Stmt::Let { name, mutable: false, value: target.clone(), span: arm.span });
+                    }
+                    let guard = arm.guard.as_ref().map(|g| l.expression(g));
+                    let value = l.expression(&arm.body);
+                    types.push(value.ty.clone());
+                    let result = Stmt::Return(value, arm.span);
+                    body.push(match guard {
+                        Some(condition) => Stmt::If {
+                            condition,
+                            then_branch: Block { statements: vec![result], span: arm.span },
+                            else_branch: None,
+                            span: arm.span,
+                        },
+                        None => result,
+                    });
+                    body
+                });
+                match test {
+                    Some(condition) => statements.push(Stmt::If {
+                        condition,
+                        then_branch: Block { statements: body, span: arm.span },
+                        else_branch: None,
+                        span: arm.span,
+                    }),
+                    None => {
+                        let guarded = arm.guard.is_some();
+                        statements.append(&mut body);
+                        // 무조건 일치하는 arm 뒤의 arm 은 실행되지 않습니다. (E0070)
+                        if !guarded {
+                            exhaustive = true;
+                            break;
+                        }
+                    }
+                }
+            }
+            if !exhaustive {
+                let null = Expr { kind: ExprKind::Literal(Value::Null), ty: Type::Null, span };
+                statements.push(Stmt::Return(null, span));
+                types.push(Type::Null);
+            }
+            let ty = match types.split_first() {
+                Some((first, rest)) if rest.iter().all(|t| t == first) => first.clone(),
+                _ => Type::Unknown,
+            };
+            (statements, ty)
+        });
+        let function = Expr {
+            kind: ExprKind::Function(vec![parameter], Block { statements, span }),
+            ty: Type::Function(1),
+            span,
+        };
+        Expr { kind: ExprKind::Call(Box::new(function), vec![subject]), ty, span }
+    }
+}
+
+/// 패턴이 `target` 과 일치하는지 검사하는 조건식. 항상 일치하면 None 입니다.
+/// 다른 타입끼리의 `==` 는 타입 오류이므로 타입을 모르는 대상은 `typeof` 를 먼저 비교합니다.
+fn pattern_test(pattern: &Pattern, target: &Expr) -> Option<Expr> {
+    let span = target.span;
+    let boolean = |kind: ExprKind| Expr { kind, ty: Type::Bool, span };
+    let binary = |op: TokenKind<'static>, left: Expr, right: Expr| boolean(ExprKind::Binary(op, Box::new(left), Box::new(right)));
+    let literal = |value: Value| Expr { ty: value_type(&value), kind: ExprKind::Literal(value), span };
+    let type_test = |ty: Type, value: Expr| {
+        if target.ty == ty {
+            return value;
+        }
+        let type_of = Expr { kind: ExprKind::TypeOf(Box::new(target.clone())), ty: Type::Unknown, span };
+        let is_type = binary(TokenKind::Eq, type_of, literal(Value::String(ty.to_string().into())));
+        binary(TokenKind::And, is_type, value)
+    };
+    match pattern {
+        Pattern::Wildcard | Pattern::Binding(_) => None,
+        Pattern::Literal(Value::Null) if target.ty == Type::Null => None,
+        // `typeof null` 은 타입 표기와 같은 "void" 입니다.
+        Pattern::Literal(Value::Null) => {
+            let type_of = Expr { kind: ExprKind::TypeOf(Box::new(target.clone())), ty: Type::Unknown, span };
+            Some(binary(TokenKind::Eq, type_of, literal(Value::String("void".into()))))
+        }
+        Pattern::Literal(value) => {
+            let equal = binary(TokenKind::Eq, target.clone(), literal(value.clone()));
+            Some(type_test(value_type(value), equal))
+        }
+        Pattern::Range { start, end, inclusive } => {
+            let lower = binary(TokenKind::GreaterEqual, target.clone(), literal(Value::Integer(*start)));
+            let op = if *inclusive { TokenKind::LessEqual } else { TokenKind::Less };
+            let upper = binary(op, target.clone(), literal(Value::Integer(*end)));
+            Some(type_test(Type::Int, binary(TokenKind::And, lower, upper)))
+        }
+        Pattern::Or(alternatives) => {
+            let tests: Option<Vec<Expr>> = alternatives.iter().map(|alt| pattern_test(alt, target)).collect();
+            tests?.into_iter().reduce(|left, right| binary(TokenKind::Or, left, right))
+        }
+        // enum 값은 `Stmt::Enum` 이 있어야 생기고 백엔드가 그 선언을 거부하므로 일치할 값이 없습니다.
+        Pattern::Variant(..) => Some(literal(Value::Boolean(false))),
+    }
+}
+
+pub(crate) fn value_type(value: &Value) -> Type {
+    match value {
+        Value::Integer(_) => Type::Int,
+        Value::Float(_) => Type::Float,
+        Value::Boolean(_) => Type::Bool,
+        Value::String(_) => Type::String,
+        Value::Null => Type::Null,
+        Value::Function(f) => Type::Function(f.parameters.len()),
+        _ => Type::Unknown,
+    }
+}
+
+/// 연산자 결과 타입을 구하는 데 쓰는 타입별 표본 값
+fn sample(ty: &Type) -> Option<Value> {
+    match ty {
+        Type::Int => Some(Value::Integer(1)),
+        Type::Float => Some(Value::Float(1.0)),
+        Type::Bool => Some(Value::Boolean(true)),
+        Type::String => Some(Value::String("s".into())),
+        Type::Null => Some(Value::Null),
+        Type::Function(_) | Type::Unknown => None,
+    }
+}
+
+impl Lowerer {
+    fn annotation_type(&self, annotation: &TypeAnnotation) -> Type {
+        self.types.resolve(annotation).map_or(Type::Unknown, |resolved| annotation_type(&resolved))
+    }
+}
+
+fn annotation_type(annotation: &TypeAnnotation) -> Type {
+    match annotation {
+        TypeAnnotation::Int => Type::Int,
+        TypeAnnotation::Float => Type::Float,
+        TypeAnnotation::Bool => Type::Bool,
+        TypeAnnotation::String => Type::String,
+        TypeAnnotation::Void => Type::Null,
+        // 합 타입의 값은 검사(`type_of x == "int"`) 전에는 어느 구성원인지 모릅니다.
+        TypeAnnotation::Any
+        | TypeAnnotation::Custom(_)
+        | TypeAnnotation::Union(_)
+        | TypeAnnotation::Array(_)
+        | TypeAnnotation::Map(..)
+        | TypeAnnotation::Infer => Type::Unknown,
+    }
+}
+
+/// IR 주석과 디버그 출력용 한 줄 표기
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ExprKind::Literal(Value::String(s)) => write!(f, "{:?}", s),
+            ExprKind::Literal(value) => write!(f, "{}", display_value(value)),
+            ExprKind::Variable(name) => write!(f, "{}", name),
+            ExprKind::Unary(op, operand) => write!(f, "{}{}", operators::symbol(op), operand),
+            ExprKind::Binary(op, left, right) => write!(f, "({} {} {})", left, operators::symbol(op), right),
+            ExprKind::If(cond, then_expr, else_expr) => write!(f, "(if {} then {} else {})", cond, then_expr, else_expr),
+            ExprKind::Function(parameters, _) => {
+                let names: Vec<&str> = parameters.iter().map(|p| p.as_str()).collect();
+                write!(f, "fn({})", names.join(", "))
+            }
+            ExprKind::Call(callee, args) => {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                write!(f, "{}({})", callee, args.join(", "))
+            }
+            ExprKind::Array(elements) => {
+                let elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            ExprKind::Map(entries) => {
+                let entries: Vec<String> = entries.iter().map(|(key, value)| format!("{}: {}", key, value)).collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            ExprKind::Reflect(inner) => write!(f, "reflect({})", inner),
+            ExprKind::Eval(inner) => write!(f, "eval({})", inner),
+            ExprKind::TypeOf(inner) => write!(f, "typeof({})", inner),
+            ExprKind::Index(target, index) => write!(f, "{}[{}]", target, index),
+            ExprKind::Slice(target, start, end) => {
+                let bound = |b: &Option<Box<Expr>>| b.as_ref().map_or(String::new(), |b| b.to_string());
+                write!(f, "{}[{}:{}]", target, bound(start), bound(end))
+            }
+            ExprKind::Assign(name, value) => write!(f, "({} = {})", name, value),
+            ExprKind::Range(start, end, inclusive) => write!(f, "({}{}{})", start, if *inclusive { "..=" } else { ".." }, end),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// input.rs
+// `read_line()` / `read_all()` 내장 함수가 읽는 표준 입력
+//
+// 실행기는 `ExecutionRequest::input_data` 를 미리 받은 텍스트로 넘기고(`Input::text`), `high run` 은
+// 호스트 프로세스의 표준 입력을 그대로 씁니다. 네이티브 바이너리에는 같은 텍스트를 표준 입력 파이프로 넘깁니다.
+//
+//   read_line()   다음 줄. 줄 끝의 `\n` (과 그 앞의 `\r`) 은 뺍니다. 입력이 끝났으면 null
+//   read_all()    남은 입력 전체. 입력이 끝났으면 빈 문자열
+
+use crate::compat::*;
+
+pub enum Input {
+    /// 미리 받은 텍스트와 지금까지 읽은 위치 (바이트)
+    Text { text: String, position: usize },
+    /// 호스트 프로세스의 표준 입력
+    #[cfg(feature = "std")]
+    Stdin,
+}
+
+impl Default for Input {
+    /// 빈 입력: 처음부터 끝입니다.
+    fn default() -> Self {
+        Input::text("")
+    }
+}
+
+impl Input {
+    pub fn text(text: &str) -> Self {
+        Input::Text { text: text.into(), position: 0 }
+    }
+
+    pub fn read_line(&mut self) -> Option<String> {
+        let mut line = match self {
+            Input::Text { text, position } => {
+                let rest = &text[*position..];
+                if rest.is_empty() {
+                    return None;
+                }
+                let end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+                *position += end;
+                rest[..end].to_string()
+            }
+            #[cfg(feature = "std")]
+            Input::Stdin => {
+                let mut line = String::new();
+                // 읽기 실패는 입력 끝으로 봅니다.
+                match std::io::stdin().read_line(&mut line) {
+                    Ok(0) | Err(_) => return None,
+                    Ok(_) => line,
+                }
+            }
+        };
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Some(line)
+    }
+
+    pub fn read_all(&mut self) -> String {
+        match self {
+            Input::Text { text, position } => {
+                let rest = text[*position..].to_string();
+                *position = text.len();
+                rest
+            }
+            #[cfg(feature = "std")]
+            Input::Stdin => {
+                use std::io::Read;
+                let mut rest = String::new();
+                let _ = std::io::stdin().read_to_string(&mut rest);
+                rest
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// interner.rs
+// 식별자 인터닝: 같은 이름은 프로그램 전체에서 하나의 `Symbol` (정수 번호) 로 나타냅니다.
+//
+// 렉서가 식별자를 한 번 인터닝하면, 파서·이름 해석기·런타임은 문자열을 복사하지 않고 번호만 주고받으며,
+// 환경 조회도 문자열 대신 번호로 해시합니다.
+// 인터닝된 문자열은 프로세스가 끝날 때까지 살아 있으므로 `&'static str` 로 빌려줄 수 있습니다.
+// 백그라운드 컴파일 스레드도 같은 번호를 쓰도록 전역 표 하나를 잠금으로 보호합니다.
+
+use core::fmt;
+
+use crate::compat::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+#[derive(Default)]
+struct Interner {
+    ids: HashMap<&'static str, Symbol>,
+    names: Vec<&'static str>,
+}
+
+fn interner() -> &'static Mutex<Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    get_or_init(&INTERNER, Default::default)
+}
+
+impl Symbol {
+    /// 이름을 인터닝합니다. 이미 있는 이름이면 할당 없이 기존 번호를 돌려줍니다.
+    pub fn intern(name: &str) -> Symbol {
+        let mut table = lock(interner());
+        if let Some(&symbol) = table.ids.get(name) {
+            return symbol;
+        }
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        let symbol = Symbol(table.names.len() as u32);
+        table.names.push(name);
+        table.ids.insert(name, symbol);
+        symbol
+    }
+
+    pub fn as_str(self) -> &'static str {
+        let table = lock(interner());
+        table.names[self.0 as usize]
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        Symbol::intern(name)
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(name: &String) -> Self {
+        Symbol::intern(name)
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 디버그 출력은 문자열과 같은 모양입니다. (AST 덤프가 번호 대신 이름을 보여주도록)
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
@@ -1,41 +1,228 @@
-use crate::data_structures::{Program, Statement};
-
-#[derive(Debug, Clone)]
-pub struct IRInstruction {
-    pub opcode: String,
-    pub operands: Vec<String>,
-}
-
-#[derive(Debug, Clone)]
-pub struct IRModule {
-    pub instructions: Vec<IRInstruction>,
-}
-
-pub fn generate_ir(program: &Program) -> IRModule {
-    let mut instructions = vec![];
-
-    for stmt in &program.statements {
-        match stmt.as_ref() {
-            Statement::LetStatement { name, value, .. } => {
-                instructions.push(IRInstruction {
-                    opcode: "let".into(),
-                    operands: vec![name.clone(), format!("{:?}", value)],
-                });
-            }
-            Statement::ReturnStatement(expr) => {
-                instructions.push(IRInstruction {
-                    opcode: "return".into(),
-                    operands: vec![format!("{:?}", expr)],
-                });
-            }
-            _ => {
-                instructions.push(IRInstruction {
-                    opcode: "noop".into(),
-                    operands: vec![],
-                });
-            }
-        }
-    }
-
-    IRModule { instructions }
-}
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::data_structures::{Span, TokenKind, Value};
+use crate::error_codes;
+use crate::escape_analysis::EscapeReport;
+use crate::formatting::display_value;
+use crate::hir::{self, Expr, ExprKind, Stmt};
+use crate::interner::Symbol;
+use crate::operators;
+
+#[derive(Debug, Clone)]
+pub struct IRInstruction {
+    pub opcode: String,
+    pub operands: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IRModule {
+    pub instructions: Vec<IRInstruction>,
+}
+
+/// 한 줄에 명령 하나: `opcode operand, operand`
+impl fmt::Display for IRModule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instr in &self.instructions {
+            writeln!(f, "{} {}", instr.opcode, instr.operands.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// HIR 을 IR 로 내립니다. 클로저 배치는 파스 트리에서 구한 탈출 분석 결과를 따릅니다.
+///
+/// 최상위의 불변 정수 바인딩은 `let` 주석 뒤에 스택 기계 명령으로 계산됩니다.
+///   `push <n>`             정수 상수를 쌓습니다
+///   `load <name>`          앞서 `store` 한 바인딩을 쌓습니다
+///   `neg`                  맨 위 값의 부호를 바꿉니다 (`wrapping_neg`)
+///   `binary <op>[, <msg>]` 위의 두 값을 꺼내 계산합니다. `/` `%` 는 나누는 수가 0 일 때 출력할 패닉 메시지를 함께 싣습니다
+///   `select <cmp>`         `a, b, l, r` 를 꺼내 `l <cmp> r` 이면 `a`, 아니면 `b` 를 쌓습니다 (삼항식)
+///   `store <name>`         맨 위 값을 꺼내 바인딩에 저장합니다
+/// 의미는 operators.rs 의 정수 연산과 같습니다.
+pub fn generate_ir(program: &hir::Program, escapes: &EscapeReport) -> IRModule {
+    let mut instructions = vec![];
+    // 현재 정수 슬롯에 값이 있는 바인딩. 다른 값으로 다시 묶이면 빠집니다.
+    let mut integers = HashSet::new();
+
+    for stmt in &program.statements {
+        match stmt {
+            // 클로저 바인딩: 탈출 분석 결과에 따라 `alloc <name> stack|heap` 로 내립니다.
+            Stmt::Let { name, value, .. } if matches!(value.kind, ExprKind::Function(..)) => {
+                integers.remove(name);
+                let placement = match escapes.binding(name.as_str()) {
+                    Some(site) if site.on_stack() => "stack",
+                    _ => "heap",
+                };
+                instructions.push(IRInstruction {
+                    opcode: "alloc".into(),
+                    operands: vec![name.to_string(), placement.into(), "closure".into()],
+                });
+            }
+            Stmt::Let { value, .. } if abort_message(value).is_some() => {
+                if let Some(message) = abort_message(value) {
+                    instructions.push(IRInstruction { opcode: "abort".into(), operands: vec![message] });
+                }
+            }
+            Stmt::Let { name, mutable, value, .. } => {
+                instructions.push(IRInstruction {
+                    opcode: "let".into(),
+                    operands: vec![name.to_string(), format!("{}: {}", value, value.ty)],
+                });
+                let mut ops = vec![];
+                if !*mutable && lower_integer(value, &integers, &mut ops) {
+                    instructions.extend(ops);
+                    instructions.push(instruction("store", vec![name.to_string()]));
+                    integers.insert(*name);
+                } else {
+                    integers.remove(name);
+                }
+            }
+            Stmt::Return(expr, _) => {
+                instructions.push(IRInstruction {
+                    opcode: "return".into(),
+                    operands: vec![expr.to_string()],
+                });
+            }
+            Stmt::Expr(expr) if abort_message(expr).is_some() => {
+                if let Some(message) = abort_message(expr) {
+                    instructions.push(IRInstruction { opcode: "abort".into(), operands: vec![message] });
+                }
+            }
+            _ => {
+                instructions.push(IRInstruction {
+                    opcode: "noop".into(),
+                    operands: vec![],
+                });
+            }
+        }
+    }
+
+    IRModule { instructions }
+}
+
+/// `panic(msg)`, 조건이 리터럴 `false` 인 `assert(cond, msg?)`, 정수 리터럴 0 으로 나누는 식은 네이티브 코드에서
+/// 메시지 출력 후 즉시 종료(abort)로 바뀝니다. 리터럴이 아닌 조건의 assert 는 아직 평가하지 못합니다.
+fn abort_message(expr: &Expr) -> Option<String> {
+    if let Some(span) = division_by_zero(expr) {
+        let message = error_codes::PANIC.message("division_by_zero", &[]);
+        return Some(format!("panic at {}..{}: {}", span.start, span.end, message));
+    }
+    let ExprKind::Call(function, args) = &expr.kind else {
+        return None;
+    };
+    let ExprKind::Variable(name) = &function.kind else {
+        return None;
+    };
+    let span = expr.span;
+    let literal = |index: usize| match args.get(index).map(|a| &a.kind) {
+        Some(ExprKind::Literal(value)) => Some(display_value(value)),
+        _ => None,
+    };
+    let location = format!("{}..{}", span.start, span.end);
+    match name.as_str() {
+        "panic" => Some(format!("panic at {}: {}", location, literal(0).unwrap_or_else(|| "명시적 panic".into()))),
+        "assert" => match args.first().map(|a| &a.kind) {
+            Some(ExprKind::Literal(Value::Boolean(false))) => Some(match literal(1) {
+                Some(message) => format!("assertion failed at {}: {}", location, message),
+                None => format!("assertion failed at {}", location),
+            }),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 식을 계산하면 반드시 지나는 `x / 0`, `x % 0` (정수 리터럴 0) 의 위치. 분기와 함수 본문 안은 보지 않습니다.
+fn division_by_zero(expr: &Expr) -> Option<Span> {
+    match &expr.kind {
+        ExprKind::Binary(TokenKind::Slash | TokenKind::Percent, left, right) => division_by_zero(left)
+            .or_else(|| division_by_zero(right))
+            .or_else(|| matches!(right.kind, ExprKind::Literal(Value::Integer(0))).then_some(expr.span)),
+        // `&&` / `||` 의 오른쪽은 단락 평가로 건너뛸 수 있습니다.
+        ExprKind::Binary(TokenKind::And | TokenKind::Or, left, _) => division_by_zero(left),
+        ExprKind::Binary(_, left, right) => division_by_zero(left).or_else(|| division_by_zero(right)),
+        ExprKind::Unary(_, operand) => division_by_zero(operand),
+        ExprKind::Call(function, args) => division_by_zero(function).or_else(|| args.iter().find_map(division_by_zero)),
+        _ => None,
+    }
+}
+
+fn instruction(opcode: &str, operands: Vec<String>) -> IRInstruction {
+    IRInstruction { opcode: opcode.into(), operands }
+}
+
+/// 정수 식을 스택 명령으로 내립니다. 내릴 수 없는 부분이 있으면 false 이며 `ops` 는 버려야 합니다.
+fn lower_integer(expr: &Expr, integers: &HashSet<Symbol>, ops: &mut Vec<IRInstruction>) -> bool {
+    match &expr.kind {
+        ExprKind::Literal(Value::Integer(n)) => {
+            ops.push(instruction("push", vec![n.to_string()]));
+            true
+        }
+        ExprKind::Variable(name) if integers.contains(name) => {
+            ops.push(instruction("load", vec![name.to_string()]));
+            true
+        }
+        ExprKind::Unary(TokenKind::Minus, operand) => {
+            let lowered = lower_integer(operand, integers, ops);
+            ops.push(instruction("neg", vec![]));
+            lowered
+        }
+        ExprKind::Binary(op, left, right) if is_integer_operator(op) => {
+            if !lower_integer(left, integers, ops) || !lower_integer(right, integers, ops) {
+                return false;
+            }
+            let mut operands = vec![operators::symbol(op).to_string()];
+            if matches!(op, TokenKind::Slash | TokenKind::Percent) {
+                let message = error_codes::PANIC.message("division_by_zero", &[]);
+                operands.push(format!("panic at {}..{}: {}", expr.span.start, expr.span.end, message));
+            }
+            ops.push(instruction("binary", operands));
+            true
+        }
+        // 두 갈래를 모두 계산하므로, 패닉할 수 있는 갈래가 있으면 내리지 않습니다.
+        ExprKind::If(cond, then_expr, else_expr) if !can_panic(then_expr) && !can_panic(else_expr) => {
+            let ExprKind::Binary(op, left, right) = &cond.kind else {
+                return false;
+            };
+            let comparison =
+                matches!(op, TokenKind::Eq | TokenKind::Neq | TokenKind::Less | TokenKind::Greater | TokenKind::LessEqual | TokenKind::GreaterEqual);
+            if !comparison {
+                return false;
+            }
+            let lowered = lower_integer(then_expr, integers, ops)
+                && lower_integer(else_expr, integers, ops)
+                && lower_integer(left, integers, ops)
+                && lower_integer(right, integers, ops);
+            ops.push(instruction("select", vec![operators::symbol(op).to_string()]));
+            lowered
+        }
+        _ => false,
+    }
+}
+
+fn is_integer_operator(op: &TokenKind) -> bool {
+    matches!(
+        op,
+        TokenKind::Plus
+            | TokenKind::Minus
+            | TokenKind::Asterisk
+            | TokenKind::Slash
+            | TokenKind::Percent
+            | TokenKind::BitAnd
+            | TokenKind::BitOr
+            | TokenKind::BitXor
+            | TokenKind::ShiftLeft
+            | TokenKind::ShiftRight
+    )
+}
+
+fn can_panic(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Binary(TokenKind::Slash | TokenKind::Percent, ..) => true,
+        ExprKind::Binary(_, left, right) => can_panic(left) || can_panic(right),
+        ExprKind::Unary(_, operand) => can_panic(operand),
+        ExprKind::If(cond, then_expr, else_expr) => can_panic(cond) || can_panic(then_expr) || can_panic(else_expr),
+        _ => false,
+    }
+}
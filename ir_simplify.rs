@@ -0,0 +1,260 @@
+// ir_simplify.rs
+// IR 생성 직전의 HIR 단순화 (상수 접기, 대수 간소화, 상수 분기 제거)
+//
+// 앞 단계 옵티마이저(optimizer.rs)는 파스 트리에서 리터럴끼리의 연산만 접습니다. 여기서는 HIR 의 타입을 써서
+// 피연산자 하나가 변수인 식도 줄이므로, 네이티브/LLVM 코드의 품질이 앞 단계가 무엇을 했는지에만 달려 있지 않습니다.
+// Rust/Python 백엔드는 원래 HIR 을 그대로 받습니다.
+//
+//   리터럴끼리의 연산      `operators` 로 계산. 패닉하는 식(0 으로 나누기)과 `MathMode` 가 받지 않는 실수는 남김
+//   x * 1, 1 * x           x   (int, float. 1.0 을 곱해도 비트가 같음)
+//   x + 0, 0 + x, x - 0    x   (int 만. float 는 -0.0 + 0.0 이 0.0 이라 바뀜)
+//   x - x                  0   (같은 int 변수)
+//   if 리터럴 조건         고른 쪽 분기. 문장이면 분기 블록을 감싼 블록에 합칩니다. `while false` 는 지웁니다.
+//   블록 합치기            let 과 defer 가 없는 블록은 바인딩 범위와 정리 시점이 같으므로 감싼 문장 목록에 펼칩니다.
+//   같은 상수 다시 읽기    바로 앞의 불변 `let a = 5` 에 이어지는 `let b = 5` 는 `let b = a` 가 되어 상수를 한 번만 적재합니다.
+//
+// 상수 분기를 펼치면 그 안의 `panic(...)` 같은 문장이 최상위로 올라와 IR 생성기가 abort 로 내릴 수 있습니다.
+//
+// `lay_out_branches` 는 단순화와 따로, 모든 백엔드가 받는 HIR 에 `--profile-use` 프로필을 적용합니다. (profile.rs)
+// else 쪽으로 더 많이 간 if-else 는 조건을 `!` 로 뒤집고 두 분기를 바꿔 자주 가는 쪽이 먼저 놓이게 합니다.
+// 조건이 bool 로 증명된 경우만 바꿉니다. 그 밖의 값은 `!` 가 오류가 되어 원래와 다른 분기로 갈 수 있습니다.
+
+use crate::data_structures::{Span, TokenKind, Value};
+use crate::hir::{value_type, Block, Expr, ExprKind, Program, Stmt, Type};
+use crate::interner::Symbol;
+use crate::operators;
+use crate::optimizer::MathMode;
+use crate::profile::Profile;
+
+pub fn simplify(program: &mut Program, math: MathMode) {
+    let simplifier = Simplifier { math };
+    simplifier.statements(&mut program.statements);
+}
+
+pub fn lay_out_branches(program: &mut Program, profile: &Profile) {
+    lay_out(&mut program.statements, profile);
+}
+
+fn lay_out(statements: &mut [Stmt], profile: &Profile) {
+    for stmt in statements {
+        match stmt {
+            Stmt::If { condition, then_branch, else_branch, span } => {
+                lay_out(&mut then_branch.statements, profile);
+                if let Some(else_branch) = else_branch {
+                    lay_out(&mut else_branch.statements, profile);
+                    let colder = profile.branch_counts(*span).is_some_and(|counts| counts.not_taken > counts.taken);
+                    if colder && condition.ty == Type::Bool {
+                        let inner = core::mem::replace(condition, literal(Value::Null, *span));
+                        *condition = Expr { kind: ExprKind::Unary(TokenKind::Bang, Box::new(inner)), ty: Type::Bool, span: *span };
+                        core::mem::swap(then_branch, else_branch);
+                    }
+                }
+            }
+            Stmt::While { body, .. } | Stmt::ForIn { body, .. } | Stmt::Defer(body, _) | Stmt::Block(body) => lay_out(&mut body.statements, profile),
+            Stmt::Let { value: Expr { kind: ExprKind::Function(_, body), .. }, .. } => lay_out(&mut body.statements, profile),
+            _ => {}
+        }
+    }
+}
+
+struct Simplifier {
+    math: MathMode,
+}
+
+impl Simplifier {
+    // ─── 문장 ─────────────────────────────
+
+    fn statements(&self, statements: &mut Vec<Stmt>) {
+        let mut out = Vec::with_capacity(statements.len());
+        for stmt in statements.drain(..) {
+            self.statement(stmt, &mut out);
+        }
+        combine_constant_loads(&mut out);
+        *statements = out;
+    }
+
+    fn block(&self, block: &mut Block) {
+        self.statements(&mut block.statements);
+    }
+
+    /// 단순화한 문장을 `out` 에 붙입니다. 지워진 문장은 붙이지 않고, 합칠 수 있는 블록은 펼쳐 붙입니다.
+    fn statement(&self, stmt: Stmt, out: &mut Vec<Stmt>) {
+        match stmt {
+            Stmt::Expr(expr) => out.push(Stmt::Expr(self.expression(expr))),
+            Stmt::Let { name, mutable, value, span } => out.push(Stmt::Let { name, mutable, value: self.expression(value), span }),
+            Stmt::Return(expr, span) => out.push(Stmt::Return(self.expression(expr), span)),
+            Stmt::Yield(expr, span) => out.push(Stmt::Yield(self.expression(expr), span)),
+            Stmt::Enum { .. } => out.push(stmt),
+            Stmt::Defer(mut body, span) => {
+                self.block(&mut body);
+                out.push(Stmt::Defer(body, span));
+            }
+            Stmt::Block(block) => self.splice(block, out),
+            Stmt::If { condition, then_branch, else_branch, span } => {
+                let condition = self.expression(condition);
+                match &condition.kind {
+                    ExprKind::Literal(Value::Boolean(true)) => self.splice(then_branch, out),
+                    ExprKind::Literal(Value::Boolean(false)) => {
+                        if let Some(else_branch) = else_branch {
+                            self.splice(else_branch, out);
+                        }
+                    }
+                    _ => {
+                        let (mut then_branch, mut else_branch) = (then_branch, else_branch);
+                        self.block(&mut then_branch);
+                        if let Some(else_branch) = &mut else_branch {
+                            self.block(else_branch);
+                        }
+                        out.push(Stmt::If { condition, then_branch, else_branch, span });
+                    }
+                }
+            }
+            Stmt::While { condition, mut body, span } => {
+                let condition = self.expression(condition);
+                if !matches!(condition.kind, ExprKind::Literal(Value::Boolean(false))) {
+                    self.block(&mut body);
+                    out.push(Stmt::While { condition, body, span });
+                }
+            }
+            Stmt::ForIn { variable, iterable, mut body, span } => {
+                let iterable = self.expression(iterable);
+                self.block(&mut body);
+                out.push(Stmt::ForIn { variable, iterable, body, span });
+            }
+        }
+    }
+
+    /// 블록을 단순화하고, 바인딩이나 defer 가 없으면 문장들을 `out` 에 그대로 펼칩니다.
+    fn splice(&self, mut block: Block, out: &mut Vec<Stmt>) {
+        self.block(&mut block);
+        if block.statements.iter().any(|s| matches!(s, Stmt::Let { .. } | Stmt::Defer(..))) {
+            out.push(Stmt::Block(block));
+        } else {
+            out.append(&mut block.statements);
+        }
+    }
+
+    // ─── 식 ─────────────────────────────
+
+    fn expression(&self, expr: Expr) -> Expr {
+        let Expr { kind, ty, span } = expr;
+        let kind = match kind {
+            ExprKind::Unary(op, operand) => {
+                let operand = self.expression(*operand);
+                if let ExprKind::Literal(value) = &operand.kind {
+                    if let Some(value) = operators::unary(&op, value).ok().filter(|v| self.math.accepts(v)) {
+                        return literal(value, span);
+                    }
+                }
+                ExprKind::Unary(op, Box::new(operand))
+            }
+            ExprKind::Binary(op, left, right) => {
+                let (left, right) = (self.expression(*left), self.expression(*right));
+                return match self.binary(&op, left, right, span) {
+                    Ok(simplified) => simplified,
+                    Err((left, right)) => Expr { kind: ExprKind::Binary(op, left, right), ty, span },
+                };
+            }
+            ExprKind::If(condition, then_expr, else_expr) => {
+                let condition = self.expression(*condition);
+                match &condition.kind {
+                    ExprKind::Literal(Value::Boolean(true)) => return self.expression(*then_expr),
+                    ExprKind::Literal(Value::Boolean(false)) => return self.expression(*else_expr),
+                    _ => ExprKind::If(
+                        Box::new(condition),
+                        Box::new(self.expression(*then_expr)),
+                        Box::new(self.expression(*else_expr)),
+                    ),
+                }
+            }
+            ExprKind::Function(parameters, mut body) => {
+                self.block(&mut body);
+                ExprKind::Function(parameters, body)
+            }
+            ExprKind::Call(callee, args) => {
+                ExprKind::Call(Box::new(self.expression(*callee)), args.into_iter().map(|a| self.expression(a)).collect())
+            }
+            ExprKind::Array(elements) => ExprKind::Array(elements.into_iter().map(|e| self.expression(e)).collect()),
+            ExprKind::Map(entries) => {
+                ExprKind::Map(entries.into_iter().map(|(key, value)| (self.expression(key), self.expression(value))).collect())
+            }
+            ExprKind::Reflect(inner) => ExprKind::Reflect(Box::new(self.expression(*inner))),
+            ExprKind::Eval(inner) => ExprKind::Eval(Box::new(self.expression(*inner))),
+            ExprKind::TypeOf(inner) => ExprKind::TypeOf(Box::new(self.expression(*inner))),
+            ExprKind::Index(target, index) => ExprKind::Index(Box::new(self.expression(*target)), Box::new(self.expression(*index))),
+            ExprKind::Slice(target, start, end) => ExprKind::Slice(
+                Box::new(self.expression(*target)),
+                start.map(|s| Box::new(self.expression(*s))),
+                end.map(|e| Box::new(self.expression(*e))),
+            ),
+            ExprKind::Assign(name, value) => ExprKind::Assign(name, Box::new(self.expression(*value))),
+            ExprKind::Range(start, end, inclusive) => {
+                ExprKind::Range(Box::new(self.expression(*start)), Box::new(self.expression(*end)), inclusive)
+            }
+            leaf @ (ExprKind::Literal(_) | ExprKind::Variable(_)) => leaf,
+        };
+        Expr { kind, ty, span }
+    }
+
+    /// 단순화한 식, 또는 줄일 수 없으면 `Binary` 에 그대로 넣을 수 있게 상자에 담은 피연산자를 돌려줍니다.
+    fn binary(&self, op: &TokenKind<'static>, left: Expr, right: Expr, span: Span) -> Result<Expr, (Box<Expr>, Box<Expr>)> {
+        if let (ExprKind::Literal(l), ExprKind::Literal(r)) = (&left.kind, &right.kind) {
+            return match operators::binary(op, l, r).ok().filter(|v| self.math.accepts(v)) {
+                Some(value) => Ok(literal(value, span)),
+                None => Err((Box::new(left), Box::new(right))),
+            };
+        }
+        let int = |e: &Expr, n: i64| matches!(e.kind, ExprKind::Literal(Value::Integer(v)) if v == n);
+        let float_one = |e: &Expr| matches!(e.kind, ExprKind::Literal(Value::Float(v)) if v.to_bits() == 1.0f64.to_bits());
+        match op {
+            TokenKind::Asterisk if right.ty == Type::Int && int(&left, 1) => Ok(right),
+            TokenKind::Asterisk if left.ty == Type::Int && int(&right, 1) => Ok(left),
+            TokenKind::Asterisk if right.ty == Type::Float && float_one(&left) => Ok(right),
+            TokenKind::Asterisk if left.ty == Type::Float && float_one(&right) => Ok(left),
+            TokenKind::Plus if right.ty == Type::Int && int(&left, 0) => Ok(right),
+            TokenKind::Plus | TokenKind::Minus if left.ty == Type::Int && int(&right, 0) => Ok(left),
+            TokenKind::Minus if left.ty == Type::Int && same_variable(&left, &right) => Ok(literal(Value::Integer(0), span)),
+            _ => Err((Box::new(left), Box::new(right))),
+        }
+    }
+}
+
+fn literal(value: Value, span: Span) -> Expr {
+    Expr { ty: value_type(&value), kind: ExprKind::Literal(value), span }
+}
+
+fn same_variable(left: &Expr, right: &Expr) -> bool {
+    matches!((&left.kind, &right.kind), (ExprKind::Variable(a), ExprKind::Variable(b)) if a == b)
+}
+
+/// 비트까지 같은 상수인지. `0.0 == -0.0` 이지만 서로 바꿔 쓸 수 없습니다.
+fn same_constant(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Integer(x), Value::Integer(y)) => x == y,
+        (Value::Float(x), Value::Float(y)) => x.to_bits() == y.to_bits(),
+        (Value::Boolean(x), Value::Boolean(y)) => x == y,
+        (Value::String(x), Value::String(y)) => x == y,
+        _ => false,
+    }
+}
+
+/// 연이은 `let` 이 바로 앞 불변 바인딩과 같은 상수를 적재하면 그 바인딩을 읽게 합니다.
+fn combine_constant_loads(statements: &mut [Stmt]) {
+    let mut loaded: Option<(Symbol, Value)> = None;
+    for stmt in statements.iter_mut() {
+        let Stmt::Let { name, mutable, value, .. } = stmt else {
+            loaded = None;
+            continue;
+        };
+        let ExprKind::Literal(constant) = &value.kind else {
+            loaded = None;
+            continue;
+        };
+        match &loaded {
+            Some((source, previous)) if *source != *name && same_constant(previous, constant) => {
+                value.kind = ExprKind::Variable(*source);
+            }
+            _ => loaded = (!*mutable).then(|| (*name, constant.clone())),
+        }
+    }
+}
@@ -0,0 +1,317 @@
+// json.rs
+// 플레이그라운드 서버와 컴파일러 데몬이 주고받는 JSON 값입니다.
+// 외부 크레이트 없이 RFC 8259 의 부분 집합을 읽고 씁니다. 객체는 키 순서를 유지합니다.
+
+use std::fmt::{self, Write as _};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// 키/값 목록으로 객체를 만듭니다.
+    pub fn object<K: Into<String>>(entries: impl IntoIterator<Item = (K, Json)>) -> Json {
+        Json::Object(entries.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// 음수가 아닌 정수일 때만 값을 줍니다.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 && *n <= u64::MAX as f64 => Some(*n as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// 문서 하나를 읽습니다. 뒤에 공백 외의 문자가 남으면 오류입니다.
+    pub fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = JsonParser { chars: text.char_indices().peekable(), text };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(value),
+            Some((i, c)) => Err(format!("{} 위치에 남은 문자 '{}'", i, c)),
+        }
+    }
+}
+
+impl From<&str> for Json {
+    fn from(s: &str) -> Self {
+        Json::String(s.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self {
+        Json::String(s)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(b: bool) -> Self {
+        Json::Bool(b)
+    }
+}
+
+impl From<u64> for Json {
+    fn from(n: u64) -> Self {
+        Json::Number(n as f64)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+/// 공백 없는 한 줄 표기. 데몬은 메시지를 줄 단위로 구분하므로 줄바꿈을 넣지 않습니다.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) if n.is_finite() && n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            Json::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Json::Number(_) => f.write_str("null"),
+            Json::String(s) => f.write_str(&escape(s)),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(entries) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}:{}", escape(key), value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// 따옴표를 포함한 JSON 문자열 표기
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// ─── 읽기 ─────────────────────────────
+
+/// 중첩 깊이 제한 (악의적인 입력으로 스택이 넘치지 않도록)
+const MAX_DEPTH: usize = 128;
+
+struct JsonParser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+}
+
+impl JsonParser<'_> {
+    fn value(&mut self) -> Result<Json, String> {
+        self.value_at(0)
+    }
+
+    fn value_at(&mut self, depth: usize) -> Result<Json, String> {
+        if depth > MAX_DEPTH {
+            return Err("중첩이 너무 깊습니다.".into());
+        }
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Err("값이 필요한데 입력이 끝났습니다.".into()),
+            Some((_, '{')) => {
+                self.chars.next();
+                let mut entries = vec![];
+                if self.eat('}') {
+                    return Ok(Json::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.string()?;
+                    self.expect(':')?;
+                    entries.push((key, self.value_at(depth + 1)?));
+                    if self.eat('}') {
+                        return Ok(Json::Object(entries));
+                    }
+                    self.expect(',')?;
+                }
+            }
+            Some((_, '[')) => {
+                self.chars.next();
+                let mut items = vec![];
+                if self.eat(']') {
+                    return Ok(Json::Array(items));
+                }
+                loop {
+                    items.push(self.value_at(depth + 1)?);
+                    if self.eat(']') {
+                        return Ok(Json::Array(items));
+                    }
+                    self.expect(',')?;
+                }
+            }
+            Some((_, '"')) => self.string().map(Json::String),
+            Some((_, 't')) => self.keyword("true", Json::Bool(true)),
+            Some((_, 'f')) => self.keyword("false", Json::Bool(false)),
+            Some((_, 'n')) => self.keyword("null", Json::Null),
+            Some((start, c)) if c == '-' || c.is_ascii_digit() => {
+                let mut end = start;
+                while let Some(&(i, c)) = self.chars.peek() {
+                    if !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    self.chars.next();
+                }
+                self.text[start..end]
+                    .parse()
+                    .map(Json::Number)
+                    .map_err(|_| format!("{} 위치의 숫자를 읽을 수 없습니다.", start))
+            }
+            Some((i, c)) => Err(format!("{} 위치에 예상하지 못한 문자 '{}'", i, c)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        match self.chars.next() {
+            Some((_, '"')) => {}
+            Some((i, c)) => return Err(format!("{} 위치에 문자열이 필요합니다. ('{}')", i, c)),
+            None => return Err("문자열이 필요한데 입력이 끝났습니다.".into()),
+        }
+        let mut out = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("문자열이 닫히지 않았습니다.".into()),
+                Some((_, '"')) => return Ok(out),
+                Some((i, '\\')) => match self.chars.next().map(|(_, c)| c) {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('/') => out.push('/'),
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some('u') => {
+                        let high = self.hex4()?;
+                        let code = if (0xD800..0xDC00).contains(&high) && self.eat_exact('\\') && self.eat_exact('u') {
+                            let low = self.hex4()?;
+                            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                        } else {
+                            high
+                        };
+                        out.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+                    }
+                    _ => return Err(format!("{} 위치의 이스케이프가 올바르지 않습니다.", i)),
+                },
+                Some((_, c)) => out.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.chars.next().and_then(|(_, c)| c.to_digit(16)).ok_or("\\u 뒤에 16진수 네 자리가 필요합니다.")?;
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        for expected in word.chars() {
+            if !self.eat_exact(expected) {
+                return Err(format!("'{}' 를 읽을 수 없습니다.", word));
+            }
+        }
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some((_, ' ' | '\t' | '\n' | '\r'))) {
+            self.chars.next();
+        }
+    }
+
+    /// 공백을 건너뛴 뒤 `c` 가 오면 소비합니다.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        self.eat_exact(c)
+    }
+
+    fn eat_exact(&mut self, c: char) -> bool {
+        if matches!(self.chars.peek(), Some(&(_, next)) if next == c) {
+            self.chars.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            match self.chars.peek() {
+                Some(&(i, found)) => Err(format!("{} 위치에 '{}' 가 필요합니다. ('{}')", i, c, found)),
+                None => Err(format!("'{}' 가 필요한데 입력이 끝났습니다.", c)),
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+// kernel.rs
+// `high kernel`: 노트북(Jupyter 식) 커널입니다. 셀을 하나씩 받아 계속 살아 있는 Engine 에서 실행합니다.
+//
+// 전송: 표준 입출력, 메시지 하나가 한 줄인 JSON-RPC 2.0 (데몬과 같은 형식). Jupyter 의 ZeroMQ 소켓은
+// 얇은 래퍼가 맡고, 커널은 실행과 상태만 책임집니다.
+//
+//   kernel_info {}                         언어 정보와 커널 버전
+//   execute     {code, silent?, atomic?}   셀 실행. 앞선 셀의 바인딩과 매크로를 그대로 씁니다.
+//               → {status: "ok", execution_count, stdout, data: {"text/plain", "application/json"?, "text/html"?}}
+//               → {status: "error", execution_count, ename, evalue, traceback}
+//               silent 이면 실행 번호를 올리지 않고 결과도 싣지 않습니다. atomic 이면 실패한 셀의
+//               바인딩 변경을 되돌립니다 (실행 전 스냅샷으로 복원).
+//   checkpoint  {encode?}                  지금 전역 상태를 저장하고 번호를 돌려줍니다. encode 이면 텍스트 스냅샷도 싣습니다.
+//   restore     {id} | {snapshot}          저장한 번호나 텍스트 스냅샷으로 되돌립니다.
+//   variables   {}                         셀에서 만든 전역 바인딩 (이름, 타입, 표시 문자열)
+//   reset       {}                         새 런타임으로 다시 시작합니다. 실행 번호와 체크포인트도 지웁니다.
+//   shutdown    {}                         응답한 뒤 종료합니다.
+//
+// 셀의 결과 값은 마지막으로 실행한 식 문장이나 return 의 값입니다 (`Engine::last_value`).
+
+use std::io::{self, BufRead, Write};
+
+use crate::artifact_metadata;
+use crate::daemon::{RpcError, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR};
+use crate::data_structures::Value;
+use crate::engine::Engine;
+use crate::formatting::display_value;
+use crate::ft_runtime;
+use crate::json::Json;
+use crate::snapshot::Snapshot;
+use crate::stdlib;
+
+/// Jupyter 커널 메시지 프로토콜 버전
+pub const PROTOCOL_VERSION: &str = "5.3";
+
+pub struct Kernel {
+    engine: Engine,
+    no_std: bool,
+    prelude: Option<String>,
+    /// 프렐류드가 만든 전역 이름. `variables` 에서 뺍니다.
+    prelude_names: Vec<String>,
+    execution_count: u64,
+    checkpoints: Vec<Snapshot>,
+}
+
+impl Kernel {
+    pub fn new(no_std: bool, prelude: Option<String>) -> Result<Self, String> {
+        let mut kernel = Self {
+            engine: Engine::new(),
+            no_std,
+            prelude,
+            prelude_names: vec![],
+            execution_count: 0,
+            checkpoints: vec![],
+        };
+        kernel.start()?;
+        Ok(kernel)
+    }
+
+    /// 새 Engine 을 만들고 표준 라이브러리와 프렐류드를 실행합니다.
+    fn start(&mut self) -> Result<(), String> {
+        self.engine = Engine::new();
+        if let Some(prelude) = stdlib::execution_prelude(self.no_std, self.prelude.as_deref()) {
+            self.engine.load_prelude(&prelude)?;
+        }
+        self.prelude_names = self.global_names();
+        self.execution_count = 0;
+        self.checkpoints.clear();
+        Ok(())
+    }
+
+    /// 표준 입력에서 요청을 읽어 표준 출력으로 응답합니다. `shutdown` 이나 입력 끝에서 돌아옵니다.
+    pub fn serve_stdio(&mut self) -> io::Result<()> {
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+        for line in stdin.lock().lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (id, method, response) = match Json::parse(&line) {
+                Err(e) => (Json::Null, None, Err(RpcError::new(PARSE_ERROR, e))),
+                Ok(message) => {
+                    let id = message.get("id").cloned().unwrap_or(Json::Null);
+                    match message.get("method").and_then(Json::as_str) {
+                        None => (id, None, Err(RpcError::new(INVALID_REQUEST, "method 가 필요합니다."))),
+                        Some(method) => {
+                            let params = message.get("params").cloned().unwrap_or(Json::object::<&str>([]));
+                            let response = self.dispatch(method, &params);
+                            (id, Some(method.to_string()), response)
+                        }
+                    }
+                }
+            };
+            writeln!(stdout, "{}", response_json(&id, response))?;
+            stdout.flush()?;
+            if method.as_deref() == Some("shutdown") {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn dispatch(&mut self, method: &str, params: &Json) -> Result<Json, RpcError> {
+        match method {
+            "kernel_info" => Ok(kernel_info()),
+            "execute" => {
+                let code = params
+                    .get("code")
+                    .and_then(Json::as_str)
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "params.code 문자열이 필요합니다."))?;
+                let flag = |key| params.get(key).and_then(Json::as_bool).unwrap_or(false);
+                Ok(self.execute(code, flag("silent"), flag("atomic")))
+            }
+            "checkpoint" => {
+                let snapshot = self.engine.snapshot();
+                let encoded = params.get("encode").and_then(Json::as_bool).unwrap_or(false).then(|| snapshot.encode());
+                self.checkpoints.push(snapshot);
+                Ok(Json::object([
+                    ("id", Json::from(self.checkpoints.len() as u64 - 1)),
+                    ("snapshot", Json::from(encoded)),
+                ]))
+            }
+            "restore" => {
+                let decoded;
+                let snapshot = match (params.get("id").and_then(Json::as_u64), params.get("snapshot").and_then(Json::as_str)) {
+                    (Some(id), _) => self
+                        .checkpoints
+                        .get(id as usize)
+                        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("체크포인트 {} 가 없습니다.", id)))?,
+                    (None, Some(text)) => {
+                        decoded = Snapshot::decode(text).map_err(|e| RpcError::new(INVALID_PARAMS, e))?;
+                        &decoded
+                    }
+                    (None, None) => return Err(RpcError::new(INVALID_PARAMS, "params.id 나 params.snapshot 이 필요합니다.")),
+                };
+                self.engine.restore(snapshot).map_err(|e| RpcError::new(INVALID_PARAMS, e))?;
+                Ok(Json::Null)
+            }
+            "variables" => Ok(Json::Array(self.variables())),
+            "reset" => {
+                self.start().map_err(|e| RpcError::new(INVALID_REQUEST, e))?;
+                Ok(Json::Null)
+            }
+            "shutdown" => Ok(Json::Null),
+            other => Err(RpcError::new(METHOD_NOT_FOUND, format!("알 수 없는 메서드: {}", other))),
+        }
+    }
+
+    /// 셀 하나를 실행합니다. 실패도 JSON-RPC 오류가 아닌 `status: "error"` 결과입니다.
+    pub fn execute(&mut self, code: &str, silent: bool, atomic: bool) -> Json {
+        if !silent {
+            self.execution_count += 1;
+        }
+        let before = atomic.then(|| self.engine.snapshot());
+        let count = ("execution_count", Json::from(self.execution_count));
+
+        match self.engine.run_cell(code) {
+            Ok(lines) => {
+                let data = match (silent, self.engine.last_value()) {
+                    (false, Some(value)) => display_data(value),
+                    _ => Json::object::<&str>([]),
+                };
+                Json::object([
+                    ("status", Json::from("ok")),
+                    count,
+                    ("stdout", Json::Array(lines.into_iter().map(Json::from).collect())),
+                    ("data", data),
+                ])
+            }
+            Err(e) => {
+                if let Some(snapshot) = &before {
+                    // 같은 런타임에서 찍은 스냅샷이므로 복원은 실패하지 않습니다.
+                    let _ = self.engine.restore(snapshot);
+                }
+                let mut lines = e.lines();
+                let evalue = lines.next().unwrap_or("").to_string();
+                let ename = if evalue.starts_with("[E005") { "MacroError" } else { "RuntimeError" };
+                Json::object([
+                    ("status", Json::from("error")),
+                    count,
+                    ("ename", Json::from(ename)),
+                    ("evalue", Json::from(evalue)),
+                    ("traceback", Json::Array(lines.map(Json::from).collect())),
+                ])
+            }
+        }
+    }
+
+    /// 셀에서 만든 전역 바인딩. 매크로와 프렐류드 정의는 뺍니다.
+    fn variables(&mut self) -> Vec<Json> {
+        let mut names = self.global_names();
+        names.retain(|name| !self.prelude_names.contains(name));
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let value = self.engine.get_global(&name)?;
+                (!matches!(value, Value::Macro(_))).then(|| {
+                    Json::object([
+                        ("name", Json::from(name)),
+                        ("type", Json::from(ft_runtime::type_name(&value))),
+                        ("value", Json::from(display_value(&value))),
+                    ])
+                })
+            })
+            .collect()
+    }
+
+    fn global_names(&mut self) -> Vec<String> {
+        let mut names: Vec<String> =
+            self.engine.runtime().environment.borrow().store.keys().map(|name| name.to_string()).collect();
+        names.sort();
+        names
+    }
+}
+
+fn kernel_info() -> Json {
+    Json::object([
+        ("protocol_version", Json::from(PROTOCOL_VERSION)),
+        ("implementation", Json::from("high")),
+        ("implementation_version", Json::from(artifact_metadata::COMPILER_VERSION)),
+        (
+            "language_info",
+            Json::object([
+                ("name", Json::from("high")),
+                ("version", Json::from(artifact_metadata::COMPILER_VERSION)),
+                ("mimetype", Json::from("text/x-high")),
+                ("file_extension", Json::from(".high")),
+            ]),
+        ),
+    ])
+}
+
+/// 셀 결과의 표시 형식들. `text/plain` 은 항상 있고, 구조가 있는 값은 JSON 과 HTML 도 싣습니다.
+fn display_data(value: &Value) -> Json {
+    let mut entries = vec![("text/plain", Json::from(display_value(value)))];
+    let structured = match value {
+        Value::Integer(i) => Some(Json::Number(*i as f64)),
+        Value::Float(f) => Some(Json::Number(*f)),
+        Value::Boolean(b) => Some(Json::from(*b)),
+        Value::Null => Some(Json::Null),
+        Value::Error(e) => Some(Json::object([("error", Json::from(e.as_str()))])),
+        Value::Reflection(info) => {
+            entries.push((
+                "text/html",
+                Json::from(format!(
+                    "<table><tr><th>type</th><td>{}</td></tr><tr><th>details</th><td>{}</td></tr></table>",
+                    escape_html(&info.type_name),
+                    escape_html(&info.details)
+                )),
+            ));
+            Some(Json::object([
+                ("type_name", Json::from(info.type_name.as_str())),
+                ("details", Json::from(info.details.as_str())),
+            ]))
+        }
+        // HTML 조각처럼 보이는 문자열은 노트북이 그대로 그리게 합니다.
+        Value::String(s) => {
+            let trimmed = s.trim();
+            if trimmed.starts_with('<') && trimmed.ends_with('>') {
+                entries.push(("text/html", Json::from(trimmed)));
+            }
+            None
+        }
+        _ => None,
+    };
+    if let Some(structured) = structured {
+        entries.push(("application/json", structured));
+    }
+    Json::object(entries)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn response_json(id: &Json, response: Result<Json, RpcError>) -> Json {
+    let body = match response {
+        Ok(result) => ("result", result),
+        Err(error) => (
+            "error",
+            Json::object([("code", Json::Number(error.code as f64)), ("message", Json::from(error.message))]),
+        ),
+    };
+    Json::object([("jsonrpc", Json::from("2.0")), ("id", id.clone()), body])
+}
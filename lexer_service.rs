@@ -1,266 +1,562 @@
-use crate::data_structures::{Span, Token, TokenKind};
-
-pub struct LexerService<'a> {
-    source: &'a str,
-    chars: std::iter::Peekable<std::str::Chars<'a>>,
-    position: usize,
-    tokens: Vec<Token>,
-    index: usize,
-}
-
-impl<'a> LexerService<'a> {
-    pub fn new(source: &'a str) -> Self {
-        let mut lexer = Self {
-            source,
-            chars: source.chars().peekable(),
-            position: 0,
-            tokens: vec![],
-            index: 0,
-        };
-        lexer.tokens = lexer.tokenize();
-        lexer
-    }
-
-    pub fn next_token(&mut self) -> Token {
-        if self.index < self.tokens.len() {
-            let tok = self.tokens[self.index].clone();
-            self.index += 1;
-            tok
-        } else {
-            Token {
-                kind: TokenKind::Eof,
-                span: Span { start: self.position, end: self.position },
-            }
-        }
-    }
-
-    fn tokenize(&mut self) -> Vec<Token> {
-        let mut tokens = Vec::new();
-
-        while self.peek().is_some() {
-            self.skip_whitespace();
-            let start = self.position;
-
-            let current_char = match self.peek() {
-                Some(&c) => c,
-                None => break,
-            };
-
-            let token = match current_char {
-                c if c.is_alphabetic() || c == '_' => self.read_identifier_or_keyword(start),
-                c if c.is_digit(10) => self.read_number(start),
-                c => self.read_symbol(start, c),
-            };
-
-            tokens.push(token);
-        }
-
-        tokens.push(Token {
-            kind: TokenKind::Eof,
-            span: Span { start: self.position, end: self.position },
-        });
-
-        tokens
-    }
-
-    fn skip_whitespace(&mut self) {
-        while let Some(c) = self.peek() {
-            if c.is_whitespace() {
-                self.advance();
-            } else {
-                break;
-            }
-        }
-    }
-
-    fn advance(&mut self) -> Option<char> {
-        let next_char = self.chars.next();
-        if next_char.is_some() {
-            self.position += 1;
-        }
-        next_char
-    }
-
-    fn peek(&mut self) -> Option<&char> {
-        self.chars.peek()
-    }
-
-    fn read_identifier_or_keyword(&mut self, start: usize) -> Token {
-        let mut literal = String::new();
-
-        while let Some(c) = self.peek() {
-            if c.is_alphanumeric() || *c == '_' {
-                literal.push(self.advance().unwrap());
-            } else {
-                break;
-            }
-        }
-
-        let kind = match literal.as_str() {
-            "fn" => TokenKind::Fn,
-            "let" => TokenKind::Let,
-            "mut" => TokenKind::Mut,
-            "if" => TokenKind::If,
-            "else" => TokenKind::Else,
-            "while" => TokenKind::While,
-            "for" => TokenKind::For,
-            "return" => TokenKind::Return,
-            "match" => TokenKind::Match,
-            "macro" => TokenKind::Macro,
-            "type_of" => TokenKind::TypeOf,
-            "eval" => TokenKind::Eval,
-            "reflect" => TokenKind::Reflect,
-            "async" => TokenKind::Async,
-            "await" => TokenKind::Await,
-            "true" => TokenKind::BooleanLiteral(true),
-            "false" => TokenKind::BooleanLiteral(false),
-            "int" => TokenKind::Int,
-            "float" => TokenKind::Float,
-            "bool" => TokenKind::Bool,
-            "string" => TokenKind::String,
-            "void" => TokenKind::Void,
-            "any" => TokenKind::Any,
-            _ => TokenKind::Identifier(literal.clone()),
-        };
-
-        Token {
-            kind,
-            span: Span { start, end: self.position },
-        }
-    }
-
-    fn read_number(&mut self, start: usize) -> Token {
-        let mut literal = String::new();
-        let mut is_float = false;
-
-        while let Some(c) = self.peek() {
-            if c.is_digit(10) {
-                literal.push(self.advance().unwrap());
-            } else if *c == '.' {
-                is_float = true;
-                literal.push(self.advance().unwrap());
-            } else {
-                break;
-            }
-        }
-
-        let kind = if is_float {
-            TokenKind::FloatLiteral(literal.clone())
-        } else {
-            let value = literal.parse::<i64>().unwrap_or_default();
-            TokenKind::IntegerLiteral(value)
-        };
-
-        Token {
-            kind,
-            span: Span { start, end: self.position },
-        }
-    }
-
-    fn read_symbol(&mut self, start: usize, current_char: char) -> Token {
-        let kind = match current_char {
-            '=' => {
-                self.advance();
-                if self.peek() == Some(&'=') {
-                    self.advance();
-                    TokenKind::Eq
-                } else {
-                    TokenKind::Assign
-                }
-            }
-            '+' => {
-                self.advance();
-                if self.peek() == Some(&'=') {
-                    self.advance();
-                    TokenKind::PlusAssign
-                } else {
-                    TokenKind::Plus
-                }
-            }
-            '-' => {
-                self.advance();
-                if self.peek() == Some(&'=') {
-                    self.advance();
-                    TokenKind::MinusAssign
-                } else {
-                    TokenKind::Minus
-                }
-            }
-            '*' => { self.advance(); TokenKind::Asterisk }
-            '/' => { self.advance(); TokenKind::Slash }
-            '%' => { self.advance(); TokenKind::Percent }
-            '!' => {
-                self.advance();
-                if self.peek() == Some(&'=') {
-                    self.advance();
-                    TokenKind::Neq
-                } else {
-                    TokenKind::Bang
-                }
-            }
-            '&' => {
-                self.advance();
-                if self.peek() == Some(&'&') {
-                    self.advance();
-                    TokenKind::And
-                } else {
-                    TokenKind::BitAnd
-                }
-            }
-            '|' => {
-                self.advance();
-                if self.peek() == Some(&'|') {
-                    self.advance();
-                    TokenKind::Or
-                } else {
-                    TokenKind::BitOr
-                }
-            }
-            '^' => { self.advance(); TokenKind::BitXor }
-            '<' => {
-                self.advance();
-                if self.peek() == Some(&'<') {
-                    self.advance();
-                    TokenKind::ShiftLeft
-                } else if self.peek() == Some(&'=') {
-                    self.advance();
-                    TokenKind::LessEqual
-                } else {
-                    TokenKind::Less
-                }
-            }
-            '>' => {
-                self.advance();
-                if self.peek() == Some(&'>') {
-                    self.advance();
-                    TokenKind::ShiftRight
-                } else if self.peek() == Some(&'=') {
-                    self.advance();
-                    TokenKind::GreaterEqual
-                } else {
-                    TokenKind::Greater
-                }
-            }
-            '?' => { self.advance(); TokenKind::Question }
-            ':' => { self.advance(); TokenKind::Colon }
-            '{' => { self.advance(); TokenKind::LBrace }
-            '}' => { self.advance(); TokenKind::RBrace }
-            '(' => { self.advance(); TokenKind::LParen }
-            ')' => { self.advance(); TokenKind::RParen }
-            '[' => { self.advance(); TokenKind::LBracket }
-            ']' => { self.advance(); TokenKind::RBracket }
-            ',' => { self.advance(); TokenKind::Comma }
-            ';' => { self.advance(); TokenKind::Semicolon }
-            '.' => { self.advance(); TokenKind::Dot }
-            _ => {
-                self.advance();
-                TokenKind::Illegal(current_char)
-            }
-        };
-
-        Token {
-            kind,
-            span: Span { start, end: self.position },
-        }
-    }
-}
+use alloc::borrow::Cow;
+use alloc::rc::Rc;
+
+use crate::bytecode::Constant;
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, Span, Token, TokenKind};
+use crate::error_codes;
+use crate::interner::Symbol;
+
+/// 렉서 플러그인이 인식한 토큰. 파서는 이를 라이브러리 생성자 호출 `constructor(argument)` 로 바꿉니다.
+#[derive(Debug, Clone)]
+pub struct PluginToken {
+    /// 호출할 함수 이름 (프렐류드나 호스트 전역으로 제공)
+    pub constructor: String,
+    /// 생성자에 넘길 리터럴 인자. 키워드처럼 인자가 없으면 None 입니다.
+    pub argument: Option<Constant>,
+}
+
+/// 렉서 확장. 임베더가 렉서를 고치지 않고 DSL 용 키워드나 리터럴 문법을 더할 수 있습니다.
+///
+/// 예: `₩1000` 을 `won(1000)` 으로, `today` 키워드를 `today()` 로 바꾸는 플러그인
+pub trait LexerPlugin {
+    fn name(&self) -> &str;
+
+    /// 식별자 자리의 단어가 플러그인 키워드이면 그 토큰입니다. 내장 키워드보다 우선하지 않습니다.
+    fn keyword(&self, _word: &str) -> Option<PluginToken> {
+        None
+    }
+
+    /// `rest` (현재 위치부터의 소스) 맨 앞에서 리터럴을 인식하면 소비할 바이트 수와 토큰을 돌려줍니다.
+    /// 내장 규칙보다 먼저 시도하므로 인식하지 않는 입력에는 None 을 돌려줘야 합니다.
+    fn scan(&self, _rest: &str) -> Option<(usize, PluginToken)> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentKind {
+    /// `// ...` 줄 끝까지
+    Line,
+    /// `/* ... */`. 안에 다시 `/* */` 를 넣을 수 있습니다.
+    Block,
+}
+
+/// 주석. 토큰으로 내보내지 않지만 포매터나 문서 생성 같은 도구가 쓸 수 있게 위치와 본문을 남겨 둡니다.
+#[derive(Debug, Clone)]
+pub struct Comment<'a> {
+    pub kind: CommentKind,
+    /// 구분자를 포함한 범위
+    pub span: Span,
+    /// 구분자(`//`, `/*`, `*/`)를 뺀 본문
+    pub text: &'a str,
+}
+
+/// 토큰의 리터럴 텍스트는 소스를 복사하지 않고 빌려 씁니다.
+pub struct LexerService<'a> {
+    source: &'a str,
+    plugins: &'a [Rc<dyn LexerPlugin>],
+    chars: core::iter::Peekable<core::str::Chars<'a>>,
+    /// 문자 단위 위치 (Span 에 기록)
+    position: usize,
+    /// 바이트 단위 위치 (소스 조각을 자를 때 사용)
+    offset: usize,
+    tokens: Vec<Token<'a>>,
+    index: usize,
+    /// 닫히지 않은 문자열 같은 토큰 단위 오류 (E0110). 파서가 가져가 자기 진단에 합칩니다.
+    diagnostics: Vec<Diagnostic>,
+    comments: Vec<Comment<'a>>,
+}
+
+impl<'a> LexerService<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self::with_plugins(source, &[])
+    }
+
+    /// 등록된 플러그인의 키워드와 리터럴 문법을 함께 인식하는 렉서를 만듭니다.
+    pub fn with_plugins(source: &'a str, plugins: &'a [Rc<dyn LexerPlugin>]) -> Self {
+        let mut lexer = Self {
+            source,
+            plugins,
+            chars: source.chars().peekable(),
+            position: 0,
+            offset: 0,
+            tokens: vec![],
+            index: 0,
+            diagnostics: vec![],
+            comments: vec![],
+        };
+        lexer.tokens = lexer.tokenize();
+        lexer
+    }
+
+    pub fn next_token(&mut self) -> Token<'a> {
+        if self.index < self.tokens.len() {
+            let tok = self.tokens[self.index].clone();
+            self.index += 1;
+            tok
+        } else {
+            Token {
+                kind: TokenKind::Eof,
+                span: Span { start: self.position, end: self.position },
+            }
+        }
+    }
+
+    /// 소스에 나온 순서의 주석
+    pub fn comments(&self) -> &[Comment<'a>] {
+        &self.comments
+    }
+
+    /// 토큰화하면서 모은 진단을 꺼냅니다.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        core::mem::take(&mut self.diagnostics)
+    }
+
+    /// 파일 끝까지 닫는 `"` 가 없는 리터럴. 여는 따옴표를 가리킵니다. (`b"` 는 `b` 부터)
+    fn unterminated(&mut self, start: usize, quote: usize) {
+        let code = &error_codes::SYNTAX_ERROR;
+        let diagnostic = code.diagnostic(Span { start, end: quote + 1 }, "unterminated_string", &[]);
+        self.diagnostics.push(diagnostic.with_help(code.message("unterminated_string_help", &[])));
+    }
+
+    fn tokenize(&mut self) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+
+        while self.peek().is_some() {
+            self.skip_trivia();
+            let start = self.position;
+
+            let current_char = match self.peek() {
+                Some(&c) => c,
+                None => break,
+            };
+
+            if let Some(token) = self.read_plugin_literal(start) {
+                tokens.push(token);
+                continue;
+            }
+
+            let token = match current_char {
+                'b' if self.chars.clone().nth(1) == Some('"') => self.read_bytes(start),
+                c if c.is_alphabetic() || c == '_' => self.read_identifier_or_keyword(start),
+                c if c.is_digit(10) => self.read_number(start),
+                '"' => self.read_string(start),
+                c => self.read_symbol(start, c),
+            };
+
+            tokens.push(token);
+        }
+
+        tokens.push(Token {
+            kind: TokenKind::Eof,
+            span: Span { start: self.position, end: self.position },
+        });
+
+        tokens
+    }
+
+    /// 공백과 주석을 건너뜁니다. 주석은 `comments` 에 남깁니다.
+    fn skip_trivia(&mut self) {
+        loop {
+            self.take_while(char::is_whitespace);
+            if self.peek() != Some(&'/') {
+                return;
+            }
+            match self.chars.clone().nth(1) {
+                Some('/') => self.read_line_comment(),
+                Some('*') => self.read_block_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn read_line_comment(&mut self) {
+        let start = self.position;
+        self.advance();
+        self.advance();
+        let text = self.take_while(|c| c != '\n');
+        self.comments.push(Comment { kind: CommentKind::Line, span: Span { start, end: self.position }, text });
+    }
+
+    /// 중첩된 `/* */` 는 짝을 맞춰 넘깁니다. 닫히지 않으면 파일 끝까지가 주석이고 E0110 입니다.
+    fn read_block_comment(&mut self) {
+        let start = self.position;
+        self.advance();
+        self.advance();
+        let from = self.offset;
+        let mut depth = 1usize;
+        let mut to = None;
+        while depth > 0 {
+            let before = self.offset;
+            match self.advance() {
+                None => break,
+                Some('/') if self.peek() == Some(&'*') => {
+                    self.advance();
+                    depth += 1;
+                }
+                Some('*') if self.peek() == Some(&'/') => {
+                    self.advance();
+                    depth -= 1;
+                    to = Some(before);
+                }
+                Some(_) => {}
+            }
+        }
+        let text = &self.source[from..to.filter(|_| depth == 0).unwrap_or(self.offset)];
+        self.comments.push(Comment { kind: CommentKind::Block, span: Span { start, end: self.position }, text });
+        if depth > 0 {
+            let code = &error_codes::SYNTAX_ERROR;
+            let diagnostic = code.diagnostic(Span { start, end: start + 2 }, "unterminated_comment", &[]);
+            self.diagnostics.push(diagnostic.with_help(code.message("unterminated_comment_help", &[])));
+        }
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let next_char = self.chars.next();
+        if let Some(c) = next_char {
+            self.position += 1;
+            self.offset += c.len_utf8();
+        }
+        next_char
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.chars.peek()
+    }
+
+    /// 조건을 만족하는 동안 문자를 소비하고, 소비한 소스 조각을 돌려줍니다.
+    fn take_while(&mut self, mut accept: impl FnMut(char) -> bool) -> &'a str {
+        let from = self.offset;
+        while let Some(&c) = self.peek() {
+            if !accept(c) {
+                break;
+            }
+            self.advance();
+        }
+        &self.source[from..self.offset]
+    }
+
+    /// 플러그인이 현재 위치의 리터럴을 인식하면 그만큼 소비합니다. 문자 경계가 아닌 길이는 무시합니다.
+    fn read_plugin_literal(&mut self, start: usize) -> Option<Token<'a>> {
+        let rest = &self.source[self.offset..];
+        let (len, token) = self.plugins.iter().find_map(|p| p.scan(rest))?;
+        if len == 0 || len > rest.len() || !rest.is_char_boundary(len) {
+            return None;
+        }
+        let end = self.offset + len;
+        while self.offset < end {
+            self.advance();
+        }
+        Some(Token { kind: plugin_kind(token), span: Span { start, end: self.position } })
+    }
+
+    fn read_identifier_or_keyword(&mut self, start: usize) -> Token<'a> {
+        let literal = self.take_while(|c| c.is_alphanumeric() || c == '_');
+
+        let kind = match literal {
+            "fn" => TokenKind::Fn,
+            "let" => TokenKind::Let,
+            "mut" => TokenKind::Mut,
+            "if" => TokenKind::If,
+            "else" => TokenKind::Else,
+            "while" => TokenKind::While,
+            "for" => TokenKind::For,
+            "in" => TokenKind::In,
+            "return" => TokenKind::Return,
+            "match" => TokenKind::Match,
+            "macro" => TokenKind::Macro,
+            "type" => TokenKind::Type,
+            "enum" => TokenKind::Enum,
+            "type_of" => TokenKind::TypeOf,
+            "eval" => TokenKind::Eval,
+            "reflect" => TokenKind::Reflect,
+            "async" => TokenKind::Async,
+            "await" => TokenKind::Await,
+            "yield" => TokenKind::Yield,
+            "defer" => TokenKind::Defer,
+            "true" => TokenKind::BooleanLiteral(true),
+            "false" => TokenKind::BooleanLiteral(false),
+            "int" => TokenKind::Int,
+            "float" => TokenKind::Float,
+            "bool" => TokenKind::Bool,
+            "string" => TokenKind::String,
+            "void" => TokenKind::Void,
+            "any" => TokenKind::Any,
+            word => match self.plugins.iter().find_map(|p| p.keyword(word)) {
+                Some(token) => plugin_kind(token),
+                None => TokenKind::Identifier(Symbol::intern(word)),
+            },
+        };
+
+        Token {
+            kind,
+            span: Span { start, end: self.position },
+        }
+    }
+
+    fn read_number(&mut self, start: usize) -> Token<'a> {
+        let from = self.offset;
+        let mut is_float = false;
+        loop {
+            match self.peek().copied() {
+                Some(c) if c.is_ascii_digit() => {}
+                // `0..10` 의 `..` 는 소수점이 아니라 범위 연산자입니다.
+                Some('.') if !self.source[self.offset + 1..].starts_with('.') => is_float = true,
+                _ => break,
+            }
+            self.advance();
+        }
+        let literal = &self.source[from..self.offset];
+
+        // `1e3`, `10px` 처럼 숫자에 바로 붙은 글자를 식별자로 떼어 읽으면 `1; e3;` 처럼 다른 뜻이 됩니다.
+        if self.peek().is_some_and(|&c| c.is_alphabetic() || c == '_') {
+            let suffix = self.take_while(|c| c.is_alphanumeric() || c == '_');
+            let code = &error_codes::SYNTAX_ERROR;
+            let diagnostic = code.diagnostic(Span { start, end: self.position }, "number_suffix", &[&literal, &suffix]);
+            self.diagnostics.push(diagnostic.with_help(code.message("number_suffix_help", &[])));
+        }
+
+        let kind = if is_float {
+            TokenKind::FloatLiteral(literal)
+        } else {
+            // 숫자만 있으므로 실패하는 경우는 범위를 넘는 것뿐입니다. 0 으로 읽고 계속하되 오류로 남깁니다.
+            let value = literal.parse::<i64>().unwrap_or_else(|_| {
+                let code = &error_codes::SYNTAX_ERROR;
+                let diagnostic = code.diagnostic(Span { start, end: self.position }, "integer_too_large", &[&literal]);
+                self.diagnostics.push(diagnostic.with_help(code.message("integer_too_large_help", &[])));
+                0
+            });
+            TokenKind::IntegerLiteral(value)
+        };
+
+        Token {
+            kind,
+            span: Span { start, end: self.position },
+        }
+    }
+
+    /// 이스케이프가 없는 문자열은 소스 조각을 빌리고, 첫 `\\` 를 만나면 그때부터 소유한 문자열로 옮겨 처리합니다.
+    fn read_string(&mut self, start: usize) -> Token<'a> {
+        self.advance(); // consume opening '"'
+        let literal = self.take_while(|c| c != '"' && c != '\\');
+        let mut text = Cow::Borrowed(literal);
+        let mut closed = false;
+
+        while let Some(c) = self.advance() {
+            match c {
+                '"' => {
+                    closed = true;
+                    break;
+                }
+                '\\' => {
+                    let escaped = self.advance();
+                    let owned = text.to_mut();
+                    match escaped {
+                        Some('n') => owned.push('\n'),
+                        Some('t') => owned.push('\t'),
+                        Some('r') => owned.push('\r'),
+                        Some('0') => owned.push('\0'),
+                        Some(e @ ('\\' | '"')) => owned.push(e),
+                        // 알 수 없는 이스케이프는 적힌 그대로 둡니다.
+                        Some(other) => {
+                            owned.push('\\');
+                            owned.push(other);
+                        }
+                        None => owned.push('\\'),
+                    }
+                }
+                other => text.to_mut().push(other),
+            }
+        }
+        if !closed {
+            self.unterminated(start, start);
+        }
+
+        Token {
+            kind: TokenKind::StringLiteral(text),
+            span: Span { start, end: self.position },
+        }
+    }
+
+    /// `b"..."`: 문자열과 같은 이스케이프에 `\xHH` 를 더합니다. 그 밖의 문자는 UTF-8 바이트로 들어갑니다.
+    fn read_bytes(&mut self, start: usize) -> Token<'a> {
+        self.advance(); // consume 'b'
+        self.advance(); // consume opening '"'
+        let mut bytes = vec![];
+        let mut closed = false;
+        while let Some(c) = self.advance() {
+            match c {
+                '"' => {
+                    closed = true;
+                    break;
+                }
+                '\\' => match self.advance() {
+                    Some('n') => bytes.push(b'\n'),
+                    Some('t') => bytes.push(b'\t'),
+                    Some('r') => bytes.push(b'\r'),
+                    Some('0') => bytes.push(0),
+                    Some(e @ ('\\' | '"')) => bytes.push(e as u8),
+                    Some('x') => {
+                        let mut digits = String::new();
+                        while let Some(&d) = self.peek().filter(|d| d.is_ascii_hexdigit() && digits.len() < 2) {
+                            digits.push(d);
+                            self.advance();
+                        }
+                        match u8::from_str_radix(&digits, 16) {
+                            Ok(byte) if digits.len() == 2 => bytes.push(byte),
+                            // 두 자리가 아니면 적힌 그대로 둡니다.
+                            _ => {
+                                bytes.extend_from_slice(b"\\x");
+                                bytes.extend_from_slice(digits.as_bytes());
+                            }
+                        }
+                    }
+                    Some(other) => {
+                        bytes.push(b'\\');
+                        bytes.extend_from_slice(other.encode_utf8(&mut [0; 4]).as_bytes());
+                    }
+                    None => bytes.push(b'\\'),
+                },
+                other => bytes.extend_from_slice(other.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+        if !closed {
+            self.unterminated(start, start + 1);
+        }
+
+        Token {
+            kind: TokenKind::BytesLiteral(bytes),
+            span: Span { start, end: self.position },
+        }
+    }
+
+    fn read_symbol(&mut self, start: usize, current_char: char) -> Token<'a> {
+        let kind = match current_char {
+            '=' => {
+                self.advance();
+                if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::Eq
+                } else if self.peek() == Some(&'>') {
+                    self.advance();
+                    TokenKind::Arrow
+                } else {
+                    TokenKind::Assign
+                }
+            }
+            '+' => {
+                self.advance();
+                if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::PlusAssign
+                } else {
+                    TokenKind::Plus
+                }
+            }
+            '-' => {
+                self.advance();
+                if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::MinusAssign
+                } else {
+                    TokenKind::Minus
+                }
+            }
+            '*' => { self.advance(); TokenKind::Asterisk }
+            '/' => { self.advance(); TokenKind::Slash }
+            '%' => { self.advance(); TokenKind::Percent }
+            '!' => {
+                self.advance();
+                if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::Neq
+                } else {
+                    TokenKind::Bang
+                }
+            }
+            '&' => {
+                self.advance();
+                if self.peek() == Some(&'&') {
+                    self.advance();
+                    TokenKind::And
+                } else {
+                    TokenKind::BitAnd
+                }
+            }
+            '|' => {
+                self.advance();
+                if self.peek() == Some(&'|') {
+                    self.advance();
+                    TokenKind::Or
+                } else {
+                    TokenKind::BitOr
+                }
+            }
+            '^' => { self.advance(); TokenKind::BitXor }
+            '~' => { self.advance(); TokenKind::Tilde }
+            '<' => {
+                self.advance();
+                if self.peek() == Some(&'<') {
+                    self.advance();
+                    TokenKind::ShiftLeft
+                } else if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::LessEqual
+                } else {
+                    TokenKind::Less
+                }
+            }
+            '>' => {
+                self.advance();
+                if self.peek() == Some(&'>') {
+                    self.advance();
+                    TokenKind::ShiftRight
+                } else if self.peek() == Some(&'=') {
+                    self.advance();
+                    TokenKind::GreaterEqual
+                } else {
+                    TokenKind::Greater
+                }
+            }
+            '?' => { self.advance(); TokenKind::Question }
+            ':' => { self.advance(); TokenKind::Colon }
+            '{' => { self.advance(); TokenKind::LBrace }
+            '}' => { self.advance(); TokenKind::RBrace }
+            '(' => { self.advance(); TokenKind::LParen }
+            ')' => { self.advance(); TokenKind::RParen }
+            '[' => { self.advance(); TokenKind::LBracket }
+            ']' => { self.advance(); TokenKind::RBracket }
+            ',' => { self.advance(); TokenKind::Comma }
+            ';' => { self.advance(); TokenKind::Semicolon }
+            '.' => {
+                self.advance();
+                if self.peek() == Some(&'.') {
+                    self.advance();
+                    if self.peek() == Some(&'=') {
+                        self.advance();
+                        TokenKind::DotDotEq
+                    } else {
+                        TokenKind::DotDot
+                    }
+                } else {
+                    TokenKind::Dot
+                }
+            }
+            '@' => { self.advance(); TokenKind::At }
+            _ => {
+                self.advance();
+                TokenKind::Illegal(current_char)
+            }
+        };
+
+        Token {
+            kind,
+            span: Span { start, end: self.position },
+        }
+    }
+}
+
+fn plugin_kind(token: PluginToken) -> TokenKind<'static> {
+    TokenKind::Custom { constructor: Symbol::intern(&token.constructor), argument: token.argument }
+}
@@ -1,23 +1,172 @@
-// src/lib.rs
-// High Programming Language Compiler/Interpreter의 루트 모듈 정의입니다.
-
-pub mod data_structures;
-pub mod lexer_service;
-pub mod parser_service;
-pub mod ft_runtime;
-pub mod analyzer_service; 
-pub mod executor_service; 
-pub mod blockchain; // Hargo-Chain 모듈 추가
-pub mod compiler_services;
-pub mod optimizer;
-
-pub mod ir_generator;      // ✅ IR 생성기 모듈
-pub mod native_codegen;    // ✅ 네이티브 코드 생성기 모듈
-
-
-// 자주 사용되는 타입들을 루트 모듈에서 직접 사용할 수 있도록 export 합니다.
-pub use data_structures::{Diagnostic, DiagnosticLevel, Program, Value};
-pub use blockchain::{Block, Blockchain};
-pub use analyzer_service::{AnalysisResult, AnalysisError, AnalyzerService};
-pub use executor_service::{ExecutionRequest, ExecutionResult, ExecutorService};
-pub use compiler_services::{CompileRequest, CompileOptions, CompileResult, CompilerService};
+// src/lib.rs
+// High Programming Language Compiler/Interpreter의 루트 모듈 정의입니다.
+//
+// 안정 공개 API 는 `api` 모듈뿐입니다. 나머지 모듈은 컴파일러 바이너리와 도구가 쓰는 내부 구현이라
+// 문서에서 숨기며, 버전 사이에 예고 없이 바뀔 수 있습니다.
+//
+// Cargo 기능 (임베딩할 때 필요한 것만 켭니다):
+//
+//   [dependencies]
+//   tokio     = { version = "1", features = ["full"], optional = true }
+//   hashbrown = "0.15"
+//   spin      = { version = "0.9", default-features = false, features = ["spin_mutex", "once"] }
+//   regex     = { version = "1", optional = true }
+//
+//   [features]
+//   default     = ["cli"]
+//   std         = []                  # 외부 프로세스 실행, 환경 변수 로케일, 백그라운드 계층 컴파일
+//   interpreter = ["std"]             # Engine, high test/bench, api::{check, run, format}
+//   analyzer    = ["std", "tokio"]    # 외부 분석 서버 클라이언트 (analyzer_service)
+//   blockchain  = ["std"]             # Hargo-Chain 증명 블록
+//   native      = ["std"]             # HIR/IR, NASM·LLVM·Rust 백엔드, 외부 도구, 실행 파일 메타데이터
+//   regex       = ["std", "dep:regex"]  # regex_match/regex_find_all/regex_replace 내장 함수
+//   cli         = ["interpreter", "analyzer", "blockchain", "native", "regex", "tokio"]
+//                                     # CompilerService, 실행기, Python 백엔드, serve/daemon, bundle, api::compile
+//
+//   [[bin]]
+//   name = "high"
+//   required-features = ["cli"]
+//
+//   [[bin]]
+//   name = "high-wasi"               # 표준 입출력 CLI (wasi_main.rs)
+//   path = "wasi_main.rs"
+//   required-features = ["interpreter"]
+//
+// wasm32-wasi 용으로는 `--no-default-features --features interpreter` 로 빌드합니다. 하위 프로세스와
+// 네트워크가 필요한 native/analyzer/cli 기능은 WASI 에서 쓸 수 없습니다.
+//
+// 기능 없이(`--no-default-features`) 빌드하면 렉서, 파서, 분석 패스, 최적화기, 트리 순회 런타임과
+// 바이트코드 VM 만 남습니다. 이 코어는 `no_std` + alloc 이라 마이크로컨트롤러나 WASI 없는 WASM 에서도
+// 돌며, tokio 에 의존하지 않습니다. 코어 모듈은 std 대신 `compat` 을 통해 alloc/hashbrown 을 씁니다.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(target_os = "wasi", any(feature = "native", feature = "analyzer", feature = "cli")))]
+compile_error!("wasm32-wasi 빌드는 `--no-default-features --features interpreter` 만 지원합니다 (하위 프로세스/네트워크 없음).");
+
+#[cfg(feature = "interpreter")]
+pub mod api;            // 안정 공개 API (semver facade)
+
+#[doc(hidden)] pub mod compat;         // no_std(alloc) 빌드용 std 대체물
+#[doc(hidden)] pub mod data_structures;
+#[doc(hidden)] pub mod interner;       // 식별자 인터닝 (Symbol)
+#[doc(hidden)] pub mod lexer_service;
+#[doc(hidden)] pub mod parser_service;
+#[doc(hidden)] pub mod precedence;     // 중위 연산자 우선순위 표 (사용자 정의 연산자)
+#[doc(hidden)] pub mod ft_runtime;
+#[doc(hidden)] pub mod patterns;       // match 패턴 매칭 및 분석
+#[doc(hidden)] pub mod generator;      // yield 제너레이터 실행 상태
+#[doc(hidden)] pub mod input;          // read_line/read_all 이 읽는 표준 입력
+#[doc(hidden)] pub mod exit_code;      // 프로그램 종료 코드 규칙 (최상위 return → high run 의 종료 코드)
+#[doc(hidden)] pub mod operators;      // 연산자 의미 (런타임/VM/상수 접기 공용)
+#[doc(hidden)] pub mod bytecode;       // 함수 본문 바이트코드와 VM
+#[doc(hidden)] pub mod tiering;        // 계층형 실행 (인터프리터 → 바이트코드)
+#[doc(hidden)] pub mod snapshot;       // 런타임 상태 스냅샷과 복원 (체크포인트/롤백)
+#[doc(hidden)] pub mod heap;           // 제너레이터 순환 수집기(GC)와 수집 통계
+#[doc(hidden)] pub mod bytes;          // 바이트 버퍼 값: 인덱싱, 자르기, 16진수 변환
+#[doc(hidden)] pub mod arrays;         // 배열 값: 인덱싱, 자르기, 요소별 비교
+#[doc(hidden)] pub mod maps;           // 맵 값: 넣은 순서를 기억하는 해시 맵과 키
+#[doc(hidden)] pub mod ranges;         // 정수 범위 값과 for-in 반복 프로토콜
+#[doc(hidden)] pub mod regexp;         // 정규식 내장 함수와 매치 값 (엔진은 regex 기능)
+#[doc(hidden)] pub mod datetime;       // 날짜/시간 값과 기간, ISO-8601·strftime 식 포맷
+#[doc(hidden)] pub mod coverage;       // 문장 단위 커버리지 카운터와 lcov/JSON 보고서
+#[doc(hidden)] pub mod trace;          // 시간 여행 디버깅용 문장 실행 기록과 high trace view
+#[doc(hidden)] pub mod profile;        // 프로필 기반 최적화용 분기·루프·호출 횟수와 프로필 파일
+#[cfg(feature = "interpreter")]
+#[doc(hidden)] pub mod benchmarks;     // 런타임 벤치마크 고정 입력 (high bench)
+#[cfg(feature = "interpreter")]
+#[doc(hidden)] pub mod engine;         // 임베딩 API (전역 값/프렐류드 주입)
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod bundle;         // 프로그램과 인터프리터를 묶은 단일 실행 파일 (high bundle)
+#[doc(hidden)] pub mod cancellation;   // 컴파일/실행 취소 토큰과 외부 도구 실행
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod reproducible;   // --reproducible: 고정 시간 증명 블록과 재컴파일 비교
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod stage_timeouts; // 컴파일 단계별 제한 시간
+#[doc(hidden)] pub mod error_codes;    // 진단 코드 레지스트리 (high explain)
+#[doc(hidden)] pub mod messages;       // 진단 메시지 카탈로그 (ko/en)
+#[doc(hidden)] pub mod diagnostic_renderer; // 색상/소스 조각 진단 출력
+#[doc(hidden)] pub mod formatting;     // format() 내장 함수 서식 처리
+#[doc(hidden)] pub mod pretty;         // 순환·깊이 제한이 있는 런타임 값 덤프 (REPL, reflect, 디버거)
+#[cfg(feature = "interpreter")]
+#[doc(hidden)] pub mod test_runner;    // @test 실행 및 실패 보고 (high test)
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod mutate;         // high mutate: AST 변이체마다 @test 를 다시 실행하는 뮤테이션 테스트
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod navigation;     // high refs: 참조 찾기와 호출 계층
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod completion;     // 편집기용 자동 완성 후보 (데몬 completion)
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod refactor;       // high refactor: 이름 바꾸기 / 함수 추출 텍스트 편집
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod semantic_tokens; // 편집기용 의미 기반 토큰 분류와 HTML 하이라이트 (high highlight)
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod verify;         // high verify: 인터프리터와 컴파일 백엔드의 실행 결과 비교
+#[cfg(feature = "analyzer")]
+#[doc(hidden)] pub mod analyzer_service; 
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod executor_service; 
+#[cfg(feature = "blockchain")]
+#[doc(hidden)] pub mod blockchain; // Hargo-Chain 모듈 추가
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod compiler_services;
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod playground_server; // high serve: 웹 플레이그라운드용 HTTP 엔드포인트
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod json;           // 서버/데몬 프로토콜용 JSON 값
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod daemon;         // high daemon: JSON-RPC 컴파일러 데몬과 클라이언트
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod config;         // high config: 설정 파일/환경 변수/플래그 계층
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod kernel;         // high kernel: 셀 단위로 실행하는 노트북 커널 (표준 입출력 JSON-RPC)
+#[doc(hidden)] pub mod optimizer;
+#[doc(hidden)] pub mod attributes;     // @name(args) 어트리뷰트
+#[doc(hidden)] pub mod resolver;       // 이름 해석 및 심볼 테이블
+#[doc(hidden)] pub mod call_graph;     // 호출 그래프와 재귀(SCC) 분석
+#[doc(hidden)] pub mod dead_code;      // 프로그램 전체 도달성: 쓰이지 않는 함수/모듈 경고와 --strip-dead
+#[doc(hidden)] pub mod division_check; // 컴파일 시점에 증명되는 0 나누기 경고 (E0033)
+#[doc(hidden)] pub mod unused_value; // 쓰이지 않는 순수 식 문장 경고 (E0104)
+#[doc(hidden)] pub mod named_types;    // `type` 별칭과 사용자 정의 타입 표기 해석 (E0011, E0012)
+#[doc(hidden)] pub mod escape_analysis; // 클로저 탈출 분석 (스택 할당)
+#[doc(hidden)] pub mod effects;        // 순수성/효과 분석 (DCE, LICM, 상수 계산)
+#[doc(hidden)] pub mod conditional_compilation; // @cfg 조건부 컴파일
+#[doc(hidden)] pub mod macro_expander; // 매크로 확장 단계
+#[doc(hidden)] pub mod stdlib;         // High 로 작성된 표준 라이브러리 (stdlib/*.high) 링크
+#[doc(hidden)] pub mod ast_printer;    // AST → High 소스 출력
+#[doc(hidden)] pub mod visitor;        // AST 순회 트레이트 (Visitor / MutVisitor)
+#[doc(hidden)] pub mod node_ids;       // AST 노드 번호와 분석 결과 표 (side table)
+
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod hir;               // ✅ 타입이 붙은 고수준 중간 표현 (파스 트리 → HIR)
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod ir_generator;      // ✅ IR 생성기 모듈
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod ir_simplify;       // IR 생성 전 HIR 상수 접기·대수 간소화·상수 분기 제거
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod rust_emitter_service; // ✅ Rust 백엔드 (HIR → cargo 프로젝트)
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub mod py_emitter_service;   // ✅ Python 3 백엔드 (교육 환경용)
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod native_codegen;    // ✅ 네이티브 코드 생성기 모듈
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod toolchain;         // ✅ 외부 도구(NASM/링커) 실행과 출력 진단
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod llvm_codegen;      // ✅ 텍스트 LLVM IR 출력 (clang 으로 빌드)
+#[cfg(feature = "native")]
+#[doc(hidden)] pub mod artifact_metadata; // ✅ 실행 파일 메타데이터 스탬핑
+
+
+// 자주 사용되는 타입들을 루트 모듈에서 직접 사용할 수 있도록 export 합니다.
+// 기존 사용자를 위해 남겨 두지만 안정 API 가 아니므로, 새 코드는 `api` 를 쓰세요.
+#[doc(hidden)] pub use data_structures::{Diagnostic, DiagnosticLevel, Program, Value};
+#[cfg(feature = "blockchain")]
+#[doc(hidden)] pub use blockchain::{Block, Blockchain};
+#[cfg(feature = "analyzer")]
+#[doc(hidden)] pub use analyzer_service::{AnalysisResult, AnalysisError, Analyzer, AnalyzerService, FunctionReport};
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub use executor_service::{ExecutionRequest, ExecutionResult, ExecutorService};
+#[cfg(feature = "cli")]
+#[doc(hidden)] pub use compiler_services::{BatchResult, BatchSummary, CompileRequest, CompileOptions, CompileResult, CompilerService};
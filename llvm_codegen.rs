@@ -0,0 +1,278 @@
+// llvm_codegen.rs
+// IRModule 을 텍스트 LLVM IR(.ll)로 출력하는 코드 생성기입니다.
+//
+// LLVM 라이브러리에 링크하지 않고 .ll 텍스트만 만듭니다. clang 이 있으면 NASM 없이
+// `clang -O2 compiled.ll -o compiled` 로 LLVM 최적화를 거친 실행 파일을 얻을 수 있습니다.
+// 포인터는 opaque `ptr` 로 쓰므로 LLVM 15 이상이 필요합니다.
+//
+// 네이티브 코드 생성기와 같은 IR 의미를 따릅니다: `abort` 는 메시지를 stderr 에 쓰고 134 로 종료,
+// `return` 은 0 으로 종료, 스택 클로저는 현재 프레임에 16바이트 자리를 잡습니다. 정수 스택 명령은 SSA 값으로,
+// 바인딩은 `@var.k` 전역으로 내리며 `sdiv`/`srem` 앞에서 나누는 수 0 (패닉) 과 -1 (감김) 을 따로 처리합니다.
+// 타깃 CPU 와 기능은 `main` 의 함수 속성으로, 메타데이터는 `.high_meta` 섹션의 상수로 남깁니다.
+
+use std::fmt::Write as _;
+
+use crate::ir_generator::{IRInstruction, IRModule};
+use crate::native_codegen::{CodegenConfig, TargetOs};
+
+/// 이 코드 생성기를 고르는 `target_platform` 값
+pub const TARGET: &str = "llvm";
+
+/// 생성된 LLVM IR 을 쓰는 파일
+pub const OUTPUT_PATH: &str = "compiled.ll";
+
+/// 스택에 놓이는 클로저 하나의 크기 (native_codegen 과 같음)
+const CLOSURE_SIZE: usize = 16;
+
+pub fn generate_llvm_ir(ir: &IRModule, config: &CodegenConfig, metadata: &[(String, String)]) -> String {
+    let mut body = String::new();
+    let mut messages: Vec<String> = vec![];
+    let mut closures = 0;
+    let mut blocks = 0;
+    let mut integers = IntegerLowering::default();
+    // 종료 명령(ret, unreachable) 뒤의 명령은 새 기본 블록에 들어가야 합니다.
+    let mut terminated = false;
+
+    for instr in &ir.instructions {
+        if terminated {
+            blocks += 1;
+            let _ = writeln!(body, "bb.{}:", blocks);
+            terminated = false;
+        }
+        match instr.opcode.as_str() {
+            "abort" => {
+                body.push_str(&abort_code(&mut messages, &instr.operands[0]));
+                terminated = true;
+            }
+            "let" => {
+                let _ = writeln!(body, "  ; let {} = {}", comment(&instr.operands[0]), comment(&instr.operands[1]));
+            }
+            // 클로저 = 코드 주소 + 환경 포인터. 탈출하지 않는 클로저는 현재 프레임에 자리를 잡습니다.
+            "alloc" if instr.operands[1] == "stack" => {
+                let _ = writeln!(
+                    body,
+                    "  %closure.{} = alloca [{} x i8], align 16 ; let {} = {} (stack)",
+                    closures,
+                    CLOSURE_SIZE,
+                    comment(&instr.operands[0]),
+                    comment(&instr.operands[2])
+                );
+                closures += 1;
+            }
+            "alloc" => {
+                // 네이티브 런타임에는 아직 힙 할당기가 없으므로 자리만 표시합니다.
+                let _ = writeln!(
+                    body,
+                    "  ; let {} = {} (heap)",
+                    comment(&instr.operands[0]),
+                    comment(&instr.operands[2])
+                );
+            }
+            "push" | "load" | "neg" | "binary" | "select" | "store" => {
+                integers.lower(instr, &mut messages, &mut body);
+            }
+            "return" => {
+                body.push_str("  ret i32 0\n");
+                terminated = true;
+            }
+            _ => {}
+        }
+    }
+    if !terminated {
+        body.push_str("  ret i32 0\n");
+    }
+
+    let mut module = String::from("; High 컴파일러가 생성한 LLVM IR\n; 빌드: clang -O2 compiled.ll -o compiled\n\n");
+    for message in &messages {
+        module.push_str(message);
+        module.push('\n');
+    }
+    for (k, name) in integers.variables.iter().enumerate() {
+        let _ = writeln!(module, "@var.{} = internal global i64 0 ; {}", k, comment(name));
+    }
+    let mut entries = metadata.to_vec();
+    entries.extend(config.metadata_entries());
+    module.push_str(&metadata_global(config.os, &entries));
+
+    module.push_str("\ndeclare i64 @write(i32, ptr, i64)\ndeclare void @exit(i32) noreturn\n\n");
+    module.push_str("define i32 @main() #0 {\nentry:\n");
+    module.push_str(&body);
+    module.push_str("}\n\n");
+
+    let features: Vec<String> = config.features.iter().map(|f| format!("+{}", f)).collect();
+    let _ = writeln!(
+        module,
+        "attributes #0 = {{ \"target-cpu\"=\"{}\" \"target-features\"=\"{}\" }}",
+        config.target_cpu,
+        features.join(",")
+    );
+    module
+}
+
+/// 메시지를 stderr 에 쓰고 134 로 끝내는 코드. 기본 블록을 닫습니다.
+fn abort_code(messages: &mut Vec<String>, text: &str) -> String {
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.push(b'\n');
+    let index = messages.len();
+    messages.push(format!(
+        "@.msg.{} = private unnamed_addr constant [{} x i8] c\"{}\"",
+        index,
+        bytes.len(),
+        escape_bytes(&bytes)
+    ));
+    format!(
+        "  call i64 @write(i32 2, ptr @.msg.{}, i64 {})\n  call void @exit(i32 134)\n  unreachable\n",
+        index,
+        bytes.len()
+    )
+}
+
+/// IR 의 정수 스택 명령을 SSA 값으로 내립니다. 스택에는 값의 이름(`%int.N`)이나 상수가 쌓입니다.
+#[derive(Default)]
+struct IntegerLowering {
+    stack: Vec<String>,
+    variables: Vec<String>,
+    values: usize,
+}
+
+impl IntegerLowering {
+    fn lower(&mut self, instr: &IRInstruction, messages: &mut Vec<String>, body: &mut String) {
+        match instr.opcode.as_str() {
+            "push" => self.stack.push(instr.operands[0].clone()),
+            "load" => {
+                let slot = self.variable(&instr.operands[0]);
+                let value = self.emit(body, format!("load i64, ptr @var.{}", slot));
+                self.stack.push(value);
+            }
+            "store" => {
+                let value = self.pop();
+                let slot = self.variable(&instr.operands[0]);
+                let _ = writeln!(body, "  store i64 {}, ptr @var.{}", value, slot);
+            }
+            "neg" => {
+                let operand = self.pop();
+                let value = self.emit(body, format!("sub i64 0, {}", operand));
+                self.stack.push(value);
+            }
+            "binary" => {
+                let right = self.pop();
+                let left = self.pop();
+                let value = match instr.operands[0].as_str() {
+                    "+" => self.emit(body, format!("add i64 {}, {}", left, right)),
+                    "-" => self.emit(body, format!("sub i64 {}, {}", left, right)),
+                    "*" => self.emit(body, format!("mul i64 {}, {}", left, right)),
+                    "&" => self.emit(body, format!("and i64 {}, {}", left, right)),
+                    "|" => self.emit(body, format!("or i64 {}, {}", left, right)),
+                    "^" => self.emit(body, format!("xor i64 {}, {}", left, right)),
+                    // LLVM 은 64 이상의 시프트를 poison 으로 보므로 operators.rs 처럼 하위 6비트만 씁니다.
+                    op @ ("<<" | ">>") => {
+                        let amount = self.emit(body, format!("and i64 {}, 63", right));
+                        let instruction = if op == "<<" { "shl" } else { "ashr" };
+                        self.emit(body, format!("{} i64 {}, {}", instruction, left, amount))
+                    }
+                    op => self.divide(op == "%", &left, &right, &instr.operands[1], messages, body),
+                };
+                self.stack.push(value);
+            }
+            "select" => {
+                let right = self.pop();
+                let left = self.pop();
+                let else_value = self.pop();
+                let then_value = self.pop();
+                let predicate = match instr.operands[0].as_str() {
+                    "==" => "eq",
+                    "!=" => "ne",
+                    "<" => "slt",
+                    "<=" => "sle",
+                    ">" => "sgt",
+                    _ => "sge",
+                };
+                let condition = self.emit(body, format!("icmp {} i64 {}, {}", predicate, left, right));
+                let value = self.emit(body, format!("select i1 {}, i64 {}, i64 {}", condition, then_value, else_value));
+                self.stack.push(value);
+            }
+            _ => {}
+        }
+    }
+
+    /// `sdiv`/`srem` 은 나누는 수가 0 이거나 `i64::MIN / -1` 이면 정의되지 않은 동작입니다.
+    /// 0 은 패닉 블록으로 보내고, -1 이면 1 로 나눈 뒤 결과를 `-left` (몫) 와 0 (나머지) 으로 바꿉니다.
+    fn divide(
+        &mut self,
+        remainder: bool,
+        left: &str,
+        right: &str,
+        message: &str,
+        messages: &mut Vec<String>,
+        body: &mut String,
+    ) -> String {
+        let is_zero = self.emit(body, format!("icmp eq i64 {}, 0", right));
+        let n = self.values;
+        let _ = writeln!(body, "  br i1 {}, label %div.zero.{n}, label %div.{n}\ndiv.zero.{n}:", is_zero);
+        body.push_str(&abort_code(messages, message));
+        let _ = writeln!(body, "div.{}:", n);
+        let is_minus_one = self.emit(body, format!("icmp eq i64 {}, -1", right));
+        let divisor = self.emit(body, format!("select i1 {}, i64 1, i64 {}", is_minus_one, right));
+        let instruction = if remainder { "srem" } else { "sdiv" };
+        let result = self.emit(body, format!("{} i64 {}, {}", instruction, left, divisor));
+        let wrapped = if remainder { "0".to_string() } else { self.emit(body, format!("sub i64 0, {}", left)) };
+        self.emit(body, format!("select i1 {}, i64 {}, i64 {}", is_minus_one, wrapped, result))
+    }
+
+    /// 명령 하나를 새 SSA 값으로 내보내고 그 이름을 돌려줍니다.
+    fn emit(&mut self, body: &mut String, instruction: String) -> String {
+        let name = format!("%int.{}", self.values);
+        self.values += 1;
+        let _ = writeln!(body, "  {} = {}", name, instruction);
+        name
+    }
+
+    fn pop(&mut self) -> String {
+        self.stack.pop().unwrap_or_else(|| "0".into())
+    }
+
+    fn variable(&mut self, name: &str) -> usize {
+        match self.variables.iter().position(|v| v == name) {
+            Some(slot) => slot,
+            None => {
+                self.variables.push(name.to_string());
+                self.variables.len() - 1
+            }
+        }
+    }
+}
+
+/// 실행 파일에 남는 `key=value` 메타데이터 상수. 최적화로 지워지지 않도록 `@llvm.used` 에 올립니다.
+fn metadata_global(os: TargetOs, entries: &[(String, String)]) -> String {
+    let mut bytes = b"HIGHMETA\n".to_vec();
+    for (key, value) in entries {
+        bytes.extend_from_slice(format!("{}={}\n", key, value).as_bytes());
+    }
+    bytes.push(0);
+    let section = if os == TargetOs::MacOs { "__DATA,__high_meta" } else { ".high_meta" };
+    format!(
+        "@high_meta = private constant [{} x i8] c\"{}\", section \"{}\"\n\
+         @llvm.used = appending global [1 x ptr] [ptr @high_meta], section \"llvm.metadata\"\n",
+        bytes.len(),
+        escape_bytes(&bytes),
+        section
+    )
+}
+
+/// LLVM 문자열 상수 표기: 출력 가능한 ASCII 는 그대로, 나머지는 `\XX` 로 씁니다.
+fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for &b in bytes {
+        if (b' '..=b'~').contains(&b) && b != b'"' && b != b'\\' {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "\\{:02X}", b);
+        }
+    }
+    out
+}
+
+/// 주석은 한 줄이어야 합니다.
+fn comment(text: &str) -> String {
+    text.replace(['\n', '\r'], " ")
+}
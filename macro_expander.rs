@@ -0,0 +1,338 @@
+// macro_expander.rs
+// 파싱 직후 매크로를 확장하여 MacroDefinition / MacroCall 노드를 AST에서 완전히 제거합니다.
+// 네이티브 및 Rust 백엔드는 확장이 끝난 AST만 다루게 됩니다.
+
+
+use crate::compat::*;
+use crate::data_structures::{Expression, MatchArm, Program, Span, Statement};
+use crate::error_codes;
+use crate::interner::Symbol;
+use crate::patterns;
+
+/// 매크로 확장이 무한히 재귀하지 않도록 하는 최대 깊이입니다.
+const MAX_EXPANSION_DEPTH: usize = 32;
+
+/// 확장 가능한 매크로: 매개변수 목록과 단일 표현식 템플릿
+#[derive(Debug, Clone)]
+struct MacroTemplate {
+    parameters: Vec<String>,
+    body: Expression,
+}
+
+/// 여러 번에 나눠 확장할 때 유지되는 매크로 정의 (프렐류드 → 사용자 코드, REPL 입력 줄)
+#[derive(Debug, Clone, Default)]
+pub struct MacroTable {
+    macros: HashMap<String, MacroTemplate>,
+}
+
+pub struct MacroExpander {
+    macros: HashMap<String, MacroTemplate>,
+    errors: Vec<String>,
+}
+
+impl MacroExpander {
+    /// 프로그램의 매크로를 모두 확장합니다. 실패한 매크로가 있으면 오류 목록을 반환합니다.
+    pub fn expand(program: &mut Program) -> Result<(), Vec<String>> {
+        Self::expand_with(program, &mut MacroTable::default())
+    }
+
+    /// 앞서 모은 정의와 함께 확장하고, 이 프로그램의 정의를 `table` 에 더합니다.
+    /// 같은 이름이면 나중 정의가 이깁니다.
+    pub fn expand_with(program: &mut Program, table: &mut MacroTable) -> Result<(), Vec<String>> {
+        let mut expander = MacroExpander { macros: core::mem::take(&mut table.macros), errors: vec![] };
+
+        expander.collect_definitions(&mut program.statements);
+        for stmt in program.statements.iter_mut() {
+            expander.expand_statement(stmt);
+        }
+        table.macros = expander.macros;
+
+        if expander.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(expander.errors)
+        }
+    }
+
+    // ─── 1단계: 매크로 정의 수집 및 제거 ─────────────────────────────
+
+    fn collect_definitions(&mut self, statements: &mut Vec<Statement>) {
+        statements.retain(|stmt| {
+            if let Statement::MacroDefinition { name, parameters, body, .. } = peel_attributes(stmt) {
+                match Self::template_body(body) {
+                    Some(expr) => {
+                        self.macros.insert(
+                            name.clone(),
+                            MacroTemplate { parameters: parameters.clone(), body: expr.clone() },
+                        );
+                    }
+                    None => self.errors.push(error_codes::MACRO_BODY.tagged("", &[name])),
+                }
+                false
+            } else {
+                true
+            }
+        });
+
+        for stmt in statements.iter_mut() {
+            if let Statement::BlockStatement { statements: inner, .. } = stmt {
+                self.collect_definitions(inner);
+            }
+        }
+    }
+
+    pub(crate) fn template_body(body: &Statement) -> Option<&Expression> {
+        match body {
+            Statement::ReturnStatement(_, expr) | Statement::ExpressionStatement(expr) => Some(expr),
+            Statement::BlockStatement { statements, .. } if statements.len() == 1 => {
+                Self::template_body(&statements[0])
+            }
+            _ => None,
+        }
+    }
+
+    // ─── 2단계: 호출 지점 확장 ─────────────────────────────
+
+    fn expand_statement(&mut self, stmt: &mut Statement) {
+        match stmt {
+            Statement::ExpressionStatement(expr)
+            | Statement::DiscardStatement(_, expr)
+            | Statement::ReturnStatement(_, expr)
+            | Statement::YieldStatement(_, expr) => {
+                self.expand_expression(expr, 0);
+            }
+            Statement::LetStatement { value, .. } => self.expand_expression(value, 0),
+            Statement::BlockStatement { statements, .. } => {
+                for s in statements.iter_mut() {
+                    self.expand_statement(s);
+                }
+            }
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                self.expand_expression(condition, 0);
+                self.expand_statement(then_branch);
+                if let Some(else_stmt) = else_branch {
+                    self.expand_statement(else_stmt);
+                }
+            }
+            Statement::WhileStatement { condition, body, .. }
+            | Statement::ForInStatement { iterable: condition, body, .. } => {
+                self.expand_expression(condition, 0);
+                self.expand_statement(body);
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => {
+                if let Some(init) = initializer {
+                    self.expand_statement(init);
+                }
+                if let Some(cond) = condition {
+                    self.expand_expression(cond, 0);
+                }
+                if let Some(inc) = increment {
+                    self.expand_expression(inc, 0);
+                }
+                self.expand_statement(body);
+            }
+            Statement::MacroDefinition { .. } => {
+                // 1단계에서 모두 제거됨
+            }
+            Statement::TypeAlias { .. } | Statement::EnumDeclaration { .. } => {}
+            Statement::Attributed { statement, .. } | Statement::DeferStatement(_, statement) => {
+                self.expand_statement(statement)
+            }
+        }
+    }
+
+    fn expand_expression(&mut self, expr: &mut Box<Expression>, depth: usize) {
+        match expr.as_mut() {
+            Expression::MacroCall(span, name, args) => {
+                for arg in args.iter_mut() {
+                    self.expand_expression(arg, depth);
+                }
+
+                let Some(template) = self.macros.get(name.as_str()).cloned() else {
+                    // 매크로가 아닌 호출은 일반 함수 호출 노드로 바꿉니다.
+                    let callee = Box::new(Expression::Identifier(*span, Symbol::intern(name)));
+                    **expr = Expression::Call(*span, callee, core::mem::take(args));
+                    return;
+                };
+
+                if depth >= MAX_EXPANSION_DEPTH {
+                    self.errors.push(error_codes::MACRO_RECURSION.tagged("", &[name, &MAX_EXPANSION_DEPTH]));
+                    return;
+                }
+                if template.parameters.len() != args.len() {
+                    self.errors.push(error_codes::MACRO_ARITY.tagged(
+                        "",
+                        &[name, &template.parameters.len(), &args.len()],
+                    ));
+                    return;
+                }
+
+                // 인자를 빌린 표는 `*expr` 를 바꾸기 전에 버려야 합니다.
+                let substituted = {
+                    let bindings: HashMap<&str, &Expression> = template
+                        .parameters
+                        .iter()
+                        .map(String::as_str)
+                        .zip(args.iter().map(|a| a.as_ref()))
+                        .collect();
+                    substitute(&template.body, &bindings)
+                };
+                let mut expanded = Box::new(group(*span, substituted));
+                self.expand_expression(&mut expanded, depth + 1);
+                *expr = expanded;
+            }
+            Expression::PrefixOperation(_, _, right) => self.expand_expression(right, depth),
+            Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+                self.expand_expression(left, depth);
+                self.expand_expression(right, depth);
+            }
+            Expression::Ternary(_, cond, then_expr, else_expr) => {
+                self.expand_expression(cond, depth);
+                self.expand_expression(then_expr, depth);
+                self.expand_expression(else_expr, depth);
+            }
+            Expression::Function(_, _, body) => self.expand_statement(body),
+            Expression::Call(_, function, args) => {
+                self.expand_expression(function, depth);
+                for arg in args.iter_mut() {
+                    self.expand_expression(arg, depth);
+                }
+            }
+            Expression::Grouped(_, inner)
+            | Expression::Reflect(_, inner)
+            | Expression::Eval(_, inner)
+            | Expression::TypeOf(_, inner)
+            | Expression::Member(_, inner, _)
+            | Expression::Assign(_, _, _, inner) => self.expand_expression(inner, depth),
+            Expression::MethodCall(_, target, _, args) => {
+                self.expand_expression(target, depth);
+                for arg in args.iter_mut() {
+                    self.expand_expression(arg, depth);
+                }
+            }
+            Expression::ArrayLiteral(_, elements) => {
+                for element in elements.iter_mut() {
+                    self.expand_expression(element, depth);
+                }
+            }
+            Expression::MapLiteral(_, entries) => {
+                for (key, value) in entries.iter_mut() {
+                    self.expand_expression(key, depth);
+                    self.expand_expression(value, depth);
+                }
+            }
+            Expression::Index(_, target, index) => {
+                self.expand_expression(target, depth);
+                self.expand_expression(index, depth);
+            }
+            Expression::Slice(_, target, start, end) => {
+                self.expand_expression(target, depth);
+                for bound in start.iter_mut().chain(end) {
+                    self.expand_expression(bound, depth);
+                }
+            }
+            Expression::Match(_, subject, arms) => {
+                self.expand_expression(subject, depth);
+                for arm in arms.iter_mut() {
+                    if let Some(guard) = &mut arm.guard {
+                        self.expand_expression(guard, depth);
+                    }
+                    self.expand_expression(&mut arm.body, depth);
+                }
+            }
+            Expression::Literal(..) | Expression::Identifier(..) => {}
+        }
+    }
+}
+
+/// 어트리뷰트 래퍼를 벗겨 실제 문장을 돌려줍니다.
+fn peel_attributes(stmt: &Statement) -> &Statement {
+    match stmt {
+        Statement::Attributed { statement, .. } => peel_attributes(statement),
+        other => other,
+    }
+}
+
+/// 템플릿 표현식에서 매개변수 식별자를 인자 표현식으로 치환합니다.
+pub(crate) fn substitute(template: &Expression, bindings: &HashMap<&str, &Expression>) -> Expression {
+    let sub = |e: &Expression| Box::new(substitute(e, bindings));
+    match template {
+        Expression::Identifier(span, name) => match bindings.get(name.as_str()) {
+            Some(arg) => group(*span, (*arg).clone()),
+            None => template.clone(),
+        },
+        Expression::PrefixOperation(span, op, right) => {
+            Expression::PrefixOperation(*span, op.clone(), sub(right))
+        }
+        Expression::InfixOperation(span, op, left, right) => {
+            Expression::InfixOperation(*span, op.clone(), sub(left), sub(right))
+        }
+        Expression::Range(span, start, end, inclusive) => Expression::Range(*span, sub(start), sub(end), *inclusive),
+        Expression::Ternary(span, cond, then_expr, else_expr) => {
+            Expression::Ternary(*span, sub(cond), sub(then_expr), sub(else_expr))
+        }
+        Expression::Call(span, function, args) => {
+            Expression::Call(*span, sub(function), args.iter().map(|a| sub(a)).collect())
+        }
+        Expression::MacroCall(span, name, args) => {
+            Expression::MacroCall(*span, name.clone(), args.iter().map(|a| sub(a)).collect())
+        }
+        Expression::Grouped(span, inner) => Expression::Grouped(*span, sub(inner)),
+        Expression::Reflect(span, inner) => Expression::Reflect(*span, sub(inner)),
+        Expression::Eval(span, inner) => Expression::Eval(*span, sub(inner)),
+        Expression::TypeOf(span, inner) => Expression::TypeOf(*span, sub(inner)),
+        Expression::ArrayLiteral(span, elements) => Expression::ArrayLiteral(*span, elements.iter().map(|e| sub(e)).collect()),
+        Expression::MapLiteral(span, entries) => {
+            Expression::MapLiteral(*span, entries.iter().map(|(key, value)| (sub(key), sub(value))).collect())
+        }
+        Expression::Index(span, target, index) => Expression::Index(*span, sub(target), sub(index)),
+        Expression::Member(span, target, name) => Expression::Member(*span, sub(target), *name),
+        Expression::MethodCall(span, target, name, args) => {
+            Expression::MethodCall(*span, sub(target), *name, args.iter().map(|a| sub(a)).collect())
+        }
+        Expression::Slice(span, target, start, end) => {
+            Expression::Slice(*span, sub(target), start.as_ref().map(|s| sub(s)), end.as_ref().map(|e| sub(e)))
+        }
+        // arm 의 패턴이 바인딩하는 이름은 그 arm 안에서 매개변수를 가립니다.
+        Expression::Match(span, subject, arms) => Expression::Match(
+            *span,
+            sub(subject),
+            arms.iter()
+                .map(|arm| {
+                    let shadowed = patterns::bindings(&arm.pattern);
+                    let mut inner = bindings.clone();
+                    inner.retain(|name, _| !shadowed.iter().any(|s| s.as_str() == *name));
+                    MatchArm {
+                        pattern: arm.pattern.clone(),
+                        guard: arm.guard.as_ref().map(|g| Box::new(substitute(g, &inner))),
+                        body: Box::new(substitute(&arm.body, &inner)),
+                        span: arm.span,
+                    }
+                })
+                .collect(),
+        ),
+        // 이름 인자를 받은 매개변수에 대입하면 그 이름에 대입합니다. (`macro bump(x) { x += 1 }`)
+        Expression::Assign(span, op, name, value) => {
+            let target = match bindings.get(name.as_str()) {
+                Some(Expression::Identifier(_, arg)) => *arg,
+                _ => *name,
+            };
+            Expression::Assign(*span, op.clone(), target, sub(value))
+        }
+        Expression::Literal(..) | Expression::Function(..) => template.clone(),
+    }
+}
+
+/// 치환 결과가 주변 연산자와 결합되지 않도록 필요한 경우에만 괄호로 감쌉니다.
+pub(crate) fn group(span: Span, expr: Expression) -> Expression {
+    match expr {
+        Expression::Literal(..)
+        | Expression::Identifier(..)
+        | Expression::Grouped(..)
+        | Expression::Call(..)
+        | Expression::MacroCall(..)
+        | Expression::Member(..)
+        | Expression::MethodCall(..) => expr,
+        other => Expression::Grouped(span, Box::new(other)),
+    }
+}
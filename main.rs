@@ -1,16 +1,159 @@
 use tokio::time::Instant;
 use std::fs;
 use std::io::{self, Write};
+use std::rc::Rc;
+use std::sync::Arc;
+use tokio::sync::mpsc;
 
 use High::compiler_services::{CompilerService, CompileRequest, CompileOptions};
-use High::analyzer_service::AnalyzerService;
+use High::config::Config;
+use High::coverage::{self, FileCoverage};
+use High::engine::Engine;
+use High::ft_runtime::Capabilities;
+use High::input::Input;
+use High::stdlib;
+use High::optimizer::MathMode;
+use High::analyzer_service::{AnalyzerService, HeuristicAnalyzer};
+use High::benchmarks;
+use High::call_graph::CallGraph;
+use High::conditional_compilation::{self, CfgContext};
+use High::lexer_service::LexerService;
+use High::parser_service::ParserService;
 use High::executor_service::{ExecutorService, ExecutionRequest, ExecutionStatus};
+use High::artifact_metadata;
+use High::bundle::{self, Bundle};
+use High::error_codes;
+use High::exit_code;
+use High::messages::{self, Locale};
+use High::test_runner;
+use High::mutate;
+use High::verify::{self, Backend, Verifier};
+use High::navigation;
+use High::refactor;
+use High::semantic_tokens;
+use High::trace::{self, Trace};
+use High::profile::{self, Profile};
+use High::playground_server::{self, PlaygroundServer};
+use High::daemon::{self, CompilerDaemon};
+use High::kernel::Kernel;
+use High::data_structures::{DiagnosticLevel, Span, Value};
+
+/// `high bench` 가 할당 횟수를 잴 수 있도록 설치합니다.
+#[global_allocator]
+static ALLOCATOR: benchmarks::CountingAllocator = benchmarks::CountingAllocator;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `high bundle` 로 만든 실행 파일이면 컴파일러 대신 묶인 프로그램만 실행합니다.
+    if let Some(bundle) = bundle::embedded() {
+        std::process::exit(bundle::run(&bundle));
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match apply_locale(args) {
+        Ok(rest) => rest,
+        Err(e) => {
+            println!("❌ {}", e);
+            return Ok(());
+        }
+    };
+    let config = match Config::load().and_then(|mut config| config.apply_flags(&args).map(|()| config)) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("❌ {}", e);
+            return Ok(());
+        }
+    };
+    match args.first().map(String::as_str) {
+        Some("version") => {
+            println!("high {}", artifact_metadata::COMPILER_VERSION);
+            return Ok(());
+        }
+        Some("inspect") => {
+            run_inspect(args.get(1).map(String::as_str));
+            return Ok(());
+        }
+        Some("explain") => {
+            run_explain(args.get(1).map(String::as_str));
+            return Ok(());
+        }
+        Some("test") => {
+            run_test_command(&args[1..]);
+            return Ok(());
+        }
+        Some("run") => {
+            std::process::exit(run_script(&args[1..]));
+        }
+        Some("trace") => {
+            run_trace(&args[1..]);
+            return Ok(());
+        }
+        Some("verify") => {
+            std::process::exit(run_verify(&args[1..]).await);
+        }
+        Some("mutate") => {
+            run_mutate(args.get(1).map(String::as_str));
+            return Ok(());
+        }
+        Some("refactor") => {
+            run_refactor(&args[1..]);
+            return Ok(());
+        }
+        Some("refs") => {
+            run_refs(&args[1..]);
+            return Ok(());
+        }
+        Some("highlight") => {
+            run_highlight(&args[1..]);
+            return Ok(());
+        }
+        Some("analyze") => {
+            run_analyze(&args[1..]);
+            return Ok(());
+        }
+        Some("bench") => {
+            run_bench(args.get(1).map(String::as_str));
+            return Ok(());
+        }
+        Some("serve") => {
+            run_serve(&args[1..]).await;
+            return Ok(());
+        }
+        Some("daemon") => {
+            run_daemon(&args[1..]).await;
+            return Ok(());
+        }
+        Some("bundle") => {
+            run_bundle(&args[1..]);
+            return Ok(());
+        }
+        Some("kernel") => {
+            run_kernel(&args[1..]);
+            return Ok(());
+        }
+        Some("config") => {
+            print!("{}", config.describe());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    if args.first().map(String::as_str) == Some("build") {
+        std::process::exit(run_build(&args[1..], &config).await);
+    }
+
+    // 하위 명령의 출력(예: DOT)을 그대로 파이프할 수 있도록 배너는 대화형 모드에서만 출력합니다.
     println!("--- High Programming Language Compiler Orchestrator ---");
 
-    let mut compiler_service = CompilerService::new();
+    let compile_options = match parse_compile_options(&args, &config) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("❌ {}", e);
+            return Ok(());
+        }
+    };
+
+    let compiler_service = CompilerService::new();
     let analyzer_service = AnalyzerService::new();
     let executor_service = ExecutorService::new();
 
@@ -40,12 +183,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let start_time = Instant::now();
 
         println!("\n[Analyzer] Running preliminary code analysis...");
-        let _ = match analyzer_service.analyze_text(&source_code).await {
+        let _ = match analyzer_service.analyze_with(&source_code, &compile_options.analyzer, compile_options.analyzer_timeout_ms).await {
             Ok(res) => {
-                println!("[Analyzer] Analysis successful.");
+                println!("[Analyzer] Analysis successful. (backend: {})", res.backend);
                 println!("  - Sentiment: {}", res.detected_sentiment);
                 println!("  - Word Count: {}", res.word_count);
-                println!("  - Readability Score: {:.2}", res.readability_score);
+                for function in &res.functions {
+                    println!(
+                        "  - {} ({:?}, {} lines): words {}, comments {:.0}%, naming {:.2}",
+                        function.name,
+                        function.kind,
+                        function.line_count,
+                        function.word_count,
+                        function.comment_density * 100.0,
+                        function.naming_score
+                    );
+                }
                 res
             },
             Err(e) => {
@@ -55,47 +208,80 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         };
 
         let request = CompileRequest {
-    source_code,
-    options: CompileOptions {
-        target_platform: "her_vm".into(),
-        optimization_level: 2,
-        emit_native: true, // ✅ 네이티브 바이너리 생성 여부
-    },
-};
+            source_code: source_code.clone(),
+            options: compile_options.clone(),
+        };
 
 
         println!("\n[Compiler] Starting full compilation pipeline...");
         let result = compiler_service.compile(request).await;
 
+        let renderer = config.renderer();
+        for diag in &result.diagnostics {
+            print!("{}", renderer.render(diag, &source_code, file_path));
+        }
+
+        if let Some(expanded) = &result.expanded_source {
+            println!("\n--- Expanded Source ---\n{}", expanded);
+        }
+
         if result.success {
             println!("\n--- Compilation Successful ---");
             println!("Compiled Output: {}", result.compiled_output);
+            if let Some(path) = &result.binary_path {
+                println!("Binary: {}", path);
+            }
+            let stats = &result.allocation_stats;
+            if stats.sites > 0 {
+                println!("Allocations: {} of {} elided to the stack", stats.stack, stats.sites);
+            }
 
             println!("\n[Executor] Requesting code execution...");
+            // 표준 입력은 이 루프가 파일 경로를 읽는 데 쓰므로 프로그램에는 빈 입력을 줍니다.
             let execution_request = ExecutionRequest {
                 compiled_code_reference: result.compiled_output.clone(),
-                input_data: Some("1, 2, 3".into()),
+                input_data: None,
+                binary_path: result.binary_path.clone(),
+                ..Default::default()
             };
 
-            let execution_result = executor_service.execute_code(execution_request).await;
+            // 오래 도는 프로그램도 멈춘 것처럼 보이지 않도록 출력은 생기는 대로 찍습니다.
+            println!("Log:");
+            let (stream, mut lines) = mpsc::unbounded_channel();
+            let printer = tokio::spawn(async move {
+                while let Some(line) = lines.recv().await {
+                    println!("  {}", line);
+                }
+            });
+            let execution_result = executor_service.execute_code_streaming(execution_request, stream).await;
+            let _ = printer.await;
 
             println!("--- Execution Result ---");
             match execution_result.status {
                 ExecutionStatus::Success => println!("Status: Success"),
                 ExecutionStatus::RuntimeError => println!("Status: Runtime Error"),
                 ExecutionStatus::Skipped => println!("Status: Skipped"),
+                ExecutionStatus::Cancelled => println!("Status: Cancelled"),
+                ExecutionStatus::TimedOut => println!("Status: Timed Out"),
             }
+            println!("Exit Code: {}", execution_result.exit_code);
 
-            println!("Log:");
-            for line in execution_result.output_log {
-                println!("  {}", line);
-            }
             println!("Execution Time: {}ms", execution_result.execution_time_ms);
+            if let Some(gc) = execution_result.gc {
+                println!(
+                    "GC: {} collections, {} objects / {} bytes reclaimed, pause {}µs (max {}µs)",
+                    gc.collections,
+                    gc.objects_reclaimed,
+                    gc.bytes_reclaimed,
+                    gc.total_pause_ns / 1000,
+                    gc.max_pause_ns / 1000
+                );
+            }
             println!("Proof Block Index: {}", result.proof_block_index);
         } else {
             println!("\n--- Compilation Failed ---");
             for error in result.errors {
-                println!("Error: {}", error);
+                print!("{}", renderer.render_message(&DiagnosticLevel::Error, &error));
             }
         }
 
@@ -105,3 +291,863 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// 명령줄 플래그를 CompileOptions로 변환합니다. 설정 계층(`high config`)의 값 위에 적용되며,
+/// 설정과 겹치는 플래그(`--target`, `--nasm` 등)는 `Config::apply_flags` 가 이미 반영했습니다.
+fn parse_compile_options(args: &[String], config: &Config) -> Result<CompileOptions, String> {
+    let mut options = config.compile_options();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            flag if Config::is_flag(flag) => {
+                iter.next();
+            }
+            "--target-cpu" => {
+                options.target_cpu = iter.next().ok_or("--target-cpu 뒤에 CPU 이름이 필요합니다.")?.clone();
+            }
+            "--target-os" => {
+                options.target_os = Some(iter.next().ok_or("--target-os 뒤에 OS 이름이 필요합니다. (linux, windows, macos)")?.clone());
+            }
+            "--features" => {
+                let list = iter.next().ok_or("--features 뒤에 기능 목록이 필요합니다.")?;
+                options.target_features = list.split(',').map(|f| f.trim().to_string()).filter(|f| !f.is_empty()).collect();
+            }
+            "--emit-expanded" => options.emit_expanded = true,
+            "--emit-rust" => {
+                options.rust_project = Some(iter.next().ok_or("--emit-rust 뒤에 출력 디렉터리가 필요합니다.")?.clone());
+            }
+            "--cargo-build" => options.cargo_build = true,
+            "--run-python" => options.run_python = true,
+            "--no-std" => options.no_std = true,
+            "--allow-process" => options.allow_process = true,
+            "--allow-debug" => options.allow_debug = true,
+            "--reproducible" => options.reproducible = true,
+            "--strip-dead" => options.strip_dead = true,
+            "--strict-math" => options.math = Some(MathMode::Strict),
+            "--fast-math" => options.math = Some(MathMode::Relaxed),
+            "--strict" => options.strict = true,
+            "--unroll-limit" => {
+                let n = iter.next().ok_or("--unroll-limit 뒤에 반복 횟수가 필요합니다.")?;
+                options.unroll_limit = Some(n.parse().map_err(|_| format!("잘못된 --unroll-limit 값: {}", n))?);
+            }
+            "--profile-use" => {
+                let path = iter.next().ok_or("--profile-use 뒤에 프로필 파일 경로가 필요합니다.")?;
+                let text = fs::read_to_string(path).map_err(|e| format!("프로필 '{}' 읽기 실패: {}", path, e))?;
+                options.profile_use = Some(Arc::new(Profile::decode(&text).map_err(|e| format!("{}: {}", path, e))?));
+            }
+            "--cfg" => {
+                options.cfg_flags.push(iter.next().ok_or("--cfg 뒤에 플래그가 필요합니다.")?.clone());
+            }
+            "--prelude" => {
+                let path = iter.next().ok_or("--prelude 뒤에 파일 경로가 필요합니다.")?;
+                let source = fs::read_to_string(path).map_err(|e| format!("프렐류드 '{}' 읽기 실패: {}", path, e))?;
+                options.prelude = Some(source);
+            }
+            "--global" => {
+                let binding = iter.next().ok_or("--global 뒤에 name=value 가 필요합니다.")?;
+                let (name, raw) = binding.split_once('=').ok_or("--global 형식은 name=value 입니다.")?;
+                options.globals.push((name.to_string(), parse_global_value(raw)));
+            }
+            "--analyzer" => {
+                options.analyzer = iter.next().ok_or("--analyzer 뒤에 분석기 이름 또는 http:// 주소가 필요합니다.")?.clone();
+            }
+            "--timeout" => {
+                let spec = iter.next().ok_or("--timeout 뒤에 <단계>=<ms> 가 필요합니다.")?;
+                options.timeouts.set(spec)?;
+            }
+            "--analyzer-timeout" => {
+                let ms = iter.next().ok_or("--analyzer-timeout 뒤에 밀리초 값이 필요합니다.")?;
+                options.analyzer_timeout_ms = ms.parse().map_err(|_| format!("잘못된 --analyzer-timeout 값: {}", ms))?;
+            }
+            other => return Err(format!("알 수 없는 옵션: {}", other)),
+        }
+    }
+
+    // Python / LLVM 타깃은 NASM 네이티브 바이너리 대신 소스나 .ll 을 만듭니다.
+    if options.run_python {
+        options.target_platform = "python".into();
+    }
+    if options.target_platform == "python" || options.target_platform == "llvm" {
+        options.emit_native = false;
+    }
+    if options.cargo_build && options.rust_project.is_none() {
+        return Err("--cargo-build 는 --emit-rust <dir> 와 함께 써야 합니다.".into());
+    }
+    Ok(options)
+}
+
+/// `--locale ko|en` (어느 위치든) 또는 HIGH_LOCALE/LANG 환경 변수로 진단 메시지 언어를 정하고,
+/// `--locale` 인자를 제외한 나머지 인자를 돌려줍니다.
+fn apply_locale(args: Vec<String>) -> Result<Vec<String>, String> {
+    let mut selected = Locale::from_env();
+    let mut rest = vec![];
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--locale" {
+            let value = iter.next().ok_or("--locale 뒤에 ko 또는 en 이 필요합니다.")?;
+            selected = Some(Locale::parse(&value).ok_or(format!("지원하지 않는 로케일: {} (ko, en)", value))?);
+        } else {
+            rest.push(arg);
+        }
+    }
+    messages::set_locale(selected.unwrap_or(Locale::Ko));
+    Ok(rest)
+}
+
+/// `--global` 값 문자열을 가장 구체적인 Value 타입으로 해석합니다.
+fn parse_global_value(raw: &str) -> Value {
+    if let Ok(i) = raw.parse::<i64>() {
+        Value::Integer(i)
+    } else if let Ok(f) = raw.parse::<f64>() {
+        Value::Float(f)
+    } else if let Ok(b) = raw.parse::<bool>() {
+        Value::Boolean(b)
+    } else {
+        Value::String(raw.into())
+    }
+}
+
+/// `high inspect <binary>`: 실행 파일에 기록된 메타데이터를 출력합니다.
+fn run_inspect(binary_path: Option<&str>) {
+    let Some(path) = binary_path else {
+        println!("사용법: high inspect <binary>");
+        return;
+    };
+
+    match artifact_metadata::read_metadata(path) {
+        Ok(metadata) => {
+            println!("--- High Artifact Metadata: {} ---", path);
+            for (key, value) in &metadata.entries {
+                println!("  {:<20} {}", key, value);
+            }
+        }
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// `high test <file> [--coverage [--coverage-out <path>]]`
+fn run_test_command(args: &[String]) {
+    let (rest, coverage_out) = match take_coverage_flags(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            println!("❌ {}", e);
+            return;
+        }
+    };
+    let Some(path) = rest.first() else {
+        println!("사용법: high test <file.high> [--coverage [--coverage-out <path>]]");
+        return;
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+    let (outcomes, coverage) = match &coverage_out {
+        Some(_) => {
+            let (outcomes, report) = test_runner::run_tests_with_coverage(&source, path);
+            (outcomes, Some(report))
+        }
+        None => (test_runner::run_tests(&source), None),
+    };
+    match outcomes {
+        Ok(outcomes) => print!("{}", test_runner::render_report(&outcomes)),
+        Err(e) => println!("❌ {}", e),
+    }
+    if let (Some(report), Some(out)) = (coverage, coverage_out) {
+        print_coverage(&[report], &out);
+    }
+}
+
+/// `high mutate <file>`: 변이체마다 파일의 `@test` 를 다시 실행하고 살아남은 변이체를 보고합니다.
+fn run_mutate(source_path: Option<&str>) {
+    let Some(path) = source_path else {
+        println!("사용법: high mutate <file.high>");
+        return;
+    };
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+    let result = mutate::run(&source, |done, total| {
+        print!("\rmutant {}/{}", done, total);
+        let _ = io::stdout().flush();
+    });
+    match result {
+        Ok(outcomes) => {
+            if !outcomes.is_empty() {
+                println!();
+            }
+            print!("{}", mutate::render_report(&outcomes, &source, path));
+        }
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// `high refactor rename <file> <pos> <new-name>` / `high refactor extract <file> <start> <end> <name>`:
+/// 편집 목록을 JSON 으로 씁니다. `--write` 가 있으면 파일에 적용합니다. 위치는 문자 위치나 `줄:열` 입니다.
+fn run_refactor(args: &[String]) {
+    const USAGE: &str = "사용법: high refactor rename <file.high> <pos> <new-name> [--write]\n       high refactor extract <file.high> <start> <end> <name> [--write]";
+    let write = args.iter().any(|a| a == "--write");
+    let args: Vec<&str> = args.iter().map(String::as_str).filter(|a| *a != "--write").collect();
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (*command, *path),
+        _ => {
+            println!("{}", USAGE);
+            return;
+        }
+    };
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+    let position = |text: &str| refactor::position(&source, text).ok_or(format!("위치가 올바르지 않습니다: {}", text));
+    let edits = match (command, &args[2..]) {
+        ("rename", [pos, new_name]) => position(pos).and_then(|pos| refactor::rename(&source, pos, new_name)),
+        ("extract", [start, end, name]) => position(start)
+            .and_then(|start| Ok((start, position(end)?)))
+            .and_then(|(start, end)| refactor::extract_function(&source, start, end, name)),
+        _ => {
+            println!("{}", USAGE);
+            return;
+        }
+    };
+    match edits {
+        Ok(edits) if write => match fs::write(path, refactor::apply(&source, &edits)) {
+            Ok(()) => println!("✅ {} 에 편집 {}개를 적용했습니다.", path, edits.len()),
+            Err(e) => println!("❌ '{}' 에 쓰지 못했습니다: {}", path, e),
+        },
+        Ok(edits) => println!("{}", refactor::edits_json(&edits)),
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// `high refs <file>:<line>:<col> [--calls]`: 커서 아래 이름의 선언과 참조, `--calls` 면 호출 계층을 씁니다.
+fn run_refs(args: &[String]) {
+    const USAGE: &str = "사용법: high refs <file.high>:<line>:<col> [--calls]";
+    let calls = args.iter().any(|a| a == "--calls");
+    let location = args.iter().find(|a| *a != "--calls").and_then(|a| a.rsplitn(3, ':').collect::<Vec<_>>().get(2).map(|path| (*path, a)));
+    let Some((path, location)) = location else {
+        println!("{}", USAGE);
+        return;
+    };
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+    let Some(offset) = refactor::position(&source, &location[path.len() + 1..]) else {
+        println!("{}", USAGE);
+        return;
+    };
+    let at = |span: Span| {
+        let (line, column) = navigation::line_column(&source, span.start);
+        format!("{}:{}:{}", path, line, column)
+    };
+    if calls {
+        match navigation::call_hierarchy(&source, offset) {
+            Ok(hierarchy) => {
+                println!("{} ({:?}, {})", hierarchy.item.name, hierarchy.item.kind, at(hierarchy.item.span));
+                for (title, calls) in [("incoming", &hierarchy.incoming), ("outgoing", &hierarchy.outgoing)] {
+                    println!("{}:", title);
+                    for (item, spans) in calls {
+                        let sites: Vec<String> = spans.iter().map(|s| at(*s)).collect();
+                        println!("  {:<16} {}", item.name, sites.join(", "));
+                    }
+                }
+            }
+            Err(e) => println!("❌ {}", e),
+        }
+        return;
+    }
+    match navigation::references(&source, offset) {
+        Ok(refs) => {
+            println!("{} ({} references)", refs.name, refs.references.len());
+            if let Some(span) = refs.declaration {
+                println!("  {}  declaration", at(span));
+            }
+            for span in &refs.references {
+                println!("  {}", at(*span));
+            }
+        }
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// `high highlight --html <file> [-o <out.html>]`: 의미 기반으로 색을 입힌 HTML 문서를 씁니다.
+fn run_highlight(args: &[String]) {
+    const USAGE: &str = "사용법: high highlight --html <file.high> [-o <out.html>]";
+    let mut html = false;
+    let mut input = None;
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--html" => html = true,
+            "-o" => output = iter.next(),
+            path if input.is_none() => input = Some(path),
+            other => {
+                println!("❌ 알 수 없는 인자: {}\n{}", other, USAGE);
+                return;
+            }
+        }
+    }
+    let (true, Some(path)) = (html, input) else {
+        println!("{}", USAGE);
+        return;
+    };
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+    let page = semantic_tokens::html_page(path, &source);
+    match output {
+        Some(out) => match fs::write(out, page) {
+            Ok(()) => println!("✅ {} 를 썼습니다.", out),
+            Err(e) => println!("❌ '{}' 에 쓰지 못했습니다: {}", out, e),
+        },
+        None => print!("{}", page),
+    }
+}
+
+/// `high run <file> [--no-std] [--allow-process] [--allow-debug] [--cfg <flag>]... [--coverage [--coverage-out <path>]] [--trace [--trace-out <path>]]`:
+/// 컴파일 파이프라인 없이 인터프리터로 실행하고 출력 줄을 씁니다. `--trace` 는 문장 실행 기록을 씁니다. (기본 `high.trace`)
+/// `@cfg` 는 호스트 OS 의 `her_vm` 타깃과 `--cfg` 플래그로 평가합니다.
+/// `high run`: 프로세스 종료 코드는 프로그램의 종료 코드입니다 (exit_code.rs). 사용법이나 파일 오류는 1 입니다.
+fn run_script(args: &[String]) -> i32 {
+    const USAGE: &str = "사용법: high run <file.high> [--no-std] [--allow-process] [--allow-debug] [--cfg <flag>]... [--coverage [--coverage-out <path>]] [--trace [--trace-out <path>]]";
+    let (rest, coverage_out) = match take_coverage_flags(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            println!("❌ {}", e);
+            return exit_code::FAILURE;
+        }
+    };
+    let (mut path, mut no_std, mut allow_process, mut allow_debug) = (None, false, false, false);
+    let (mut trace_out, mut cfg_flags) = (None, vec![]);
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--no-std" => no_std = true,
+            "--allow-process" => allow_process = true,
+            "--allow-debug" => allow_debug = true,
+            "--cfg" => match iter.next() {
+                Some(flag) => cfg_flags.push(flag.clone()),
+                None => {
+                    println!("{}", USAGE);
+                    return exit_code::FAILURE;
+                }
+            },
+            "--trace" => trace_out = trace_out.or(Some("high.trace".to_string())),
+            "--trace-out" => match iter.next() {
+                Some(out) => trace_out = Some(out.clone()),
+                None => {
+                    println!("{}", USAGE);
+                    return exit_code::FAILURE;
+                }
+            },
+            flag if flag.starts_with("--") || path.is_some() => {
+                println!("{}", USAGE);
+                return exit_code::FAILURE;
+            }
+            file => path = Some(file.to_string()),
+        }
+    }
+    let Some(path) = path else {
+        println!("{}", USAGE);
+        return exit_code::FAILURE;
+    };
+    let source = match fs::read_to_string(&path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return exit_code::FAILURE;
+        }
+    };
+
+    let cfg = CfgContext::new("her_vm", &cfg_flags);
+    let mut engine = Engine::new();
+    engine.set_capabilities(Capabilities { processes: allow_process, debug: allow_debug, ..Capabilities::default() });
+    engine.set_cfg(cfg.clone());
+    if let Some(prelude) = stdlib::execution_prelude(no_std, None) {
+        if let Err(e) = engine.load_prelude(&prelude) {
+            println!("❌ {}", e);
+            return exit_code::FAILURE;
+        }
+    }
+    // 프렐류드를 실행한 뒤에 켜서 사용자 파일의 문장만 셉니다.
+    let coverage = coverage_out.as_ref().map(|_| engine.enable_coverage());
+    let recorded = trace_out.as_ref().map(|_| engine.enable_trace());
+    engine.set_input(Input::Stdin);
+    // 출력은 끝날 때까지 모으지 않고 생기는 대로 찍습니다.
+    engine.set_output_sink(Some(Rc::new(|line: &str| println!("{}", line))));
+    let code = match engine.run(&source) {
+        Ok(_) => engine.exit_code(),
+        Err(e) => {
+            println!("❌ {}", e);
+            exit_code::RUNTIME_ERROR
+        }
+    };
+    if let (Some(recorded), Some(out)) = (recorded, trace_out) {
+        let mut log = recorded.borrow_mut();
+        log.file = Some(path.clone());
+        match fs::write(&out, log.encode()) {
+            Ok(()) => println!("실행 기록: {} ({} 단계, high trace view {})", out, log.events.len(), out),
+            Err(e) => println!("❌ 실행 기록 '{}' 쓰기 실패: {}", out, e),
+        }
+    }
+    if let (Some(coverage), Some(out)) = (coverage, coverage_out) {
+        // 실행하지 않은 `@cfg` 항목은 놓친 문장으로 세지 않습니다.
+        let mut program = ParserService::new(LexerService::new(&source)).parse_program();
+        conditional_compilation::prune_program(&mut program, &cfg);
+        print_coverage(&[coverage.borrow().report(&path, &source, &program)], &out);
+    }
+    code
+}
+
+/// `high build <file> [컴파일 옵션] [--profile-generate [--profile-out <path>]]`: 파일 하나를 대화 없이 컴파일합니다.
+/// `--profile-generate` 면 컴파일하는 대신 인터프리터로 실행하며 분기·루프·호출 횟수를 세어 프로필(기본 `high.profile`)을
+/// 쓰고, 프로그램의 종료 코드로 끝납니다. 그 파일을 `--profile-use` 로 주면 옵티마이저가 씁니다. (profile.rs)
+/// 컴파일은 성공하면 0, 실패하면 1 로 끝납니다.
+async fn run_build(args: &[String], config: &Config) -> i32 {
+    const USAGE: &str = "사용법: high build <file.high> [컴파일 옵션] [--profile-use <path>] [--profile-generate [--profile-out <path>]]";
+    let Some((path, flags)) = args.split_first().filter(|(path, _)| !path.starts_with("--")) else {
+        println!("{}", USAGE);
+        return exit_code::FAILURE;
+    };
+    let (mut rest, mut profile_out) = (vec![], None);
+    let mut iter = flags.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--profile-generate" => profile_out = profile_out.or(Some(profile::DEFAULT_PATH.to_string())),
+            "--profile-out" => match iter.next() {
+                Some(out) => profile_out = Some(out.clone()),
+                None => {
+                    println!("{}", USAGE);
+                    return exit_code::FAILURE;
+                }
+            },
+            _ => rest.push(arg.clone()),
+        }
+    }
+    let options = match parse_compile_options(&rest, config) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("❌ {}", e);
+            return exit_code::FAILURE;
+        }
+    };
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return exit_code::FAILURE;
+        }
+    };
+    if let Some(out) = profile_out {
+        return generate_profile(&source, &options, &out);
+    }
+
+    let result = CompilerService::new().compile(CompileRequest { source_code: source.clone(), options }).await;
+    let renderer = config.renderer();
+    for diag in &result.diagnostics {
+        print!("{}", renderer.render(diag, &source, path));
+    }
+    if !result.success {
+        println!("\n--- Compilation Failed ---");
+        for error in &result.errors {
+            print!("{}", renderer.render_message(&DiagnosticLevel::Error, error));
+        }
+        return exit_code::FAILURE;
+    }
+    println!("\n--- Compilation Successful ---");
+    println!("Compiled Output: {}", result.compiled_output);
+    if let Some(binary) = &result.binary_path {
+        println!("Binary: {}", binary);
+    }
+    exit_code::SUCCESS
+}
+
+/// 프로필을 켠 인터프리터로 프로그램을 한 번 실행하고 프로필을 `out` 에 씁니다.
+fn generate_profile(source: &str, options: &CompileOptions, out: &str) -> i32 {
+    let mut engine = Engine::new();
+    engine.set_capabilities(Capabilities { processes: options.allow_process, debug: options.allow_debug, ..Capabilities::default() });
+    // 프로필을 쓸 빌드와 같은 `@cfg` 항목을 실행합니다.
+    let mut cfg = CfgContext::new(&options.target_platform, &options.cfg_flags);
+    if let Some(os) = &options.target_os {
+        cfg.target_os = os.clone();
+    }
+    engine.set_cfg(cfg);
+    if let Some(prelude) = stdlib::execution_prelude(options.no_std, options.prelude.as_deref()) {
+        if let Err(e) = engine.load_prelude(&prelude) {
+            println!("❌ {}", e);
+            return exit_code::FAILURE;
+        }
+    }
+    let recorded = engine.enable_profile();
+    engine.set_input(Input::Stdin);
+    engine.set_output_sink(Some(Rc::new(|line: &str| println!("{}", line))));
+    let code = match engine.run(source) {
+        Ok(_) => engine.exit_code(),
+        Err(e) => {
+            println!("❌ {}", e);
+            exit_code::RUNTIME_ERROR
+        }
+    };
+    let mut profile = recorded.borrow().clone();
+    profile.source = Some(profile::source_hash(source));
+    let (branches, loops, functions) = profile.sites();
+    match fs::write(out, profile.encode()) {
+        Ok(()) => println!("프로필: {} (if 문 {}개, 루프 {}개, 함수 {}개)", out, branches, loops, functions),
+        Err(e) => {
+            println!("❌ 프로필 '{}' 쓰기 실패: {}", out, e);
+            return exit_code::FAILURE;
+        }
+    }
+    code
+}
+
+/// `high verify <file> [--backend rust|native|python] [--no-std]`: 인터프리터와 백엔드의 출력·종료 코드를 비교합니다.
+/// 같으면 0, 다르거나 비교할 수 없으면 1 로 끝납니다.
+async fn run_verify(args: &[String]) -> i32 {
+    const USAGE: &str = "사용법: high verify <file.high> [--backend rust|native|python] [--no-std]";
+    let (mut path, mut backend, mut no_std) = (None, Backend::Rust, false);
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--no-std" => no_std = true,
+            "--backend" => match iter.next().map(|name| Backend::parse(name)) {
+                Some(Ok(parsed)) => backend = parsed,
+                Some(Err(e)) => {
+                    println!("❌ {}", e);
+                    return exit_code::FAILURE;
+                }
+                None => {
+                    println!("{}", USAGE);
+                    return exit_code::FAILURE;
+                }
+            },
+            flag if flag.starts_with("--") || path.is_some() => {
+                println!("{}", USAGE);
+                return exit_code::FAILURE;
+            }
+            file => path = Some(file.to_string()),
+        }
+    }
+    let Some(path) = path else {
+        println!("{}", USAGE);
+        return exit_code::FAILURE;
+    };
+    let source = match fs::read_to_string(&path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return exit_code::FAILURE;
+        }
+    };
+    let report = match Verifier::new(backend, no_std).verify(&source).await {
+        Ok(report) => report,
+        Err(e) => {
+            println!("❌ {} 백엔드로 빌드하지 못했습니다:\n{}", backend.name(), e);
+            return exit_code::FAILURE;
+        }
+    };
+    let show = |observation: &verify::Observation| {
+        format!("출력 {:?}, 종료 코드 {}", observation.stdout.trim_end(), observation.exit_code)
+    };
+    if report.agrees() {
+        println!("✅ 인터프리터와 {} 백엔드의 결과가 같습니다. ({})", backend.name(), show(&report.interpreter));
+        return exit_code::SUCCESS;
+    }
+    println!("❌ 인터프리터와 {} 백엔드의 결과가 다릅니다.", backend.name());
+    println!("   인터프리터: {}", show(&report.interpreter));
+    println!("   {:<10} {}", format!("{}:", backend.name()), show(&report.compiled));
+    match report.first_divergence {
+        Some(divergence) => {
+            let (line, _) = navigation::line_column(&source, divergence.span.start);
+            println!("처음 갈라지는 문장: {}:{} `let {}`", path, line, divergence.binding);
+            println!("   인터프리터: {}", show(&divergence.interpreter));
+            println!("   {:<10} {}", format!("{}:", backend.name()), show(&divergence.backend));
+        }
+        None => println!("최상위 바인딩의 값은 모두 같습니다. 마지막 바인딩 뒤의 문장을 확인하세요."),
+    }
+    exit_code::FAILURE
+}
+
+/// `high trace view <log> [--source <file>]`: 실행 기록을 한 단계씩 앞뒤로 오가며 봅니다.
+/// 소스는 기록에 적힌 파일을 읽고, 옮겨졌으면 `--source` 로 알려 줍니다.
+fn run_trace(args: &[String]) {
+    const USAGE: &str = "사용법: high trace view <file.trace> [--source <file.high>]";
+    let (log_path, source_path) = match args {
+        [view, log] if view == "view" => (log, None),
+        [view, log, flag, source] if view == "view" && flag == "--source" => (log, Some(source.clone())),
+        _ => {
+            println!("{}", USAGE);
+            return;
+        }
+    };
+    let recorded = match fs::read_to_string(log_path).map_err(|e| format!("'{}' 읽기 실패: {}", log_path, e)).and_then(|text| Trace::decode(&text)) {
+        Ok(recorded) => recorded,
+        Err(e) => {
+            println!("❌ {}", e);
+            return;
+        }
+    };
+    let source = source_path.or_else(|| recorded.file.clone()).and_then(|path| fs::read_to_string(path).ok());
+    let clear = io::IsTerminal::is_terminal(&io::stdout());
+    if let Err(e) = trace::view(&recorded, source.as_deref(), &mut io::stdin().lock(), &mut io::stdout(), clear) {
+        println!("❌ {}", e);
+    }
+}
+
+/// `--coverage` 와 `--coverage-out <path>` 를 뺀 인자, 그리고 커버리지를 켰으면 보고서 경로를 돌려줍니다.
+/// `--coverage-out` 만 줘도 커버리지를 켭니다. 기본 보고서는 lcov 형식의 `coverage.lcov` 입니다.
+fn take_coverage_flags(args: &[String]) -> Result<(Vec<String>, Option<String>), String> {
+    let (mut rest, mut out) = (vec![], None);
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--coverage" => out = out.or(Some("coverage.lcov".to_string())),
+            "--coverage-out" => out = Some(iter.next().ok_or("--coverage-out 뒤에 파일 경로가 필요합니다. (.lcov 또는 .json)")?.clone()),
+            _ => rest.push(arg.clone()),
+        }
+    }
+    Ok((rest, out))
+}
+
+/// 파일별 비율과 실행되지 않은 줄을 출력하고 보고서를 씁니다.
+fn print_coverage(files: &[FileCoverage], out: &str) {
+    println!("\ncoverage:");
+    for file in files {
+        println!("  {}", file.summary());
+        let missed = file.missed_lines();
+        if !missed.is_empty() {
+            let lines: Vec<String> = missed.iter().map(usize::to_string).collect();
+            println!("    실행되지 않은 줄: {}", lines.join(", "));
+        }
+    }
+    match coverage::write_report(files, out) {
+        Ok(()) => println!("커버리지 보고서: {}", out),
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// `high analyze <file> [--callgraph [--dot]]`: 함수별 지표 또는 호출 그래프를 출력합니다.
+fn run_analyze(args: &[String]) {
+    let Some(path) = args.iter().find(|a| !a.starts_with("--")) else {
+        println!("사용법: high analyze <file.high> [--callgraph [--dot]]");
+        return;
+    };
+    let source = match fs::read_to_string(path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+
+    if !args.iter().any(|a| a == "--callgraph") {
+        for function in HeuristicAnalyzer::analyze_functions(&source) {
+            println!(
+                "{} ({:?}, {} lines): words {}, comments {:.0}%, naming {:.2}",
+                function.name,
+                function.kind,
+                function.line_count,
+                function.word_count,
+                function.comment_density * 100.0,
+                function.naming_score
+            );
+        }
+        return;
+    }
+
+    let mut program = ParserService::new(LexerService::new(&source)).parse_program();
+    conditional_compilation::prune_program(&mut program, &CfgContext::new("her_vm", &[]));
+    let graph = CallGraph::build(&program);
+    if args.iter().any(|a| a == "--dot") {
+        print!("{}", graph.to_dot());
+    } else {
+        print!("{}", graph.render_text());
+    }
+}
+
+fn run_bench(iterations: Option<&str>) {
+    let iterations = match iterations.map(str::parse::<u32>) {
+        None => benchmarks::DEFAULT_ITERATIONS,
+        Some(Ok(n)) if n > 0 => n,
+        Some(_) => {
+            println!("사용법: high bench [반복 횟수]");
+            return;
+        }
+    };
+    match benchmarks::variable_lookup(iterations) {
+        Ok(result) => print!("{}", result.render_text()),
+        Err(e) => println!("❌ {}", e),
+    }
+    print!("{}", benchmarks::identifier_allocations(benchmarks::DEFAULT_IDENTIFIERS).render_text());
+}
+
+/// `high bundle <file> [-o out] [--runner high] [--no-std]`: 인터프리터를 포함한 실행 파일을 만듭니다.
+fn run_bundle(args: &[String]) {
+    const USAGE: &str = "사용법: high bundle <file.high> [-o <출력>] [--runner <high 실행 파일>] [--no-std]";
+    let mut source_path = None;
+    let mut output = None;
+    let mut runner = None;
+    let mut no_std = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = iter.next().cloned(),
+            "--runner" => runner = iter.next().cloned(),
+            "--no-std" => no_std = true,
+            path if source_path.is_none() && !path.starts_with('-') => source_path = Some(path.to_string()),
+            _ => {
+                println!("{}", USAGE);
+                return;
+            }
+        }
+    }
+    let Some(source_path) = source_path else {
+        println!("{}", USAGE);
+        return;
+    };
+
+    let source = match fs::read_to_string(&source_path) {
+        Ok(code) => code,
+        Err(e) => {
+            println!("❌ Failed to read file '{}': {}", source_path, e);
+            return;
+        }
+    };
+    let path = std::path::Path::new(&source_path);
+    let name = path.file_name().map_or(source_path.clone(), |n| n.to_string_lossy().into_owned());
+    let bundle = match Bundle::from_source(&name, &source, no_std) {
+        Ok(bundle) => bundle,
+        Err(errors) => {
+            for error in errors {
+                println!("❌ {}", error);
+            }
+            return;
+        }
+    };
+
+    let output = output.unwrap_or_else(|| {
+        let stem = path.file_stem().map_or("bundle".into(), |s| s.to_string_lossy().into_owned());
+        if cfg!(target_os = "windows") { format!("{}.exe", stem) } else { stem }
+    });
+    let runner = match runner.map(Into::into).map_or_else(std::env::current_exe, Ok) {
+        Ok(runner) => runner,
+        Err(e) => {
+            println!("❌ 실행기 경로를 찾을 수 없습니다: {}", e);
+            return;
+        }
+    };
+    match bundle::write(&runner, &bundle, std::path::Path::new(&output)) {
+        Ok(()) => println!("✅ 번들 생성 완료: {} (실행기: {})", output, runner.display()),
+        Err(e) => println!("❌ {}", e),
+    }
+}
+
+/// `high kernel [--no-std] [--prelude file]`: 표준 입출력으로 셀 실행 요청을 받는 노트북 커널을 띄웁니다.
+fn run_kernel(args: &[String]) {
+    const USAGE: &str = "사용법: high kernel [--no-std] [--prelude <file>]";
+    let mut no_std = false;
+    let mut prelude_path = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--no-std" => no_std = true,
+            "--prelude" if prelude_path.is_none() => prelude_path = iter.next().cloned(),
+            _ => {
+                eprintln!("{}", USAGE);
+                return;
+            }
+        }
+    }
+    let prelude = match prelude_path.map(|path| fs::read_to_string(&path).map_err(|e| (path, e))).transpose() {
+        Ok(prelude) => prelude,
+        Err((path, e)) => {
+            eprintln!("❌ Failed to read file '{}': {}", path, e);
+            return;
+        }
+    };
+    // 표준 출력은 프로토콜 전용이므로 사람에게 보이는 메시지는 표준 오류로 냅니다.
+    let mut kernel = match Kernel::new(no_std, prelude) {
+        Ok(kernel) => kernel,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return;
+        }
+    };
+    if let Err(e) = kernel.serve_stdio() {
+        eprintln!("❌ 커널 입출력 오류: {}", e);
+    }
+}
+
+/// `high serve [--port N] [--host ADDR]`: 플레이그라운드 HTTP 서버를 띄웁니다.
+async fn run_serve(args: &[String]) {
+    let Some((host, port)) = parse_listen_address(args, playground_server::DEFAULT_HOST, playground_server::DEFAULT_PORT) else {
+        println!("사용법: high serve [--port 8080] [--host 127.0.0.1]");
+        return;
+    };
+    if let Err(e) = PlaygroundServer::new().serve(&host, port).await {
+        println!("❌ 서버 시작 실패 ({}:{}): {}", host, port, e);
+    }
+}
+
+/// `high daemon [--port N] [--host ADDR]`: JSON-RPC 컴파일러 데몬을 띄웁니다.
+async fn run_daemon(args: &[String]) {
+    let Some((host, port)) = parse_listen_address(args, daemon::DEFAULT_HOST, daemon::DEFAULT_PORT) else {
+        println!("사용법: high daemon [--port 7420] [--host 127.0.0.1]");
+        return;
+    };
+    if let Err(e) = CompilerDaemon::new().serve(&host, port).await {
+        println!("❌ 데몬 시작 실패 ({}:{}): {}", host, port, e);
+    }
+}
+
+/// `--port N` / `--host ADDR` 를 읽습니다. 알 수 없는 인자가 있으면 None 입니다.
+fn parse_listen_address(args: &[String], default_host: &str, default_port: u16) -> Option<(String, u16)> {
+    let mut host = default_host.to_string();
+    let mut port = default_port;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match (arg.as_str(), iter.next()) {
+            ("--port", Some(value)) => port = value.parse().ok()?,
+            ("--host", Some(value)) => host = value.clone(),
+            _ => return None,
+        }
+    }
+    Some((host, port))
+}
+
+fn run_explain(code: Option<&str>) {
+    let Some(code) = code else {
+        println!("사용법: high explain <code>   (예: high explain E0010)");
+        println!();
+        for entry in error_codes::ALL {
+            println!("  {}  {}", entry.code, entry.title.get());
+        }
+        return;
+    };
+
+    match error_codes::explain(code) {
+        Some(text) => print!("{}", text),
+        None => println!("❌ 알 수 없는 진단 코드 '{}' (`high explain` 으로 전체 목록을 볼 수 있습니다)", code),
+    }
+}
@@ -0,0 +1,130 @@
+// maps.rs
+// 맵 값(`Value::Map`)의 연산입니다.
+//
+// 맵은 키에서 값으로 가는 해시 표이며 키를 넣은 순서를 기억합니다. `keys`, `values`, 출력, 스냅샷이 모두 그 순서를
+// 따르므로 같은 프로그램은 실행마다 같은 결과를 냅니다. 배열처럼 바뀌지 않는 값이라 `insert` 는 새 맵을 만듭니다.
+//
+//   { "a": 1, "b": 2 }       리터럴. 같은 키가 다시 나오면 뒤의 값이 앞의 자리를 차지합니다. `{}` 는 빈 맵입니다.
+//   m[key]                   키의 값. 없는 키면 패닉 (E0030)
+//   insert(m, key, value)    key 의 값을 바꾸거나 끝에 덧붙인 새 맵
+//   contains_key(m, key)     키가 있는지
+//   keys(m)  values(m)       넣은 순서의 배열
+//   len(m)                   항목 수
+//   a == b                   키가 같고 키마다 값이 `==` 이면 참. 넣은 순서는 보지 않습니다.
+//
+// 키는 int, bool, string 만 됩니다. float 는 NaN 과 -0.0 때문에 `==` 가 해시와 맞지 않습니다.
+
+use alloc::sync::Arc;
+use core::fmt;
+
+use crate::compat::*;
+use crate::data_structures::{TokenKind, Value};
+use crate::operators;
+
+/// 맵 키. 같은 값이면 같은 해시입니다.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    Integer(i64),
+    Boolean(bool),
+    String(Arc<str>),
+}
+
+impl Key {
+    /// 키가 될 수 없는 값이면 None 입니다.
+    pub fn from_value(value: &Value) -> Option<Key> {
+        match value {
+            Value::Integer(i) => Some(Key::Integer(*i)),
+            Value::Boolean(b) => Some(Key::Boolean(*b)),
+            Value::String(s) => Some(Key::String(s.clone())),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            Key::Integer(i) => Value::Integer(*i),
+            Key::Boolean(b) => Value::Boolean(*b),
+            Key::String(s) => Value::String(s.clone()),
+        }
+    }
+}
+
+/// 출력용. 문자열 키는 따옴표로 감쌉니다. (`{"a": 1}`)
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Integer(i) => write!(f, "{}", i),
+            Key::Boolean(b) => write!(f, "{}", b),
+            Key::String(s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+/// 넣은 순서를 기억하는 해시 맵. `slots` 는 키에서 `entries` 의 자리로 갑니다.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    entries: Vec<(Key, Value)>,
+    slots: HashMap<Key, usize>,
+}
+
+impl Map {
+    pub fn get(&self, key: &Key) -> Option<&Value> {
+        self.slots.get(key).map(|&slot| &self.entries[slot].1)
+    }
+
+    pub fn contains_key(&self, key: &Key) -> bool {
+        self.slots.contains_key(key)
+    }
+
+    /// 있는 키면 값을 바꾸고 자리는 그대로 둡니다.
+    pub fn insert(&mut self, key: Key, value: Value) {
+        match self.slots.get(&key) {
+            Some(&slot) => self.entries[slot].1 = value,
+            None => {
+                self.slots.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 넣은 순서의 항목
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
+
+impl FromIterator<(Key, Value)> for Map {
+    fn from_iter<I: IntoIterator<Item = (Key, Value)>>(entries: I) -> Self {
+        let mut map = Map::default();
+        for (key, value) in entries {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+/// 키마다 `==`. 비교할 수 없는 값 쌍이 있으면 None 입니다.
+pub fn equal(a: &Map, b: &Map) -> Option<bool> {
+    if a.len() != b.len() {
+        return Some(false);
+    }
+    for (key, x) in a.iter() {
+        let Some(y) = b.get(key) else {
+            return Some(false);
+        };
+        match operators::binary(&TokenKind::Eq, x, y).ok()? {
+            Value::Boolean(true) => {}
+            Value::Boolean(false) => return Some(false),
+            _ => return None,
+        }
+    }
+    Some(true)
+}
@@ -0,0 +1,443 @@
+// messages.rs
+// 진단 메시지 카탈로그: 진단 코드(와 세부 키)별 한국어/영어 메시지입니다.
+//
+// 로케일은 `--locale ko|en` 또는 환경 변수 HIGH_LOCALE (없으면 LANG) 로 정해지며,
+// 기본값은 한국어입니다. 메시지 템플릿의 `{0}`, `{1}` ... 은 인자로 치환됩니다.
+
+use core::fmt::Display;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::compat::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Locale {
+    Ko,
+    En,
+}
+
+impl Locale {
+    /// "ko", "en", "en_US.UTF-8" 같은 문자열을 해석합니다.
+    pub fn parse(text: &str) -> Option<Self> {
+        let lang = text.split(['_', '-', '.']).next().unwrap_or("").to_ascii_lowercase();
+        match lang.as_str() {
+            "ko" => Some(Locale::Ko),
+            "en" | "c" | "posix" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// HIGH_LOCALE, LANG 순서로 환경 변수를 확인합니다.
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Option<Self> {
+        ["HIGH_LOCALE", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|value| Locale::parse(&value))
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        _ => Locale::Ko,
+    }
+}
+
+/// 로케일별 고정 문자열입니다.
+#[derive(Debug, Clone, Copy)]
+pub struct Localized {
+    pub ko: &'static str,
+    pub en: &'static str,
+}
+
+impl Localized {
+    pub fn get(&self) -> &'static str {
+        match locale() {
+            Locale::Ko => self.ko,
+            Locale::En => self.en,
+        }
+    }
+}
+
+/// 키에 해당하는 현재 로케일의 메시지를 인자로 채워 돌려줍니다.
+/// 카탈로그에 없는 키는 키 자체를 돌려주어 누락을 바로 알 수 있게 합니다.
+pub fn tr(key: &str, args: &[&dyn Display]) -> String {
+    let Some((_, text)) = CATALOG.iter().find(|(k, _)| *k == key) else {
+        return key.to_string();
+    };
+    // 한 번만 훑으며 치환하므로 인자 값 안의 `{1}` 같은 문자열은 다시 치환되지 않습니다.
+    let mut message = String::new();
+    let mut rest = text.get();
+    while let Some(open) = rest.find('{') {
+        message.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let placeholder = after
+            .find('}')
+            .and_then(|close| after[..close].parse::<usize>().ok().map(|index| (index, close)));
+        match placeholder {
+            Some((index, close)) if index < args.len() => {
+                message.push_str(&args[index].to_string());
+                rest = &after[close + 1..];
+            }
+            _ => {
+                message.push('{');
+                rest = after;
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+/// 카탈로그와 진단 코드 레지스트리에서 쓰는 짧은 생성자입니다.
+pub const fn t(ko: &'static str, en: &'static str) -> Localized {
+    Localized { ko, en }
+}
+
+/// 키는 진단 코드(예: "E0003") 또는 `코드.세부키` (예: "E0003.assert") 입니다.
+/// 코드가 없는 공통 문구는 `영역.이름` 형식을 씁니다.
+const CATALOG: &[(&str, Localized)] = &[
+    // ─── 런타임 ─────────────────────────────
+    ("E0001", t("정의되지 않은 변수 '{0}'", "undefined variable '{0}'")),
+    ("E0002", t("호출할 수 없는 값입니다: {0}", "value is not callable: {0}")),
+    ("E0003.function", t(
+        "함수 호출 인자 개수 불일치: {0}개 예상, {1}개 제공",
+        "wrong number of arguments: expected {0}, got {1}",
+    )),
+    ("E0003.assert", t("assert() 에는 조건식이 필요합니다.", "assert() requires a condition")),
+    ("E0003.format", t("format() 에는 서식 문자열이 필요합니다.", "format() requires a format string")),
+    ("E0004.expression", t("지원하지 않는 표현식입니다.", "unsupported expression")),
+    ("E0004.regex", t(
+        "{0}() 는 정규식 엔진이 있는 빌드(regex 기능)에서만 쓸 수 있습니다.",
+        "{0}() requires a build with the regex engine (the regex feature)",
+    )),
+    ("E0004.run_process", t(
+        "run_process() 는 하위 프로세스를 만들 수 있는 빌드(std 기능, WASI 제외)에서만 쓸 수 있습니다.",
+        "run_process() requires a build that can spawn processes (the std feature, not WASI)",
+    )),
+    ("E0004.now", t("now() 는 시계가 있는 빌드(std 기능)에서만 쓸 수 있습니다.", "now() requires a build with a clock (the std feature)")),
+    ("E0004.read_file_bytes", t(
+        "read_file_bytes() 는 파일 시스템이 있는 빌드(std 기능)에서만 쓸 수 있습니다.",
+        "read_file_bytes() requires a build with file system access (the std feature)",
+    )),
+    ("E0005", t("eval 실패: {0}", "eval failed: {0}")),
+    ("E0006.process", t(
+        "run_process() 는 프로세스 실행 권한이 필요합니다. `--allow-process` 로 허용하세요.",
+        "run_process() needs the process capability; pass `--allow-process` to grant it",
+    )),
+    ("E0006.files", t("{0}() 는 파일 접근 권한이 필요합니다.", "{0}() needs the file capability")),
+    ("E0006.debug", t(
+        "{0}() 는 디버그 권한이 필요합니다. `--allow-debug` 로 허용하세요.",
+        "{0}() needs the debug capability; pass `--allow-debug` to grant it",
+    )),
+    ("E0007", t("'{0}' 은 불변 바인딩이므로 대입할 수 없습니다", "cannot assign to immutable binding '{0}'")),
+    ("E0007.help", t("바꿀 바인딩은 `let mut {0}` 으로 선언하세요.", "declare it with `let mut {0}` to allow assignment")),
+    ("E0010.eval", t("eval() 은 문자열을 받습니다.", "eval() expects a string")),
+    ("E0010.format", t("format() 의 첫 인자는 문자열이어야 합니다.", "the first argument of format() must be a string")),
+    ("E0010.weak_ref", t(
+        "weak_ref() 와 on_finalize() 는 제너레이터에만 쓸 수 있습니다 ({0} 은 복사되는 값입니다).",
+        "weak_ref() and on_finalize() only accept generators ({0} is a copied value)",
+    )),
+    ("E0010.deref", t("deref() 는 weak_ref() 결과를 받습니다 ({0} 을 받음).", "deref() expects a weak reference, got {0}")),
+    ("E0010.on_finalize", t(
+        "on_finalize() 의 두 번째 인자는 함수여야 합니다 ({0} 을 받음).",
+        "the second argument of on_finalize() must be a function, got {0}",
+    )),
+    ("E0010.index", t(
+        "인덱싱은 bytes 와 배열에 정수 인덱스로, 맵에 키로 씁니다 ({0} 을 받음).",
+        "only bytes and arrays (with an integer index) and maps (with a key) can be indexed (got {0})",
+    )),
+    ("E0010.map_key", t("맵 키는 int, bool, string 이어야 합니다 ({0} 을 받음).", "map keys must be int, bool or string (got {0})")),
+    ("E0010.builtin", t("{0}() 는 {1} 을 받습니다 ({2} 을 받음).", "{0}() expects {1}, got {2}")),
+    ("E0010.datetime", t("날짜/시간 오류: {0}", "date/time error: {0}")),
+    ("E0010.regex", t("잘못된 정규식입니다: {0}", "invalid regular expression: {0}")),
+    ("E0010.from_hex", t("from_hex() 인자가 올바른 16진수가 아닙니다: {0}", "from_hex() argument is not valid hex: {0}")),
+    ("E0010.from_utf8", t(
+        "from_utf8() 인자가 올바른 UTF-8 이 아닙니다 ({0}번째 바이트부터).",
+        "from_utf8() argument is not valid UTF-8 (from byte {0})",
+    )),
+    ("E0010.operator", t(
+        "연산자 '{0}' 는 {1} 와 {2} 에 쓸 수 없습니다.",
+        "operator '{0}' cannot be applied to {1} and {2}",
+    )),
+    ("E0010.range", t("범위의 양 끝은 int 여야 합니다 ({0}..{1} 을 받음).", "range bounds must be int, got {0}..{1}")),
+    ("E0010.condition", t("조건식은 bool 이어야 합니다 ({0} 을 받음).", "a condition must be a bool, got {0}")),
+    ("E0010.not_derived", t("enum {0} 에 @derive({1}) 가 없습니다.", "enum {0} does not derive {1}")),
+    ("E0010.unary_operator", t("연산자 '{0}' 는 {1} 에 쓸 수 없습니다.", "operator '{0}' cannot be applied to {1}")),
+    ("E0010.annotation", t("'{0}' 은 {1} 로 표기되었지만 {2} 값을 받습니다.", "'{0}' is annotated as {1} but is given a {2} value")),
+    ("E0010.element", t(
+        "'{0}' 은 {1} 로 표기되었지만 요소 [{2}] 가 {3} 값입니다.",
+        "'{0}' is annotated as {1} but element [{2}] is a {3} value",
+    )),
+    ("E0010.coercion", t("'{0}' 은 {1} 로 표기되었지만 실행 중에 {2} 값을 받았습니다.", "'{0}' is annotated as {1} but received a {2} value at run time")),
+    ("E0010.union_operand", t(
+        "'{0}' 은 여기서 {1} 일 수 있어 연산자 '{2}' 를 쓸 수 없습니다.",
+        "'{0}' may be {1} here, which operator '{2}' does not accept",
+    )),
+    ("E0010.union_operand_help", t(
+        "`if type_of {0} == \"int\" { ... }` 처럼 먼저 타입을 검사해 좁히세요.",
+        "narrow it first with a type test such as `if type_of {0} == \"int\" { ... }`",
+    )),
+    ("E0011", t("알 수 없는 타입 '{0}'", "unknown type '{0}'")),
+    ("E0011.help", t("'{0}' 을 쓰려던 것인가요?", "did you mean '{0}'?")),
+    ("E0012.duplicate", t("타입 '{0}' 이 이미 선언되었습니다", "type '{0}' is already declared")),
+    ("E0012.cycle", t("타입 별칭이 끝나지 않고 순환합니다: {0}", "type aliases form a cycle: {0}")),
+    ("E0013", t("'{0}' 의 타입을 알 수 없어 암시적으로 any 가 됩니다.", "the type of '{0}' cannot be inferred and would be implicitly any")),
+    ("E0013.help", t(
+        "타입 표기를 붙이세요. 동적으로 다루려면 `let {0}: any = ...` 로 명시합니다.",
+        "add a type annotation, or write `let {0}: any = ...` to keep it dynamic",
+    )),
+    // ─── 서식 ─────────────────────────────
+    ("E0020.unmatched_close", t(
+        "서식 문자열에 짝이 없는 '}' 가 있습니다. '}}' 로 쓰세요.",
+        "unmatched '}' in format string; write '}}' for a literal brace",
+    )),
+    ("E0020.unclosed_open", t("서식 문자열의 '{' 가 닫히지 않았습니다.", "unclosed '{' in format string")),
+    ("E0020.bad_index", t("잘못된 인자 위치입니다: '{0}'", "invalid argument position: '{0}'")),
+    ("E0020.too_large", t("너비와 정밀도는 {1} 이하여야 합니다: '{0}'", "width and precision must be at most {1}: '{0}'")),
+    ("E0020.missing_precision", t("'.' 뒤에 정밀도 숫자가 필요합니다: '{0}'", "expected a precision after '.': '{0}'")),
+    ("E0020.bad_spec", t("지원하지 않는 서식 지정자입니다: '{0}'", "unsupported format specifier: '{0}'")),
+    ("E0020.missing_argument", t(
+        "format 인자가 부족합니다: {0}번 인자가 필요하지만 {1}개만 주어졌습니다.",
+        "not enough format arguments: argument {0} is required but only {1} were given",
+    )),
+    ("E0020.unused_argument", t(
+        "format 의 {0}번 인자가 서식 문자열에서 사용되지 않았습니다.",
+        "format argument {0} is never used in the format string",
+    )),
+    ("E0020.hex_non_integer", t(
+        "16진수 서식은 정수에만 쓸 수 있습니다. (받은 값: {0})",
+        "hex formatting only applies to integers (got {0})",
+    )),
+    // ─── 실행 흐름 ─────────────────────────────
+    ("E0030", t("패닉: {0}", "panic: {0}")),
+    ("E0030.explicit", t("명시적 panic", "explicit panic")),
+    ("E0030.division_by_zero", t("0으로 나누었습니다.", "division by zero")),
+    ("E0030.index_out_of_bounds", t("인덱스 {0} 이 범위를 벗어났습니다 (길이 {1}).", "index {0} out of bounds (length {1})")),
+    ("E0030.slice_out_of_bounds", t(
+        "범위 [{0}:{1}] 이 범위를 벗어났습니다 (길이 {2}).",
+        "slice [{0}:{1}] out of bounds (length {2})",
+    )),
+    ("E0030.not_iterable", t(
+        "{0} 값은 for-in 으로 돌 수 없습니다 (range, array, generator 만 됩니다).",
+        "cannot iterate over a {0} value with for-in (expected range, array or generator)",
+    )),
+    ("E0030.unknown_key", t("'{0}' 이라는 필드, 그룹, 키가 없습니다.", "no field, group or key named '{0}'")),
+    ("E0030.assert_failed", t("assertion 실패", "assertion failed")),
+    ("E0030.assert_failed_message", t("assertion 실패: {0}", "assertion failed: {0}")),
+    ("E0030.assert_not_bool", t(
+        "assert() 조건은 bool 이어야 합니다. (받은 타입: {0})",
+        "assert() condition must be a bool (got {0})",
+    )),
+    ("E0031", t(
+        "'yield' 는 제너레이터 함수 안에서만 사용할 수 있습니다.",
+        "'yield' can only be used inside a generator function",
+    )),
+    ("E0032", t("실행 흐름 불균형: 문장 {0}개", "Unbalanced execution flow: {0} statements")),
+    ("E0032.help", t(
+        "제어 흐름이 올바르게 종료되도록 하세요.",
+        "Ensure control flows terminate correctly.",
+    )),
+    ("E0033", t("{0}에서 나누는 쪽이 항상 0 입니다", "the divisor of this {0} is always zero")),
+    ("E0033.division", t("나눗셈", "division")),
+    ("E0033.remainder", t("나머지 연산", "remainder")),
+    ("E0033.help", t(
+        "실행되면 패닉합니다. 의도한 것이면 `@allow(division_by_zero)` 를 붙이세요.",
+        "this panics when it runs; add `@allow(division_by_zero)` if that is intended",
+    )),
+    ("trace.header", t("호출 스택 (가장 안쪽부터):", "call stack (innermost first):")),
+    ("trace.frame", t("{0}: {1} (호출 위치 {2})", "{0}: {1} (called at {2})")),
+    ("trace.top_level", t("<최상위>", "<top level>")),
+    ("trace.anonymous", t("<익명 함수>", "<anonymous function>")),
+    ("runtime.process_failed", t("'{0}' 을 실행하지 못했습니다: {1}", "failed to run '{0}': {1}")),
+    ("runtime.read_failed", t("파일 '{0}' 을 읽지 못했습니다: {1}", "failed to read file '{0}': {1}")),
+    ("runtime.executed", t("{0}개 문장을 실행했습니다.", "Executed {0} statements successfully.")),
+    // ─── 어트리뷰트 ─────────────────────────────
+    ("E0040", t("알 수 없는 어트리뷰트 '@{0}' 는 무시됩니다.", "unknown attribute '@{0}' is ignored")),
+    ("E0040.help", t("사용 가능한 어트리뷰트: {0}", "available attributes: {0}")),
+    ("E0041", t("@{0}: {1}", "@{0}: {1}")),
+    ("E0041.cfg_empty", t("@cfg 에는 최소 하나의 조건이 필요합니다.", "@cfg requires at least one condition")),
+    ("E0041.no_args", t("이 어트리뷰트는 인자를 받지 않습니다.", "this attribute takes no arguments")),
+    ("E0041.allow_empty", t("@allow 에는 허용할 린트 이름이 필요합니다.", "@allow requires a lint name")),
+    ("E0041.deprecated_args", t(
+        "@deprecated 는 선택적인 문자열 메시지 하나만 받습니다.",
+        "@deprecated takes a single optional string message",
+    )),
+    ("E0041.derive_args", t(
+        "@derive 는 eq, show, clone 중 하나 이상만 받습니다.",
+        "@derive takes one or more of eq, show, clone",
+    )),
+    ("E0042", t("@{0} 는 {1}에만 붙일 수 있습니다.", "@{0} can only be placed on {1}")),
+    ("E0042.type_declaration", t("enum 선언", "enum declarations")),
+    // ─── 매크로 ─────────────────────────────
+    ("E0050", t(
+        "매크로 '{0}'의 본문은 단일 표현식(또는 return 문)이어야 합니다.",
+        "the body of macro '{0}' must be a single expression (or return statement)",
+    )),
+    ("E0051", t("매크로 '{0}' 확장 깊이가 {1}를 초과했습니다.", "expansion of macro '{0}' exceeded depth {1}")),
+    ("E0052", t(
+        "매크로 '{0}' 인자 개수 불일치: {1}개 예상, {2}개 제공",
+        "wrong number of arguments to macro '{0}': expected {1}, got {2}",
+    )),
+    ("compile.macro_failed", t("매크로 확장 실패: {0}", "macro expansion failed: {0}")),
+    ("compile.batch_summary", t(
+        "프로그램 {0}개: 성공 {1}, 실패 {2}, 취소 {3}, 시간 초과 {4} ({5}ms)",
+        "{0} programs: {1} succeeded, {2} failed, {3} cancelled, {4} timed out ({5}ms)",
+    )),
+    // ─── 사용자 정의 연산자 ─────────────────────────────
+    ("operator.precedence", t(
+        "연산자 '{0}' 의 우선순위 {1} 은 1 이상 {2} 이하여야 합니다.",
+        "precedence {1} of operator '{0}' must be between 1 and {2}",
+    )),
+    ("operator.conflict", t(
+        "연산자 '{0}' 는 이미 등록된 연산자 '{1}' (우선순위 {2}) 와 겹칩니다.",
+        "operator '{0}' conflicts with registered operator '{1}' (precedence {2})",
+    )),
+    ("operator.not_single_token", t(
+        "연산자 '{0}' 는 토큰 하나로 읽히지 않습니다.",
+        "operator '{0}' does not lex as a single token",
+    )),
+    ("operator.reserved", t(
+        "'{0}' 는 문법이 이미 쓰는 키워드나 기호라서 연산자로 쓸 수 없습니다.",
+        "'{0}' is a keyword or symbol reserved by the grammar",
+    )),
+    // ─── 이름 해석 ─────────────────────────────
+    ("E0060", t("사용 중단된(deprecated) '{0}' 를 사용합니다{1}", "use of deprecated '{0}'{1}")),
+    ("E0060.help", t(
+        "'{0}' 는 {1} 에서 @deprecated 로 선언되었습니다. 파일 맨 앞에 `@!allow(deprecated)` 를 두면 경고가 꺼집니다.",
+        "'{0}' was declared @deprecated at {1}. Put `@!allow(deprecated)` at the top of the file to silence this warning.",
+    )),
+    ("E0061", t("{0} '{1}' 는 어디에서도 쓰이지 않습니다", "{0} '{1}' is never used")),
+    ("E0061.function", t("함수", "function")),
+    ("E0061.macro", t("매크로", "macro")),
+    ("E0061.help", t(
+        "진입점이면 `@export` 를 붙이고, 아니면 지우세요. `--strip-dead` 는 코드 생성 전에 이 정의를 뺍니다.",
+        "Mark it `@export` if it is an entry point, or remove it. `--strip-dead` drops it before code generation.",
+    )),
+    ("E0062", t("표준 라이브러리 모듈 '{0}' 는 쓰이지 않아 제거했습니다", "standard library module '{0}' is unused and was removed")),
+    // ─── match 검사 ─────────────────────────────
+    ("E0070", t("{0}번째 match arm 에는 도달할 수 없습니다.", "match arm #{0} is unreachable")),
+    ("E0070.help", t(
+        "앞선 arm 들이 이 패턴의 모든 값을 이미 처리합니다.",
+        "earlier arms already handle every value of this pattern",
+    )),
+    ("E0071", t("or-패턴의 {0}번째 대안은 앞선 대안에 가려집니다.", "alternative #{0} of the or-pattern is shadowed by an earlier one")),
+    ("E0072", t("match 가 모든 경우를 처리하지 않습니다.", "match does not handle every case")),
+    ("E0072.help", t(
+        "나머지 값을 처리하려면 `_ => ...` arm 을 추가하세요.",
+        "add a `_ => ...` arm to handle the remaining values",
+    )),
+    // ─── 진단 출력 ─────────────────────────────
+    ("render.info", t("정보", "note")),
+    ("render.warning", t("경고", "warning")),
+    ("render.error", t("오류", "error")),
+    ("render.fatal", t("치명적 오류", "fatal")),
+    ("render.help", t("도움말", "help")),
+    // ─── 컴파일 / 코드 생성 ─────────────────────────────
+    ("E0080", t(
+        "컴파일 실패: 실행 흐름이 균형을 이루지 않음 (return 누락 또는 위치 오류).",
+        "compilation failed: unbalanced execution flow (missing or misplaced return)",
+    )),
+    ("E0081", t("타깃 설정 오류: {0}", "invalid target configuration: {0}")),
+    ("E0081.unknown_cpu", t(
+        "알 수 없는 타깃 CPU '{0}' (지원: x86-64, x86-64-v2, x86-64-v3)",
+        "unknown target CPU '{0}' (supported: x86-64, x86-64-v2, x86-64-v3)",
+    )),
+    ("E0081.unknown_os", t(
+        "알 수 없는 타깃 OS '{0}' (지원: linux, windows, macos)",
+        "unknown target OS '{0}' (supported: linux, windows, macos)",
+    )),
+    ("E0081.unknown_feature", t("알 수 없는 타깃 기능 '{0}'", "unknown target feature '{0}'")),
+    ("E0090", t("어셈블리 생성 실패: {0}", "failed to generate assembly: {0}")),
+    ("E0091", t("링커 실패: {0}", "linking failed: {0}")),
+    ("E0092", t("{0} 백엔드는 `{1}` 를 지원하지 않습니다.", "the {0} backend does not support `{1}`")),
+    ("E0092.nested_function", t(
+        "{0} 백엔드는 최상위 `let` 이 아닌 곳의 함수 정의를 지원하지 않습니다.",
+        "the {0} backend only supports functions bound by a top-level `let`",
+    )),
+    ("E0092.function_value", t(
+        "{0} 백엔드는 함수 '{1}' 를 값으로 쓰는 것을 지원하지 않습니다.",
+        "the {0} backend does not support using function '{1}' as a value",
+    )),
+    ("E0092.indirect_call", t(
+        "{0} 백엔드는 함수 값 호출을 지원하지 않습니다. 최상위 함수 이름으로 호출하세요.",
+        "the {0} backend does not support calls through function values; call a top-level function by name",
+    )),
+    ("E0092.global_capture", t(
+        "{0} 백엔드에서 함수 '{1}' 는 최상위 변수 '{2}' 를 읽을 수 없습니다.",
+        "in the {0} backend, function '{1}' cannot read top-level variable '{2}'",
+    )),
+    ("E0092.format_template", t(
+        "{0} 백엔드에서 format() 의 첫 인자는 문자열 리터럴이어야 합니다.",
+        "in the {0} backend, the first argument of format() must be a string literal",
+    )),
+    ("E0093", t("cargo build 실패: {0}", "cargo build failed: {0}")),
+    ("E0094", t("Python 실행 실패: {0}", "running Python failed: {0}")),
+    ("E0095", t("작업이 취소되었습니다.", "the operation was cancelled")),
+    ("E0096", t("{0} 단계가 제한 시간 {1}ms 안에 끝나지 않았습니다.", "the {0} stage did not finish within {1}ms")),
+    ("E0097", t("다시 컴파일한 '{0}' 이 {1}번째 바이트부터 다릅니다.", "recompiled '{0}' differs starting at byte {1}")),
+    ("E0097.rebuild", t("재현성 확인용 재컴파일 실패: {0}", "recompiling for the reproducibility check failed: {0}")),
+    ("E0098", t("프로필이 이 소스로 만든 것이 아니어서 쓰지 않습니다.", "the profile was not recorded from this source and is ignored")),
+    // ─── 코드 분석 ─────────────────────────────
+    ("E0100", t("함수 '{0}' 가 {1}줄로 너무 깁니다. (기준 {2}줄)", "function '{0}' is {1} lines long (limit {2})")),
+    ("E0100.help", t("독립적인 부분을 작은 함수로 나누세요.", "split independent parts into smaller functions")),
+    ("E0101", t("함수 '{0}' 의 이름이 의미를 드러내지 않습니다: {1}", "function '{0}' uses uninformative names: {1}")),
+    ("E0102", t("분석기 {0}: {1}", "analyzer {0}: {1}")),
+    ("E0103", t("분석기를 사용할 수 없어 기본 분석기로 대신했습니다: {0}", "analyzer unavailable, fell back to the default analyzer: {0}")),
+    ("E0104", t("식의 값이 쓰이지 않습니다", "the value of this expression is unused")),
+    ("E0104.help", t("일부러 버리는 값이면 `_ = 식` 으로 쓰세요.", "write `_ = expr` to discard the value on purpose")),
+    ("E0104.implicit_result", t("함수의 마지막 식은 반환값이 되지 않습니다", "the last expression of a function is not its result")),
+    ("E0104.implicit_result_help", t("값을 돌려주려면 `return` 을 붙이세요.", "add `return` to return the value")),
+    // ─── 구문 ─────────────────────────────
+    ("E0110.token", t("예상하지 못한 '{0}'", "unexpected '{0}'")),
+    ("E0110.unclosed_block", t("'{' 가 닫히지 않았습니다", "unclosed '{'")),
+    ("E0110.unclosed_block_help", t("블록 끝에 '}' 를 넣으세요.", "add '}' at the end of the block")),
+    ("E0110.unterminated_string", t("문자열이 닫히지 않았습니다", "unterminated string literal")),
+    ("E0110.unterminated_string_help", t("문자열 끝에 '\"' 를 넣으세요.", "add '\"' at the end of the string")),
+    ("E0110.unterminated_comment", t("블록 주석이 닫히지 않았습니다", "unterminated block comment")),
+    ("E0110.unterminated_comment_help", t(
+        "주석 끝에 '*/' 를 넣으세요. 안쪽 '/*' 마다 '*/' 가 하나씩 필요합니다.",
+        "add '*/' at the end of the comment; each nested '/*' needs its own '*/'",
+    )),
+    ("E0110.number_suffix", t("숫자 '{0}' 뒤에 '{1}' 가 붙어 있습니다", "'{1}' directly follows the number '{0}'")),
+    ("E0110.number_suffix_help", t(
+        "지수 표기와 단위 접미사는 지원하지 않습니다. `1e3` 은 `1000.0` 처럼 쓰세요.",
+        "exponent notation and unit suffixes are not supported; write `1e3` as `1000.0`",
+    )),
+    ("E0110.unexpected", t("{1}에서 예상하지 못한 '{0}'", "unexpected '{0}' in {1}")),
+    ("E0110.missing_comma", t("{1}의 '{0}' 앞에 ',' 가 빠졌습니다", "missing ',' before '{0}' in {1}")),
+    ("E0110.unclosed", t("{0}의 '{1}' 가 닫히지 않았습니다", "unclosed '{1}' in {0}")),
+    ("E0110.unclosed_help", t("목록 끝에 '{0}' 를 넣으세요.", "add '{0}' at the end of the list")),
+    ("E0110.too_many", t("{0}은 최대 {1}개까지입니다", "{0} may have at most {1} entries")),
+    ("E0110.arguments", t("인자 목록", "argument list")),
+    ("E0110.parameters", t("매개변수 목록", "parameter list")),
+    ("E0110.attribute_arguments", t("어트리뷰트 인자 목록", "attribute argument list")),
+    ("E0110.match_arms", t("match arm 목록", "match arms")),
+    ("E0110.array_elements", t("배열 요소 목록", "array element list")),
+    ("E0110.map_entries", t("맵 항목 목록", "map entry list")),
+    ("E0110.variants", t("enum 변형 목록", "enum variant list")),
+    ("E0110.variant_fields", t("변형 필드 목록", "variant field list")),
+    ("E0110.ternary_colon", t("조건식의 '?' 뒤에 ':' 가 빠졌습니다", "missing ':' after '?' in conditional expression")),
+    ("E0110.ternary_colon_help", t("`조건 ? 참일 때 값 : 거짓일 때 값` 으로 쓰세요.", "write it as `condition ? then : else`")),
+    ("E0110.too_deep", t("식이나 블록이 {0}단계보다 깊게 겹쳐 있습니다", "expressions or blocks are nested more than {0} levels deep")),
+    ("E0110.too_deep_help", t(
+        "안쪽 부분을 `let` 바인딩이나 함수로 나누세요.",
+        "split the inner part into `let` bindings or functions",
+    )),
+    ("E0110.integer_too_large", t("정수 '{0}' 가 64비트 정수 범위를 넘습니다", "integer literal '{0}' does not fit in 64 bits")),
+    ("E0110.integer_too_large_help", t(
+        "정수는 9223372036854775807 까지입니다. 더 큰 수는 실수(`1.0`)로 쓰세요.",
+        "integers go up to 9223372036854775807; write larger numbers as floats (`1.0`)",
+    )),
+    ("E0110.assignment_target", t("'{0}' 의 왼쪽은 이름이어야 합니다", "the left side of '{0}' must be a name")),
+    ("E0110.assignment_target_help", t(
+        "필드나 요소는 바꿀 수 없습니다. 새 값을 만들어 이름에 다시 대입하세요.",
+        "fields and elements cannot be changed; build a new value and assign it to the name",
+    )),
+];
@@ -0,0 +1,234 @@
+// mutate.rs
+// `high mutate <file>`: 뮤테이션 테스트
+//
+// 프로그램의 AST 를 한 곳만 바꾼 변이체(mutant)마다 파일의 `@test` 를 다시 실행합니다. 테스트가 하나라도
+// 실패하면 변이체를 잡은(killed) 것이고, 모두 통과하면 살아남은(survived) 것입니다. 살아남은 변이체는
+// 테스트가 그 코드의 동작을 확인하지 않는다는 뜻이므로 소스 범위와 함께 보고합니다.
+//
+//   비교 연산자 뒤집기   <  → >=   >  → <=   <= → >   >= → <   == → !=   != → ==
+//   정수 상수 ±1         n → n + 1,  n → n - 1
+//   문장 지우기          식 문장, return, defer (let 은 지우면 이름이 사라져 언제나 잡히므로 빼고)
+//
+// `@test` 문장과 매크로 정의 본문은 바꾸지 않습니다. 변이는 옵티마이저 패스처럼 `MutVisitor` 로 적용하며,
+// 목록을 만드는 순회와 적용하는 순회가 같은 순서로 자리를 세므로 n 번째 자리는 같은 노드를 가리킵니다.
+// 변이 때문에 끝나지 않는 반복문은 제한 시간(변이 전 실행 시간의 10배, 최소 1초)이 지나면 멈추고 잡은 것으로 봅니다.
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::attributes::has_attribute;
+use crate::cancellation::CancellationToken;
+use crate::data_structures::{Expression, Program, Span, Statement, TokenKind, Value};
+use crate::ft_runtime::HighEnduranceRuntime;
+use crate::navigation::line_column;
+use crate::test_runner::{self, TestOutcome};
+use crate::visitor::{walk_expression_mut, walk_statement_mut, MutVisitor};
+
+/// 변이체 하나의 테스트 제한 시간 하한
+const MIN_MUTANT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// AST 의 한 자리를 바꾸는 방법
+#[derive(Debug, Clone)]
+pub struct Mutation {
+    pub span: Span,
+    /// `'<' → '>='` 같은 설명
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutantStatus {
+    /// 테스트가 실패했습니다.
+    Killed,
+    /// 제한 시간 안에 테스트가 끝나지 않았습니다. (잡은 것으로 셉니다)
+    TimedOut,
+    /// 모든 테스트가 통과했습니다.
+    Survived,
+}
+
+#[derive(Debug, Clone)]
+pub struct MutantOutcome {
+    pub mutation: Mutation,
+    pub status: MutantStatus,
+}
+
+/// 프로그램에서 만들 수 있는 변이 목록 (소스 순서)
+pub fn mutations(program: &Program) -> Vec<Mutation> {
+    let mut mutator = Mutator { target: None, next: 0, found: vec![] };
+    mutator.visit_program(&mut program.clone());
+    mutator.found
+}
+
+/// `index` 번째 변이를 적용한 프로그램
+pub fn mutant(program: &Program, index: usize) -> Program {
+    let mut program = program.clone();
+    Mutator { target: Some(index), next: 0, found: vec![] }.visit_program(&mut program);
+    program
+}
+
+/// 변이 전 테스트를 실행해 모두 통과하는지 확인한 뒤, 변이체마다 테스트를 돌립니다.
+/// `progress` 는 변이체 하나가 끝날 때마다 (끝난 수, 전체 수) 로 불립니다.
+pub fn run(source: &str, mut progress: impl FnMut(usize, usize)) -> Result<Vec<MutantOutcome>, String> {
+    let program = test_runner::test_program(source);
+    let started = Instant::now();
+    match run_tests(program.clone(), None) {
+        (Ok(outcomes), _) if outcomes.is_empty() => return Err("@test 가 없어 변이체를 판정할 수 없습니다.".into()),
+        (Ok(outcomes), _) => {
+            if let Some(failed) = outcomes.iter().find(|o| !o.passed()) {
+                return Err(format!("변이 전에도 테스트 '{}' 가 실패합니다. 테스트를 먼저 고치세요.", failed.name));
+            }
+        }
+        (Err(e), _) => return Err(e),
+    }
+    let limit = (started.elapsed() * 10).max(MIN_MUTANT_TIMEOUT);
+
+    let mutations = mutations(&program);
+    let mut outcomes = vec![];
+    for (index, mutation) in mutations.iter().enumerate() {
+        let status = match run_tests(mutant(&program, index), Some(limit)) {
+            (_, true) => MutantStatus::TimedOut,
+            (Ok(results), _) if results.iter().all(TestOutcome::passed) => MutantStatus::Survived,
+            _ => MutantStatus::Killed,
+        };
+        outcomes.push(MutantOutcome { mutation: mutation.clone(), status });
+        progress(index + 1, mutations.len());
+    }
+    Ok(outcomes)
+}
+
+/// 테스트를 실행합니다. 제한 시간이 지나 멈췄으면 두 번째 값이 true 입니다.
+fn run_tests(program: Program, limit: Option<Duration>) -> (Result<Vec<TestOutcome>, String>, bool) {
+    let mut runtime = HighEnduranceRuntime::new();
+    let token = CancellationToken::new();
+    runtime.cancellation = token.clone();
+    let (done, finished) = mpsc::channel::<()>();
+    if let Some(limit) = limit {
+        let token = token.clone();
+        thread::spawn(move || {
+            if finished.recv_timeout(limit) == Err(mpsc::RecvTimeoutError::Timeout) {
+                token.cancel();
+            }
+        });
+    }
+    let outcomes = test_runner::run_program(program, &mut runtime);
+    let _ = done.send(());
+    (outcomes, token.is_cancelled())
+}
+
+/// 잡은 비율과 살아남은 변이체 목록
+pub fn render_report(outcomes: &[MutantOutcome], source: &str, path: &str) -> String {
+    let survived: Vec<&MutantOutcome> = outcomes.iter().filter(|o| o.status == MutantStatus::Survived).collect();
+    let timed_out = outcomes.iter().filter(|o| o.status == MutantStatus::TimedOut).count();
+    let killed = outcomes.len() - survived.len();
+    let mut report = format!("mutants: {} total, {} killed ({} timed out), {} survived\n", outcomes.len(), killed, timed_out, survived.len());
+    if !outcomes.is_empty() {
+        report.push_str(&format!("mutation score: {:.1}%\n", killed as f64 * 100.0 / outcomes.len() as f64));
+    }
+    if !survived.is_empty() {
+        report.push_str("\nsurviving mutants:\n");
+        for outcome in survived {
+            let span = outcome.mutation.span;
+            let (line, column) = line_column(source, span.start);
+            let code = source[span.byte_range(source)].lines().next().unwrap_or("");
+            report.push_str(&format!(
+                "  {}:{}:{} ({}..{}) {}\n      {}\n",
+                path, line, column, span.start, span.end, outcome.mutation.description, code.trim()
+            ));
+        }
+    }
+    report
+}
+
+/// 변이 자리를 세면서, `target` 번째 자리에 닿으면 그 변이를 적용합니다. `target` 이 None 이면 목록만 모읍니다.
+struct Mutator {
+    target: Option<usize>,
+    next: usize,
+    found: Vec<Mutation>,
+}
+
+impl Mutator {
+    /// 자리 하나를 세고, 이번이 적용할 자리인지 돌려줍니다.
+    fn site(&mut self, span: Span, description: String) -> bool {
+        let index = self.next;
+        self.next += 1;
+        if self.target.is_none() {
+            self.found.push(Mutation { span, description });
+        }
+        self.target == Some(index)
+    }
+}
+
+/// 비교 연산자의 부정
+fn negated(op: &TokenKind<'static>) -> Option<TokenKind<'static>> {
+    Some(match op {
+        TokenKind::Less => TokenKind::GreaterEqual,
+        TokenKind::Greater => TokenKind::LessEqual,
+        TokenKind::LessEqual => TokenKind::Greater,
+        TokenKind::GreaterEqual => TokenKind::Less,
+        TokenKind::Eq => TokenKind::Neq,
+        TokenKind::Neq => TokenKind::Eq,
+        _ => return None,
+    })
+}
+
+impl MutVisitor for Mutator {
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        match stmt {
+            Statement::Attributed { attributes, .. } if has_attribute(attributes, "test") => return,
+            Statement::MacroDefinition { .. } => return,
+            Statement::ExpressionStatement(_)
+            | Statement::DiscardStatement(..)
+            | Statement::ReturnStatement(..)
+            | Statement::DeferStatement(..) => {
+                let span = stmt.span();
+                if self.site(span, "문장 지우기".into()) {
+                    *stmt = Statement::BlockStatement { statements: vec![], span };
+                    return;
+                }
+            }
+            _ => {}
+        }
+        walk_statement_mut(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression) {
+        match expr {
+            Expression::InfixOperation(span, op, ..) => {
+                if let Some(flipped) = negated(op) {
+                    if self.site(*span, format!("'{}' → '{}'", op, flipped)) {
+                        *op = flipped;
+                    }
+                }
+            }
+            Expression::Literal(span, Value::Integer(n)) => {
+                let (span, n) = (*span, *n);
+                for delta in [1, -1] {
+                    let Some(changed) = n.checked_add(delta) else {
+                        continue;
+                    };
+                    if self.site(span, format!("{} → {}", n, changed)) {
+                        *expr = Expression::Literal(span, Value::Integer(changed));
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+        walk_expression_mut(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_locates_mutants_after_non_ascii_text() {
+        let source = "// 한글 주석\nlet 값 = 1 < 2;";
+        let start = source.chars().position(|c| c == '<').unwrap() - 2;
+        let mutation = Mutation { span: Span { start, end: start + 5 }, description: "'<' → '>='".into() };
+        let report = render_report(&[MutantOutcome { mutation, status: MutantStatus::Survived }], source, "a.high");
+        assert!(report.contains("a.high:2:9"), "{}", report);
+        assert!(report.ends_with("\n      1 < 2\n"), "{}", report);
+    }
+}
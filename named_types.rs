@@ -0,0 +1,659 @@
+// named_types.rs
+// 이름 붙은 타입: `type Meters = int` 별칭 선언과 `let d: Meters = ...` 같은 사용자 정의 타입 표기의 해석
+//
+// 타입 이름은 프로그램 전체에서 하나의 이름공간을 씁니다. 블록 안에서 선언한 별칭도 어디서나 보이며,
+// 선언보다 앞에서 써도 됩니다. 별칭의 대상이 다시 별칭이면 내장 타입에 닿을 때까지 따라갑니다.
+//
+// 합 타입 `int | string` 은 구성원 중 하나의 값입니다. 별칭을 풀면 구성원을 펼치고 중복을 지우며,
+// `any` 가 섞이면 전체가 `any` 입니다. 합 타입으로 표기한 바인딩은 흐름에 따라 좁혀집니다.
+//
+//   if type_of x == "int" { ... } else { ... }     then 에서는 int, else 에서는 나머지 구성원
+//
+// 좁혀지지 않은 합 타입 값에 어떤 구성원으로는 실행되지 않는 연산을 쓰면 E0010 입니다.
+//
+// 점진적 타입: 표기가 없고 초기값으로 타입을 알 수 없는 바인딩(호출 결과, 매개변수 등)은 `any` 입니다.
+//
+//   대입      `any` 값은 어떤 표기에도, 어떤 값은 `any` 표기에도 들어갑니다. 타입을 아는 값은 표기의
+//             구성원에 속해야 하며 int 는 float 자리에 넓혀 들어갑니다. (`assignable`) 요소 타입이 섞인
+//             배열 리터럴은 배열로 표기된 자리에서 요소마다 검사합니다.
+//   연산      피연산자 중 하나라도 `any` 면 검사하지 않고 실행 시점의 연산자 검사(E0010)에 맡깁니다.
+//   실행 시점 표기된 `let` 이 강제 변환 지점입니다. int 는 float 로 넓히고, 속하지 않는 값이면 바인딩이
+//             E0010 오류 값이 됩니다. (`coerce`, ft_runtime)
+//   --strict  암시적 any 를 금지합니다. 명시한 `: any` 는 허용합니다. (E0013)
+//
+//   E0010  타입을 아는 값이 표기된 타입에 맞지 않거나, 타입을 아는 피연산자(좁히지 않은 합 타입 값 포함)에
+//          쓸 수 없는 연산, bool 이 아닌 조건식
+//   E0011  선언되지 않은 타입 이름. 비슷한 이름(내장 타입 포함)이 있으면 도움말로 제안합니다.
+//   E0012  잘못된 별칭: 같은 이름을 두 번 선언했거나, 별칭끼리 서로를 가리켜 끝나지 않습니다.
+//   E0013  --strict 에서 표기도 없고 초기값으로 타입을 알 수 없는 바인딩
+
+use alloc::rc::Rc;
+
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, Expression, Program, Span, Statement, TokenKind, TypeAnnotation, Value};
+use crate::error_codes;
+use crate::interner::Symbol;
+use crate::maps::Map;
+use crate::operators::{self, OperatorError};
+use crate::stdlib::LinkedModule;
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+/// 표기에 쓸 수 있는 내장 타입 이름 (제안 후보)
+const BUILTIN_TYPES: &[&str] = &["int", "float", "bool", "string", "void", "any"];
+
+/// 선언된 타입 이름 하나
+#[derive(Debug, Clone)]
+pub enum NamedType {
+    /// `type NAME = target`
+    Alias { target: TypeAnnotation, span: Span },
+}
+
+/// 이름 표기를 해석하지 못한 이유
+#[derive(Debug, Clone, PartialEq)]
+pub enum Unresolved {
+    Unknown(String),
+    /// 순환을 이루는 별칭 이름들 (선언을 따라간 순서)
+    Cycle(Vec<String>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NamedTypes {
+    types: HashMap<Symbol, NamedType>,
+    /// 먼저 선언된 것이 남고, 뒤의 같은 이름 선언은 여기에 모입니다. (E0012)
+    duplicates: Vec<(Symbol, Span)>,
+}
+
+impl NamedTypes {
+    pub fn collect(program: &Program) -> Self {
+        let mut collector = Collector(NamedTypes::default());
+        collector.visit_program(program);
+        collector.0
+    }
+
+    /// 별칭을 등록합니다. 이미 있는 이름이면 등록하지 않고 false 입니다.
+    pub fn declare(&mut self, name: Symbol, target: &TypeAnnotation, span: Span) -> bool {
+        if self.types.contains_key(&name) {
+            return false;
+        }
+        self.types.insert(name, NamedType::Alias { target: target.clone(), span });
+        true
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NamedType> {
+        self.types.get(&Symbol::intern(name))
+    }
+
+    /// 사용자 정의 표기를 내장 타입 표기(또는 내장 타입들의 합)로 바꿉니다. 내장 타입 표기는 그대로 돌려줍니다.
+    pub fn resolve(&self, annotation: &TypeAnnotation) -> Result<TypeAnnotation, Unresolved> {
+        self.resolve_in(annotation, &mut vec![])
+    }
+
+    fn resolve_in(&self, annotation: &TypeAnnotation, seen: &mut Vec<String>) -> Result<TypeAnnotation, Unresolved> {
+        match annotation {
+            TypeAnnotation::Custom(name) => {
+                if let Some(start) = seen.iter().position(|s| s == name) {
+                    return Err(Unresolved::Cycle(seen[start..].to_vec()));
+                }
+                let Some(NamedType::Alias { target, .. }) = self.get(name) else {
+                    return Err(Unresolved::Unknown(name.clone()));
+                };
+                seen.push(name.clone());
+                let resolved = self.resolve_in(target, seen);
+                seen.pop();
+                resolved
+            }
+            TypeAnnotation::Union(members) => {
+                let mut flat: Vec<TypeAnnotation> = vec![];
+                for member in members {
+                    for resolved in self::members(&self.resolve_in(member, seen)?) {
+                        if !flat.contains(&resolved) {
+                            flat.push(resolved);
+                        }
+                    }
+                }
+                Ok(union_of(flat))
+            }
+            TypeAnnotation::Array(element) => Ok(TypeAnnotation::Array(Box::new(self.resolve_in(element, seen)?))),
+            TypeAnnotation::Map(key, value) => {
+                Ok(TypeAnnotation::Map(Box::new(self.resolve_in(key, seen)?), Box::new(self.resolve_in(value, seen)?)))
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// `name` 과 철자가 가까운 타입 이름. 편집 거리가 이름 길이의 1/3 (최소 1) 이하인 것 중 가장 가까운 것입니다.
+    pub fn suggest(&self, name: &str) -> Option<String> {
+        let limit = (name.chars().count() / 3).max(1);
+        let mut candidates: Vec<&str> = BUILTIN_TYPES.to_vec();
+        candidates.extend(self.types.keys().map(|s| s.as_str()));
+        candidates.sort_unstable();
+        candidates
+            .into_iter()
+            .map(|candidate| (edit_distance(&name.to_lowercase(), &candidate.to_lowercase()), candidate))
+            .filter(|&(distance, _)| distance <= limit)
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, candidate)| candidate.to_string())
+    }
+}
+
+/// 합 타입의 구성원. 합 타입이 아니면 자기 자신 하나입니다.
+pub fn members(annotation: &TypeAnnotation) -> Vec<TypeAnnotation> {
+    match annotation {
+        TypeAnnotation::Union(members) => members.clone(),
+        other => vec![other.clone()],
+    }
+}
+
+/// 구성원 목록을 다시 표기로. `any` 가 있으면 `any`, 하나뿐이면 그 구성원입니다.
+fn union_of(mut members: Vec<TypeAnnotation>) -> TypeAnnotation {
+    if members.contains(&TypeAnnotation::Any) {
+        TypeAnnotation::Any
+    } else if members.len() == 1 {
+        members.remove(0)
+    } else {
+        TypeAnnotation::Union(members)
+    }
+}
+
+/// 런타임 타입 검사: 값이 해석된 표기에 속하는지. 구성원 이름은 `type_of` 결과와 같습니다.
+pub fn admits(annotation: &TypeAnnotation, value: &Value) -> bool {
+    match annotation {
+        TypeAnnotation::Any | TypeAnnotation::Infer => true,
+        TypeAnnotation::Union(members) => members.iter().any(|m| admits(m, value)),
+        TypeAnnotation::Int => matches!(value, Value::Integer(_)),
+        TypeAnnotation::Float => matches!(value, Value::Float(_)),
+        TypeAnnotation::Bool => matches!(value, Value::Boolean(_)),
+        TypeAnnotation::String => matches!(value, Value::String(_)),
+        TypeAnnotation::Void => matches!(value, Value::Null),
+        TypeAnnotation::Array(element) => matches!(value, Value::Array(items) if items.iter().all(|item| admits(element, item))),
+        TypeAnnotation::Map(key_type, value_type) => matches!(value, Value::Map(map) if map.iter().all(|(key, value)| {
+            admits(key_type, &key.to_value()) && admits(value_type, value)
+        })),
+        TypeAnnotation::Custom(_) => false,
+    }
+}
+
+/// 정적 타입 `from` 의 값을 `to` 로 표기된 자리에 둘 수 있는지. 둘 중 하나가 `any` 면 언제나 됩니다.
+pub fn assignable(from: &TypeAnnotation, to: &TypeAnnotation) -> bool {
+    if matches!(from, TypeAnnotation::Any | TypeAnnotation::Infer) || matches!(to, TypeAnnotation::Any | TypeAnnotation::Infer) {
+        return true;
+    }
+    let accepted = members(to);
+    members(from).iter().all(|m| {
+        accepted.contains(m)
+            || (*m == TypeAnnotation::Int && accepted.contains(&TypeAnnotation::Float))
+            || matches!(m, TypeAnnotation::Array(from_element) if accepted.iter().any(|a| {
+                matches!(a, TypeAnnotation::Array(to_element) if assignable(from_element, to_element))
+            }))
+            || matches!(m, TypeAnnotation::Map(from_key, from_value) if accepted.iter().any(|a| {
+                matches!(a, TypeAnnotation::Map(to_key, to_value)
+                    if assignable(from_key, to_key) && assignable(from_value, to_value))
+            }))
+    })
+}
+
+/// 표기된 바인딩에 값을 넣는 실행 시점 강제 변환. 속하면 그대로, int 를 float 자리에 넣으면 넓히고, 아니면 None 입니다.
+/// 배열은 요소마다, 맵은 값마다 강제 변환합니다. (`let xs: [float] = [1, 2.5]`)
+pub fn coerce(annotation: &TypeAnnotation, value: Value) -> Option<Value> {
+    match value {
+        _ if admits(annotation, &value) => Some(value),
+        Value::Integer(i) if admits(annotation, &Value::Float(0.0)) => Some(Value::Float(i as f64)),
+        Value::Array(items) => members(annotation).iter().find_map(|member| {
+            let TypeAnnotation::Array(element) = member else {
+                return None;
+            };
+            let items: Option<Vec<Value>> = items.iter().map(|item| coerce(element, item.clone())).collect();
+            items.map(|items| Value::Array(items.into()))
+        }),
+        Value::Map(map) => members(annotation).iter().find_map(|member| {
+            let TypeAnnotation::Map(key_type, value_type) = member else {
+                return None;
+            };
+            let entries: Option<Map> = map
+                .iter()
+                .map(|(key, value)| {
+                    if !admits(key_type, &key.to_value()) {
+                        return None;
+                    }
+                    Some((key.clone(), coerce(value_type, value.clone())?))
+                })
+                .collect();
+            entries.map(|entries| Value::Map(Rc::new(entries)))
+        }),
+        _ => None,
+    }
+}
+
+/// 연산이 실행되는지 알아볼 때 쓰는 구성원별 표본 값
+fn sample(annotation: &TypeAnnotation) -> Option<Value> {
+    match annotation {
+        TypeAnnotation::Int => Some(Value::Integer(1)),
+        TypeAnnotation::Float => Some(Value::Float(1.0)),
+        TypeAnnotation::Bool => Some(Value::Boolean(true)),
+        TypeAnnotation::String => Some(Value::String("s".into())),
+        TypeAnnotation::Void => Some(Value::Null),
+        _ => None,
+    }
+}
+
+/// 타입 표기와 별칭 선언을 검사하고, 합 타입 바인딩을 흐름에 따라 좁혀 가며 연산을 검사합니다. (E0010, E0011, E0012)
+/// `strict` 면 암시적 any 바인딩도 오류입니다. (E0013) 링크된 표준 라이브러리 모듈(`modules`)의 문장은 strict 대상이 아닙니다.
+pub fn check_program(program: &Program, modules: &[LinkedModule], strict: bool) -> Vec<Diagnostic> {
+    let types = NamedTypes::collect(program);
+    let mut check = Check { types: &types, strict, scopes: vec![HashMap::new()], narrowed: vec![None], diagnostics: vec![] };
+    for (name, span) in &types.duplicates {
+        check.diagnostics.push(error_codes::INVALID_TYPE_ALIAS.diagnostic(*span, "duplicate", &[&name.as_str()]));
+    }
+    for (index, stmt) in program.statements.iter().enumerate() {
+        check.strict = strict && !modules.iter().any(|m| m.statements.contains(&index));
+        check.visit_statement(stmt);
+    }
+    check.diagnostics
+}
+
+struct Collector(NamedTypes);
+
+impl Visitor for Collector {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if let Statement::TypeAlias { name, target, span } = stmt {
+            if !self.0.declare(*name, target, *span) {
+                self.0.duplicates.push((*name, *span));
+            }
+        }
+        walk_statement(self, stmt);
+    }
+}
+
+struct Check<'a> {
+    types: &'a NamedTypes,
+    strict: bool,
+    /// 바인딩 → 해석된(또는 초기값에서 알아낸) 타입. 타입을 모르는 `any` 바인딩은 None 으로 바깥 이름을 가립니다.
+    scopes: Vec<HashMap<Symbol, Option<TypeAnnotation>>>,
+    /// 스코프마다 타입을 좁힌 이름 (`scopes` 와 같은 길이). 대입은 좁힌 타입이 아닌 선언된 타입을 따릅니다.
+    narrowed: Vec<Option<Symbol>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Check<'_> {
+    fn scoped(&mut self, bindings: Vec<(Symbol, Option<TypeAnnotation>)>, f: impl FnOnce(&mut Self)) {
+        self.narrowed_scope(None, bindings, f);
+    }
+
+    fn narrowed_scope(&mut self, narrowed: Option<Symbol>, bindings: Vec<(Symbol, Option<TypeAnnotation>)>, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(bindings.into_iter().collect());
+        self.narrowed.push(narrowed);
+        f(self);
+        self.narrowed.pop();
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: Symbol, annotation: Option<TypeAnnotation>) {
+        self.scopes.last_mut().expect("최상위 스코프가 있어야 합니다").insert(name, annotation);
+    }
+
+    fn lookup(&self, name: Symbol) -> Option<&TypeAnnotation> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name)).and_then(|a| a.as_ref())
+    }
+
+    /// 대입 대상의 선언된 타입. 대입한 뒤로는 좁힌 타입이 맞지 않으므로 그 스코프들도 선언된 타입으로 되돌립니다.
+    fn assign(&mut self, name: Symbol) -> Option<TypeAnnotation> {
+        let declared = self
+            .scopes
+            .iter()
+            .zip(&self.narrowed)
+            .rev()
+            .find_map(|(scope, narrowed)| scope.get(&name).filter(|_| *narrowed != Some(name)))
+            .cloned()
+            .flatten();
+        for (scope, narrowed) in self.scopes.iter_mut().zip(&self.narrowed).rev() {
+            match scope.get_mut(&name) {
+                Some(binding) if *narrowed == Some(name) => *binding = declared.clone(),
+                Some(_) => break,
+                None => {}
+            }
+        }
+        declared
+    }
+
+    /// 표기를 해석합니다. 해석하지 못하면 E0011 을 내고 None 입니다.
+    fn annotation(&mut self, annotation: &TypeAnnotation, span: Span) -> Option<TypeAnnotation> {
+        match self.types.resolve(annotation) {
+            Ok(resolved) => Some(resolved),
+            Err(Unresolved::Unknown(name)) => {
+                let code = &error_codes::UNKNOWN_TYPE;
+                let mut diagnostic = code.diagnostic(span, "", &[&name]);
+                if let Some(suggestion) = self.types.suggest(&name) {
+                    diagnostic = diagnostic.with_help(code.message("help", &[&suggestion]));
+                }
+                self.diagnostics.push(diagnostic);
+                None
+            }
+            // 순환은 별칭 선언 쪽에서 알립니다.
+            Err(Unresolved::Cycle(_)) => None,
+        }
+    }
+
+    /// 식의 정적으로 알 수 있는 타입 표기 (리터럴, 타입을 아는 바인딩). None 이면 `any` 입니다.
+    fn static_type(&self, expr: &Expression) -> Option<TypeAnnotation> {
+        match expr {
+            Expression::Literal(_, value) => literal_type(value),
+            Expression::Identifier(_, name) => self.lookup(*name).cloned(),
+            Expression::Grouped(_, inner) => self.static_type(inner),
+            Expression::PrefixOperation(_, op, operand) => {
+                let value = operators::unary(op, &sample(&self.static_type(operand)?)?).ok()?;
+                literal_type(&value)
+            }
+            // 두 갈래의 타입을 모두 알고 같을 때만 압니다.
+            Expression::Ternary(_, _, then_expr, else_expr) => {
+                self.common_type([then_expr.as_ref(), else_expr.as_ref()].into_iter())
+            }
+            // 요소의 타입을 모두 알고 같을 때만 압니다. 빈 배열과 빈 맵은 `any` 입니다.
+            Expression::ArrayLiteral(_, elements) => {
+                Some(TypeAnnotation::Array(Box::new(self.common_type(elements.iter().map(|e| e.as_ref()))?)))
+            }
+            Expression::MapLiteral(_, entries) => Some(TypeAnnotation::Map(
+                Box::new(self.common_type(entries.iter().map(|(key, _)| key.as_ref()))?),
+                Box::new(self.common_type(entries.iter().map(|(_, value)| value.as_ref()))?),
+            )),
+            _ => None,
+        }
+    }
+
+    /// 식들의 정적 타입이 모두 같으면 그 타입입니다.
+    fn common_type<'e>(&self, mut exprs: impl Iterator<Item = &'e Expression>) -> Option<TypeAnnotation> {
+        let first = self.static_type(exprs.next()?)?;
+        for expr in exprs {
+            if self.static_type(expr)? != first {
+                return None;
+            }
+        }
+        Some(first)
+    }
+
+    /// 요소 타입이 섞인 배열 리터럴은 정적 타입을 모르므로, 배열로 표기된 자리에서는 요소를 하나씩 맞춰 봅니다.
+    /// (`let xs: [int] = [1, "a"]` 는 `"a"` 위치의 E0010)
+    fn check_elements(&mut self, name: Symbol, expected: &TypeAnnotation, value: &Expression) {
+        let (TypeAnnotation::Array(element), Expression::ArrayLiteral(_, items)) = (expected, unwrap_grouped(value)) else {
+            return;
+        };
+        for (index, item) in items.iter().enumerate() {
+            match self.static_type(item) {
+                Some(found) if !assignable(&found, element) => {
+                    let code = &error_codes::TYPE_MISMATCH;
+                    self.diagnostics.push(code.diagnostic(item.span(), "element", &[&name, expected, &index, &found]));
+                }
+                Some(_) => {}
+                None => self.check_elements(name, element, item),
+            }
+        }
+    }
+
+    /// 합 타입 피연산자의 어떤 구성원으로는 연산이 타입 오류가 되면 알립니다.
+    fn check_operation(&mut self, span: Span, op: &TokenKind, left: &Expression, right: &Expression) {
+        if matches!(op, TokenKind::And | TokenKind::Or) {
+            return;
+        }
+        let (Some(left_type), Some(right_type)) = (self.static_type(left), self.static_type(right)) else {
+            return;
+        };
+        for (operand, own, other, own_on_left) in [(left, &left_type, &right_type, true), (right, &right_type, &left_type, false)] {
+            let (Expression::Identifier(_, name), TypeAnnotation::Union(own_members)) = (unwrap_grouped(operand), own) else {
+                continue;
+            };
+            for member in own_members {
+                let failing = members(other).iter().any(|other_member| {
+                    let (Some(a), Some(b)) = (sample(member), sample(other_member)) else {
+                        return false;
+                    };
+                    let (l, r) = if own_on_left { (a, b) } else { (b, a) };
+                    matches!(operators::binary(op, &l, &r), Err(OperatorError::TypeMismatch))
+                });
+                if failing {
+                    let code = &error_codes::TYPE_MISMATCH;
+                    self.diagnostics.push(
+                        code.diagnostic(span, "union_operand", &[name, &member, &operators::symbol(op)])
+                            .with_help(code.message("union_operand_help", &[name])),
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    /// 피연산자 타입(합 타입이면 그 구성원 중 하나)으로 전위 연산이 타입 오류가 되면 알립니다.
+    fn check_unary(&mut self, span: Span, op: &TokenKind, operand: &Expression) {
+        let Some(operand_type) = self.static_type(operand) else {
+            return;
+        };
+        let failing = members(&operand_type).into_iter().find(|member| {
+            sample(member).is_some_and(|value| matches!(operators::unary(op, &value), Err(OperatorError::TypeMismatch)))
+        });
+        let Some(member) = failing else {
+            return;
+        };
+        let code = &error_codes::TYPE_MISMATCH;
+        let diagnostic = match (unwrap_grouped(operand), &operand_type) {
+            (Expression::Identifier(_, name), TypeAnnotation::Union(_)) => code
+                .diagnostic(span, "union_operand", &[name, &member, &operators::symbol(op)])
+                .with_help(code.message("union_operand_help", &[name])),
+            _ => code.diagnostic(span, "unary_operator", &[&operators::symbol(op), &member]),
+        };
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// `type_of x == "int"` / `!=` 조건이 참일 때와 거짓일 때 `x` 의 타입
+    fn narrowing(&self, condition: &Expression) -> Option<(Symbol, TypeAnnotation, TypeAnnotation)> {
+        let Expression::InfixOperation(_, op @ (TokenKind::Eq | TokenKind::Neq), left, right) = unwrap_grouped(condition) else {
+            return None;
+        };
+        let (test, name) = match (unwrap_grouped(left), unwrap_grouped(right)) {
+            (Expression::TypeOf(_, operand), Expression::Literal(_, Value::String(name)))
+            | (Expression::Literal(_, Value::String(name)), Expression::TypeOf(_, operand)) => (operand, name),
+            _ => return None,
+        };
+        let Expression::Identifier(_, variable) = unwrap_grouped(test) else {
+            return None;
+        };
+        let declared = members(self.lookup(*variable)?);
+        let (matching, rest): (Vec<_>, Vec<_>) = declared.into_iter().partition(|m| m.to_string() == name.as_ref());
+        if matching.is_empty() || rest.is_empty() {
+            return None;
+        }
+        let (when_true, when_false) = (union_of(matching), union_of(rest));
+        Some(match op {
+            TokenKind::Eq => (*variable, when_true, when_false),
+            _ => (*variable, when_false, when_true),
+        })
+    }
+}
+
+impl Visitor for Check<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::LetStatement { name, value, type_annotation, is_mutable, span } => {
+                self.visit_expression(value);
+                let found = self.static_type(value);
+                // 표기 없는 `let mut` 은 다른 타입의 값도 대입할 수 있으므로 타입을 모릅니다.
+                let Some(annotation) = type_annotation else {
+                    let implicit = found.is_none() || *is_mutable;
+                    if self.strict && implicit && !matches!(unwrap_grouped(value), Expression::Function(..)) {
+                        let code = &error_codes::IMPLICIT_ANY;
+                        self.diagnostics.push(code.diagnostic(*span, "", &[name]).with_help(code.message("help", &[name])));
+                    }
+                    self.bind(*name, if *is_mutable { None } else { found });
+                    return;
+                };
+                let resolved = self.annotation(annotation, *span);
+                match (&resolved, &found) {
+                    (Some(expected), Some(found)) if !assignable(found, expected) => {
+                        self.diagnostics.push(error_codes::TYPE_MISMATCH.diagnostic(*span, "annotation", &[name, expected, found]));
+                    }
+                    (Some(expected), None) => self.check_elements(*name, expected, value),
+                    _ => {}
+                }
+                self.bind(*name, resolved);
+            }
+            Statement::TypeAlias { name, target, span } => match self.types.resolve(&TypeAnnotation::Custom(name.to_string())) {
+                Err(Unresolved::Cycle(names)) if names[0] == name.as_str() => {
+                    let chain = names.iter().chain(names.first()).cloned().collect::<Vec<_>>().join(" -> ");
+                    self.diagnostics.push(error_codes::INVALID_TYPE_ALIAS.diagnostic(*span, "cycle", &[&chain]));
+                }
+                Err(Unresolved::Cycle(_)) => {}
+                _ => {
+                    self.annotation(target, *span);
+                }
+            },
+            Statement::BlockStatement { .. } => self.scoped(vec![], |c| walk_statement(c, stmt)),
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                self.visit_expression(condition);
+                let narrowing = self.narrowing(condition);
+                let narrowed = narrowing.as_ref().map(|(name, ..)| *name);
+                let (then_bindings, else_bindings) = match narrowing {
+                    Some((name, when_true, when_false)) => (vec![(name, Some(when_true))], vec![(name, Some(when_false))]),
+                    None => (vec![], vec![]),
+                };
+                self.narrowed_scope(narrowed, then_bindings, |c| c.visit_statement(then_branch));
+                if let Some(else_branch) = else_branch {
+                    self.narrowed_scope(narrowed, else_bindings, |c| c.visit_statement(else_branch));
+                }
+            }
+            // 반복 변수의 타입: 범위면 int, 요소 타입을 아는 배열이면 그 타입입니다.
+            Statement::ForInStatement { variable, iterable, body, .. } => {
+                self.visit_expression(iterable);
+                let element = match (unwrap_grouped(iterable), self.static_type(iterable)) {
+                    (Expression::Range(..), _) => Some(TypeAnnotation::Int),
+                    (_, Some(TypeAnnotation::Array(element))) => Some(*element),
+                    _ => None,
+                };
+                self.scoped(vec![(*variable, element)], |c| c.visit_statement(body));
+            }
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        match expr {
+            // 매개변수는 표기가 없으므로 바깥의 같은 이름을 가립니다.
+            Expression::Function(_, parameters, _) => {
+                let bindings = parameters.iter().map(|p| (*p, None)).collect();
+                self.scoped(bindings, |c| walk_expression(c, expr));
+            }
+            Expression::InfixOperation(span, op, left, right) => {
+                walk_expression(self, expr);
+                self.check_operation(*span, op, left, right);
+            }
+            Expression::PrefixOperation(span, op, operand) => {
+                walk_expression(self, expr);
+                self.check_unary(*span, op, operand);
+            }
+            // 범위의 양 끝은 int 여야 합니다. 타입을 모르는 쪽은 런타임이 검사합니다.
+            Expression::Range(span, start, end, _) => {
+                walk_expression(self, expr);
+                let (start, end) = (self.static_type(start), self.static_type(end));
+                if [&start, &end].iter().any(|t| t.as_ref().is_some_and(|t| *t != TypeAnnotation::Int)) {
+                    let (start, end) = (start.unwrap_or(TypeAnnotation::Any), end.unwrap_or(TypeAnnotation::Any));
+                    self.diagnostics.push(error_codes::TYPE_MISMATCH.diagnostic(*span, "range", &[&start, &end]));
+                }
+            }
+            Expression::Ternary(_, condition, ..) => {
+                walk_expression(self, expr);
+                if let Some(found) = self.static_type(condition).filter(|t| *t != TypeAnnotation::Bool) {
+                    self.diagnostics.push(error_codes::TYPE_MISMATCH.diagnostic(condition.span(), "condition", &[&found]));
+                }
+            }
+            // 대입한 값(복합 대입이면 연산 결과)도 선언된 타입에 맞아야 합니다.
+            Expression::Assign(span, op, name, value) => {
+                walk_expression(self, expr);
+                let current = Expression::Identifier(*span, *name);
+                let operation = match op {
+                    TokenKind::PlusAssign => Some(TokenKind::Plus),
+                    TokenKind::MinusAssign => Some(TokenKind::Minus),
+                    _ => None,
+                };
+                let compound = operation.is_some();
+                let found = match operation {
+                    Some(op) => {
+                        self.check_operation(*span, &op, &current, value);
+                        let (left, right) = (self.static_type(&current), self.static_type(value));
+                        left.as_ref()
+                            .and_then(sample)
+                            .zip(right.as_ref().and_then(sample))
+                            .and_then(|(l, r)| operators::binary(&op, &l, &r).ok())
+                            .and_then(|v| literal_type(&v))
+                    }
+                    None => self.static_type(value),
+                };
+                match (self.assign(*name), found) {
+                    (Some(expected), Some(found)) if !assignable(&found, &expected) => {
+                        self.diagnostics.push(error_codes::TYPE_MISMATCH.diagnostic(*span, "annotation", &[name, &expected, &found]));
+                    }
+                    (Some(expected), None) if !compound => self.check_elements(*name, &expected, value),
+                    _ => {}
+                }
+            }
+            _ => walk_expression(self, expr),
+        }
+    }
+}
+
+fn unwrap_grouped(expr: &Expression) -> &Expression {
+    match expr {
+        Expression::Grouped(_, inner) => unwrap_grouped(inner),
+        other => other,
+    }
+}
+
+fn literal_type(value: &Value) -> Option<TypeAnnotation> {
+    match value {
+        Value::Integer(_) => Some(TypeAnnotation::Int),
+        Value::Float(_) => Some(TypeAnnotation::Float),
+        Value::Boolean(_) => Some(TypeAnnotation::Bool),
+        Value::String(_) => Some(TypeAnnotation::String),
+        Value::Null => Some(TypeAnnotation::Void),
+        _ => None,
+    }
+}
+
+/// 레벤슈타인 편집 거리 (문자 단위)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(ca != *cb);
+            current.push(substitute.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer_service::LexerService;
+    use crate::parser_service::ParserService;
+
+    fn check(source: &str) -> Vec<(String, &str)> {
+        let program = ParserService::new(LexerService::new(source)).parse_program();
+        check_program(&program, &[], false)
+            .into_iter()
+            .map(|d| (d.message.clone(), &source[d.span.start..d.span.end]))
+            .collect()
+    }
+
+    #[test]
+    fn mixed_array_literals_are_checked_against_the_element_annotation() {
+        let found = check("let xs: [int] = [1, \"a\", 2.5];");
+        assert_eq!(found.len(), 2, "{:?}", found);
+        assert_eq!(found[0].1, "\"a\"");
+        assert!(found[0].0.contains("[1]") && found[0].0.contains("string"), "{:?}", found);
+        assert_eq!(found[1].1, "2.5");
+
+        assert!(check("let xs: [float] = [1, 2.5];").is_empty());
+        assert!(check("let xs: [int | string] = [1, \"a\"];").is_empty());
+        assert_eq!(check("let xs: [[int]] = [[1], [2, true]];").len(), 1);
+        assert_eq!(check("let mut xs: [int] = [];\nxs = [1, \"a\"];").len(), 1);
+    }
+}
@@ -1,92 +1,571 @@
-use crate::ir_generator::IRModule;
-use std::fs::File;
-use std::io::Write;
-use std::process::Command;
-
-pub fn generate_native_binary(ir: &IRModule, asm_path: &str) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    let mut asm = String::from("global main\nsection .text\nmain:\n");
-
-    #[cfg(not(target_os = "windows"))]
-    let mut asm = String::from("section .text\n global _start\n_start:\n");
-
-    for instr in &ir.instructions {
-        match instr.opcode.as_str() {
-            "let" => {
-                asm.push_str(&format!("  ; let {} = {}\n", instr.operands[0], instr.operands[1]));
-            }
-            "return" => {
-                #[cfg(target_os = "windows")]
-                asm.push_str("  mov eax, 0\n  ret\n");
-
-                #[cfg(not(target_os = "windows"))]
-                asm.push_str("  mov rax, 60\n  xor rdi, rdi\n  syscall\n");
-            }
-            _ => {
-                asm.push_str("  nop\n");
-            }
-        }
-    }
-
-    let mut file = File::create(asm_path).map_err(|e| e.to_string())?;
-    file.write_all(asm.as_bytes()).map_err(|e| e.to_string())?;
-
-    Ok(())
-}
-
-pub fn assemble_and_link(asm_path: &str, output_path: &str) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        let obj_path = "compiled.obj";
-
-        let nasm_status = Command::new("nasm")
-            .args(&["-f", "win64", asm_path, "-o", obj_path])
-            .status()
-            .map_err(|e| format!("NASM 실행 실패: {}", e))?;
-
-        if !nasm_status.success() {
-            return Err("NASM 어셈블 실패".into());
-        }
-
-        let gcc_status = Command::new("gcc")
-            .args(&[obj_path, "-o", output_path])
-            .status()
-            .map_err(|e| format!("GCC 링커 실패: {}", e))?;
-
-        if !gcc_status.success() {
-            return Err("GCC 링커 실패".into());
-        }
-
-        Ok(())
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        let obj_path = "compiled.o";
-
-        let nasm_status = Command::new("nasm")
-            .args(&["-f", "elf64", asm_path, "-o", obj_path])
-            .status()
-            .map_err(|e| format!("NASM 실행 실패: {}", e))?;
-
-        if !nasm_status.success() {
-            return Err("NASM 어셈블 실패".into());
-        }
-
-        let ld_status = Command::new("ld")
-            .args(&[obj_path, "-o", output_path])
-            .status()
-            .map_err(|e| format!("LD 링커 실패: {}", e))?;
-
-        if !ld_status.success() {
-            return Err("LD 링커 실패".into());
-        }
-
-        Command::new("chmod")
-            .args(&["+x", output_path])
-            .status()
-            .map_err(|e| format!("실행 권한 부여 실패: {}", e))?;
-
-        Ok(())
-    }
-}
+use crate::error_codes;
+use crate::ir_generator::{IRInstruction, IRModule};
+use std::fs::File;
+use std::io::Write;
+
+// ─── 타깃 CPU / 기능 설정 ─────────────────────────────
+
+/// 어떤 CPU에서도 안전하게 실행되는 기본 타깃 CPU입니다.
+pub const BASELINE_CPU: &str = "x86-64";
+
+/// `--features`로 켜고 끌 수 있는 코드 생성 기능 목록입니다.
+/// NASM 출력은 `cmov` 로 정수 삼항식을 분기 없이 만들고, LLVM 출력은 모든 기능을 `target-features` 로 넘깁니다.
+pub const KNOWN_FEATURES: &[&str] = &["cmov", "sse2", "sse4.2", "popcnt", "avx2"];
+
+/// 실행 파일을 만들 운영체제. 오브젝트 형식, 진입점, 종료 방법이 여기에 따라 달라집니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetOs {
+    /// ELF, `_start` 진입점, 리눅스 시스템 호출
+    Linux,
+    /// PE/COFF, C 런타임의 `main`
+    Windows,
+    /// Mach-O, libSystem 의 `_main`. 시스템 호출 번호가 안정적이지 않으므로 libc 를 부릅니다.
+    MacOs,
+}
+
+impl TargetOs {
+    /// 컴파일러가 돌고 있는 OS. 모르는 유닉스 계열은 ELF 로 봅니다.
+    pub fn host() -> Self {
+        match std::env::consts::OS {
+            "windows" => TargetOs::Windows,
+            "macos" => TargetOs::MacOs,
+            _ => TargetOs::Linux,
+        }
+    }
+
+    /// `--target-os` 값을 읽습니다. `@cfg(target_os = ...)` 와 같은 이름을 씁니다.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "linux" => Ok(TargetOs::Linux),
+            "windows" => Ok(TargetOs::Windows),
+            "macos" | "darwin" => Ok(TargetOs::MacOs),
+            other => Err(error_codes::INVALID_TARGET.message("unknown_os", &[&other])),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TargetOs::Linux => "linux",
+            TargetOs::Windows => "windows",
+            TargetOs::MacOs => "macos",
+        }
+    }
+
+    /// NASM `-f` 인자
+    pub fn object_format(self) -> &'static str {
+        match self {
+            TargetOs::Linux => "elf64",
+            TargetOs::Windows => "win64",
+            TargetOs::MacOs => "macho64",
+        }
+    }
+
+    pub fn object_path(self) -> &'static str {
+        match self {
+            TargetOs::Windows => "compiled.obj",
+            _ => "compiled.o",
+        }
+    }
+
+    pub fn binary_path(self) -> &'static str {
+        match self {
+            TargetOs::Windows => "compiled.exe",
+            _ => "compiled.out",
+        }
+    }
+}
+
+/// 코드 생성 백엔드에 전달되는 타깃 CPU 및 기능 설정입니다.
+#[derive(Debug, Clone)]
+pub struct CodegenConfig {
+    pub target_cpu: String,
+    pub features: Vec<String>,
+    /// 기본값은 호스트 OS 입니다. (`--target-os`)
+    pub os: TargetOs,
+}
+
+impl CodegenConfig {
+    /// 기본(baseline) 설정: x86-64 필수 기능만 사용합니다.
+    pub fn baseline() -> Self {
+        Self::new(BASELINE_CPU, &[]).expect("baseline CPU는 항상 유효합니다")
+    }
+
+    /// CPU 이름과 `+feat` / `-feat` 형식의 기능 목록으로 설정을 만듭니다.
+    pub fn new(target_cpu: &str, requested: &[String]) -> Result<Self, String> {
+        let mut features: Vec<String> = match target_cpu {
+            "x86-64" => vec!["cmov", "sse2"],
+            "x86-64-v2" => vec!["cmov", "sse2", "sse4.2", "popcnt"],
+            "x86-64-v3" => vec!["cmov", "sse2", "sse4.2", "popcnt", "avx2"],
+            other => {
+                return Err(error_codes::INVALID_TARGET.message("unknown_cpu", &[&other]))
+            }
+        }
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        for raw in requested {
+            let (enable, name) = match raw.strip_prefix('-') {
+                Some(rest) => (false, rest),
+                None => (true, raw.strip_prefix('+').unwrap_or(raw)),
+            };
+            if !KNOWN_FEATURES.contains(&name) {
+                return Err(error_codes::INVALID_TARGET.message("unknown_feature", &[&name]));
+            }
+            features.retain(|f| f != name);
+            if enable {
+                features.push(name.to_string());
+            }
+        }
+
+        Ok(Self { target_cpu: target_cpu.to_string(), features, os: TargetOs::host() })
+    }
+
+    pub fn with_os(mut self, os: TargetOs) -> Self {
+        self.os = os;
+        self
+    }
+
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.iter().any(|f| f == name)
+    }
+
+    /// 바이너리 메타데이터 섹션에 기록될 항목들입니다.
+    pub fn metadata_entries(&self) -> Vec<(String, String)> {
+        vec![
+            ("target_cpu".into(), self.target_cpu.clone()),
+            ("features".into(), self.features.join(",")),
+            ("target_os".into(), self.os.name().into()),
+        ]
+    }
+}
+
+// ─── 어셈블리 생성 ─────────────────────────────
+
+/// 스택에 놓이는 클로저 하나의 크기 (코드 주소 + 환경 포인터, 16바이트 정렬 유지)
+const CLOSURE_SIZE: usize = 16;
+
+pub fn generate_native_binary(
+    ir: &IRModule,
+    asm_path: &str,
+    config: &CodegenConfig,
+    metadata: &[(String, String)],
+) -> Result<(), String> {
+    let asm = generate_assembly(ir, config, metadata);
+    let mut file = File::create(asm_path).map_err(|e| e.to_string())?;
+    file.write_all(asm.as_bytes()).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// NASM 소스 텍스트. `generate_native_binary` 가 파일로 쓰는 내용과 같습니다.
+pub fn generate_assembly(ir: &IRModule, config: &CodegenConfig, metadata: &[(String, String)]) -> String {
+    let os = config.os;
+    let mut asm = match os {
+        TargetOs::Windows => String::from("global main\nsection .text\nmain:\n"),
+        TargetOs::Linux => String::from("section .text\n global _start\n_start:\n"),
+        // libSystem 이 `_main` 을 부르므로 프레임을 세워 호출 전 스택을 16바이트로 맞춥니다.
+        TargetOs::MacOs => String::from(
+            "default rel\nextern _write\nextern _exit\nsection .text\n global _main\n_main:\n  push rbp\n  mov rbp, rsp\n",
+        ),
+    };
+
+    let mut messages: Vec<String> = vec![];
+    let mut integers = IntegerLowering::default();
+
+    for instr in &ir.instructions {
+        match instr.opcode.as_str() {
+            "abort" => {
+                let label = message_label(&mut messages, &instr.operands[0]);
+                asm.push_str(&abort_code(os, &label));
+            }
+            "let" => {
+                asm.push_str(&format!("  ; let {} = {}\n", instr.operands[0], instr.operands[1]));
+            }
+            "push" | "load" | "neg" | "binary" | "select" | "store" => {
+                integers.lower(instr, config, &mut messages, &mut asm);
+            }
+            // 클로저 = 코드 주소 + 환경 포인터. 탈출하지 않는 클로저는 현재 프레임에 자리를 잡습니다.
+            "alloc" if instr.operands[1] == "stack" => {
+                asm.push_str(&format!("  ; let {} = {} (stack)\n  sub rsp, {}\n", instr.operands[0], instr.operands[2], CLOSURE_SIZE));
+            }
+            "alloc" => {
+                // 네이티브 런타임에는 아직 힙 할당기가 없으므로 자리만 표시합니다.
+                asm.push_str(&format!("  ; let {} = {} (heap)\n  nop\n", instr.operands[0], instr.operands[2]));
+            }
+            "return" => {
+                match os {
+                    TargetOs::Windows => asm.push_str("  mov eax, 0\n  ret\n"),
+                    TargetOs::Linux => asm.push_str("  mov rax, 60\n  xor rdi, rdi\n  syscall\n"),
+                    TargetOs::MacOs => asm.push_str("  xor eax, eax\n  mov rsp, rbp\n  pop rbp\n  ret\n"),
+                }
+            }
+            _ => {
+                asm.push_str("  nop\n");
+            }
+        }
+    }
+
+    if !messages.is_empty() {
+        // NASM 은 macho64 에서도 `.rodata` 를 `__TEXT,__const` 로 옮겨 줍니다.
+        asm.push_str("\nsection .rodata\n");
+        for message in &messages {
+            asm.push_str(message);
+        }
+    }
+
+    asm.push_str(&integers.bss_section());
+
+    let mut entries = metadata.to_vec();
+    entries.extend(config.metadata_entries());
+    asm.push_str(&metadata_section(os, &entries));
+    asm
+}
+
+/// 메시지를 `.rodata` 에 올리고 그 레이블을 돌려줍니다. 길이는 `<label>_len` 입니다.
+fn message_label(messages: &mut Vec<String>, text: &str) -> String {
+    let label = format!("abort_msg_{}", messages.len());
+    messages.push(format!("{}: db {}, 10\n{}_len equ $ - {}\n", label, db_bytes(text), label, label));
+    label
+}
+
+/// 메시지를 표준 에러에 쓰고 134 로 끝내는 코드
+fn abort_code(os: TargetOs, label: &str) -> String {
+    match os {
+        TargetOs::Windows => format!("  ; abort: {}\n  mov eax, 3\n  ret\n", label),
+        TargetOs::Linux => format!(
+            "  mov rax, 1\n  mov rdi, 2\n  lea rsi, [rel {l}]\n  mov rdx, {l}_len\n  syscall\n  mov rax, 60\n  mov rdi, 134\n  syscall\n",
+            l = label
+        ),
+        // 스택 클로저가 rsp 를 16 단위로만 옮기므로 정렬은 프롤로그 그대로입니다.
+        TargetOs::MacOs => format!(
+            "  mov edi, 2\n  lea rsi, [rel {l}]\n  mov edx, {l}_len\n  call _write\n  mov edi, 134\n  call _exit\n",
+            l = label
+        ),
+    }
+}
+
+// ─── 정수 식 ─────────────────────────────
+
+/// IR 의 정수 스택 명령을 내립니다. 스택 깊이 k 의 값은 `tmp_k`, 바인딩은 `var_k` 에 두므로
+/// rsp 는 움직이지 않습니다. 깊이는 컴파일 시점에 정해집니다.
+#[derive(Default)]
+struct IntegerLowering {
+    depth: usize,
+    max_depth: usize,
+    variables: Vec<String>,
+    labels: usize,
+}
+
+impl IntegerLowering {
+    fn lower(&mut self, instr: &IRInstruction, config: &CodegenConfig, messages: &mut Vec<String>, asm: &mut String) {
+        match instr.opcode.as_str() {
+            "push" => {
+                // i64::MIN 도 그대로 읽히도록 음수는 16진수로 씁니다.
+                let value = instr.operands[0].parse::<i64>().unwrap_or(0);
+                let immediate = if value < 0 { format!("{:#x}", value as u64) } else { value.to_string() };
+                asm.push_str(&format!("  mov rax, {}\n", immediate));
+                self.push(asm);
+            }
+            "load" => {
+                asm.push_str(&format!("  mov rax, [rel var_{}]\n", self.variable(&instr.operands[0])));
+                self.push(asm);
+            }
+            "store" => {
+                self.depth -= 1;
+                let slot = self.variable(&instr.operands[0]);
+                asm.push_str(&format!("  mov rax, [rel tmp_{}]\n  mov [rel var_{}], rax\n", self.depth, slot));
+            }
+            "neg" => asm.push_str(&format!("  neg qword [rel tmp_{}]\n", self.depth - 1)),
+            "binary" => {
+                self.depth -= 2;
+                asm.push_str(&format!("  mov rax, [rel tmp_{}]\n  mov rcx, [rel tmp_{}]\n", self.depth, self.depth + 1));
+                match instr.operands[0].as_str() {
+                    "+" => asm.push_str("  add rax, rcx\n"),
+                    "-" => asm.push_str("  sub rax, rcx\n"),
+                    "*" => asm.push_str("  imul rax, rcx\n"),
+                    "&" => asm.push_str("  and rax, rcx\n"),
+                    "|" => asm.push_str("  or rax, rcx\n"),
+                    "^" => asm.push_str("  xor rax, rcx\n"),
+                    // 하드웨어가 시프트 양을 `cl & 63` 으로 자르므로 operators.rs 와 같습니다.
+                    "<<" => asm.push_str("  shl rax, cl\n"),
+                    ">>" => asm.push_str("  sar rax, cl\n"),
+                    op => {
+                        let label = message_label(messages, &instr.operands[1]);
+                        self.divide(op == "%", config.os, &label, asm);
+                    }
+                }
+                self.push(asm);
+            }
+            "select" => {
+                self.depth -= 4;
+                let [then_slot, else_slot, left, right] = [0, 1, 2, 3].map(|i| self.depth + i);
+                let (taken, skipped) = match instr.operands[0].as_str() {
+                    "==" => ("e", "ne"),
+                    "!=" => ("ne", "e"),
+                    "<" => ("l", "ge"),
+                    "<=" => ("le", "g"),
+                    ">" => ("g", "le"),
+                    _ => ("ge", "l"),
+                };
+                asm.push_str(&format!(
+                    "  mov rax, [rel tmp_{}]\n  cmp rax, [rel tmp_{}]\n  mov rax, [rel tmp_{}]\n",
+                    left, right, else_slot
+                ));
+                if config.has_feature("cmov") {
+                    asm.push_str(&format!("  cmov{} rax, [rel tmp_{}]\n", taken, then_slot));
+                } else {
+                    let label = format!("select_{}", self.label());
+                    asm.push_str(&format!("  j{} {}\n  mov rax, [rel tmp_{}]\n{}:\n", skipped, label, then_slot, label));
+                }
+                self.push(asm);
+            }
+            _ => {}
+        }
+    }
+
+    /// `rax / rcx` 또는 `rax % rcx` 를 rax 에 남깁니다. `idiv` 는 나누는 수가 0 이면 #DE 를 내고
+    /// `i64::MIN / -1` 에서도 #DE 를 내므로, 0 은 패닉으로 보내고 -1 은 `idiv` 없이 계산합니다.
+    fn divide(&mut self, remainder: bool, os: TargetOs, message: &str, asm: &mut String) {
+        let n = self.label();
+        let (zero, divide, done) = (format!("div_zero_{}", n), format!("div_{}", n), format!("div_done_{}", n));
+        let by_minus_one = if remainder { "  xor eax, eax\n" } else { "  neg rax\n" };
+        let result = if remainder { "  mov rax, rdx\n" } else { "" };
+        asm.push_str(&format!(
+            "  test rcx, rcx\n  jz {zero}\n  cmp rcx, -1\n  jne {divide}\n{by_minus_one}  jmp {done}\n{zero}:\n{abort}{divide}:\n  cqo\n  idiv rcx\n{result}{done}:\n",
+            abort = abort_code(os, message),
+        ));
+    }
+
+    fn push(&mut self, asm: &mut String) {
+        asm.push_str(&format!("  mov [rel tmp_{}], rax\n", self.depth));
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+    }
+
+    fn variable(&mut self, name: &str) -> usize {
+        match self.variables.iter().position(|v| v == name) {
+            Some(slot) => slot,
+            None => {
+                self.variables.push(name.to_string());
+                self.variables.len() - 1
+            }
+        }
+    }
+
+    /// 레이블이 겹치지 않도록 붙이는 번호
+    fn label(&mut self) -> usize {
+        self.labels += 1;
+        self.labels
+    }
+
+    fn bss_section(&self) -> String {
+        if self.max_depth == 0 && self.variables.is_empty() {
+            return String::new();
+        }
+        let mut section = String::from("\nsection .bss\n");
+        for k in 0..self.max_depth {
+            section.push_str(&format!("tmp_{}: resq 1\n", k));
+        }
+        for (k, name) in self.variables.iter().enumerate() {
+            section.push_str(&format!("var_{}: resq 1  ; {}\n", k, name));
+        }
+        section
+    }
+}
+
+/// 문자열을 NASM `db` 바이트 목록으로 바꿉니다. (따옴표나 한글이 있어도 안전합니다)
+fn db_bytes(text: &str) -> String {
+    text.bytes().map(|b| b.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// 실행 파일에 남는 `key=value` 메타데이터 섹션을 생성합니다.
+fn metadata_section(os: TargetOs, entries: &[(String, String)]) -> String {
+    let mut section = String::from(match os {
+        TargetOs::Windows => "\nsection .hmeta rdata\n",
+        TargetOs::Linux => "\nsection .high_meta progbits noalloc noexec nowrite\n",
+        TargetOs::MacOs => "\nsection __DATA,__high_meta\n",
+    });
+
+    section.push_str("  db \"HIGHMETA\", 10\n");
+    for (key, value) in entries {
+        section.push_str(&format!("  db \"{}={}\", 10\n", key, value));
+    }
+    section.push_str("  db 0\n");
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cancellation::CancellationToken;
+    use crate::toolchain::{MockRunner, ToolPaths, Toolchain};
+    use std::sync::Arc;
+
+    fn module(opcodes: &[(&str, &[&str])]) -> IRModule {
+        IRModule {
+            instructions: opcodes
+                .iter()
+                .map(|(opcode, operands)| IRInstruction {
+                    opcode: opcode.to_string(),
+                    operands: operands.iter().map(|o| o.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// 호스트 OS 로 어셈블리를 만들고, 모의 실행기로 도구 호출을 기록합니다.
+    fn host_build(ir: &IRModule) -> (CodegenConfig, String, Vec<crate::toolchain::Invocation>) {
+        let config = CodegenConfig::baseline();
+        let asm = generate_assembly(ir, &config, &[]);
+        let runner = Arc::new(MockRunner::new());
+        let toolchain = Toolchain::with_runner(ToolPaths::default(), runner.clone());
+        toolchain
+            .assemble_and_link(config.os, "out/compiled.asm", config.os.binary_path(), &CancellationToken::new())
+            .expect("모의 실행기는 실패하지 않습니다");
+        (config, asm, runner.calls())
+    }
+
+    #[test]
+    fn host_matches_compile_target() {
+        let expected = if cfg!(target_os = "windows") {
+            TargetOs::Windows
+        } else if cfg!(target_os = "macos") {
+            TargetOs::MacOs
+        } else {
+            TargetOs::Linux
+        };
+        assert_eq!(TargetOs::host(), expected);
+        assert_eq!(CodegenConfig::baseline().os, expected);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_host_builds_elf_with_start_entry() {
+        let (config, asm, calls) = host_build(&module(&[("abort", &["boom"]), ("return", &[])]));
+        assert_eq!(config.os, TargetOs::Linux);
+        assert!(asm.starts_with("section .text\n global _start\n_start:\n"));
+        assert!(asm.contains("mov rax, 60\n  xor rdi, rdi\n  syscall"));
+        assert!(asm.contains("section .high_meta"));
+        assert!(asm.contains("db \"target_os=linux\", 10"));
+        assert_eq!(calls[0].args, ["-f", "elf64", "out/compiled.asm", "-o", "out/compiled.o"]);
+        assert_eq!(calls[1].program, "ld");
+        assert_eq!(calls[2].program, "chmod");
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn macos_host_builds_macho_with_libsystem_main() {
+        let (config, asm, calls) = host_build(&module(&[("abort", &["boom"]), ("return", &[])]));
+        assert_eq!(config.os, TargetOs::MacOs);
+        assert!(asm.contains("global _main\n_main:\n  push rbp\n  mov rbp, rsp\n"));
+        assert!(asm.contains("call _write") && asm.contains("call _exit"));
+        assert!(!asm.contains("syscall"), "macOS 시스템 호출 번호에 기대면 안 됩니다");
+        assert!(asm.contains("section __DATA,__high_meta"));
+        assert!(asm.contains("db \"target_os=macos\", 10"));
+        assert_eq!(calls[0].args, ["-f", "macho64", "out/compiled.asm", "-o", "out/compiled.o"]);
+        assert_eq!(calls[1].program, "cc");
+        assert_eq!(calls[2].program, "chmod");
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn windows_host_builds_coff_with_crt_main() {
+        let (config, asm, calls) = host_build(&module(&[("abort", &["boom"]), ("return", &[])]));
+        assert_eq!(config.os, TargetOs::Windows);
+        assert!(asm.starts_with("global main\nsection .text\nmain:\n"));
+        assert!(asm.contains("section .hmeta rdata"));
+        assert!(asm.contains("db \"target_os=windows\", 10"));
+        assert_eq!(calls[0].args, ["-f", "win64", "out/compiled.asm", "-o", "out/compiled.obj"]);
+        assert_eq!(calls[1].program, "gcc");
+        assert_eq!(calls.len(), 2);
+    }
+
+    /// `let z = 5 - 5; let d = m / z;` 와 `let s = a < b ? a : b;` 를 내린 IR
+    fn integer_module() -> IRModule {
+        module(&[
+            ("push", &["5"]),
+            ("push", &["5"]),
+            ("binary", &["-"]),
+            ("store", &["z"]),
+            ("push", &["-9223372036854775808"]),
+            ("store", &["m"]),
+            ("load", &["m"]),
+            ("load", &["z"]),
+            ("binary", &["/", "boom"]),
+            ("store", &["d"]),
+            ("load", &["z"]),
+            ("load", &["m"]),
+            ("load", &["z"]),
+            ("load", &["m"]),
+            ("select", &["<"]),
+            ("store", &["s"]),
+            ("return", &[]),
+        ])
+    }
+
+    #[test]
+    fn integer_select_uses_cmov_only_when_enabled() {
+        let with_cmov = generate_assembly(&integer_module(), &CodegenConfig::baseline(), &[]);
+        assert!(with_cmov.contains("cmovl rax, [rel tmp_0]"));
+        assert!(!with_cmov.contains("select_"));
+
+        let config = CodegenConfig::new("x86-64", &["-cmov".to_string()]).unwrap();
+        let without = generate_assembly(&integer_module(), &config, &[]);
+        assert!(!without.contains("cmov"));
+        assert!(without.contains("  jge select_2\n  mov rax, [rel tmp_0]\nselect_2:\n"));
+    }
+
+    #[test]
+    fn division_guards_zero_and_minus_one_before_idiv() {
+        let asm = generate_assembly(&integer_module(), &CodegenConfig::baseline(), &[]);
+        let guard = asm.find("test rcx, rcx\n  jz div_zero_1\n  cmp rcx, -1\n  jne div_1\n  neg rax\n").unwrap();
+        let idiv = asm.find("div_1:\n  cqo\n  idiv rcx\n").unwrap();
+        assert!(guard < idiv);
+        assert!(asm[guard..idiv].contains("div_zero_1:\n"), "0 으로 나누면 idiv 전에 패닉해야 합니다");
+        assert!(asm.contains("mov rax, 0x8000000000000000"));
+        assert!(asm.contains("section .bss\ntmp_0: resq 1\n"));
+    }
+
+    /// 실제 NASM 과 링커로 호스트 실행 파일을 만들어 돌려 봅니다. NASM 이 없으면 건너뜁니다.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn host_binary_runs_with_real_tools() {
+        use crate::toolchain::ProcessRunner;
+        use std::process::Command;
+
+        if Command::new("nasm").arg("-v").output().is_err() {
+            eprintln!("nasm 이 없어 건너뜁니다");
+            return;
+        }
+        let dir = std::env::temp_dir().join(format!("high-native-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let build = |name: &str, ir: &IRModule| {
+            let asm_path = dir.join(format!("{}.asm", name)).to_string_lossy().into_owned();
+            let bin_path = dir.join(name).to_string_lossy().into_owned();
+            let config = CodegenConfig::baseline();
+            generate_native_binary(ir, &asm_path, &config, &[]).unwrap();
+            Toolchain::with_runner(ToolPaths::default(), Arc::new(ProcessRunner))
+                .assemble_and_link(config.os, &asm_path, &bin_path, &CancellationToken::new())
+                .unwrap_or_else(|e| panic!("{}", e));
+            Command::new(&bin_path).output().unwrap()
+        };
+
+        let ok = build("ok", &module(&[("let", &["x", "1"]), ("return", &[])]));
+        assert_eq!(ok.status.code(), Some(0));
+
+        let aborted = build("abort", &module(&[("abort", &["boom"]), ("return", &[])]));
+        assert_eq!(aborted.status.code(), Some(134));
+        assert_eq!(String::from_utf8_lossy(&aborted.stderr), "boom\n");
+
+        // `i64::MIN / -1` 은 감기고, 0 으로 나누는 순간 패닉합니다.
+        let mut ir = module(&[("push", &["-9223372036854775808"]), ("push", &["-1"]), ("binary", &["/", "unused"]), ("store", &["q"])]);
+        ir.instructions.extend(integer_module().instructions);
+        let divided = build("divide", &ir);
+        assert_eq!(divided.status.code(), Some(134));
+        assert_eq!(String::from_utf8_lossy(&divided.stderr), "boom\n");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,307 @@
+// navigation.rs
+// 코드 탐색: 참조 찾기와 호출 계층 (`high refs`, 데몬의 references / callHierarchy)
+//
+//   참조 찾기   커서 아래 이름이 가리키는 선언을 해석기의 심볼 표로 찾고, 같은 선언을 가리키는 모든 참조
+//               (식별자와 `name(args)` 호출)와 선언 자리를 돌려줍니다. 가려진 같은 이름은 다른 심볼입니다.
+//   호출 계층   커서가 호출 지점 위면 불린 함수, 아니면 커서를 감싼 가장 안쪽 함수(매크로) 정의를 기준으로
+//               그것을 부르는 쪽(incoming)과 그것이 부르는 쪽(outgoing)을 호출 지점과 함께 돌려줍니다.
+//               함수 밖의 최상위 코드는 `<main>` 입니다. 그래프는 `high analyze --callgraph` 와 같은 CallGraph 입니다.
+//
+// 위치와 범위는 스팬과 같은 문자 위치입니다. 리팩터링(refactor.rs)도 여기의 파일 분석을 씁니다.
+
+use crate::call_graph::{CallGraph, CallNode, NodeKind, TOP_LEVEL};
+use crate::data_structures::{Expression, Program, Span, Statement, Token, TokenKind};
+use crate::interner;
+use crate::json::Json;
+use crate::lexer_service::LexerService;
+use crate::node_ids::{NodeIndex, SideTable};
+use crate::parser_service::ParserService;
+use crate::resolver::{Resolver, Symbol};
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+/// 한 심볼의 선언과 참조
+#[derive(Debug, Clone)]
+pub struct SymbolReferences {
+    pub name: String,
+    /// 선언에서 이름이 적힌 자리
+    pub declaration: Option<Span>,
+    /// 소스 순서의 참조 (선언 자리는 빠집니다)
+    pub references: Vec<Span>,
+}
+
+impl SymbolReferences {
+    pub fn to_json(&self) -> Json {
+        let location = |span: &Span, declaration: bool| {
+            Json::object([
+                ("start", Json::from(span.start as u64)),
+                ("end", Json::from(span.end as u64)),
+                ("declaration", Json::from(declaration)),
+            ])
+        };
+        let locations = self.declaration.iter().map(|span| location(span, true));
+        let locations = locations.chain(self.references.iter().map(|span| location(span, false)));
+        Json::object([("name", Json::from(self.name.as_str())), ("locations", Json::Array(locations.collect()))])
+    }
+}
+
+/// `offset` 에 있는 이름의 선언과 모든 참조
+pub fn references(source: &str, offset: usize) -> Result<SymbolReferences, String> {
+    let file = File::analyze(source);
+    let key = file.symbol_at(offset)?;
+    Ok(SymbolReferences {
+        name: key.0.as_str().to_string(),
+        declaration: file.declarations.iter().find(|d| d.key == key).map(|d| d.name_span),
+        references: file.identifiers.iter().filter(|(.., s)| s.as_ref().map(key_of) == Some(key)).map(|(span, ..)| *span).collect(),
+    })
+}
+
+// ─── 호출 계층 ─────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct CallItem {
+    pub name: String,
+    pub kind: NodeKind,
+    /// 정의 문장 전체
+    pub span: Span,
+}
+
+impl CallItem {
+    fn new(node: &CallNode) -> Self {
+        Self { name: node.name.clone(), kind: node.kind.clone(), span: node.declaration }
+    }
+
+    fn to_json(&self) -> Json {
+        let kind = match self.kind {
+            NodeKind::TopLevel => "main",
+            NodeKind::Function => "function",
+            NodeKind::Macro => "macro",
+        };
+        Json::object([
+            ("name", Json::from(self.name.as_str())),
+            ("kind", Json::from(kind)),
+            ("start", Json::from(self.span.start as u64)),
+            ("end", Json::from(self.span.end as u64)),
+        ])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CallHierarchy {
+    pub item: CallItem,
+    /// 이 함수를 부르는 쪽과 그 안의 호출 지점들
+    pub incoming: Vec<(CallItem, Vec<Span>)>,
+    /// 이 함수가 부르는 쪽과 이 함수 안의 호출 지점들
+    pub outgoing: Vec<(CallItem, Vec<Span>)>,
+}
+
+impl CallHierarchy {
+    pub fn to_json(&self) -> Json {
+        let calls = |calls: &[(CallItem, Vec<Span>)], key: &str| {
+            Json::Array(
+                calls
+                    .iter()
+                    .map(|(item, spans)| {
+                        let ranges = spans.iter().map(|s| Json::Array(vec![Json::from(s.start as u64), Json::from(s.end as u64)]));
+                        Json::object([(key, item.to_json()), ("ranges", Json::Array(ranges.collect()))])
+                    })
+                    .collect(),
+            )
+        };
+        Json::object([
+            ("item", self.item.to_json()),
+            ("incoming", calls(&self.incoming, "from")),
+            ("outgoing", calls(&self.outgoing, "to")),
+        ])
+    }
+}
+
+/// `offset` 의 함수를 기준으로 한 호출 계층
+pub fn call_hierarchy(source: &str, offset: usize) -> Result<CallHierarchy, String> {
+    let program = ParserService::new(LexerService::new(source)).parse_program();
+    let graph = CallGraph::build(&program);
+    let node = match graph.calls.iter().find(|call| contains(call.span, offset)) {
+        Some(call) => call.callee,
+        None => (0..graph.nodes.len())
+            .filter(|&n| contains(graph.nodes[n].declaration, offset))
+            .min_by_key(|&n| graph.nodes[n].declaration.end - graph.nodes[n].declaration.start)
+            .unwrap_or(TOP_LEVEL),
+    };
+    let group = |pick: &dyn Fn(usize, usize) -> Option<usize>| {
+        let mut groups: Vec<(usize, Vec<Span>)> = vec![];
+        for call in &graph.calls {
+            let Some(other) = pick(call.caller, call.callee) else {
+                continue;
+            };
+            match groups.iter_mut().find(|(n, _)| *n == other) {
+                Some((_, spans)) => spans.push(call.span),
+                None => groups.push((other, vec![call.span])),
+            }
+        }
+        groups.into_iter().map(|(n, spans)| (CallItem::new(&graph.nodes[n]), spans)).collect()
+    };
+    Ok(CallHierarchy {
+        item: CallItem::new(&graph.nodes[node]),
+        incoming: group(&|caller, callee| (callee == node).then_some(caller)),
+        outgoing: group(&|caller, callee| (caller == node).then_some(callee)),
+    })
+}
+
+// ─── 파일 분석 ─────────────────────────────
+
+/// 선언을 구별하는 열쇠: (이름, 선언 범위). 해석기가 참조마다 기록하는 `Symbol` 과 같은 값입니다.
+pub(crate) type Key = (interner::Symbol, usize, usize);
+
+pub(crate) fn key_of(symbol: &Symbol) -> Key {
+    (symbol.name, symbol.declaration_span.start, symbol.declaration_span.end)
+}
+
+pub(crate) struct Declaration {
+    pub key: Key,
+    /// 선언에서 이름이 적힌 자리
+    pub name_span: Span,
+}
+
+/// 파싱한 프로그램과 토큰, 이름 참조와 선언 자리
+pub(crate) struct File<'a> {
+    pub program: Program,
+    pub tokens: Vec<Token<'a>>,
+    /// 소스 순서의 이름 참조 (식별자와 `name(args)` 의 이름): (이름 자리, 이름, 가리키는 선언)
+    pub identifiers: Vec<(Span, interner::Symbol, Option<Symbol>)>,
+    pub declarations: Vec<Declaration>,
+}
+
+impl<'a> File<'a> {
+    pub fn analyze(source: &'a str) -> Self {
+        let program = ParserService::new(LexerService::new(source)).parse_program();
+        let tokens = tokenize(source);
+        let index = NodeIndex::build(&program);
+        let symbols = Resolver::resolve_symbols(&program, &index);
+        let mut collector = Collector { tokens: &tokens, index: &index, symbols: &symbols, attribute: None, identifiers: vec![], declarations: vec![] };
+        collector.visit_program(&program);
+        let Collector { identifiers, declarations, .. } = collector;
+        File { program, tokens, identifiers, declarations }
+    }
+
+    /// `offset` 의 선언 자리나 참조가 가리키는 심볼
+    pub fn symbol_at(&self, offset: usize) -> Result<Key, String> {
+        if let Some(declaration) = self.declarations.iter().find(|d| contains(d.name_span, offset)) {
+            return Ok(declaration.key);
+        }
+        let (span, name, symbol) = self
+            .identifiers
+            .iter()
+            .find(|(span, ..)| contains(*span, offset))
+            .ok_or_else(|| format!("{} 위치에 이름이 없습니다.", offset))?;
+        symbol
+            .as_ref()
+            .map(key_of)
+            .ok_or_else(|| format!("'{}' ({}..{}) 는 이 파일에서 선언되지 않은 전역·내장 이름입니다.", name, span.start, span.end))
+    }
+}
+
+/// 이름 참조와 선언 자리를 모읍니다. 선언 범위는 해석기와 같은 규칙(바로 바깥 어트리뷰트부터)으로 셉니다.
+struct Collector<'c, 'a> {
+    tokens: &'c [Token<'a>],
+    index: &'c NodeIndex,
+    symbols: &'c SideTable<Symbol>,
+    /// 바로 바깥 `Attributed` 의 첫 어트리뷰트 범위
+    attribute: Option<Span>,
+    identifiers: Vec<(Span, interner::Symbol, Option<Symbol>)>,
+    declarations: Vec<Declaration>,
+}
+
+impl Collector<'_, '_> {
+    fn declare(&mut self, name: interner::Symbol, declaration_span: Span, within: Span, after_paren: bool) {
+        if let Some(name_span) = name_token(self.tokens, within, name, after_paren) {
+            self.declarations.push(Declaration { key: (name, declaration_span.start, declaration_span.end), name_span });
+        }
+    }
+
+    /// 식과 이름 호출의 범위는 뒤 토큰까지 덮을 수 있으므로 이름 길이만큼만 씁니다.
+    fn reference(&mut self, expr: &Expression, start: usize, name: interner::Symbol) {
+        let span = Span { start, end: start + name.as_str().chars().count() };
+        let symbol = self.index.expression(expr).and_then(|id| self.symbols.get(id)).cloned();
+        self.identifiers.push((span, name, symbol));
+    }
+}
+
+impl Visitor for Collector<'_, '_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        let attribute = self.attribute.take();
+        let declaration_span = attribute.unwrap_or(stmt.span());
+        match stmt {
+            Statement::Attributed { attributes, .. } => self.attribute = attributes.first().map(|a| a.span),
+            Statement::LetStatement { name, .. } => self.declare(*name, declaration_span, stmt.span(), false),
+            Statement::MacroDefinition { name, parameters, .. } => {
+                self.declare(interner::Symbol::intern(name), declaration_span, stmt.span(), false);
+                for p in parameters {
+                    self.declare(interner::Symbol::intern(p), declaration_span, stmt.span(), true);
+                }
+            }
+            _ => {}
+        }
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Identifier(span, name) => self.reference(expr, span.start, *name),
+            Expression::MacroCall(span, name, _) => self.reference(expr, span.start, interner::Symbol::intern(name)),
+            // 메서드 이름은 받는 식 뒤의 첫 `name` 토큰입니다.
+            Expression::MethodCall(span, target, name, _) => {
+                let after_target = Span { start: target.span().end, end: span.end };
+                if let Some(name_span) = name_token(self.tokens, after_target, *name, false) {
+                    self.reference(expr, name_span.start, *name);
+                }
+            }
+            // 대입 대상은 식의 맨 앞 이름입니다.
+            Expression::Assign(span, _, name, _) => self.reference(expr, span.start, *name),
+            Expression::Function(span, parameters, _) => {
+                for p in parameters {
+                    self.declare(*p, *span, *span, true);
+                }
+            }
+            _ => {}
+        }
+        walk_expression(self, expr);
+    }
+}
+
+pub(crate) fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut lexer = LexerService::new(source);
+    let mut tokens = vec![];
+    loop {
+        let token = lexer.next_token();
+        if matches!(token.kind, TokenKind::Eof) {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+/// `within` 안에서 `name` 이 처음 나오는 식별자 토큰. `after_paren` 이면 첫 '(' 뒤부터 찾습니다. (매개변수 목록)
+fn name_token(tokens: &[Token], within: Span, name: interner::Symbol, after_paren: bool) -> Option<Span> {
+    let mut tokens = tokens.iter().filter(|t| within.start <= t.span.start && t.span.end <= within.end);
+    if after_paren {
+        tokens.find(|t| matches!(t.kind, TokenKind::LParen))?;
+    }
+    tokens.find(|t| matches!(t.kind, TokenKind::Identifier(n) if n == name)).map(|t| t.span)
+}
+
+/// 끝 위치도 포함합니다. (이름 바로 뒤의 커서)
+pub(crate) fn contains(span: Span, offset: usize) -> bool {
+    span.start <= offset && offset <= span.end
+}
+
+/// 문자 위치 → 1부터 세는 (줄, 열)
+pub fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let mut line_start = 0;
+    let mut line = 1;
+    for (i, c) in source.chars().take(offset).enumerate() {
+        if c == '\n' {
+            line += 1;
+            line_start = i + 1;
+        }
+    }
+    (line, offset - line_start + 1)
+}
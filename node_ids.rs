@@ -0,0 +1,255 @@
+// node_ids.rs
+// AST 노드 번호와 노드별 부가 정보 표(side table)입니다.
+//
+// 파싱이 끝난 프로그램을 전위 순회하며 문장과 식마다 `NodeId` 를 매깁니다. 프로그램 자신은
+// `Program::root_id` (0) 이고, 이후 번호는 소스 순서를 따르므로 같은 소스는 항상 같은 번호를 받습니다.
+// 한 노드의 하위 트리는 연속된 번호를 차지하므로, 최상위 문장 하나가 바뀌면 그 구간만 지우면 됩니다.
+//
+// 분석 결과(타입, 해석된 심볼, 상수 값, 효과)는 AST 안이 아니라 `SideTable` 에 번호로 저장합니다.
+// 번호는 노드의 주소로 찾으므로, AST 를 고친 뒤에는 `NodeIndex` 를 다시 만들어야 합니다.
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::compat::*;
+use crate::data_structures::{Expression, Program, Span, Statement, TypeAnnotation, Value};
+use crate::effects::{Effect, EffectTable};
+use crate::operators;
+use crate::resolver::{self, Resolver};
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// 노드 주소 → 번호, 번호 → 소스 범위
+#[derive(Debug, Default)]
+pub struct NodeIndex {
+    statements: HashMap<*const Statement, NodeId>,
+    expressions: HashMap<*const Expression, NodeId>,
+    spans: Vec<Span>,
+    /// 최상위 문장별 번호 구간
+    top_level: Vec<Range<NodeId>>,
+}
+
+impl NodeIndex {
+    pub fn build(program: &Program) -> Self {
+        let mut index = NodeIndex::default();
+        index.spans.push(program.span);
+        for stmt in &program.statements {
+            let start = index.next_id();
+            index.visit_statement(stmt);
+            let end = index.next_id();
+            index.top_level.push(start..end);
+        }
+        index
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    pub fn statement(&self, stmt: &Statement) -> Option<NodeId> {
+        self.statements.get(&(stmt as *const Statement)).copied()
+    }
+
+    pub fn expression(&self, expr: &Expression) -> Option<NodeId> {
+        self.expression_at(expr)
+    }
+
+    /// 주소로 식의 번호를 찾습니다. 주소가 가리키는 값은 읽지 않습니다.
+    pub fn expression_at(&self, expr: *const Expression) -> Option<NodeId> {
+        self.expressions.get(&expr).copied()
+    }
+
+    pub fn span(&self, id: NodeId) -> Option<Span> {
+        self.spans.get(id.0 as usize).copied()
+    }
+
+    /// `index` 번째 최상위 문장과 그 하위 노드들의 번호 구간
+    pub fn top_level(&self, index: usize) -> Option<Range<NodeId>> {
+        self.top_level.get(index).cloned()
+    }
+
+    /// 루트를 포함한 노드 수
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    fn next_id(&self) -> NodeId {
+        NodeId(self.spans.len() as u32)
+    }
+}
+
+impl Visitor for NodeIndex {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        let id = self.next_id();
+        self.statements.insert(stmt, id);
+        self.spans.push(stmt.span());
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        let id = self.next_id();
+        self.expressions.insert(expr, id);
+        self.spans.push(expr.span());
+        walk_expression(self, expr);
+    }
+}
+
+/// 노드 번호로 찾는 분석 결과 표
+#[derive(Debug, Clone)]
+pub struct SideTable<T> {
+    entries: HashMap<NodeId, T>,
+}
+
+impl<T> SideTable<T> {
+    pub fn new() -> Self {
+        SideTable { entries: HashMap::new() }
+    }
+
+    pub fn insert(&mut self, id: NodeId, value: T) {
+        self.entries.insert(id, value);
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<&T> {
+        self.entries.get(&id)
+    }
+
+    pub fn remove(&mut self, id: NodeId) -> Option<T> {
+        self.entries.remove(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.entries.iter().map(|(id, value)| (*id, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 번호 구간(하위 트리) 안의 결과를 지웁니다.
+    pub fn invalidate(&mut self, range: &Range<NodeId>) {
+        self.entries.retain(|id, _| !range.contains(id));
+    }
+}
+
+impl<T> Default for SideTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 한 프로그램에 대한 분석 결과 모음
+#[derive(Debug, Default)]
+pub struct AstMetadata {
+    /// 식의 타입: 상수로 계산되는 식의 값 타입과, let 에 적힌 타입 표기
+    pub types: SideTable<TypeAnnotation>,
+    /// 식별자 식과 이름 호출 식이 가리키는 선언
+    pub symbols: SideTable<resolver::Symbol>,
+    /// 컴파일 시점에 값이 정해지는 식
+    pub constants: SideTable<Value>,
+    /// 문장과 식의 효과
+    pub effects: SideTable<Effect>,
+}
+
+impl AstMetadata {
+    pub fn collect(program: &Program, index: &NodeIndex) -> Self {
+        let effects = EffectTable::infer(program);
+        let mut collector = Collector { index, effects: &effects, metadata: AstMetadata::default() };
+        collector.visit_program(program);
+        let mut metadata = collector.metadata;
+        metadata.symbols = Resolver::resolve_symbols(program, index);
+        metadata
+    }
+
+    /// 하위 트리 하나의 결과를 모든 표에서 지웁니다.
+    pub fn invalidate(&mut self, range: &Range<NodeId>) {
+        self.types.invalidate(range);
+        self.symbols.invalidate(range);
+        self.constants.invalidate(range);
+        self.effects.invalidate(range);
+    }
+}
+
+struct Collector<'a> {
+    index: &'a NodeIndex,
+    effects: &'a EffectTable,
+    metadata: AstMetadata,
+}
+
+impl Collector<'_> {
+    fn constant(&self, expr: &Expression) -> Option<&Value> {
+        self.metadata.constants.get(self.index.expression(expr)?)
+    }
+
+    /// 자식이 모두 상수이면 실행 시점과 같은 의미로 계산합니다. 오류가 나는 식은 상수로 보지 않습니다.
+    fn evaluate(&self, expr: &Expression) -> Option<Value> {
+        match expr {
+            Expression::Literal(_, value) => Some(value.clone()),
+            Expression::Grouped(_, inner) => self.constant(inner).cloned(),
+            Expression::PrefixOperation(_, op, inner) => operators::unary(op, self.constant(inner)?).ok(),
+            Expression::InfixOperation(_, op, left, right) => {
+                operators::binary(op, self.constant(left)?, self.constant(right)?).ok()
+            }
+            Expression::Ternary(_, cond, then_expr, else_expr) => match self.constant(cond)? {
+                Value::Boolean(true) => self.constant(then_expr).cloned(),
+                Value::Boolean(false) => self.constant(else_expr).cloned(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for Collector<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt);
+        let Some(id) = self.index.statement(stmt) else {
+            return;
+        };
+        self.metadata.effects.insert(id, self.effects.statement(stmt));
+        if let Statement::LetStatement { type_annotation: Some(annotation), .. } = stmt {
+            self.metadata.types.insert(id, annotation.clone());
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        // 자식의 상수 값을 먼저 구해야 부모를 계산할 수 있습니다.
+        walk_expression(self, expr);
+        let Some(id) = self.index.expression(expr) else {
+            return;
+        };
+        self.metadata.effects.insert(id, self.effects.expression(expr));
+        if let Some(value) = self.evaluate(expr) {
+            if let Some(ty) = value_type(&value) {
+                self.metadata.types.insert(id, ty);
+            }
+            self.metadata.constants.insert(id, value);
+        }
+    }
+}
+
+fn value_type(value: &Value) -> Option<TypeAnnotation> {
+    match value {
+        Value::Integer(_) => Some(TypeAnnotation::Int),
+        Value::Float(_) => Some(TypeAnnotation::Float),
+        Value::Boolean(_) => Some(TypeAnnotation::Bool),
+        Value::String(_) => Some(TypeAnnotation::String),
+        Value::Null => Some(TypeAnnotation::Void),
+        _ => None,
+    }
+}
@@ -0,0 +1,198 @@
+// operators.rs
+// 단항/이항 연산자의 의미입니다.
+//
+// 트리 순회 런타임, 바이트코드 VM, 최적화기의 상수 접기가 모두 이 정의를 쓰므로
+// 어느 단계에서 계산해도 결과가 같습니다.
+//
+// 정수 나눗셈과 나머지 (Rust, Python 백엔드도 같은 규칙을 따릅니다)
+//   `a / b`   몫을 0 쪽으로 자릅니다(truncating).   -7 / 2 == -3,  7 / -2 == -3
+//   `a % b`   나머지의 부호는 나뉘는 수 `a` 를 따르며 항상 `a == (a / b) * b + a % b` 입니다.   -7 % 2 == -1,  7 % -2 == 1
+//   오버플로  `i64::MIN / -1` 은 `i64::MIN` 으로 감기고 `i64::MIN % -1` 은 0 입니다.
+//   0 나누기  `b == 0` 이면 패닉(E0030)입니다. 컴파일 시점에 증명되면 E0033 경고도 냅니다. (division_check)
+//
+// 비트 연산 (정수끼리만)
+//   `&` `|` `^`   64비트 2의 보수 비트 연산
+//   `a << b`      왼쪽 시프트. 넘친 비트는 버립니다.
+//   `a >> b`      산술(부호 유지) 오른쪽 시프트.   -8 >> 1 == -4
+//   시프트 양     하위 6비트만 씁니다 (`b & 63`). x86-64 의 `shl`/`sar` 과 Rust 의 `wrapping_shl` 과 같습니다.   1 << 64 == 1
+// x86-64 의 `cqo; idiv` 는 이 의미와 같지만 `i64::MIN / -1` 에서 #DE 를 내므로, 네이티브 코드는 나누는 수가
+// 0 인 경우(패닉)와 -1 인 경우(부호 반전)를 `idiv` 앞에서 따로 처리합니다. (native_codegen, llvm_codegen)
+
+use crate::arrays;
+use crate::compat::*;
+use crate::data_structures::{TokenKind, Value};
+use crate::maps;
+
+/// 연산을 계산하지 못한 이유
+#[derive(Debug, Clone, PartialEq)]
+pub enum OperatorError {
+    /// 피연산자 타입에 대해 정의되지 않은 연산
+    TypeMismatch,
+    /// 정수를 0으로 나누거나 나머지를 구함
+    DivisionByZero,
+    /// enum 에 `@derive(...)` 하지 않은 동작 (`"eq"`)
+    NotDerived(&'static str),
+}
+
+pub fn binary(op: &TokenKind, left: &Value, right: &Value) -> Result<Value, OperatorError> {
+    use Value::{Boolean, DateTime, Duration, Float, Integer};
+
+    let value = match (op, left, right) {
+        // ─── 산술 ─────────────────────────────
+        (TokenKind::Slash | TokenKind::Percent, Integer(_), Integer(0)) => return Err(OperatorError::DivisionByZero),
+        (TokenKind::Plus, Integer(a), Integer(b)) => Integer(a.wrapping_add(*b)),
+        (TokenKind::Minus, Integer(a), Integer(b)) => Integer(a.wrapping_sub(*b)),
+        (TokenKind::Asterisk, Integer(a), Integer(b)) => Integer(a.wrapping_mul(*b)),
+        (TokenKind::Slash, Integer(a), Integer(b)) => Integer(a.wrapping_div(*b)),
+        (TokenKind::Percent, Integer(a), Integer(b)) => Integer(a.wrapping_rem(*b)),
+
+        (TokenKind::BitAnd, Integer(a), Integer(b)) => Integer(a & b),
+        (TokenKind::BitOr, Integer(a), Integer(b)) => Integer(a | b),
+        (TokenKind::BitXor, Integer(a), Integer(b)) => Integer(a ^ b),
+        // `as u32` 로 음수도 하위 비트가 남고, wrapping_* 가 하위 6비트만 씁니다.
+        (TokenKind::ShiftLeft, Integer(a), Integer(b)) => Integer(a.wrapping_shl(*b as u32)),
+        (TokenKind::ShiftRight, Integer(a), Integer(b)) => Integer(a.wrapping_shr(*b as u32)),
+
+        (TokenKind::Plus, Float(a), Float(b)) => Float(a + b),
+        (TokenKind::Minus, Float(a), Float(b)) => Float(a - b),
+        (TokenKind::Asterisk, Float(a), Float(b)) => Float(a * b),
+        (TokenKind::Slash, Float(a), Float(b)) => Float(a / b),
+
+        (TokenKind::Plus, Value::String(a), Value::String(b)) => Value::String(format!("{}{}", a, b).into()),
+        (TokenKind::Plus, Value::Bytes(a), Value::Bytes(b)) => Value::Bytes([a.as_ref(), b.as_ref()].concat().into()),
+        (TokenKind::Plus, Value::Array(a), Value::Array(b)) => Value::Array([a.as_ref(), b.as_ref()].concat().into()),
+
+        // ─── 날짜/시간 (datetime.rs) ─────────────────────────────
+        (TokenKind::Plus, DateTime(t), Duration(d)) | (TokenKind::Plus, Duration(d), DateTime(t)) => DateTime(t.shifted(*d)),
+        (TokenKind::Minus, DateTime(t), Duration(d)) => DateTime(t.shifted(d.saturating_neg())),
+        (TokenKind::Minus, DateTime(a), DateTime(b)) => Duration(a.millis.saturating_sub(b.millis)),
+        (TokenKind::Plus, Duration(a), Duration(b)) => Duration(a.saturating_add(*b)),
+        (TokenKind::Minus, Duration(a), Duration(b)) => Duration(a.saturating_sub(*b)),
+        (TokenKind::Asterisk, Duration(d), Integer(n)) | (TokenKind::Asterisk, Integer(n), Duration(d)) => {
+            Duration(d.saturating_mul(*n))
+        }
+        (TokenKind::Slash, Duration(_), Integer(0)) => return Err(OperatorError::DivisionByZero),
+        (TokenKind::Slash, Duration(d), Integer(n)) => Duration(d.wrapping_div(*n)),
+        // 오프셋은 표시 방식일 뿐이므로 시각끼리 비교합니다.
+        (_, DateTime(a), DateTime(b)) => Boolean(compare(op, a.millis, b.millis)?),
+        (_, Duration(a), Duration(b)) => Boolean(compare(op, *a, *b)?),
+
+        // ─── 비교 ─────────────────────────────
+        (TokenKind::Eq, Integer(a), Integer(b)) => Boolean(a == b),
+        (TokenKind::Neq, Integer(a), Integer(b)) => Boolean(a != b),
+        (TokenKind::Less, Integer(a), Integer(b)) => Boolean(a < b),
+        (TokenKind::Greater, Integer(a), Integer(b)) => Boolean(a > b),
+        (TokenKind::LessEqual, Integer(a), Integer(b)) => Boolean(a <= b),
+        (TokenKind::GreaterEqual, Integer(a), Integer(b)) => Boolean(a >= b),
+
+        (TokenKind::Eq, Float(a), Float(b)) => Boolean(a == b),
+        (TokenKind::Neq, Float(a), Float(b)) => Boolean(a != b),
+        (TokenKind::Less, Float(a), Float(b)) => Boolean(a < b),
+        (TokenKind::Greater, Float(a), Float(b)) => Boolean(a > b),
+        (TokenKind::LessEqual, Float(a), Float(b)) => Boolean(a <= b),
+        (TokenKind::GreaterEqual, Float(a), Float(b)) => Boolean(a >= b),
+
+        (TokenKind::Eq, Value::String(a), Value::String(b)) => Boolean(a == b),
+        (TokenKind::Neq, Value::String(a), Value::String(b)) => Boolean(a != b),
+        (TokenKind::Eq, Value::Bytes(a), Value::Bytes(b)) => Boolean(a == b),
+        (TokenKind::Neq, Value::Bytes(a), Value::Bytes(b)) => Boolean(a != b),
+        (TokenKind::Eq | TokenKind::Neq, Value::Array(a), Value::Array(b)) => {
+            let equal = arrays::equal(a, b).ok_or(OperatorError::TypeMismatch)?;
+            Boolean(equal == matches!(op, TokenKind::Eq))
+        }
+        (TokenKind::Eq, Value::Range(a, b), Value::Range(c, d)) => Boolean((a, b) == (c, d)),
+        (TokenKind::Neq, Value::Range(a, b), Value::Range(c, d)) => Boolean((a, b) != (c, d)),
+        (TokenKind::Eq | TokenKind::Neq, Value::Map(a), Value::Map(b)) => {
+            let equal = maps::equal(a, b).ok_or(OperatorError::TypeMismatch)?;
+            Boolean(equal == matches!(op, TokenKind::Eq))
+        }
+        // 같은 enum 의 같은 변형이고 필드끼리 `==` 이면 같습니다. 비교는 `@derive(eq)` 한 enum 에만 있습니다.
+        (TokenKind::Eq | TokenKind::Neq, Value::EnumVariant(a), Value::EnumVariant(b)) => {
+            if a.enum_name == b.enum_name && !a.derives.eq {
+                return Err(OperatorError::NotDerived("eq"));
+            }
+            let equal = match (&a.fields, &b.fields) {
+                _ if a.enum_name != b.enum_name || a.name != b.name => false,
+                (Some(x), Some(y)) => arrays::equal(x, y).ok_or(OperatorError::TypeMismatch)?,
+                (x, y) => x.is_none() && y.is_none(),
+            };
+            Boolean(equal == matches!(op, TokenKind::Eq))
+        }
+        (TokenKind::Eq, Boolean(a), Boolean(b)) => Boolean(a == b),
+        (TokenKind::Neq, Boolean(a), Boolean(b)) => Boolean(a != b),
+        // 런타임 타입 검사: `type_of x == "int"`
+        (TokenKind::Eq | TokenKind::Neq, Value::Type(a), Value::Type(b)) => Boolean((a == b) == matches!(op, TokenKind::Eq)),
+        (TokenKind::Eq | TokenKind::Neq, Value::Type(t), Value::String(name))
+        | (TokenKind::Eq | TokenKind::Neq, Value::String(name), Value::Type(t)) => {
+            Boolean((t.as_str() == name.as_ref()) == matches!(op, TokenKind::Eq))
+        }
+
+        // ─── 논리 ─────────────────────────────
+        // 단락 평가는 호출하는 쪽이 맡습니다. 여기서는 두 값이 모두 계산된 경우입니다.
+        (TokenKind::And, Boolean(a), Boolean(b)) => Boolean(*a && *b),
+        (TokenKind::Or, Boolean(a), Boolean(b)) => Boolean(*a || *b),
+
+        _ => return Err(OperatorError::TypeMismatch),
+    };
+    Ok(value)
+}
+
+pub fn unary(op: &TokenKind, operand: &Value) -> Result<Value, OperatorError> {
+    match (op, operand) {
+        (TokenKind::Minus, Value::Integer(a)) => Ok(Value::Integer(a.wrapping_neg())),
+        (TokenKind::Minus, Value::Float(a)) => Ok(Value::Float(-a)),
+        (TokenKind::Minus, Value::Duration(d)) => Ok(Value::Duration(d.saturating_neg())),
+        (TokenKind::Bang, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+        (TokenKind::Tilde, Value::Integer(a)) => Ok(Value::Integer(!a)),
+        _ => Err(OperatorError::TypeMismatch),
+    }
+}
+
+/// 순서가 있는 값의 비교. 비교 연산자가 아니면 타입 오류입니다.
+fn compare(op: &TokenKind, a: i64, b: i64) -> Result<bool, OperatorError> {
+    match op {
+        TokenKind::Eq => Ok(a == b),
+        TokenKind::Neq => Ok(a != b),
+        TokenKind::Less => Ok(a < b),
+        TokenKind::Greater => Ok(a > b),
+        TokenKind::LessEqual => Ok(a <= b),
+        TokenKind::GreaterEqual => Ok(a >= b),
+        _ => Err(OperatorError::TypeMismatch),
+    }
+}
+
+/// `&&` / `||` 의 왼쪽 값만으로 결과가 정해지면 그 값입니다.
+pub fn short_circuit(op: &TokenKind, left: &Value) -> Option<Value> {
+    match (op, left) {
+        (TokenKind::And, Value::Boolean(false)) => Some(Value::Boolean(false)),
+        (TokenKind::Or, Value::Boolean(true)) => Some(Value::Boolean(true)),
+        _ => None,
+    }
+}
+
+/// 진단 메시지에 쓰는 연산자 기호
+pub fn symbol(op: &TokenKind) -> &'static str {
+    match op {
+        TokenKind::Plus => "+",
+        TokenKind::Minus => "-",
+        TokenKind::Asterisk => "*",
+        TokenKind::Slash => "/",
+        TokenKind::Percent => "%",
+        TokenKind::Eq => "==",
+        TokenKind::Neq => "!=",
+        TokenKind::Less => "<",
+        TokenKind::Greater => ">",
+        TokenKind::LessEqual => "<=",
+        TokenKind::GreaterEqual => ">=",
+        TokenKind::And => "&&",
+        TokenKind::Or => "||",
+        TokenKind::BitAnd => "&",
+        TokenKind::BitOr => "|",
+        TokenKind::BitXor => "^",
+        TokenKind::ShiftLeft => "<<",
+        TokenKind::ShiftRight => ">>",
+        TokenKind::Bang => "!",
+        TokenKind::Tilde => "~",
+        _ => "?",
+    }
+}
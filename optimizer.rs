@@ -1,142 +1,1082 @@
-use crate::data_structures::{
-    Program, Statement, Expression, Value, TokenKind, Span,
-};
-
-pub struct Optimizer;
-
-impl Optimizer {
-    pub fn optimize(program: &mut Program) {
-        for stmt in program.statements.iter_mut() {
-            Self::optimize_statement(stmt);
-        }
-    }
-
-    fn optimize_statement(stmt: &mut Box<Statement>) {
-    match stmt.as_mut() {
-        Statement::ExpressionStatement(expr) => {
-            Self::optimize_expression(expr);
-        }
-        Statement::LetStatement { value, .. } => {
-            Self::optimize_expression(value);
-        }
-        Statement::ReturnStatement(expr) => {
-            Self::optimize_expression(expr);
-        }
-        Statement::IfStatement { condition, then_branch, else_branch } => {
-            Self::optimize_expression(condition);
-            Self::optimize_statement(then_branch);
-            if let Some(else_stmt) = else_branch {
-                Self::optimize_statement(else_stmt);
-            }
-        }
-        Statement::BlockStatement { statements, .. } => {
-            for s in statements.iter_mut() {
-                Self::optimize_statement(s);
-            }
-        }
-        Statement::ForStatement { initializer, condition, increment, body } => {
-            if let Some(init) = initializer {
-                Self::optimize_statement(init);
-            }
-            if let Some(cond) = condition {
-                Self::optimize_expression(cond);
-            }
-            if let Some(inc) = increment {
-                Self::optimize_expression(inc);
-            }
-            Self::optimize_statement(body);
-        }
-        Statement::WhileStatement { condition, body } => {
-            Self::optimize_expression(condition);
-            Self::optimize_statement(body);
-        }
-        Statement::MacroDefinition { .. } => {
-            // 매크로 정의는 확장기에서 처리
-        }
-    }
-}
-
-
-    fn optimize_expression(expr: &mut Box<Expression>) {
-        match expr.as_mut() {
-            Expression::InfixOperation(span, op, left, right) => {
-                Self::optimize_expression(left);
-                Self::optimize_expression(right);
-
-                if let (Expression::Literal(_, l), Expression::Literal(_, r)) = (&**left, &**right) {
-                    if let Some(val) = Self::fold_constants(op, l, r) {
-                        *expr = Box::new(Expression::Literal(*span, val));
-                    }
-                }
-            }
-            Expression::Grouped(span, inner) => {
-                Self::optimize_expression(inner);
-                if let Expression::Literal(_, val) = &**inner {
-                    *expr = Box::new(Expression::Literal(*span, val.clone()));
-                }
-            }
-            Expression::Ternary(_, cond, then_expr, else_expr) => {
-                Self::optimize_expression(cond);
-                Self::optimize_expression(then_expr);
-                Self::optimize_expression(else_expr);
-
-                if let Expression::Literal(_, Value::Boolean(b)) = &**cond {
-                    *expr = if *b {
-                        then_expr.clone()
-                    } else {
-                        else_expr.clone()
-                    };
-                }
-            }
-            Expression::Call(_, func, args) => {
-                Self::optimize_expression(func);
-                for arg in args.iter_mut() {
-                    Self::optimize_expression(arg);
-                }
-            }
-            Expression::Reflect(_, inner)
-            | Expression::Eval(_, inner)
-            | Expression::TypeOf(_, inner) => {
-                Self::optimize_expression(inner);
-            }
-            Expression::MacroCall(_, _, args) => {
-                for arg in args.iter_mut() {
-                    Self::optimize_expression(arg);
-                }
-            }
-            _ => {}
-        }
-    }
-
-    fn fold_constants(op: &TokenKind, left: &Value, right: &Value) -> Option<Value> {
-        match (op, left, right) {
-            // ─── 산술 ─────────────────────────────
-            (TokenKind::Plus, Value::Integer(a), Value::Integer(b)) => Some(Value::Integer(a + b)),
-            (TokenKind::Minus, Value::Integer(a), Value::Integer(b)) => Some(Value::Integer(a - b)),
-            (TokenKind::Asterisk, Value::Integer(a), Value::Integer(b)) => Some(Value::Integer(a * b)),
-            (TokenKind::Slash, Value::Integer(a), Value::Integer(b)) if *b != 0 => Some(Value::Integer(a / b)),
-
-            (TokenKind::Plus, Value::Float(a), Value::Float(b)) => Some(Value::Float(a + b)),
-            (TokenKind::Minus, Value::Float(a), Value::Float(b)) => Some(Value::Float(a - b)),
-            (TokenKind::Asterisk, Value::Float(a), Value::Float(b)) => Some(Value::Float(a * b)),
-            (TokenKind::Slash, Value::Float(a), Value::Float(b)) if *b != 0.0 => Some(Value::Float(a / b)),
-
-            // ─── 비교 ─────────────────────────────
-            (TokenKind::Eq, Value::Integer(a), Value::Integer(b)) => Some(Value::Boolean(a == b)),
-            (TokenKind::Neq, Value::Integer(a), Value::Integer(b)) => Some(Value::Boolean(a != b)),
-            (TokenKind::Less, Value::Integer(a), Value::Integer(b)) => Some(Value::Boolean(a < b)),
-            (TokenKind::Greater, Value::Integer(a), Value::Integer(b)) => Some(Value::Boolean(a > b)),
-            (TokenKind::LessEqual, Value::Integer(a), Value::Integer(b)) => Some(Value::Boolean(a <= b)),
-            (TokenKind::GreaterEqual, Value::Integer(a), Value::Integer(b)) => Some(Value::Boolean(a >= b)),
-
-            (TokenKind::Eq, Value::Float(a), Value::Float(b)) => Some(Value::Boolean(a == b)),
-            (TokenKind::Neq, Value::Float(a), Value::Float(b)) => Some(Value::Boolean(a != b)),
-            (TokenKind::Less, Value::Float(a), Value::Float(b)) => Some(Value::Boolean(a < b)),
-            (TokenKind::Greater, Value::Float(a), Value::Float(b)) => Some(Value::Boolean(a > b)),
-            (TokenKind::LessEqual, Value::Float(a), Value::Float(b)) => Some(Value::Boolean(a <= b)),
-            (TokenKind::GreaterEqual, Value::Float(a), Value::Float(b)) => Some(Value::Boolean(a >= b)),
-
-            _ => None
-        }
-    }
-}
+
+use crate::attributes::has_attribute;
+use crate::call_graph::CallGraph;
+use crate::compat::*;
+use crate::data_structures::{
+    Program, Statement, Expression, Value, TokenKind, Span,
+};
+use crate::effects::{Effect, EffectTable};
+use crate::formatting;
+use crate::interner::Symbol;
+use crate::operators;
+use crate::patterns;
+use crate::profile::{Profile, HOT_ITERATIONS};
+use crate::macro_expander::{group, substitute, MacroExpander};
+use crate::visitor::{walk_expression, walk_expression_mut, walk_statement, walk_statement_mut, MutVisitor, Visitor};
+
+/// `@inline` 바인딩이 사용 지점에서 바뀔 내용
+#[derive(Debug, Clone)]
+enum Inlinable {
+    Constant(Value),
+    /// 본문이 단일 식인 함수: 호출을 인자로 치환한 본문 식으로 바꿉니다.
+    Function { parameters: Vec<Symbol>, body: Expression },
+}
+
+/// 실수 상수 접기의 엄격도
+///
+/// 접은 값은 리터럴로 백엔드에 넘어가므로, 실행 시점에 계산한 값과 비트 단위로 같게 적힐 수 있어야 관찰 결과가 같습니다.
+/// 연산 자체는 어느 모드에서나 `operators::binary` 로 계산하며 식의 결합 순서를 바꾸지 않습니다.
+///
+///   Strict   결과가 High 리터럴로 같은 비트를 적을 수 있는 실수일 때만 접습니다. (`formatting::float_literal`)
+///            NaN 은 접지 않습니다. 실행 시점 NaN 의 부호·페이로드는 플랫폼마다 다르고 백엔드는 표준 NaN 을 씁니다.
+///            무한대도 리터럴 표기가 없으므로 접지 않습니다. `-0.0` 은 부호 비트까지 보존되므로 접습니다.
+///   Relaxed  계산되는 모든 연산을 접습니다. 접은 NaN 은 백엔드의 표준 NaN 으로 바뀔 수 있습니다.
+///
+/// 비교 연산은 결과가 불리언이므로 NaN 이나 `-0.0` 피연산자여도 (`0.0 == -0.0` 은 참) 두 모드 모두 접습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathMode {
+    Strict,
+    Relaxed,
+}
+
+impl MathMode {
+    /// `--strict-math` / `--fast-math` 를 주지 않았을 때: -O0/-O1 은 Strict, -O2 이상은 Relaxed
+    pub fn for_level(optimization_level: u8) -> Self {
+        if optimization_level <= 1 {
+            MathMode::Strict
+        } else {
+            MathMode::Relaxed
+        }
+    }
+
+    /// 접은 결과를 리터럴로 바꿔도 되는지
+    pub(crate) fn accepts(self, value: &Value) -> bool {
+        match (self, value) {
+            (MathMode::Strict, Value::Float(f)) => formatting::float_literal(*f).is_some(),
+            _ => true,
+        }
+    }
+}
+
+/// 공통 부분식 제거를 켜는 최소 최적화 수준 (-O2)
+pub const CSE_LEVEL: u8 = 2;
+
+/// 최적화 수준(1-3)과 그에 딸린 설정. 최적화 수준 0 이면 옵티마이저를 부르지 않습니다.
+#[derive(Debug, Clone, Copy)]
+pub struct OptimizerSettings<'a> {
+    pub level: u8,
+    pub math: MathMode,
+    /// 펼칠 루프의 최대 반복 횟수. 0 이면 펼치지 않습니다.
+    pub unroll_limit: u32,
+    /// `--profile-use`: 인라이닝과 루프 펼치기에 쓰는 실행 프로필 (profile.rs)
+    pub profile: Option<&'a Profile>,
+}
+
+impl OptimizerSettings<'_> {
+    /// `--unroll-limit` 을 주지 않았을 때: -O1 은 펼치지 않고, -O2 는 8번, -O3 은 16번까지
+    pub fn default_unroll_limit(level: u8) -> u32 {
+        match level {
+            0 | 1 => 0,
+            2 => 8,
+            _ => 16,
+        }
+    }
+}
+
+pub struct Optimizer;
+
+impl Optimizer {
+    pub fn optimize(program: &mut Program, settings: OptimizerSettings) {
+        // `@inline let NAME = <상수>` 바인딩은 이후 사용 지점에 값을 직접 넣고,
+        // `@inline let NAME = fn(...) { return <식> }` 은 호출 지점에 본문을 펼칩니다.
+        // 재귀 함수를 펼치면 끝나지 않으므로 호출 그래프의 순환에 속한 함수는 제외합니다.
+        // 프로필이 있으면 자주 불린 함수도 `@inline` 이 붙은 것처럼 펼칩니다.
+        let recursive = CallGraph::build(program).recursive_names();
+        let effects = EffectTable::infer(program);
+        let called = Self::assigned_in_functions(program);
+        let mut inliner = Inliner { constants: HashMap::new() };
+        let mut folder = ConstantFolder { effects: &effects, math: settings.math };
+
+        for stmt in program.statements.iter_mut() {
+            inliner.visit_statement(stmt);
+            folder.visit_statement(stmt);
+
+            let binding = match &*stmt {
+                Statement::Attributed { attributes, statement, .. } if has_attribute(attributes, "inline") => Some(statement.as_ref()),
+                hot @ Statement::LetStatement { value, .. } if Self::is_hot_function(value, settings.profile) => Some(hot),
+                _ => None,
+            };
+            if let Some(Statement::LetStatement { name, value, is_mutable: false, .. }) = binding {
+                if let Some(inlinable) = Self::inlinable(value, recursive.contains(name.as_str())) {
+                    inliner.constants.insert(*name, inlinable);
+                }
+            }
+        }
+
+        // 조건을 접은 뒤에 반복 횟수를 셉니다.
+        if settings.unroll_limit > 0 {
+            LoopUnroller { effects: &effects, limit: settings.unroll_limit, profile: settings.profile, called: &called }
+                .visit_program(program);
+        }
+        // 효과 분석이 순수하다고 증명한 코드만 재사용하거나 옮기거나 지웁니다.
+        if settings.level >= CSE_LEVEL {
+            CommonSubexpressions { effects: &effects, called: &called }.visit_program(program);
+        }
+        LoopHoister { effects: &effects, called: &called }.visit_program(program);
+        DeadCodeEliminator { effects: &effects }.visit_program(program);
+    }
+
+    fn inlinable(value: &Expression, recursive: bool) -> Option<Inlinable> {
+        match value {
+            Expression::Literal(_, val) => Some(Inlinable::Constant(val.clone())),
+            Expression::Function(_, parameters, body) if !recursive => {
+                let body = MacroExpander::template_body(body)?;
+                // 매개변수 외의 이름을 쓰는 본문은 호출 지점에서 다른 바인딩을 가리킬 수 있습니다.
+                let mut check = OnlyUses { parameters, ok: true };
+                check.visit_expression(body);
+                check.ok.then(|| Inlinable::Function { parameters: parameters.clone(), body: body.clone() })
+            }
+            _ => None,
+        }
+    }
+
+    fn is_hot_function(value: &Expression, profile: Option<&Profile>) -> bool {
+        match (value, profile) {
+            (Expression::Function(_, _, body), Some(profile)) => profile.is_hot_call(body.span()),
+            _ => false,
+        }
+    }
+
+    /// 문장이 이름을 참조할 수 있는지. eval() 은 어떤 이름이든 참조할 수 있으므로 참으로 봅니다.
+    fn statement_mentions(stmt: &Statement, name: Symbol) -> bool {
+        let mut mentions = Mentions { names: &[name], found: false };
+        mentions.visit_statement(stmt);
+        mentions.found
+    }
+
+    fn mentions_any(expr: &Expression, names: &[Symbol]) -> bool {
+        let mut mentions = Mentions { names, found: false };
+        mentions.visit_expression(expr);
+        mentions.found
+    }
+
+    /// 문장이 (중첩 블록 포함) 바인딩하거나 대입하는 이름들
+    fn bound_names(stmt: &Statement, names: &mut Vec<Symbol>) {
+        BoundNames { names }.visit_statement(stmt);
+    }
+
+    /// 함수 본문 안에서 대입하는 이름들. 함수는 호출한 쪽의 환경에서 이름을 찾으므로 이 이름들은 어떤 호출에서든 바뀔 수 있습니다.
+    fn assigned_in_functions(program: &Program) -> Vec<Symbol> {
+        struct Assigned {
+            depth: usize,
+            names: Vec<Symbol>,
+        }
+        impl Visitor for Assigned {
+            fn visit_expression(&mut self, expr: &Expression) {
+                match expr {
+                    Expression::Function(..) => {
+                        self.depth += 1;
+                        walk_expression(self, expr);
+                        self.depth -= 1;
+                    }
+                    Expression::Assign(_, _, name, _) if self.depth > 0 => {
+                        self.names.push(*name);
+                        walk_expression(self, expr);
+                    }
+                    _ => walk_expression(self, expr),
+                }
+            }
+        }
+        let mut assigned = Assigned { depth: 0, names: vec![] };
+        assigned.visit_program(program);
+        assigned.names
+    }
+}
+
+// ─── @inline 치환 ─────────────────────────────
+
+/// 인라인 상수 참조를 리터럴로, 인라인 함수 호출을 본문 식으로 바꿉니다.
+/// 같은 이름이 다시 바인딩되면 그 이후로는 치환하지 않습니다.
+struct Inliner {
+    constants: HashMap<Symbol, Inlinable>,
+}
+
+impl Inliner {
+    /// 블록·분기·함수 안에서 지운 이름이 바깥으로 새지 않도록 표를 되돌립니다.
+    fn scoped(&mut self, visit: impl FnOnce(&mut Self)) {
+        let saved = self.constants.clone();
+        visit(self);
+        self.constants = saved;
+    }
+}
+
+impl MutVisitor for Inliner {
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        if self.constants.is_empty() {
+            return;
+        }
+        match stmt {
+            Statement::LetStatement { name, .. } => {
+                let name = *name;
+                walk_statement_mut(self, stmt);
+                self.constants.remove(&name);
+            }
+            Statement::MacroDefinition { .. } => {}
+            Statement::ForInStatement { variable, iterable, body, .. } => {
+                self.visit_expression(iterable);
+                self.scoped(|inliner| {
+                    inliner.constants.remove(variable);
+                    inliner.visit_statement(body);
+                });
+            }
+            Statement::ExpressionStatement(_)
+            | Statement::DiscardStatement(_, _)
+            | Statement::ReturnStatement(_, _)
+            | Statement::YieldStatement(_, _)
+            | Statement::Attributed { .. } => walk_statement_mut(self, stmt),
+            _ => self.scoped(|inliner| walk_statement_mut(inliner, stmt)),
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression) {
+        match expr {
+            Expression::Identifier(span, name) => {
+                if let Some(Inlinable::Constant(val)) = self.constants.get(&*name) {
+                    *expr = Expression::Literal(*span, val.clone());
+                }
+            }
+            Expression::Function(_, parameters, body) => self.scoped(|inliner| {
+                for p in parameters.iter() {
+                    inliner.constants.remove(p);
+                }
+                inliner.visit_statement(body);
+            }),
+            Expression::Match(_, subject, arms) => {
+                self.visit_expression(subject);
+                for arm in arms.iter_mut() {
+                    self.scoped(|inliner| {
+                        for name in patterns::bindings(&arm.pattern) {
+                            inliner.constants.remove(&name);
+                        }
+                        if let Some(guard) = &mut arm.guard {
+                            inliner.visit_expression(guard);
+                        }
+                        inliner.visit_expression(&mut arm.body);
+                    });
+                }
+            }
+            Expression::Call(span, callee, args) => {
+                for arg in args.iter_mut() {
+                    self.visit_expression(arg);
+                }
+                let Expression::Identifier(_, name) = callee.as_ref() else {
+                    return;
+                };
+                let Some(Inlinable::Function { parameters, body }) = self.constants.get(name) else {
+                    return;
+                };
+                // 인자를 본문에 복사하므로 부수 효과가 없는 인자일 때만 펼칩니다.
+                let simple = args.iter().all(|a| matches!(a.as_ref(), Expression::Literal(..) | Expression::Identifier(..)));
+                if parameters.len() == args.len() && simple {
+                    let inlined = {
+                        let bindings: HashMap<&str, &Expression> =
+                            parameters.iter().map(|p| p.as_str()).zip(args.iter().map(|a| a.as_ref())).collect();
+                        substitute(body, &bindings)
+                    };
+                    *expr = group(*span, inlined);
+                    self.visit_expression(expr);
+                }
+            }
+            _ => walk_expression_mut(self, expr),
+        }
+    }
+}
+
+/// 본문의 식별자가 모두 매개변수(또는 호출 대상 이름)인지 확인합니다.
+struct OnlyUses<'a> {
+    parameters: &'a [Symbol],
+    ok: bool,
+}
+
+impl Visitor for OnlyUses<'_> {
+    fn visit_expression(&mut self, expr: &Expression) {
+        if !self.ok {
+            return;
+        }
+        match expr {
+            Expression::Identifier(_, name) => self.ok = self.parameters.contains(name),
+            Expression::Call(_, callee, args) if matches!(callee.as_ref(), Expression::Identifier(..)) => {
+                for arg in args {
+                    self.visit_expression(arg);
+                }
+            }
+            // 매개변수에 대입하는 본문을 펼치면 인자로 넘긴 바인딩이 바뀝니다.
+            Expression::Function(..) | Expression::Reflect(..) | Expression::Eval(..) | Expression::Assign(..) => self.ok = false,
+            _ => walk_expression(self, expr),
+        }
+    }
+}
+
+// ─── 상수 접기 ─────────────────────────────
+
+/// 자식부터 접은 뒤, 피연산자가 모두 리터럴인 연산과 조건이 리터럴인 삼항식을 값으로 바꿉니다.
+struct ConstantFolder<'a> {
+    effects: &'a EffectTable,
+    math: MathMode,
+}
+
+impl ConstantFolder<'_> {
+    /// 인자가 모두 리터럴인 순수 내장 함수 호출을 컴파일 시점에 계산합니다. (현재는 format)
+    fn evaluate_pure_builtin(&self, func: &Expression, args: &[Box<Expression>]) -> Option<Value> {
+        let Expression::Identifier(_, name) = func else {
+            return None;
+        };
+        if *name != "format" || !self.effects.is_builtin(name.as_str()) {
+            return None;
+        }
+        let mut values = args
+            .iter()
+            .map(|a| match a.as_ref() {
+                Expression::Literal(_, val) => Some(val.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<Value>>>()?;
+        if values.is_empty() {
+            return None;
+        }
+        let Value::String(template) = values.remove(0) else {
+            return None;
+        };
+        // 서식 오류는 실행 시점의 오류 메시지를 그대로 보여주도록 남겨 둡니다.
+        formatting::format_values(&template, &values).ok().map(|text| Value::String(text.into()))
+    }
+
+    /// 실행 시점과 같은 의미로 계산합니다. 0으로 나누기처럼 실행 시점에 패닉하는 식과
+    /// 현재 `MathMode` 가 리터럴로 받지 않는 결과는 접지 않습니다.
+    fn fold_constants(&self, op: &TokenKind, left: &Value, right: &Value) -> Option<Value> {
+        operators::binary(op, left, right).ok().filter(|value| self.math.accepts(value))
+    }
+}
+
+impl MutVisitor for ConstantFolder<'_> {
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        // 매크로 정의는 확장기에서 처리
+        if !matches!(stmt, Statement::MacroDefinition { .. }) {
+            walk_statement_mut(self, stmt);
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression) {
+        walk_expression_mut(self, expr);
+        let folded = match expr {
+            Expression::PrefixOperation(span, op, operand) => match operand.as_ref() {
+                Expression::Literal(_, value) => {
+                    operators::unary(op, value).ok().filter(|value| self.math.accepts(value)).map(|val| Expression::Literal(*span, val))
+                }
+                _ => None,
+            },
+            Expression::InfixOperation(span, op, left, right) => match (left.as_ref(), right.as_ref()) {
+                (Expression::Literal(_, l), Expression::Literal(_, r)) => {
+                    self.fold_constants(op, l, r).map(|val| Expression::Literal(*span, val))
+                }
+                _ => None,
+            },
+            Expression::Grouped(span, inner) => match inner.as_ref() {
+                Expression::Literal(_, val) => Some(Expression::Literal(*span, val.clone())),
+                _ => None,
+            },
+            Expression::Ternary(_, cond, then_expr, else_expr) => match cond.as_ref() {
+                Expression::Literal(_, Value::Boolean(b)) => {
+                    Some(if *b { then_expr.as_ref().clone() } else { else_expr.as_ref().clone() })
+                }
+                _ => None,
+            },
+            Expression::Call(span, func, args) => {
+                self.evaluate_pure_builtin(func, args).map(|val| Expression::Literal(*span, val))
+            }
+            _ => None,
+        };
+        if let Some(folded) = folded {
+            *expr = folded;
+        }
+    }
+}
+
+// ─── 루프 불변 코드 이동 (LICM) ─────────────────────────────
+
+/// 루프 본문의 `let name = <순수 식>` 중 반복마다 값이 같은 것을 루프 앞으로 옮깁니다.
+/// 바인딩이 루프 뒤로 새어 나가지 않도록 `{ let name = ...; while ... }` 블록으로 감쌉니다.
+struct LoopHoister<'a> {
+    effects: &'a EffectTable,
+    /// 함수 안에서 대입하는 이름: 루프 안의 어느 호출에서든 바뀔 수 있습니다.
+    called: &'a [Symbol],
+}
+
+impl LoopHoister<'_> {
+    /// 루프 본문에서 옮길 수 있는 let 문을 빼냅니다.
+    fn take_invariants(&self, loop_stmt: &mut Statement) -> Vec<Statement> {
+        // 루프가 바꾸는 이름: 본문과 for 초기화·증가 식에서 바인딩하거나 대입하는 이름, 함수 안에서 대입하는 이름
+        let (body, mut loop_names, header) = match loop_stmt {
+            Statement::WhileStatement { condition, body, .. } => {
+                let mut names = self.called.to_vec();
+                BoundNames { names: &mut names }.visit_expression(condition);
+                (body, names, vec![condition.as_ref().clone()])
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => {
+                let mut names = self.called.to_vec();
+                if let Some(init) = initializer {
+                    Optimizer::bound_names(init, &mut names);
+                }
+                for expr in condition.iter().chain(increment.iter()) {
+                    BoundNames { names: &mut names }.visit_expression(expr);
+                }
+                let header = condition.iter().chain(increment.iter()).map(|e| e.as_ref().clone()).collect();
+                (body, names, header)
+            }
+            _ => return vec![],
+        };
+        let Statement::BlockStatement { statements, .. } = body.as_mut() else {
+            return vec![];
+        };
+        for s in statements.iter() {
+            Optimizer::bound_names(s, &mut loop_names);
+        }
+
+        let mut hoisted = vec![];
+        let mut index = 0;
+        while index < statements.len() {
+            let movable = match &statements[index] {
+                Statement::LetStatement { name, value, is_mutable: false, .. } => {
+                    self.effects.expression(value) == Effect::Pure
+                        && !Optimizer::mentions_any(value, &loop_names)
+                        // 옮긴 바인딩이 바깥의 같은 이름을 가리던 곳에 영향을 주면 안 됩니다.
+                        && loop_names.iter().filter(|n| *n == name).count() == 1
+                        && !header.iter().any(|e| Optimizer::mentions_any(e, core::slice::from_ref(name)))
+                        && !statements[..index].iter().any(|s| Optimizer::statement_mentions(s, *name))
+                }
+                _ => false,
+            };
+            if movable {
+                hoisted.push(statements.remove(index));
+            } else {
+                index += 1;
+            }
+        }
+        hoisted
+    }
+}
+
+impl MutVisitor for LoopHoister<'_> {
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        if matches!(stmt, Statement::MacroDefinition { .. }) {
+            return;
+        }
+        // 안쪽 루프부터 옮겨야 바깥 루프까지 여러 단계로 끌어올릴 수 있습니다.
+        walk_statement_mut(self, stmt);
+        if !matches!(stmt, Statement::WhileStatement { .. } | Statement::ForStatement { .. }) {
+            return;
+        }
+        let hoisted = self.take_invariants(stmt);
+        if !hoisted.is_empty() {
+            let span = stmt.span();
+            let loop_stmt = core::mem::replace(stmt, Statement::ExpressionStatement(Box::new(
+                Expression::Literal(Span { start: 0, end: 0 }, Value::Null),
+            )));
+            let mut statements = hoisted;
+            statements.push(loop_stmt);
+            *stmt = Statement::BlockStatement { statements, span };
+        }
+    }
+}
+
+// ─── 루프 펼치기 ─────────────────────────────
+
+/// 펼친 루프 하나가 차지할 수 있는 최대 문장 수 (반복 횟수 × 본문 문장 수)
+pub const UNROLL_BUDGET: usize = 64;
+
+/// 반복 횟수를 증명할 수 있는 루프를 본문 복사본으로 바꿉니다.
+///
+///   조건이 리터럴 false 인 while/for       지웁니다 (for 의 초기화에 효과가 있으면 남김)
+///   for (let i = 정수; i <비교> 정수; 증가)   반복마다 `{ let i = 값; 본문 }` 을 차례로 씁니다
+///
+/// 반복 횟수가 `limit` 를 넘거나 펼친 크기가 `UNROLL_BUDGET` 을 넘으면 그대로 둡니다. 본문이 카운터를 다시
+/// 바인딩하거나 대입하거나 eval 을 쓰면 펼치지 않고, 함수 안에서 대입하는 이름이 카운터여도 펼치지 않습니다.
+/// 카운터를 바꾸는 증가 식은 `counter_step` 이 알아봅니다.
+struct LoopUnroller<'a> {
+    effects: &'a EffectTable,
+    limit: u32,
+    profile: Option<&'a Profile>,
+    called: &'a [Symbol],
+}
+
+impl LoopUnroller<'_> {
+    /// 이 본문을 가진 루프를 펼칠 반복 횟수 한도. 프로필에서 실행되지 않은 루프는 0 입니다.
+    fn limit_for(&self, body: &Statement) -> u32 {
+        let Some(profile) = self.profile else {
+            return self.limit;
+        };
+        match profile.loop_counts(body.span()) {
+            None => 0,
+            Some(counts) if counts.iterations >= HOT_ITERATIONS => self.limit.saturating_mul(2),
+            Some(_) => self.limit,
+        }
+    }
+
+    fn unrolled(&self, loop_stmt: &Statement) -> Option<Statement> {
+        let span = loop_stmt.span();
+        match loop_stmt {
+            Statement::WhileStatement { condition, .. } if is_false(condition) => Some(empty_block(span)),
+            Statement::ForStatement { initializer, condition: Some(condition), .. } if is_false(condition) => {
+                Some(match initializer {
+                    Some(init) if self.effects.statement(init) != Effect::Pure => {
+                        Statement::BlockStatement { statements: vec![init.as_ref().clone()], span }
+                    }
+                    _ => empty_block(span),
+                })
+            }
+            Statement::ForStatement {
+                initializer: Some(init),
+                condition: Some(condition),
+                increment: Some(increment),
+                body,
+                ..
+            } => {
+                let Statement::LetStatement { name, value, is_mutable, span: let_span, .. } = init.as_ref() else {
+                    return None;
+                };
+                let values = self.trip_values(*name, value, condition, increment, self.limit_for(body))?;
+                let mut bound = vec![];
+                Optimizer::bound_names(body, &mut bound);
+                if bound.contains(name)
+                    || self.called.contains(name)
+                    || has_eval(body)
+                    || values.len() * statement_count(body) > UNROLL_BUDGET
+                {
+                    return None;
+                }
+                let statements = values
+                    .into_iter()
+                    .map(|counter| {
+                        let binding = Statement::LetStatement {
+                            name: *name,
+                            value: Box::new(Expression::Literal(*let_span, Value::Integer(counter))),
+                            type_annotation: None,
+                            is_mutable: *is_mutable,
+                            span: *let_span,
+                        };
+                        let iteration = vec![binding, body.as_ref().clone()];
+                        Statement::BlockStatement { statements: iteration, span: body.span() }
+                    })
+                    .collect();
+                Some(Statement::BlockStatement { statements, span })
+            }
+            _ => None,
+        }
+    }
+
+    /// 조건이 참인 동안의 카운터 값들. 증명할 수 없거나 `limit` 번을 넘으면 None 입니다.
+    fn trip_values(&self, counter: Symbol, start: &Expression, condition: &Expression, increment: &Expression, limit: u32) -> Option<Vec<i64>> {
+        let Expression::Literal(_, Value::Integer(mut current)) = start else {
+            return None;
+        };
+        let step = counter_step(increment, counter)?;
+        let Expression::InfixOperation(_, op, left, right) = condition else {
+            return None;
+        };
+        let mut values = vec![];
+        loop {
+            let operand = |side: &Expression| match side {
+                Expression::Identifier(_, name) if *name == counter => Some(Value::Integer(current)),
+                Expression::Literal(_, value @ Value::Integer(_)) => Some(value.clone()),
+                _ => None,
+            };
+            match operators::binary(op, &operand(left)?, &operand(right)?).ok()? {
+                Value::Boolean(true) if values.len() < limit as usize => {
+                    values.push(current);
+                    current = current.checked_add(step)?;
+                }
+                Value::Boolean(false) => return Some(values),
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// 증가 식이 카운터에 더하는 값: `i += k`, `i -= k`, `i = i + k`, `i = k + i`, `i = i - k` (k 는 정수 리터럴)
+fn counter_step(increment: &Expression, counter: Symbol) -> Option<i64> {
+    let literal = |expr: &Expression| match expr {
+        Expression::Literal(_, Value::Integer(k)) => Some(*k),
+        _ => None,
+    };
+    let is_counter = |expr: &Expression| matches!(expr, Expression::Identifier(_, name) if *name == counter);
+    match increment {
+        Expression::Grouped(_, inner) => counter_step(inner, counter),
+        Expression::Assign(_, op, name, value) if *name == counter => match (op, value.as_ref()) {
+            (TokenKind::PlusAssign, value) => literal(value),
+            (TokenKind::MinusAssign, value) => literal(value)?.checked_neg(),
+            (TokenKind::Assign, Expression::InfixOperation(_, TokenKind::Plus, left, right)) => {
+                match (is_counter(left), is_counter(right)) {
+                    (true, false) => literal(right),
+                    (false, true) => literal(left),
+                    _ => None,
+                }
+            }
+            (TokenKind::Assign, Expression::InfixOperation(_, TokenKind::Minus, left, right)) if is_counter(left) => {
+                literal(right)?.checked_neg()
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl MutVisitor for LoopUnroller<'_> {
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        if matches!(stmt, Statement::MacroDefinition { .. }) {
+            return;
+        }
+        // 안쪽 루프부터 펼쳐야 바깥 루프의 크기를 펼친 뒤 기준으로 잽니다.
+        walk_statement_mut(self, stmt);
+        if let Some(unrolled) = self.unrolled(stmt) {
+            *stmt = unrolled;
+        }
+    }
+}
+
+fn is_false(expr: &Expression) -> bool {
+    match expr {
+        Expression::Literal(_, Value::Boolean(false)) => true,
+        Expression::Grouped(_, inner) => is_false(inner),
+        _ => false,
+    }
+}
+
+fn empty_block(span: Span) -> Statement {
+    Statement::BlockStatement { statements: vec![], span }
+}
+
+fn has_eval(stmt: &Statement) -> bool {
+    let mut mentions = Mentions { names: &[], found: false };
+    mentions.visit_statement(stmt);
+    mentions.found
+}
+
+/// 중첩 블록을 포함한 문장 수
+fn statement_count(stmt: &Statement) -> usize {
+    struct Count(usize);
+    impl Visitor for Count {
+        fn visit_statement(&mut self, stmt: &Statement) {
+            self.0 += 1;
+            walk_statement(self, stmt);
+        }
+    }
+    let mut count = Count(0);
+    count.visit_statement(stmt);
+    count.0
+}
+
+// ─── 죽은 코드 제거 (DCE) ─────────────────────────────
+
+/// 값이 버려지는 순수 식 문장과, 이후 어디서도 쓰이지 않는 순수 let 바인딩을 지웁니다.
+/// 어트리뷰트가 붙은 문장(`@test`, `@inline` 등)은 그대로 둡니다.
+struct DeadCodeEliminator<'a> {
+    effects: &'a EffectTable,
+}
+
+impl DeadCodeEliminator<'_> {
+    fn prune(&self, statements: &mut Vec<Statement>) {
+        // 뒤에서부터 지워야 `let a = 1; let b = a` 에서 b 가 지워진 뒤 a 도 지울 수 있습니다.
+        let mut index = statements.len();
+        while index > 0 {
+            index -= 1;
+            let dead = match &statements[index] {
+                Statement::ExpressionStatement(expr) | Statement::DiscardStatement(_, expr) => {
+                    self.effects.expression(expr) == Effect::Pure
+                }
+                Statement::LetStatement { name, value, .. } => {
+                    self.effects.expression(value) == Effect::Pure
+                        && !statements[index + 1..].iter().any(|s| Optimizer::statement_mentions(s, *name))
+                }
+                _ => false,
+            };
+            if dead {
+                statements.remove(index);
+            }
+        }
+    }
+}
+
+impl MutVisitor for DeadCodeEliminator<'_> {
+    fn visit_program(&mut self, program: &mut Program) {
+        for stmt in program.statements.iter_mut() {
+            self.visit_statement(stmt);
+        }
+        self.prune(&mut program.statements);
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        // 매크로 본문의 마지막 식은 확장 결과이므로 지우면 안 됩니다.
+        if matches!(stmt, Statement::MacroDefinition { .. }) {
+            return;
+        }
+        walk_statement_mut(self, stmt);
+        if let Statement::BlockStatement { statements, .. } = stmt {
+            self.prune(statements);
+        }
+    }
+}
+
+// ─── 공통 부분식 제거 (CSE) ─────────────────────────────
+
+/// 불변 `let t = <순수 식>` 뒤에서 같은 식을 다시 계산하는 곳을 `t` 로 바꿉니다.
+/// 같은 문장 목록의 뒤쪽 문장과 그 안쪽(중첩 블록, 함수 본문)만 바꾸며, 식이 쓰는 이름이나 `t` 가
+/// 다시 바인딩되거나 대입되면 그 뒤로는 쓰지 않습니다. eval 은 어떤 이름이든 바인딩할 수 있으므로 모두 잊습니다.
+/// 함수 안에서 대입하는 이름을 쓰는 식은 어느 호출 뒤에든 값이 달라질 수 있어 기억하지 않습니다.
+struct CommonSubexpressions<'a> {
+    effects: &'a EffectTable,
+    called: &'a [Symbol],
+}
+
+/// 계산해 둔 식: 구조 키, 식이 쓰는 이름, 값을 담은 바인딩
+struct Available {
+    key: String,
+    names: Vec<Symbol>,
+    binding: Symbol,
+}
+
+impl CommonSubexpressions<'_> {
+    fn eliminate(&mut self, statements: &mut [Statement]) {
+        let mut available: Vec<Available> = vec![];
+        for stmt in statements.iter_mut() {
+            if !available.is_empty() {
+                Reuse { available: &available }.visit_statement(stmt);
+            }
+            self.visit_statement(stmt);
+
+            if has_eval(stmt) {
+                available.clear();
+                continue;
+            }
+            let mut bound = vec![];
+            Optimizer::bound_names(stmt, &mut bound);
+            available.retain(|a| !bound.contains(&a.binding) && !a.names.iter().any(|n| bound.contains(n)));
+            if let Statement::LetStatement { name, value, is_mutable: false, .. } = &*stmt {
+                let mut names = vec![];
+                let candidate = !matches!(value.as_ref(), Expression::Literal(..) | Expression::Identifier(..))
+                    && self.effects.expression(value) == Effect::Pure
+                    && !Optimizer::mentions_any(value, self.called);
+                if let (true, Some(key)) = (candidate, structural_key(value, &mut names)) {
+                    if !names.contains(name) {
+                        available.push(Available { key, names, binding: *name });
+                    }
+                }
+            }
+        }
+    }
+
+}
+
+impl MutVisitor for CommonSubexpressions<'_> {
+    fn visit_program(&mut self, program: &mut Program) {
+        self.eliminate(&mut program.statements);
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        match stmt {
+            Statement::MacroDefinition { .. } => {}
+            Statement::BlockStatement { statements, .. } => self.eliminate(statements),
+            _ => walk_statement_mut(self, stmt),
+        }
+    }
+}
+
+/// 계산해 둔 식과 구조가 같은 부분식을 그 바인딩으로 바꿉니다.
+struct Reuse<'a> {
+    available: &'a [Available],
+}
+
+impl Reuse<'_> {
+    /// 안쪽에서 다시 바인딩되는 이름에 기대는 항목을 뺀 목록
+    fn shadowed(&self, bound: &[Symbol]) -> Vec<Available> {
+        self.available
+            .iter()
+            .filter(|a| !bound.contains(&a.binding) && !a.names.iter().any(|n| bound.contains(n)))
+            .map(|a| Available { key: a.key.clone(), names: a.names.clone(), binding: a.binding })
+            .collect()
+    }
+}
+
+impl MutVisitor for Reuse<'_> {
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        if matches!(stmt, Statement::MacroDefinition { .. }) {
+            return;
+        }
+        let mut bound = vec![];
+        Optimizer::bound_names(stmt, &mut bound);
+        if bound.is_empty() {
+            return walk_statement_mut(self, stmt);
+        }
+        let available = self.shadowed(&bound);
+        walk_statement_mut(&mut Reuse { available: &available }, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression) {
+        if let Expression::Function(_, parameters, body) = expr {
+            let mut bound = parameters.clone();
+            Optimizer::bound_names(body, &mut bound);
+            let available = self.shadowed(&bound);
+            return Reuse { available: &available }.visit_statement(body);
+        }
+        if let Expression::Match(_, subject, arms) = expr {
+            self.visit_expression(subject);
+            for arm in arms.iter_mut() {
+                let available = self.shadowed(&patterns::bindings(&arm.pattern));
+                let mut reuse = Reuse { available: &available };
+                if let Some(guard) = &mut arm.guard {
+                    reuse.visit_expression(guard);
+                }
+                reuse.visit_expression(&mut arm.body);
+            }
+            return;
+        }
+        let mut names = vec![];
+        if let Some(key) = structural_key(expr, &mut names) {
+            if let Some(found) = self.available.iter().find(|a| a.key == key) {
+                *expr = Expression::Identifier(expr.span(), found.binding);
+                return;
+            }
+        }
+        walk_expression_mut(self, expr);
+    }
+}
+
+/// 괄호와 위치를 뺀 식의 구조. 연산자, 이름, 리터럴, 호출만 있는 식에만 있습니다.
+fn structural_key(expr: &Expression, names: &mut Vec<Symbol>) -> Option<String> {
+    Some(match expr {
+        Expression::Literal(_, value) => format!("{:?}", value),
+        Expression::Identifier(_, name) => {
+            names.push(*name);
+            name.to_string()
+        }
+        Expression::Grouped(_, inner) => return structural_key(inner, names),
+        Expression::PrefixOperation(_, op, operand) => format!("({:?} {})", op, structural_key(operand, names)?),
+        Expression::InfixOperation(_, op, left, right) => {
+            format!("({:?} {} {})", op, structural_key(left, names)?, structural_key(right, names)?)
+        }
+        Expression::Index(_, target, index) => format!("([] {} {})", structural_key(target, names)?, structural_key(index, names)?),
+        Expression::Member(_, target, name) => format!("(. {} {})", structural_key(target, names)?, name),
+        Expression::Call(_, callee, args) => {
+            let mut key = format!("(call {}", structural_key(callee, names)?);
+            for arg in args {
+                key.push(' ');
+                key.push_str(&structural_key(arg, names)?);
+            }
+            key.push(')');
+            key
+        }
+        // `a.f(b)` 는 `f(a, b)` 와 같은 호출입니다.
+        Expression::MethodCall(_, target, name, args) => {
+            names.push(*name);
+            let mut key = format!("(call {} {}", name, structural_key(target, names)?);
+            for arg in args {
+                key.push(' ');
+                key.push_str(&structural_key(arg, names)?);
+            }
+            key.push(')');
+            key
+        }
+        _ => return None,
+    })
+}
+
+// ─── 이름 참조 검사 ─────────────────────────────
+
+/// 매크로 본문은 호출 지점에서 펼쳐지므로 그 안의 이름도 참조로 봅니다.
+struct Mentions<'a> {
+    names: &'a [Symbol],
+    found: bool,
+}
+
+impl Visitor for Mentions<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        if !self.found {
+            walk_statement(self, stmt);
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        if self.found {
+            return;
+        }
+        match expr {
+            Expression::Identifier(_, id) => self.found = self.names.contains(id),
+            Expression::MacroCall(_, id, _) if self.names.iter().any(|n| n == id.as_str()) => self.found = true,
+            Expression::MethodCall(_, _, id, _) | Expression::Assign(_, _, id, _) if self.names.contains(id) => self.found = true,
+            Expression::Eval(..) => self.found = true,
+            _ => walk_expression(self, expr),
+        }
+    }
+}
+
+/// let 과 매크로 정의가 바인딩하는 이름과 대입하는 이름을 모읍니다. 함수 본문은 들여다보지 않습니다.
+/// (함수 안의 대입은 `Optimizer::assigned_in_functions`)
+struct BoundNames<'a> {
+    names: &'a mut Vec<Symbol>,
+}
+
+impl Visitor for BoundNames<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::LetStatement { name, .. } => {
+                self.names.push(*name);
+                walk_statement(self, stmt);
+            }
+            Statement::MacroDefinition { name, .. } => self.names.push(Symbol::intern(name)),
+            Statement::ForInStatement { variable, .. } => {
+                self.names.push(*variable);
+                walk_statement(self, stmt);
+            }
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Function(..) => {}
+            Expression::Assign(_, _, name, _) => {
+                self.names.push(*name);
+                walk_expression(self, expr);
+            }
+            _ => walk_expression(self, expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_printer;
+    use crate::bytecode;
+    use crate::engine::run_to_exit;
+    use crate::lexer_service::LexerService;
+    use crate::parser_service::ParserService;
+
+    /// `return <식>;` 을 접어 다시 소스로 적은 프로그램과, 접은 값 (리터럴이 되었을 때)
+    fn fold(expression: &str, math: MathMode) -> (String, Option<Value>) {
+        let mut program = ParserService::new(LexerService::new(&format!("return {};", expression))).parse_program();
+        Optimizer::optimize(&mut program, OptimizerSettings { level: 1, math, unroll_limit: 0, profile: None });
+        let folded = match &program.statements[0] {
+            Statement::ReturnStatement(_, value) => match value.as_ref() {
+                Expression::Literal(_, value) => Some(value.clone()),
+                _ => None,
+            },
+            other => panic!("{:?}", other),
+        };
+        (ast_printer::print_program(&program), folded)
+    }
+
+    /// NaN 은 비트 대신 NaN 인지만 봅니다. (실행 시점 NaN 의 부호·페이로드는 정해져 있지 않습니다)
+    fn same(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Float(x), Value::Float(y)) if x.is_nan() => y.is_nan(),
+            (Value::Float(x), Value::Float(y)) => x.to_bits() == y.to_bits(),
+            _ => format!("{:?}", a) == format!("{:?}", b),
+        }
+    }
+
+    const EXPRESSIONS: &[&str] = &[
+        "0.1 + 0.2",
+        "1.0 / 3.0",
+        "2.5 * 4.0 - 0.75",
+        "-0.0 * 1.0",
+        "0.0 - 0.0",
+        "-(0.0)",
+        "1.0 / 0.0",
+        "0.0 / 0.0",
+        "0.0 == -0.0",
+        "(0.0 / 0.0) == (0.0 / 0.0)",
+        "7 / 2",
+        "-7 % 3",
+        "1 + 2 * 3",
+        "(1.5 < 2.5) ? 1.5 : 2.5",
+    ];
+
+    #[test]
+    fn folded_programs_match_the_interpreter() {
+        for math in [MathMode::Strict, MathMode::Relaxed] {
+            for &expression in EXPRESSIONS {
+                let expected = run_to_exit(&format!("return {};", expression));
+                let (printed, folded) = fold(expression, math);
+                // Strict 가 접은 리터럴은 소스로 적었다가 다시 읽어도 같은 값입니다. Relaxed 의 NaN·무한대는 표기가 없습니다.
+                if math == MathMode::Strict {
+                    let reread = run_to_exit(&printed);
+                    assert!(same(&expected, &reread), "{}: {:?} != {:?} ({})", expression, expected, reread, printed);
+                }
+                if let Some(folded) = folded {
+                    assert!(same(&expected, &folded), "{:?} {}: {:?} != {:?}", math, expression, expected, folded);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn strict_math_only_folds_values_with_a_literal_spelling() {
+        assert!(matches!(fold("0.1 + 0.2", MathMode::Strict).1, Some(Value::Float(f)) if f == 0.1 + 0.2));
+        assert!(matches!(fold("-0.0 * 1.0", MathMode::Strict).1, Some(Value::Float(f)) if f.to_bits() == (-0.0f64).to_bits()));
+        assert!(fold("0.0 / 0.0", MathMode::Strict).1.is_none());
+        assert!(fold("1.0 / 0.0", MathMode::Strict).1.is_none());
+        assert!(matches!(fold("0.0 / 0.0", MathMode::Relaxed).1, Some(Value::Float(f)) if f.is_nan()));
+        assert!(matches!(fold("1.0 / 0.0", MathMode::Relaxed).1, Some(Value::Float(f)) if f == f64::INFINITY));
+        // 비교는 결과가 불리언이므로 NaN 피연산자여도 접습니다.
+        assert!(matches!(fold("(0.0 / 0.0) == (0.0 / 0.0)", MathMode::Relaxed).1, Some(Value::Boolean(false))));
+    }
+
+    fn optimized(source: &str, level: u8) -> Program {
+        let mut program = ParserService::new(LexerService::new(source)).parse_program();
+        Optimizer::optimize(&mut program, OptimizerSettings { level, math: MathMode::Strict, unroll_limit: 0, profile: None });
+        program
+    }
+
+    /// 첫 문장 `let f = fn(...) { ... }` 의 본문을 옮긴 바이트코드 명령어 수
+    fn instructions(program: &Program) -> usize {
+        match &program.statements[0] {
+            Statement::LetStatement { value, .. } => match value.as_ref() {
+                Expression::Function(_, parameters, body) => bytecode::compile(parameters, body).unwrap().code.len(),
+                other => panic!("{:?}", other),
+            },
+            other => panic!("{:?}", other),
+        }
+    }
+
+    const CSE_FIXTURES: &[&str] = &[
+        "let f = fn(a, b) { let s = a * b + 1; let t = a * b + 1; return (a * b + 1) * t + s; };\nreturn f(3, 4);",
+        "let f = fn(x, y) { let d = (x - y) * (x - y); if (d > 0) { return d + (x - y) * (x - y); } return -((x - y) * (x - y)); };\nreturn f(7, 2);",
+        "let f = fn(n) { let h = n / 2 + n % 3; return (n / 2 + n % 3 > 4) ? (n / 2 + n % 3) * 2 : h; };\nreturn f(11);",
+    ];
+
+    #[test]
+    fn cse_reduces_instruction_counts() {
+        for &source in CSE_FIXTURES {
+            let (before, after) = (optimized(source, CSE_LEVEL - 1), optimized(source, CSE_LEVEL));
+            assert!(instructions(&after) < instructions(&before), "{}: {} >= {}", source, instructions(&after), instructions(&before));
+            let expected = run_to_exit(source);
+            for program in [&before, &after] {
+                let result = run_to_exit(&ast_printer::print_program(program));
+                assert!(same(&expected, &result), "{}: {:?} != {:?}", source, expected, result);
+            }
+        }
+    }
+
+    #[test]
+    fn cse_keeps_recomputing_after_rebinding_or_effects() {
+        for source in [
+            // 식이 쓰는 이름을 다시 바인딩하면 앞서 계산한 값은 낡은 값입니다.
+            "let f = fn(a) { let s = a * 2; let a = a + 1; return a * 2 + s; };\nreturn f(5);",
+            // 효과가 있는 호출은 다시 불러야 합니다.
+            "let f = fn(a) { let s = print(a) + a; return print(a) + a + s; };\nreturn f(5);",
+        ] {
+            let (before, after) = (optimized(source, CSE_LEVEL - 1), optimized(source, CSE_LEVEL));
+            assert_eq!(instructions(&after), instructions(&before), "{}", source);
+        }
+    }
+}
@@ -1,281 +1,1256 @@
-use crate::data_structures::*;
-use crate::lexer_service::LexerService;
-
-pub struct ParserService<'a> {
-    lexer: LexerService<'a>,
-    current: Token,
-    peek: Token,
-}
-
-impl<'a> ParserService<'a> {
-    pub fn new(mut lexer: LexerService<'a>) -> Self {
-        let mut parser = Self {
-            lexer,
-            current: Token { kind: TokenKind::Eof, span: Span { start: 0, end: 0 } },
-            peek: Token { kind: TokenKind::Eof, span: Span { start: 0, end: 0 } },
-        };
-        parser.advance();
-        parser.advance();
-        parser
-    }
-
-    fn advance(&mut self) {
-        let next = self.lexer.next_token();
-        self.current = std::mem::replace(&mut self.peek, next);
-    }
-
-    pub fn parse_program(&mut self) -> Program {
-        let mut statements = vec![];
-        while !matches!(self.current.kind, TokenKind::Eof) {
-            if let Some(stmt) = self.parse_statement() {
-                statements.push(Box::new(stmt));
-            } else {
-                self.advance();
-            }
-        }
-        Program {
-            root_id: 0,
-            statements,
-            span: Span { start: 0, end: 0 },
-        }
-    }
-
-    fn parse_statement(&mut self) -> Option<Statement> {
-        match self.current.kind {
-            TokenKind::Let => self.parse_let_statement(),
-            TokenKind::Return => self.parse_return_statement(),
-            TokenKind::If => self.parse_if_statement(),
-            TokenKind::For => self.parse_for_statement(),
-            TokenKind::Macro => self.parse_macro_definition(),
-            TokenKind::LBrace => self.parse_block_statement(),
-            _ => self.parse_expression_statement(),
-        }
-    }
-
-    fn parse_let_statement(&mut self) -> Option<Statement> {
-        self.advance(); // consume 'let'
-        let is_mutable = if matches!(self.current.kind, TokenKind::Mut) {
-            self.advance();
-            true
-        } else {
-            false
-        };
-
-        let name = if let TokenKind::Identifier(id) = &self.current.kind {
-            id.clone()
-        } else {
-            return None;
-        };
-        self.advance();
-
-        let type_annotation = if matches!(self.current.kind, TokenKind::Colon) {
-            self.advance();
-            self.parse_type_annotation()
-        } else {
-            None
-        };
-
-        if !matches!(self.current.kind, TokenKind::Assign) {
-            return None;
-        }
-        self.advance();
-
-        let value = self.parse_expression()?;
-        Some(Statement::LetStatement {
-            name,
-            value: Box::new(value),
-            type_annotation,
-            is_mutable,
-        })
-    }
-
-    fn parse_return_statement(&mut self) -> Option<Statement> {
-        self.advance(); // consume 'return'
-        let expr = self.parse_expression()?;
-        Some(Statement::ReturnStatement(Box::new(expr)))
-    }
-
-    fn parse_if_statement(&mut self) -> Option<Statement> {
-        self.advance(); // consume 'if'
-        let condition = self.parse_expression()?;
-        let then_branch = self.parse_statement()?;
-        let else_branch = if matches!(self.current.kind, TokenKind::Else) {
-            self.advance();
-            Some(Box::new(self.parse_statement()?))
-        } else {
-            None
-        };
-        Some(Statement::IfStatement {
-            condition: Box::new(condition),
-            then_branch: Box::new(then_branch),
-            else_branch,
-        })
-    }
-
-    fn parse_for_statement(&mut self) -> Option<Statement> {
-        self.advance(); // consume 'for'
-        let initializer = if !matches!(self.current.kind, TokenKind::Semicolon) {
-            Some(Box::new(self.parse_statement()?))
-        } else {
-            self.advance();
-            None
-        };
-
-        let condition = if !matches!(self.current.kind, TokenKind::Semicolon) {
-            Some(Box::new(self.parse_expression()?))
-        } else {
-            self.advance();
-            None
-        };
-
-        let increment = if !matches!(self.current.kind, TokenKind::LBrace) {
-            Some(Box::new(self.parse_expression()?))
-        } else {
-            None
-        };
-
-        let body = self.parse_statement()?;
-        Some(Statement::ForStatement {
-            initializer,
-            condition,
-            increment,
-            body: Box::new(body),
-        })
-    }
-
-    fn parse_macro_definition(&mut self) -> Option<Statement> {
-        self.advance(); // consume 'macro'
-        let name = if let TokenKind::Identifier(id) = &self.current.kind {
-            id.clone()
-        } else {
-            return None;
-        };
-        self.advance();
-
-        let mut params = vec![];
-        if matches!(self.current.kind, TokenKind::LParen) {
-            self.advance();
-            while !matches!(self.current.kind, TokenKind::RParen) {
-                if let TokenKind::Identifier(id) = &self.current.kind {
-                    params.push(id.clone());
-                    self.advance();
-                    if matches!(self.current.kind, TokenKind::Comma) {
-                        self.advance();
-                    }
-                } else {
-                    break;
-                }
-            }
-            self.advance(); // consume ')'
-        }
-
-        let body = self.parse_block_statement()?;
-        Some(Statement::MacroDefinition {
-            name,
-            parameters: params,
-            body: Box::new(body),
-        })
-    }
-
-    fn parse_block_statement(&mut self) -> Option<Statement> {
-        self.advance(); // consume '{'
-        let mut statements = vec![];
-        while !matches!(self.current.kind, TokenKind::RBrace) {
-            if let Some(stmt) = self.parse_statement() {
-                statements.push(Box::new(stmt));
-            } else {
-                self.advance();
-            }
-        }
-        self.advance(); // consume '}'
-        Some(Statement::BlockStatement {
-            statements,
-            span: Span { start: 0, end: 0 },
-        })
-    }
-
-    fn parse_expression_statement(&mut self) -> Option<Statement> {
-        let expr = self.parse_expression()?;
-        Some(Statement::ExpressionStatement(Box::new(expr)))
-    }
-
-    fn parse_expression(&mut self) -> Option<Expression> {
-        let start = self.current.span.start;
-
-        match &self.current.kind {
-            TokenKind::Eval => {
-                self.advance();
-                let inner = self.parse_expression()?;
-                Some(Expression::Eval(Span { start, end: self.current.span.end }, Box::new(inner)))
-            }
-            TokenKind::Reflect => {
-                self.advance();
-                let inner = self.parse_expression()?;
-                Some(Expression::Reflect(Span { start, end: self.current.span.end }, Box::new(inner)))
-            }
-            TokenKind::TypeOf => {
-                self.advance();
-                let inner = self.parse_expression()?;
-                Some(Expression::TypeOf(Span { start, end: self.current.span.end }, Box::new(inner)))
-            }
-            TokenKind::Identifier(name) => {
-                let id = name.clone();
-                self.advance();
-                if matches!(self.current.kind, TokenKind::LParen) {
-                    self.advance();
-                    let mut args = vec![];
-                    while !matches!(self.current.kind, TokenKind::RParen) {
-                        let arg = self.parse_expression()?;
-                        args.push(Box::new(arg));
-                        if matches!(self.current.kind, TokenKind::Comma) {
-                            self.advance();
-                        }
-                    }
-                    self.advance(); // consume ')'
-                    Some(Expression::MacroCall(Span { start, end: self.current.span.end }, id, args))
-                } else {
-                    Some(Expression::Identifier(Span { start, end: self.current.span.end }, id))
-                }
-            }
-            TokenKind::IntegerLiteral(val) => {
-                let v = Value::Integer(*val);
-                self.advance();
-                Some(Expression::Literal(Span { start, end: self.current.span.end }, v))
-            }
-            TokenKind::FloatLiteral(s) => {
-                let v = Value::Float(s.parse().unwrap_or(0.0));
-                self.advance();
-                Some(Expression::Literal(Span { start, end: self.current.span.end }, v))
-            }
-            TokenKind::BooleanLiteral(b) => {
-                let v = Value::Boolean(*b);
-                self.advance();
-                Some(Expression::Literal(Span { start, end: self.current.span.end }, v))
-            }
-            TokenKind::LParen => {
-                self.advance();
-                let inner = self.parse_expression()?;
-                if matches!(self.current.kind, TokenKind::RParen) {
-                    self.advance();
-                    Some(Expression::Grouped(Span { start, end: self.current.span.end }, Box::new(inner)))
-                } else {
-                    None
-                }
-            }
-            _ => None
-        }
-    }
-
-        fn parse_type_annotation(&mut self) -> Option<TypeAnnotation> {
-        match &self.current.kind {
-            TokenKind::Identifier(name) => Some(TypeAnnotation::Custom(name.clone())),
-            TokenKind::Int => Some(TypeAnnotation::Int),
-            TokenKind::Float => Some(TypeAnnotation::Float),
-            TokenKind::Bool => Some(TypeAnnotation::Bool),
-            TokenKind::String => Some(TypeAnnotation::String),
-            TokenKind::Void => Some(TypeAnnotation::Void),
-            TokenKind::Any => Some(TypeAnnotation::Any),
-            _ => None,
-        }
-    }
-}
+use crate::compat::*;
+use crate::data_structures::*;
+use crate::error_codes;
+use crate::interner::Symbol;
+use crate::lexer_service::{Comment, LexerService};
+use crate::precedence::{Associativity, InfixAction, PrecedenceTable};
+
+/// 괄호 목록(호출 인자, 매개변수, 어트리뷰트 인자) 하나에 들어갈 수 있는 최대 요소 수
+pub const MAX_LIST_ITEMS: usize = 255;
+
+/// 식과 문장을 겹쳐 쓸 수 있는 최대 깊이. 디버그 빌드가 스레드 기본 스택(2MB)을 다 쓰기 전에 E0110 으로 멈춥니다.
+pub const MAX_NESTING: usize = 128;
+
+pub struct ParserService<'a> {
+    lexer: LexerService<'a>,
+    current: Token<'a>,
+    peek: Token<'a>,
+    /// 바로 앞에서 소비한 토큰의 끝 위치
+    previous_end: usize,
+    operators: &'a PrecedenceTable,
+    /// 구문 오류 (E0110). 오류가 나도 파싱은 복구해서 계속합니다.
+    diagnostics: Vec<Diagnostic>,
+    /// 지금까지 선언된 필드 없는 enum 변형. 패턴 자리의 이 이름들은 바인딩이 아니라 변형입니다.
+    enum_variants: Vec<Symbol>,
+    /// 지금 겹쳐 읽고 있는 식·문장의 깊이 (`nested`)
+    depth: usize,
+    /// `MAX_NESTING` 을 넘었는지. 넘은 뒤에는 파일 끝까지 건너뛰고 구문 오류를 더 남기지 않습니다.
+    too_deep: bool,
+}
+
+impl<'a> ParserService<'a> {
+    pub fn new(lexer: LexerService<'a>) -> Self {
+        Self::with_operators(lexer, PrecedenceTable::shared_standard())
+    }
+
+    /// 사용자 정의 중위 연산자가 등록된 표로 식을 파싱합니다.
+    pub fn with_operators(lexer: LexerService<'a>, operators: &'a PrecedenceTable) -> Self {
+        let mut parser = Self {
+            lexer,
+            current: Token { kind: TokenKind::Eof, span: Span { start: 0, end: 0 } },
+            peek: Token { kind: TokenKind::Eof, span: Span { start: 0, end: 0 } },
+            previous_end: 0,
+            operators,
+            diagnostics: vec![],
+            enum_variants: vec![],
+            depth: 0,
+            too_deep: false,
+        };
+        parser.diagnostics = parser.lexer.take_diagnostics();
+        parser.advance();
+        parser.advance();
+        parser
+    }
+
+    fn advance(&mut self) {
+        let next = self.lexer.next_token();
+        self.previous_end = self.current.span.end;
+        self.current = core::mem::replace(&mut self.peek, next);
+    }
+
+    /// `start` 부터 바로 앞에서 소비한 토큰의 끝까지
+    fn span_from(&self, start: usize) -> Span {
+        Span { start, end: self.previous_end }
+    }
+
+    /// 지금까지 모은 구문 오류를 꺼냅니다.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        core::mem::take(&mut self.diagnostics)
+    }
+
+    /// 렉서가 모은 주석 (소스 순서)
+    pub fn comments(&self) -> &[Comment<'a>] {
+        self.lexer.comments()
+    }
+
+    /// 구문 오류를 남깁니다. 같은 자리의 오류가 이미 있으면 첫 것만 남깁니다.
+    fn error(&mut self, diagnostic: Diagnostic) {
+        if !self.too_deep && self.diagnostics.last().is_none_or(|last| last.span.start != diagnostic.span.start) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// 한 단계 더 겹쳐 읽습니다. `MAX_NESTING` 을 넘으면 그 자리를 알리고 파일 끝까지 건너뜁니다.
+    /// 빠져나오는 쪽들이 닫히지 않은 괄호마다 오류를 내지 않도록 그 뒤의 구문 오류는 남기지 않습니다.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        if self.depth >= MAX_NESTING {
+            let code = &error_codes::SYNTAX_ERROR;
+            let diagnostic = code.diagnostic(self.current.span, "too_deep", &[&MAX_NESTING]);
+            self.error(diagnostic.with_help(code.message("too_deep_help", &[])));
+            self.too_deep = true;
+            while !matches!(self.current.kind, TokenKind::Eof) {
+                self.advance();
+            }
+            return None;
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// `(` 를 지난 자리에서 `)` 까지 쉼표로 구분한 목록을 읽고 `)` 를 소비합니다. 끝의 쉼표는 허용합니다.
+    /// `list` 는 진단에 쓰는 목록 이름의 메시지 키(E0110.<list>), `open` 은 여는 괄호 자리입니다.
+    ///
+    /// 복구 규칙 (어느 경우에도 매 반복에서 토큰을 하나 이상 소비하거나 목록을 끝냅니다):
+    /// - 요소를 읽지 못하면 그 자리를 알리고 같은 깊이의 `,` 나 `)` 까지 건너뜁니다.
+    /// - 요소 뒤에 `,` 도 `)` 도 아닌 토큰이 오면 쉼표가 빠진 것으로 알리고 그 토큰부터 다음 요소로 읽습니다.
+    /// - `)` 전에 `;`, `}`, 문장을 시작하는 키워드, 파일 끝이 오면 닫히지 않은 목록으로 알리고 그 토큰은 남겨 둡니다.
+    fn parse_list<T>(&mut self, list: &str, open: Span, element: impl FnMut(&mut Self) -> Option<T>) -> Vec<T> {
+        self.parse_delimited(list, open, Delimiter::Paren, element)
+    }
+
+    /// `parse_list` 와 같되 `close` 로 닫는 목록입니다. (`[a, b]`)
+    fn parse_delimited<T>(
+        &mut self,
+        list: &str,
+        open: Span,
+        close: Delimiter,
+        mut element: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Vec<T> {
+        let code = &error_codes::SYNTAX_ERROR;
+        let name = code.message(list, &[]);
+        let mut items = vec![];
+        loop {
+            match self.current.kind {
+                ref kind if close.closes(kind) => {
+                    self.advance();
+                    return items;
+                }
+                ref kind if ends_list(kind) => {
+                    let diagnostic = code.diagnostic(open, "unclosed", &[&name, &close.opening()]);
+                    self.error(diagnostic.with_help(code.message("unclosed_help", &[&close.closing()])));
+                    return items;
+                }
+                _ => {}
+            }
+            let start = self.current.span.start;
+            match element(self) {
+                Some(_) if items.len() == MAX_LIST_ITEMS => {
+                    let span = self.span_from(start);
+                    self.error(code.diagnostic(span, "too_many", &[&name, &MAX_LIST_ITEMS]));
+                }
+                Some(item) => items.push(item),
+                None => {
+                    let span = self.current.span;
+                    let token = self.current.kind.to_string();
+                    self.error(code.diagnostic(span, "unexpected", &[&token, &name]));
+                    self.skip_list_element(close);
+                }
+            }
+            match self.current.kind {
+                TokenKind::Comma => self.advance(),
+                ref kind if close.closes(kind) => {}
+                ref kind if ends_list(kind) => {}
+                _ => {
+                    let span = self.current.span;
+                    let token = self.current.kind.to_string();
+                    self.error(code.diagnostic(span, "missing_comma", &[&token, &name]));
+                }
+            }
+        }
+    }
+
+    /// 목록 요소 하나를 건너뜁니다. 안쪽 괄호는 짝을 맞춰 넘기고, 같은 깊이의 `,` 와 닫는 괄호나 목록을 끝내는 토큰에서 멈춥니다.
+    fn skip_list_element(&mut self, close: Delimiter) {
+        let mut depth = 0usize;
+        loop {
+            match self.current.kind {
+                TokenKind::Eof => return,
+                TokenKind::Comma if depth == 0 => return,
+                ref kind if depth == 0 && close.closes(kind) => return,
+                ref kind if depth == 0 && ends_list(kind) => return,
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        // 파일 맨 앞의 `@!name(args)` 는 파일 전체에 적용되는 어트리뷰트입니다.
+        let mut attributes = vec![];
+        while matches!(self.current.kind, TokenKind::At) && matches!(self.peek.kind, TokenKind::Bang) {
+            self.advance(); // consume '@', parse_attribute가 '!'를 '@' 자리로 소비합니다.
+            match self.parse_attribute() {
+                Some(attribute) => attributes.push(attribute),
+                None => break,
+            }
+        }
+
+        let statements = self.parse_statements(false);
+        Program {
+            root_id: 0,
+            statements,
+            span: self.span_from(0),
+            attributes,
+        }
+    }
+
+    /// 문장들을 파일 끝까지, `in_block` 이면 `}` 까지 읽습니다. 문장 사이의 `;` 는 건너뜁니다.
+    ///
+    /// 문장을 읽지 못하면 (실패한 시도가 이미 알리지 않았을 때) 멈춘 자리의 토큰을 E0110 으로 알리고 다음 문장 경계까지 건너뜁니다. 실패한 시도가
+    /// 토큰을 하나도 소비하지 않았으면 하나를 소비하므로, 반복마다 반드시 앞으로 나아갑니다.
+    fn parse_statements(&mut self, in_block: bool) -> Vec<Statement> {
+        let mut statements = vec![];
+        loop {
+            match self.current.kind {
+                TokenKind::Eof => return statements,
+                TokenKind::RBrace if in_block => return statements,
+                TokenKind::Semicolon => {
+                    self.advance();
+                    continue;
+                }
+                _ => {}
+            }
+            let start = self.current.span.start;
+            let reported = self.diagnostics.len();
+            match self.parse_statement() {
+                Some(stmt) => statements.push(stmt),
+                None => {
+                    if self.diagnostics.len() == reported {
+                        let token = self.current.kind.to_string();
+                        self.error(error_codes::SYNTAX_ERROR.diagnostic(self.current.span, "token", &[&token]));
+                    }
+                    self.synchronize(start);
+                }
+            }
+        }
+    }
+
+    /// 실패한 문장 뒤의 복구: `;` 다음, `}`, 문장을 시작하는 키워드, 파일 끝 중 가장 가까운 곳으로 갑니다.
+    /// `start` 는 실패한 문장이 시작한 자리입니다.
+    fn synchronize(&mut self, start: usize) {
+        if self.current.span.start == start && !matches!(self.current.kind, TokenKind::Eof) {
+            self.advance();
+        }
+        loop {
+            match self.current.kind {
+                TokenKind::Semicolon => return self.advance(),
+                TokenKind::RBrace | TokenKind::At | TokenKind::LBrace => return,
+                ref kind if ends_list(kind) => return,
+                _ => self.advance(),
+            }
+        }
+    }
+
+    /// 블록 안의 문장은 바깥 문장보다 한 단계 깊습니다. (`nested`)
+    fn parse_statement(&mut self) -> Option<Statement> {
+        self.nested(|parser| match parser.current.kind {
+            TokenKind::Let => parser.parse_let_statement(),
+            TokenKind::Return => parser.parse_return_statement(),
+            TokenKind::Yield => parser.parse_yield_statement(),
+            TokenKind::Defer => parser.parse_defer_statement(),
+            TokenKind::If => parser.parse_if_statement(),
+            TokenKind::While => parser.parse_while_statement(),
+            TokenKind::For => parser.parse_for_statement(),
+            TokenKind::Fn if matches!(parser.peek.kind, TokenKind::Identifier(_)) => parser.parse_function_declaration(),
+            TokenKind::Macro => parser.parse_macro_definition(),
+            TokenKind::Type => parser.parse_type_alias(),
+            TokenKind::Enum => parser.parse_enum_declaration(),
+            TokenKind::LBrace => parser.parse_block_statement(),
+            TokenKind::At => parser.parse_attributed_statement(),
+            TokenKind::Identifier(name) if name.as_str() == "_" && matches!(parser.peek.kind, TokenKind::Assign) => {
+                parser.parse_discard_statement()
+            }
+            _ => parser.parse_expression_statement(),
+        })
+    }
+
+    /// `@name(args)` 어트리뷰트들과 그 뒤의 문장을 파싱합니다.
+    fn parse_attributed_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        let mut attributes = vec![];
+        while matches!(self.current.kind, TokenKind::At) {
+            attributes.push(self.parse_attribute()?);
+        }
+        let statement = self.parse_statement()?;
+        Some(Statement::Attributed {
+            attributes,
+            statement: Box::new(statement),
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_attribute(&mut self) -> Option<Attribute> {
+        let start = self.current.span.start;
+        self.advance(); // consume '@'
+
+        let name = if let TokenKind::Identifier(id) = &self.current.kind {
+            id.to_string()
+        } else {
+            return None;
+        };
+        let mut end = self.current.span.end;
+        self.advance();
+
+        let mut args = vec![];
+        if matches!(self.current.kind, TokenKind::LParen) {
+            let open = self.current.span;
+            self.advance();
+            args = self.parse_list("attribute_arguments", open, Self::parse_attribute_arg);
+            end = self.previous_end;
+        }
+
+        Some(Attribute { name, args, span: Span { start, end } })
+    }
+
+    fn parse_attribute_arg(&mut self) -> Option<AttributeArg> {
+        match &self.current.kind {
+            TokenKind::StringLiteral(text) => {
+                let arg = AttributeArg::Literal(text.to_string());
+                self.advance();
+                Some(arg)
+            }
+            TokenKind::Identifier(key) => {
+                let key = key.to_string();
+                self.advance();
+                if !matches!(self.current.kind, TokenKind::Assign) {
+                    return Some(AttributeArg::Flag(key));
+                }
+                self.advance(); // consume '='
+                let value = match &self.current.kind {
+                    TokenKind::StringLiteral(text) => text.to_string(),
+                    TokenKind::Identifier(text) => text.to_string(),
+                    TokenKind::IntegerLiteral(v) => v.to_string(),
+                    _ => return None,
+                };
+                self.advance();
+                Some(AttributeArg::KeyValue(key, value))
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_let_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'let'
+        let is_mutable = if matches!(self.current.kind, TokenKind::Mut) {
+            self.advance();
+            true
+        } else {
+            false
+        };
+
+        let name = if let TokenKind::Identifier(id) = &self.current.kind {
+            *id
+        } else {
+            return None;
+        };
+        self.advance();
+
+        let type_annotation = if matches!(self.current.kind, TokenKind::Colon) {
+            self.advance();
+            self.parse_type_annotation()
+        } else {
+            None
+        };
+
+        if !matches!(self.current.kind, TokenKind::Assign) {
+            return None;
+        }
+        self.advance();
+
+        let value = self.parse_expression()?;
+        Some(Statement::LetStatement {
+            name,
+            value: Box::new(value),
+            type_annotation,
+            is_mutable,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_return_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'return'
+        let expr = self.parse_expression()?;
+        Some(Statement::ReturnStatement(self.span_from(start), Box::new(expr)))
+    }
+
+    fn parse_yield_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'yield'
+        let expr = self.parse_expression()?;
+        Some(Statement::YieldStatement(self.span_from(start), Box::new(expr)))
+    }
+
+    fn parse_defer_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'defer'
+        if !matches!(self.current.kind, TokenKind::LBrace) {
+            return None;
+        }
+        let body = self.parse_block_statement()?;
+        Some(Statement::DeferStatement(self.span_from(start), Box::new(body)))
+    }
+
+    fn parse_if_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'if'
+        let condition = self.parse_expression()?;
+        let then_branch = self.parse_statement()?;
+        let else_branch = if matches!(self.current.kind, TokenKind::Else) {
+            self.advance();
+            Some(Box::new(self.parse_statement()?))
+        } else {
+            None
+        };
+        Some(Statement::IfStatement {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch,
+            span: self.span_from(start),
+        })
+    }
+
+    /// `while <조건> { ... }`
+    fn parse_while_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'while'
+        let condition = self.parse_expression()?;
+        if !matches!(self.current.kind, TokenKind::LBrace) {
+            return None;
+        }
+        let body = self.parse_block_statement()?;
+        Some(Statement::WhileStatement {
+            condition: Box::new(condition),
+            body: Box::new(body),
+            span: self.span_from(start),
+        })
+    }
+
+    /// `for <초기화>; <조건>; <증가> { ... }` 또는 `for <이름> in <식> { ... }`
+    fn parse_for_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'for'
+        if let (TokenKind::Identifier(variable), TokenKind::In) = (&self.current.kind, &self.peek.kind) {
+            let variable = *variable;
+            self.advance();
+            self.advance(); // consume 'in'
+            let iterable = self.parse_expression()?;
+            let body = self.parse_statement()?;
+            return Some(Statement::ForInStatement {
+                variable,
+                iterable: Box::new(iterable),
+                body: Box::new(body),
+                span: self.span_from(start),
+            });
+        }
+        let initializer = if !matches!(self.current.kind, TokenKind::Semicolon) {
+            let initializer = self.parse_statement()?;
+            if matches!(self.current.kind, TokenKind::Semicolon) {
+                self.advance();
+            }
+            Some(Box::new(initializer))
+        } else {
+            self.advance();
+            None
+        };
+
+        let condition = if !matches!(self.current.kind, TokenKind::Semicolon) {
+            let condition = self.parse_expression()?;
+            if matches!(self.current.kind, TokenKind::Semicolon) {
+                self.advance();
+            }
+            Some(Box::new(condition))
+        } else {
+            self.advance();
+            None
+        };
+
+        let increment = if !matches!(self.current.kind, TokenKind::LBrace) {
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+
+        let body = self.parse_statement()?;
+        Some(Statement::ForStatement {
+            initializer,
+            condition,
+            increment,
+            body: Box::new(body),
+            span: self.span_from(start),
+        })
+    }
+
+    /// `fn name(a, b) { ... }` 는 `let name = fn(a, b) { ... }` 와 같습니다.
+    fn parse_function_declaration(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'fn'
+        let TokenKind::Identifier(name) = &self.current.kind else {
+            return None;
+        };
+        let name = *name;
+        self.advance();
+        let function = self.parse_function_rest(start)?;
+        Some(Statement::LetStatement {
+            name,
+            value: Box::new(function),
+            type_annotation: None,
+            is_mutable: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// `fn` (과 선언이면 이름) 뒤의 `(a, b) { ... }`
+    fn parse_function_rest(&mut self, start: usize) -> Option<Expression> {
+        if !matches!(self.current.kind, TokenKind::LParen) {
+            return None;
+        }
+        let open = self.current.span;
+        self.advance();
+        let parameters = self.parse_list("parameters", open, |parser| {
+            let TokenKind::Identifier(id) = &parser.current.kind else {
+                return None;
+            };
+            let name = *id;
+            parser.advance();
+            Some(name)
+        });
+        if !matches!(self.current.kind, TokenKind::LBrace) {
+            return None;
+        }
+        let body = self.parse_block_statement()?;
+        Some(Expression::Function(self.span_from(start), parameters, Box::new(body)))
+    }
+
+    /// `match <식> { <패턴> [if <guard>] => <식>, ... }`. 마지막 arm 뒤의 쉼표는 허용합니다.
+    ///
+    /// arm 을 읽지 못하면 그 자리를 알리고 같은 깊이의 `,` 나 `}` 까지 건너뛴 뒤 다음 arm 을 읽습니다.
+    fn parse_match_expression(&mut self) -> Option<Expression> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'match'
+        let subject = self.parse_expression()?;
+        if !matches!(self.current.kind, TokenKind::LBrace) {
+            return None;
+        }
+        let open = self.current.span;
+        self.advance(); // consume '{'
+
+        let code = &error_codes::SYNTAX_ERROR;
+        let mut arms = vec![];
+        loop {
+            match self.current.kind {
+                TokenKind::RBrace => {
+                    self.advance();
+                    break;
+                }
+                TokenKind::Eof => {
+                    self.error(code.diagnostic(open, "unclosed_block", &[]).with_help(code.message("unclosed_block_help", &[])));
+                    break;
+                }
+                _ => {}
+            }
+            match self.parse_match_arm() {
+                Some(arm) => arms.push(arm),
+                None => {
+                    let token = self.current.kind.to_string();
+                    let name = code.message("match_arms", &[]);
+                    self.error(code.diagnostic(self.current.span, "unexpected", &[&token, &name]));
+                    self.skip_match_arm();
+                }
+            }
+            match self.current.kind {
+                TokenKind::Comma => self.advance(),
+                TokenKind::RBrace | TokenKind::Eof => {}
+                _ => {
+                    let token = self.current.kind.to_string();
+                    let name = code.message("match_arms", &[]);
+                    self.error(code.diagnostic(self.current.span, "missing_comma", &[&token, &name]));
+                }
+            }
+        }
+        Some(Expression::Match(self.span_from(start), Box::new(subject), arms))
+    }
+
+    fn parse_match_arm(&mut self) -> Option<MatchArm> {
+        let start = self.current.span.start;
+        let pattern = self.parse_pattern()?;
+        let guard = if matches!(self.current.kind, TokenKind::If) {
+            self.advance(); // consume 'if'
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+        if !matches!(self.current.kind, TokenKind::Arrow) {
+            return None;
+        }
+        self.advance(); // consume '=>'
+        let body = self.parse_expression()?;
+        Some(MatchArm { pattern, guard, body: Box::new(body), span: self.span_from(start) })
+    }
+
+    /// `|` 로 나눈 대안들. 대안은 `_`, 이름, 리터럴입니다.
+    fn parse_pattern(&mut self) -> Option<Pattern> {
+        let mut alternatives = vec![self.parse_single_pattern()?];
+        while matches!(self.current.kind, TokenKind::BitOr) {
+            self.advance(); // consume '|'
+            alternatives.push(self.parse_single_pattern()?);
+        }
+        Some(if alternatives.len() == 1 { alternatives.remove(0) } else { Pattern::Or(alternatives) })
+    }
+
+    fn parse_single_pattern(&mut self) -> Option<Pattern> {
+        let pattern = match &self.current.kind {
+            TokenKind::Identifier(name) if name.as_str() == "_" => Pattern::Wildcard,
+            // `Rgb(r, g, b)`
+            TokenKind::Identifier(name) if matches!(self.peek.kind, TokenKind::LParen) => {
+                let name = *name;
+                self.advance();
+                let open = self.current.span;
+                self.advance();
+                let fields = self.parse_list("variant_fields", open, Self::parse_pattern);
+                return Some(Pattern::Variant(name, fields));
+            }
+            TokenKind::Identifier(name) if self.enum_variants.contains(name) => Pattern::Variant(*name, vec![]),
+            TokenKind::Identifier(name) => Pattern::Binding(*name),
+            // `1..5`, `1..=5`
+            TokenKind::IntegerLiteral(start) if matches!(self.peek.kind, TokenKind::DotDot | TokenKind::DotDotEq) => {
+                let start = *start;
+                self.advance();
+                let inclusive = matches!(self.current.kind, TokenKind::DotDotEq);
+                self.advance(); // consume '..' or '..='
+                let TokenKind::IntegerLiteral(end) = self.current.kind else {
+                    return None;
+                };
+                Pattern::Range { start, end, inclusive }
+            }
+            TokenKind::IntegerLiteral(v) => Pattern::Literal(Value::Integer(*v)),
+            TokenKind::FloatLiteral(text) => Pattern::Literal(Value::Float(text.parse().ok()?)),
+            TokenKind::StringLiteral(text) => Pattern::Literal(Value::String(text.as_ref().into())),
+            TokenKind::BytesLiteral(b) => Pattern::Literal(Value::Bytes(b.as_slice().into())),
+            TokenKind::BooleanLiteral(b) => Pattern::Literal(Value::Boolean(*b)),
+            _ => return None,
+        };
+        self.advance();
+        Some(pattern)
+    }
+
+    /// 읽지 못한 arm 을 건너뜁니다. 안쪽 괄호는 짝을 맞춰 넘기고, 같은 깊이의 `,` `}` 에서 멈춥니다.
+    fn skip_match_arm(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            match self.current.kind {
+                TokenKind::Eof => return,
+                TokenKind::Comma | TokenKind::RBrace if depth == 0 => return,
+                TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => depth += 1,
+                TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    fn parse_macro_definition(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'macro'
+        let name = if let TokenKind::Identifier(id) = &self.current.kind {
+            id.to_string()
+        } else {
+            return None;
+        };
+        self.advance();
+
+        let mut params = vec![];
+        if matches!(self.current.kind, TokenKind::LParen) {
+            let open = self.current.span;
+            self.advance();
+            params = self.parse_list("parameters", open, |parser| {
+                let TokenKind::Identifier(id) = &parser.current.kind else {
+                    return None;
+                };
+                let name = id.to_string();
+                parser.advance();
+                Some(name)
+            });
+        }
+
+        let body = self.parse_block_statement()?;
+        Some(Statement::MacroDefinition {
+            name,
+            parameters: params,
+            body: Box::new(body),
+            span: self.span_from(start),
+        })
+    }
+
+    /// `type Name = <타입>`
+    fn parse_type_alias(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'type'
+        let name = if let TokenKind::Identifier(id) = &self.current.kind {
+            *id
+        } else {
+            return None;
+        };
+        self.advance();
+
+        if !matches!(self.current.kind, TokenKind::Assign) {
+            return None;
+        }
+        self.advance();
+
+        let target = self.parse_type_annotation()?;
+        Some(Statement::TypeAlias { name, target, span: self.span_from(start) })
+    }
+
+    /// `enum Color { Red, Green, Rgb(int, int, int) }`
+    fn parse_enum_declaration(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume 'enum'
+        let TokenKind::Identifier(name) = &self.current.kind else {
+            return None;
+        };
+        let name = *name;
+        self.advance();
+
+        if !matches!(self.current.kind, TokenKind::LBrace) {
+            return None;
+        }
+        let open = self.current.span;
+        self.advance();
+        let variants = self.parse_delimited("variants", open, Delimiter::Brace, |parser| {
+            let variant_start = parser.current.span.start;
+            let TokenKind::Identifier(variant) = &parser.current.kind else {
+                return None;
+            };
+            let variant = *variant;
+            parser.advance();
+            let mut fields = vec![];
+            if matches!(parser.current.kind, TokenKind::LParen) {
+                let open = parser.current.span;
+                parser.advance();
+                fields = parser.parse_list("variant_fields", open, Self::parse_type_annotation);
+            }
+            Some(VariantDeclaration { name: variant, fields, span: parser.span_from(variant_start) })
+        });
+        self.enum_variants.extend(variants.iter().filter(|v| v.fields.is_empty()).map(|v| v.name));
+        Some(Statement::EnumDeclaration { name, variants, span: self.span_from(start) })
+    }
+
+    fn parse_block_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        let open = self.current.span;
+        self.advance(); // consume '{'
+        let statements = self.parse_statements(true);
+        if matches!(self.current.kind, TokenKind::RBrace) {
+            self.advance(); // consume '}'
+        } else {
+            let code = &error_codes::SYNTAX_ERROR;
+            self.error(code.diagnostic(open, "unclosed_block", &[]).with_help(code.message("unclosed_block_help", &[])));
+        }
+        let end = self.previous_end;
+        Some(Statement::BlockStatement {
+            statements,
+            span: Span { start, end },
+        })
+    }
+
+    /// `_ = expr`
+    fn parse_discard_statement(&mut self) -> Option<Statement> {
+        let start = self.current.span.start;
+        self.advance(); // consume '_'
+        self.advance(); // consume '='
+        let expr = self.parse_expression()?;
+        Some(Statement::DiscardStatement(self.span_from(start), Box::new(expr)))
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<Statement> {
+        let expr = self.parse_expression()?;
+        Some(Statement::ExpressionStatement(Box::new(expr)))
+    }
+
+    /// 대입은 조건식보다도 느슨하게 묶이고 오른쪽부터 묶습니다. (`a = b = 1`) 대입 대상은 이름이어야 하며,
+    /// 아니면 오른쪽까지 읽은 뒤 식 전체를 버립니다.
+    fn parse_expression(&mut self) -> Option<Expression> {
+        let start = self.current.span.start;
+        let target = self.parse_conditional()?;
+        let op = match self.current.kind {
+            TokenKind::Assign => TokenKind::Assign,
+            TokenKind::PlusAssign => TokenKind::PlusAssign,
+            TokenKind::MinusAssign => TokenKind::MinusAssign,
+            _ => return Some(target),
+        };
+        self.advance();
+        let value = self.nested(Self::parse_expression)?;
+        let Expression::Identifier(_, name) = target else {
+            let code = &error_codes::SYNTAX_ERROR;
+            let diagnostic = code.diagnostic(target.span(), "assignment_target", &[&op]);
+            self.error(diagnostic.with_help(code.message("assignment_target_help", &[])));
+            return None;
+        };
+        Some(Expression::Assign(self.span_from(start), op, name, Box::new(value)))
+    }
+
+    /// `cond ? a : b`. 중위 연산자보다 느슨하고 대입보다 단단하게 묶이며 오른쪽부터 묶습니다.
+    /// (`a ? b : c ? d : e` = `a ? b : (c ? d : e)`)
+    fn parse_conditional(&mut self) -> Option<Expression> {
+        let start = self.current.span.start;
+        let condition = self.parse_range()?;
+        if !matches!(self.current.kind, TokenKind::Question) {
+            return Some(condition);
+        }
+        self.advance(); // consume '?'
+        let then_expr = self.nested(Self::parse_conditional)?;
+        if !matches!(self.current.kind, TokenKind::Colon) {
+            let code = &error_codes::SYNTAX_ERROR;
+            let diagnostic = code.diagnostic(self.current.span, "ternary_colon", &[]);
+            self.error(diagnostic.with_help(code.message("ternary_colon_help", &[])));
+            return None;
+        }
+        self.advance(); // consume ':'
+        let else_expr = self.nested(Self::parse_conditional)?;
+        Some(Expression::Ternary(self.span_from(start), Box::new(condition), Box::new(then_expr), Box::new(else_expr)))
+    }
+
+    /// `start..end`, `start..=end`. 모든 중위 연산자보다 느슨하게 묶이고 이어 쓸 수 없습니다. (`a..b..c` 는 오류)
+    fn parse_range(&mut self) -> Option<Expression> {
+        let start = self.current.span.start;
+        let range_start = self.parse_infix(0)?;
+        let inclusive = match self.current.kind {
+            TokenKind::DotDot => false,
+            TokenKind::DotDotEq => true,
+            _ => return Some(range_start),
+        };
+        self.advance(); // consume '..' or '..='
+        let range_end = self.parse_infix(0)?;
+        Some(Expression::Range(self.span_from(start), Box::new(range_start), Box::new(range_end), inclusive))
+    }
+
+    /// 우선순위가 `min_precedence` 보다 높은 중위 연산자만 묶습니다. (Pratt)
+    fn parse_infix(&mut self, min_precedence: u8) -> Option<Expression> {
+        let start = self.current.span.start;
+        let mut left = self.parse_unary()?;
+
+        while let Some(op) = self.operators.infix(&self.current.kind) {
+            if op.precedence <= min_precedence {
+                break;
+            }
+            let next_min = match op.associativity {
+                Associativity::Left => op.precedence,
+                Associativity::Right => op.precedence - 1,
+            };
+            let action = op.action.clone();
+            self.advance();
+            let right = self.nested(|parser| parser.parse_infix(next_min))?;
+            let span = self.span_from(start);
+            left = match action {
+                InfixAction::Builtin(kind) => Expression::InfixOperation(span, kind, Box::new(left), Box::new(right)),
+                InfixAction::Call(function) => Expression::Call(
+                    span,
+                    Box::new(Expression::Identifier(span, function)),
+                    vec![Box::new(left), Box::new(right)],
+                ),
+            };
+        }
+        Some(left)
+    }
+
+    /// `-x`, `!x`, `~x`. 전위 연산자는 어떤 중위 연산자보다 단단하게, 뒤따르는 인덱싱보다는 느슨하게 묶습니다.
+    /// (`-a * b` = `(-a) * b`, `-xs[0]` = `-(xs[0])`)
+    /// 괄호나 전위 연산자로 겹친 식은 모두 여기를 지나므로 깊이를 여기서 셉니다. 대입, 조건식, 중위 연산자의
+    /// 오른쪽처럼 자기 자신을 바로 부르는 곳은 그 자리에서 셉니다.
+    fn parse_unary(&mut self) -> Option<Expression> {
+        self.nested(|parser| {
+            let start = parser.current.span.start;
+            let op = match parser.current.kind {
+                TokenKind::Minus => TokenKind::Minus,
+                TokenKind::Bang => TokenKind::Bang,
+                TokenKind::Tilde => TokenKind::Tilde,
+                _ => {
+                    let primary = parser.parse_primary()?;
+                    return parser.parse_index(start, primary);
+                }
+            };
+            parser.advance();
+            let operand = parser.parse_unary()?;
+            Some(Expression::PrefixOperation(parser.span_from(start), op, Box::new(operand)))
+        })
+    }
+
+    /// 뒤따르는 `[index]`, `[start:end]`, `.name`, `.name(args)` 를 이어서 읽습니다. 자르기의 양 끝은 생략할 수 있습니다.
+    fn parse_index(&mut self, start: usize, mut target: Expression) -> Option<Expression> {
+        loop {
+            match self.current.kind {
+                TokenKind::LBracket => {}
+                TokenKind::Dot => {
+                    target = self.parse_member(start, target)?;
+                    continue;
+                }
+                _ => return Some(target),
+            }
+            self.advance(); // consume '['
+            let first = match self.current.kind {
+                TokenKind::Colon => None,
+                _ => Some(Box::new(self.parse_expression()?)),
+            };
+            let sliced = matches!(self.current.kind, TokenKind::Colon);
+            let end = if sliced {
+                self.advance(); // consume ':'
+                match self.current.kind {
+                    TokenKind::RBracket => None,
+                    _ => Some(Box::new(self.parse_expression()?)),
+                }
+            } else {
+                None
+            };
+            if !matches!(self.current.kind, TokenKind::RBracket) {
+                return None;
+            }
+            self.advance(); // consume ']'
+            let span = self.span_from(start);
+            target = match (sliced, first) {
+                (false, Some(index)) => Expression::Index(span, Box::new(target), index),
+                (false, None) => return None,
+                (true, first) => Expression::Slice(span, Box::new(target), first, end),
+            };
+        }
+    }
+
+    /// `.` 뒤의 `name` 이나 `name(args)`
+    fn parse_member(&mut self, start: usize, target: Expression) -> Option<Expression> {
+        self.advance(); // consume '.'
+        let TokenKind::Identifier(name) = &self.current.kind else {
+            return None;
+        };
+        let name = *name;
+        self.advance();
+        if !matches!(self.current.kind, TokenKind::LParen) {
+            return Some(Expression::Member(self.span_from(start), Box::new(target), name));
+        }
+        let open = self.current.span;
+        self.advance();
+        let args = self.parse_list("arguments", open, |parser| parser.parse_expression().map(Box::new));
+        Some(Expression::MethodCall(self.span_from(start), Box::new(target), name, args))
+    }
+
+    fn parse_primary(&mut self) -> Option<Expression> {
+        let start = self.current.span.start;
+
+        match &self.current.kind {
+            TokenKind::Eval => {
+                self.advance();
+                let inner = self.parse_expression()?;
+                Some(Expression::Eval(self.span_from(start), Box::new(inner)))
+            }
+            TokenKind::Reflect => {
+                self.advance();
+                let inner = self.parse_expression()?;
+                Some(Expression::Reflect(self.span_from(start), Box::new(inner)))
+            }
+            // `type_of x == "int"` 이 타입 검사가 되도록 바로 뒤의 기본 식만 받습니다.
+            TokenKind::TypeOf => {
+                self.advance();
+                let operand_start = self.current.span.start;
+                let primary = self.parse_primary()?;
+                let inner = self.parse_index(operand_start, primary)?;
+                Some(Expression::TypeOf(self.span_from(start), Box::new(inner)))
+            }
+            TokenKind::Identifier(name) => {
+                let id = *name;
+                self.advance();
+                if matches!(self.current.kind, TokenKind::LParen) {
+                    let open = self.current.span;
+                    self.advance();
+                    let args = self.parse_list("arguments", open, |parser| parser.parse_expression().map(Box::new));
+                    Some(Expression::MacroCall(self.span_from(start), id.to_string(), args))
+                } else {
+                    Some(Expression::Identifier(self.span_from(start), id))
+                }
+            }
+            TokenKind::Fn => {
+                self.advance();
+                self.parse_function_rest(start)
+            }
+            TokenKind::Match => self.parse_match_expression(),
+            TokenKind::LBracket => {
+                let open = self.current.span;
+                self.advance();
+                let elements = self.parse_delimited("array_elements", open, Delimiter::Bracket, |parser| {
+                    parser.parse_expression().map(Box::new)
+                });
+                Some(Expression::ArrayLiteral(self.span_from(start), elements))
+            }
+            // 식 자리의 `{` 는 맵입니다. 문장 자리의 `{` 는 블록이므로 `{ "a": 1 }` 만으로 된 문장은 쓸 수 없습니다.
+            TokenKind::LBrace => {
+                let open = self.current.span;
+                self.advance();
+                let entries = self.parse_delimited("map_entries", open, Delimiter::Brace, |parser| {
+                    let key = parser.parse_expression()?;
+                    if !matches!(parser.current.kind, TokenKind::Colon) {
+                        return None;
+                    }
+                    parser.advance();
+                    Some((Box::new(key), Box::new(parser.parse_expression()?)))
+                });
+                Some(Expression::MapLiteral(self.span_from(start), entries))
+            }
+            TokenKind::IntegerLiteral(val) => {
+                let v = Value::Integer(*val);
+                self.advance();
+                Some(Expression::Literal(self.span_from(start), v))
+            }
+            TokenKind::FloatLiteral(s) => {
+                let v = Value::Float(s.parse().unwrap_or(0.0));
+                self.advance();
+                Some(Expression::Literal(self.span_from(start), v))
+            }
+            TokenKind::StringLiteral(text) => {
+                let v = Value::String(text.as_ref().into());
+                self.advance();
+                Some(Expression::Literal(self.span_from(start), v))
+            }
+            TokenKind::BytesLiteral(b) => {
+                let v = Value::Bytes(b.as_slice().into());
+                self.advance();
+                Some(Expression::Literal(self.span_from(start), v))
+            }
+            TokenKind::BooleanLiteral(b) => {
+                let v = Value::Boolean(*b);
+                self.advance();
+                Some(Expression::Literal(self.span_from(start), v))
+            }
+            TokenKind::Custom { constructor, argument } => {
+                let span = self.current.span;
+                let callee = Box::new(Expression::Identifier(span, *constructor));
+                let args = argument.iter().map(|c| Box::new(Expression::Literal(span, c.to_value()))).collect();
+                self.advance();
+                Some(Expression::Call(span, callee, args))
+            }
+            TokenKind::LParen => {
+                self.advance();
+                let inner = self.parse_expression()?;
+                if matches!(self.current.kind, TokenKind::RParen) {
+                    self.advance();
+                    Some(Expression::Grouped(self.span_from(start), Box::new(inner)))
+                } else {
+                    None
+                }
+            }
+            _ => None
+        }
+    }
+
+    /// `int`, `Meters`, `int | string | void`
+    fn parse_type_annotation(&mut self) -> Option<TypeAnnotation> {
+        let first = self.parse_type_name()?;
+        if !matches!(self.current.kind, TokenKind::BitOr) {
+            return Some(first);
+        }
+        let mut members = vec![first];
+        while matches!(self.current.kind, TokenKind::BitOr) {
+            self.advance();
+            members.push(self.parse_type_name()?);
+        }
+        Some(TypeAnnotation::Union(members))
+    }
+
+    fn parse_type_name(&mut self) -> Option<TypeAnnotation> {
+        // `[int]`: 요소 타입 하나를 대괄호로 감쌉니다. 요소 타입은 합 타입일 수 있습니다. (`[int | string]`)
+        if matches!(self.current.kind, TokenKind::LBracket) {
+            self.advance();
+            let element = self.parse_type_annotation()?;
+            if !matches!(self.current.kind, TokenKind::RBracket) {
+                return None;
+            }
+            self.advance();
+            return Some(TypeAnnotation::Array(Box::new(element)));
+        }
+        // `{string: int}`: 키 타입과 값 타입
+        if matches!(self.current.kind, TokenKind::LBrace) {
+            self.advance();
+            let key = self.parse_type_annotation()?;
+            if !matches!(self.current.kind, TokenKind::Colon) {
+                return None;
+            }
+            self.advance();
+            let value = self.parse_type_annotation()?;
+            if !matches!(self.current.kind, TokenKind::RBrace) {
+                return None;
+            }
+            self.advance();
+            return Some(TypeAnnotation::Map(Box::new(key), Box::new(value)));
+        }
+        let annotation = match &self.current.kind {
+            TokenKind::Identifier(name) => Some(TypeAnnotation::Custom(name.to_string())),
+            TokenKind::Int => Some(TypeAnnotation::Int),
+            TokenKind::Float => Some(TypeAnnotation::Float),
+            TokenKind::Bool => Some(TypeAnnotation::Bool),
+            TokenKind::String => Some(TypeAnnotation::String),
+            TokenKind::Void => Some(TypeAnnotation::Void),
+            TokenKind::Any => Some(TypeAnnotation::Any),
+            _ => None,
+        };
+        if annotation.is_some() {
+            self.advance();
+        }
+        annotation
+    }
+}
+
+/// 쉼표로 구분한 목록을 닫는 괄호
+#[derive(Clone, Copy)]
+enum Delimiter {
+    Paren,
+    Bracket,
+    Brace,
+}
+
+impl Delimiter {
+    fn opening(self) -> &'static str {
+        match self {
+            Delimiter::Paren => "(",
+            Delimiter::Bracket => "[",
+            Delimiter::Brace => "{",
+        }
+    }
+
+    fn closing(self) -> &'static str {
+        match self {
+            Delimiter::Paren => ")",
+            Delimiter::Bracket => "]",
+            Delimiter::Brace => "}",
+        }
+    }
+
+    fn closes(self, kind: &TokenKind) -> bool {
+        matches!(
+            (self, kind),
+            (Delimiter::Paren, TokenKind::RParen)
+                | (Delimiter::Bracket, TokenKind::RBracket)
+                | (Delimiter::Brace, TokenKind::RBrace)
+        )
+    }
+}
+
+/// 괄호 목록 안에 올 수 없어 닫히지 않은 목록의 끝으로 보는 토큰
+fn ends_list(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Semicolon
+            | TokenKind::RBrace
+            | TokenKind::Eof
+            | TokenKind::Let
+            | TokenKind::Return
+            | TokenKind::If
+            | TokenKind::For
+            | TokenKind::While
+            | TokenKind::Macro
+            | TokenKind::Type
+            | TokenKind::Enum
+            | TokenKind::Defer
+            | TokenKind::Yield
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::run_to_exit;
+
+    fn parse(source: &str) -> (Program, Vec<Diagnostic>) {
+        let mut parser = ParserService::new(LexerService::new(source));
+        let program = parser.parse_program();
+        (program, parser.take_diagnostics())
+    }
+
+    fn messages(source: &str) -> Vec<String> {
+        parse(source).1.iter().map(|d| d.message.clone()).collect()
+    }
+
+    #[test]
+    fn nesting_up_to_the_limit_parses() {
+        let depth = MAX_NESTING - 2;
+        let source = format!("return {}1{};", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse(&source).1.is_empty());
+        assert!(matches!(run_to_exit(&source), Value::Integer(1)));
+        let blocks = format!("{}let x = 1;{}\nreturn 2;", "{".repeat(depth), "}".repeat(depth));
+        assert!(parse(&blocks).1.is_empty());
+    }
+
+    /// 스택을 넘칠 만큼 겹친 입력도 패닉 없이 E0110 하나로 끝납니다.
+    #[test]
+    fn deep_nesting_is_reported_instead_of_overflowing_the_stack() {
+        let deep = 100_000;
+        let too_deep = |source: &str| {
+            let messages = messages(source);
+            assert_eq!(messages.iter().filter(|m| m.contains(&MAX_NESTING.to_string())).count(), 1, "{:?}", messages);
+        };
+        too_deep(&format!("return {}1{};", "(".repeat(deep), ")".repeat(deep)));
+        too_deep(&format!("return {}1;", "-".repeat(deep)));
+        too_deep(&format!("return {}1;", "[".repeat(deep)));
+        too_deep(&format!("{}return 1;", "{".repeat(deep)));
+        too_deep(&format!("return {}0;", "1 ? 2 : ".repeat(deep)));
+        too_deep(&format!("return {}1;", "x = ".repeat(deep)));
+        too_deep(&format!("return {}0{};", "f(".repeat(deep), ")".repeat(deep)));
+    }
+
+    /// 기본 식의 위치는 마지막 토큰에서 끝나고 뒤따르는 `;` 나 `)` 를 넣지 않습니다.
+    #[test]
+    fn primary_expression_spans_end_at_their_last_token() {
+        let source = "let a = 5;\nlet s = \"hi\";\nlet b = b\"\\x01\";\nlet t = true;\nlet g = (a);\nold_f(a);";
+        let (program, diagnostics) = parse(source);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let text = |span: Span| &source[span.start..span.end];
+        let values: Vec<&str> = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::LetStatement { value, .. } => Some(text(value.span())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(values, ["5", "\"hi\"", "b\"\\x01\"", "true", "(a)"]);
+        let Some(Statement::ExpressionStatement(call)) = program.statements.last() else {
+            panic!("{:?}", program.statements.last());
+        };
+        let Expression::MacroCall(span, _, args) = call.as_ref() else {
+            panic!("{:?}", call);
+        };
+        assert_eq!((text(*span), text(args[0].span())), ("old_f(a)", "a"));
+    }
+
+    #[test]
+    fn huge_integer_literals_are_errors() {
+        assert!(matches!(run_to_exit("return 9223372036854775807;"), Value::Integer(i64::MAX)));
+        let huge = "9".repeat(40);
+        let messages = messages(&format!("return {};", huge));
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains(&huge), "{:?}", messages);
+        // 실수는 범위를 넘으면 무한대로 읽힙니다.
+        assert!(parse(&format!("return {}.0;", "9".repeat(400))).1.is_empty());
+    }
+
+    #[test]
+    fn invalid_assignment_targets_drop_the_whole_expression() {
+        let (program, diagnostics) = parse("1 = print(2);\nx[0] += 1;\nreturn 3;");
+        assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+        assert!(diagnostics.iter().all(|d| d.message.contains("=")), "{:?}", diagnostics);
+        assert_eq!(program.statements.len(), 1);
+        assert!(matches!(program.statements[0], Statement::ReturnStatement(..)));
+    }
+
+    #[test]
+    fn garbage_and_long_inputs_always_make_progress() {
+        for source in ["@@@@ ))) ]]] }}} ,,, :: ??", ")(][}{", "let let let = = =", "fn fn fn ( ( (", "match { => => }"] {
+            let (_, diagnostics) = parse(source);
+            assert!(!diagnostics.is_empty(), "{}", source);
+        }
+        let (program, diagnostics) = parse(&"1;".repeat(100_000));
+        assert!(diagnostics.is_empty());
+        assert_eq!(program.statements.len(), 100_000);
+    }
+}
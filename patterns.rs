@@ -0,0 +1,193 @@
+// patterns.rs
+// match 패턴의 런타임 매칭과 정적 분석(도달 불가능한 arm, 비완전 매칭)을 담당합니다.
+//
+// enum 변형 패턴은 변형 이름으로 맞춥니다. 비완전 매칭 검사는 어떤 enum 의 변형을 모두 덮은 arm 목록을
+// 완전하다고 봅니다. 필드 패턴이 모든 값을 받지 않는 arm 은 그 변형을 일부만 덮습니다.
+
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, MatchArm, Pattern, Span, Value};
+use crate::error_codes;
+use crate::interner::Symbol;
+
+// ─── 런타임 매칭 ─────────────────────────────
+
+/// 값이 패턴과 일치하면 바인딩 목록을, 아니면 None 을 돌려줍니다.
+pub fn match_pattern(pattern: &Pattern, value: &Value) -> Option<Vec<(Symbol, Value)>> {
+    match pattern {
+        Pattern::Wildcard => Some(vec![]),
+        Pattern::Binding(name) => Some(vec![(*name, value.clone())]),
+        Pattern::Literal(expected) => literal_eq(expected, value).then(Vec::new),
+        Pattern::Range { start, end, inclusive } => match value {
+            Value::Integer(v) if *inclusive => (start..=end).contains(&v).then(Vec::new),
+            Value::Integer(v) => (start..end).contains(&v).then(Vec::new),
+            _ => None,
+        },
+        Pattern::Or(alternatives) => alternatives.iter().find_map(|p| match_pattern(p, value)),
+        Pattern::Variant(name, patterns) => {
+            let Value::EnumVariant(variant) = value else {
+                return None;
+            };
+            let fields = variant.fields.as_ref().filter(|fields| variant.name == *name && fields.len() == patterns.len())?;
+            let mut bound = vec![];
+            for (pattern, field) in patterns.iter().zip(fields) {
+                bound.extend(match_pattern(pattern, field)?);
+            }
+            Some(bound)
+        }
+    }
+}
+
+/// 패턴이 바인딩하는 이름들. or-패턴은 대안마다 같은 이름을 묶는다고 보고 모두 모읍니다.
+pub fn bindings(pattern: &Pattern) -> Vec<Symbol> {
+    match pattern {
+        Pattern::Binding(name) => vec![*name],
+        Pattern::Or(alternatives) => alternatives.iter().flat_map(bindings).collect(),
+        Pattern::Variant(_, fields) => fields.iter().flat_map(bindings).collect(),
+        Pattern::Wildcard | Pattern::Literal(_) | Pattern::Range { .. } => vec![],
+    }
+}
+
+fn literal_eq(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Integer(a), Value::Integer(b)) => a == b,
+        (Value::Float(a), Value::Float(b)) => a == b,
+        (Value::Boolean(a), Value::Boolean(b)) => a == b,
+        (Value::String(a), Value::String(b)) => a == b,
+        (Value::Null, Value::Null) => true,
+        _ => false,
+    }
+}
+
+// ─── 정적 분석 ─────────────────────────────
+
+/// 패턴이 덮는 값의 집합을 근사한 공간입니다.
+#[derive(Debug, Clone, Default)]
+struct Space {
+    all: bool,
+    /// 겹치지 않게 정렬된 정수 구간 (양 끝 포함)
+    ints: Vec<(i64, i64)>,
+    bools: [bool; 2],
+    others: Vec<String>,
+    /// 모든 필드 값을 덮은 enum 변형
+    variants: Vec<Symbol>,
+    /// 일부 필드 값만 덮은 enum 변형
+    partial_variants: Vec<Symbol>,
+}
+
+impl Space {
+    fn of(pattern: &Pattern) -> Space {
+        let mut space = Space::default();
+        match pattern {
+            Pattern::Wildcard | Pattern::Binding(_) => space.all = true,
+            Pattern::Literal(Value::Integer(v)) => space.ints.push((*v, *v)),
+            Pattern::Literal(Value::Boolean(b)) => space.bools[usize::from(*b)] = true,
+            Pattern::Literal(other) => space.others.push(format!("{:?}", other)),
+            Pattern::Range { start, end, inclusive } => {
+                let last = if *inclusive { Some(*end) } else { end.checked_sub(1) };
+                if let Some(last) = last.filter(|last| last >= start) {
+                    space.ints.push((*start, last));
+                }
+            }
+            Pattern::Or(alternatives) => {
+                for alt in alternatives {
+                    space.union(&Space::of(alt));
+                }
+            }
+            Pattern::Variant(name, fields) if fields.iter().all(|field| Space::of(field).all) => space.variants.push(*name),
+            Pattern::Variant(name, _) => space.partial_variants.push(*name),
+        }
+        space
+    }
+
+    fn union(&mut self, other: &Space) {
+        self.all |= other.all;
+        self.ints.extend(other.ints.iter().copied());
+        self.ints.sort();
+        let mut merged: Vec<(i64, i64)> = vec![];
+        for (lo, hi) in self.ints.drain(..) {
+            match merged.last_mut() {
+                Some((_, last_hi)) if lo <= last_hi.saturating_add(1) => *last_hi = (*last_hi).max(hi),
+                _ => merged.push((lo, hi)),
+            }
+        }
+        self.ints = merged;
+        self.bools[0] |= other.bools[0];
+        self.bools[1] |= other.bools[1];
+        for o in &other.others {
+            if !self.others.contains(o) {
+                self.others.push(o.clone());
+            }
+        }
+        for v in &other.variants {
+            if !self.variants.contains(v) {
+                self.variants.push(*v);
+            }
+        }
+        self.partial_variants.extend(other.partial_variants.iter().filter(|v| !self.variants.contains(v)));
+    }
+
+    /// `other` 의 모든 값이 이미 이 공간에 포함되는지 확인합니다.
+    fn covers(&self, other: &Space, enums: &[Vec<Symbol>]) -> bool {
+        if self.all {
+            return true;
+        }
+        if other.all {
+            return self.is_total(enums);
+        }
+        other.ints.iter().all(|(lo, hi)| self.ints.iter().any(|(a, b)| a <= lo && hi <= b))
+            && (!other.bools[0] || self.bools[0])
+            && (!other.bools[1] || self.bools[1])
+            && other.others.iter().all(|o| self.others.contains(o))
+            && other.variants.iter().chain(&other.partial_variants).all(|v| self.variants.contains(v))
+    }
+
+    /// 정수 전체, bool 전체, 또는 한 enum 의 변형 전체를 덮으면 (해당 타입에 대해) 완전한 매칭입니다.
+    fn is_total(&self, enums: &[Vec<Symbol>]) -> bool {
+        self.all
+            || (self.bools[0] && self.bools[1])
+            || self.ints.first() == Some(&(i64::MIN, i64::MAX))
+            || enums.iter().any(|variants| !variants.is_empty() && variants.iter().all(|v| self.variants.contains(v)))
+    }
+}
+
+/// match arm 목록을 검사하여 도달 불가능한 arm 과 비완전 매칭에 대한 경고를 만듭니다.
+/// guard 가 있는 arm 은 실패할 수 있으므로 이후 arm 을 가리지 않습니다.
+/// `enums` 는 보이는 enum 선언마다 변형 이름 목록입니다.
+pub fn check_arms(arms: &[MatchArm], match_span: Span, enums: &[Vec<Symbol>]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut covered = Space::default();
+
+    for (i, arm) in arms.iter().enumerate() {
+        let space = Space::of(&arm.pattern);
+        if covered.covers(&space, enums) {
+            diagnostics.push(
+                error_codes::UNREACHABLE_ARM
+                    .diagnostic(arm.span, "", &[&(i + 1)])
+                    .with_help(error_codes::UNREACHABLE_ARM.message("help", &[])),
+            );
+        }
+        if let Pattern::Or(alternatives) = &arm.pattern {
+            let mut seen = Space::default();
+            for (j, alt) in alternatives.iter().enumerate() {
+                let alt_space = Space::of(alt);
+                if j > 0 && seen.covers(&alt_space, enums) {
+                    diagnostics.push(error_codes::SHADOWED_ALTERNATIVE.diagnostic(arm.span, "", &[&(j + 1)]));
+                }
+                seen.union(&alt_space);
+            }
+        }
+        if arm.guard.is_none() {
+            covered.union(&space);
+        }
+    }
+
+    if !covered.is_total(enums) {
+        diagnostics.push(
+            error_codes::NON_EXHAUSTIVE_MATCH
+                .diagnostic(match_span, "", &[])
+                .with_help(error_codes::NON_EXHAUSTIVE_MATCH.message("help", &[])),
+        );
+    }
+
+    diagnostics
+}
@@ -0,0 +1,315 @@
+// playground_server.rs
+// `high serve`: 웹 플레이그라운드 프런트엔드가 쓰는 HTTP 엔드포인트입니다.
+//
+//   POST /compile   본문 = High 소스 (text/plain). 질의 문자열:
+//                     emit=rust,ir,expanded   함께 돌려줄 생성물 (쉼표로 구분)
+//                     opt=<0-3>               최적화 단계 (기본 0)
+//                     no_std=1                표준 라이브러리를 링크하지 않음
+//   POST /highlight 본문 = High 소스. 의미 기반으로 색을 입힌 HTML 조각을 {"html": ...} 로 돌려줍니다.
+//   GET  /health    서버 상태와 컴파일러 버전
+//
+// 응답은 모두 JSON 이며, 브라우저에서 바로 부를 수 있도록 CORS 헤더를 붙입니다.
+// 컴파일은 CompilerService 의 인터프리터 경로(네이티브 코드 생성 없음)를 그대로 씁니다.
+// CompilerService 는 `&mut self` 로 동작하고 실행기의 Engine 은 스레드 사이를 옮길 수 없으므로
+// 요청은 한 번에 하나씩 처리합니다. 끝나지 않는 프로그램은 실행 단계 제한 시간(`PLAYGROUND_EXECUTION_MS`)에서 멈춥니다.
+
+use std::io;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{timeout, Instant};
+
+use crate::api;
+use crate::artifact_metadata;
+use crate::compiler_services::{CompileOptions, CompileRequest, CompileResult, CompilerService};
+use crate::data_structures::{Diagnostic, DiagnosticLevel};
+use crate::diagnostic_renderer::DiagnosticRenderer;
+use crate::json::Json;
+use crate::semantic_tokens;
+
+pub const DEFAULT_PORT: u16 = 8080;
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// 요청 본문의 최대 크기
+const MAX_BODY_BYTES: usize = 1 << 20;
+/// 요청 머리(요청 줄과 헤더)의 최대 크기
+const MAX_HEAD_BYTES: usize = 16 << 10;
+/// 요청 하나를 다 읽을 때까지 기다리는 시간
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// 플레이그라운드 프로그램의 실행 단계 제한 시간. 한 요청이 서버를 오래 붙잡지 않도록 기본값보다 짧습니다.
+const PLAYGROUND_EXECUTION_MS: u64 = 5_000;
+/// 진단을 렌더링할 때 쓰는 파일 이름
+const SOURCE_NAME: &str = "playground.high";
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+impl HttpRequest {
+    fn query(&self, key: &str) -> Option<&str> {
+        self.query.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+struct HttpResponse {
+    status: u16,
+    body: String,
+}
+
+impl HttpResponse {
+    fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, Json::object([("error", Json::from(message))]).to_string())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            _ => "Error",
+        };
+        format!(
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json; charset=utf-8\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+pub struct PlaygroundServer {
+    compiler: CompilerService,
+}
+
+impl PlaygroundServer {
+    pub fn new() -> Self {
+        Self { compiler: CompilerService::new() }
+    }
+
+    /// `host:port` 에서 요청을 받습니다. 바인딩에 실패하지 않는 한 돌아오지 않습니다.
+    pub async fn serve(&mut self, host: &str, port: u16) -> io::Result<()> {
+        let listener = TcpListener::bind((host, port)).await?;
+        println!("[Playground] http://{} 에서 요청을 기다립니다. (POST /compile)", listener.local_addr()?);
+
+        loop {
+            let (mut stream, peer) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    println!("[Playground] 연결 수락 실패: {}", e);
+                    continue;
+                }
+            };
+            let response = match timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+                Ok(Ok(request)) => {
+                    println!("[Playground] {} {} ({})", request.method, request.path, peer);
+                    self.handle(request).await
+                }
+                Ok(Err(response)) => response,
+                Err(_) => HttpResponse::error(408, "요청을 제때 받지 못했습니다."),
+            };
+            if let Err(e) = stream.write_all(&response.to_bytes()).await {
+                println!("[Playground] 응답 쓰기 실패 ({}): {}", peer, e);
+            }
+        }
+    }
+
+    async fn handle(&mut self, request: HttpRequest) -> HttpResponse {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("OPTIONS", _) => HttpResponse::json(204, String::new()),
+            ("GET", "/health") => HttpResponse::json(
+                200,
+                Json::object([
+                    ("status", Json::from("ok")),
+                    ("version", Json::from(artifact_metadata::COMPILER_VERSION)),
+                ])
+                .to_string(),
+            ),
+            ("POST", "/compile") => self.compile(&request).await,
+            ("POST", "/highlight") => HttpResponse::json(
+                200,
+                Json::object([("html", Json::from(semantic_tokens::render_html(&request.body)))]).to_string(),
+            ),
+            (_, "/health" | "/compile" | "/highlight") => HttpResponse::error(405, "지원하지 않는 메서드입니다."),
+            _ => HttpResponse::error(404, "알 수 없는 경로입니다."),
+        }
+    }
+
+    async fn compile(&mut self, request: &HttpRequest) -> HttpResponse {
+        let start_time = Instant::now();
+        let mut options = CompileOptions::default();
+        options.timeouts.execution_ms = PLAYGROUND_EXECUTION_MS;
+        for item in request.query("emit").unwrap_or("").split(',').filter(|s| !s.is_empty()) {
+            match item {
+                "rust" => options.emit_rust_source = true,
+                "ir" => options.emit_ir = true,
+                "expanded" => options.emit_expanded = true,
+                other => return HttpResponse::error(400, &format!("알 수 없는 emit 항목: {}", other)),
+            }
+        }
+        if let Some(level) = request.query("opt") {
+            match level.parse::<u8>() {
+                Ok(level) if level <= 3 => options.optimization_level = level,
+                _ => return HttpResponse::error(400, &format!("opt 는 0-3 이어야 합니다: {}", level)),
+            }
+        }
+        options.no_std = matches!(request.query("no_std"), Some("1" | "true"));
+
+        let source = request.body.clone();
+        // 구문 오류가 있으면 정리한 소스 대신 null 입니다. 오류 자체는 diagnostics 에 있습니다.
+        let formatted = api::format(&source).ok();
+        let result = self
+            .compiler
+            .compile(CompileRequest { source_code: source.clone(), options })
+            .await;
+
+        let mut json = result_json(&result, &source);
+        if let Json::Object(entries) = &mut json {
+            entries.insert(3, ("formatted".into(), Json::from(formatted)));
+            entries.push(("time_ms".into(), Json::from(start_time.elapsed().as_millis() as u64)));
+        }
+        HttpResponse::json(200, json.to_string())
+    }
+}
+
+impl Default for PlaygroundServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ─── 요청 읽기 ─────────────────────────────
+
+/// 요청 줄, 헤더, `Content-Length` 만큼의 본문을 읽습니다. 실패하면 돌려줄 오류 응답을 줍니다.
+async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest, HttpResponse> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(i) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break i;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err(HttpResponse::error(413, "요청 헤더가 너무 큽니다."));
+        }
+        let n = stream.read(&mut chunk).await.map_err(|e| HttpResponse::error(400, &e.to_string()))?;
+        if n == 0 {
+            return Err(HttpResponse::error(400, "요청이 중간에 끊겼습니다."));
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(HttpResponse::error(400, "요청 줄을 읽을 수 없습니다."));
+    };
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .map(|(_, value)| value.trim().parse::<usize>())
+        .transpose()
+        .map_err(|_| HttpResponse::error(400, "Content-Length 가 올바르지 않습니다."))?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpResponse::error(413, "소스가 너무 큽니다."));
+    }
+
+    let mut body = buffer[head_end + 4..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await.map_err(|e| HttpResponse::error(400, &e.to_string()))?;
+        if n == 0 {
+            return Err(HttpResponse::error(400, "요청 본문이 중간에 끊겼습니다."));
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+    let body = String::from_utf8(body).map_err(|_| HttpResponse::error(400, "소스는 UTF-8 이어야 합니다."))?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    Ok(HttpRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (key.to_string(), value.to_string())
+            })
+            .collect(),
+        body,
+    })
+}
+
+// ─── JSON 응답 ─────────────────────────────
+
+/// 컴파일 결과의 JSON 표기. 데몬의 `compile` 응답도 같은 모양을 씁니다.
+pub(crate) fn result_json(result: &CompileResult, source: &str) -> Json {
+    let renderer = DiagnosticRenderer::new(false);
+    let diagnostics = result
+        .diagnostics
+        .iter()
+        .map(|diag| diagnostic_json(diag, renderer.render(diag, source, SOURCE_NAME)))
+        .collect();
+    let errors = result
+        .errors
+        .iter()
+        .map(|error| Json::from(renderer.render_message(&DiagnosticLevel::Error, error)))
+        .collect();
+    let output = result.execution_log.iter().map(|line| Json::from(line.as_str())).collect();
+
+    Json::object([
+        ("success", Json::from(result.success)),
+        ("diagnostics", Json::Array(diagnostics)),
+        ("errors", Json::Array(errors)),
+        (
+            "result",
+            Json::object([
+                ("status", Json::from(format!("{:?}", result.execution_status))),
+                ("exit_code", Json::Number(result.exit_code as f64)),
+                ("output", Json::Array(output)),
+            ]),
+        ),
+        ("expanded", Json::from(result.expanded_source.clone())),
+        ("rust", Json::from(result.rust_source.clone())),
+        ("ir", Json::from(result.ir_listing.clone())),
+    ])
+}
+
+fn diagnostic_json(diag: &Diagnostic, rendered: String) -> Json {
+    let level = match diag.level {
+        DiagnosticLevel::Info => "info",
+        DiagnosticLevel::Warning => "warning",
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::HerFatal => "fatal",
+    };
+    Json::object([
+        ("level", Json::from(level)),
+        ("code", Json::from(diag.code)),
+        ("message", Json::from(diag.message.as_str())),
+        ("help", Json::from(diag.help.clone())),
+        ("start", Json::from(diag.span.start as u64)),
+        ("end", Json::from(diag.span.end as u64)),
+        ("rendered", Json::from(rendered)),
+    ])
+}
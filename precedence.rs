@@ -0,0 +1,192 @@
+// precedence.rs
+// 중위 연산자의 우선순위와 결합 방향 표입니다. 파서의 Pratt 루프는 이 표만 보고 식을 묶습니다.
+//
+// 숫자가 클수록 먼저 묶입니다. 기본 표 (모두 왼쪽 결합):
+//   1   ||
+//   2   &&
+//   3   ==  !=
+//   4   <  >  <=  >=
+//   5   |
+//   6   ^
+//   7   &
+//   8   <<  >>
+//   9   +  -
+//   10  *  /  %
+// 비트 연산자는 Rust 처럼 비교보다 먼저 묶이므로 `x & 1 == 0` 은 `(x & 1) == 0` 입니다.
+//
+// 임베더는 함수에 연결된 사용자 정의 중위 연산자를 등록할 수 있습니다. (`a dot b` → `dot_product(a, b)`)
+// 연산자 철자는 토큰 하나여야 하며, 이미 쓰이는 철자나 문법 기호와 겹치면 등록할 때 거절합니다.
+
+use core::mem::discriminant;
+
+use crate::compat::*;
+use crate::data_structures::TokenKind;
+use crate::interner::Symbol;
+use crate::lexer_service::LexerService;
+use crate::messages;
+
+/// 사용자 정의 연산자에 줄 수 있는 가장 높은 우선순위
+pub const MAX_PRECEDENCE: u8 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a - b - c` = `(a - b) - c`
+    Left,
+    /// `a ^ b ^ c` = `a ^ (b ^ c)`
+    Right,
+}
+
+/// 중위 연산이 만드는 노드
+#[derive(Debug, Clone)]
+pub enum InfixAction {
+    /// 내장 연산자: `Expression::InfixOperation`
+    Builtin(TokenKind<'static>),
+    /// 사용자 정의 연산자: `function(left, right)` 호출
+    Call(Symbol),
+}
+
+/// 연산자 철자가 가리키는 토큰
+#[derive(Debug, Clone)]
+enum Spelling {
+    /// 기호 토큰 (`^`, `<<` 등)
+    Token(TokenKind<'static>),
+    /// 식별자 토큰으로 읽히는 단어 (`dot`, `cross` 등)
+    Word(Symbol),
+}
+
+#[derive(Debug, Clone)]
+pub struct InfixOperator {
+    pub spelling: String,
+    pub precedence: u8,
+    pub associativity: Associativity,
+    pub action: InfixAction,
+    token: Spelling,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrecedenceTable {
+    operators: Vec<InfixOperator>,
+}
+
+impl PrecedenceTable {
+    /// 내장 연산자만 있는 표
+    pub fn standard() -> Self {
+        use TokenKind::*;
+        let levels: [(u8, &[TokenKind<'static>]); 10] = [
+            (1, &[Or]),
+            (2, &[And]),
+            (3, &[Eq, Neq]),
+            (4, &[Less, Greater, LessEqual, GreaterEqual]),
+            (5, &[BitOr]),
+            (6, &[BitXor]),
+            (7, &[BitAnd]),
+            (8, &[ShiftLeft, ShiftRight]),
+            (9, &[Plus, Minus]),
+            (10, &[Asterisk, Slash, Percent]),
+        ];
+        let operators = levels
+            .iter()
+            .flat_map(|(precedence, tokens)| {
+                tokens.iter().map(move |token| InfixOperator {
+                    spelling: token.to_string(),
+                    precedence: *precedence,
+                    associativity: Associativity::Left,
+                    action: InfixAction::Builtin(token.clone()),
+                    token: Spelling::Token(token.clone()),
+                })
+            })
+            .collect();
+        Self { operators }
+    }
+
+    /// 파서가 따로 표를 받지 않을 때 쓰는 공유 기본 표
+    pub fn shared_standard() -> &'static PrecedenceTable {
+        static STANDARD: OnceLock<PrecedenceTable> = OnceLock::new();
+        get_or_init(&STANDARD, PrecedenceTable::standard)
+    }
+
+    pub fn operators(&self) -> &[InfixOperator] {
+        &self.operators
+    }
+
+    /// 토큰이 중위 연산자이면 그 정보입니다.
+    pub fn infix(&self, token: &TokenKind) -> Option<&InfixOperator> {
+        self.operators.iter().find(|op| match (&op.token, token) {
+            (Spelling::Word(word), TokenKind::Identifier(name)) => word == name,
+            (Spelling::Token(kind), token) => discriminant(kind) == discriminant(token),
+            _ => false,
+        })
+    }
+
+    /// `left <spelling> right` 를 `function(left, right)` 호출로 읽는 연산자를 등록합니다.
+    pub fn register(
+        &mut self,
+        spelling: &str,
+        precedence: u8,
+        associativity: Associativity,
+        function: &str,
+    ) -> Result<(), String> {
+        if !(1..=MAX_PRECEDENCE).contains(&precedence) {
+            return Err(messages::tr("operator.precedence", &[&spelling, &precedence, &MAX_PRECEDENCE]));
+        }
+        let token = parse_spelling(spelling)?;
+        if let Some(existing) = self.infix(&token_of(&token)) {
+            return Err(messages::tr("operator.conflict", &[&spelling, &existing.spelling, &existing.precedence]));
+        }
+        self.operators.push(InfixOperator {
+            spelling: spelling.to_string(),
+            precedence,
+            associativity,
+            action: InfixAction::Call(Symbol::intern(function)),
+            token,
+        });
+        Ok(())
+    }
+}
+
+impl Default for PrecedenceTable {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// 철자를 렉서로 읽어 연산자로 쓸 수 있는 토큰 하나인지 확인합니다.
+fn parse_spelling(spelling: &str) -> Result<Spelling, String> {
+    let mut lexer = LexerService::new(spelling);
+    let first = lexer.next_token().kind;
+    if !matches!(lexer.next_token().kind, TokenKind::Eof) {
+        return Err(messages::tr("operator.not_single_token", &[&spelling]));
+    }
+    use TokenKind::*;
+    let token = match first {
+        Identifier(name) => return Ok(Spelling::Word(name)),
+        Plus => Plus,
+        Minus => Minus,
+        Asterisk => Asterisk,
+        Slash => Slash,
+        Percent => Percent,
+        Eq => Eq,
+        Neq => Neq,
+        Less => Less,
+        Greater => Greater,
+        LessEqual => LessEqual,
+        GreaterEqual => GreaterEqual,
+        And => And,
+        Or => Or,
+        BitAnd => BitAnd,
+        BitOr => BitOr,
+        BitXor => BitXor,
+        ShiftLeft => ShiftLeft,
+        ShiftRight => ShiftRight,
+        // 키워드, 리터럴, 괄호·구분 기호는 문법이 이미 쓰고 있습니다.
+        _ => return Err(messages::tr("operator.reserved", &[&spelling])),
+    };
+    Ok(Spelling::Token(token))
+}
+
+fn token_of(spelling: &Spelling) -> TokenKind<'static> {
+    match spelling {
+        Spelling::Token(token) => token.clone(),
+        Spelling::Word(word) => TokenKind::Identifier(*word),
+    }
+}
@@ -0,0 +1,203 @@
+// pretty.rs
+// 런타임 값의 구조 덤프 (REPL 결과, reflect, 디버거 변수 표시)
+//
+// 모양은 `Value` 의 `Debug` 와 같지만(`Integer(3)`, `String("a")`) 두 가지를 막습니다.
+//   - 순환: 멈춘 제너레이터는 자기 호출 환경을 붙잡고, 그 환경이 다시 제너레이터를 담을 수 있습니다 (heap.rs).
+//     지금 펼치고 있는 제너레이터를 다시 만나면 `<cycle>` 로 씁니다. 같은 값이 나란히 두 번 나오는 것은 순환이 아닙니다.
+//   - 깊이: `MAX_DEPTH` 단계보다 깊은 값은 `…` 로 줄입니다.
+// 제너레이터는 가장 안쪽 프레임 환경의 바인딩만 보여 줍니다. 바깥(전역) 환경까지 펼치면 프로그램 전체가 나옵니다.
+// 실행 중이라 빌릴 수 없는 제너레이터는 `<running>` 입니다.
+//
+// `dump` 는 한 줄, `pretty` 는 한 줄이 `WIDTH` 를 넘는 묶음만 여러 줄로 펼칩니다.
+//
+//   Record(process {
+//       status: Integer(0),
+//       stdout: String("…"),
+//   })
+
+use alloc::rc::Rc;
+
+use crate::compat::*;
+use crate::data_structures::Value;
+
+/// 펼치는 최대 깊이
+pub const MAX_DEPTH: usize = 6;
+/// `pretty` 가 한 줄에 두는 최대 글자 수
+pub const WIDTH: usize = 80;
+
+const INDENT: &str = "    ";
+
+/// 한 줄 덤프
+pub fn dump(value: &Value) -> String {
+    let mut out = String::new();
+    Dumper::default().node(value, 0).flat(&mut out);
+    out
+}
+
+/// 길면 여러 줄로 펼치는 덤프
+pub fn pretty(value: &Value) -> String {
+    let mut out = String::new();
+    Dumper::default().node(value, 0).render(&mut out, 0);
+    out
+}
+
+enum Node {
+    Leaf(String),
+    /// `Return(` 값 `)` 처럼 값 하나를 감쌉니다.
+    Wrap(&'static str, Box<Node>, &'static str),
+    /// `head {` 이름: 값, ... `}` + tail
+    Group { head: String, entries: Vec<(String, Node)>, tail: &'static str },
+}
+
+impl Node {
+    fn width(&self) -> usize {
+        match self {
+            Node::Leaf(text) => text.chars().count(),
+            Node::Wrap(open, inner, close) => open.len() + inner.width() + close.len(),
+            Node::Group { head, entries, tail } => {
+                let inner: usize = entries.iter().map(|(name, node)| name.chars().count() + 2 + node.width() + 2).sum();
+                head.chars().count() + 4 + inner + tail.len()
+            }
+        }
+    }
+
+    fn flat(&self, out: &mut String) {
+        match self {
+            Node::Leaf(text) => out.push_str(text),
+            Node::Wrap(open, inner, close) => {
+                out.push_str(open);
+                inner.flat(out);
+                out.push_str(close);
+            }
+            Node::Group { head, entries, tail } => {
+                out.push_str(head);
+                if entries.is_empty() {
+                    out.push_str(" {}");
+                } else {
+                    out.push_str(" { ");
+                    for (i, (name, node)) in entries.iter().enumerate() {
+                        if i > 0 {
+                            out.push_str(", ");
+                        }
+                        out.push_str(name);
+                        out.push_str(": ");
+                        node.flat(out);
+                    }
+                    out.push_str(" }");
+                }
+                out.push_str(tail);
+            }
+        }
+    }
+
+    fn render(&self, out: &mut String, indent: usize) {
+        if indent * INDENT.len() + self.width() <= WIDTH {
+            return self.flat(out);
+        }
+        match self {
+            Node::Leaf(_) => self.flat(out),
+            Node::Wrap(open, inner, close) => {
+                out.push_str(open);
+                inner.render(out, indent);
+                out.push_str(close);
+            }
+            Node::Group { head, entries, tail } => {
+                out.push_str(head);
+                out.push_str(" {\n");
+                for (name, node) in entries {
+                    out.push_str(&INDENT.repeat(indent + 1));
+                    out.push_str(name);
+                    out.push_str(": ");
+                    node.render(out, indent + 1);
+                    out.push_str(",\n");
+                }
+                out.push_str(&INDENT.repeat(indent));
+                out.push('}');
+                out.push_str(tail);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Dumper {
+    /// 지금 펼치고 있는 제너레이터들 (바깥부터)
+    open: Vec<usize>,
+}
+
+impl Dumper {
+    fn node(&mut self, value: &Value, depth: usize) -> Node {
+        if depth > MAX_DEPTH {
+            return Node::Leaf("…".into());
+        }
+        match value {
+            Value::Return(inner) => Node::Wrap("Return(", Box::new(self.node(inner, depth + 1)), ")"),
+            Value::Function(function) => {
+                let parameters: Vec<String> = function.parameters.iter().map(|p| p.to_string()).collect();
+                Node::Leaf(format!("Function(fn#{}({}))", function.id, parameters.join(", ")))
+            }
+            Value::Record(record) => Node::Group {
+                head: format!("Record({}", record.name),
+                entries: record.fields.iter().map(|(name, field)| (name.clone(), self.node(field, depth + 1))).collect(),
+                tail: ")",
+            },
+            Value::Array(items) => Node::Group {
+                head: "Array".into(),
+                entries: items.iter().enumerate().map(|(i, item)| (i.to_string(), self.node(item, depth + 1))).collect(),
+                tail: "",
+            },
+            Value::Map(map) => Node::Group {
+                head: "Map".into(),
+                entries: map.iter().map(|(key, value)| (key.to_string(), self.node(value, depth + 1))).collect(),
+                tail: "",
+            },
+            Value::EnumVariant(variant) => match &variant.fields {
+                Some(fields) if !fields.is_empty() => Node::Group {
+                    head: format!("{}::{}", variant.enum_name, variant.name),
+                    entries: fields.iter().enumerate().map(|(i, field)| (i.to_string(), self.node(field, depth + 1))).collect(),
+                    tail: "",
+                },
+                _ => Node::Leaf(format!("{}::{}", variant.enum_name, variant.name)),
+            },
+            Value::Generator(state) => {
+                let key = Rc::as_ptr(state) as usize;
+                if self.open.contains(&key) {
+                    return Node::Leaf("Generator(<cycle>)".into());
+                }
+                let Ok(state) = state.try_borrow() else {
+                    return Node::Leaf("Generator(<running>)".into());
+                };
+                if state.is_finished() {
+                    return Node::Leaf("Generator(finished)".into());
+                }
+                let Some(env) = state.environments().last().cloned() else {
+                    return Node::Leaf("Generator(suspended)".into());
+                };
+                drop(state);
+                let Ok(env) = env.try_borrow() else {
+                    return Node::Leaf("Generator(<running>)".into());
+                };
+                let mut bindings: Vec<(String, &Value)> =
+                    env.store.iter().map(|(name, value)| (name.to_string(), value)).collect();
+                if let Some(names) = &env.slot_names {
+                    for (name, slot) in names.iter().zip(&env.slots) {
+                        if let Some(value) = slot {
+                            bindings.push((name.to_string(), value));
+                        }
+                    }
+                }
+                bindings.sort_by(|a, b| a.0.cmp(&b.0));
+                self.open.push(key);
+                let entries = bindings.into_iter().map(|(name, value)| (name, self.node(value, depth + 1))).collect();
+                self.open.pop();
+                Node::Group { head: "Generator(suspended".into(), entries, tail: ")" }
+            }
+            Value::WeakRef(target) => match target.upgrade() {
+                Some(state) => Node::Wrap("WeakRef(", Box::new(self.node(&Value::Generator(state), depth + 1)), ")"),
+                None => Node::Leaf("WeakRef(collected)".into()),
+            },
+            // 나머지는 다른 값을 담지 않으므로 `Debug` 그대로입니다.
+            other => Node::Leaf(format!("{:?}", other)),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// profile.rs
+// 프로필 기반 최적화용 실행 프로필 (high build --profile-generate / --profile-use)
+//
+// 프로필을 켠 런타임은 실행하면서 분기, 루프, 호출 횟수를 셉니다. 키는 소스의 문자 범위이므로 프로필은
+// 그것을 만든 소스에만 맞습니다. 파일에 소스 해시를 적어 두고, 다른 소스를 컴파일할 때 주면 컴파일러가
+// 경고(E0098)하고 쓰지 않습니다. 프로필을 켠 런타임도 커버리지처럼 함수를 바이트코드로 올리지 않습니다.
+//
+//   branch   if 문 (문장 범위)        then 쪽으로 간 횟수, else 쪽(또는 건너뛴) 횟수
+//   loop     while/for 본문 (본문 범위) 루프에 들어간 횟수, 본문을 실행한 횟수
+//   call     함수 본문 (본문 범위)     호출 횟수
+//
+// 옵티마이저가 쓰는 곳 (optimizer.rs)
+//   인라이닝     `HOT_CALLS` 번 이상 불린 함수는 `@inline` 이 없어도 호출 지점에 펼칩니다.
+//   블록 배치    else 쪽으로 더 많이 간 if-else 는 조건을 뒤집어 자주 가는 쪽을 then(먼저 놓이는 쪽)에 둡니다.
+//   루프 펼치기  한 번도 들어가지 않은 루프는 펼치지 않고, 본문을 `HOT_ITERATIONS` 번 이상 돈 루프는 한도를 두 배로 둡니다.
+//
+// 파일 형식: trace.rs 처럼 한 줄에 한 항목, 탭으로 나눕니다.
+//   high-profile 1
+//   source  <hash>                         프로필을 만든 소스의 FNV-1a 해시 (16진수)
+//   branch  <start> <end> <taken> <not_taken>
+//   loop    <start> <end> <entries> <iterations>
+//   call    <start> <end> <calls>
+
+use alloc::collections::BTreeMap;
+
+use crate::compat::*;
+use crate::data_structures::Span;
+
+/// 이만큼 이상 불린 함수는 뜨거운 함수입니다.
+pub const HOT_CALLS: u64 = 100;
+/// 본문을 이만큼 이상 실행한 루프는 뜨거운 루프입니다.
+pub const HOT_ITERATIONS: u64 = 1000;
+/// `--profile-generate` 의 기본 출력 파일
+pub const DEFAULT_PATH: &str = "high.profile";
+
+const HEADER: &str = "high-profile 1";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchCounts {
+    pub taken: u64,
+    pub not_taken: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoopCounts {
+    pub entries: u64,
+    pub iterations: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    /// 프로필을 만든 소스의 해시. 실행 중에는 없고 파일로 쓸 때 붙입니다.
+    pub source: Option<u64>,
+    branches: BTreeMap<(usize, usize), BranchCounts>,
+    loops: BTreeMap<(usize, usize), LoopCounts>,
+    calls: BTreeMap<(usize, usize), u64>,
+}
+
+impl Profile {
+    // ─── 실행 중 ─────────────────────────────
+
+    pub fn branch(&mut self, span: Span, taken: bool) {
+        let counts = self.branches.entry((span.start, span.end)).or_default();
+        if taken {
+            counts.taken += 1;
+        } else {
+            counts.not_taken += 1;
+        }
+    }
+
+    pub fn enter_loop(&mut self, body: Span) {
+        self.loops.entry((body.start, body.end)).or_default().entries += 1;
+    }
+
+    pub fn iterate(&mut self, body: Span) {
+        self.loops.entry((body.start, body.end)).or_default().iterations += 1;
+    }
+
+    pub fn call(&mut self, body: Span) {
+        *self.calls.entry((body.start, body.end)).or_insert(0) += 1;
+    }
+
+    // ─── 옵티마이저 ─────────────────────────────
+
+    /// 프로필이 이 소스로 만들어졌는지
+    pub fn matches(&self, source: &str) -> bool {
+        self.source == Some(source_hash(source))
+    }
+
+    /// 실행되지 않은 if 문이면 None 입니다.
+    pub fn branch_counts(&self, span: Span) -> Option<BranchCounts> {
+        self.branches.get(&(span.start, span.end)).copied()
+    }
+
+    /// 본문이 `body` 인 루프. 실행되지 않은 루프면 None 입니다.
+    pub fn loop_counts(&self, body: Span) -> Option<LoopCounts> {
+        self.loops.get(&(body.start, body.end)).copied()
+    }
+
+    pub fn calls(&self, body: Span) -> u64 {
+        self.calls.get(&(body.start, body.end)).copied().unwrap_or(0)
+    }
+
+    pub fn is_hot_call(&self, body: Span) -> bool {
+        self.calls(body) >= HOT_CALLS
+    }
+
+    /// 센 if 문, 루프, 함수의 수
+    pub fn sites(&self) -> (usize, usize, usize) {
+        (self.branches.len(), self.loops.len(), self.calls.len())
+    }
+
+    // ─── 파일 ─────────────────────────────
+
+    pub fn encode(&self) -> String {
+        let mut out = format!("{}\n", HEADER);
+        if let Some(hash) = self.source {
+            out.push_str(&format!("source\t{:016x}\n", hash));
+        }
+        for ((start, end), counts) in &self.branches {
+            out.push_str(&format!("branch\t{}\t{}\t{}\t{}\n", start, end, counts.taken, counts.not_taken));
+        }
+        for ((start, end), counts) in &self.loops {
+            out.push_str(&format!("loop\t{}\t{}\t{}\t{}\n", start, end, counts.entries, counts.iterations));
+        }
+        for ((start, end), calls) in &self.calls {
+            out.push_str(&format!("call\t{}\t{}\t{}\n", start, end, calls));
+        }
+        out
+    }
+
+    pub fn decode(text: &str) -> Result<Profile, String> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, l)| l) != Some(HEADER) {
+            return Err(format!("프로필 파일이 아닙니다. (첫 줄이 '{}' 이어야 합니다)", HEADER));
+        }
+        let mut profile = Profile::default();
+        for (number, line) in lines {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let bad = || format!("{}번째 줄을 읽을 수 없습니다: {}", number + 1, line);
+            let index = |text: &str| text.parse::<usize>().map_err(|_| bad());
+            let count = |text: &str| text.parse::<u64>().map_err(|_| bad());
+            match fields.as_slice() {
+                ["source", hash] => profile.source = Some(u64::from_str_radix(hash, 16).map_err(|_| bad())?),
+                ["branch", start, end, taken, not_taken] => {
+                    let counts = BranchCounts { taken: count(taken)?, not_taken: count(not_taken)? };
+                    profile.branches.insert((index(start)?, index(end)?), counts);
+                }
+                ["loop", start, end, entries, iterations] => {
+                    let counts = LoopCounts { entries: count(entries)?, iterations: count(iterations)? };
+                    profile.loops.insert((index(start)?, index(end)?), counts);
+                }
+                ["call", start, end, calls] => {
+                    profile.calls.insert((index(start)?, index(end)?), count(calls)?);
+                }
+                [""] => {}
+                _ => return Err(bad()),
+            }
+        }
+        Ok(profile)
+    }
+}
+
+/// 소스의 FNV-1a 해시. 프로필 파일은 컴파일러 버전이 바뀌어도 남으므로 표준 라이브러리 해셔 대신 고정된 함수를 씁니다.
+pub fn source_hash(source: &str) -> u64 {
+    source.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
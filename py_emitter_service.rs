@@ -0,0 +1,722 @@
+// py_emitter_service.rs
+// HIR 을 Python 3 소스로 옮기는 백엔드입니다. Python 만 설치된 교육 환경에서 High 프로그램을 돌리기 위한 것입니다.
+//
+// `CompileOptions::target_platform = "python"` 이면 선택되고, `--run-python` 은 생성된 코드를
+// Python 인터프리터의 표준 입력으로 넘겨 실행합니다.
+//
+// 옮기는 방식:
+//   - 함수는 `def` 가 됩니다. 식 안의 함수 리터럴은 바로 앞에 `def _fn1(..)` 로 꺼내 둡니다.
+//     클로저는 Python 클로저가 그대로 맡습니다.
+//   - 블록 스코프가 없는 Python 에서 안쪽 `let` 이 바깥 변수를 가리지 않도록, 바깥에 같은 이름이 있으면 새 이름을 붙입니다.
+//   - defer 는 블록의 나머지를 `try:` 로 감싸고 `finally:` 에서 실행합니다.
+//   - 타입이 알려진 연산은 Python 연산자로 쓰고(정수는 `_wrap` 으로 i64 넘침을 맞춤),
+//     모르는 연산은 operators.rs 와 같은 의미의 `_binary` / `_unary` 도우미를 부릅니다.
+//   - `format(...)` 서식 문자열은 컴파일할 때 검증하고 조각별 코드로 펼칩니다.
+//
+// 실행 오류 메시지는 생성할 때의 로케일로 고정되며, 오류가 나면 종료 코드 101 로 끝납니다.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::process::Command;
+use std::time::Instant;
+
+use crate::cancellation::{run_command, CancellationToken, CommandError};
+use crate::data_structures::{Diagnostic, Span, TokenKind, Value};
+use crate::error_codes;
+use crate::exit_code;
+use crate::executor_service::{ExecutionResult, ExecutionStatus};
+use crate::formatting::{self, Align, FormatSpec, Radix, Segment};
+use crate::hir::{self, Block, Expr, ExprKind, Stmt, Type};
+use crate::interner::Symbol;
+use crate::messages;
+use crate::operators;
+
+/// 이 백엔드를 고르는 `target_platform` 값
+pub const TARGET: &str = "python";
+
+/// 생성된 Python 소스를 쓰는 파일
+pub const OUTPUT_PATH: &str = "compiled.py";
+/// 생성된 코드를 실행하는 Python 인터프리터
+pub const INTERPRETER: &str = if cfg!(target_os = "windows") { "python" } else { "python3" };
+
+/// 진단 메시지에 쓰는 백엔드 이름
+const BACKEND: &str = "Python";
+
+pub struct PyEmitterService;
+
+impl PyEmitterService {
+    /// HIR 프로그램을 Python 소스로 옮깁니다. 옮길 수 없는 구문이 있으면 그 진단들을 돌려줍니다.
+    pub fn emit(program: &hir::Program) -> Result<String, Vec<Diagnostic>> {
+        let mut emitter = Emitter {
+            scopes: vec![HashMap::new()],
+            depth: 0,
+            fresh: 0,
+            out: String::new(),
+            indent: 0,
+            diagnostics: vec![],
+        };
+        emitter.statements(&program.statements);
+        if !emitter.diagnostics.is_empty() {
+            return Err(emitter.diagnostics);
+        }
+        Ok(format!("{}\n# ─── 프로그램 ───\n\n{}", prelude(), emitter.out))
+    }
+
+    /// 생성된 소스를 Python 인터프리터의 표준 입력으로 넘겨 실행합니다. 취소되면 인터프리터를 종료합니다.
+    pub fn run(source: &str, token: &CancellationToken) -> Result<ExecutionResult, String> {
+        let start = Instant::now();
+        let output = match run_command(Command::new(INTERPRETER).arg("-"), Some(source.as_bytes()), token) {
+            Ok(output) => output,
+            Err(CommandError::Cancelled) => {
+                return Ok(ExecutionResult {
+                    output_log: vec![error_codes::CANCELLED.message("", &[])],
+                    status: ExecutionStatus::Cancelled,
+                    execution_time_ms: start.elapsed().as_millis(),
+                    exit_code: exit_code::CANCELLED,
+                    gc: None,
+                })
+            }
+            Err(CommandError::Io(e)) => return Err(format!("{} 실행 실패: {}", INTERPRETER, e)),
+        };
+
+        let mut output_log: Vec<String> = String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect();
+        output_log.extend(String::from_utf8_lossy(&output.stderr).lines().map(String::from));
+        Ok(ExecutionResult {
+            output_log,
+            status: if exit_code::is_failure(output.status.code()) {
+                ExecutionStatus::RuntimeError
+            } else {
+                ExecutionStatus::Success
+            },
+            execution_time_ms: start.elapsed().as_millis(),
+            exit_code: output.status.code().unwrap_or(exit_code::RUNTIME_ERROR),
+            gc: None,
+        })
+    }
+}
+
+// ─── 코드 생성 ─────────────────────────────
+
+struct Emitter {
+    /// High 이름 → Python 이름 (바깥 스코프부터)
+    scopes: Vec<HashMap<Symbol, String>>,
+    /// 지금 생성 중인 함수 중첩 깊이. 0 이면 모듈 최상위입니다.
+    depth: usize,
+    /// 새 이름과 꺼낸 함수에 붙이는 번호
+    fresh: usize,
+    out: String,
+    indent: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Emitter {
+    fn line(&mut self, text: &str) {
+        let _ = writeln!(self.out, "{}{}", "    ".repeat(self.indent), text);
+    }
+
+    fn unsupported(&mut self, span: Span, construct: &str) {
+        self.diagnostics.push(error_codes::BACKEND_UNSUPPORTED.diagnostic(span, "", &[&BACKEND, &construct]));
+    }
+
+    /// 들여쓴 코드 묶음을 만듭니다. 아무 줄도 나오지 않으면 `pass` 를 둡니다.
+    fn suite(&mut self, f: impl FnOnce(&mut Self)) {
+        self.indent += 1;
+        let before = self.out.len();
+        f(self);
+        if self.out.len() == before {
+            self.line("pass");
+        }
+        self.indent -= 1;
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn lookup(&self, name: Symbol) -> String {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name)).cloned().unwrap_or_else(|| ident(name))
+    }
+
+    /// `let` 이 새로 묶는 이름입니다. 바깥 스코프에 같은 이름이 보이면 가리지 않도록 새 이름을 씁니다.
+    fn bind(&mut self, name: Symbol) -> String {
+        let (innermost, outer) = self.scopes.split_last_mut().expect("스코프가 있어야 합니다");
+        if let Some(existing) = innermost.get(&name) {
+            return existing.clone();
+        }
+        let python = if outer.iter().any(|scope| scope.contains_key(&name)) {
+            self.fresh += 1;
+            format!("{}__{}", ident(name), self.fresh)
+        } else {
+            ident(name)
+        };
+        innermost.insert(name, python.clone());
+        python
+    }
+
+    // ─── 문장 ─────────────────────────────
+
+    fn statements(&mut self, statements: &[Stmt]) {
+        for (i, stmt) in statements.iter().enumerate() {
+            if let Stmt::Defer(body, _) = stmt {
+                // 블록의 나머지가 어떻게 끝나든 defer 본문이 실행됩니다.
+                self.line("try:");
+                self.suite(|e| e.statements(&statements[i + 1..]));
+                self.line("finally:");
+                self.suite(|e| e.block(body));
+                return;
+            }
+            self.statement(stmt);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.scoped(|e| e.statements(&block.statements));
+    }
+
+    fn statement(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expr(expr) => {
+                let code = self.expression(expr);
+                self.line(&code);
+            }
+            Stmt::Let { name, value: Expr { kind: ExprKind::Function(parameters, body), .. }, .. } => {
+                // 재귀 호출이 자기 이름을 찾을 수 있도록 본문보다 먼저 묶습니다.
+                let python = self.bind(*name);
+                self.function(&python, parameters, body);
+            }
+            Stmt::Let { name, value, .. } => {
+                let code = self.expression(value);
+                let python = self.bind(*name);
+                self.line(&format!("{} = {}", python, code));
+            }
+            Stmt::Return(expr, _) => {
+                let code = self.expression(expr);
+                if self.depth > 0 {
+                    self.line(&format!("return {}", code));
+                } else {
+                    // 최상위 return 은 프로그램의 결과입니다.
+                    self.line(&format!("_finish({})", code));
+                }
+            }
+            Stmt::Yield(expr, span) => {
+                if self.depth == 0 {
+                    self.diagnostics.push(error_codes::YIELD_OUTSIDE_GENERATOR.diagnostic(*span, "", &[]));
+                }
+                let code = self.expression(expr);
+                self.line(&format!("yield {}", code));
+            }
+            // 보통은 `statements` 가 블록의 나머지와 함께 처리합니다.
+            Stmt::Defer(..) => self.statements(std::slice::from_ref(stmt)),
+            Stmt::Block(block) => self.block(block),
+            Stmt::Enum { span, .. } => self.unsupported(*span, "enum"),
+            Stmt::ForIn { span, .. } => self.unsupported(*span, "for-in"),
+            Stmt::If { condition, then_branch, else_branch, .. } => {
+                let code = self.condition(condition);
+                self.line(&format!("if {}:", code));
+                self.suite(|e| e.block(then_branch));
+                if let Some(else_branch) = else_branch {
+                    self.line("else:");
+                    self.suite(|e| e.block(else_branch));
+                }
+            }
+            Stmt::While { condition, body, .. } => {
+                let code = self.condition(condition);
+                self.line(&format!("while {}:", code));
+                self.suite(|e| e.block(body));
+            }
+        }
+    }
+
+    fn function(&mut self, python: &str, parameters: &[Symbol], body: &Block) {
+        self.scopes.push(parameters.iter().map(|p| (*p, ident(*p))).collect());
+        let params: Vec<String> = parameters.iter().map(|p| ident(*p)).collect();
+        self.line(&format!("def {}({}):", python, params.join(", ")));
+        self.depth += 1;
+        self.suite(|e| e.block(body));
+        self.depth -= 1;
+        self.scopes.pop();
+    }
+
+    // ─── 식 ─────────────────────────────
+
+    /// 조건식은 `true` 일 때만 참입니다. bool 로 알려진 식은 그대로 씁니다.
+    fn condition(&mut self, expr: &Expr) -> String {
+        let code = self.expression(expr);
+        if expr.ty == Type::Bool {
+            code
+        } else {
+            format!("{} is True", code)
+        }
+    }
+
+    fn expression(&mut self, expr: &Expr) -> String {
+        if let Some(raw) = self.int_arithmetic(expr) {
+            return format!("_wrap({})", raw);
+        }
+        match &expr.kind {
+            ExprKind::Literal(value) => self.literal(value, expr.span),
+            ExprKind::Variable(name) => self.lookup(*name),
+            ExprKind::Unary(op, operand) => {
+                let code = self.expression(operand);
+                match (op, &operand.ty) {
+                    (TokenKind::Minus, Type::Float) => format!("(-{})", code),
+                    (TokenKind::Bang, Type::Bool) => format!("(not {})", code),
+                    _ => format!("_unary({:?}, {})", operators::symbol(op), code),
+                }
+            }
+            ExprKind::Binary(op, left, right) => self.binary(op, left, right),
+            ExprKind::If(cond, then_expr, else_expr) => {
+                let cond = self.condition(cond);
+                let (then_expr, else_expr) = (self.expression(then_expr), self.expression(else_expr));
+                format!("({} if {} else {})", then_expr, cond, else_expr)
+            }
+            ExprKind::Function(parameters, body) => {
+                self.fresh += 1;
+                let python = format!("_fn{}", self.fresh);
+                self.function(&python, parameters, body);
+                python
+            }
+            ExprKind::Call(callee, args) => self.call(callee, args),
+            ExprKind::TypeOf(inner) => format!("_type_of({})", self.expression(inner)),
+            ExprKind::Reflect(_) => self.construct(expr.span, "reflect"),
+            ExprKind::Eval(_) => self.construct(expr.span, "eval"),
+            ExprKind::Array(_) => self.construct(expr.span, "array"),
+            ExprKind::Map(_) => self.construct(expr.span, "map"),
+            ExprKind::Index(..) => self.construct(expr.span, "index"),
+            ExprKind::Slice(..) => self.construct(expr.span, "slice"),
+            ExprKind::Assign(..) => self.construct(expr.span, "assignment"),
+            ExprKind::Range(..) => self.construct(expr.span, "range"),
+        }
+    }
+
+    fn construct(&mut self, span: Span, name: &str) -> String {
+        self.unsupported(span, name);
+        "None".into()
+    }
+
+    /// 정수 `+ - *` 와 단항 `-` 는 i64 로 감싸기 전의 Python 식으로 모읍니다. 감싸기는 가장 바깥에서 한 번만 하면 됩니다.
+    /// 해당하지 않으면 자식 식을 건드리지 않고 `None` 을 돌려줍니다.
+    fn int_arithmetic(&mut self, expr: &Expr) -> Option<String> {
+        if expr.ty != Type::Int {
+            return None;
+        }
+        match &expr.kind {
+            ExprKind::Binary(op @ (TokenKind::Plus | TokenKind::Minus | TokenKind::Asterisk), left, right)
+                if left.ty == Type::Int && right.ty == Type::Int =>
+            {
+                let left = self.int_operand(left);
+                let right = self.int_operand(right);
+                Some(format!("({} {} {})", left, operators::symbol(op), right))
+            }
+            ExprKind::Unary(TokenKind::Minus, operand) if operand.ty == Type::Int => {
+                Some(format!("(-{})", self.int_operand(operand)))
+            }
+            _ => None,
+        }
+    }
+
+    fn int_operand(&mut self, expr: &Expr) -> String {
+        match self.int_arithmetic(expr) {
+            Some(raw) => raw,
+            None => self.expression(expr),
+        }
+    }
+
+    fn binary(&mut self, op: &TokenKind, left: &Expr, right: &Expr) -> String {
+        use TokenKind::{And, Asterisk, Eq, Greater, GreaterEqual, Less, LessEqual, Minus, Neq, Or, Percent, Plus, Slash};
+
+        let symbol = operators::symbol(op);
+        let (l, r) = (self.expression(left), self.expression(right));
+        let native = match (op, &left.ty, &right.ty) {
+            (Slash, Type::Int, Type::Int) => return format!("_div({}, {})", l, r),
+            (Percent, Type::Int, Type::Int) => return format!("_rem({}, {})", l, r),
+            (Slash, Type::Float, Type::Float) => return format!("_fdiv({}, {})", l, r),
+            (Plus | Minus | Asterisk, Type::Float, Type::Float) => Some(symbol),
+            (Plus, Type::String, Type::String) => Some(symbol),
+            (Eq | Neq | Less | Greater | LessEqual | GreaterEqual, Type::Int, Type::Int)
+            | (Eq | Neq | Less | Greater | LessEqual | GreaterEqual, Type::Float, Type::Float) => Some(symbol),
+            (Eq | Neq, Type::String, Type::String) | (Eq | Neq, Type::Bool, Type::Bool) => Some(symbol),
+            (And, Type::Bool, Type::Bool) => Some("and"),
+            (Or, Type::Bool, Type::Bool) => Some("or"),
+            _ => None,
+        };
+        match (native, op) {
+            (Some(python), _) => format!("({} {} {})", l, python, r),
+            // 오른쪽 식은 왼쪽 값으로 결과가 정해지지 않을 때만 계산합니다.
+            (None, And) => format!("_and({}, lambda: {})", l, r),
+            (None, Or) => format!("_or({}, lambda: {})", l, r),
+            (None, _) => format!("_binary({:?}, {}, {})", symbol, l, r),
+        }
+    }
+
+    fn literal(&mut self, value: &Value, span: Span) -> String {
+        match value {
+            Value::Integer(i) => i.to_string(),
+            Value::Float(f) if f.is_nan() => "float(\"nan\")".into(),
+            Value::Float(f) if f.is_infinite() => format!("float(\"{}inf\")", if *f < 0.0 { "-" } else { "" }),
+            Value::Float(f) => format!("{:?}", f),
+            Value::Boolean(true) => "True".into(),
+            Value::Boolean(false) => "False".into(),
+            Value::String(s) => py_string(s),
+            Value::Null => "None".into(),
+            other => self.construct(span, &formatting::display_value(other)),
+        }
+    }
+
+    fn call(&mut self, callee: &Expr, args: &[Expr]) -> String {
+        // 같은 이름의 지역 바인딩이 없을 때만 내장 함수입니다.
+        let builtin = match &callee.kind {
+            ExprKind::Variable(name) if !self.scopes.iter().any(|scope| scope.contains_key(name)) => Some(name.as_str()),
+            _ => None,
+        };
+        match builtin {
+            Some("format") => return self.format(args, callee.span),
+            Some(name @ ("panic" | "assert")) => {
+                let args: Vec<String> = args.iter().map(|a| self.expression(a)).collect();
+                return format!("_{}({})", name, args.join(", "));
+            }
+            _ => {}
+        }
+        let callee = self.expression(callee);
+        let args: Vec<String> = args.iter().map(|a| self.expression(a)).collect();
+        format!("{}({})", callee, args.join(", "))
+    }
+
+    /// 서식 문자열을 검증한 뒤 텍스트와 자리표시자를 이어 붙이는 식으로 펼칩니다.
+    fn format(&mut self, args: &[Expr], span: Span) -> String {
+        let Some((template, rest)) = args.split_first() else {
+            self.diagnostics.push(error_codes::ARITY_MISMATCH.diagnostic(span, "format", &[]));
+            return "None".into();
+        };
+        let ExprKind::Literal(Value::String(text)) = &template.kind else {
+            self.diagnostics.push(error_codes::BACKEND_UNSUPPORTED.diagnostic(template.span, "format_template", &[&BACKEND]));
+            return "None".into();
+        };
+        let segments = match formatting::parse_template(text) {
+            Ok(segments) => segments,
+            Err(e) => {
+                self.diagnostics.push(error_codes::INVALID_FORMAT.at(template.span, e));
+                return "None".into();
+            }
+        };
+
+        let values: Vec<String> = rest.iter().map(|a| self.expression(a)).collect();
+        // 변수와 리터럴은 그대로 쓰고, 그 밖의 인자는 한 번씩만 계산되도록 람다 매개변수에 묶습니다.
+        let inline = rest.iter().all(|a| matches!(a.kind, ExprKind::Variable(_) | ExprKind::Literal(_)));
+        let mut used = vec![false; values.len()];
+        let mut parts = vec![];
+        for segment in &segments {
+            match segment {
+                Segment::Text(text) => parts.push(py_string(text)),
+                Segment::Placeholder { index, spec } => {
+                    let Some(slot) = used.get_mut(*index) else {
+                        self.diagnostics.push(error_codes::INVALID_FORMAT.diagnostic(
+                            template.span,
+                            "missing_argument",
+                            &[index, &values.len()],
+                        ));
+                        continue;
+                    };
+                    *slot = true;
+                    let value = if inline { values[*index].clone() } else { format!("_a{}", index) };
+                    parts.push(if *spec == FormatSpec::default() {
+                        format!("_show({})", value)
+                    } else {
+                        format!("_spec({}, {})", value, spec_args(spec))
+                    });
+                }
+            }
+        }
+        if let Some(unused) = used.iter().position(|u| !u) {
+            self.diagnostics.push(error_codes::INVALID_FORMAT.diagnostic(span, "unused_argument", &[&unused]));
+        }
+        let body = if parts.is_empty() { "\"\"".to_string() } else { parts.join(" + ") };
+        let params: Vec<String> = (0..values.len()).map(|i| format!("_a{}", i)).collect();
+        if inline {
+            body
+        } else {
+            format!("(lambda {}: {})({})", params.join(", "), body, values.join(", "))
+        }
+    }
+}
+
+/// `_spec` 도우미에 넘기는 서식 지정자 인자: fill, align, zero_pad, width, precision, radix
+fn spec_args(spec: &FormatSpec) -> String {
+    let align = match spec.align {
+        None => "None",
+        Some(Align::Left) => "\"<\"",
+        Some(Align::Right) => "\">\"",
+        Some(Align::Center) => "\"^\"",
+    };
+    let optional = |n: Option<usize>| n.map_or("None".to_string(), |n| n.to_string());
+    let radix = match spec.radix {
+        Radix::Decimal => "\"\"",
+        Radix::LowerHex => "\"x\"",
+        Radix::UpperHex => "\"X\"",
+    };
+    format!(
+        "{}, {}, {}, {}, {}, {}",
+        py_string(&spec.fill.to_string()),
+        align,
+        if spec.zero_pad { "True" } else { "False" },
+        optional(spec.width),
+        optional(spec.precision),
+        radix
+    )
+}
+
+/// Python 문자열 리터럴
+fn py_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// High 식별자를 Python 식별자로 옮깁니다. 키워드와 생성된 코드가 쓰는 이름에는 `_` 를 붙입니다.
+fn ident(name: Symbol) -> String {
+    const RESERVED: &[&str] = &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def",
+        "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+        // 생성된 코드가 가져오는 모듈
+        "sys", "math", "Decimal",
+    ];
+    match name.as_str() {
+        n if RESERVED.contains(&n) => format!("{}_", n),
+        // 밑줄로 시작하는 이름은 런타임 도우미가 씁니다.
+        n if n.starts_with('_') => format!("u{}", n),
+        n => n.to_string(),
+    }
+}
+
+// ─── 생성된 런타임 ─────────────────────────────
+
+/// 생성된 코드 앞에 붙는 런타임 도우미. 메시지는 지금 로케일의 카탈로그 문구로 채웁니다.
+fn prelude() -> String {
+    let quoted = |key: &str, args: &[&dyn std::fmt::Display]| py_string(&messages::tr(key, args));
+    PRELUDE
+        .replace("@PANIC@", &quoted("E0030", &[&"{0}"]))
+        .replace("@EXPLICIT@", &quoted("E0030.explicit", &[]))
+        .replace("@DIVISION_BY_ZERO@", &quoted("E0030.division_by_zero", &[]))
+        .replace("@ASSERT_FAILED@", &quoted("E0030.assert_failed", &[]))
+        .replace("@ASSERT_FAILED_MESSAGE@", &quoted("E0030.assert_failed_message", &[&"{0}"]))
+        .replace("@ASSERT_NOT_BOOL@", &quoted("E0030.assert_not_bool", &[&"{0}"]))
+        .replace("@OPERATOR@", &quoted("E0010.operator", &[&"{0}", &"{1}", &"{2}"]))
+        .replace("@UNARY_OPERATOR@", &quoted("E0010.unary_operator", &[&"{0}", &"{1}"]))
+        .replace("@HEX@", &quoted("E0020.hex_non_integer", &[&"{0}"]))
+}
+
+const PRELUDE: &str = r#"# 생성된 파일입니다: High 소스에서 만든 Python 3 프로그램
+import math
+import sys
+from decimal import Decimal
+
+# ─── 런타임 (operators.rs 와 같은 의미) ───
+
+def _fail(message):
+    print(@PANIC@.format(message), file=sys.stderr)
+    sys.exit(101)
+
+
+def _wrap(n):
+    # High 정수는 i64 이고 넘치면 2의 보수로 감깁니다.
+    n &= 0xFFFFFFFFFFFFFFFF
+    return n - 0x10000000000000000 if n >= 0x8000000000000000 else n
+
+
+def _type_of(v):
+    if v is None:
+        return "void"
+    t = _type_name(v)
+    return t if t in ("int", "float", "bool", "string") else "unknown"
+
+
+def _type_name(v):
+    if v is None:
+        return "null"
+    if isinstance(v, bool):
+        return "bool"
+    if isinstance(v, int):
+        return "int"
+    if isinstance(v, float):
+        return "float"
+    if isinstance(v, str):
+        return "string"
+    if callable(v):
+        return "function"
+    return type(v).__name__
+
+
+def _show(v):
+    if v is None:
+        return "null"
+    if isinstance(v, bool):
+        return "true" if v else "false"
+    if isinstance(v, float):
+        if v != v:
+            return "NaN"
+        if math.isinf(v):
+            return "inf" if v > 0 else "-inf"
+        text = format(Decimal(repr(v)), "f")
+        return text[:-2] if text.endswith(".0") else text
+    return str(v)
+
+
+def _spec(v, fill, align, zero_pad, width, precision, radix):
+    numeric = _type_name(v) in ("int", "float")
+    if radix:
+        if _type_name(v) != "int":
+            _fail(@HEX@.format(_show(v)))
+        body = format(v & 0xFFFFFFFFFFFFFFFF, radix)
+    elif precision is not None and isinstance(v, float):
+        body = format(v, ".%df" % precision)
+    elif precision is not None and isinstance(v, str):
+        body = v[:precision]
+    else:
+        body = _show(v)
+    padding = (width or 0) - len(body)
+    if padding <= 0:
+        return body
+    if zero_pad and align is None and numeric:
+        sign = "-" if body.startswith("-") else ""
+        return sign + "0" * padding + body[len(sign):]
+    align = align or (">" if numeric else "<")
+    if align == "<":
+        return body + fill * padding
+    if align == ">":
+        return fill * padding + body
+    return fill * (padding // 2) + body + fill * (padding - padding // 2)
+
+
+def _div(a, b):
+    if b == 0:
+        _fail(@DIVISION_BY_ZERO@)
+    q = abs(a) // abs(b)
+    return _wrap(q if (a < 0) == (b < 0) else -q)
+
+
+def _rem(a, b):
+    if b == 0:
+        _fail(@DIVISION_BY_ZERO@)
+    q = abs(a) // abs(b)
+    return _wrap(a - b * (q if (a < 0) == (b < 0) else -q))
+
+
+def _fdiv(a, b):
+    if b == 0.0:
+        if a != a or a == 0.0:
+            return float("nan")
+        return math.copysign(math.inf, a) * math.copysign(1.0, b)
+    return a / b
+
+
+_ARITHMETIC = {"+": lambda a, b: a + b, "-": lambda a, b: a - b, "*": lambda a, b: a * b}
+# 시프트 양은 하위 6비트만 씁니다 (operators.rs). 파이썬의 `>>` 는 음수에서도 산술 시프트입니다.
+_BITWISE = {
+    "&": lambda a, b: a & b,
+    "|": lambda a, b: a | b,
+    "^": lambda a, b: a ^ b,
+    "<<": lambda a, b: a << (b & 63),
+    ">>": lambda a, b: a >> (b & 63),
+}
+_COMPARISON = {
+    "==": lambda a, b: a == b,
+    "!=": lambda a, b: a != b,
+    "<": lambda a, b: a < b,
+    ">": lambda a, b: a > b,
+    "<=": lambda a, b: a <= b,
+    ">=": lambda a, b: a >= b,
+}
+
+
+def _binary(op, a, b):
+    ta, tb = _type_name(a), _type_name(b)
+    if ta == tb == "int":
+        if op == "/":
+            return _div(a, b)
+        if op == "%":
+            return _rem(a, b)
+        if op in _ARITHMETIC:
+            return _wrap(_ARITHMETIC[op](a, b))
+        if op in _BITWISE:
+            return _wrap(_BITWISE[op](a, b))
+        if op in _COMPARISON:
+            return _COMPARISON[op](a, b)
+    elif ta == tb == "float":
+        if op == "/":
+            return _fdiv(a, b)
+        if op in _ARITHMETIC:
+            return _ARITHMETIC[op](a, b)
+        if op in _COMPARISON:
+            return _COMPARISON[op](a, b)
+    elif ta == tb == "string":
+        if op == "+":
+            return a + b
+        if op in ("==", "!="):
+            return _COMPARISON[op](a, b)
+    elif ta == tb == "bool":
+        if op in ("==", "!="):
+            return _COMPARISON[op](a, b)
+        if op == "&&":
+            return a and b
+        if op == "||":
+            return a or b
+    _fail(@OPERATOR@.format(op, ta, tb))
+
+
+def _unary(op, a):
+    t = _type_name(a)
+    if op == "-" and t == "int":
+        return _wrap(-a)
+    if op == "-" and t == "float":
+        return -a
+    if op == "!" and t == "bool":
+        return not a
+    if op == "~" and t == "int":
+        return ~a
+    _fail(@UNARY_OPERATOR@.format(op, t))
+
+
+def _and(a, right):
+    return False if a is False else _binary("&&", a, right())
+
+
+def _or(a, right):
+    return True if a is True else _binary("||", a, right())
+
+
+def _panic(message=None):
+    _fail(@EXPLICIT@ if message is None else _show(message))
+
+
+def _assert(condition, message=None):
+    if condition is True:
+        return None
+    if condition is False:
+        _fail(@ASSERT_FAILED@ if message is None else @ASSERT_FAILED_MESSAGE@.format(_show(message)))
+    _fail(@ASSERT_NOT_BOOL@.format(_type_name(condition)))
+
+
+def _finish(result):
+    # 최상위 return 값을 출력하고 끝냅니다. null 은 출력하지 않습니다. 정수면 그 값이 종료 코드입니다. (exit_code.rs)
+    if result is not None:
+        print(_show(result))
+    if type(result) is int:
+        sys.exit(result if -2**31 <= result < 2**31 else 1)
+    sys.exit(0)
+"#;
@@ -0,0 +1,78 @@
+// ranges.rs
+// 정수 범위 값(`Value::Range`)과 for-in 반복의 프로토콜입니다.
+//
+// 범위는 양 끝만 담으므로 `0..1000000` 도 요소를 만들지 않습니다. 끝은 포함하지 않으며, 끝이 시작보다
+// 작거나 같으면 빈 범위입니다. 배열처럼 바뀌지 않는 값입니다.
+//
+//   start..end       리터럴. 양 끝은 int 여야 합니다. (E0010)
+//   start..=end      끝을 포함하는 범위. `start..end + 1` 과 같은 값이며, 끝이 int 최댓값이면 그 값은 빠집니다.
+//   xs[start..end]   배열과 바이트 버퍼에서는 `xs[start:end]` 와 같습니다.
+//   len(r)           요소 수. 빈 범위면 0
+//   a == b           양 끝이 같으면 참
+//
+// for-in 은 값에서 `Cursor` 를 만들고 `next` 가 None 을 줄 때까지 돕니다.
+//
+//   범위         start, start + 1, ..., end - 1
+//   배열         요소를 앞에서부터
+//   제너레이터   다음 `yield` 값을 하나씩. 돌다 멈춘 제너레이터는 남은 값부터 이어서 돕니다.
+
+use core::cell::RefCell;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+use crate::data_structures::Value;
+use crate::ft_runtime::HighEnduranceRuntime;
+use crate::generator::GeneratorState;
+
+/// `start..end` 의 요소 수
+pub fn len(start: i64, end: i64) -> i64 {
+    end.saturating_sub(start).max(0)
+}
+
+/// for-in 이 도는 값의 현재 위치
+#[derive(Debug, Clone)]
+pub enum Cursor {
+    Range { next: i64, end: i64 },
+    Array { items: Arc<[Value]>, index: usize },
+    Generator(Rc<RefCell<GeneratorState>>),
+}
+
+impl Cursor {
+    /// 돌 수 없는 값이면 None 입니다.
+    pub fn new(iterable: &Value) -> Option<Cursor> {
+        match iterable {
+            Value::Range(start, end) => Some(Cursor::Range { next: *start, end: *end }),
+            Value::Array(items) => Some(Cursor::Array { items: items.clone(), index: 0 }),
+            Value::Generator(state) => Some(Cursor::Generator(state.clone())),
+            _ => None,
+        }
+    }
+
+    /// 다음 요소. 제너레이터는 다음 `yield` 까지 실행합니다.
+    pub fn next(&mut self, rt: &mut HighEnduranceRuntime) -> Option<Value> {
+        match self {
+            Cursor::Range { next, end } => {
+                if *next >= *end {
+                    return None;
+                }
+                *next += 1;
+                Some(Value::Integer(*next - 1))
+            }
+            Cursor::Array { items, index } => {
+                let item = items.get(*index).cloned()?;
+                *index += 1;
+                Some(item)
+            }
+            Cursor::Generator(state) => rt.resume_generator(state),
+        }
+    }
+
+    /// 아직 내놓지 않은 요소들을 다시 돌 수 있는 값. 스냅샷은 이 값을 담고 복원할 때 `new` 로 되돌립니다.
+    pub fn remaining(&self) -> Value {
+        match self {
+            Cursor::Range { next, end } => Value::Range(*next, *end),
+            Cursor::Array { items, index } => Value::Array(items[*index..].into()),
+            Cursor::Generator(state) => Value::Generator(state.clone()),
+        }
+    }
+}
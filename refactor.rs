@@ -0,0 +1,346 @@
+// refactor.rs
+// 리팩터링: 이름 바꾸기와 함수 추출 (`high refactor`, 데몬의 rename / codeAction)
+//
+// 결과는 고친 파일 전체가 아니라 바꿀 범위와 새 텍스트의 목록(TextEdit)입니다. 범위는 스팬과 같은 문자 위치이며,
+// 편집끼리 겹치지 않고 시작 위치 순서로 정렬되어 있습니다.
+//
+//   이름 바꾸기  커서 아래의 변수·매개변수·매크로를 찾아, 해석기가 같은 선언에 연결한 모든 참조와 선언 자리를 바꿉니다.
+//                같은 이름의 다른 선언(가림)은 건드리지 않습니다. 새 이름이 파일 어디에든 이미 있으면 참조가 다른
+//                선언에 붙잡힐 수 있으므로 거절합니다.
+//   함수 추출    한 문장 목록 안의 연속한 문장들이나 식 하나를 `let 이름 = fn(매개변수) { ... };` 로 옮기고 그 자리를
+//                호출로 바꿉니다. 선택 밖에서 선언된 지역 이름은 매개변수가 되고, 전역·내장 이름은 그대로 둡니다.
+//                새 함수는 선택을 감싼 최상위 문장 바로 앞에 놓입니다. return/yield/defer 가 있거나, 선택 안에서
+//                선언한 이름을 선택 밖에서 쓰면 옮긴 뒤 동작이 달라지므로 거절합니다.
+
+use crate::ast_printer;
+use crate::data_structures::{Expression, Program, Span, Statement, Token, TokenKind};
+use crate::interner;
+use crate::json::Json;
+use crate::lexer_service::LexerService;
+use crate::navigation::{key_of, tokenize, File};
+use crate::parser_service::ParserService;
+use crate::resolver::SymbolKind;
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+const INDENT: &str = "    ";
+
+/// `span` 을 `new_text` 로 바꿉니다. 빈 범위는 그 위치에 끼워 넣기입니다.
+#[derive(Debug, Clone)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("start", Json::from(self.span.start as u64)),
+            ("end", Json::from(self.span.end as u64)),
+            ("new_text", Json::from(self.new_text.as_str())),
+        ])
+    }
+}
+
+/// 편집 목록을 소스에 적용한 결과
+pub fn apply(source: &str, edits: &[TextEdit]) -> String {
+    let mut text = source.to_string();
+    for edit in edits.iter().rev() {
+        let range = byte_offset(&text, edit.span.start)..byte_offset(&text, edit.span.end);
+        text.replace_range(range, &edit.new_text);
+    }
+    text
+}
+
+pub fn edits_json(edits: &[TextEdit]) -> Json {
+    Json::object([("edits", Json::Array(edits.iter().map(TextEdit::to_json).collect()))])
+}
+
+/// `42` (문자 위치) 또는 `3:7` (1부터 세는 줄:열) 을 문자 위치로 바꿉니다.
+pub fn position(source: &str, text: &str) -> Option<usize> {
+    let Some((line, column)) = text.split_once(':') else {
+        return text.parse().ok();
+    };
+    let (line, column): (usize, usize) = (line.parse().ok()?, column.parse().ok()?);
+    let line_start: usize = source.split('\n').take(line.checked_sub(1)?).map(|l| l.chars().count() + 1).sum();
+    Some(line_start + column.checked_sub(1)?)
+}
+
+/// 파일에 아직 없는 `base`, `base1`, `base2`, ... 중 첫 이름
+pub fn fresh_name(source: &str, base: &str) -> String {
+    let tokens = tokenize(source);
+    (0..).map(|n| if n == 0 { base.to_string() } else { format!("{}{}", base, n) }).find(|name| !mentions(&tokens, name)).unwrap()
+}
+
+// ─── 이름 바꾸기 ─────────────────────────────
+
+/// `offset` 에 있는 이름을 `new_name` 으로 바꾸는 편집
+pub fn rename(source: &str, offset: usize, new_name: &str) -> Result<Vec<TextEdit>, String> {
+    let file = File::analyze(source);
+    let key = file.symbol_at(offset)?;
+    let old_name = key.0.as_str();
+    if old_name == new_name {
+        return Ok(vec![]);
+    }
+    check_identifier(new_name)?;
+    if mentions(&file.tokens, new_name) {
+        return Err(format!("'{}' 는 이미 파일에서 쓰이고 있어 참조가 다른 선언을 가리킬 수 있습니다.", new_name));
+    }
+
+    let mut edits: Vec<TextEdit> = file
+        .declarations
+        .iter()
+        .filter(|d| d.key == key)
+        .map(|d| d.name_span)
+        .chain(file.identifiers.iter().filter(|(.., s)| s.as_ref().map(key_of) == Some(key)).map(|(span, ..)| *span))
+        .map(|span| TextEdit { span, new_text: new_name.to_string() })
+        .collect();
+    edits.sort_by_key(|e| e.span.start);
+    edits.dedup_by_key(|e| e.span.start);
+    Ok(edits)
+}
+
+// ─── 함수 추출 ─────────────────────────────
+
+/// `start..end` 를 새 함수 `name` 으로 옮기는 편집
+pub fn extract_function(source: &str, start: usize, end: usize, name: &str) -> Result<Vec<TextEdit>, String> {
+    check_identifier(name)?;
+    let file = File::analyze(source);
+    if mentions(&file.tokens, name) {
+        return Err(format!("'{}' 는 이미 파일에서 쓰이고 있습니다.", name));
+    }
+    let chars: Vec<char> = source.chars().collect();
+    let selection = trim(&chars, start, end.min(chars.len()));
+    if selection.start >= selection.end {
+        return Err("선택한 범위가 비어 있습니다.".into());
+    }
+
+    let (replaced, body, statement_form) = match statement_run(&file.program, &chars, selection) {
+        Some(run) => {
+            let mut checker = EscapeCheck(None);
+            for stmt in run {
+                checker.visit_statement(stmt);
+            }
+            if let Some(keyword) = checker.0 {
+                return Err(format!("선택 안의 {} 는 추출한 함수 안에서 다른 뜻이 됩니다.", keyword));
+            }
+            let last = extent(&chars, run[run.len() - 1].span());
+            let replaced = Span { start: run[0].span().start, end: after_semicolon(&chars, last.end) };
+            (replaced, text(&chars, replaced), true)
+        }
+        None => {
+            let selected = text(&chars, selection);
+            let printed = match ParserService::new(LexerService::new(&selected)).parse_program().statements.as_slice() {
+                [Statement::ExpressionStatement(expr)] => Some(ast_printer::print_expression(expr)),
+                _ => None,
+            };
+            let mut finder = ExpressionAt { start: selection.start, printed, in_macro: false, found: false };
+            finder.visit_program(&file.program);
+            if !finder.found {
+                return Err("선택이 문장 목록의 연속한 문장이나 식 하나와 맞지 않습니다.".into());
+            }
+            (selection, format!("return {};", selected), false)
+        }
+    };
+
+    // 선택 밖에서 선언된 지역 이름 → 매개변수, 선택 안에서 선언된 이름을 밖에서 쓰면 거절
+    let inside = |span: Span| replaced.start <= span.start && span.end <= replaced.end;
+    let mut parameters: Vec<interner::Symbol> = vec![];
+    for (span, name, symbol) in &file.identifiers {
+        let Some(symbol) = symbol.as_ref().filter(|s| s.kind != SymbolKind::Macro) else {
+            continue;
+        };
+        match (inside(*span), inside(symbol.declaration_span)) {
+            (true, false) if !parameters.contains(name) => parameters.push(*name),
+            (false, true) => return Err(format!("선택 안에서 선언한 '{}' 를 선택 밖({}..{})에서 씁니다.", name, span.start, span.end)),
+            _ => {}
+        }
+    }
+    let parameters: Vec<&str> = parameters.iter().map(|p| p.as_str()).collect();
+    let parameters = parameters.join(", ");
+
+    let top_level = file
+        .program
+        .statements
+        .iter()
+        .map(|stmt| stmt.span())
+        .find(|span| span.start <= replaced.start && replaced.start < span.end.max(span.start + 1))
+        .map_or(replaced.start, |span| span.start);
+    let insert_at = line_start(&chars, top_level);
+    let function = format!(
+        "let {} = fn({}) {{\n{}}};\n\n",
+        name,
+        parameters,
+        reindent(&body, indentation(&chars, replaced.start))
+    );
+    let call = format!("{}({}){}", name, parameters, if statement_form { ";" } else { "" });
+    Ok(vec![
+        TextEdit { span: Span { start: insert_at, end: insert_at }, new_text: function },
+        TextEdit { span: replaced, new_text: call },
+    ])
+}
+
+/// 선택과 정확히 맞는 연속한 문장들. 감싸는 목록이 여럿이면 가장 바깥 목록을 고릅니다.
+fn statement_run<'p>(program: &'p Program, chars: &[char], selection: Span) -> Option<&'p [Statement]> {
+    let mut lists = vec![program.statements.as_slice()];
+    let mut next = 0;
+    while let Some(list) = lists.get(next).copied() {
+        next += 1;
+        for stmt in list {
+            child_lists(stmt, &mut lists);
+        }
+    }
+    lists.into_iter().find_map(|list| {
+        let first = list.iter().position(|s| selection.start <= s.span().start && s.span().start < selection.end)?;
+        let count = list[first..].iter().take_while(|s| extent(chars, s.span()).end <= selection.end).count();
+        let run = &list[first..first + count];
+        // 선택 중 문장이 덮지 않는 곳은 공백과 ';' 뿐이어야 합니다.
+        let covered = |i: usize| run.iter().any(|s| s.span().start <= i && i < extent(chars, s.span()).end);
+        let exact = !run.is_empty()
+            && (selection.start..selection.end).all(|i| covered(i) || chars[i].is_whitespace() || chars[i] == ';');
+        exact.then_some(run)
+    })
+}
+
+/// 문장 안의 블록 문장 목록. 매크로 정의 본문은 확장 전 템플릿이므로 들어가지 않습니다.
+fn child_lists<'p>(stmt: &'p Statement, lists: &mut Vec<&'p [Statement]>) {
+    match stmt {
+        Statement::BlockStatement { statements, .. } => lists.push(statements),
+        Statement::IfStatement { then_branch, else_branch, .. } => {
+            child_lists(then_branch, lists);
+            if let Some(else_stmt) = else_branch {
+                child_lists(else_stmt, lists);
+            }
+        }
+        Statement::WhileStatement { body, .. } | Statement::DeferStatement(_, body) => child_lists(body, lists),
+        Statement::ForStatement { body, .. } | Statement::ForInStatement { body, .. } => child_lists(body, lists),
+        Statement::Attributed { statement, .. } => child_lists(statement, lists),
+        Statement::ExpressionStatement(_)
+        | Statement::DiscardStatement(..)
+        | Statement::LetStatement { .. }
+        | Statement::ReturnStatement(..)
+        | Statement::YieldStatement(..)
+        | Statement::MacroDefinition { .. }
+        | Statement::TypeAlias { .. }
+        | Statement::EnumDeclaration { .. } => {}
+    }
+}
+
+/// 함수로 옮기면 뜻이 바뀌는 문장을 찾습니다. 안쪽 함수 리터럴의 본문은 보지 않습니다.
+struct EscapeCheck(Option<&'static str>);
+
+impl Visitor for EscapeCheck {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::ReturnStatement(..) => self.0 = self.0.or(Some("return")),
+            Statement::YieldStatement(..) => self.0 = self.0.or(Some("yield")),
+            Statement::DeferStatement(..) => self.0 = self.0.or(Some("defer")),
+            _ => walk_statement(self, stmt),
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        if !matches!(expr, Expression::Function(..)) {
+            walk_expression(self, expr);
+        }
+    }
+}
+
+/// 매크로 정의 밖에서 `start` 에서 시작하고 선택만 따로 파싱한 식과 같은 식이 있는지.
+/// 식별자와 상수의 범위는 뒤 토큰까지 덮을 수 있어, 범위 대신 다시 출력한 텍스트로 비교합니다.
+struct ExpressionAt {
+    start: usize,
+    printed: Option<String>,
+    in_macro: bool,
+    found: bool,
+}
+
+impl Visitor for ExpressionAt {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        let was_in_macro = self.in_macro;
+        self.in_macro |= matches!(stmt, Statement::MacroDefinition { .. });
+        walk_statement(self, stmt);
+        self.in_macro = was_in_macro;
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        self.found |= !self.in_macro
+            && expr.span().start == self.start
+            && self.printed.as_deref() == Some(ast_printer::print_expression(expr).as_str());
+        walk_expression(self, expr);
+    }
+}
+
+fn mentions(tokens: &[Token], name: &str) -> bool {
+    tokens.iter().any(|t| matches!(t.kind, TokenKind::Identifier(n) if n.as_str() == name))
+}
+
+fn check_identifier(name: &str) -> Result<(), String> {
+    match tokenize(name).as_slice() {
+        [Token { kind: TokenKind::Identifier(n), .. }] if n.as_str() == name => Ok(()),
+        _ => Err(format!("'{}' 는 식별자로 쓸 수 없습니다.", name)),
+    }
+}
+
+// ─── 텍스트 ─────────────────────────────
+
+pub(crate) fn byte_offset(text: &str, position: usize) -> usize {
+    text.char_indices().nth(position).map_or(text.len(), |(i, _)| i)
+}
+
+fn text(chars: &[char], span: Span) -> String {
+    chars[span.start..span.end].iter().collect()
+}
+
+/// 앞뒤 공백과 끝의 ';' 를 뺀 범위
+fn trim(chars: &[char], mut start: usize, mut end: usize) -> Span {
+    while start < end && chars[start].is_whitespace() {
+        start += 1;
+    }
+    while start < end && (chars[end - 1].is_whitespace() || chars[end - 1] == ';') {
+        end -= 1;
+    }
+    Span { start, end }
+}
+
+/// 파서가 붙인 범위는 뒤의 ';' 나 공백까지 덮을 수 있으므로, 그것을 뺀 실제 텍스트 범위를 씁니다.
+fn extent(chars: &[char], span: Span) -> Span {
+    trim(chars, span.start, span.end.min(chars.len()))
+}
+
+/// 문장 범위는 끝의 ';' 를 포함하지 않으므로, 같은 줄에서 바로 이어지는 ';' 까지 늘립니다.
+fn after_semicolon(chars: &[char], end: usize) -> usize {
+    let next = (end..chars.len()).find(|&i| chars[i] != ' ' && chars[i] != '\t');
+    match next {
+        Some(i) if chars[i] == ';' => i + 1,
+        _ => end,
+    }
+}
+
+fn line_start(chars: &[char], offset: usize) -> usize {
+    chars[..offset].iter().rposition(|&c| c == '\n').map_or(0, |i| i + 1)
+}
+
+/// `offset` 앞의 줄 머리 공백
+fn indentation(chars: &[char], offset: usize) -> String {
+    let prefix: String = chars[line_start(chars, offset)..offset].iter().collect();
+    if prefix.chars().all(char::is_whitespace) {
+        prefix
+    } else {
+        String::new()
+    }
+}
+
+/// 둘째 줄부터 원래 들여쓰기 `base` 를 벗기고, 모든 줄을 함수 본문 깊이로 들여씁니다.
+fn reindent(body: &str, base: String) -> String {
+    body.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = if i == 0 { line } else { line.strip_prefix(base.as_str()).unwrap_or(line) };
+            if line.trim().is_empty() {
+                "\n".to_string()
+            } else {
+                format!("{}{}\n", INDENT, line)
+            }
+        })
+        .collect()
+}
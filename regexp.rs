@@ -0,0 +1,147 @@
+// regexp.rs
+// 정규식 내장 함수와 매치 값(`Value::Match`, `Value::Matches`)입니다. 정규식 엔진은 `regex` 크레이트이며
+// `regex` 기능을 켜야 쓸 수 있습니다. 기능이 없으면 내장 함수는 E0004 오류 값을 돌려줍니다.
+//
+//   regex_match(pattern, text)                 첫 매치. 없으면 null
+//   regex_find_all(pattern, text)              겹치지 않는 모든 매치 (`ms[i]`, `len(ms)`)
+//   regex_replace(pattern, text, replacement)  모든 매치를 바꾼 문자열. `$1`, `${name}` 으로 그룹을 참조합니다.
+//
+// 매치 값은 그룹을 담은 읽기 전용 구조체입니다.
+//
+//   m[0]  m[1]  m["year"]          그룹 텍스트. 매치에 참여하지 않은 그룹은 null
+//   len(m)                         그룹 수 (전체 매치 포함)
+//   match_start(m, g)  match_end(m, g)   그룹 g 의 [시작, 끝) 위치. g 를 생략하면 0 (전체 매치)
+//
+// 위치는 바이트가 아닌 문자 단위이므로 `len(text)` 와 같은 기준입니다. 컴파일한 정규식은 패턴별로
+// 스레드마다 캐시합니다.
+
+use alloc::sync::Arc;
+
+use crate::bytes::AccessError;
+use crate::compat::*;
+use crate::data_structures::Value;
+
+/// 매치 하나. `groups[0]` 은 전체 매치입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexMatch {
+    pub groups: Vec<Option<Group>>,
+    /// 그룹 번호별 이름. 이름 없는 그룹은 None 입니다.
+    pub names: Vec<Option<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub text: String,
+    /// 문자 단위 [start, end)
+    pub start: usize,
+    pub end: usize,
+}
+
+impl RegexMatch {
+    /// 번호나 이름으로 그룹을 찾습니다. 없는 그룹이면 None, 매치에 참여하지 않은 그룹이면 Some(None) 입니다.
+    pub fn group(&self, key: &Value) -> Option<Option<&Group>> {
+        let index = match key {
+            Value::Integer(i) => usize::try_from(*i).ok()?,
+            Value::String(name) => self.names.iter().position(|n| n.as_deref() == Some(name.as_ref()))?,
+            _ => return None,
+        };
+        self.groups.get(index).map(Option::as_ref)
+    }
+
+    /// `match("2024-01" at 3..10)`
+    pub fn display(&self) -> String {
+        match self.groups.first() {
+            Some(Some(whole)) => format!("match({:?} at {}..{})", whole.text, whole.start, whole.end),
+            _ => "match()".into(),
+        }
+    }
+}
+
+/// `m[key]` 와 `ms[i]`
+pub fn index(target: &Value, key: &Value) -> Result<Value, AccessError> {
+    match (target, key) {
+        (Value::Match(m), Value::Integer(_) | Value::String(_)) => match m.group(key) {
+            Some(group) => Ok(group.map_or(Value::Null, |g| Value::String(g.text.as_str().into()))),
+            None => Err(match key {
+                Value::Integer(index) => AccessError::IndexOutOfBounds { index: *index, len: m.groups.len() },
+                _ => AccessError::UnknownKey(crate::formatting::display_value(key)),
+            }),
+        },
+        (Value::Matches(all), Value::Integer(i)) => usize::try_from(*i)
+            .ok()
+            .and_then(|at| all.get(at))
+            .map(|m| Value::Match(Arc::new(m.clone())))
+            .ok_or(AccessError::IndexOutOfBounds { index: *i, len: all.len() }),
+        _ => Err(AccessError::TypeMismatch),
+    }
+}
+
+/// `match_start` / `match_end`. 그룹이 없거나 참여하지 않았으면 null 입니다.
+pub fn position(m: &RegexMatch, key: &Value, end: bool) -> Value {
+    match m.group(key) {
+        Some(Some(group)) => Value::Integer(if end { group.end } else { group.start } as i64),
+        _ => Value::Null,
+    }
+}
+
+#[cfg(feature = "regex")]
+mod engine {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use regex::{Captures, Regex};
+
+    use super::{Group, RegexMatch};
+
+    /// 스레드마다 유지하는 컴파일 캐시의 최대 크기. 넘치면 비웁니다.
+    const CACHE_LIMIT: usize = 64;
+
+    thread_local! {
+        static CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+    }
+
+    fn compiled(pattern: &str) -> Result<Regex, String> {
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if let Some(regex) = cache.get(pattern) {
+                return Ok(regex.clone());
+            }
+            let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+            if cache.len() >= CACHE_LIMIT {
+                cache.clear();
+            }
+            cache.insert(pattern.to_string(), regex.clone());
+            Ok(regex)
+        })
+    }
+
+    fn to_match(regex: &Regex, text: &str, captures: &Captures) -> RegexMatch {
+        let chars = |byte: usize| text[..byte].chars().count();
+        RegexMatch {
+            groups: captures
+                .iter()
+                .map(|group| {
+                    group.map(|g| Group { text: g.as_str().to_string(), start: chars(g.start()), end: chars(g.end()) })
+                })
+                .collect(),
+            names: regex.capture_names().map(|n| n.map(str::to_string)).collect(),
+        }
+    }
+
+    pub fn find(pattern: &str, text: &str) -> Result<Option<RegexMatch>, String> {
+        let regex = compiled(pattern)?;
+        Ok(regex.captures(text).map(|c| to_match(&regex, text, &c)))
+    }
+
+    pub fn find_all(pattern: &str, text: &str) -> Result<Vec<RegexMatch>, String> {
+        let regex = compiled(pattern)?;
+        Ok(regex.captures_iter(text).map(|c| to_match(&regex, text, &c)).collect())
+    }
+
+    pub fn replace(pattern: &str, text: &str, replacement: &str) -> Result<String, String> {
+        Ok(compiled(pattern)?.replace_all(text, replacement).into_owned())
+    }
+}
+
+#[cfg(feature = "regex")]
+pub use engine::{find, find_all, replace};
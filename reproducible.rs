@@ -0,0 +1,46 @@
+// reproducible.rs
+// `--reproducible` 빌드
+//
+// 같은 소스와 옵션이면 산출물이 바이트 단위로 같아야 증명 블록이 가리키는 결과를 다른 곳에서 다시 만들어
+// 확인할 수 있습니다. 이 모드에서는
+//
+//   - 증명 블록의 시간을 현재 시각 대신 SOURCE_DATE_EPOCH (없으면 0) 로 고정하고,
+//   - 컴파일이 끝나면 같은 요청을 `CompilerService` 로 처음부터 다시 컴파일해(`Pass::Rebuild`) 파일로 쓴
+//     산출물(NASM 어셈블리, LLVM IR, Python 소스, Rust 프로젝트)을 바이트 단위로 비교합니다. 두 번째 컴파일은
+//     산출물을 쓰고 멈추므로 외부 분석기, 링커, 실행, 증명 블록은 한 번만 거칩니다.
+//
+// 산출물에는 시간이나 경로가 들어가지 않고, 컴파일러 패스는 해시 맵을 조회에만 써서 출력 순서가 AST 순서를
+// 따르므로 고정할 시드는 없습니다. 새 패스가 해시 맵 순회 순서를 출력에 흘리면 이 검증이 잡아냅니다.
+// 링크된 실행 파일은 외부 링커가 만들고 체인 위치에 따라 증명 해시가 새겨지므로 어셈블리를 대신 비교합니다.
+
+/// 증명 블록 시간. SOURCE_DATE_EPOCH 가 숫자가 아니면 0 입니다.
+pub fn timestamp() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH").ok().and_then(|value| value.parse().ok()).unwrap_or(0)
+}
+
+/// 컴파일이 파일로 쓴 산출물 (이름, 내용)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Artifacts(pub Vec<(String, Vec<u8>)>);
+
+impl Artifacts {
+    pub fn push(&mut self, name: impl Into<String>, content: impl Into<Vec<u8>>) {
+        self.0.push((name.into(), content.into()));
+    }
+
+    /// 두 번째 빌드와 처음 달라지는 곳. 같으면 None, 다르면 (산출물 이름, 바이트 위치) 입니다.
+    pub fn first_difference(&self, other: &Artifacts) -> Option<(String, usize)> {
+        for (index, (name, content)) in self.0.iter().enumerate() {
+            let Some((other_name, other_content)) = other.0.get(index) else {
+                return Some((name.clone(), 0));
+            };
+            if other_name != name {
+                return Some((name.clone(), 0));
+            }
+            if content != other_content {
+                let at = content.iter().zip(other_content).position(|(a, b)| a != b);
+                return Some((name.clone(), at.unwrap_or(content.len().min(other_content.len()))));
+            }
+        }
+        other.0.get(self.0.len()).map(|(name, _)| (name.clone(), 0))
+    }
+}
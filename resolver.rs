@@ -0,0 +1,441 @@
+// resolver.rs
+// 이름 해석기: 스코프별 심볼 테이블을 만들고, 각 참조를 선언에 연결합니다.
+// 현재는 @deprecated 심볼 참조, 불변 바인딩에 대한 대입(E0007), match arm 검사(patterns.rs)의 진단을 생성하고,
+// 식별자마다 가리키는 선언을 기록합니다. 자동 완성(completion.rs)에는 커서 위치에서 보이는 선언을 알려줍니다.
+//
+// `SlotResolver` 는 바이트코드 컴파일러가 함수 본문의 지역 변수에 (스코프 깊이, 칸 번호) 를 매기는 데 씁니다.
+
+
+use crate::attributes;
+use crate::compat::*;
+use crate::error_codes;
+use crate::interner;
+use crate::patterns;
+use crate::node_ids::{NodeIndex, SideTable};
+use crate::data_structures::{
+    Attribute, AttributeArg, Diagnostic, Expression, Program, Span, Statement,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+    Variable,
+    Parameter,
+    Macro,
+}
+
+/// 선언된 이름 하나에 대한 정보입니다.
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub name: interner::Symbol,
+    pub kind: SymbolKind,
+    /// `@deprecated` 선언: 바깥 Option은 사용 중단 여부, 안쪽은 메시지입니다.
+    pub deprecated: Option<Option<String>>,
+    pub declaration_span: Span,
+    /// `let mut` 으로 선언되어 대입할 수 있는지
+    pub mutable: bool,
+}
+
+pub struct Resolver {
+    scopes: Vec<HashMap<interner::Symbol, Symbol>>,
+    diagnostics: Vec<Diagnostic>,
+    /// `@allow(deprecated)` 가 적용된 중첩 깊이 (0이면 경고를 냅니다)
+    allow_deprecated: usize,
+    /// 식별자 식과 이름 호출 식 → 가리키는 선언
+    resolved: HashMap<*const Expression, Symbol>,
+    /// 지금까지 본 enum 선언마다 변형 이름들 (match 의 비완전 매칭 검사용)
+    enums: Vec<Vec<interner::Symbol>>,
+    /// `visible_at` 의 커서 위치
+    cursor: Option<usize>,
+    /// 커서에 처음 닿았을 때의 스코프들 (바깥부터)
+    visible: Option<Vec<HashMap<interner::Symbol, Symbol>>>,
+}
+
+impl Resolver {
+    /// 프로그램 전체를 해석하고 생성된 진단을 반환합니다.
+    pub fn resolve_program(program: &Program) -> Vec<Diagnostic> {
+        Self::run(program, None).diagnostics
+    }
+
+    /// `offset` 에서 보이는 선언들. 안쪽 스코프부터, 같은 스코프 안에서는 나중 선언부터입니다. (자동 완성)
+    /// 커서가 초기값 안에 있는 `let` 의 이름은 아직 보이지 않고, 선언보다 뒤의 정의는 끌어올리지 않습니다.
+    pub fn visible_at(program: &Program, offset: usize) -> Vec<Symbol> {
+        let resolver = Self::run(program, Some(offset));
+        let scopes = resolver.visible.unwrap_or(resolver.scopes);
+        scopes
+            .into_iter()
+            .rev()
+            .flat_map(|scope| {
+                let mut symbols: Vec<Symbol> = scope.into_values().collect();
+                symbols.sort_by_key(|symbol| core::cmp::Reverse(symbol.declaration_span.start));
+                symbols
+            })
+            .collect()
+    }
+
+    /// 식별자 식과 이름 호출(`name(args)`)마다 가리키는 선언을 노드 번호로 돌려줍니다. 전역·내장 이름은 빠집니다.
+    pub fn resolve_symbols(program: &Program, index: &NodeIndex) -> SideTable<Symbol> {
+        let mut table = SideTable::new();
+        for (expr, symbol) in Self::run(program, None).resolved {
+            if let Some(id) = index.expression_at(expr) {
+                table.insert(id, symbol);
+            }
+        }
+        table
+    }
+
+    fn run(program: &Program, cursor: Option<usize>) -> Self {
+        let mut resolver = Resolver {
+            scopes: vec![HashMap::new()],
+            diagnostics: vec![],
+            allow_deprecated: usize::from(allows(&program.attributes, "deprecated")),
+            resolved: HashMap::new(),
+            enums: vec![],
+            cursor,
+            visible: None,
+        };
+        for stmt in &program.statements {
+            resolver.resolve_statement(stmt, &[]);
+        }
+        resolver
+    }
+
+    // ─── 스코프 ─────────────────────────────
+
+    /// `span` 은 스코프가 덮는 소스 범위입니다. 커서가 그 안의 마지막 선언 뒤에 있으면 닫기 전에 스코프들을 남깁니다.
+    fn with_scope(&mut self, span: Span, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.reached(|cursor| cursor < span.end);
+        self.scopes.pop();
+    }
+
+    /// 커서에 닿았으면(`at` 이 참이면) 처음 한 번만 지금의 스코프들을 남깁니다.
+    fn reached(&mut self, at: impl FnOnce(usize) -> bool) {
+        if self.visible.is_none() && self.cursor.is_some_and(at) {
+            self.visible = Some(self.scopes.clone());
+        }
+    }
+
+    fn declare(&mut self, symbol: Symbol) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(symbol.name, symbol);
+        }
+    }
+
+    fn lookup(&self, name: interner::Symbol) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(&name))
+    }
+
+    // ─── 문장 ─────────────────────────────
+
+    /// `attributes`는 바로 바깥 `Attributed` 래퍼에서 전달된 어트리뷰트입니다.
+    fn resolve_statement(&mut self, stmt: &Statement, attributes: &[Attribute]) {
+        let declaration_span = attributes.first().map_or(stmt.span(), |a| a.span);
+        let span = stmt.span();
+        self.reached(|cursor| cursor <= span.start);
+        match stmt {
+            Statement::ExpressionStatement(expr)
+            | Statement::DiscardStatement(_, expr)
+            | Statement::ReturnStatement(_, expr)
+            | Statement::YieldStatement(_, expr) => {
+                self.resolve_expression(expr);
+            }
+            Statement::LetStatement { name, value, is_mutable, .. } => {
+                self.resolve_expression(value);
+                self.reached(|cursor| cursor < span.end);
+                self.declare(Symbol {
+                    name: *name,
+                    kind: SymbolKind::Variable,
+                    deprecated: attributes::deprecation(attributes),
+                    declaration_span,
+                    mutable: *is_mutable,
+                });
+            }
+            Statement::BlockStatement { statements, .. } => self.with_scope(span, |r| {
+                for s in statements {
+                    r.resolve_statement(s, &[]);
+                }
+            }),
+            Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+                self.resolve_expression(condition);
+                self.with_scope(then_branch.span(), |r| r.resolve_statement(then_branch, &[]));
+                if let Some(else_stmt) = else_branch {
+                    self.with_scope(else_stmt.span(), |r| r.resolve_statement(else_stmt, &[]));
+                }
+            }
+            Statement::DeferStatement(_, body) => self.with_scope(span, |r| r.resolve_statement(body, &[])),
+            Statement::WhileStatement { condition, body, .. } => {
+                self.resolve_expression(condition);
+                self.with_scope(body.span(), |r| r.resolve_statement(body, &[]));
+            }
+            Statement::ForStatement { initializer, condition, increment, body, .. } => self.with_scope(span, |r| {
+                if let Some(init) = initializer {
+                    r.resolve_statement(init, &[]);
+                }
+                if let Some(cond) = condition {
+                    r.resolve_expression(cond);
+                }
+                if let Some(inc) = increment {
+                    r.resolve_expression(inc);
+                }
+                r.resolve_statement(body, &[]);
+            }),
+            Statement::ForInStatement { variable, iterable, body, .. } => {
+                self.resolve_expression(iterable);
+                self.with_scope(span, |r| {
+                    r.declare(Symbol {
+                        name: *variable,
+                        kind: SymbolKind::Variable,
+                        deprecated: None,
+                        declaration_span: span,
+                        mutable: false,
+                    });
+                    r.resolve_statement(body, &[]);
+                });
+            }
+            Statement::MacroDefinition { name, parameters, body, .. } => {
+                self.declare(Symbol {
+                    name: interner::Symbol::intern(name),
+                    kind: SymbolKind::Macro,
+                    deprecated: attributes::deprecation(attributes),
+                    declaration_span,
+                    mutable: false,
+                });
+                self.with_scope(span, |r| {
+                    for p in parameters {
+                        r.declare(Symbol {
+                            name: interner::Symbol::intern(p),
+                            kind: SymbolKind::Parameter,
+                            deprecated: None,
+                            declaration_span,
+                            mutable: false,
+                        });
+                    }
+                    r.resolve_statement(body, &[]);
+                });
+            }
+            Statement::Attributed { attributes: own, statement, .. } => {
+                let allow = allows(own, "deprecated");
+                self.allow_deprecated += usize::from(allow);
+                self.resolve_statement(statement, own);
+                self.allow_deprecated -= usize::from(allow);
+            }
+            // 타입 이름은 값 이름과 따로 해석합니다. (named_types)
+            Statement::TypeAlias { .. } => {}
+            // 변형 이름은 값(또는 생성자)으로 바인딩됩니다. enum 이름 자체는 값이 아닙니다.
+            Statement::EnumDeclaration { variants, .. } => {
+                for variant in variants {
+                    self.declare(Symbol {
+                        name: variant.name,
+                        kind: SymbolKind::Variable,
+                        deprecated: attributes::deprecation(attributes),
+                        declaration_span: variant.span,
+                        mutable: false,
+                    });
+                }
+                self.enums.push(variants.iter().map(|v| v.name).collect());
+            }
+        }
+    }
+
+    // ─── 표현식 ─────────────────────────────
+
+    fn resolve_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Identifier(span, name) => {
+                if let Some(symbol) = self.lookup(*name) {
+                    self.resolved.insert(expr, symbol.clone());
+                }
+                self.check_reference(*name, *span);
+            }
+            Expression::MacroCall(span, name, args) => {
+                let name = interner::Symbol::intern(name);
+                if let Some(symbol) = self.lookup(name) {
+                    self.resolved.insert(expr, symbol.clone());
+                }
+                self.check_reference(name, *span);
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+            }
+            Expression::PrefixOperation(_, _, right) => self.resolve_expression(right),
+            Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+                self.resolve_expression(left);
+                self.resolve_expression(right);
+            }
+            Expression::Ternary(_, cond, then_expr, else_expr) => {
+                self.resolve_expression(cond);
+                self.resolve_expression(then_expr);
+                self.resolve_expression(else_expr);
+            }
+            Expression::Function(span, parameters, body) => self.with_scope(*span, |r| {
+                for p in parameters {
+                    r.declare(Symbol {
+                        name: *p,
+                        kind: SymbolKind::Parameter,
+                        deprecated: None,
+                        declaration_span: *span,
+                        mutable: false,
+                    });
+                }
+                r.resolve_statement(body, &[]);
+            }),
+            Expression::Call(_, function, args) => {
+                self.resolve_expression(function);
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+            }
+            Expression::ArrayLiteral(_, elements) => {
+                for element in elements {
+                    self.resolve_expression(element);
+                }
+            }
+            Expression::MapLiteral(_, entries) => {
+                for (key, value) in entries {
+                    self.resolve_expression(key);
+                    self.resolve_expression(value);
+                }
+            }
+            Expression::Grouped(_, inner)
+            | Expression::Reflect(_, inner)
+            | Expression::Eval(_, inner)
+            | Expression::TypeOf(_, inner) => self.resolve_expression(inner),
+            Expression::Index(_, target, index) => {
+                self.resolve_expression(target);
+                self.resolve_expression(index);
+            }
+            Expression::Member(_, target, _) => self.resolve_expression(target),
+            // 메서드 이름은 받는 값을 첫 인자로 부르는 함수 이름입니다.
+            Expression::MethodCall(span, target, name, args) => {
+                self.resolve_expression(target);
+                if let Some(symbol) = self.lookup(*name) {
+                    self.resolved.insert(expr, symbol.clone());
+                }
+                self.check_reference(*name, *span);
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+            }
+            Expression::Slice(_, target, start, end) => {
+                self.resolve_expression(target);
+                for bound in start.iter().chain(end) {
+                    self.resolve_expression(bound);
+                }
+            }
+            Expression::Match(span, subject, arms) => {
+                self.resolve_expression(subject);
+                self.diagnostics.extend(patterns::check_arms(arms, *span, &self.enums));
+                for arm in arms {
+                    self.with_scope(arm.span, |r| {
+                        for name in patterns::bindings(&arm.pattern) {
+                            r.declare(Symbol {
+                                name,
+                                kind: SymbolKind::Variable,
+                                deprecated: None,
+                                declaration_span: arm.span,
+                                mutable: false,
+                            });
+                        }
+                        if let Some(guard) = &arm.guard {
+                            r.resolve_expression(guard);
+                        }
+                        r.resolve_expression(&arm.body);
+                    });
+                }
+            }
+            // 대입도 선언을 가리킵니다. 선언을 모르는 이름(전역, 호출한 쪽의 바인딩)은 실행 중에 검사합니다.
+            Expression::Assign(span, _, name, value) => {
+                self.resolve_expression(value);
+                if let Some(symbol) = self.lookup(*name).cloned() {
+                    if !symbol.mutable {
+                        let code = &error_codes::ASSIGN_IMMUTABLE;
+                        let diagnostic = code.diagnostic(*span, "", &[name]).with_help(code.message("help", &[name]));
+                        self.diagnostics.push(diagnostic);
+                    }
+                    self.resolved.insert(expr, symbol);
+                }
+                self.check_reference(*name, *span);
+            }
+            Expression::Literal(..) => {}
+        }
+    }
+
+    fn check_reference(&mut self, name: interner::Symbol, use_span: Span) {
+        if self.allow_deprecated > 0 {
+            return;
+        }
+        let Some(symbol) = self.lookup(name) else {
+            return;
+        };
+        let Some(message) = &symbol.deprecated else {
+            return;
+        };
+
+        let detail = message.as_deref().map(|m| format!(": {}", m)).unwrap_or_default();
+        let declared_at = format!("{}..{}", symbol.declaration_span.start, symbol.declaration_span.end);
+        let diagnostic = error_codes::DEPRECATED_USE
+            .diagnostic(use_span, "", &[&name, &detail])
+            .with_help(error_codes::DEPRECATED_USE.message("help", &[&name, &declared_at]));
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+// ─── 지역 변수 칸 ─────────────────────────────
+
+/// 함수 한 프레임 안의 스코프별 칸 배치입니다.
+///
+/// 칸 번호는 스코프 안에서 처음 선언된 순서이며, 같은 이름을 다시 선언하면 같은 칸을 씁니다.
+/// 참조는 그 시점까지 선언된 이름만 보고 해석하므로, 실행 중 환경을 따라 찾는 결과와 같습니다.
+/// 프레임 안에서 찾지 못한 이름(전역, 호출한 쪽의 바인딩)은 실행 시점에 이름으로 찾습니다.
+#[derive(Debug, Default)]
+pub struct SlotResolver {
+    /// 열려 있는 스코프들의 배치 번호 (바깥부터)
+    open: Vec<usize>,
+    /// 배치 번호 → 칸 이름들
+    layouts: Vec<Vec<interner::Symbol>>,
+}
+
+impl SlotResolver {
+    /// 새 스코프를 열고 그 배치 번호를 돌려줍니다.
+    pub fn enter(&mut self) -> usize {
+        self.layouts.push(vec![]);
+        self.open.push(self.layouts.len() - 1);
+        self.layouts.len() - 1
+    }
+
+    pub fn exit(&mut self) {
+        self.open.pop();
+    }
+
+    /// 현재 스코프에 이름을 선언하고 칸 번호를 돌려줍니다.
+    pub fn declare(&mut self, name: interner::Symbol) -> usize {
+        let layout = &mut self.layouts[*self.open.last().expect("열린 스코프가 있어야 합니다")];
+        match layout.iter().position(|&n| n == name) {
+            Some(slot) => slot,
+            None => {
+                layout.push(name);
+                layout.len() - 1
+            }
+        }
+    }
+
+    /// 이름을 (바깥으로 몇 단계, 칸 번호) 로 해석합니다. 프레임 밖의 이름이면 None 입니다.
+    pub fn resolve(&self, name: interner::Symbol) -> Option<(usize, usize)> {
+        self.open.iter().rev().enumerate().find_map(|(depth, &layout)| {
+            self.layouts[layout].iter().position(|&n| n == name).map(|slot| (depth, slot))
+        })
+    }
+
+    /// 배치 번호 순의 칸 이름들
+    pub fn into_layouts(self) -> Vec<Vec<interner::Symbol>> {
+        self.layouts
+    }
+}
+
+/// `@allow(lint)` 어트리뷰트가 주어진 린트를 허용하는지 확인합니다.
+pub(crate) fn allows(attributes: &[Attribute], lint: &str) -> bool {
+    attributes.iter().any(|a| {
+        a.name == "allow" && a.args.iter().any(|arg| matches!(arg, AttributeArg::Flag(f) if f == lint))
+    })
+}
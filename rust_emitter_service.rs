@@ -1,167 +1,645 @@
-// rust_emitter_service.rs
-// 검증된 Program (AST)을 유효한 Rust 소스 코드로 변환하는 트랜스파일러 서비스입니다.
-
-use crate::data_structures::{
-    Diagnostic, DiagnosticLevel, Program, Statement, Expression, TokenKind,
-};
-use std::fmt::Write;
-
-pub struct RustEmitterService;
-
-impl RustEmitterService {
-    /// Program AST를 받아 Rust 소스 코드 문자열을 생성합니다.
-    pub fn run(program: &Program) -> Result<String, Diagnostic> {
-        println!("\n[RUST EMITTER] START: Generating Rust Source Code.");
-        
-        let mut rust_code = String::new();
-
-        // 1. Rust 표준 라이브러리 사용 및 주석
-        writeln!(rust_code, "// [Transpiled Code] - Generated from custom language AST").unwrap();
-        writeln!(rust_code, "// File: generated_code.rs\n").unwrap();
-
-        // 2. main 함수 시작
-        writeln!(rust_code, "fn main() {{").unwrap();
-
-        // 3. Statement들을 순회하며 Rust 코드로 변환
-        let num_statements = program.statements.len();
-        for (i, statement) in program.statements.iter().enumerate() {
-            let is_last_statement = i == num_statements - 1;
-            
-            // 모든 코드는 4칸 들여쓰기 합니다.
-            match self.emit_statement(statement, is_last_statement)? {
-                Some(code) => {
-                    writeln!(rust_code, "    {}", code).unwrap();
-                },
-                None => {
-                    // ExpressionStatement에서 아무것도 생성하지 않은 경우 (e.g., 단순 함수 정의)
-                }
-            }
-        }
-        
-        // 4. main 함수 종료
-        writeln!(rust_code, "}}").unwrap();
-        
-        println!("[RUST EMITTER] SUCCESS: Rust source code generated.");
-        Ok(rust_code)
-    }
-
-    /// Statement 노드를 Rust 코드로 변환합니다.
-    /// `is_last_statement`가 참이면 세미콜론을 생략하여 값 반환을 시뮬레이션합니다.
-    fn emit_statement(&self, stmt: &Statement, is_last_statement: bool) -> Result<Option<String>, Diagnostic> {
-        match stmt {
-            Statement::LetStatement { name, value, type_name, span: _ } => {
-                // type_name이 있다면 사용하고, 없으면 i64로 추정합니다.
-                let rust_type = type_name.as_deref().unwrap_or("i64"); 
-                let expr_code = self.emit_expression(value)?;
-                
-                // Rust의 let 바인딩은 항상 세미콜론으로 끝나야 합니다.
-                Ok(Some(format!("let {}: {} = {};", name, rust_type, expr_code)))
-            },
-            Statement::ReturnStatement { value, span: _ } => {
-                let expr_code = self.emit_expression(value)?;
-                
-                // DSL의 return을 Rust의 명시적 return으로 변환합니다.
-                Ok(Some(format!("return {};", expr_code)))
-            },
-            Statement::ExpressionStatement(expr) => {
-                let expr_code = self.emit_expression(expr)?;
-                
-                // 마지막 표현식(Implicit Return)이 아니라면 세미콜론을 추가합니다.
-                let semicolon = if is_last_statement { "" } else { ";" };
-                
-                // 간단한 ExpressionStatement (예: 함수 호출) 처리
-                Ok(Some(format!("{}{}", expr_code, semicolon)))
-            },
-        }
-    }
-    
-    /// Expression 노드를 Rust 코드로 변환합니다.
-    fn emit_expression(&self, expr: &Expression) -> Result<String, Diagnostic> {
-        match expr {
-            Expression::Integer(i) => Ok(i.to_string()),
-            Expression::Boolean(b) => Ok(b.to_string()),
-            Expression::StringLiteral(s) => Ok(format!("\"{}\"", s)),
-            Expression::Identifier(name) => Ok(name.clone()),
-            
-            Expression::Binary { op, left, right, span } => {
-                let left_code = self.emit_expression(left)?;
-                let right_code = self.emit_expression(right)?;
-                
-                let op_str = match op {
-                    // 산술 연산자
-                    TokenKind::Plus => "+",
-                    TokenKind::Minus => "-",
-                    TokenKind::Asterisk => "*",
-                    TokenKind::Slash => "/",
-                    // 비교/관계 연산자 (새로 추가됨)
-                    TokenKind::EqualEqual => "==",
-                    TokenKind::NotEqual => "!=",
-                    TokenKind::LessThan => "<",
-                    TokenKind::GreaterThan => ">",
-                    TokenKind::LessThanOrEqual => "<=",
-                    TokenKind::GreaterThanOrEqual => ">=",
-                    _ => {
-                        return Err(Diagnostic {
-                            level: DiagnosticLevel::Error,
-                            message: format!("Unsupported binary operator for Rust emitter: {:?}", op),
-                            span: span.clone(),
-                            help: None,
-                        });
-                    }
-                };
-                
-                // 연산자 우선순위를 위해 괄호를 사용합니다.
-                Ok(format!("({} {} {})", left_code, op_str, right_code))
-            },
-            
-            Expression::Call { function, arguments, span } => {
-                let func_name = match function.as_ref() {
-                    Expression::Identifier(name) => name,
-                    _ => {
-                        return Err(Diagnostic {
-                            level: DiagnosticLevel::Error,
-                            message: "Function call target must be a simple identifier.".to_string(),
-                            span: span.clone(),
-                            help: None,
-                        });
-                    }
-                };
-                
-                // 인수 리스트 생성
-                let mut arg_list = Vec::new();
-                for arg in arguments {
-                    arg_list.push(self.emit_expression(arg)?);
-                }
-                let args_str = arg_list.join(", ");
-                
-                // 임시로 'print' 함수는 Rust의 println!으로 매핑합니다.
-                if func_name == "print" {
-                    Ok(format!("println!(\"{{}}\", {})", args_str))
-                } else {
-                    Ok(format!("{}({})", func_name, args_str))
-                }
-            },
-            
-            // 기타 복잡한 Expression 유형은 현재 트랜스파일러에서 지원하지 않는다고 가정합니다.
-            expr => Err(Diagnostic {
-                level: DiagnosticLevel::Error,
-                message: format!("Unsupported expression type for Rust emitter: {:?}", expr),
-                span: expr.get_span().unwrap_or_default(), 
-                help: Some("This feature is not yet supported in the Rust transpiler backend.".to_string()),
-            }),
-        }
-    }
-}
-
-// Span을 쉽게 가져오기 위한 Expression 트레이트 헬퍼
-trait GetSpan {
-    fn get_span(&self) -> Option<crate::data_structures::Span>;
-}
-
-impl GetSpan for Expression {
-    fn get_span(&self) -> Option<crate::data_structures::Span> {
-        // 모든 Expression variant에 span 필드가 없으므로, 현재는 None을 반환합니다.
-        // 실제 구현에서는 모든 Expression에 Span을 포함시켜야 Diagnostic 정보가 정확해집니다.
-        None
-    }
-}
+// rust_emitter_service.rs
+// HIR 을 독립된 cargo 프로젝트로 옮기는 Rust 백엔드입니다.
+//
+// 생성되는 프로젝트:
+//   Cargo.toml
+//   src/value.rs   동적 값 타입과 연산자 (operators.rs 와 같은 의미)
+//   src/main.rs    최상위 `let f = fn(..) {..}` 는 `fn f(..) -> Value` 항목, 나머지 최상위 문장은 `main` 본문
+//
+// 값의 타입은 실행 시점에 정해지므로 생성된 코드는 모든 값을 `Value` 로 다룹니다.
+// 연산 오류(타입 불일치, 0 나누기)와 panic/assert 실패는 메시지를 출력하고 종료 코드 101 로 끝납니다.
+// 메시지는 프로젝트를 생성할 때의 로케일로 고정됩니다.
+//
+// Rust 로 옮길 수 없는 구문(함수 값, 중첩 함수, 최상위 변수를 읽는 함수, reflect/eval/typeof,
+// yield/defer)은 E0092 진단으로 알립니다.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cancellation::{run_command, CancellationToken, CommandError};
+use crate::data_structures::{Diagnostic, Span, TokenKind, Value};
+use crate::error_codes;
+use crate::formatting::{self, display_value, Segment};
+use crate::hir::{self, Block, Expr, ExprKind, Stmt};
+use crate::interner::Symbol;
+use crate::messages;
+use crate::operators;
+
+/// 진단 메시지에 쓰는 백엔드 이름
+const BACKEND: &str = "Rust";
+
+/// 생성된 cargo 프로젝트: 프로젝트 루트 기준 경로와 파일 내용
+#[derive(Debug, Clone)]
+pub struct RustProject {
+    /// cargo 패키지 이름이자 실행 파일 이름
+    pub name: String,
+    pub files: Vec<(String, String)>,
+}
+
+impl RustProject {
+    pub fn file(&self, path: &str) -> Option<&str> {
+        self.files.iter().find(|(p, _)| p == path).map(|(_, content)| content.as_str())
+    }
+
+    /// 프로젝트 파일들을 `dir` 아래에 씁니다. 디렉터리가 없으면 만듭니다.
+    pub fn write_to(&self, dir: &Path) -> io::Result<()> {
+        for (path, content) in &self.files {
+            let target = dir.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(target, content)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct RustEmitterService;
+
+impl RustEmitterService {
+    /// HIR 프로그램으로 cargo 프로젝트를 만듭니다. 옮길 수 없는 구문이 있으면 그 진단들을 돌려줍니다.
+    pub fn emit_project(program: &hir::Program, name: &str) -> Result<RustProject, Vec<Diagnostic>> {
+        let name = package_name(name);
+        let main_rs = Emitter::new(program).emit_main(program)?;
+        Ok(RustProject {
+            files: vec![
+                ("Cargo.toml".into(), cargo_toml(&name)),
+                ("src/value.rs".into(), value_rs()),
+                ("src/main.rs".into(), main_rs),
+            ],
+            name,
+        })
+    }
+
+    /// 생성된 프로젝트에서 `cargo build --release` 를 실행하고 실행 파일 경로를 돌려줍니다.
+    /// 취소되면 cargo 를 종료합니다.
+    pub fn cargo_build(dir: &Path, name: &str, token: &CancellationToken) -> Result<PathBuf, String> {
+        let output = run_command(
+            Command::new("cargo").args(["build", "--release", "--quiet"]).current_dir(dir),
+            None,
+            token,
+        )
+        .map_err(|e| match e {
+            CommandError::Cancelled => error_codes::CANCELLED.message("", &[]),
+            CommandError::Io(e) => format!("cargo 실행 실패: {}", e),
+        })?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        let binary = if cfg!(target_os = "windows") { format!("{}.exe", name) } else { name.to_string() };
+        Ok(dir.join("target").join("release").join(binary))
+    }
+}
+
+/// cargo 패키지 이름으로 쓸 수 있게 고칩니다. (영문 소문자, 숫자, `_`, `-`)
+fn package_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    match cleaned.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => cleaned,
+        Some(_) => format!("high_{}", cleaned),
+        None => "high_program".into(),
+    }
+}
+
+fn cargo_toml(name: &str) -> String {
+    format!(
+        "\
+[package]
+name = \"{}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+
+# 바깥 디렉터리의 cargo 워크스페이스에 묶이지 않도록 독립 워크스페이스로 둡니다.
+[workspace]
+",
+        name
+    )
+}
+
+// ─── 코드 생성 ─────────────────────────────
+
+struct Emitter {
+    /// 최상위 함수 이름 → 매개변수 개수
+    functions: HashMap<Symbol, usize>,
+    /// 함수가 아닌 최상위 바인딩 (함수 본문에서는 읽을 수 없습니다)
+    globals: HashSet<Symbol>,
+    /// 지금 보이는 지역 바인딩 (바깥 스코프부터)
+    scopes: Vec<HashSet<Symbol>>,
+    /// 본문을 생성 중인 함수. `None` 이면 `main` 입니다.
+    function: Option<Symbol>,
+    out: String,
+    indent: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Emitter {
+    fn new(program: &hir::Program) -> Self {
+        let mut functions = HashMap::new();
+        let mut globals = HashSet::new();
+        for stmt in &program.statements {
+            match stmt {
+                Stmt::Let { name, value: Expr { kind: ExprKind::Function(parameters, _), .. }, .. } => {
+                    functions.insert(*name, parameters.len());
+                }
+                Stmt::Let { name, .. } => {
+                    globals.insert(*name);
+                }
+                _ => {}
+            }
+        }
+        Emitter {
+            functions,
+            globals,
+            scopes: vec![HashSet::new()],
+            function: None,
+            out: String::new(),
+            indent: 0,
+            diagnostics: vec![],
+        }
+    }
+
+    fn emit_main(mut self, program: &hir::Program) -> Result<String, Vec<Diagnostic>> {
+        self.out.push_str("// 생성된 파일입니다: High 소스에서 만든 Rust 프로그램\n");
+        self.out.push_str("#![allow(unused_variables, unused_mut, unreachable_code, non_snake_case)]\n\n");
+        self.out.push_str("mod value;\n\nuse value::Value;\n\n");
+
+        let mut main_body = vec![];
+        for stmt in &program.statements {
+            match stmt {
+                Stmt::Let { name, value: Expr { kind: ExprKind::Function(parameters, body), .. }, .. } => {
+                    self.function(*name, parameters, body);
+                }
+                other => main_body.push(other),
+            }
+        }
+
+        self.line("fn main() {");
+        self.indent += 1;
+        for stmt in main_body {
+            self.statement(stmt);
+        }
+        self.indent -= 1;
+        self.line("}");
+
+        if self.diagnostics.is_empty() {
+            Ok(self.out)
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn function(&mut self, name: Symbol, parameters: &[Symbol], body: &Block) {
+        let params: Vec<String> = parameters.iter().map(|p| format!("mut {}: Value", ident(*p))).collect();
+        self.line(&format!("fn {}({}) -> Value {{", ident(name), params.join(", ")));
+
+        let outer_scopes = std::mem::replace(&mut self.scopes, vec![parameters.iter().copied().collect()]);
+        let outer_function = self.function.replace(name);
+        self.indent += 1;
+        for stmt in &body.statements {
+            self.statement(stmt);
+        }
+        // 끝까지 실행되면 null 을 돌려줍니다.
+        self.line("Value::Null");
+        self.indent -= 1;
+        self.scopes = outer_scopes;
+        self.function = outer_function;
+
+        self.line("}");
+        self.out.push('\n');
+    }
+
+    fn line(&mut self, text: &str) {
+        let _ = writeln!(self.out, "{}{}", "    ".repeat(self.indent), text);
+    }
+
+    fn unsupported(&mut self, span: Span, detail: &str, args: &[&dyn std::fmt::Display]) {
+        let mut full: Vec<&dyn std::fmt::Display> = vec![&BACKEND];
+        full.extend_from_slice(args);
+        self.diagnostics.push(error_codes::BACKEND_UNSUPPORTED.diagnostic(span, detail, &full));
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.scopes.push(HashSet::new());
+        self.indent += 1;
+        for stmt in &block.statements {
+            self.statement(stmt);
+        }
+        self.indent -= 1;
+        self.scopes.pop();
+    }
+
+    // ─── 문장 ─────────────────────────────
+
+    fn statement(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expr(expr) => {
+                let code = self.expression(expr);
+                self.line(&format!("let _ = {};", code));
+            }
+            Stmt::Let { name, value, .. } => {
+                let code = self.expression(value);
+                self.scopes.last_mut().expect("스코프가 있어야 합니다").insert(*name);
+                self.line(&format!("let mut {} = {};", ident(*name), code));
+            }
+            Stmt::Return(expr, _) => {
+                let code = self.expression(expr);
+                if self.function.is_some() {
+                    self.line(&format!("return {};", code));
+                } else {
+                    // 최상위 return 은 프로그램의 결과입니다.
+                    self.line(&format!("value::print_result({});", code));
+                    self.line("return;");
+                }
+            }
+            Stmt::Yield(_, span) => self.unsupported(*span, "", &[&"yield"]),
+            Stmt::Defer(_, span) => self.unsupported(*span, "", &[&"defer"]),
+            Stmt::Enum { span, .. } => self.unsupported(*span, "", &[&"enum"]),
+            Stmt::ForIn { span, .. } => self.unsupported(*span, "", &[&"for-in"]),
+            Stmt::Block(block) => {
+                self.line("{");
+                self.block(block);
+                self.line("}");
+            }
+            Stmt::If { condition, then_branch, else_branch, .. } => {
+                let code = self.expression(condition);
+                self.line(&format!("if ({}).is_true() {{", code));
+                self.block(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.line("} else {");
+                    self.block(else_branch);
+                }
+                self.line("}");
+            }
+            Stmt::While { condition, body, .. } => {
+                let code = self.expression(condition);
+                self.line(&format!("while ({}).is_true() {{", code));
+                self.block(body);
+                self.line("}");
+            }
+        }
+    }
+
+    // ─── 식 ─────────────────────────────
+
+    fn is_local(&self, name: Symbol) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(&name))
+    }
+
+    fn expression(&mut self, expr: &Expr) -> String {
+        match &expr.kind {
+            ExprKind::Literal(value) => self.literal(value, expr.span),
+            ExprKind::Variable(name) => self.variable(*name, expr.span),
+            ExprKind::Unary(op, operand) => {
+                let operand = self.expression(operand);
+                format!("value::unary({:?}, {})", operators::symbol(op), operand)
+            }
+            ExprKind::Binary(op, left, right) => {
+                let (left, right) = (self.expression(left), self.expression(right));
+                match op {
+                    // 오른쪽 식은 왼쪽 값으로 결과가 정해지지 않을 때만 계산합니다.
+                    TokenKind::And => format!("value::and({}, || {})", left, right),
+                    TokenKind::Or => format!("value::or({}, || {})", left, right),
+                    _ => format!("value::binary({:?}, {}, {})", operators::symbol(op), left, right),
+                }
+            }
+            ExprKind::If(cond, then_expr, else_expr) => {
+                let (cond, then_expr, else_expr) =
+                    (self.expression(cond), self.expression(then_expr), self.expression(else_expr));
+                format!("if ({}).is_true() {{ {} }} else {{ {} }}", cond, then_expr, else_expr)
+            }
+            ExprKind::Function(..) => {
+                self.unsupported(expr.span, "nested_function", &[]);
+                "Value::Null".into()
+            }
+            ExprKind::Call(callee, args) => self.call(callee, args, expr.span),
+            ExprKind::Reflect(_) => self.construct(expr.span, "reflect"),
+            ExprKind::Eval(_) => self.construct(expr.span, "eval"),
+            ExprKind::TypeOf(_) => self.construct(expr.span, "typeof"),
+            ExprKind::Array(_) => self.construct(expr.span, "array"),
+            ExprKind::Map(_) => self.construct(expr.span, "map"),
+            ExprKind::Index(..) => self.construct(expr.span, "index"),
+            ExprKind::Slice(..) => self.construct(expr.span, "slice"),
+            ExprKind::Assign(..) => self.construct(expr.span, "assignment"),
+            ExprKind::Range(..) => self.construct(expr.span, "range"),
+        }
+    }
+
+    fn construct(&mut self, span: Span, name: &str) -> String {
+        self.unsupported(span, "", &[&name]);
+        "Value::Null".into()
+    }
+
+    fn literal(&mut self, value: &Value, span: Span) -> String {
+        match value {
+            Value::Integer(i) => format!("Value::Int({}i64)", i),
+            Value::Float(f) if f.is_nan() => "Value::Float(f64::NAN)".into(),
+            Value::Float(f) if f.is_infinite() => {
+                format!("Value::Float({}f64::INFINITY)", if *f < 0.0 { "-" } else { "" })
+            }
+            Value::Float(f) => format!("Value::Float({:?}f64)", f),
+            Value::Boolean(b) => format!("Value::Bool({})", b),
+            Value::String(s) => format!("Value::Str(String::from({:?}))", s),
+            Value::Null => "Value::Null".into(),
+            other => self.construct(span, &display_value(other)),
+        }
+    }
+
+    fn variable(&mut self, name: Symbol, span: Span) -> String {
+        if self.is_local(name) {
+            return format!("{}.clone()", ident(name));
+        }
+        if self.functions.contains_key(&name) {
+            self.unsupported(span, "function_value", &[&name]);
+        } else if let (Some(function), true) = (self.function, self.globals.contains(&name)) {
+            self.unsupported(span, "global_capture", &[&function, &name]);
+        } else {
+            self.diagnostics.push(error_codes::UNDEFINED_VARIABLE.diagnostic(span, "", &[&name]));
+        }
+        "Value::Null".into()
+    }
+
+    fn call(&mut self, callee: &Expr, args: &[Expr], span: Span) -> String {
+        let name = match &callee.kind {
+            ExprKind::Variable(name) if !self.is_local(*name) => *name,
+            _ => {
+                self.unsupported(span, "indirect_call", &[]);
+                return "Value::Null".into();
+            }
+        };
+        if let Some(&arity) = self.functions.get(&name) {
+            if arity != args.len() {
+                self.diagnostics.push(error_codes::ARITY_MISMATCH.diagnostic(span, "function", &[&arity, &args.len()]));
+            }
+            let args: Vec<String> = args.iter().map(|a| self.expression(a)).collect();
+            return format!("{}({})", ident(name), args.join(", "));
+        }
+        match name.as_str() {
+            "format" => self.format(args, span),
+            builtin @ ("panic" | "assert") => {
+                if builtin == "assert" && args.is_empty() {
+                    self.diagnostics.push(error_codes::ARITY_MISMATCH.diagnostic(span, "assert", &[]));
+                }
+                let mut code: Vec<String> = args.iter().take(2).map(|a| self.expression(a)).collect();
+                let message = if code.len() > usize::from(builtin == "assert") { code.pop() } else { None };
+                let message = message.map_or("None".to_string(), |m| format!("Some({})", m));
+                code.push(message);
+                format!("value::{}({})", builtin, code.join(", "))
+            }
+            _ => {
+                self.diagnostics.push(error_codes::UNDEFINED_VARIABLE.diagnostic(callee.span, "", &[&name]));
+                "Value::Null".into()
+            }
+        }
+    }
+
+    /// `format("...", args)` 는 검증한 뒤 템플릿을 그대로 Rust `format!` 에 넘깁니다.
+    fn format(&mut self, args: &[Expr], span: Span) -> String {
+        let Some((template, rest)) = args.split_first() else {
+            self.diagnostics.push(error_codes::ARITY_MISMATCH.diagnostic(span, "format", &[]));
+            return "Value::Null".into();
+        };
+        let ExprKind::Literal(Value::String(text)) = &template.kind else {
+            self.unsupported(template.span, "format_template", &[]);
+            return "Value::Null".into();
+        };
+        match formatting::parse_template(text) {
+            Ok(segments) => {
+                let mut used = vec![false; rest.len()];
+                for segment in &segments {
+                    if let Segment::Placeholder { index, .. } = segment {
+                        match used.get_mut(*index) {
+                            Some(slot) => *slot = true,
+                            None => self.diagnostics.push(error_codes::INVALID_FORMAT.diagnostic(
+                                template.span,
+                                "missing_argument",
+                                &[index, &rest.len()],
+                            )),
+                        }
+                    }
+                }
+                if let Some(unused) = used.iter().position(|u| !u) {
+                    self.diagnostics.push(error_codes::INVALID_FORMAT.diagnostic(span, "unused_argument", &[&unused]));
+                }
+            }
+            Err(e) => self.diagnostics.push(error_codes::INVALID_FORMAT.at(template.span, e)),
+        }
+        let mut code = vec![format!("{:?}", text.as_ref())];
+        code.extend(rest.iter().map(|a| self.expression(a)));
+        format!("Value::Str(format!({}))", code.join(", "))
+    }
+}
+
+/// High 식별자를 Rust 식별자로 옮깁니다. 키워드는 raw 식별자로, raw 로 쓸 수 없는 이름은 `_` 를 붙입니다.
+fn ident(name: Symbol) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn",
+        "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become",
+        "box", "do", "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+    ];
+    match name.as_str() {
+        // `main` 과 `value` 는 생성된 코드가 쓰는 이름입니다.
+        n @ ("self" | "Self" | "super" | "crate" | "main" | "value" | "Value") => format!("{}_", n),
+        n if KEYWORDS.contains(&n) => format!("r#{}", n),
+        n => n.to_string(),
+    }
+}
+
+// ─── 생성된 런타임 ─────────────────────────────
+
+/// 생성된 프로젝트의 `src/value.rs`. 런타임 메시지는 지금 로케일의 카탈로그 문구로 채웁니다.
+fn value_rs() -> String {
+    let quoted = |key: &str, args: &[&dyn std::fmt::Display]| format!("{:?}", messages::tr(key, args));
+    VALUE_RS
+        .replace("@PANIC@", &quoted("E0030", &[&"{0}"]))
+        .replace("@EXPLICIT@", &quoted("E0030.explicit", &[]))
+        .replace("@DIVISION_BY_ZERO@", &quoted("E0030.division_by_zero", &[]))
+        .replace("@ASSERT_FAILED@", &quoted("E0030.assert_failed", &[]))
+        .replace("@ASSERT_FAILED_MESSAGE@", &quoted("E0030.assert_failed_message", &[&"{0}"]))
+        .replace("@ASSERT_NOT_BOOL@", &quoted("E0030.assert_not_bool", &[&"{0}"]))
+        .replace("@OPERATOR@", &quoted("E0010.operator", &[&"{0}", &"{1}", &"{2}"]))
+        .replace("@UNARY_OPERATOR@", &quoted("E0010.unary_operator", &[&"{0}", &"{1}"]))
+        .replace("@HEX@", &quoted("E0020.hex_non_integer", &[&"{0}"]))
+}
+
+const VALUE_RS: &str = r#"// 생성된 파일입니다: High 런타임 값과 연산자
+// 프로그램이 쓰지 않는 연산도 함께 들어 있습니다.
+#![allow(dead_code)]
+
+use std::fmt;
+use std::process;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    Null,
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Bool(_) => "bool",
+            Value::Str(_) => "string",
+            Value::Null => "null",
+        }
+    }
+
+    /// 조건식은 `true` 일 때만 참입니다.
+    pub fn is_true(&self) -> bool {
+        matches!(self, Value::Bool(true))
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => fmt::Display::fmt(i, f),
+            Value::Float(x) => fmt::Display::fmt(x, f),
+            Value::Bool(b) => f.pad(if *b { "true" } else { "false" }),
+            Value::Str(s) => f.pad(s),
+            Value::Null => f.pad("null"),
+        }
+    }
+}
+
+impl fmt::LowerHex for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => fmt::LowerHex::fmt(i, f),
+            other => fail(message(@HEX@, &[&format!("{:?}", other)])),
+        }
+    }
+}
+
+impl fmt::UpperHex for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Int(i) => fmt::UpperHex::fmt(i, f),
+            other => fail(message(@HEX@, &[&format!("{:?}", other)])),
+        }
+    }
+}
+
+fn message(template: &str, args: &[&str]) -> String {
+    args.iter().enumerate().fold(template.to_string(), |text, (i, arg)| text.replace(&format!("{{{}}}", i), arg))
+}
+
+/// 실행 오류: 메시지를 출력하고 종료 코드 101 로 끝냅니다.
+pub fn fail(text: String) -> ! {
+    eprintln!("{}", message(@PANIC@, &[&text]));
+    process::exit(101)
+}
+
+/// 최상위 return 값을 출력합니다. null 은 출력하지 않습니다. 정수면 그 값을 종료 코드로 끝냅니다. (exit_code.rs)
+pub fn print_result(result: Value) {
+    if result != Value::Null {
+        println!("{}", result);
+    }
+    if let Value::Int(n) = result {
+        process::exit(i32::try_from(n).unwrap_or(1))
+    }
+}
+
+pub fn binary(op: &str, left: Value, right: Value) -> Value {
+    use Value::{Bool, Float, Int, Str};
+
+    match (op, &left, &right) {
+        ("/" | "%", Int(_), Int(0)) => fail(message(@DIVISION_BY_ZERO@, &[])),
+        ("+", Int(a), Int(b)) => Int(a.wrapping_add(*b)),
+        ("-", Int(a), Int(b)) => Int(a.wrapping_sub(*b)),
+        ("*", Int(a), Int(b)) => Int(a.wrapping_mul(*b)),
+        ("/", Int(a), Int(b)) => Int(a.wrapping_div(*b)),
+        ("%", Int(a), Int(b)) => Int(a.wrapping_rem(*b)),
+        ("&", Int(a), Int(b)) => Int(a & b),
+        ("|", Int(a), Int(b)) => Int(a | b),
+        ("^", Int(a), Int(b)) => Int(a ^ b),
+        ("<<", Int(a), Int(b)) => Int(a.wrapping_shl(*b as u32)),
+        (">>", Int(a), Int(b)) => Int(a.wrapping_shr(*b as u32)),
+
+        ("+", Float(a), Float(b)) => Float(a + b),
+        ("-", Float(a), Float(b)) => Float(a - b),
+        ("*", Float(a), Float(b)) => Float(a * b),
+        ("/", Float(a), Float(b)) => Float(a / b),
+
+        ("+", Str(a), Str(b)) => Str(format!("{}{}", a, b)),
+
+        ("==", Int(a), Int(b)) => Bool(a == b),
+        ("!=", Int(a), Int(b)) => Bool(a != b),
+        ("<", Int(a), Int(b)) => Bool(a < b),
+        (">", Int(a), Int(b)) => Bool(a > b),
+        ("<=", Int(a), Int(b)) => Bool(a <= b),
+        (">=", Int(a), Int(b)) => Bool(a >= b),
+
+        ("==", Float(a), Float(b)) => Bool(a == b),
+        ("!=", Float(a), Float(b)) => Bool(a != b),
+        ("<", Float(a), Float(b)) => Bool(a < b),
+        (">", Float(a), Float(b)) => Bool(a > b),
+        ("<=", Float(a), Float(b)) => Bool(a <= b),
+        (">=", Float(a), Float(b)) => Bool(a >= b),
+
+        ("==", Str(a), Str(b)) => Bool(a == b),
+        ("!=", Str(a), Str(b)) => Bool(a != b),
+        ("==", Bool(a), Bool(b)) => Bool(a == b),
+        ("!=", Bool(a), Bool(b)) => Bool(a != b),
+
+        ("&&", Bool(a), Bool(b)) => Bool(*a && *b),
+        ("||", Bool(a), Bool(b)) => Bool(*a || *b),
+
+        _ => fail(message(@OPERATOR@, &[op, left.type_name(), right.type_name()])),
+    }
+}
+
+pub fn unary(op: &str, operand: Value) -> Value {
+    match (op, &operand) {
+        ("-", Value::Int(a)) => Value::Int(a.wrapping_neg()),
+        ("-", Value::Float(a)) => Value::Float(-a),
+        ("!", Value::Bool(b)) => Value::Bool(!b),
+        ("~", Value::Int(a)) => Value::Int(!a),
+        _ => fail(message(@UNARY_OPERATOR@, &[op, operand.type_name()])),
+    }
+}
+
+pub fn and(left: Value, right: impl FnOnce() -> Value) -> Value {
+    match left {
+        Value::Bool(false) => left,
+        _ => binary("&&", left, right()),
+    }
+}
+
+pub fn or(left: Value, right: impl FnOnce() -> Value) -> Value {
+    match left {
+        Value::Bool(true) => left,
+        _ => binary("||", left, right()),
+    }
+}
+
+pub fn panic(text: Option<Value>) -> Value {
+    match text {
+        Some(value) => fail(value.to_string()),
+        None => fail(message(@EXPLICIT@, &[])),
+    }
+}
+
+pub fn assert(condition: Value, text: Option<Value>) -> Value {
+    match (condition, text) {
+        (Value::Bool(true), _) => Value::Null,
+        (Value::Bool(false), Some(value)) => fail(message(@ASSERT_FAILED_MESSAGE@, &[&value.to_string()])),
+        (Value::Bool(false), None) => fail(message(@ASSERT_FAILED@, &[])),
+        (other, _) => fail(message(@ASSERT_NOT_BOOL@, &[other.type_name()])),
+    }
+}
+"#;
@@ -0,0 +1,380 @@
+// semantic_tokens.rs
+// 편집기용 의미 기반 토큰 분류 (데몬의 semanticTokens, `high highlight --html`, 플레이그라운드 POST /highlight)
+//
+// 렉서 토큰 하나하나를 종류로 나눕니다. 키워드·타입·리터럴은 토큰 종류만으로, 식별자는 선언 자리와
+// 해석기가 참조마다 기록한 선언으로 정합니다.
+//
+//   keyword    let, fn, if, return, ... (true/false 는 literal)
+//   variable   let 으로 선언한 값과 그 참조, 파일에 선언이 없는 이름
+//   parameter  fn/매크로 매개변수와 그 참조
+//   function   `let f = fn(...)` 로 선언한 이름과 그 참조, 매크로가 아닌 이름의 호출 (내장 함수 포함)
+//   macro      매크로 이름, 파일에 정의된 매크로의 호출, `@attr` 이름
+//   type       int, float, ... 와 `let x: Name` 의 타입 이름
+//   literal    숫자, 문자열, 바이트열, 불리언, 렉서 플러그인 리터럴
+//
+// 연산자와 괄호는 분류하지 않습니다. 선언 자리의 토큰에는 `declaration` 수식자가 붙습니다.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data_structures::{Expression, Program, Span, Statement, Token, TokenKind};
+use crate::interner;
+use crate::json::Json;
+use crate::lexer_service::LexerService;
+use crate::node_ids::{NodeIndex, SideTable};
+use crate::parser_service::ParserService;
+use crate::resolver::{Resolver, Symbol, SymbolKind};
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    Keyword,
+    Variable,
+    Parameter,
+    Function,
+    Macro,
+    Type,
+    Literal,
+}
+
+/// LSP `SemanticTokensLegend.tokenTypes` 순서. `TokenType as u32` 가 이 목록의 번호입니다.
+pub const TOKEN_TYPES: [&str; 7] = ["keyword", "variable", "parameter", "function", "macro", "type", "literal"];
+/// LSP `SemanticTokensLegend.tokenModifiers`
+pub const TOKEN_MODIFIERS: [&str; 1] = ["declaration"];
+
+impl TokenType {
+    pub fn name(self) -> &'static str {
+        TOKEN_TYPES[self as usize]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SemanticToken {
+    pub span: Span,
+    pub token_type: TokenType,
+    pub declaration: bool,
+}
+
+/// 소스 순서의 분류된 토큰
+pub fn classify(source: &str) -> Vec<SemanticToken> {
+    let program = ParserService::new(LexerService::new(source)).parse_program();
+    let references = References::collect(&program);
+    let tokens = tokenize(source);
+
+    let mut out = vec![];
+    // fn/매크로 매개변수 목록 안인지: '(' 를 만나면 true, ')' 에서 false
+    let (mut parameter_list_next, mut in_parameters) = (false, false);
+    for (i, token) in tokens.iter().enumerate() {
+        let previous = |n: usize| i.checked_sub(n).map(|j| &tokens[j].kind);
+        let (token_type, declaration) = match &token.kind {
+            TokenKind::LParen if parameter_list_next => {
+                in_parameters = true;
+                continue;
+            }
+            TokenKind::RParen => {
+                in_parameters = false;
+                continue;
+            }
+            TokenKind::Fn => {
+                parameter_list_next = true;
+                (TokenType::Keyword, false)
+            }
+            kind if keyword(kind) => (TokenType::Keyword, false),
+            TokenKind::Int | TokenKind::Float | TokenKind::Bool | TokenKind::String | TokenKind::Void | TokenKind::Any => {
+                (TokenType::Type, false)
+            }
+            TokenKind::IntegerLiteral(_)
+            | TokenKind::FloatLiteral(_)
+            | TokenKind::StringLiteral(_)
+            | TokenKind::BytesLiteral(_)
+            | TokenKind::BooleanLiteral(_)
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::Custom { .. } => (TokenType::Literal, false),
+            TokenKind::Identifier(_) if in_parameters => (TokenType::Parameter, true),
+            TokenKind::Identifier(_) => match (previous(1), previous(2), previous(3)) {
+                (Some(TokenKind::Macro), ..) => {
+                    parameter_list_next = true;
+                    (TokenType::Macro, true)
+                }
+                (Some(TokenKind::At), ..) => (TokenType::Macro, false),
+                // `fn name(...)` 선언
+                (Some(TokenKind::Fn), ..) => (TokenType::Function, true),
+                // `type Meters = int` 의 별칭 이름과, `= Meters` 처럼 별칭 대상으로 쓴 이름
+                (Some(TokenKind::Type), ..) => (TokenType::Type, true),
+                (Some(TokenKind::Assign), Some(TokenKind::Identifier(_)), Some(TokenKind::Type)) => (TokenType::Type, false),
+                (Some(TokenKind::Let | TokenKind::Mut), ..) => {
+                    let let_token = if matches!(previous(1), Some(TokenKind::Mut)) { i - 2 } else { i - 1 };
+                    let function = references.function_lets.contains(&tokens[let_token].span.start);
+                    (if function { TokenType::Function } else { TokenType::Variable }, true)
+                }
+                // `for x in xs` 의 반복 변수
+                (Some(TokenKind::For), ..) => (TokenType::Variable, true),
+                (Some(TokenKind::Colon), Some(TokenKind::Identifier(_)), Some(TokenKind::Let | TokenKind::Mut)) => {
+                    (TokenType::Type, false)
+                }
+                _ => (references.at(token.span.start), false),
+            },
+            _ => continue,
+        };
+        if !matches!(token.kind, TokenKind::Fn | TokenKind::Macro | TokenKind::Identifier(_)) {
+            parameter_list_next = false;
+        }
+        out.push(SemanticToken { span: token.span, token_type, declaration });
+    }
+    out
+}
+
+fn keyword(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Let
+            | TokenKind::Mut
+            | TokenKind::If
+            | TokenKind::Else
+            | TokenKind::While
+            | TokenKind::For
+            | TokenKind::In
+            | TokenKind::Return
+            | TokenKind::Match
+            | TokenKind::Macro
+            | TokenKind::Type
+            | TokenKind::Enum
+            | TokenKind::TypeOf
+            | TokenKind::Eval
+            | TokenKind::Reflect
+            | TokenKind::Async
+            | TokenKind::Await
+            | TokenKind::Yield
+            | TokenKind::Defer
+    )
+}
+
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut lexer = LexerService::new(source);
+    let mut tokens = vec![];
+    loop {
+        let token = lexer.next_token();
+        if matches!(token.kind, TokenKind::Eof) {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+// ─── 해석기 정보 ─────────────────────────────
+
+/// 식별자 참조의 분류 (토큰 시작 위치 → 종류)
+struct References {
+    at: HashMap<usize, TokenType>,
+    /// 함수 리터럴로 초기화한 `let` 문장의 시작 위치 (`let` 키워드 토큰의 자리)
+    function_lets: HashSet<usize>,
+}
+
+impl References {
+    fn collect(program: &Program) -> Self {
+        let index = NodeIndex::build(program);
+        let symbols = Resolver::resolve_symbols(program, &index);
+        let mut collector = Collector {
+            index: &index,
+            symbols: &symbols,
+            attribute: None,
+            functions: HashSet::new(),
+            macros: program.statements.iter().filter_map(macro_name).collect(),
+            references: References { at: HashMap::new(), function_lets: HashSet::new() },
+        };
+        collector.visit_program(program);
+        collector.references
+    }
+
+    fn at(&self, start: usize) -> TokenType {
+        self.at.get(&start).copied().unwrap_or(TokenType::Variable)
+    }
+}
+
+struct Collector<'c> {
+    index: &'c NodeIndex,
+    symbols: &'c SideTable<Symbol>,
+    /// 바로 바깥 `Attributed` 의 첫 어트리뷰트 범위 (해석기가 선언 범위로 쓰는 값)
+    attribute: Option<Span>,
+    /// 함수 리터럴로 초기화한 선언: (이름, 선언 범위 시작, 끝)
+    functions: HashSet<(interner::Symbol, usize, usize)>,
+    /// 파일에서 정의한 매크로 이름
+    macros: HashSet<String>,
+    references: References,
+}
+
+impl Visitor for Collector<'_> {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        let declaration_span = self.attribute.take().unwrap_or(stmt.span());
+        match stmt {
+            Statement::Attributed { attributes, .. } => self.attribute = attributes.first().map(|a| a.span),
+            Statement::LetStatement { name, value, span, .. } => {
+                if let Expression::Function(..) = value.as_ref() {
+                    self.functions.insert((*name, declaration_span.start, declaration_span.end));
+                    self.references.function_lets.insert(span.start);
+                }
+            }
+            _ => {}
+        }
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::Identifier(span, _) => {
+                let symbol = self.index.expression(expr).and_then(|id| self.symbols.get(id));
+                let token_type = match symbol {
+                    Some(symbol) => match symbol.kind {
+                        SymbolKind::Parameter => TokenType::Parameter,
+                        SymbolKind::Macro => TokenType::Macro,
+                        SymbolKind::Variable => {
+                            let key = (symbol.name, symbol.declaration_span.start, symbol.declaration_span.end);
+                            if self.functions.contains(&key) {
+                                TokenType::Function
+                            } else {
+                                TokenType::Variable
+                            }
+                        }
+                    },
+                    None => TokenType::Variable,
+                };
+                self.references.at.insert(span.start, token_type);
+            }
+            // 파일에 선언이 없는 이름을 호출하면 내장 함수입니다.
+            Expression::Call(_, function, _) => {
+                if let Expression::Identifier(span, _) = function.as_ref() {
+                    if self.index.expression(function).and_then(|id| self.symbols.get(id)).is_none() {
+                        walk_expression(self, expr);
+                        self.references.at.insert(span.start, TokenType::Function);
+                        return;
+                    }
+                }
+            }
+            // `name(args)` 는 모두 매크로 호출로 파싱되므로, 파일에 그 이름의 매크로가 없으면 함수 호출입니다.
+            Expression::MacroCall(span, name, _) => {
+                let symbol = self.index.expression(expr).and_then(|id| self.symbols.get(id));
+                let token_type = match symbol.map(|s| &s.kind) {
+                    Some(SymbolKind::Macro) => TokenType::Macro,
+                    Some(SymbolKind::Parameter) => TokenType::Parameter,
+                    // 매크로는 정의보다 앞에서도 부를 수 있으므로 이름으로도 확인합니다.
+                    _ if self.macros.contains(name.as_str()) => TokenType::Macro,
+                    _ => TokenType::Function,
+                };
+                self.references.at.insert(span.start, token_type);
+            }
+            _ => {}
+        }
+        walk_expression(self, expr);
+    }
+}
+
+/// 최상위 매크로 정의의 이름 (어트리뷰트가 붙은 정의 포함)
+fn macro_name(stmt: &Statement) -> Option<String> {
+    match stmt {
+        Statement::MacroDefinition { name, .. } => Some(name.clone()),
+        Statement::Attributed { statement, .. } => macro_name(statement),
+        _ => None,
+    }
+}
+
+// ─── 출력 ─────────────────────────────
+
+/// LSP `SemanticTokens.data`: 토큰마다 (줄 차이, 시작 열 차이, 길이, 종류, 수식자 비트) 다섯 개씩.
+/// 열과 길이는 UTF-16 단위이며, 여러 줄에 걸친 토큰은 첫 줄까지만 씁니다.
+pub fn encode_lsp(source: &str, tokens: &[SemanticToken]) -> Vec<u32> {
+    // 문자 위치 → (줄, UTF-16 열)
+    let mut positions = Vec::with_capacity(source.len() + 1);
+    let (mut line, mut column) = (0u32, 0u32);
+    for c in source.chars() {
+        positions.push((line, column));
+        if c == '\n' {
+            line += 1;
+            column = 0;
+        } else {
+            column += c.len_utf16() as u32;
+        }
+    }
+    positions.push((line, column));
+
+    let mut data = Vec::with_capacity(tokens.len() * 5);
+    let (mut previous_line, mut previous_column) = (0, 0);
+    for token in tokens {
+        let Some(&(line, column)) = positions.get(token.span.start) else {
+            break;
+        };
+        let end = (token.span.start..token.span.end.min(positions.len() - 1))
+            .find(|&i| positions[i + 1].0 != line)
+            .map_or(token.span.end.min(positions.len() - 1), |i| i + 1);
+        let length = positions[end].1.max(column) - column;
+        let delta_column = if line == previous_line { column - previous_column } else { column };
+        data.extend([line - previous_line, delta_column, length, token.token_type as u32, u32::from(token.declaration)]);
+        (previous_line, previous_column) = (line, column);
+    }
+    data
+}
+
+/// 데몬 `semanticTokens` 응답: `{legend: {tokenTypes, tokenModifiers}, data}`
+pub fn to_json(source: &str) -> Json {
+    let names = |names: &[&str]| Json::Array(names.iter().map(|n| Json::from(*n)).collect());
+    Json::object([
+        ("legend", Json::object([("tokenTypes", names(&TOKEN_TYPES)), ("tokenModifiers", names(&TOKEN_MODIFIERS))])),
+        ("data", Json::Array(encode_lsp(source, &classify(source)).into_iter().map(|n| Json::from(n as u64)).collect())),
+    ])
+}
+
+/// 분류한 토큰을 `<span class="hl-종류">` 로 감싼 `<pre>` 조각. 문서와 플레이그라운드에 그대로 넣습니다.
+pub fn render_html(source: &str) -> String {
+    let tokens = classify(source);
+    let mut html = String::from("<pre class=\"high-code\"><code>");
+    let mut tokens = tokens.iter().peekable();
+    let mut open: Option<usize> = None;
+    for (i, c) in source.chars().enumerate() {
+        if open == Some(i) {
+            html.push_str("</span>");
+            open = None;
+        }
+        while tokens.peek().is_some_and(|t| t.span.end <= i) {
+            tokens.next();
+        }
+        if let Some(token) = tokens.peek().filter(|t| t.span.start == i && open.is_none()) {
+            let declaration = if token.declaration { " hl-declaration" } else { "" };
+            html.push_str(&format!("<span class=\"hl-{}{}\">", token.token_type.name(), declaration));
+            open = Some(token.span.end);
+        }
+        match c {
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '&' => html.push_str("&amp;"),
+            '"' => html.push_str("&quot;"),
+            c => html.push(c),
+        }
+    }
+    if open.is_some() {
+        html.push_str("</span>");
+    }
+    html.push_str("</code></pre>");
+    html
+}
+
+/// 색 정의를 함께 넣은 HTML 문서 한 장 (`high highlight --html`)
+pub fn html_page(title: &str, source: &str) -> String {
+    let title = title.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+        title,
+        STYLE,
+        render_html(source)
+    )
+}
+
+const STYLE: &str = "\
+.high-code { background: #fafafa; color: #383a42; padding: 1em; font-family: ui-monospace, monospace; }
+.hl-keyword { color: #a626a4; }
+.hl-variable { color: #383a42; }
+.hl-parameter { color: #986801; font-style: italic; }
+.hl-function { color: #4078f2; }
+.hl-macro { color: #0184bc; }
+.hl-type { color: #c18401; }
+.hl-literal { color: #50a14f; }
+.hl-declaration { font-weight: bold; }
+";
@@ -0,0 +1,778 @@
+// snapshot.rs
+// 런타임 상태의 스냅샷과 복원입니다. REPL, 노트북, 게임 스크립트처럼 오래 도는 세션이
+// 체크포인트를 남기고 나중에 그 상태로 되돌릴 때 씁니다.
+//
+// 스냅샷에는 전역 환경에서 닿을 수 있는 모든 것이 들어갑니다: 바인딩 값, 함수 본문, 제너레이터.
+// 함수 값은 환경을 붙잡지 않고(이름은 호출 시점의 환경에서 찾습니다) 본문만 가지므로 본문 AST 를 그대로
+// 담습니다. 환경을 붙잡는 값은 멈춰 있는 제너레이터뿐입니다. 제너레이터의 프레임은 자기 호출 환경을
+// 가리키고, 그 환경의 바깥은 전역 환경입니다.
+//
+// 그래서 환경과 제너레이터는 `Rc` 주소로 표에 한 번씩만 넣고 값은 표 번호로 가리킵니다. 같은 환경을
+// 공유하던 값들은 복원 뒤에도 공유하고, 제너레이터가 자기 자신을 담은 환경처럼 순환하는 경우도 끝납니다.
+// 0 번 환경이 전역입니다.
+//
+// `encode` 는 텍스트 형식이며 본문은 `ast_printer` 로 출력한 소스입니다. 문자열은 `<바이트 수>:<내용>`
+// 으로 적으므로 어떤 내용이든 이스케이프 없이 들어갑니다. 복원하면 함수는 새 번호를 받으므로
+// 계층형 실행의 호출 횟수는 처음부터 다시 셉니다.
+
+use alloc::rc::{Rc, Weak};
+use alloc::sync::Arc;
+use core::cell::RefCell;
+use core::fmt::Write as _;
+
+use crate::ast_printer;
+use crate::bytes;
+use crate::compat::*;
+use crate::data_structures::{
+    Derives, EnumVariant, Expression, FunctionValue, Program, Record, ReflectionInfo, Span, Statement, TypeAnnotation, Value,
+};
+use crate::datetime::DateTime;
+use crate::ft_runtime::Environment;
+use crate::generator::GeneratorState;
+use crate::interner::Symbol;
+use crate::maps::{Key, Map};
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+use crate::regexp::{Group, RegexMatch};
+
+/// 형식 버전. 다른 버전의 스냅샷은 읽지 않습니다.
+const FORMAT_HEADER: &str = "high-snapshot 3";
+
+/// 런타임 상태의 이미지. 안에 `Rc` 가 없으므로 복제해 두고 여러 번 복원할 수 있습니다.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// 0 번이 전역 환경
+    pub environments: Vec<EnvironmentImage>,
+    pub generators: Vec<GeneratorImage>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentImage {
+    pub outer: Option<usize>,
+    /// 이름 순으로 정렬됩니다.
+    pub bindings: Vec<(String, ValueImage)>,
+    pub slots: Vec<Option<ValueImage>>,
+    pub slot_names: Option<Vec<String>>,
+    /// `let mut` 바인딩과 그 타입 표기. 이름 순으로 정렬됩니다.
+    pub mutable: Vec<(String, Option<TypeAnnotation>)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ValueImage {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Bytes(Vec<u8>),
+    Match(RegexMatch),
+    Matches(Vec<RegexMatch>),
+    DateTime { millis: i64, offset_minutes: i32 },
+    Duration(i64),
+    /// `start..end`
+    Range(i64, i64),
+    Record { name: String, fields: Vec<(String, ValueImage)> },
+    Array(Vec<ValueImage>),
+    /// 넣은 순서의 (키, 값)
+    Map(Vec<(ValueImage, ValueImage)>),
+    /// 생성자면 `fields` 가 None 입니다.
+    EnumVariant { enum_name: String, name: String, field_types: Vec<TypeAnnotation>, fields: Option<Vec<ValueImage>>, derives: Derives },
+    Null,
+    Return(Box<ValueImage>),
+    Error(String),
+    Reflection { type_name: String, details: String },
+    Macro(String),
+    Type(String),
+    /// `id` 는 스냅샷 안에서 같은 함수 값을 알아보는 데만 씁니다.
+    Function { id: usize, parameters: Vec<String>, body: Statement },
+    /// `Snapshot::generators` 의 번호
+    Generator(usize),
+    /// 약한 참조. 찍을 때 대상이 살아 있었으면 그 제너레이터 번호입니다. 복원 뒤 다른 곳에서 붙잡지 않는
+    /// 대상은 곧바로 해제되므로, 찍을 때처럼 죽은 참조가 됩니다.
+    WeakRef(Option<usize>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorImage {
+    pub frames: Vec<FrameImage>,
+    pub finished: bool,
+    pub returned: Option<ValueImage>,
+}
+
+/// 제너레이터의 멈춘 실행 위치. `env` 는 `Snapshot::environments` 의 번호입니다.
+#[derive(Debug, Clone)]
+pub enum FrameImage {
+    Block { statements: Vec<Statement>, index: usize, env: usize, deferred: Vec<Statement> },
+    While { condition: Expression, body: Statement, env: usize },
+    For { condition: Option<Expression>, increment: Option<Expression>, body: Statement, started: bool, env: usize },
+    /// `remaining` 은 아직 돌지 않은 요소들을 담은 값입니다. (`Cursor::remaining`)
+    ForIn { variable: String, remaining: ValueImage, body: Statement, env: usize },
+}
+
+impl Snapshot {
+    /// `root` 에서 닿을 수 있는 상태를 담습니다.
+    pub fn capture(root: &Rc<RefCell<Environment>>) -> Snapshot {
+        let mut capture = Capture::default();
+        capture.environment(root);
+        Snapshot { environments: capture.environments, generators: capture.generators }
+    }
+
+    /// 새 환경 그래프를 만들고 전역 환경을 돌려줍니다. 번호가 맞지 않으면 오류입니다.
+    pub fn rebuild(&self) -> Result<Rc<RefCell<Environment>>, String> {
+        if self.environments.is_empty() {
+            return Err("스냅샷에 전역 환경이 없습니다.".into());
+        }
+        let mut rebuild = Rebuild {
+            environments: self.environments.iter().map(|_| Rc::new(RefCell::new(Environment::new()))).collect(),
+            generators: self.generators.iter().map(|_| Rc::new(RefCell::new(GeneratorState::finished()))).collect(),
+            functions: HashMap::new(),
+        };
+        for (index, image) in self.environments.iter().enumerate() {
+            let environment = rebuild.environment_from(image)?;
+            *rebuild.environments[index].borrow_mut() = environment;
+        }
+        for (index, image) in self.generators.iter().enumerate() {
+            let state = GeneratorState::from_image(image, &mut rebuild)?;
+            *rebuild.generators[index].borrow_mut() = state;
+        }
+        Ok(rebuild.environments[0].clone())
+    }
+
+    pub fn encode(&self) -> String {
+        let mut w = Writer { out: String::from(FORMAT_HEADER) };
+        w.word("envs");
+        w.number(self.environments.len());
+        for env in &self.environments {
+            w.optional(env.outer, |w, outer| w.number(outer));
+            w.number(env.bindings.len());
+            for (name, value) in &env.bindings {
+                w.text(name);
+                w.value(value);
+            }
+            w.number(env.slots.len());
+            for slot in &env.slots {
+                w.optional(slot.as_ref(), |w, value| w.value(value));
+            }
+            w.optional(env.slot_names.as_ref(), |w, names| {
+                w.number(names.len());
+                names.iter().for_each(|name| w.text(name));
+            });
+            w.number(env.mutable.len());
+            for (name, annotation) in &env.mutable {
+                w.text(name);
+                w.optional(annotation.as_ref(), |w, annotation| w.text(&annotation.to_string()));
+            }
+        }
+        w.word("gens");
+        w.number(self.generators.len());
+        for generator in &self.generators {
+            w.flag(generator.finished);
+            w.optional(generator.returned.as_ref(), |w, value| w.value(value));
+            w.number(generator.frames.len());
+            for frame in &generator.frames {
+                match frame {
+                    FrameImage::Block { statements, index, env, deferred } => {
+                        w.word("block");
+                        w.text(&print_statements(statements));
+                        w.number(*index);
+                        w.number(*env);
+                        w.text(&print_statements(deferred));
+                    }
+                    FrameImage::While { condition, body, env } => {
+                        w.word("while");
+                        w.text(&ast_printer::print_expression(condition));
+                        w.text(&print_statements(core::slice::from_ref(body)));
+                        w.number(*env);
+                    }
+                    FrameImage::For { condition, increment, body, started, env } => {
+                        w.word("for");
+                        w.optional(condition.as_ref(), |w, c| w.text(&ast_printer::print_expression(c)));
+                        w.optional(increment.as_ref(), |w, i| w.text(&ast_printer::print_expression(i)));
+                        w.text(&print_statements(core::slice::from_ref(body)));
+                        w.flag(*started);
+                        w.number(*env);
+                    }
+                    FrameImage::ForIn { variable, remaining, body, env } => {
+                        w.word("forin");
+                        w.text(variable);
+                        w.value(remaining);
+                        w.text(&print_statements(core::slice::from_ref(body)));
+                        w.number(*env);
+                    }
+                }
+            }
+        }
+        w.out.push('\n');
+        w.out
+    }
+
+    pub fn decode(text: &str) -> Result<Snapshot, String> {
+        let rest = text
+            .strip_prefix(FORMAT_HEADER)
+            .ok_or_else(|| format!("스냅샷 형식이 아닙니다. (첫 줄이 '{}' 이어야 합니다)", FORMAT_HEADER))?;
+        let mut r = Reader { rest };
+        r.expect("envs")?;
+        let mut environments = vec![];
+        for _ in 0..r.number()? {
+            let outer = r.optional(|r| r.number())?;
+            let mut bindings = vec![];
+            for _ in 0..r.number()? {
+                bindings.push((r.text()?.to_string(), r.value()?));
+            }
+            let mut slots = vec![];
+            for _ in 0..r.number()? {
+                slots.push(r.optional(|r| r.value())?);
+            }
+            let slot_names = r.optional(|r| (0..r.number()?).map(|_| r.text().map(str::to_string)).collect())?;
+            let mut mutable = vec![];
+            for _ in 0..r.number()? {
+                mutable.push((r.text()?.to_string(), r.optional(|r| parse_type(r.text()?))?));
+            }
+            environments.push(EnvironmentImage { outer, bindings, slots, slot_names, mutable });
+        }
+        r.expect("gens")?;
+        let mut generators = vec![];
+        for _ in 0..r.number()? {
+            let finished = r.flag()?;
+            let returned = r.optional(|r| r.value())?;
+            let mut frames = vec![];
+            for _ in 0..r.number()? {
+                frames.push(match r.word()? {
+                    "block" => FrameImage::Block {
+                        statements: parse_statements(r.text()?),
+                        index: r.number()?,
+                        env: r.number()?,
+                        deferred: parse_statements(r.text()?),
+                    },
+                    "while" => FrameImage::While {
+                        condition: parse_expression(r.text()?)?,
+                        body: parse_statement(r.text()?)?,
+                        env: r.number()?,
+                    },
+                    "for" => FrameImage::For {
+                        condition: r.optional(|r| parse_expression(r.text()?))?,
+                        increment: r.optional(|r| parse_expression(r.text()?))?,
+                        body: parse_statement(r.text()?)?,
+                        started: r.flag()?,
+                        env: r.number()?,
+                    },
+                    "forin" => FrameImage::ForIn {
+                        variable: r.text()?.to_string(),
+                        remaining: r.value()?,
+                        body: parse_statement(r.text()?)?,
+                        env: r.number()?,
+                    },
+                    other => return Err(format!("알 수 없는 제너레이터 프레임 '{}'", other)),
+                });
+            }
+            generators.push(GeneratorImage { frames, finished, returned });
+        }
+        Ok(Snapshot { environments, generators })
+    }
+}
+
+// ─── 담기 ─────────────────────────────
+
+/// 주소로 환경과 제너레이터를 한 번씩만 표에 넣습니다.
+#[derive(Default)]
+pub(crate) struct Capture {
+    environments: Vec<EnvironmentImage>,
+    generators: Vec<GeneratorImage>,
+    environment_ids: HashMap<usize, usize>,
+    generator_ids: HashMap<usize, usize>,
+}
+
+impl Capture {
+    pub(crate) fn environment(&mut self, env: &Rc<RefCell<Environment>>) -> usize {
+        let key = Rc::as_ptr(env) as usize;
+        if let Some(&index) = self.environment_ids.get(&key) {
+            return index;
+        }
+        // 순환을 끊도록 내용을 담기 전에 번호부터 정합니다.
+        let index = self.environments.len();
+        self.environment_ids.insert(key, index);
+        self.environments.push(EnvironmentImage::default());
+
+        let env = env.borrow();
+        let outer = env.outer.as_ref().map(|outer| self.environment(outer));
+        let mut bindings: Vec<(String, ValueImage)> =
+            env.store.iter().map(|(name, value)| (name.as_str().to_string(), self.value(value))).collect();
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        let slots = env.slots.iter().map(|slot| slot.as_ref().map(|value| self.value(value))).collect();
+        let slot_names = env.slot_names.as_ref().map(|names| names.iter().map(|n| n.as_str().to_string()).collect());
+        let mut mutable: Vec<(String, Option<TypeAnnotation>)> =
+            env.mutable.iter().map(|(name, annotation)| (name.as_str().to_string(), annotation.clone())).collect();
+        mutable.sort_by(|a, b| a.0.cmp(&b.0));
+        self.environments[index] = EnvironmentImage { outer, bindings, slots, slot_names, mutable };
+        index
+    }
+
+    pub(crate) fn value(&mut self, value: &Value) -> ValueImage {
+        match value {
+            Value::Integer(i) => ValueImage::Integer(*i),
+            Value::Float(f) => ValueImage::Float(*f),
+            Value::Boolean(b) => ValueImage::Boolean(*b),
+            Value::String(s) => ValueImage::String(s.to_string()),
+            Value::Bytes(b) => ValueImage::Bytes(b.to_vec()),
+            Value::Match(m) => ValueImage::Match(m.as_ref().clone()),
+            Value::Matches(all) => ValueImage::Matches(all.to_vec()),
+            Value::DateTime(t) => ValueImage::DateTime { millis: t.millis, offset_minutes: t.offset_minutes },
+            Value::Duration(d) => ValueImage::Duration(*d),
+            Value::Range(start, end) => ValueImage::Range(*start, *end),
+            Value::Record(record) => ValueImage::Record {
+                name: record.name.clone(),
+                fields: record.fields.iter().map(|(name, value)| (name.clone(), self.value(value))).collect(),
+            },
+            Value::Array(items) => ValueImage::Array(items.iter().map(|item| self.value(item)).collect()),
+            Value::Map(map) => {
+                ValueImage::Map(map.iter().map(|(key, value)| (self.value(&key.to_value()), self.value(value))).collect())
+            }
+            Value::EnumVariant(variant) => ValueImage::EnumVariant {
+                enum_name: variant.enum_name.to_string(),
+                name: variant.name.to_string(),
+                field_types: variant.field_types.clone(),
+                fields: variant.fields.as_ref().map(|fields| fields.iter().map(|field| self.value(field)).collect()),
+                derives: variant.derives,
+            },
+            Value::Null => ValueImage::Null,
+            Value::Return(inner) => ValueImage::Return(Box::new(self.value(inner))),
+            Value::Error(e) => ValueImage::Error(e.clone()),
+            Value::Reflection(info) => {
+                ValueImage::Reflection { type_name: info.type_name.clone(), details: info.details.clone() }
+            }
+            Value::Macro(name) => ValueImage::Macro(name.clone()),
+            Value::Type(name) => ValueImage::Type(name.clone()),
+            Value::Function(func) => ValueImage::Function {
+                id: func.id,
+                parameters: func.parameters.iter().map(|p| p.as_str().to_string()).collect(),
+                body: func.body.clone(),
+            },
+            Value::Generator(state) => ValueImage::Generator(self.generator(state)),
+            Value::WeakRef(target) => ValueImage::WeakRef(target.upgrade().map(|state| self.generator(&state))),
+        }
+    }
+
+    fn generator(&mut self, state: &Rc<RefCell<GeneratorState>>) -> usize {
+        let key = Rc::as_ptr(state) as usize;
+        if let Some(&index) = self.generator_ids.get(&key) {
+            return index;
+        }
+        let index = self.generators.len();
+        self.generator_ids.insert(key, index);
+        self.generators.push(GeneratorImage::default());
+        let image = state.borrow().to_image(self);
+        self.generators[index] = image;
+        index
+    }
+}
+
+// ─── 복원 ─────────────────────────────
+
+/// 표 번호를 새로 만든 환경과 제너레이터로 바꿉니다.
+pub(crate) struct Rebuild {
+    environments: Vec<Rc<RefCell<Environment>>>,
+    generators: Vec<Rc<RefCell<GeneratorState>>>,
+    /// 스냅샷의 함수 번호 → 새 함수 값 (같은 함수 값은 복원 뒤에도 같은 번호)
+    functions: HashMap<usize, FunctionValue>,
+}
+
+impl Rebuild {
+    pub(crate) fn environment(&self, index: usize) -> Result<Rc<RefCell<Environment>>, String> {
+        self.environments.get(index).cloned().ok_or_else(|| format!("없는 환경 번호 {}", index))
+    }
+
+    fn environment_from(&mut self, image: &EnvironmentImage) -> Result<Environment, String> {
+        let mut env = match image.outer {
+            Some(outer) => Environment::new_enclosed(self.environment(outer)?),
+            None => Environment::new(),
+        };
+        for (name, value) in &image.bindings {
+            env.set(Symbol::intern(name), self.value(value)?);
+        }
+        env.mutable = image.mutable.iter().map(|(name, annotation)| (Symbol::intern(name), annotation.clone())).collect();
+        env.slots = image.slots.iter().map(|slot| slot.as_ref().map(|v| self.value(v)).transpose()).collect::<Result<_, _>>()?;
+        env.slot_names = image.slot_names.as_ref().map(|names| names.iter().map(|n| Symbol::intern(n)).collect());
+        if env.slot_names.as_ref().is_some_and(|names| names.len() != env.slots.len()) {
+            return Err("환경의 칸 수와 칸 이름 수가 다릅니다.".into());
+        }
+        Ok(env)
+    }
+
+    pub(crate) fn value(&mut self, image: &ValueImage) -> Result<Value, String> {
+        Ok(match image {
+            ValueImage::Integer(i) => Value::Integer(*i),
+            ValueImage::Float(f) => Value::Float(*f),
+            ValueImage::Boolean(b) => Value::Boolean(*b),
+            ValueImage::String(s) => Value::String(s.as_str().into()),
+            ValueImage::Bytes(b) => Value::Bytes(b.as_slice().into()),
+            ValueImage::Match(m) => Value::Match(Arc::new(m.clone())),
+            ValueImage::Matches(all) => Value::Matches(all.as_slice().into()),
+            ValueImage::DateTime { millis, offset_minutes } => {
+                Value::DateTime(DateTime { millis: *millis, offset_minutes: *offset_minutes })
+            }
+            ValueImage::Duration(d) => Value::Duration(*d),
+            ValueImage::Range(start, end) => Value::Range(*start, *end),
+            ValueImage::Record { name, fields } => Value::Record(Rc::new(Record {
+                name: name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|(field, image)| Ok((field.clone(), self.value(image)?)))
+                    .collect::<Result<_, String>>()?,
+            })),
+            ValueImage::Array(items) => {
+                Value::Array(items.iter().map(|image| self.value(image)).collect::<Result<Vec<_>, String>>()?.into())
+            }
+            ValueImage::Map(entries) => {
+                let mut map = Map::default();
+                for (key, value) in entries {
+                    let key = self.value(key)?;
+                    let key = Key::from_value(&key).ok_or("맵 키가 int, bool, string 이 아닙니다.")?;
+                    map.insert(key, self.value(value)?);
+                }
+                Value::Map(Rc::new(map))
+            }
+            ValueImage::EnumVariant { enum_name, name, field_types, fields, derives } => Value::EnumVariant(Rc::new(EnumVariant {
+                enum_name: Symbol::intern(enum_name),
+                name: Symbol::intern(name),
+                field_types: field_types.clone(),
+                fields: match fields {
+                    Some(fields) => Some(fields.iter().map(|image| self.value(image)).collect::<Result<_, String>>()?),
+                    None => None,
+                },
+                derives: *derives,
+            })),
+            ValueImage::Null => Value::Null,
+            ValueImage::Return(inner) => Value::Return(Box::new(self.value(inner)?)),
+            ValueImage::Error(e) => Value::Error(e.clone()),
+            ValueImage::Reflection { type_name, details } => {
+                Value::Reflection(ReflectionInfo { type_name: type_name.clone(), details: details.clone() })
+            }
+            ValueImage::Macro(name) => Value::Macro(name.clone()),
+            ValueImage::Type(name) => Value::Type(name.clone()),
+            ValueImage::Function { id, parameters, body } => {
+                let func = self.functions.entry(*id).or_insert_with(|| {
+                    FunctionValue::new(parameters.iter().map(|p| Symbol::intern(p)).collect(), body.clone())
+                });
+                Value::Function(Box::new(func.clone()))
+            }
+            ValueImage::Generator(index) => Value::Generator(
+                self.generators.get(*index).cloned().ok_or_else(|| format!("없는 제너레이터 번호 {}", index))?,
+            ),
+            ValueImage::WeakRef(None) => Value::WeakRef(Weak::new()),
+            ValueImage::WeakRef(Some(index)) => Value::WeakRef(Rc::downgrade(
+                self.generators.get(*index).ok_or_else(|| format!("없는 제너레이터 번호 {}", index))?,
+            )),
+        })
+    }
+}
+
+// ─── 텍스트 형식 ─────────────────────────────
+
+struct Writer {
+    out: String,
+}
+
+impl Writer {
+    fn word(&mut self, word: &str) {
+        self.out.push(' ');
+        self.out.push_str(word);
+    }
+
+    fn number(&mut self, n: impl core::fmt::Display) {
+        let _ = write!(self.out, " {}", n);
+    }
+
+    fn flag(&mut self, b: bool) {
+        self.word(if b { "1" } else { "0" });
+    }
+
+    fn text(&mut self, s: &str) {
+        let _ = write!(self.out, " {}:{}", s.len(), s);
+    }
+
+    /// 없으면 `-`, 있으면 `+` 다음에 값
+    fn optional<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => {
+                self.word("+");
+                write(self, value);
+            }
+            None => self.word("-"),
+        }
+    }
+
+    /// 그룹 수, 그룹마다 `+ 시작 끝 텍스트` 나 `-`, 그다음 그룹 이름들
+    fn regex_match(&mut self, m: &RegexMatch) {
+        self.number(m.groups.len());
+        for group in &m.groups {
+            self.optional(group.as_ref(), |w, g| {
+                w.number(g.start);
+                w.number(g.end);
+                w.text(&g.text);
+            });
+        }
+        for name in &m.names {
+            self.optional(name.as_deref(), |w, name| w.text(name));
+        }
+    }
+
+    fn value(&mut self, value: &ValueImage) {
+        match value {
+            ValueImage::Integer(i) => {
+                self.word("i");
+                self.number(i);
+            }
+            // 비트 그대로 적어 NaN 과 -0.0 까지 보존합니다.
+            ValueImage::Float(f) => {
+                self.word("f");
+                self.number(f.to_bits());
+            }
+            ValueImage::Boolean(b) => {
+                self.word("b");
+                self.flag(*b);
+            }
+            ValueImage::String(s) => {
+                self.word("s");
+                self.text(s);
+            }
+            // 바이트 버퍼는 16진수 텍스트로 적습니다.
+            ValueImage::Bytes(b) => {
+                self.word("bytes");
+                self.text(&bytes::to_hex(b));
+            }
+            ValueImage::Match(m) => {
+                self.word("match");
+                self.regex_match(m);
+            }
+            ValueImage::Matches(all) => {
+                self.word("matches");
+                self.number(all.len());
+                all.iter().for_each(|m| self.regex_match(m));
+            }
+            ValueImage::DateTime { millis, offset_minutes } => {
+                self.word("dt");
+                self.number(millis);
+                self.number(offset_minutes);
+            }
+            ValueImage::Duration(d) => {
+                self.word("dur");
+                self.number(d);
+            }
+            ValueImage::Range(start, end) => {
+                self.word("rng");
+                self.number(start);
+                self.number(end);
+            }
+            ValueImage::Record { name, fields } => {
+                self.word("rec");
+                self.text(name);
+                self.number(fields.len());
+                for (field, value) in fields {
+                    self.text(field);
+                    self.value(value);
+                }
+            }
+            ValueImage::Array(items) => {
+                self.word("arr");
+                self.number(items.len());
+                items.iter().for_each(|item| self.value(item));
+            }
+            ValueImage::Map(entries) => {
+                self.word("map");
+                self.number(entries.len());
+                for (key, value) in entries {
+                    self.value(key);
+                    self.value(value);
+                }
+            }
+            ValueImage::EnumVariant { enum_name, name, field_types, fields, derives } => {
+                self.word("enum");
+                self.text(enum_name);
+                self.text(name);
+                self.number(field_types.len());
+                field_types.iter().for_each(|t| self.text(&t.to_string()));
+                self.optional(fields.as_ref(), |w, fields| fields.iter().for_each(|field| w.value(field)));
+                self.flag(derives.eq);
+                self.flag(derives.show);
+                self.flag(derives.clone);
+            }
+            ValueImage::Null => self.word("n"),
+            ValueImage::Return(inner) => {
+                self.word("ret");
+                self.value(inner);
+            }
+            ValueImage::Error(e) => {
+                self.word("err");
+                self.text(e);
+            }
+            ValueImage::Reflection { type_name, details } => {
+                self.word("refl");
+                self.text(type_name);
+                self.text(details);
+            }
+            ValueImage::Macro(name) => {
+                self.word("macro");
+                self.text(name);
+            }
+            ValueImage::Type(name) => {
+                self.word("type");
+                self.text(name);
+            }
+            ValueImage::Function { id, parameters, body } => {
+                self.word("fn");
+                self.number(id);
+                self.number(parameters.len());
+                parameters.iter().for_each(|p| self.text(p));
+                self.text(&print_statements(core::slice::from_ref(body)));
+            }
+            ValueImage::Generator(index) => {
+                self.word("gen");
+                self.number(index);
+            }
+            ValueImage::WeakRef(index) => {
+                self.word("weak");
+                self.optional(*index, |w, index| w.number(index));
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Reader<'a> {
+    fn word(&mut self) -> Result<&'a str, String> {
+        let rest = self.rest.trim_start();
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if end == 0 {
+            return Err("스냅샷이 중간에 끝났습니다.".into());
+        }
+        self.rest = &rest[end..];
+        Ok(&rest[..end])
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        match self.word()? {
+            word if word == expected => Ok(()),
+            word => Err(format!("'{}' 가 와야 할 자리에 '{}' 가 있습니다.", expected, word)),
+        }
+    }
+
+    fn number<T: core::str::FromStr>(&mut self) -> Result<T, String> {
+        let word = self.word()?;
+        word.parse().map_err(|_| format!("숫자가 아닙니다: '{}'", word))
+    }
+
+    fn flag(&mut self) -> Result<bool, String> {
+        match self.word()? {
+            "1" => Ok(true),
+            "0" => Ok(false),
+            word => Err(format!("0 또는 1 이 아닙니다: '{}'", word)),
+        }
+    }
+
+    fn text(&mut self) -> Result<&'a str, String> {
+        let rest = self.rest.trim_start();
+        let (len, body) = rest.split_once(':').ok_or("문자열 길이가 없습니다.")?;
+        let len: usize = len.parse().map_err(|_| format!("잘못된 문자열 길이 '{}'", len))?;
+        let text = body.get(..len).ok_or("문자열이 중간에 끝났습니다.")?;
+        self.rest = &body[len..];
+        Ok(text)
+    }
+
+    fn optional<T>(&mut self, read: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<Option<T>, String> {
+        match self.word()? {
+            "-" => Ok(None),
+            "+" => read(self).map(Some),
+            word => Err(format!("'+' 또는 '-' 가 아닙니다: '{}'", word)),
+        }
+    }
+
+    fn regex_match(&mut self) -> Result<RegexMatch, String> {
+        let count: usize = self.number()?;
+        let groups = (0..count)
+            .map(|_| {
+                self.optional(|r| Ok(Group { start: r.number()?, end: r.number()?, text: r.text()?.to_string() }))
+            })
+            .collect::<Result<_, _>>()?;
+        let names = (0..count).map(|_| self.optional(|r| r.text().map(str::to_string))).collect::<Result<_, _>>()?;
+        Ok(RegexMatch { groups, names })
+    }
+
+    fn value(&mut self) -> Result<ValueImage, String> {
+        Ok(match self.word()? {
+            "i" => ValueImage::Integer(self.number()?),
+            "f" => ValueImage::Float(f64::from_bits(self.number()?)),
+            "b" => ValueImage::Boolean(self.flag()?),
+            "s" => ValueImage::String(self.text()?.to_string()),
+            "bytes" => ValueImage::Bytes(bytes::from_hex(self.text()?)?),
+            "match" => ValueImage::Match(self.regex_match()?),
+            "matches" => ValueImage::Matches((0..self.number()?).map(|_| self.regex_match()).collect::<Result<_, _>>()?),
+            "dt" => ValueImage::DateTime { millis: self.number()?, offset_minutes: self.number()? },
+            "dur" => ValueImage::Duration(self.number()?),
+            "rng" => ValueImage::Range(self.number()?, self.number()?),
+            "rec" => {
+                let name = self.text()?.to_string();
+                let fields = (0..self.number()?)
+                    .map(|_| Ok((self.text()?.to_string(), self.value()?)))
+                    .collect::<Result<_, String>>()?;
+                ValueImage::Record { name, fields }
+            }
+            "arr" => ValueImage::Array((0..self.number()?).map(|_| self.value()).collect::<Result<_, _>>()?),
+            "map" => ValueImage::Map((0..self.number()?).map(|_| Ok((self.value()?, self.value()?))).collect::<Result<_, String>>()?),
+            "enum" => {
+                let (enum_name, name) = (self.text()?.to_string(), self.text()?.to_string());
+                let field_types: Vec<TypeAnnotation> = (0..self.number()?).map(|_| parse_type(self.text()?)).collect::<Result<_, _>>()?;
+                let fields = self.optional(|r| (0..field_types.len()).map(|_| r.value()).collect())?;
+                let derives = Derives { eq: self.flag()?, show: self.flag()?, clone: self.flag()? };
+                ValueImage::EnumVariant { enum_name, name, field_types, fields, derives }
+            }
+            "n" => ValueImage::Null,
+            "ret" => ValueImage::Return(Box::new(self.value()?)),
+            "err" => ValueImage::Error(self.text()?.to_string()),
+            "refl" => ValueImage::Reflection { type_name: self.text()?.to_string(), details: self.text()?.to_string() },
+            "macro" => ValueImage::Macro(self.text()?.to_string()),
+            "type" => ValueImage::Type(self.text()?.to_string()),
+            "fn" => {
+                let id = self.number()?;
+                let parameters = (0..self.number()?).map(|_| self.text().map(str::to_string)).collect::<Result<_, _>>()?;
+                ValueImage::Function { id, parameters, body: parse_statement(self.text()?)? }
+            }
+            "gen" => ValueImage::Generator(self.number()?),
+            "weak" => ValueImage::WeakRef(self.optional(|r| r.number())?),
+            other => return Err(format!("알 수 없는 값 종류 '{}'", other)),
+        })
+    }
+}
+
+fn print_statements(statements: &[Statement]) -> String {
+    ast_printer::print_program(&Program {
+        root_id: 0,
+        statements: statements.to_vec(),
+        span: Span { start: 0, end: 0 },
+        attributes: vec![],
+    })
+}
+
+fn parse_statements(source: &str) -> Vec<Statement> {
+    ParserService::new(LexerService::new(source)).parse_program().statements
+}
+
+fn parse_statement(source: &str) -> Result<Statement, String> {
+    parse_statements(source).into_iter().next().ok_or_else(|| format!("문장을 읽을 수 없습니다: {}", source))
+}
+
+/// 타입 표기는 `let _: T = 0` 의 표기로 읽습니다.
+fn parse_type(source: &str) -> Result<TypeAnnotation, String> {
+    match parse_statement(&format!("let _: {} = 0", source))? {
+        Statement::LetStatement { type_annotation: Some(annotation), .. } => Ok(annotation),
+        _ => Err(format!("타입을 읽을 수 없습니다: {}", source)),
+    }
+}
+
+fn parse_expression(source: &str) -> Result<Expression, String> {
+    match parse_statement(source)? {
+        Statement::ExpressionStatement(expr) => Ok(*expr),
+        _ => Err(format!("식을 읽을 수 없습니다: {}", source)),
+    }
+}
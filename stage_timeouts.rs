@@ -0,0 +1,219 @@
+// stage_timeouts.rs
+// 컴파일 단계별 제한 시간입니다.
+//
+// 단계마다 `StageWatch` 를 열면 요청의 취소 토큰에서 자식 토큰을 만들고, 감시 작업이
+// `tokio::time::timeout` 으로 제한 시간을 기다립니다. 시간이 지나면 자식 토큰만 취소하므로
+// 그 단계의 외부 도구(NASM, 링커, cargo, Python)와 인터프리터는 멈추지만 요청 전체의 토큰은
+// 그대로입니다. 파싱과 최적화처럼 중간에 멈출 수 없는 동기 단계는 끝난 뒤 걸린 시간으로 판정합니다.
+//
+// 감시 작업은 다른 작업자 스레드에서 돌아야 하므로 멀티 스레드 런타임(`#[tokio::main]` 기본값)을 가정합니다.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::cancellation::CancellationToken;
+
+/// 제한 시간을 두는 컴파일 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Analysis,
+    Parsing,
+    Optimization,
+    Codegen,
+    /// 외부 도구 실행 (NASM, 링커, cargo)
+    Tools,
+    Execution,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 6] = [
+        Stage::Analysis,
+        Stage::Parsing,
+        Stage::Optimization,
+        Stage::Codegen,
+        Stage::Tools,
+        Stage::Execution,
+    ];
+
+    /// `--timeout <단계>=<ms>` 에서 쓰는 이름
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Analysis => "analysis",
+            Stage::Parsing => "parsing",
+            Stage::Optimization => "optimization",
+            Stage::Codegen => "codegen",
+            Stage::Tools => "tools",
+            Stage::Execution => "execution",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Stage> {
+        Stage::ALL.into_iter().find(|stage| stage.name() == name)
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 단계별 제한 시간 (밀리초). 0 이면 제한하지 않습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageTimeouts {
+    pub analysis_ms: u64,
+    pub parsing_ms: u64,
+    pub optimization_ms: u64,
+    pub codegen_ms: u64,
+    /// cargo 빌드가 들어가므로 가장 깁니다.
+    pub tools_ms: u64,
+    pub execution_ms: u64,
+}
+
+impl Default for StageTimeouts {
+    fn default() -> Self {
+        Self {
+            analysis_ms: 10_000,
+            parsing_ms: 5_000,
+            optimization_ms: 5_000,
+            codegen_ms: 10_000,
+            tools_ms: 300_000,
+            execution_ms: 30_000,
+        }
+    }
+}
+
+impl StageTimeouts {
+    /// 제한이 없으면 `None` 입니다.
+    pub fn limit(&self, stage: Stage) -> Option<Duration> {
+        match *self.field(stage) {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// `<단계>=<ms>` 형식의 설정 하나를 적용합니다. (`--timeout execution=500`)
+    pub fn set(&mut self, spec: &str) -> Result<(), String> {
+        let (name, ms) = spec.split_once('=').ok_or_else(|| format!("'<단계>=<ms>' 형식이어야 합니다: {}", spec))?;
+        let stage = Stage::from_name(name.trim()).ok_or_else(|| {
+            let names: Vec<_> = Stage::ALL.iter().map(|s| s.name()).collect();
+            format!("알 수 없는 단계 '{}' (가능한 값: {})", name, names.join(", "))
+        })?;
+        *self.field_mut(stage) = ms.trim().parse().map_err(|_| format!("잘못된 밀리초 값: {}", ms))?;
+        Ok(())
+    }
+
+    fn field(&self, stage: Stage) -> &u64 {
+        match stage {
+            Stage::Analysis => &self.analysis_ms,
+            Stage::Parsing => &self.parsing_ms,
+            Stage::Optimization => &self.optimization_ms,
+            Stage::Codegen => &self.codegen_ms,
+            Stage::Tools => &self.tools_ms,
+            Stage::Execution => &self.execution_ms,
+        }
+    }
+
+    fn field_mut(&mut self, stage: Stage) -> &mut u64 {
+        match stage {
+            Stage::Analysis => &mut self.analysis_ms,
+            Stage::Parsing => &mut self.parsing_ms,
+            Stage::Optimization => &mut self.optimization_ms,
+            Stage::Codegen => &mut self.codegen_ms,
+            Stage::Tools => &mut self.tools_ms,
+            Stage::Execution => &mut self.execution_ms,
+        }
+    }
+}
+
+/// 단계가 정상적으로 끝나지 못한 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageError {
+    TimedOut(Stage, Duration),
+    /// 요청 전체가 취소됨
+    Cancelled,
+}
+
+/// 한 단계의 제한 시간 감시
+pub struct StageWatch {
+    stage: Stage,
+    limit: Option<Duration>,
+    started: Instant,
+    parent: CancellationToken,
+    token: CancellationToken,
+    expired: Arc<AtomicBool>,
+    watchdog: Option<JoinHandle<()>>,
+}
+
+impl StageWatch {
+    /// `parent` 의 자식 토큰으로 단계를 시작합니다. 제한이 있으면 감시 작업을 띄워,
+    /// 시간이 지나는 즉시 `token()` 을 취소합니다.
+    pub fn start(stage: Stage, timeouts: &StageTimeouts, parent: &CancellationToken) -> Self {
+        let mut watch = Self::measure(stage, timeouts, parent);
+        watch.watchdog = watch.limit.map(|limit| {
+            let token = watch.token.clone();
+            let expired = watch.expired.clone();
+            tokio::spawn(async move {
+                // 단계가 먼저 끝나면 `finish` 가 작업을 멈추고, 취소되면 `cancelled` 가 먼저 돌아옵니다.
+                if tokio::time::timeout(limit, token.cancelled()).await.is_err() {
+                    expired.store(true, Ordering::SeqCst);
+                    token.cancel();
+                }
+            })
+        });
+        watch
+    }
+
+    /// 감시 작업 없이 걸린 시간만 잽니다. 중간에 멈출 수 없는 동기 단계에 씁니다.
+    pub fn measure(stage: Stage, timeouts: &StageTimeouts, parent: &CancellationToken) -> Self {
+        Self {
+            stage,
+            limit: timeouts.limit(stage),
+            started: Instant::now(),
+            parent: parent.clone(),
+            token: parent.child_token(),
+            expired: Arc::new(AtomicBool::new(false)),
+            watchdog: None,
+        }
+    }
+
+    /// 이 단계의 작업에 넘길 토큰. 제한 시간이 지나거나 요청이 취소되면 취소됩니다.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// 단계를 마칩니다. 제한 시간을 넘겼거나 요청이 취소되었으면 오류입니다.
+    pub fn finish(self) -> Result<(), StageError> {
+        self.finish_excluding(Duration::ZERO)
+    }
+
+    /// `excluded` 만큼(다른 단계로 따로 잰 시간)을 빼고 제한 시간을 판정합니다.
+    pub fn finish_excluding(mut self, excluded: Duration) -> Result<(), StageError> {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+        let over_limit = self.limit.is_some_and(|limit| self.elapsed().saturating_sub(excluded) > limit);
+        match self.limit {
+            Some(limit) if self.expired.load(Ordering::SeqCst) || over_limit => Err(StageError::TimedOut(self.stage, limit)),
+            _ if self.parent.is_cancelled() => Err(StageError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for StageWatch {
+    fn drop(&mut self) {
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.abort();
+        }
+    }
+}
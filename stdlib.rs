@@ -0,0 +1,142 @@
+// stdlib.rs
+// High 로 작성된 표준 라이브러리(`stdlib/*.high`)를 사용자 프로그램과 함께 링크합니다.
+//
+// 모듈 규칙:
+// - 모듈 하나는 `stdlib/<이름>.high` 파일 하나이며, 파일 이름이 모듈 이름입니다.
+// - 모듈이 내보내는 것은 최상위 정의뿐입니다. 지금은 모두 매크로라서 쓰이지 않은 정의는
+//   확장 단계에서 사라지고, 어떤 백엔드 출력에도 남지 않습니다.
+// - 이름은 아직 전역입니다. 사용자 정의가 같은 이름이면 사용자 정의가 이깁니다.
+// - 모듈은 `MODULES` 순서로 링크되며, 앞선 모듈의 정의만 쓸 수 있습니다. 사용자 코드는 쓰지 않습니다.
+// - 주석 문법이 아직 없으므로 모듈 설명은 아래 `StdModule::summary` 에 둡니다.
+// - list, option, result 의 이름은 흔한 사용자 이름과 겹치지 않도록 모듈 이름으로 시작합니다.
+// - 인자를 두 번 쓰는 매크로는 `match` 로 한 번만 계산합니다. 그 바인딩 이름은 `__` 로 시작합니다.
+//
+// option 은 값 또는 null(`read_line()` 이 입력 끝에서 주는 값)이고, result 는 `{"ok": 값}` 또는
+// `{"err": 오류}` 맵입니다.
+
+use core::ops::Range;
+
+use crate::compat::*;
+use crate::data_structures::Program;
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+
+/// 표준 라이브러리 모듈 하나
+#[derive(Debug, Clone, Copy)]
+pub struct StdModule {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub source: &'static str,
+}
+
+/// 링크 순서대로 나열한 표준 라이브러리 모듈
+pub const MODULES: &[StdModule] = &[
+    StdModule {
+        name: "math",
+        summary: "square, cube, average, is_even/is_odd, divides, pi/tau/e",
+        source: include_str!("stdlib/math.high"),
+    },
+    StdModule {
+        name: "string",
+        summary: "to_string, concat, join, quote, hex, fixed2",
+        source: include_str!("stdlib/string.high"),
+    },
+    StdModule {
+        name: "list",
+        summary: "list_is_empty, list_first, list_last, list_rest, list_append, list_get_or",
+        source: include_str!("stdlib/list.high"),
+    },
+    StdModule {
+        name: "option",
+        summary: "option_is_none, option_is_some, option_unwrap_or, option_expect",
+        source: include_str!("stdlib/option.high"),
+    },
+    StdModule {
+        name: "result",
+        summary: "result_ok, result_err, result_is_ok, result_is_err, result_unwrap_or, result_error",
+        source: include_str!("stdlib/result.high"),
+    },
+    StdModule {
+        name: "check",
+        summary: "assert_eq, assert_ne, unreachable, todo",
+        source: include_str!("stdlib/check.high"),
+    },
+];
+
+/// 모든 모듈을 이어 붙인 소스. 인터프리터 실행 시 프렐류드 앞에 놓입니다.
+pub fn source() -> String {
+    MODULES.iter().map(|module| module.source).collect::<Vec<_>>().join("\n")
+}
+
+/// 인터프리터에 먼저 실행할 소스: 표준 라이브러리(`no_std` 가 아니면) 다음에 사용자 프렐류드
+pub fn execution_prelude(no_std: bool, prelude: Option<&str>) -> Option<String> {
+    match (no_std, prelude) {
+        (true, prelude) => prelude.map(str::to_string),
+        (false, Some(prelude)) => Some(format!("{}\n{}", source(), prelude)),
+        (false, None) => Some(source()),
+    }
+}
+
+/// 링크된 모듈 하나가 차지하는 최상위 문장 범위
+#[derive(Debug, Clone)]
+pub struct LinkedModule {
+    pub name: &'static str,
+    pub statements: Range<usize>,
+}
+
+/// 표준 라이브러리 정의를 사용자 프로그램 앞에 붙입니다. 매크로 확장 전에 호출해야 합니다.
+/// 돌려주는 범위는 도달성 분석(dead_code)이 쓰지 않는 모듈을 찾을 때 씁니다.
+pub fn link(program: &mut Program) -> Vec<LinkedModule> {
+    let mut statements = vec![];
+    let mut linked = vec![];
+    for module in MODULES {
+        let parsed = ParserService::new(LexerService::new(module.source)).parse_program();
+        let start = statements.len();
+        statements.extend(parsed.statements);
+        linked.push(LinkedModule { name: module.name, statements: start..statements.len() });
+    }
+    statements.append(&mut program.statements);
+    program.statements = statements;
+    linked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_structures::Value;
+    use crate::engine::{run_to_exit, Engine};
+
+    /// 표준 라이브러리를 앞에 붙인 프로그램의 `return` 값. 식 목록은 `format` 으로 한 줄에 적습니다.
+    fn with_std(expressions: &str) -> String {
+        match run_to_exit(&format!("{}\nreturn format(\"{{}}\", [{}]);", source(), expressions)) {
+            Value::String(text) => text.to_string(),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn list_helpers() {
+        let list = "list_append([1, 2], 3)";
+        let expressions = format!(
+            "list_is_empty([]), list_first({l}), list_last({l}), list_rest({l}), list_get_or({l}, 2, 0), list_get_or({l}, 3, 0), list_get_or({l}, -1, 0)",
+            l = list
+        );
+        assert_eq!(with_std(&expressions), "[true, 1, 3, [2, 3], 3, 0, 0]");
+    }
+
+    #[test]
+    fn option_helpers_treat_null_as_none() {
+        let expressions =
+            "option_is_none(read_line()), option_is_some(1), option_unwrap_or(read_line(), \"none\"), option_unwrap_or(2, 0), option_expect(3, \"x\")";
+        assert_eq!(with_std(expressions), "[true, true, \"none\", 2, 3]");
+        let failed = Engine::new().run(&format!("{}\nreturn option_expect(read_line(), \"입력 없음\");", source()));
+        assert!(failed.unwrap_err().starts_with("입력 없음"));
+    }
+
+    #[test]
+    fn result_helpers() {
+        let expressions = "result_is_ok(result_ok(1)), result_is_err(result_err(\"e\")), result_is_ok(result_err(\"e\")), \
+                           result_unwrap_or(result_ok(5), 0), result_unwrap_or(result_err(\"e\"), 0), result_error(result_err(\"bad\"))";
+        assert_eq!(with_std(expressions), "[true, true, false, 5, 0, \"bad\"]");
+    }
+}
@@ -0,0 +1,128 @@
+// test_runner.rs
+// `high test <file>`: `@test` 가 붙은 최상위 문장을 하나씩 실행하고 결과를 보고합니다.
+//
+// `@test` 가 없는 최상위 문장은 모든 테스트보다 먼저 한 번 실행되는 준비 코드입니다.
+// 각 테스트는 준비 코드의 환경을 감싼 새 스코프에서 실행되므로 서로의 바인딩을 보지 못합니다.
+// `assert()` / `panic()` 으로 발생한 패닉은 호출 위치와 호출 스택과 함께 실패로 보고됩니다.
+// `--coverage` 를 주면 준비 코드와 테스트가 실행한 문장을 세어 커버리지 보고서를 씁니다. (coverage.rs)
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::attributes::has_attribute;
+use crate::conditional_compilation::{self, CfgContext};
+use crate::coverage::FileCoverage;
+use crate::data_structures::{Program, Statement};
+use crate::ft_runtime::{Environment, HighEnduranceRuntime, RuntimePanic};
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+
+/// 테스트 하나의 실행 결과입니다.
+#[derive(Debug, Clone)]
+pub struct TestOutcome {
+    pub name: String,
+    pub failure: Option<RuntimePanic>,
+}
+
+impl TestOutcome {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// 소스의 `@test` 문장을 모두 실행합니다. 준비 코드가 패닉하면 Err 입니다.
+pub fn run_tests(source: &str) -> Result<Vec<TestOutcome>, String> {
+    run_program(test_program(source), &mut HighEnduranceRuntime::new())
+}
+
+/// `run_tests` 와 같고, 준비 코드와 테스트가 실행한 문장의 커버리지 보고서를 함께 돌려줍니다. (high test --coverage)
+pub fn run_tests_with_coverage(source: &str, path: &str) -> (Result<Vec<TestOutcome>, String>, FileCoverage) {
+    let program = test_program(source);
+    let mut runtime = HighEnduranceRuntime::new();
+    let coverage = runtime.enable_coverage();
+    let outcomes = run_program(program.clone(), &mut runtime);
+    let report = coverage.borrow().report(path, source, &program);
+    (outcomes, report)
+}
+
+/// `@cfg(test)` 를 켜고 읽은 프로그램
+pub fn test_program(source: &str) -> Program {
+    let mut parser = ParserService::new(LexerService::new(source));
+    let mut program = parser.parse_program();
+    let ctx = CfgContext::new("her_vm", &["test".to_string()]);
+    conditional_compilation::prune_program(&mut program, &ctx);
+    program
+}
+
+/// 읽어 둔 프로그램의 테스트를 `runtime` 에서 실행합니다. (high mutate 가 변이체마다 부릅니다)
+pub fn run_program(program: Program, runtime: &mut HighEnduranceRuntime) -> Result<Vec<TestOutcome>, String> {
+    let (tests, setup): (Vec<_>, Vec<_>) = program.statements.into_iter().partition(|stmt| {
+        matches!(stmt, Statement::Attributed { attributes, .. } if has_attribute(attributes, "test"))
+    });
+
+    let _ = runtime.execute_program(Program { root_id: 0, statements: setup, span: program.span, attributes: vec![] });
+    if let Some(panic) = runtime.panic.take() {
+        return Err(format!("테스트 준비 코드에서 패닉이 발생했습니다: {}", panic.message));
+    }
+
+    let mut outcomes = vec![];
+    for (index, test) in tests.into_iter().enumerate() {
+        let name = test_name(&test, index);
+        let shared = runtime.environment.clone();
+        runtime.environment = Rc::new(RefCell::new(Environment::new_enclosed(shared.clone())));
+        let _ = runtime.execute_program(Program { root_id: 0, span: test.span(), statements: vec![test], attributes: vec![] });
+        runtime.environment = shared;
+        outcomes.push(TestOutcome { name, failure: runtime.panic.take() });
+    }
+    Ok(outcomes)
+}
+
+/// `@test let name = ...` 는 바인딩 이름을, 그 외에는 순번과 위치를 테스트 이름으로 씁니다.
+fn test_name(stmt: &Statement, index: usize) -> String {
+    match stmt {
+        Statement::Attributed { attributes, statement, .. } => match statement.as_ref() {
+            Statement::LetStatement { name, .. } => name.to_string(),
+            Statement::Attributed { .. } => test_name(statement, index),
+            _ => {
+                let span = attributes[0].span;
+                format!("test#{} ({}..{})", index + 1, span.start, span.end)
+            }
+        },
+        _ => format!("test#{}", index + 1),
+    }
+}
+
+/// cargo test 와 비슷한 형식의 결과 보고서를 만듭니다.
+pub fn render_report(outcomes: &[TestOutcome]) -> String {
+    let mut report = format!("running {} tests\n", outcomes.len());
+    for outcome in outcomes {
+        let status = if outcome.passed() { "ok" } else { "FAILED" };
+        report.push_str(&format!("test {} ... {}\n", outcome.name, status));
+    }
+
+    let failures: Vec<&TestOutcome> = outcomes.iter().filter(|o| !o.passed()).collect();
+    if !failures.is_empty() {
+        report.push_str("\nfailures:\n");
+        for outcome in &failures {
+            if let Some(panic) = &outcome.failure {
+                report.push_str(&format!(
+                    "\n---- {} ----\n{} ({}..{})\n{}\n",
+                    outcome.name,
+                    panic.message,
+                    panic.span.start,
+                    panic.span.end,
+                    panic.stack_trace()
+                ));
+            }
+        }
+    }
+
+    let result = if failures.is_empty() { "ok" } else { "FAILED" };
+    report.push_str(&format!(
+        "\ntest result: {}. {} passed; {} failed\n",
+        result,
+        outcomes.len() - failures.len(),
+        failures.len()
+    ));
+    report
+}
@@ -0,0 +1,168 @@
+// tiering.rs
+// 계층형 실행: 모든 함수는 트리 순회 인터프리터로 시작하고, 자주 호출되는 함수는 바이트코드 VM 으로 올라갑니다.
+//
+// 런타임은 함수 값(FunctionValue::id)마다 호출 횟수를 셉니다. 문턱값에 이르면 본문을 명령어 열로 옮기고,
+// 최적화와 검증은 백그라운드에서 합니다 (`tokio` 기능으로 빌드해 런타임 안이면 spawn_blocking, 아니면 별도 스레드, WASI 는 그 자리).
+// AST 는 Rc 를 담을 수 있는 Value 를 포함해 스레드로 보낼 수 없으므로, 옮기기만 호출한 스레드에서 합니다.
+// 결과는 채널로 돌아오고, 그 다음 호출부터 디스패치가 컴파일된 쪽으로 바뀝니다.
+// `std` 기능이 없으면 스레드가 없으므로 문턱값에 이른 호출에서 바로 최적화까지 마칩니다.
+// 컴파일이 끝나기 전의 호출과 컴파일할 수 없는 함수는 계속 인터프리터로 실행됩니다.
+
+use alloc::rc::Rc;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::bytecode::{self, Chunk};
+use crate::compat::*;
+use crate::data_structures::FunctionValue;
+
+/// 이 횟수만큼 인터프리터로 호출된 함수를 컴파일합니다.
+pub const DEFAULT_TIER_UP_THRESHOLD: u32 = 100;
+
+#[derive(Debug)]
+enum Tier {
+    Counting(u32),
+    Compiling,
+    Compiled(Rc<Chunk>),
+    /// 컴파일할 수 없는 본문: 계속 인터프리터로 실행합니다.
+    Interpreted,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TierStats {
+    pub interpreted_calls: u64,
+    pub compiled_calls: u64,
+    /// 바이트코드로 올라간 함수 수
+    pub compiled_functions: u64,
+    /// 컴파일할 수 없어 인터프리터에 남은 함수 수
+    pub rejected_functions: u64,
+}
+
+type CompileResult = (usize, Result<Chunk, String>);
+
+pub struct Tiering {
+    /// None 이면 항상 인터프리터로 실행합니다.
+    pub threshold: Option<u32>,
+    pub stats: TierStats,
+    functions: HashMap<usize, Tier>,
+    #[cfg(feature = "std")]
+    sender: Sender<CompileResult>,
+    #[cfg(feature = "std")]
+    receiver: Receiver<CompileResult>,
+}
+
+impl Tiering {
+    pub fn new(threshold: Option<u32>) -> Self {
+        #[cfg(feature = "std")]
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            threshold,
+            stats: TierStats::default(),
+            functions: HashMap::new(),
+            #[cfg(feature = "std")]
+            sender,
+            #[cfg(feature = "std")]
+            receiver,
+        }
+    }
+
+    /// 컴파일된 본문이 있으면 돌려줍니다. 끝난 백그라운드 컴파일 결과도 여기서 받아들입니다.
+    pub fn compiled(&mut self, id: usize) -> Option<Rc<Chunk>> {
+        #[cfg(feature = "std")]
+        while let Ok(result) = self.receiver.try_recv() {
+            self.accept(result);
+        }
+        match self.functions.get(&id) {
+            Some(Tier::Compiled(chunk)) => {
+                self.stats.compiled_calls += 1;
+                Some(chunk.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// 인터프리터로 실행할 호출을 기록합니다. 문턱값에 이르면 컴파일을 시작합니다.
+    pub fn record_call(&mut self, func: &FunctionValue) {
+        self.stats.interpreted_calls += 1;
+        let Some(threshold) = self.threshold else {
+            return;
+        };
+        let tier = self.functions.entry(func.id).or_insert(Tier::Counting(0));
+        let Tier::Counting(count) = tier else {
+            return;
+        };
+        *count += 1;
+        if *count < threshold {
+            return;
+        }
+
+        match bytecode::compile(&func.parameters, &func.body) {
+            Ok(chunk) => {
+                *tier = Tier::Compiling;
+                #[cfg(feature = "std")]
+                spawn_optimize(func.id, chunk, self.sender.clone());
+                #[cfg(not(feature = "std"))]
+                self.accept((func.id, bytecode::optimize(chunk)));
+            }
+            Err(_) => {
+                *tier = Tier::Interpreted;
+                self.stats.rejected_functions += 1;
+            }
+        }
+    }
+
+    /// 진행 중인 백그라운드 컴파일이 모두 끝날 때까지 기다립니다. (벤치마크처럼 결과가 결정적이어야 할 때)
+    pub fn wait_for_pending(&mut self) {
+        #[cfg(feature = "std")]
+        while self.functions.values().any(|tier| matches!(tier, Tier::Compiling)) {
+            match self.receiver.recv() {
+                Ok(result) => self.accept(result),
+                Err(_) => break,
+            }
+        }
+    }
+
+    pub fn is_compiled(&self, id: usize) -> bool {
+        matches!(self.functions.get(&id), Some(Tier::Compiled(_)))
+    }
+
+    fn accept(&mut self, (id, result): CompileResult) {
+        let tier = match result {
+            Ok(chunk) => {
+                self.stats.compiled_functions += 1;
+                Tier::Compiled(Rc::new(chunk))
+            }
+            Err(_) => {
+                self.stats.rejected_functions += 1;
+                Tier::Interpreted
+            }
+        };
+        self.functions.insert(id, tier);
+    }
+}
+
+impl Default for Tiering {
+    fn default() -> Self {
+        Self::new(Some(DEFAULT_TIER_UP_THRESHOLD))
+    }
+}
+
+#[cfg(feature = "std")]
+fn spawn_optimize(id: usize, chunk: Chunk, sender: Sender<CompileResult>) {
+    let job = move || {
+        // 받는 쪽(런타임)이 먼저 사라졌으면 결과는 버립니다.
+        let _ = sender.send((id, bytecode::optimize(chunk)));
+    };
+    // WASI 에는 스레드가 없으므로 그 자리에서 최적화합니다. 결과는 다음 호출에서 받아들입니다.
+    #[cfg(target_os = "wasi")]
+    job();
+    #[cfg(not(target_os = "wasi"))]
+    {
+        #[cfg(feature = "tokio")]
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn_blocking(job);
+            return;
+        }
+        std::thread::spawn(job);
+    }
+}
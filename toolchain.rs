@@ -0,0 +1,315 @@
+// toolchain.rs
+// 네이티브 백엔드가 부르는 외부 도구(NASM, 링커)를 실행하고 그 출력을 진단으로 옮깁니다.
+//
+// 도구 경로는 `ToolPaths` 로 바꿀 수 있고(`--nasm`, `--linker`), 실제 프로세스 실행은
+// `ToolRunner` 뒤에 있어 도구가 없는 환경에서는 `MockRunner` 로 바꿔 끼울 수 있습니다.
+// 표준 출력과 오류는 모두 모아서, 흔한 오류 형식(`파일:줄: error: ...`, `ld: cannot find ...`,
+// `undefined reference to ...`)을 `ToolMessage` 로 읽어 냅니다.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+use crate::cancellation::{run_command, CancellationToken, CommandError};
+use crate::data_structures::{Diagnostic, DiagnosticLevel, Span};
+use crate::error_codes;
+use crate::native_codegen::TargetOs;
+
+/// 외부 도구 경로. 비워 두면 타깃 OS 의 기본 도구를 PATH 에서 찾습니다.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolPaths {
+    pub assembler: Option<String>,
+    pub linker: Option<String>,
+}
+
+impl ToolPaths {
+    pub fn assembler(&self) -> &str {
+        self.assembler.as_deref().unwrap_or("nasm")
+    }
+
+    /// 리눅스는 `ld` 로 바로 링크하고, Windows 와 macOS 는 C 런타임(libSystem)을 붙여 주는 드라이버를 씁니다.
+    pub fn linker(&self, os: TargetOs) -> &str {
+        self.linker.as_deref().unwrap_or(match os {
+            TargetOs::Linux => "ld",
+            TargetOs::Windows => "gcc",
+            TargetOs::MacOs => "cc",
+        })
+    }
+}
+
+/// 도구 실행 한 번
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl fmt::Display for Invocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        Ok(())
+    }
+}
+
+/// 끝난 도구의 종료 상태와 출력
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutput {
+    pub success: bool,
+    /// 시그널로 끝났으면 `None`
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// 도구를 실제로 실행하는 쪽. 테스트에서는 `MockRunner` 로 바꿉니다.
+pub trait ToolRunner: Send + Sync {
+    fn run(&self, invocation: &Invocation, token: &CancellationToken) -> Result<ToolOutput, CommandError>;
+}
+
+/// 자식 프로세스로 도구를 실행합니다.
+#[derive(Debug, Default)]
+pub struct ProcessRunner;
+
+impl ToolRunner for ProcessRunner {
+    fn run(&self, invocation: &Invocation, token: &CancellationToken) -> Result<ToolOutput, CommandError> {
+        let output = run_command(Command::new(&invocation.program).args(&invocation.args), None, token)?;
+        Ok(ToolOutput {
+            success: output.status.success(),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// 프로세스를 띄우지 않고 정해 둔 출력을 차례로 돌려주는 실행기. 받은 호출은 기록합니다.
+/// 준비한 출력이 다 떨어지면 성공(빈 출력)으로 답합니다.
+#[derive(Debug, Default)]
+pub struct MockRunner {
+    outputs: Mutex<VecDeque<ToolOutput>>,
+    calls: Mutex<Vec<Invocation>>,
+}
+
+impl MockRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 다음 호출이 돌려줄 출력을 추가합니다.
+    pub fn push_output(&self, output: ToolOutput) {
+        self.outputs.lock().unwrap_or_else(|e| e.into_inner()).push_back(output);
+    }
+
+    /// 다음 호출이 `stderr` 를 쓰고 종료 코드 1 로 실패하게 합니다.
+    pub fn push_failure(&self, stderr: &str) {
+        self.push_output(ToolOutput { success: false, exit_code: Some(1), stdout: String::new(), stderr: stderr.into() });
+    }
+
+    /// 지금까지 받은 호출
+    pub fn calls(&self) -> Vec<Invocation> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+impl ToolRunner for MockRunner {
+    fn run(&self, invocation: &Invocation, token: &CancellationToken) -> Result<ToolOutput, CommandError> {
+        if token.is_cancelled() {
+            return Err(CommandError::Cancelled);
+        }
+        self.calls.lock().unwrap_or_else(|e| e.into_inner()).push(invocation.clone());
+        let next = self.outputs.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        Ok(next.unwrap_or(ToolOutput { success: true, exit_code: Some(0), ..ToolOutput::default() }))
+    }
+}
+
+/// 도구 출력에서 읽어 낸 메시지 한 줄
+#[derive(Debug, Clone)]
+pub struct ToolMessage {
+    /// 메시지를 낸 도구 (`nasm`, `ld` 등 경로를 뺀 이름)
+    pub tool: String,
+    pub level: DiagnosticLevel,
+    /// `compiled.asm:12` 같은 위치. 생성된 파일을 가리키므로 High 소스 범위로는 옮기지 않습니다.
+    pub location: Option<String>,
+    pub text: String,
+}
+
+impl fmt::Display for ToolMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{}: {}: {}", self.tool, location, self.text),
+            None => write!(f, "{}: {}", self.tool, self.text),
+        }
+    }
+}
+
+/// 도구 실행 실패
+#[derive(Debug)]
+pub enum ToolError {
+    /// 도구를 실행하지 못함 (경로가 틀렸거나 설치되지 않음)
+    Unavailable { tool: String, reason: String },
+    /// 도구가 실패 상태로 끝남
+    Failed { invocation: Invocation, exit_code: Option<i32>, messages: Vec<ToolMessage>, output: String },
+    Cancelled,
+}
+
+impl ToolError {
+    /// 읽어 낸 도구 메시지를 진단으로 만듭니다. 메시지를 하나도 읽지 못했으면 출력 전체를 씁니다.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            ToolError::Failed { invocation, messages, output, .. } if messages.is_empty() && !output.trim().is_empty() => {
+                vec![error_codes::LINK_FAILED.at(NO_SPAN, format!("{}: {}", invocation.program, output.trim()))]
+            }
+            ToolError::Failed { messages, .. } => message_diagnostics(messages),
+            _ => vec![],
+        }
+    }
+}
+
+impl fmt::Display for ToolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ToolError::Unavailable { tool, reason } => write!(f, "{} 실행 실패: {}", tool, reason),
+            ToolError::Failed { invocation, exit_code, messages, .. } => {
+                match exit_code {
+                    Some(code) => write!(f, "{} 가 종료 코드 {} 로 실패했습니다", invocation.program, code)?,
+                    None => write!(f, "{} 가 시그널로 종료되었습니다", invocation.program)?,
+                }
+                // 첫 오류만 요약에 붙이고, 나머지는 진단으로 보여 줍니다.
+                match messages.iter().find(|m| matches!(m.level, DiagnosticLevel::Error)) {
+                    Some(first) => write!(f, " ({})", first),
+                    None => Ok(()),
+                }
+            }
+            ToolError::Cancelled => f.write_str(&error_codes::CANCELLED.message("", &[])),
+        }
+    }
+}
+
+/// 생성된 파일의 위치라서 High 소스에서는 가리킬 곳이 없습니다.
+const NO_SPAN: Span = Span { start: 0, end: 0 };
+
+/// 경고처럼 실패가 아닌 메시지도 같은 모양의 진단으로 옮깁니다.
+pub fn message_diagnostics(messages: &[ToolMessage]) -> Vec<Diagnostic> {
+    messages
+        .iter()
+        .map(|message| {
+            let mut diag = error_codes::LINK_FAILED.at(NO_SPAN, message.to_string());
+            diag.level = message.level.clone();
+            diag
+        })
+        .collect()
+}
+
+/// 어셈블러와 링커
+pub struct Toolchain {
+    paths: ToolPaths,
+    runner: Arc<dyn ToolRunner>,
+}
+
+impl Toolchain {
+    /// 실제 프로세스를 띄우는 도구 모음
+    pub fn new(paths: ToolPaths) -> Self {
+        Self::with_runner(paths, Arc::new(ProcessRunner))
+    }
+
+    pub fn with_runner(paths: ToolPaths, runner: Arc<dyn ToolRunner>) -> Self {
+        Self { paths, runner }
+    }
+
+    /// `os` 용으로 어셈블하고 링크합니다. 목적 파일은 어셈블리 파일 옆에 둡니다.
+    /// 성공하면 도구가 남긴 경고를 돌려줍니다.
+    pub fn assemble_and_link(&self, os: TargetOs, asm_path: &str, output_path: &str, token: &CancellationToken) -> Result<Vec<ToolMessage>, ToolError> {
+        let obj_path = Path::new(asm_path).with_file_name(os.object_path());
+        let obj_path = obj_path.to_string_lossy();
+        let obj_path = obj_path.as_ref();
+        let mut warnings = self.invoke(self.paths.assembler(), &["-f", os.object_format(), asm_path, "-o", obj_path], token)?;
+        warnings.extend(self.invoke(self.paths.linker(os), &[obj_path, "-o", output_path], token)?);
+        if os != TargetOs::Windows {
+            self.invoke("chmod", &["+x", output_path], token)?;
+        }
+        Ok(warnings)
+    }
+
+    /// 도구 하나를 실행합니다. 실패 상태로 끝나면 읽어 낸 메시지와 함께 오류입니다.
+    fn invoke(&self, program: &str, args: &[&str], token: &CancellationToken) -> Result<Vec<ToolMessage>, ToolError> {
+        let invocation = Invocation { program: program.into(), args: args.iter().map(|arg| arg.to_string()).collect() };
+        let output = self.runner.run(&invocation, token).map_err(|e| match e {
+            CommandError::Cancelled => ToolError::Cancelled,
+            CommandError::Io(e) => ToolError::Unavailable { tool: program.into(), reason: e.to_string() },
+        })?;
+        let combined = format!("{}{}", output.stdout, output.stderr);
+        let messages = parse_messages(program, &combined);
+        if output.success {
+            Ok(messages)
+        } else {
+            Err(ToolError::Failed { invocation, exit_code: output.exit_code, messages, output: combined })
+        }
+    }
+}
+
+// ─── 출력 읽기 ─────────────────────────────
+
+/// NASM, GNU ld, gcc 의 흔한 메시지 형식을 읽습니다. 알아보지 못한 줄은 건너뜁니다.
+///
+///   compiled.asm:12: error: symbol `foo' undefined          (NASM)
+///   nasm: fatal: unable to open input file `x.asm'          (도구 이름 접두사)
+///   compiled.o:(.text+0x5): undefined reference to `foo'    (ld)
+///   ld: cannot find -lc                                     (ld)
+///   main.c:3:5: warning: unused variable 'x'                (gcc)
+pub fn parse_messages(tool: &str, output: &str) -> Vec<ToolMessage> {
+    let tool_name = tool.rsplit(['/', '\\']).next().unwrap_or(tool);
+    output.lines().filter_map(|line| parse_line(tool_name, line.trim())).collect()
+}
+
+fn parse_line(tool_name: &str, line: &str) -> Option<ToolMessage> {
+    if line.is_empty() {
+        return None;
+    }
+    // `위치: 등급: 내용` — 위치는 `파일:줄` 또는 `파일:줄:열`, 혹은 도구 이름입니다.
+    for (marker, level) in LEVEL_MARKERS {
+        if let Some(index) = line.find(marker) {
+            let prefix = &line[..index];
+            let text = line[index + marker.len()..].trim().to_string();
+            let location = match prefix.trim() {
+                "" => None,
+                p if is_tool_prefix(p, tool_name) => None,
+                p => Some(p.to_string()),
+            };
+            return Some(ToolMessage { tool: tool_name.into(), level: level.clone(), location, text });
+        }
+    }
+    if let Some(index) = line.find("undefined reference to") {
+        let location = line[..index].trim_end_matches([':', ' ']);
+        return Some(ToolMessage {
+            tool: tool_name.into(),
+            level: DiagnosticLevel::Error,
+            location: (!location.is_empty()).then(|| location.to_string()),
+            text: line[index..].to_string(),
+        });
+    }
+    // `ld: cannot find -lc` 처럼 등급 없이 도구 이름만 붙은 줄은 오류로 봅니다.
+    let (prefix, text) = line.split_once(": ")?;
+    if is_tool_prefix(prefix, tool_name) && !text.ends_with(':') {
+        return Some(ToolMessage { tool: tool_name.into(), level: DiagnosticLevel::Error, location: None, text: text.to_string() });
+    }
+    None
+}
+
+/// 앞에서부터 찾으므로 `fatal error` 가 `error` 보다 먼저 와야 합니다.
+const LEVEL_MARKERS: [(&str, DiagnosticLevel); 5] = [
+    (": fatal error: ", DiagnosticLevel::Error),
+    (": fatal: ", DiagnosticLevel::Error),
+    (": error: ", DiagnosticLevel::Error),
+    (": warning: ", DiagnosticLevel::Warning),
+    (": note: ", DiagnosticLevel::Info),
+];
+
+fn is_tool_prefix(prefix: &str, tool_name: &str) -> bool {
+    let name = prefix.rsplit(['/', '\\']).next().unwrap_or(prefix);
+    name == tool_name || name.trim_end_matches(".exe") == tool_name || matches!(name, "ld" | "collect2" | "gcc" | "nasm")
+}
@@ -0,0 +1,296 @@
+// trace.rs
+// 시간 여행 디버깅용 실행 기록 (high run --trace, high trace view)
+//
+// 기록을 켠 런타임은 문장을 실행할 때마다 사건(event) 하나를 남깁니다: 문장 범위, 실행 전후에 보이는
+// 바인딩의 차이, 식 문장과 return 의 값. 사건은 문장이 시작한 순서이며, if/while 처럼 안에 문장이 있는
+// 문장의 차이에는 안쪽 문장이 바꾼 바깥 바인딩이 모두 들어갑니다. 블록과 `@attr` 는 커버리지처럼 안쪽
+// 문장으로 기록합니다. 기록을 켠 런타임은 함수를 바이트코드로 올리지 않으므로 모든 문장이 인터프리터를 지나갑니다.
+//
+// 로그 형식: 한 줄에 한 항목, 탭으로 나눕니다. 값의 탭·줄바꿈·역슬래시는 `\t` `\n` `\\` 로, 바인딩 없음은 `\-` 로 씁니다.
+//   high-trace 1                    머리
+//   file  <path>                    기록한 소스 파일 (view 가 소스 줄을 보여 줄 때 씁니다)
+//   @     <start> <end>             사건 시작 (문장 범위, 문자 위치)
+//   =     <value>                   식 문장 / return 의 값
+//   ~     <name> <before> <after>   바인딩 변경
+
+use crate::compat::*;
+use crate::data_structures::{Span, Statement, Value};
+use crate::formatting::display_value;
+
+/// 로그에 남기는 값 표시의 최대 글자 수. 넘으면 잘라 `…` 를 붙입니다.
+pub const MAX_VALUE_CHARS: usize = 120;
+
+const HEADER: &str = "high-trace 1";
+const UNBOUND: &str = "\\-";
+
+/// 바인딩 하나의 변경. `None` 은 그 시점에 바인딩이 없다는 뜻입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    pub span: Span,
+    pub value: Option<String>,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    pub file: Option<String>,
+    pub events: Vec<TraceEvent>,
+}
+
+/// 사건이 열린 뒤 닫히기 전까지 들고 있는 값: (사건 번호, 실행 전 바인딩)
+pub type TraceMark = (usize, Vec<(String, String)>);
+
+/// 기록할 문장인지 (coverage 와 같은 규칙)
+pub fn traced(stmt: &Statement) -> bool {
+    !matches!(stmt, Statement::BlockStatement { .. } | Statement::Attributed { .. })
+}
+
+/// 값의 로그 표시
+pub fn show(value: &Value) -> String {
+    let text = display_value(value);
+    match text.char_indices().nth(MAX_VALUE_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+impl Trace {
+    /// 사건을 열고 번호를 돌려줍니다.
+    pub fn begin(&mut self, span: Span) -> usize {
+        self.events.push(TraceEvent { span, value: None, changes: vec![] });
+        self.events.len() - 1
+    }
+
+    /// 실행 전후 바인딩을 비교해 사건을 닫습니다. 두 목록은 이름마다 하나씩입니다.
+    pub fn end(&mut self, event: usize, before: &[(String, String)], after: &[(String, String)], value: Option<String>) {
+        let lookup = |bindings: &[(String, String)], name: &str| bindings.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone());
+        let mut changes = vec![];
+        for (name, value) in after {
+            let previous = lookup(before, name);
+            if previous.as_ref() != Some(value) {
+                changes.push(Change { name: name.clone(), before: previous, after: Some(value.clone()) });
+            }
+        }
+        for (name, value) in before {
+            if lookup(after, name).is_none() {
+                changes.push(Change { name: name.clone(), before: Some(value.clone()), after: None });
+            }
+        }
+        if let Some(slot) = self.events.get_mut(event) {
+            slot.changes = changes;
+            slot.value = value;
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut out = format!("{}\n", HEADER);
+        if let Some(file) = &self.file {
+            out.push_str(&format!("file\t{}\n", escape(file)));
+        }
+        for event in &self.events {
+            out.push_str(&format!("@\t{}\t{}\n", event.span.start, event.span.end));
+            if let Some(value) = &event.value {
+                out.push_str(&format!("=\t{}\n", escape(value)));
+            }
+            for change in &event.changes {
+                let side = |v: &Option<String>| v.as_deref().map_or(UNBOUND.to_string(), escape);
+                out.push_str(&format!("~\t{}\t{}\t{}\n", escape(&change.name), side(&change.before), side(&change.after)));
+            }
+        }
+        out
+    }
+
+    pub fn decode(text: &str) -> Result<Trace, String> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, l)| l) != Some(HEADER) {
+            return Err(format!("실행 기록 파일이 아닙니다. (첫 줄이 '{}' 이어야 합니다)", HEADER));
+        }
+        let mut trace = Trace::default();
+        for (number, line) in lines {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let bad = || format!("{}번째 줄을 읽을 수 없습니다: {}", number + 1, line);
+            let number = |text: &str| text.parse::<usize>().map_err(|_| bad());
+            match fields.as_slice() {
+                ["file", path] => trace.file = Some(unescape(path)),
+                ["@", start, end] => {
+                    let span = Span { start: number(start)?, end: number(end)? };
+                    trace.events.push(TraceEvent { span, value: None, changes: vec![] });
+                }
+                ["=", value] => trace.events.last_mut().ok_or_else(bad)?.value = Some(unescape(value)),
+                ["~", name, before, after] => {
+                    let side = |v: &str| (v != UNBOUND).then(|| unescape(v));
+                    let change = Change { name: unescape(name), before: side(before), after: side(after) };
+                    trace.events.last_mut().ok_or_else(bad)?.changes.push(change);
+                }
+                [""] => {}
+                _ => return Err(bad()),
+            }
+        }
+        Ok(trace)
+    }
+
+    /// `step` 번째 사건까지 기록된 이름별 마지막 값 (이름 순, 바인딩이 사라졌으면 빠집니다)
+    pub fn known_values(&self, step: usize) -> Vec<(String, String)> {
+        let mut values: Vec<(String, String)> = vec![];
+        for change in self.events.iter().take(step + 1).flat_map(|e| &e.changes) {
+            values.retain(|(name, _)| *name != change.name);
+            if let Some(after) = &change.after {
+                values.push((change.name.clone(), after.clone()));
+            }
+        }
+        values.sort();
+        values
+    }
+
+    /// `from` 다음(뒤로 가면 이전)에서 `name` 을 바꾸는 첫 사건
+    pub fn find_change(&self, name: &str, from: usize, forward: bool) -> Option<usize> {
+        let changes = |i: &usize| self.events[*i].changes.iter().any(|c| c.name == name);
+        if forward {
+            (from + 1..self.events.len()).find(changes)
+        } else {
+            (0..from.min(self.events.len())).rev().find(changes)
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+// ─── high trace view ─────────────────────────────
+
+/// 사건 하나의 화면: 위치와 소스 줄, 값, 바인딩 변경, 지금까지 알려진 값
+pub fn render_step(trace: &Trace, source: Option<&str>, step: usize) -> String {
+    let Some(event) = trace.events.get(step) else {
+        return "기록된 사건이 없습니다.\n".into();
+    };
+    let mut out = format!("단계 {}/{}", step + 1, trace.events.len());
+    if let Some(file) = &trace.file {
+        out.push_str(&format!("  {}", file));
+    }
+    match source {
+        Some(source) => {
+            let (line, column) = line_column(source, event.span.start);
+            out.push_str(&format!(":{}:{}\n\n", line, column));
+            let lines: Vec<&str> = source.lines().collect();
+            let last = (line + 2).min(lines.len());
+            for (n, text) in lines.iter().enumerate().take(last).skip(line.saturating_sub(3)) {
+                let marker = if n + 1 == line { '>' } else { ' ' };
+                out.push_str(&format!("{} {:>4} | {}\n", marker, n + 1, text));
+                if n + 1 == line {
+                    let width = source.chars().skip(event.span.start).take(event.span.end.saturating_sub(event.span.start));
+                    let width = width.take_while(|&c| c != '\n').count().max(1);
+                    out.push_str(&format!("       | {}{}\n", " ".repeat(column - 1), "^".repeat(width)));
+                }
+            }
+        }
+        None => out.push_str(&format!("  ({}..{})\n", event.span.start, event.span.end)),
+    }
+    out.push('\n');
+    if let Some(value) = &event.value {
+        out.push_str(&format!("값: {}\n", value));
+    }
+    if event.changes.is_empty() {
+        out.push_str("바인딩 변경 없음\n");
+    }
+    for change in &event.changes {
+        let side = |v: &Option<String>| v.clone().unwrap_or_else(|| "(없음)".into());
+        out.push_str(&format!("  {}: {} → {}\n", change.name, side(&change.before), side(&change.after)));
+    }
+    let known = trace.known_values(step);
+    if !known.is_empty() {
+        out.push_str("\n알려진 값:\n");
+        for (name, value) in known {
+            out.push_str(&format!("  {} = {}\n", name, value));
+        }
+    }
+    out
+}
+
+/// 1부터 세는 (줄, 열)
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before: String = source.chars().take(offset).collect();
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    (line, column)
+}
+
+pub const VIEW_HELP: &str = "\
+[Enter]/n 다음  p 이전  g <N> N번째 단계로  f <이름> 다음 변경  r <이름> 이전 변경  0 처음  $ 끝  q 끝내기";
+
+/// 줄 단위 명령으로 기록을 앞뒤로 오갑니다. `clear` 이면 단계마다 화면을 지웁니다. (터미널 출력)
+#[cfg(feature = "std")]
+pub fn view(
+    trace: &Trace,
+    source: Option<&str>,
+    input: &mut impl std::io::BufRead,
+    output: &mut impl std::io::Write,
+    clear: bool,
+) -> std::io::Result<()> {
+    if trace.events.is_empty() {
+        return writeln!(output, "기록된 사건이 없습니다.");
+    }
+    let last = trace.events.len() - 1;
+    let mut step = 0;
+    let mut notice = String::new();
+    loop {
+        if clear {
+            write!(output, "\x1b[2J\x1b[H")?;
+        }
+        write!(output, "{}", render_step(trace, source, step))?;
+        if !notice.is_empty() {
+            writeln!(output, "\n{}", core::mem::take(&mut notice))?;
+        }
+        write!(output, "\n{}\n> ", VIEW_HELP)?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let (command, argument) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let argument = argument.trim();
+        match command {
+            "" | "n" => step = (step + 1).min(last),
+            "p" | "b" => step = step.saturating_sub(1),
+            "0" => step = 0,
+            "$" => step = last,
+            "g" => match argument.parse::<usize>() {
+                Ok(n) if (1..=last + 1).contains(&n) => step = n - 1,
+                _ => notice = format!("1 에서 {} 사이의 단계 번호가 필요합니다.", last + 1),
+            },
+            "f" | "r" if argument.is_empty() => notice = "이름이 필요합니다.".into(),
+            "f" | "r" => match trace.find_change(argument, step, command == "f") {
+                Some(found) => step = found,
+                None => notice = format!("'{}' 를 바꾸는 단계가 {} 없습니다.", argument, if command == "f" { "뒤에" } else { "앞에" }),
+            },
+            "q" => return Ok(()),
+            other => notice = format!("알 수 없는 명령: {}", other),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// unused_value.rs
+// 쓰이지 않는 순수 식 문장 (E0104)
+//
+// `1 + 2;` 처럼 효과 분석(effects.rs)으로 순수(Pure)라고 증명되는 식 문장은 값을 계산한 뒤 그대로 버리므로
+// 아무 일도 하지 않습니다. 입출력이 있거나 효과를 알 수 없는 식(호출 등)은 대상이 아닙니다.
+//
+// 함수 본문의 마지막 식 문장은 반환값이 되지 않습니다. 이 경우 `return` 을 빠뜨렸을 가능성이 높으므로
+// 따로 알려 줍니다. 프로그램(또는 REPL 셀)의 마지막 최상위 문장은 결과로 출력되므로 검사하지 않습니다.
+//
+// 값을 일부러 버릴 때는 `_ = expr;` 로 씁니다. `@allow(unused_value)` 를 붙인 문장이나
+// 파일 맨 앞의 `@!allow(unused_value)` 로 끕니다. 매크로 정의 본문은 확장 전 틀이므로 검사하지 않습니다.
+
+use crate::compat::*;
+use crate::data_structures::{Diagnostic, Expression, Program, Statement};
+use crate::effects::{Effect, EffectTable};
+use crate::error_codes;
+use crate::resolver::allows;
+use crate::visitor::{walk_expression, walk_statement, Visitor};
+
+pub fn check_program(program: &Program) -> Vec<Diagnostic> {
+    if allows(&program.attributes, "unused_value") {
+        return vec![];
+    }
+    let mut check = UnusedValue { effects: EffectTable::infer(program), function_tail: None, diagnostics: vec![] };
+    let count = program.statements.len();
+    for (index, stmt) in program.statements.iter().enumerate() {
+        if index + 1 == count && matches!(stmt, Statement::ExpressionStatement(_)) {
+            walk_statement(&mut check, stmt);
+        } else {
+            check.visit_statement(stmt);
+        }
+    }
+    check.diagnostics
+}
+
+struct UnusedValue {
+    effects: EffectTable,
+    /// 지금 검사 중인 함수 본문의 마지막 문장 위치
+    function_tail: Option<usize>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for UnusedValue {
+    fn visit_statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::MacroDefinition { .. } => return,
+            Statement::Attributed { attributes, .. } if allows(attributes, "unused_value") => return,
+            Statement::ExpressionStatement(expr) if self.effects.expression(expr) == Effect::Pure => {
+                let code = &error_codes::UNUSED_VALUE;
+                let span = stmt.span();
+                self.diagnostics.push(if self.function_tail == Some(span.start) {
+                    code.diagnostic(span, "implicit_result", &[]).with_help(code.message("implicit_result_help", &[]))
+                } else {
+                    code.diagnostic(span, "", &[]).with_help(code.message("help", &[]))
+                });
+            }
+            _ => {}
+        }
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        if let Expression::Function(_, _, body) = expr {
+            let tail = match body.as_ref() {
+                Statement::BlockStatement { statements, .. } => statements.last().map(|last| last.span().start),
+                _ => None,
+            };
+            let saved = core::mem::replace(&mut self.function_tail, tail);
+            walk_expression(self, expr);
+            self.function_tail = saved;
+            return;
+        }
+        walk_expression(self, expr);
+    }
+}
@@ -0,0 +1,199 @@
+// verify.rs
+// `high verify <file>`: 인터프리터와 컴파일 백엔드의 결과 비교 (적합성 검사)
+//
+// 같은 프로그램을 트리 순회 인터프리터와 백엔드 하나로 실행하고, 관찰할 수 있는 결과가 같은지 봅니다.
+// 관찰할 수 있는 결과는 표준 출력과 종료 코드(exit_code.rs)입니다. 백엔드가 출력하는 것은 최상위 `return`
+// 값 한 줄뿐이므로(null 이면 없음) 인터프리터 쪽도 그 값을 `display_value` 로 한 줄 만들어 비교합니다.
+// 패닉 메시지(표준 오류)는 백엔드마다 형식이 달라 비교하지 않고 종료 코드만 봅니다.
+//
+//   --backend rust     (기본) Rust 프로젝트를 만들어 cargo build 한 실행 파일
+//   --backend native   네이티브 코드 생성기(NASM + 링커)로 만든 실행 파일
+//   --backend python   Python 백엔드가 만든 compiled.py
+//
+// 결과가 다르면 처음 갈라지는 문장을 찾습니다. 최상위 `let` 마다 그 문장까지만 실행하고 바인딩 값을
+// `return` 하는 탐침 프로그램을 만들어 양쪽에서 실행합니다. 한 번 달라진 값은 그 뒤에서도 다르다고 보고
+// 이분 탐색하므로, 백엔드 빌드는 최상위 바인딩 수의 로그만큼 더 합니다. 탐침이 컴파일되지 않으면
+// 위치는 보고하지 않습니다.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::cancellation::{run_command, CancellationToken};
+use crate::compiler_services::{CompileOptions, CompileRequest, CompilerService};
+use crate::data_structures::{Span, Statement, Value};
+use crate::engine::Engine;
+use crate::executor_service::ExecutionStatus;
+use crate::exit_code;
+use crate::formatting::display_value;
+use crate::lexer_service::LexerService;
+use crate::parser_service::ParserService;
+use crate::py_emitter_service;
+use crate::refactor::byte_offset;
+use crate::stdlib;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Rust,
+    Native,
+    Python,
+}
+
+impl Backend {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "rust" => Ok(Backend::Rust),
+            "native" => Ok(Backend::Native),
+            "python" => Ok(Backend::Python),
+            other => Err(format!("알 수 없는 백엔드 '{}' (rust, native, python)", other)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Rust => "rust",
+            Backend::Native => "native",
+            Backend::Python => "python",
+        }
+    }
+}
+
+/// 한 번의 실행에서 관찰한 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub stdout: String,
+    pub exit_code: i32,
+}
+
+/// 결과가 처음 갈라지는 최상위 바인딩
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub binding: String,
+    pub span: Span,
+    /// 그 바인딩까지 실행한 탐침의 결과
+    pub interpreter: Observation,
+    pub backend: Observation,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub backend: Backend,
+    pub interpreter: Observation,
+    pub compiled: Observation,
+    pub first_divergence: Option<Divergence>,
+}
+
+impl Report {
+    pub fn agrees(&self) -> bool {
+        self.interpreter == self.compiled
+    }
+}
+
+pub struct Verifier {
+    backend: Backend,
+    no_std: bool,
+    compiler: CompilerService,
+    /// Rust 백엔드의 프로젝트 디렉터리. 탐침마다 다시 쓰므로 cargo 의 증분 빌드를 이어 씁니다.
+    project_dir: PathBuf,
+}
+
+impl Verifier {
+    pub fn new(backend: Backend, no_std: bool) -> Self {
+        let project_dir = std::env::temp_dir().join(format!("high_verify_{}", std::process::id()));
+        Verifier { backend, no_std, compiler: CompilerService::new(), project_dir }
+    }
+
+    /// 프로그램 전체를 비교하고, 다르면 처음 갈라지는 바인딩을 찾습니다.
+    /// 백엔드에서 프로그램을 만들 수 없으면 Err 입니다.
+    pub async fn verify(&self, source: &str) -> Result<Report, String> {
+        let interpreter = self.interpret(source);
+        let compiled = self.compile_and_run(source).await?;
+        let first_divergence = if interpreter == compiled { None } else { self.localize(source).await };
+        Ok(Report { backend: self.backend, interpreter, compiled, first_divergence })
+    }
+
+    fn interpret(&self, source: &str) -> Observation {
+        let mut engine = Engine::new();
+        if let Some(prelude) = stdlib::execution_prelude(self.no_std, None) {
+            if engine.load_prelude(&prelude).is_err() {
+                return Observation { stdout: String::new(), exit_code: exit_code::RUNTIME_ERROR };
+            }
+        }
+        match engine.run(source) {
+            Ok(_) => Observation {
+                // 오류 값을 돌려주면 백엔드는 표준 오류에 메시지를 쓰고 101 로 끝나므로 표준 출력은 비어 있습니다.
+                stdout: match engine.exit_value() {
+                    Some(Value::Null | Value::Error(_)) | None => String::new(),
+                    Some(value) => format!("{}\n", display_value(value)),
+                },
+                exit_code: engine.exit_code(),
+            },
+            Err(_) => Observation { stdout: String::new(), exit_code: exit_code::RUNTIME_ERROR },
+        }
+    }
+
+    async fn compile_and_run(&self, source: &str) -> Result<Observation, String> {
+        let mut options = CompileOptions { no_std: self.no_std, ..CompileOptions::default() };
+        match self.backend {
+            Backend::Rust => {
+                options.rust_project = Some(self.project_dir.display().to_string());
+                options.cargo_build = true;
+            }
+            Backend::Native => options.emit_native = true,
+            Backend::Python => options.target_platform = py_emitter_service::TARGET.into(),
+        }
+        let result = self.compiler.compile(CompileRequest { source_code: source.to_string(), options }).await;
+        // 컴파일러도 끝에 인터프리터로 한 번 실행하므로, 런타임 오류로만 실패했으면 산출물은 있습니다.
+        if !result.success && !matches!(result.execution_status, ExecutionStatus::RuntimeError) {
+            return Err(result.errors.join("\n"));
+        }
+        let mut command = match self.backend {
+            Backend::Python => {
+                let mut command = Command::new(py_emitter_service::INTERPRETER);
+                command.arg(py_emitter_service::OUTPUT_PATH);
+                command
+            }
+            _ => Command::new(result.binary_path.ok_or("백엔드가 실행 파일을 만들지 않았습니다.")?),
+        };
+        let output = run_command(&mut command, None, &CancellationToken::new()).map_err(|e| e.to_string())?;
+        Ok(Observation {
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            exit_code: output.status.code().unwrap_or(exit_code::RUNTIME_ERROR),
+        })
+    }
+
+    /// 값이 처음 달라지는 최상위 `let` 을 이분 탐색으로 찾습니다.
+    async fn localize(&self, source: &str) -> Option<Divergence> {
+        let program = ParserService::new(LexerService::new(source)).parse_program();
+        let bindings: Vec<(String, Span)> = program
+            .statements
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::LetStatement { name, span, .. } => Some((name.to_string(), *span)),
+                _ => None,
+            })
+            .collect();
+        let (mut low, mut high) = (0, bindings.len());
+        let mut found = None;
+        while low < high {
+            let middle = (low + high) / 2;
+            let (name, span) = &bindings[middle];
+            let probe = format!("{};\nreturn {}\n", &source[..byte_offset(source, span.end)], name);
+            let interpreter = self.interpret(&probe);
+            let backend = self.compile_and_run(&probe).await.ok()?;
+            if interpreter == backend {
+                low = middle + 1;
+            } else {
+                found = Some(Divergence { binding: name.clone(), span: *span, interpreter, backend });
+                high = middle;
+            }
+        }
+        found
+    }
+}
+
+impl Drop for Verifier {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.project_dir);
+    }
+}
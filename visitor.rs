@@ -0,0 +1,271 @@
+// visitor.rs
+// AST 순회 트레이트입니다. 분석·최적화·린트 패스는 관심 있는 노드의 메서드만 재정의하고,
+// 나머지 노드는 기본 구현(`walk_*`)이 자식으로 내려가며 방문합니다.
+//
+//   struct CountCalls(usize);
+//   impl Visitor for CountCalls {
+//       fn visit_expression(&mut self, expr: &Expression) {
+//           if let Expression::Call(..) = expr { self.0 += 1; }
+//           walk_expression(self, expr);
+//       }
+//   }
+//
+// 재정의한 메서드에서 `walk_*` 를 부르지 않으면 그 노드의 자식은 방문하지 않습니다.
+// 자식을 먼저 방문하려면(후위 순회) 노드를 처리하기 전에 `walk_*` 를 부르면 됩니다.
+//
+// 매크로 정의 본문도 기본 순회 대상입니다. 매크로 본문을 템플릿으로 다뤄야 하는 패스는
+// `Statement::MacroDefinition` 을 직접 처리해야 합니다.
+
+use crate::data_structures::{Expression, Program, Statement};
+
+/// 읽기 전용 순회
+pub trait Visitor {
+    fn visit_program(&mut self, program: &Program) {
+        for stmt in &program.statements {
+            self.visit_statement(stmt);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        walk_expression(self, expr);
+    }
+}
+
+/// 제자리 변경 순회. 노드를 통째로 바꾸려면 `*stmt = ...` / `*expr = ...` 로 덮어씁니다.
+pub trait MutVisitor {
+    fn visit_program(&mut self, program: &mut Program) {
+        for stmt in program.statements.iter_mut() {
+            self.visit_statement(stmt);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        walk_statement_mut(self, stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression) {
+        walk_expression_mut(self, expr);
+    }
+}
+
+/// 문장의 자식 문장과 식을 소스 순서대로 방문합니다.
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, stmt: &Statement) {
+    match stmt {
+        Statement::ExpressionStatement(expr)
+        | Statement::DiscardStatement(_, expr)
+        | Statement::ReturnStatement(_, expr)
+        | Statement::YieldStatement(_, expr) => visitor.visit_expression(expr),
+        Statement::LetStatement { value, .. } => visitor.visit_expression(value),
+        Statement::BlockStatement { statements, .. } => {
+            for s in statements {
+                visitor.visit_statement(s);
+            }
+        }
+        Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(then_branch);
+            if let Some(else_stmt) = else_branch {
+                visitor.visit_statement(else_stmt);
+            }
+        }
+        Statement::WhileStatement { condition, body, .. }
+        | Statement::ForInStatement { iterable: condition, body, .. } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(body);
+        }
+        Statement::ForStatement { initializer, condition, increment, body, .. } => {
+            if let Some(init) = initializer {
+                visitor.visit_statement(init);
+            }
+            if let Some(cond) = condition {
+                visitor.visit_expression(cond);
+            }
+            if let Some(inc) = increment {
+                visitor.visit_expression(inc);
+            }
+            visitor.visit_statement(body);
+        }
+        Statement::MacroDefinition { body, .. }
+        | Statement::DeferStatement(_, body)
+        | Statement::Attributed { statement: body, .. } => visitor.visit_statement(body),
+        Statement::TypeAlias { .. } | Statement::EnumDeclaration { .. } => {}
+    }
+}
+
+/// 식의 자식 식(과 함수 본문)을 소스 순서대로 방문합니다.
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expression) {
+    match expr {
+        Expression::Literal(..) | Expression::Identifier(..) => {}
+        Expression::PrefixOperation(_, _, inner)
+        | Expression::Grouped(_, inner)
+        | Expression::Reflect(_, inner)
+        | Expression::Eval(_, inner)
+        | Expression::TypeOf(_, inner)
+        | Expression::Member(_, inner, _)
+        | Expression::Assign(_, _, _, inner) => visitor.visit_expression(inner),
+        Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        Expression::Ternary(_, cond, then_expr, else_expr) => {
+            visitor.visit_expression(cond);
+            visitor.visit_expression(then_expr);
+            visitor.visit_expression(else_expr);
+        }
+        Expression::Function(_, _, body) => visitor.visit_statement(body),
+        Expression::Call(_, callee, args) => {
+            visitor.visit_expression(callee);
+            for arg in args {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::MacroCall(_, _, args) | Expression::ArrayLiteral(_, args) => {
+            for arg in args {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::MethodCall(_, target, _, args) => {
+            visitor.visit_expression(target);
+            for arg in args {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::MapLiteral(_, entries) => {
+            for (key, value) in entries {
+                visitor.visit_expression(key);
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Index(_, target, index) => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(index);
+        }
+        Expression::Slice(_, target, start, end) => {
+            visitor.visit_expression(target);
+            for bound in start.iter().chain(end) {
+                visitor.visit_expression(bound);
+            }
+        }
+        Expression::Match(_, subject, arms) => {
+            visitor.visit_expression(subject);
+            for arm in arms {
+                if let Some(guard) = &arm.guard {
+                    visitor.visit_expression(guard);
+                }
+                visitor.visit_expression(&arm.body);
+            }
+        }
+    }
+}
+
+pub fn walk_statement_mut<V: MutVisitor + ?Sized>(visitor: &mut V, stmt: &mut Statement) {
+    match stmt {
+        Statement::ExpressionStatement(expr)
+        | Statement::DiscardStatement(_, expr)
+        | Statement::ReturnStatement(_, expr)
+        | Statement::YieldStatement(_, expr) => visitor.visit_expression(expr),
+        Statement::LetStatement { value, .. } => visitor.visit_expression(value),
+        Statement::BlockStatement { statements, .. } => {
+            for s in statements.iter_mut() {
+                visitor.visit_statement(s);
+            }
+        }
+        Statement::IfStatement { condition, then_branch, else_branch, .. } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(then_branch);
+            if let Some(else_stmt) = else_branch {
+                visitor.visit_statement(else_stmt);
+            }
+        }
+        Statement::WhileStatement { condition, body, .. }
+        | Statement::ForInStatement { iterable: condition, body, .. } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statement(body);
+        }
+        Statement::ForStatement { initializer, condition, increment, body, .. } => {
+            if let Some(init) = initializer {
+                visitor.visit_statement(init);
+            }
+            if let Some(cond) = condition {
+                visitor.visit_expression(cond);
+            }
+            if let Some(inc) = increment {
+                visitor.visit_expression(inc);
+            }
+            visitor.visit_statement(body);
+        }
+        Statement::MacroDefinition { body, .. }
+        | Statement::DeferStatement(_, body)
+        | Statement::Attributed { statement: body, .. } => visitor.visit_statement(body),
+        Statement::TypeAlias { .. } | Statement::EnumDeclaration { .. } => {}
+    }
+}
+
+pub fn walk_expression_mut<V: MutVisitor + ?Sized>(visitor: &mut V, expr: &mut Expression) {
+    match expr {
+        Expression::Literal(..) | Expression::Identifier(..) => {}
+        Expression::PrefixOperation(_, _, inner)
+        | Expression::Grouped(_, inner)
+        | Expression::Reflect(_, inner)
+        | Expression::Eval(_, inner)
+        | Expression::TypeOf(_, inner)
+        | Expression::Member(_, inner, _)
+        | Expression::Assign(_, _, _, inner) => visitor.visit_expression(inner),
+        Expression::InfixOperation(_, _, left, right) | Expression::Range(_, left, right, _) => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        Expression::Ternary(_, cond, then_expr, else_expr) => {
+            visitor.visit_expression(cond);
+            visitor.visit_expression(then_expr);
+            visitor.visit_expression(else_expr);
+        }
+        Expression::Function(_, _, body) => visitor.visit_statement(body),
+        Expression::Call(_, callee, args) => {
+            visitor.visit_expression(callee);
+            for arg in args.iter_mut() {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::MacroCall(_, _, args) | Expression::ArrayLiteral(_, args) => {
+            for arg in args.iter_mut() {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::MethodCall(_, target, _, args) => {
+            visitor.visit_expression(target);
+            for arg in args.iter_mut() {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::MapLiteral(_, entries) => {
+            for (key, value) in entries.iter_mut() {
+                visitor.visit_expression(key);
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Index(_, target, index) => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(index);
+        }
+        Expression::Slice(_, target, start, end) => {
+            visitor.visit_expression(target);
+            for bound in start.iter_mut().chain(end) {
+                visitor.visit_expression(bound);
+            }
+        }
+        Expression::Match(_, subject, arms) => {
+            visitor.visit_expression(subject);
+            for arm in arms.iter_mut() {
+                if let Some(guard) = &mut arm.guard {
+                    visitor.visit_expression(guard);
+                }
+                visitor.visit_expression(&mut arm.body);
+            }
+        }
+    }
+}
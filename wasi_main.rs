@@ -0,0 +1,165 @@
+// wasi_main.rs
+// `high-wasi`: wasm32-wasi 용 인터프리터 CLI 입니다. 웹 IDE 와 서버리스 샌드박스에서 돌리는 용도입니다.
+//
+//   cargo build --release --target wasm32-wasip1 --no-default-features --features interpreter
+//   wasmtime run --dir . high-wasi.wasm -- run main.high
+//   echo 'return 1 + 2' | wasmtime run high-wasi.wasm
+//
+// tokio, 하위 프로세스, 네이티브 코드 생성이 없으므로 `interpreter` 기능만으로 빌드됩니다. 파일은 런타임이
+// 열어 준(preopen) 디렉터리에서만 읽을 수 있으며, 경로를 주지 않거나 `-` 를 주면 표준 입력을 읽습니다.
+// 프로그램 출력은 표준 출력, 진단과 오류는 표준 오류로 나가며, 실패하면 종료 코드가 1 입니다.
+
+use std::fs;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use High::api::{self, Global, Options, Severity};
+use High::error_codes;
+use High::messages::{self, Locale};
+use High::test_runner;
+
+const USAGE: &str = "사용법: high-wasi [run|check|fmt|test] [<file.high>|-] [--no-std] [--global <이름>=<값>] [--cfg <플래그>] [--prelude <file>] [--locale ko|en]
+       high-wasi explain [<code>]
+       high-wasi version";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("❌ {}", e);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 오류 문자열이 비어 있으면 이미 진단을 출력했다는 뜻입니다.
+fn run(args: Vec<String>) -> Result<(), String> {
+    let (command, rest) = match args.first().map(String::as_str) {
+        Some(command @ ("run" | "check" | "fmt" | "test" | "explain" | "version")) => (command, &args[1..]),
+        Some("help" | "--help" | "-h") => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        _ => ("run", &args[..]),
+    };
+
+    let mut options = Options::default();
+    let mut locale = Locale::from_env();
+    let mut path = None;
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().cloned().ok_or_else(|| format!("{} 뒤에 값이 필요합니다.\n{}", arg, USAGE));
+        match arg.as_str() {
+            "--no-std" => options.no_std = true,
+            "--global" => {
+                let spec = value()?;
+                let (name, raw) = spec.split_once('=').ok_or_else(|| format!("'<이름>=<값>' 형식이어야 합니다: {}", spec))?;
+                options.globals.push((name.to_string(), parse_global(raw)));
+            }
+            "--cfg" => options.cfg_flags.push(value()?),
+            "--prelude" => options.prelude = Some(read_source(Some(&value()?))?),
+            "--locale" => {
+                let raw = value()?;
+                locale = Some(Locale::parse(&raw).ok_or_else(|| format!("지원하지 않는 로케일: {} (ko, en)", raw))?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("알 수 없는 옵션 '{}'\n{}", flag, USAGE)),
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    messages::set_locale(locale.unwrap_or(Locale::Ko));
+
+    match command {
+        "version" => {
+            println!("high-wasi {} (api {})", env!("CARGO_PKG_VERSION"), api::API_VERSION);
+            Ok(())
+        }
+        "explain" => explain(path.as_deref()),
+        _ => {
+            let name = path.as_deref().filter(|p| *p != "-").unwrap_or("<stdin>").to_string();
+            let source = read_source(path.as_deref())?;
+            match command {
+                "check" => check(&source, &name, &options),
+                "fmt" => match api::format(&source) {
+                    Ok(formatted) => {
+                        print!("{}", formatted);
+                        Ok(())
+                    }
+                    // 구문 오류가 있으면 다시 쓰지 않고 진단만 보여 줍니다.
+                    Err(errors) => {
+                        for diagnostic in &errors {
+                            eprint!("{}", diagnostic.render(&source, &name, false));
+                        }
+                        Err(String::new())
+                    }
+                },
+                "test" => {
+                    let outcomes = test_runner::run_tests(&source)?;
+                    print!("{}", test_runner::render_report(&outcomes));
+                    Ok(())
+                }
+                _ => {
+                    // 실행 전에 검사해서, 오류가 있으면 소스 위치가 붙은 진단으로 보여 줍니다.
+                    check(&source, &name, &options)?;
+                    for line in api::run(&source, &options)? {
+                        println!("{}", line);
+                    }
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+/// 진단을 표준 오류로 출력합니다. 오류가 하나라도 있으면 실패입니다.
+fn check(source: &str, name: &str, options: &Options) -> Result<(), String> {
+    let diagnostics = api::check(source, options);
+    for diagnostic in &diagnostics {
+        eprint!("{}", diagnostic.render(source, name, false));
+    }
+    match diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        true => Err(String::new()),
+        false => Ok(()),
+    }
+}
+
+fn explain(code: Option<&str>) -> Result<(), String> {
+    let Some(code) = code else {
+        for entry in error_codes::ALL {
+            println!("  {}  {}", entry.code, entry.title.get());
+        }
+        return Ok(());
+    };
+    let text = error_codes::explain(code).ok_or_else(|| format!("알 수 없는 진단 코드 '{}'", code))?;
+    print!("{}", text);
+    Ok(())
+}
+
+/// 경로가 없거나 `-` 이면 표준 입력을 끝까지 읽습니다.
+fn read_source(path: Option<&str>) -> Result<String, String> {
+    match path {
+        Some(path) if path != "-" => {
+            fs::read_to_string(path).map_err(|e| format!("Failed to read file '{}': {}", path, e))
+        }
+        _ => {
+            let mut source = String::new();
+            io::stdin().read_to_string(&mut source).map_err(|e| format!("표준 입력 읽기 실패: {}", e))?;
+            Ok(source)
+        }
+    }
+}
+
+/// `--global` 값 문자열을 가장 구체적인 타입으로 해석합니다. (`high` 의 `--global` 과 같은 규칙)
+fn parse_global(raw: &str) -> Global {
+    if let Ok(i) = raw.parse::<i64>() {
+        Global::Integer(i)
+    } else if let Ok(f) = raw.parse::<f64>() {
+        Global::Float(f)
+    } else if let Ok(b) = raw.parse::<bool>() {
+        Global::Boolean(b)
+    } else {
+        Global::String(raw.to_string())
+    }
+}